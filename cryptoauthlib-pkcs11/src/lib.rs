@@ -0,0 +1,609 @@
+// PKCS#11 entry points. Pointers passed by the application are trusted
+// to be valid as the specification requires, hence no per-function safety docs.
+#![allow(clippy::missing_safety_doc)]
+
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate log;
+
+mod token;
+mod types;
+
+use std::env;
+use std::ptr;
+use std::slice;
+use std::sync::Mutex;
+
+use rust_cryptoauthlib::{setup_atecc_device, AtcaIface, AtcaIfaceCfg, AtcaIfaceI2c, AtcaStatus};
+
+use token::{Attribute, Token, MECHANISMS, TOKEN_SLOT_ID};
+pub use types::*;
+
+lazy_static! {
+    static ref TOKEN: Mutex<Option<Token>> = Mutex::new(None);
+}
+
+static FUNCTION_LIST: CK_FUNCTION_LIST = CK_FUNCTION_LIST {
+    version: CK_VERSION {
+        major: 2,
+        minor: 40,
+    },
+    C_Initialize: Some(C_Initialize),
+    C_Finalize: Some(C_Finalize),
+    C_GetInfo: Some(C_GetInfo),
+    C_GetFunctionList: Some(C_GetFunctionList),
+    C_GetSlotList: Some(C_GetSlotList),
+    C_GetSlotInfo: Some(C_GetSlotInfo),
+    C_GetTokenInfo: Some(C_GetTokenInfo),
+    C_GetMechanismList: Some(C_GetMechanismList),
+    C_GetMechanismInfo: Some(C_GetMechanismInfo),
+    C_InitToken: Some(not_supported),
+    C_InitPIN: Some(not_supported),
+    C_SetPIN: Some(not_supported),
+    C_OpenSession: Some(C_OpenSession),
+    C_CloseSession: Some(C_CloseSession),
+    C_CloseAllSessions: Some(C_CloseAllSessions),
+    C_GetSessionInfo: Some(C_GetSessionInfo),
+    C_GetOperationState: Some(not_supported),
+    C_SetOperationState: Some(not_supported),
+    C_Login: Some(C_Login),
+    C_Logout: Some(C_Logout),
+    C_CreateObject: Some(not_supported),
+    C_CopyObject: Some(not_supported),
+    C_DestroyObject: Some(not_supported),
+    C_GetObjectSize: Some(not_supported),
+    C_GetAttributeValue: Some(C_GetAttributeValue),
+    C_SetAttributeValue: Some(not_supported),
+    C_FindObjectsInit: Some(C_FindObjectsInit),
+    C_FindObjects: Some(C_FindObjects),
+    C_FindObjectsFinal: Some(C_FindObjectsFinal),
+    C_EncryptInit: Some(not_supported),
+    C_Encrypt: Some(not_supported),
+    C_EncryptUpdate: Some(not_supported),
+    C_EncryptFinal: Some(not_supported),
+    C_DecryptInit: Some(not_supported),
+    C_Decrypt: Some(not_supported),
+    C_DecryptUpdate: Some(not_supported),
+    C_DecryptFinal: Some(not_supported),
+    C_DigestInit: Some(not_supported),
+    C_Digest: Some(not_supported),
+    C_DigestUpdate: Some(not_supported),
+    C_DigestKey: Some(not_supported),
+    C_DigestFinal: Some(not_supported),
+    C_SignInit: Some(C_SignInit),
+    C_Sign: Some(C_Sign),
+    C_SignUpdate: Some(not_supported),
+    C_SignFinal: Some(not_supported),
+    C_SignRecoverInit: Some(not_supported),
+    C_SignRecover: Some(not_supported),
+    C_VerifyInit: Some(not_supported),
+    C_Verify: Some(not_supported),
+    C_VerifyUpdate: Some(not_supported),
+    C_VerifyFinal: Some(not_supported),
+    C_VerifyRecoverInit: Some(not_supported),
+    C_VerifyRecover: Some(not_supported),
+    C_DigestEncryptUpdate: Some(not_supported),
+    C_DecryptDigestUpdate: Some(not_supported),
+    C_SignEncryptUpdate: Some(not_supported),
+    C_DecryptVerifyUpdate: Some(not_supported),
+    C_GenerateKey: Some(not_supported),
+    C_GenerateKeyPair: Some(C_GenerateKeyPair),
+    C_WrapKey: Some(not_supported),
+    C_UnwrapKey: Some(not_supported),
+    C_DeriveKey: Some(not_supported),
+    C_SeedRandom: Some(C_SeedRandom),
+    C_GenerateRandom: Some(C_GenerateRandom),
+    C_GetFunctionStatus: Some(not_supported),
+    C_CancelFunction: Some(not_supported),
+    C_WaitForSlotEvent: Some(not_supported),
+};
+
+unsafe extern "C" fn not_supported() -> CK_RV {
+    CKR_FUNCTION_NOT_SUPPORTED
+}
+
+/// Runs a function on the initialized token and converts its result to a return value
+fn with_token<F>(f: F) -> CK_RV
+where
+    F: FnOnce(&mut Token) -> Result<(), CK_RV>,
+{
+    let mut token = match TOKEN.lock() {
+        Ok(token) => token,
+        Err(_) => return CKR_GENERAL_ERROR,
+    };
+    match token.as_mut() {
+        Some(token) => match f(token) {
+            Ok(()) => CKR_OK,
+            Err(rv) => rv,
+        },
+        None => CKR_CRYPTOKI_NOT_INITIALIZED,
+    }
+}
+
+fn check_slot(slot_id: CK_SLOT_ID) -> Result<(), CK_RV> {
+    match slot_id {
+        TOKEN_SLOT_ID => Ok(()),
+        _ => Err(CKR_SLOT_ID_INVALID),
+    }
+}
+
+unsafe fn template(
+    attributes: *const CK_ATTRIBUTE,
+    count: CK_ULONG,
+) -> Result<Vec<Attribute>, CK_RV> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    if attributes.is_null() {
+        return Err(CKR_ARGUMENTS_BAD);
+    }
+    let mut template = Vec::new();
+    for attribute in slice::from_raw_parts(attributes, count as usize) {
+        let value = match attribute.ulValueLen {
+            0 => Vec::new(),
+            _ if attribute.pValue.is_null() => return Err(CKR_ARGUMENTS_BAD),
+            len => slice::from_raw_parts(attribute.pValue as *const u8, len as usize).to_vec(),
+        };
+        template.push((attribute.type_, value));
+    }
+    Ok(template)
+}
+
+/// Reads a numeric environment variable, decimal or hexadecimal with the '0x' prefix
+fn env_u8(name: &str, default: u8) -> u8 {
+    let value = match env::var(name) {
+        Ok(value) => value,
+        Err(_) => return default,
+    };
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => value.parse::<u8>(),
+    };
+    parsed.unwrap_or_else(|_| {
+        warn!("Invalid value of {}: {}, using {}", name, value, default);
+        default
+    })
+}
+
+fn iface_cfg_from_env() -> AtcaIfaceCfg {
+    let devtype = env::var("ATECC_PKCS11_DEVICE_TYPE").unwrap_or_else(|_| "atecc608a".to_owned());
+    AtcaIfaceCfg::default()
+        .set_iface_type("i2c".to_owned())
+        .set_devtype(devtype)
+        .set_wake_delay(1600)
+        .set_rx_retries(20)
+        .set_iface(
+            AtcaIface::default().set_atcai2c(
+                AtcaIfaceI2c::default()
+                    .set_slave_address(env_u8("ATECC_PKCS11_I2C_ADDRESS", 0xC0))
+                    .set_bus(env_u8("ATECC_PKCS11_I2C_BUS", 1))
+                    .set_baud(400000),
+            ),
+        )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_Initialize(_init_args: CK_VOID_PTR) -> CK_RV {
+    let mut token = match TOKEN.lock() {
+        Ok(token) => token,
+        Err(_) => return CKR_GENERAL_ERROR,
+    };
+    if token.is_some() {
+        return CKR_CRYPTOKI_ALREADY_INITIALIZED;
+    }
+    let device = match setup_atecc_device(iface_cfg_from_env()) {
+        Ok(device) => device,
+        Err(err) => {
+            error!("ATECC device setup failed: {}", err);
+            return CKR_DEVICE_ERROR;
+        }
+    };
+    match Token::new(device) {
+        Ok(new_token) => {
+            *token = Some(new_token);
+            CKR_OK
+        }
+        Err(err) => {
+            error!("Reading ATECC configuration failed: {}", err);
+            CKR_DEVICE_ERROR
+        }
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_Finalize(reserved: CK_VOID_PTR) -> CK_RV {
+    if !reserved.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    let mut token = match TOKEN.lock() {
+        Ok(token) => token,
+        Err(_) => return CKR_GENERAL_ERROR,
+    };
+    match token.take() {
+        Some(token) => match token.release() {
+            AtcaStatus::AtcaSuccess => CKR_OK,
+            _ => CKR_DEVICE_ERROR,
+        },
+        None => CKR_CRYPTOKI_NOT_INITIALIZED,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetInfo(info: *mut CK_INFO) -> CK_RV {
+    if info.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|_| {
+        let info = &mut *info;
+        info.cryptokiVersion = FUNCTION_LIST.version;
+        token::pad(&mut info.manufacturerID, "rust-cryptoauthlib");
+        info.flags = 0;
+        token::pad(&mut info.libraryDescription, "ATECC PKCS#11 module");
+        info.libraryVersion = CK_VERSION { major: 0, minor: 1 };
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetFunctionList(function_list: *mut *const CK_FUNCTION_LIST) -> CK_RV {
+    if function_list.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    *function_list = &FUNCTION_LIST;
+    CKR_OK
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetSlotList(
+    _token_present: CK_BBOOL,
+    slot_list: *mut CK_SLOT_ID,
+    count: *mut CK_ULONG,
+) -> CK_RV {
+    if count.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|_| {
+        if !slot_list.is_null() {
+            if *count < 1 {
+                *count = 1;
+                return Err(CKR_BUFFER_TOO_SMALL);
+            }
+            *slot_list = TOKEN_SLOT_ID;
+        }
+        *count = 1;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetSlotInfo(slot_id: CK_SLOT_ID, info: *mut CK_SLOT_INFO) -> CK_RV {
+    if info.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|_| {
+        check_slot(slot_id)?;
+        let info = &mut *info;
+        token::pad(&mut info.slotDescription, "ATECC");
+        token::pad(&mut info.manufacturerID, "Microchip Technology Inc");
+        info.flags = CKF_TOKEN_PRESENT | CKF_HW_SLOT;
+        info.hardwareVersion = CK_VERSION::default();
+        info.firmwareVersion = CK_VERSION::default();
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetTokenInfo(slot_id: CK_SLOT_ID, info: *mut CK_TOKEN_INFO) -> CK_RV {
+    if info.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        check_slot(slot_id)?;
+        token.token_info(&mut *info);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetMechanismList(
+    slot_id: CK_SLOT_ID,
+    mechanism_list: *mut CK_MECHANISM_TYPE,
+    count: *mut CK_ULONG,
+) -> CK_RV {
+    if count.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|_| {
+        check_slot(slot_id)?;
+        if !mechanism_list.is_null() {
+            if (*count as usize) < MECHANISMS.len() {
+                *count = MECHANISMS.len() as CK_ULONG;
+                return Err(CKR_BUFFER_TOO_SMALL);
+            }
+            ptr::copy_nonoverlapping(MECHANISMS.as_ptr(), mechanism_list, MECHANISMS.len());
+        }
+        *count = MECHANISMS.len() as CK_ULONG;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetMechanismInfo(
+    slot_id: CK_SLOT_ID,
+    mechanism: CK_MECHANISM_TYPE,
+    info: *mut CK_MECHANISM_INFO,
+) -> CK_RV {
+    if info.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|_| {
+        check_slot(slot_id)?;
+        let flags = match mechanism {
+            CKM_ECDSA | CKM_ECDSA_SHA256 => CKF_SIGN,
+            CKM_EC_KEY_PAIR_GEN => CKF_GENERATE_KEY_PAIR,
+            _ => return Err(CKR_MECHANISM_INVALID),
+        };
+        let info = &mut *info;
+        info.ulMinKeySize = 256;
+        info.ulMaxKeySize = 256;
+        info.flags = CKF_HW | flags | CKF_EC_F_P | CKF_EC_NAMEDCURVE | CKF_EC_UNCOMPRESS;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_OpenSession(
+    slot_id: CK_SLOT_ID,
+    flags: CK_FLAGS,
+    _application: CK_VOID_PTR,
+    _notify: CK_NOTIFY,
+    session: *mut CK_SESSION_HANDLE,
+) -> CK_RV {
+    if session.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        check_slot(slot_id)?;
+        if flags & CKF_SERIAL_SESSION == 0 {
+            return Err(CKR_SESSION_PARALLEL_NOT_SUPPORTED);
+        }
+        *session = token.open_session(flags);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_CloseSession(session: CK_SESSION_HANDLE) -> CK_RV {
+    with_token(|token| token.close_session(session))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_CloseAllSessions(slot_id: CK_SLOT_ID) -> CK_RV {
+    with_token(|token| {
+        check_slot(slot_id)?;
+        token.close_all_sessions();
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetSessionInfo(
+    session: CK_SESSION_HANDLE,
+    info: *mut CK_SESSION_INFO,
+) -> CK_RV {
+    if info.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        let flags = token.session_flags(session)?;
+        let info = &mut *info;
+        info.slotID = TOKEN_SLOT_ID;
+        info.state = match flags & CKF_RW_SESSION {
+            0 => CKS_RO_PUBLIC_SESSION,
+            _ => CKS_RW_PUBLIC_SESSION,
+        };
+        info.flags = flags;
+        info.ulDeviceError = 0;
+        Ok(())
+    })
+}
+
+/// The token has no PIN, logging in only checks the session handle
+#[no_mangle]
+pub unsafe extern "C" fn C_Login(
+    session: CK_SESSION_HANDLE,
+    _user_type: CK_USER_TYPE,
+    _pin: *mut CK_BYTE,
+    _pin_len: CK_ULONG,
+) -> CK_RV {
+    with_token(|token| token.session_flags(session).map(|_| ()))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_Logout(session: CK_SESSION_HANDLE) -> CK_RV {
+    with_token(|token| token.session_flags(session).map(|_| ()))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GetAttributeValue(
+    session: CK_SESSION_HANDLE,
+    object: CK_OBJECT_HANDLE,
+    attributes: *mut CK_ATTRIBUTE,
+    count: CK_ULONG,
+) -> CK_RV {
+    if attributes.is_null() && count != 0 {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        token.session_flags(session)?;
+        let mut result = Ok(());
+        for attribute in slice::from_raw_parts_mut(attributes, count as usize) {
+            let value = match token.attribute(object, attribute.type_) {
+                Ok(value) => value,
+                Err(CKR_ATTRIBUTE_TYPE_INVALID) => {
+                    attribute.ulValueLen = CK_UNAVAILABLE_INFORMATION;
+                    result = Err(CKR_ATTRIBUTE_TYPE_INVALID);
+                    continue;
+                }
+                Err(rv) => return Err(rv),
+            };
+            if !attribute.pValue.is_null() {
+                if (attribute.ulValueLen as usize) < value.len() {
+                    attribute.ulValueLen = CK_UNAVAILABLE_INFORMATION;
+                    result = Err(CKR_BUFFER_TOO_SMALL);
+                    continue;
+                }
+                ptr::copy_nonoverlapping(value.as_ptr(), attribute.pValue as *mut u8, value.len());
+            }
+            attribute.ulValueLen = value.len() as CK_ULONG;
+        }
+        result
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_FindObjectsInit(
+    session: CK_SESSION_HANDLE,
+    attributes: *mut CK_ATTRIBUTE,
+    count: CK_ULONG,
+) -> CK_RV {
+    let template = match template(attributes, count) {
+        Ok(template) => template,
+        Err(rv) => return rv,
+    };
+    with_token(|token| token.find_init(session, &template))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_FindObjects(
+    session: CK_SESSION_HANDLE,
+    objects: *mut CK_OBJECT_HANDLE,
+    max_count: CK_ULONG,
+    count: *mut CK_ULONG,
+) -> CK_RV {
+    if objects.is_null() || count.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        let found = token.find(session, max_count as usize)?;
+        ptr::copy_nonoverlapping(found.as_ptr(), objects, found.len());
+        *count = found.len() as CK_ULONG;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_FindObjectsFinal(session: CK_SESSION_HANDLE) -> CK_RV {
+    with_token(|token| token.find_final(session))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_SignInit(
+    session: CK_SESSION_HANDLE,
+    mechanism: *mut CK_MECHANISM,
+    key: CK_OBJECT_HANDLE,
+) -> CK_RV {
+    if mechanism.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| token.sign_init(session, (*mechanism).mechanism, key))
+}
+
+/// A NULL signature buffer only queries the signature length,
+/// the signing operation stays active in that case and when the buffer is too small
+#[no_mangle]
+pub unsafe extern "C" fn C_Sign(
+    session: CK_SESSION_HANDLE,
+    data: *mut CK_BYTE,
+    data_len: CK_ULONG,
+    signature: *mut CK_BYTE,
+    signature_len: *mut CK_ULONG,
+) -> CK_RV {
+    if signature_len.is_null() || (data.is_null() && data_len != 0) {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        let len = token.sign_len(session)?;
+        if signature.is_null() {
+            *signature_len = len as CK_ULONG;
+            return Ok(());
+        }
+        if (*signature_len as usize) < len {
+            *signature_len = len as CK_ULONG;
+            return Err(CKR_BUFFER_TOO_SMALL);
+        }
+        let data: &[u8] = match data_len {
+            0 => &[],
+            _ => slice::from_raw_parts(data, data_len as usize),
+        };
+        let result = token.sign(session, data)?;
+        ptr::copy_nonoverlapping(result.as_ptr(), signature, result.len());
+        *signature_len = result.len() as CK_ULONG;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn C_GenerateKeyPair(
+    session: CK_SESSION_HANDLE,
+    mechanism: *mut CK_MECHANISM,
+    public_attributes: *mut CK_ATTRIBUTE,
+    public_count: CK_ULONG,
+    private_attributes: *mut CK_ATTRIBUTE,
+    private_count: CK_ULONG,
+    public_key: *mut CK_OBJECT_HANDLE,
+    private_key: *mut CK_OBJECT_HANDLE,
+) -> CK_RV {
+    if mechanism.is_null() || public_key.is_null() || private_key.is_null() {
+        return CKR_ARGUMENTS_BAD;
+    }
+    let public_template = match template(public_attributes, public_count) {
+        Ok(template) => template,
+        Err(rv) => return rv,
+    };
+    let private_template = match template(private_attributes, private_count) {
+        Ok(template) => template,
+        Err(rv) => return rv,
+    };
+    with_token(|token| {
+        let (public_handle, private_handle) = token.generate_key_pair(
+            session,
+            (*mechanism).mechanism,
+            &public_template,
+            &private_template,
+        )?;
+        *public_key = public_handle;
+        *private_key = private_handle;
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_SeedRandom(
+    session: CK_SESSION_HANDLE,
+    _seed: *mut CK_BYTE,
+    _seed_len: CK_ULONG,
+) -> CK_RV {
+    with_token(|token| {
+        token.session_flags(session)?;
+        Err(CKR_RANDOM_SEED_NOT_SUPPORTED)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn C_GenerateRandom(
+    session: CK_SESSION_HANDLE,
+    random: *mut CK_BYTE,
+    random_len: CK_ULONG,
+) -> CK_RV {
+    if random.is_null() && random_len != 0 {
+        return CKR_ARGUMENTS_BAD;
+    }
+    with_token(|token| {
+        let bytes = token.random(session, random_len as usize)?;
+        ptr::copy_nonoverlapping(bytes.as_ptr(), random, bytes.len());
+        Ok(())
+    })
+}
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+
+use rust_cryptoauthlib::{
+    AtcaSlot, AtcaStatus, AteccDevice, KeyType, SignMode, ATCA_ATECC_PUB_KEY_SIZE,
+    ATCA_RANDOM_BUFFER_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
+
+use crate::types::*;
+
+/// The only slot (in the PKCS#11 sense) of the module, it holds the chip token
+pub(crate) const TOKEN_SLOT_ID: CK_SLOT_ID = 0;
+
+/// DER encoded OID of the secp256r1 curve (CKA_EC_PARAMS)
+const P256_EC_PARAMS: [u8; 10] = [0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+
+/// Mechanisms supported by the token
+pub(crate) const MECHANISMS: [CK_MECHANISM_TYPE; 3] =
+    [CKM_ECDSA, CKM_ECDSA_SHA256, CKM_EC_KEY_PAIR_GEN];
+
+/// Attribute of a template, type and raw value as passed by the application
+pub(crate) type Attribute = (CK_ATTRIBUTE_TYPE, Vec<u8>);
+
+#[derive(Default)]
+struct Session {
+    flags: CK_FLAGS,
+    find: Option<Vec<CK_OBJECT_HANDLE>>,
+    sign: Option<(CK_MECHANISM_TYPE, u8)>,
+}
+
+/// State of the module: the chip with its slot configuration and the open sessions.
+/// The object handle is composed of the object class and the ATECC slot number,
+/// so objects need no bookkeeping and handles stay valid across sessions.
+pub(crate) struct Token {
+    device: AteccDevice,
+    slots: Vec<AtcaSlot>,
+    sessions: HashMap<CK_SESSION_HANDLE, Session>,
+    next_session: CK_SESSION_HANDLE,
+}
+
+impl Token {
+    pub(crate) fn new(device: AteccDevice) -> Result<Token, AtcaStatus> {
+        let mut slots = Vec::new();
+        match device.get_config(&mut slots) {
+            AtcaStatus::AtcaSuccess => Ok(Token {
+                device,
+                slots,
+                sessions: HashMap::new(),
+                next_session: 1,
+            }),
+            err => Err(err),
+        }
+    }
+
+    /// Releases the chip, the token cannot be used afterwards
+    pub(crate) fn release(&self) -> AtcaStatus {
+        self.device.release()
+    }
+
+    pub(crate) fn token_info(&self, info: &mut CK_TOKEN_INFO) {
+        let serial_number: String = self.device.get_serial_number()[..8]
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+
+        pad(&mut info.label, "ATECC");
+        pad(&mut info.manufacturerID, "Microchip Technology Inc");
+        pad(&mut info.model, &self.device.get_device_type().to_string());
+        pad(&mut info.serialNumber, &serial_number);
+        info.flags = CKF_RNG | CKF_TOKEN_INITIALIZED;
+        info.ulMaxSessionCount = 0;
+        info.ulSessionCount = self.sessions.len() as CK_ULONG;
+        info.ulMaxRwSessionCount = 0;
+        info.ulRwSessionCount = self
+            .sessions
+            .values()
+            .filter(|session| session.flags & CKF_RW_SESSION != 0)
+            .count() as CK_ULONG;
+        info.ulMaxPinLen = 0;
+        info.ulMinPinLen = 0;
+        info.ulTotalPublicMemory = CK_UNAVAILABLE_INFORMATION;
+        info.ulFreePublicMemory = CK_UNAVAILABLE_INFORMATION;
+        info.ulTotalPrivateMemory = CK_UNAVAILABLE_INFORMATION;
+        info.ulFreePrivateMemory = CK_UNAVAILABLE_INFORMATION;
+        info.hardwareVersion = CK_VERSION::default();
+        info.firmwareVersion = CK_VERSION::default();
+        pad(&mut info.utcTime, "");
+    }
+
+    pub(crate) fn open_session(&mut self, flags: CK_FLAGS) -> CK_SESSION_HANDLE {
+        let handle = self.next_session;
+        self.next_session += 1;
+        self.sessions.insert(
+            handle,
+            Session {
+                flags,
+                ..Default::default()
+            },
+        );
+        handle
+    }
+
+    pub(crate) fn close_session(&mut self, handle: CK_SESSION_HANDLE) -> Result<(), CK_RV> {
+        match self.sessions.remove(&handle) {
+            Some(_) => Ok(()),
+            None => Err(CKR_SESSION_HANDLE_INVALID),
+        }
+    }
+
+    pub(crate) fn close_all_sessions(&mut self) {
+        self.sessions.clear();
+    }
+
+    pub(crate) fn session_flags(&self, handle: CK_SESSION_HANDLE) -> Result<CK_FLAGS, CK_RV> {
+        self.session(handle).map(|session| session.flags)
+    }
+
+    /// Returns the value of an object attribute encoded as PKCS#11 expects it
+    pub(crate) fn attribute(
+        &self,
+        object: CK_OBJECT_HANDLE,
+        attribute: CK_ATTRIBUTE_TYPE,
+    ) -> Result<Vec<u8>, CK_RV> {
+        let (class, slot_id) = self.object(object)?;
+        let is_private = class == CKO_PRIVATE_KEY;
+
+        match attribute {
+            CKA_CLASS => Ok(ulong(class)),
+            CKA_KEY_TYPE => Ok(ulong(CKK_EC)),
+            CKA_ID => Ok(vec![slot_id]),
+            CKA_LABEL => Ok(format!("slot {}", slot_id).into_bytes()),
+            CKA_TOKEN | CKA_LOCAL => Ok(bool(true)),
+            CKA_PRIVATE | CKA_MODIFIABLE => Ok(bool(false)),
+            CKA_EC_PARAMS => Ok(P256_EC_PARAMS.to_vec()),
+            CKA_SIGN if is_private => Ok(bool(true)),
+            CKA_SENSITIVE | CKA_ALWAYS_SENSITIVE | CKA_NEVER_EXTRACTABLE if is_private => {
+                Ok(bool(true))
+            }
+            CKA_EXTRACTABLE if is_private => Ok(bool(false)),
+            CKA_VERIFY if !is_private => Ok(bool(true)),
+            CKA_EC_POINT if !is_private => {
+                let mut public_key = Vec::with_capacity(ATCA_ATECC_PUB_KEY_SIZE);
+                match self.device.get_public_key(slot_id, &mut public_key) {
+                    AtcaStatus::AtcaSuccess => (),
+                    _ => return Err(CKR_DEVICE_ERROR),
+                }
+                // DER OCTET STRING holding the uncompressed point
+                let mut ec_point = vec![0x04, (public_key.len() + 1) as u8, 0x04];
+                ec_point.extend_from_slice(&public_key);
+                Ok(ec_point)
+            }
+            _ => Err(CKR_ATTRIBUTE_TYPE_INVALID),
+        }
+    }
+
+    pub(crate) fn find_init(
+        &mut self,
+        handle: CK_SESSION_HANDLE,
+        template: &[Attribute],
+    ) -> Result<(), CK_RV> {
+        if self.session(handle)?.find.is_some() {
+            return Err(CKR_OPERATION_ACTIVE);
+        }
+        let mut found = Vec::new();
+        for object in self.objects() {
+            let mut matches = true;
+            for (attribute, value) in template {
+                match self.attribute(object, *attribute) {
+                    Ok(ref object_value) if object_value == value => (),
+                    Err(CKR_DEVICE_ERROR) => return Err(CKR_DEVICE_ERROR),
+                    _ => {
+                        matches = false;
+                        break;
+                    }
+                }
+            }
+            if matches {
+                found.push(object);
+            }
+        }
+        // Handles are returned from the end of the list
+        found.reverse();
+        self.session_mut(handle)?.find = Some(found);
+        Ok(())
+    }
+
+    pub(crate) fn find(
+        &mut self,
+        handle: CK_SESSION_HANDLE,
+        max_count: usize,
+    ) -> Result<Vec<CK_OBJECT_HANDLE>, CK_RV> {
+        let found = match self.session_mut(handle)?.find.as_mut() {
+            Some(found) => found,
+            None => return Err(CKR_OPERATION_NOT_INITIALIZED),
+        };
+        let mut objects = Vec::new();
+        while objects.len() < max_count {
+            match found.pop() {
+                Some(object) => objects.push(object),
+                None => break,
+            }
+        }
+        Ok(objects)
+    }
+
+    pub(crate) fn find_final(&mut self, handle: CK_SESSION_HANDLE) -> Result<(), CK_RV> {
+        match self.session_mut(handle)?.find.take() {
+            Some(_) => Ok(()),
+            None => Err(CKR_OPERATION_NOT_INITIALIZED),
+        }
+    }
+
+    pub(crate) fn sign_init(
+        &mut self,
+        handle: CK_SESSION_HANDLE,
+        mechanism: CK_MECHANISM_TYPE,
+        key: CK_OBJECT_HANDLE,
+    ) -> Result<(), CK_RV> {
+        if self.session(handle)?.sign.is_some() {
+            return Err(CKR_OPERATION_ACTIVE);
+        }
+        if mechanism != CKM_ECDSA && mechanism != CKM_ECDSA_SHA256 {
+            return Err(CKR_MECHANISM_INVALID);
+        }
+        let slot_id = match self.object(key) {
+            Ok((CKO_PRIVATE_KEY, slot_id)) => slot_id,
+            _ => return Err(CKR_KEY_HANDLE_INVALID),
+        };
+        self.session_mut(handle)?.sign = Some((mechanism, slot_id));
+        Ok(())
+    }
+
+    /// Length of the signature produced by the active signing operation
+    pub(crate) fn sign_len(&self, handle: CK_SESSION_HANDLE) -> Result<usize, CK_RV> {
+        match self.session(handle)?.sign {
+            Some(_) => Ok(ATCA_SIG_SIZE),
+            None => Err(CKR_OPERATION_NOT_INITIALIZED),
+        }
+    }
+
+    /// Signs the data and finishes the signing operation.
+    /// For CKM_ECDSA the data is a SHA256 digest, the signature is R || S.
+    pub(crate) fn sign(
+        &mut self,
+        handle: CK_SESSION_HANDLE,
+        data: &[u8],
+    ) -> Result<Vec<u8>, CK_RV> {
+        let (mechanism, slot_id) = match self.session_mut(handle)?.sign.take() {
+            Some(operation) => operation,
+            None => return Err(CKR_OPERATION_NOT_INITIALIZED),
+        };
+        if mechanism == CKM_ECDSA_SHA256 {
+            return self
+                .device
+                .sign_message(slot_id, data)
+                .map_err(|_| CKR_DEVICE_ERROR);
+        }
+        if data.len() != ATCA_SHA2_256_DIGEST_SIZE {
+            return Err(CKR_DATA_LEN_RANGE);
+        }
+        let mut signature = Vec::with_capacity(ATCA_SIG_SIZE);
+        match self
+            .device
+            .sign_hash(SignMode::External(data.to_vec()), slot_id, &mut signature)
+        {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            _ => Err(CKR_DEVICE_ERROR),
+        }
+    }
+
+    /// Generates a P256 key pair in the ATECC slot given by CKA_ID
+    /// of the private key template (or of the public key template).
+    /// Returns handles of the public and the private key.
+    pub(crate) fn generate_key_pair(
+        &mut self,
+        handle: CK_SESSION_HANDLE,
+        mechanism: CK_MECHANISM_TYPE,
+        public_template: &[Attribute],
+        private_template: &[Attribute],
+    ) -> Result<(CK_OBJECT_HANDLE, CK_OBJECT_HANDLE), CK_RV> {
+        self.session(handle)?;
+        if mechanism != CKM_EC_KEY_PAIR_GEN {
+            return Err(CKR_MECHANISM_INVALID);
+        }
+        for (attribute, value) in public_template {
+            if *attribute == CKA_EC_PARAMS && value[..] != P256_EC_PARAMS[..] {
+                return Err(CKR_ATTRIBUTE_VALUE_INVALID);
+            }
+        }
+        let id = private_template
+            .iter()
+            .chain(public_template.iter())
+            .find(|(attribute, _)| *attribute == CKA_ID)
+            .map(|(_, value)| value);
+        let slot_id = match id {
+            Some(value) if value.len() == 1 => value[0],
+            Some(_) => return Err(CKR_ATTRIBUTE_VALUE_INVALID),
+            None => return Err(CKR_TEMPLATE_INCOMPLETE),
+        };
+        let private_key = object_handle(CKO_PRIVATE_KEY, slot_id);
+        if self.object(private_key).is_err() {
+            return Err(CKR_TEMPLATE_INCONSISTENT);
+        }
+        match self.device.gen_key(KeyType::P256EccKey, slot_id) {
+            AtcaStatus::AtcaSuccess => Ok((object_handle(CKO_PUBLIC_KEY, slot_id), private_key)),
+            _ => Err(CKR_DEVICE_ERROR),
+        }
+    }
+
+    pub(crate) fn random(&self, handle: CK_SESSION_HANDLE, len: usize) -> Result<Vec<u8>, CK_RV> {
+        self.session(handle)?;
+        let mut random = Vec::with_capacity(len + ATCA_RANDOM_BUFFER_SIZE);
+        let mut block = Vec::with_capacity(ATCA_RANDOM_BUFFER_SIZE);
+        while random.len() < len {
+            match self.device.random(&mut block) {
+                AtcaStatus::AtcaSuccess => random.extend_from_slice(&block),
+                _ => return Err(CKR_DEVICE_ERROR),
+            }
+        }
+        random.truncate(len);
+        Ok(random)
+    }
+
+    fn session(&self, handle: CK_SESSION_HANDLE) -> Result<&Session, CK_RV> {
+        self.sessions.get(&handle).ok_or(CKR_SESSION_HANDLE_INVALID)
+    }
+
+    fn session_mut(&mut self, handle: CK_SESSION_HANDLE) -> Result<&mut Session, CK_RV> {
+        self.sessions
+            .get_mut(&handle)
+            .ok_or(CKR_SESSION_HANDLE_INVALID)
+    }
+
+    /// Every slot configured for a P256 key holds a public key object,
+    /// slots holding a private key hold a private key object as well
+    fn objects(&self) -> Vec<CK_OBJECT_HANDLE> {
+        let mut objects = Vec::new();
+        for slot in self
+            .slots
+            .iter()
+            .filter(|slot| slot.config.key_type == KeyType::P256EccKey)
+        {
+            objects.push(object_handle(CKO_PUBLIC_KEY, slot.id));
+            if slot.config.ecc_key_attr.is_private {
+                objects.push(object_handle(CKO_PRIVATE_KEY, slot.id));
+            }
+        }
+        objects
+    }
+
+    /// Splits an object handle into the object class and the ATECC slot number
+    fn object(&self, object: CK_OBJECT_HANDLE) -> Result<(CK_OBJECT_CLASS, u8), CK_RV> {
+        match self.objects().contains(&object) {
+            true => Ok((object >> 8, (object & 0xFF) as u8)),
+            false => Err(CKR_OBJECT_HANDLE_INVALID),
+        }
+    }
+}
+
+fn object_handle(class: CK_OBJECT_CLASS, slot_id: u8) -> CK_OBJECT_HANDLE {
+    (class << 8) | slot_id as CK_OBJECT_HANDLE
+}
+
+fn ulong(value: CK_ULONG) -> Vec<u8> {
+    value.to_ne_bytes().to_vec()
+}
+
+fn bool(value: bool) -> Vec<u8> {
+    match value {
+        true => vec![CK_TRUE],
+        false => vec![CK_FALSE],
+    }
+}
+
+/// Copies a string into a blank padded PKCS#11 character field
+pub(crate) fn pad(field: &mut [u8], value: &str) {
+    for (i, byte) in field.iter_mut().enumerate() {
+        *byte = *value.as_bytes().get(i).unwrap_or(&b' ');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_cryptoauthlib::{setup_atecc_device, AtcaIfaceCfg};
+
+    fn test_token() -> Token {
+        let iface_cfg = AtcaIfaceCfg::default()
+            .set_iface_type("test-interface".to_owned())
+            .set_devtype("always-success".to_owned());
+        Token::new(setup_atecc_device(iface_cfg).unwrap()).unwrap()
+    }
+
+    fn test_slot(id: u8, key_type: KeyType, is_private: bool) -> AtcaSlot {
+        let mut slot = AtcaSlot {
+            id,
+            ..Default::default()
+        };
+        slot.config.key_type = key_type;
+        slot.config.ecc_key_attr.is_private = is_private;
+        slot
+    }
+
+    #[test]
+    fn objects_and_attributes() {
+        let mut token = test_token();
+        token.slots = vec![
+            test_slot(0, KeyType::P256EccKey, true),
+            test_slot(9, KeyType::Aes, false),
+            test_slot(11, KeyType::P256EccKey, false),
+        ];
+        let session = token.open_session(CKF_SERIAL_SESSION);
+
+        token
+            .find_init(session, &[(CKA_CLASS, ulong(CKO_PRIVATE_KEY))])
+            .unwrap();
+        assert_eq!(token.find(session, 10), Ok(vec![0x300]));
+        assert_eq!(token.find(session, 10), Ok(vec![]));
+        token.find_final(session).unwrap();
+
+        token.find_init(session, &[(CKA_ID, vec![11])]).unwrap();
+        assert_eq!(token.find(session, 10), Ok(vec![0x20B]));
+        token.find_final(session).unwrap();
+        assert_eq!(token.find(session, 1), Err(CKR_OPERATION_NOT_INITIALIZED));
+
+        assert_eq!(token.attribute(0x300, CKA_ID), Ok(vec![0]));
+        assert_eq!(token.attribute(0x300, CKA_SIGN), Ok(bool(true)));
+        assert_eq!(
+            token.attribute(0x300, CKA_EC_POINT),
+            Err(CKR_ATTRIBUTE_TYPE_INVALID)
+        );
+        assert_eq!(
+            token.attribute(0x20B, CKA_EC_PARAMS),
+            Ok(P256_EC_PARAMS.to_vec())
+        );
+        assert_eq!(
+            token.attribute(0x309, CKA_ID),
+            Err(CKR_OBJECT_HANDLE_INVALID)
+        );
+    }
+
+    #[test]
+    fn sign_and_generate() {
+        let mut token = test_token();
+        token.slots = vec![test_slot(2, KeyType::P256EccKey, true)];
+        let session = token.open_session(CKF_SERIAL_SESSION);
+
+        assert_eq!(token.sign_len(session), Err(CKR_OPERATION_NOT_INITIALIZED));
+        assert_eq!(
+            token.sign_init(session, CKM_ECDSA, 0x202),
+            Err(CKR_KEY_HANDLE_INVALID)
+        );
+        token.sign_init(session, CKM_ECDSA_SHA256, 0x302).unwrap();
+        assert_eq!(
+            token.sign_init(session, CKM_ECDSA, 0x302),
+            Err(CKR_OPERATION_ACTIVE)
+        );
+        assert_eq!(token.sign_len(session), Ok(ATCA_SIG_SIZE));
+        assert_eq!(
+            token.sign(session, b"message").map(|sig| sig.len()),
+            Ok(ATCA_SIG_SIZE)
+        );
+        assert_eq!(
+            token.sign(session, b"message"),
+            Err(CKR_OPERATION_NOT_INITIALIZED)
+        );
+
+        token.sign_init(session, CKM_ECDSA, 0x302).unwrap();
+        assert_eq!(token.sign(session, &[0; 20]), Err(CKR_DATA_LEN_RANGE));
+
+        assert_eq!(
+            token.generate_key_pair(session, CKM_EC_KEY_PAIR_GEN, &[], &[]),
+            Err(CKR_TEMPLATE_INCOMPLETE)
+        );
+        assert_eq!(
+            token.generate_key_pair(session, CKM_EC_KEY_PAIR_GEN, &[], &[(CKA_ID, vec![3])]),
+            Err(CKR_TEMPLATE_INCONSISTENT)
+        );
+        assert_eq!(
+            token.generate_key_pair(
+                session,
+                CKM_EC_KEY_PAIR_GEN,
+                &[(
+                    CKA_EC_PARAMS,
+                    vec![0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x22]
+                )],
+                &[(CKA_ID, vec![2])]
+            ),
+            Err(CKR_ATTRIBUTE_VALUE_INVALID)
+        );
+    }
+
+    #[test]
+    fn random_and_sessions() {
+        let mut token = test_token();
+        let session = token.open_session(CKF_SERIAL_SESSION);
+        assert_eq!(token.random(session, 45).map(|random| random.len()), Ok(45));
+        token.close_session(session).unwrap();
+        assert_eq!(token.random(session, 1), Err(CKR_SESSION_HANDLE_INVALID));
+        assert_eq!(
+            token.close_session(session),
+            Err(CKR_SESSION_HANDLE_INVALID)
+        );
+    }
+}
@@ -0,0 +1,304 @@
+// Subset of the PKCS#11 v2.40 types and constants used by the module.
+// The structures follow the Unix ABI of the specification (no packing).
+#![allow(non_camel_case_types, non_snake_case)]
+
+use std::os::raw::{c_uchar, c_ulong, c_void};
+
+pub type CK_BYTE = c_uchar;
+pub type CK_BBOOL = CK_BYTE;
+pub type CK_ULONG = c_ulong;
+pub type CK_RV = CK_ULONG;
+pub type CK_FLAGS = CK_ULONG;
+pub type CK_SLOT_ID = CK_ULONG;
+pub type CK_SESSION_HANDLE = CK_ULONG;
+pub type CK_OBJECT_HANDLE = CK_ULONG;
+pub type CK_OBJECT_CLASS = CK_ULONG;
+pub type CK_MECHANISM_TYPE = CK_ULONG;
+pub type CK_ATTRIBUTE_TYPE = CK_ULONG;
+pub type CK_USER_TYPE = CK_ULONG;
+pub type CK_VOID_PTR = *mut c_void;
+pub type CK_NOTIFY =
+    Option<unsafe extern "C" fn(CK_SESSION_HANDLE, CK_ULONG, CK_VOID_PTR) -> CK_RV>;
+
+pub const CK_TRUE: CK_BBOOL = 1;
+pub const CK_FALSE: CK_BBOOL = 0;
+pub const CK_UNAVAILABLE_INFORMATION: CK_ULONG = !0;
+
+// Return values
+pub const CKR_OK: CK_RV = 0x000;
+pub const CKR_HOST_MEMORY: CK_RV = 0x002;
+pub const CKR_SLOT_ID_INVALID: CK_RV = 0x003;
+pub const CKR_GENERAL_ERROR: CK_RV = 0x005;
+pub const CKR_FUNCTION_FAILED: CK_RV = 0x006;
+pub const CKR_ARGUMENTS_BAD: CK_RV = 0x007;
+pub const CKR_ATTRIBUTE_TYPE_INVALID: CK_RV = 0x012;
+pub const CKR_ATTRIBUTE_VALUE_INVALID: CK_RV = 0x013;
+pub const CKR_DATA_LEN_RANGE: CK_RV = 0x021;
+pub const CKR_DEVICE_ERROR: CK_RV = 0x030;
+pub const CKR_FUNCTION_NOT_SUPPORTED: CK_RV = 0x054;
+pub const CKR_KEY_HANDLE_INVALID: CK_RV = 0x060;
+pub const CKR_MECHANISM_INVALID: CK_RV = 0x070;
+pub const CKR_OBJECT_HANDLE_INVALID: CK_RV = 0x082;
+pub const CKR_OPERATION_ACTIVE: CK_RV = 0x090;
+pub const CKR_OPERATION_NOT_INITIALIZED: CK_RV = 0x091;
+pub const CKR_SESSION_HANDLE_INVALID: CK_RV = 0x0B3;
+pub const CKR_SESSION_PARALLEL_NOT_SUPPORTED: CK_RV = 0x0B4;
+pub const CKR_TEMPLATE_INCOMPLETE: CK_RV = 0x0D0;
+pub const CKR_TEMPLATE_INCONSISTENT: CK_RV = 0x0D1;
+pub const CKR_RANDOM_SEED_NOT_SUPPORTED: CK_RV = 0x120;
+pub const CKR_BUFFER_TOO_SMALL: CK_RV = 0x150;
+pub const CKR_CRYPTOKI_NOT_INITIALIZED: CK_RV = 0x190;
+pub const CKR_CRYPTOKI_ALREADY_INITIALIZED: CK_RV = 0x191;
+
+// Flags
+pub const CKF_TOKEN_PRESENT: CK_FLAGS = 0x0001;
+pub const CKF_HW_SLOT: CK_FLAGS = 0x0004;
+pub const CKF_RNG: CK_FLAGS = 0x0001;
+pub const CKF_TOKEN_INITIALIZED: CK_FLAGS = 0x0400;
+pub const CKF_RW_SESSION: CK_FLAGS = 0x0002;
+pub const CKF_SERIAL_SESSION: CK_FLAGS = 0x0004;
+pub const CKF_HW: CK_FLAGS = 0x0001;
+pub const CKF_SIGN: CK_FLAGS = 0x0800;
+pub const CKF_GENERATE_KEY_PAIR: CK_FLAGS = 0x10000;
+pub const CKF_EC_F_P: CK_FLAGS = 0x100000;
+pub const CKF_EC_NAMEDCURVE: CK_FLAGS = 0x800000;
+pub const CKF_EC_UNCOMPRESS: CK_FLAGS = 0x1000000;
+
+// Session states
+pub const CKS_RO_PUBLIC_SESSION: CK_ULONG = 0;
+pub const CKS_RW_PUBLIC_SESSION: CK_ULONG = 2;
+
+// Object classes and key types
+pub const CKO_PUBLIC_KEY: CK_OBJECT_CLASS = 2;
+pub const CKO_PRIVATE_KEY: CK_OBJECT_CLASS = 3;
+pub const CKK_EC: CK_ULONG = 3;
+
+// Attributes
+pub const CKA_CLASS: CK_ATTRIBUTE_TYPE = 0x000;
+pub const CKA_TOKEN: CK_ATTRIBUTE_TYPE = 0x001;
+pub const CKA_PRIVATE: CK_ATTRIBUTE_TYPE = 0x002;
+pub const CKA_LABEL: CK_ATTRIBUTE_TYPE = 0x003;
+pub const CKA_KEY_TYPE: CK_ATTRIBUTE_TYPE = 0x100;
+pub const CKA_ID: CK_ATTRIBUTE_TYPE = 0x102;
+pub const CKA_SENSITIVE: CK_ATTRIBUTE_TYPE = 0x103;
+pub const CKA_SIGN: CK_ATTRIBUTE_TYPE = 0x108;
+pub const CKA_VERIFY: CK_ATTRIBUTE_TYPE = 0x10A;
+pub const CKA_EXTRACTABLE: CK_ATTRIBUTE_TYPE = 0x162;
+pub const CKA_LOCAL: CK_ATTRIBUTE_TYPE = 0x163;
+pub const CKA_NEVER_EXTRACTABLE: CK_ATTRIBUTE_TYPE = 0x164;
+pub const CKA_ALWAYS_SENSITIVE: CK_ATTRIBUTE_TYPE = 0x165;
+pub const CKA_MODIFIABLE: CK_ATTRIBUTE_TYPE = 0x170;
+pub const CKA_EC_PARAMS: CK_ATTRIBUTE_TYPE = 0x180;
+pub const CKA_EC_POINT: CK_ATTRIBUTE_TYPE = 0x181;
+
+// Mechanisms
+pub const CKM_EC_KEY_PAIR_GEN: CK_MECHANISM_TYPE = 0x1040;
+pub const CKM_ECDSA: CK_MECHANISM_TYPE = 0x1041;
+pub const CKM_ECDSA_SHA256: CK_MECHANISM_TYPE = 0x1044;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CK_VERSION {
+    pub major: CK_BYTE,
+    pub minor: CK_BYTE,
+}
+
+#[repr(C)]
+pub struct CK_INFO {
+    pub cryptokiVersion: CK_VERSION,
+    pub manufacturerID: [CK_BYTE; 32],
+    pub flags: CK_FLAGS,
+    pub libraryDescription: [CK_BYTE; 32],
+    pub libraryVersion: CK_VERSION,
+}
+
+#[repr(C)]
+pub struct CK_SLOT_INFO {
+    pub slotDescription: [CK_BYTE; 64],
+    pub manufacturerID: [CK_BYTE; 32],
+    pub flags: CK_FLAGS,
+    pub hardwareVersion: CK_VERSION,
+    pub firmwareVersion: CK_VERSION,
+}
+
+#[repr(C)]
+pub struct CK_TOKEN_INFO {
+    pub label: [CK_BYTE; 32],
+    pub manufacturerID: [CK_BYTE; 32],
+    pub model: [CK_BYTE; 16],
+    pub serialNumber: [CK_BYTE; 16],
+    pub flags: CK_FLAGS,
+    pub ulMaxSessionCount: CK_ULONG,
+    pub ulSessionCount: CK_ULONG,
+    pub ulMaxRwSessionCount: CK_ULONG,
+    pub ulRwSessionCount: CK_ULONG,
+    pub ulMaxPinLen: CK_ULONG,
+    pub ulMinPinLen: CK_ULONG,
+    pub ulTotalPublicMemory: CK_ULONG,
+    pub ulFreePublicMemory: CK_ULONG,
+    pub ulTotalPrivateMemory: CK_ULONG,
+    pub ulFreePrivateMemory: CK_ULONG,
+    pub hardwareVersion: CK_VERSION,
+    pub firmwareVersion: CK_VERSION,
+    pub utcTime: [CK_BYTE; 16],
+}
+
+#[repr(C)]
+pub struct CK_SESSION_INFO {
+    pub slotID: CK_SLOT_ID,
+    pub state: CK_ULONG,
+    pub flags: CK_FLAGS,
+    pub ulDeviceError: CK_ULONG,
+}
+
+#[repr(C)]
+pub struct CK_MECHANISM_INFO {
+    pub ulMinKeySize: CK_ULONG,
+    pub ulMaxKeySize: CK_ULONG,
+    pub flags: CK_FLAGS,
+}
+
+#[repr(C)]
+pub struct CK_MECHANISM {
+    pub mechanism: CK_MECHANISM_TYPE,
+    pub pParameter: CK_VOID_PTR,
+    pub ulParameterLen: CK_ULONG,
+}
+
+#[repr(C)]
+pub struct CK_ATTRIBUTE {
+    pub type_: CK_ATTRIBUTE_TYPE,
+    pub pValue: CK_VOID_PTR,
+    pub ulValueLen: CK_ULONG,
+}
+
+/// Entry of the function list for a function the module does not implement.
+/// All such entries point to one function that ignores its arguments,
+/// which is sound for the C calling convention where the caller cleans up.
+pub type CK_NOT_SUPPORTED = Option<unsafe extern "C" fn() -> CK_RV>;
+
+#[repr(C)]
+pub struct CK_FUNCTION_LIST {
+    pub version: CK_VERSION,
+    pub C_Initialize: Option<unsafe extern "C" fn(CK_VOID_PTR) -> CK_RV>,
+    pub C_Finalize: Option<unsafe extern "C" fn(CK_VOID_PTR) -> CK_RV>,
+    pub C_GetInfo: Option<unsafe extern "C" fn(*mut CK_INFO) -> CK_RV>,
+    pub C_GetFunctionList: Option<unsafe extern "C" fn(*mut *const CK_FUNCTION_LIST) -> CK_RV>,
+    pub C_GetSlotList:
+        Option<unsafe extern "C" fn(CK_BBOOL, *mut CK_SLOT_ID, *mut CK_ULONG) -> CK_RV>,
+    pub C_GetSlotInfo: Option<unsafe extern "C" fn(CK_SLOT_ID, *mut CK_SLOT_INFO) -> CK_RV>,
+    pub C_GetTokenInfo: Option<unsafe extern "C" fn(CK_SLOT_ID, *mut CK_TOKEN_INFO) -> CK_RV>,
+    pub C_GetMechanismList:
+        Option<unsafe extern "C" fn(CK_SLOT_ID, *mut CK_MECHANISM_TYPE, *mut CK_ULONG) -> CK_RV>,
+    pub C_GetMechanismInfo: Option<
+        unsafe extern "C" fn(CK_SLOT_ID, CK_MECHANISM_TYPE, *mut CK_MECHANISM_INFO) -> CK_RV,
+    >,
+    pub C_InitToken: CK_NOT_SUPPORTED,
+    pub C_InitPIN: CK_NOT_SUPPORTED,
+    pub C_SetPIN: CK_NOT_SUPPORTED,
+    pub C_OpenSession: Option<
+        unsafe extern "C" fn(
+            CK_SLOT_ID,
+            CK_FLAGS,
+            CK_VOID_PTR,
+            CK_NOTIFY,
+            *mut CK_SESSION_HANDLE,
+        ) -> CK_RV,
+    >,
+    pub C_CloseSession: Option<unsafe extern "C" fn(CK_SESSION_HANDLE) -> CK_RV>,
+    pub C_CloseAllSessions: Option<unsafe extern "C" fn(CK_SLOT_ID) -> CK_RV>,
+    pub C_GetSessionInfo:
+        Option<unsafe extern "C" fn(CK_SESSION_HANDLE, *mut CK_SESSION_INFO) -> CK_RV>,
+    pub C_GetOperationState: CK_NOT_SUPPORTED,
+    pub C_SetOperationState: CK_NOT_SUPPORTED,
+    pub C_Login: Option<
+        unsafe extern "C" fn(CK_SESSION_HANDLE, CK_USER_TYPE, *mut CK_BYTE, CK_ULONG) -> CK_RV,
+    >,
+    pub C_Logout: Option<unsafe extern "C" fn(CK_SESSION_HANDLE) -> CK_RV>,
+    pub C_CreateObject: CK_NOT_SUPPORTED,
+    pub C_CopyObject: CK_NOT_SUPPORTED,
+    pub C_DestroyObject: CK_NOT_SUPPORTED,
+    pub C_GetObjectSize: CK_NOT_SUPPORTED,
+    pub C_GetAttributeValue: Option<
+        unsafe extern "C" fn(
+            CK_SESSION_HANDLE,
+            CK_OBJECT_HANDLE,
+            *mut CK_ATTRIBUTE,
+            CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_SetAttributeValue: CK_NOT_SUPPORTED,
+    pub C_FindObjectsInit:
+        Option<unsafe extern "C" fn(CK_SESSION_HANDLE, *mut CK_ATTRIBUTE, CK_ULONG) -> CK_RV>,
+    pub C_FindObjects: Option<
+        unsafe extern "C" fn(
+            CK_SESSION_HANDLE,
+            *mut CK_OBJECT_HANDLE,
+            CK_ULONG,
+            *mut CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_FindObjectsFinal: Option<unsafe extern "C" fn(CK_SESSION_HANDLE) -> CK_RV>,
+    pub C_EncryptInit: CK_NOT_SUPPORTED,
+    pub C_Encrypt: CK_NOT_SUPPORTED,
+    pub C_EncryptUpdate: CK_NOT_SUPPORTED,
+    pub C_EncryptFinal: CK_NOT_SUPPORTED,
+    pub C_DecryptInit: CK_NOT_SUPPORTED,
+    pub C_Decrypt: CK_NOT_SUPPORTED,
+    pub C_DecryptUpdate: CK_NOT_SUPPORTED,
+    pub C_DecryptFinal: CK_NOT_SUPPORTED,
+    pub C_DigestInit: CK_NOT_SUPPORTED,
+    pub C_Digest: CK_NOT_SUPPORTED,
+    pub C_DigestUpdate: CK_NOT_SUPPORTED,
+    pub C_DigestKey: CK_NOT_SUPPORTED,
+    pub C_DigestFinal: CK_NOT_SUPPORTED,
+    pub C_SignInit: Option<
+        unsafe extern "C" fn(CK_SESSION_HANDLE, *mut CK_MECHANISM, CK_OBJECT_HANDLE) -> CK_RV,
+    >,
+    pub C_Sign: Option<
+        unsafe extern "C" fn(
+            CK_SESSION_HANDLE,
+            *mut CK_BYTE,
+            CK_ULONG,
+            *mut CK_BYTE,
+            *mut CK_ULONG,
+        ) -> CK_RV,
+    >,
+    pub C_SignUpdate: CK_NOT_SUPPORTED,
+    pub C_SignFinal: CK_NOT_SUPPORTED,
+    pub C_SignRecoverInit: CK_NOT_SUPPORTED,
+    pub C_SignRecover: CK_NOT_SUPPORTED,
+    pub C_VerifyInit: CK_NOT_SUPPORTED,
+    pub C_Verify: CK_NOT_SUPPORTED,
+    pub C_VerifyUpdate: CK_NOT_SUPPORTED,
+    pub C_VerifyFinal: CK_NOT_SUPPORTED,
+    pub C_VerifyRecoverInit: CK_NOT_SUPPORTED,
+    pub C_VerifyRecover: CK_NOT_SUPPORTED,
+    pub C_DigestEncryptUpdate: CK_NOT_SUPPORTED,
+    pub C_DecryptDigestUpdate: CK_NOT_SUPPORTED,
+    pub C_SignEncryptUpdate: CK_NOT_SUPPORTED,
+    pub C_DecryptVerifyUpdate: CK_NOT_SUPPORTED,
+    pub C_GenerateKey: CK_NOT_SUPPORTED,
+    pub C_GenerateKeyPair: Option<
+        unsafe extern "C" fn(
+            CK_SESSION_HANDLE,
+            *mut CK_MECHANISM,
+            *mut CK_ATTRIBUTE,
+            CK_ULONG,
+            *mut CK_ATTRIBUTE,
+            CK_ULONG,
+            *mut CK_OBJECT_HANDLE,
+            *mut CK_OBJECT_HANDLE,
+        ) -> CK_RV,
+    >,
+    pub C_WrapKey: CK_NOT_SUPPORTED,
+    pub C_UnwrapKey: CK_NOT_SUPPORTED,
+    pub C_DeriveKey: CK_NOT_SUPPORTED,
+    pub C_SeedRandom:
+        Option<unsafe extern "C" fn(CK_SESSION_HANDLE, *mut CK_BYTE, CK_ULONG) -> CK_RV>,
+    pub C_GenerateRandom:
+        Option<unsafe extern "C" fn(CK_SESSION_HANDLE, *mut CK_BYTE, CK_ULONG) -> CK_RV>,
+    pub C_GetFunctionStatus: CK_NOT_SUPPORTED,
+    pub C_CancelFunction: CK_NOT_SUPPORTED,
+    pub C_WaitForSlotEvent: CK_NOT_SUPPORTED,
+}
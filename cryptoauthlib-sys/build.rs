@@ -3,6 +3,10 @@ fn main() {
         .no_build_target(true) // Prevent installing in host system directories
         .define("ATCA_BUILD_SHARED_LIBS", "0") // Build CAL as a static library
         .define("ATCA_HAL_I2C", "ON") // Include i2c support
+        .define("ATCA_HAL_SWI_UART", "ON") // Include SWI (over UART) support
+        .define("ATCA_HAL_KIT_HID", "ON") // Include USB HID development kit support
+        .define("ATCA_HAL_KIT_UART", "ON") // Include UART development kit support
+        .define("ATCA_HAL_CUSTOM", "ON") // Allow HAL functions provided by the caller
         .build();
     // Below caller LD_FLAGS are defined. First -L then -l
     println!("cargo:rustc-link-search=native={}/build/lib", dst.display());
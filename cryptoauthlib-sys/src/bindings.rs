@@ -230,13 +230,13 @@ pub const WRITE_MAC_VL_IDX: u32 = 37;
 pub const WRITE_MAC_SIZE: u32 = 32;
 pub const ATCA_SHA2_256_DIGEST_SIZE: u32 = 32;
 pub const ATCA_AES_GCM_IV_STD_LENGTH: u32 = 12;
-pub type size_t = ::std::os::raw::c_ulong;
-pub type wchar_t = ::std::os::raw::c_int;
+pub type size_t = ::core::ffi::c_ulong;
+pub type wchar_t = ::core::ffi::c_int;
 #[repr(C)]
 #[repr(align(16))]
 #[derive(Debug, Copy, Clone)]
 pub struct max_align_t {
-    pub __clang_max_align_nonce1: ::std::os::raw::c_longlong,
+    pub __clang_max_align_nonce1: ::core::ffi::c_longlong,
     pub __bindgen_padding_0: u64,
     pub __clang_max_align_nonce2: u128,
 }
@@ -244,18 +244,18 @@ pub struct max_align_t {
 #[test]
 fn bindgen_test_layout_max_align_t() {
     assert_eq!(
-        ::std::mem::size_of::<max_align_t>(),
+        ::core::mem::size_of::<max_align_t>(),
         32usize,
         concat!("Size of: ", stringify!(max_align_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<max_align_t>(),
+        ::core::mem::align_of::<max_align_t>(),
         16usize,
         concat!("Alignment of ", stringify!(max_align_t))
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<max_align_t>())).__clang_max_align_nonce1 as *const _ as usize
+            &(*(::core::ptr::null::<max_align_t>())).__clang_max_align_nonce1 as *const _ as usize
         },
         0usize,
         concat!(
@@ -267,7 +267,7 @@ fn bindgen_test_layout_max_align_t() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<max_align_t>())).__clang_max_align_nonce2 as *const _ as usize
+            &(*(::core::ptr::null::<max_align_t>())).__clang_max_align_nonce2 as *const _ as usize
         },
         16usize,
         concat!(
@@ -280,123 +280,123 @@ fn bindgen_test_layout_max_align_t() {
 }
 extern "C" {
     pub fn memcpy(
-        __dest: *mut ::std::os::raw::c_void,
-        __src: *const ::std::os::raw::c_void,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_void;
+        __dest: *mut ::core::ffi::c_void,
+        __src: *const ::core::ffi::c_void,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_void;
 }
 extern "C" {
     pub fn memmove(
-        __dest: *mut ::std::os::raw::c_void,
-        __src: *const ::std::os::raw::c_void,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_void;
+        __dest: *mut ::core::ffi::c_void,
+        __src: *const ::core::ffi::c_void,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_void;
 }
 extern "C" {
     pub fn memccpy(
-        __dest: *mut ::std::os::raw::c_void,
-        __src: *const ::std::os::raw::c_void,
-        __c: ::std::os::raw::c_int,
+        __dest: *mut ::core::ffi::c_void,
+        __src: *const ::core::ffi::c_void,
+        __c: ::core::ffi::c_int,
         __n: size_t,
-    ) -> *mut ::std::os::raw::c_void;
+    ) -> *mut ::core::ffi::c_void;
 }
 extern "C" {
     pub fn memset(
-        __s: *mut ::std::os::raw::c_void,
-        __c: ::std::os::raw::c_int,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_void;
+        __s: *mut ::core::ffi::c_void,
+        __c: ::core::ffi::c_int,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_void;
 }
 extern "C" {
     pub fn memcmp(
-        __s1: *const ::std::os::raw::c_void,
-        __s2: *const ::std::os::raw::c_void,
-        __n: ::std::os::raw::c_ulong,
-    ) -> ::std::os::raw::c_int;
+        __s1: *const ::core::ffi::c_void,
+        __s2: *const ::core::ffi::c_void,
+        __n: ::core::ffi::c_ulong,
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn memchr(
-        __s: *const ::std::os::raw::c_void,
-        __c: ::std::os::raw::c_int,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_void;
+        __s: *const ::core::ffi::c_void,
+        __c: ::core::ffi::c_int,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_void;
 }
 extern "C" {
     pub fn strcpy(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strncpy(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_char;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strcat(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strncat(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_char;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strcmp(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
-    ) -> ::std::os::raw::c_int;
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strncmp(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> ::std::os::raw::c_int;
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strcoll(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
-    ) -> ::std::os::raw::c_int;
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strxfrm(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> ::std::os::raw::c_ulong;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> ::core::ffi::c_ulong;
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct __locale_struct {
     pub __locales: [*mut __locale_data; 13usize],
-    pub __ctype_b: *const ::std::os::raw::c_ushort,
-    pub __ctype_tolower: *const ::std::os::raw::c_int,
-    pub __ctype_toupper: *const ::std::os::raw::c_int,
-    pub __names: [*const ::std::os::raw::c_char; 13usize],
+    pub __ctype_b: *const ::core::ffi::c_ushort,
+    pub __ctype_tolower: *const ::core::ffi::c_int,
+    pub __ctype_toupper: *const ::core::ffi::c_int,
+    pub __names: [*const ::core::ffi::c_char; 13usize],
 }
 #[allow(deref_nullptr)]
 #[test]
 fn bindgen_test_layout___locale_struct() {
     assert_eq!(
-        ::std::mem::size_of::<__locale_struct>(),
+        ::core::mem::size_of::<__locale_struct>(),
         232usize,
         concat!("Size of: ", stringify!(__locale_struct))
     );
     assert_eq!(
-        ::std::mem::align_of::<__locale_struct>(),
+        ::core::mem::align_of::<__locale_struct>(),
         8usize,
         concat!("Alignment of ", stringify!(__locale_struct))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__locale_struct>())).__locales as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<__locale_struct>())).__locales as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -406,7 +406,7 @@ fn bindgen_test_layout___locale_struct() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__locale_struct>())).__ctype_b as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<__locale_struct>())).__ctype_b as *const _ as usize },
         104usize,
         concat!(
             "Offset of field: ",
@@ -416,7 +416,7 @@ fn bindgen_test_layout___locale_struct() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__locale_struct>())).__ctype_tolower as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<__locale_struct>())).__ctype_tolower as *const _ as usize },
         112usize,
         concat!(
             "Offset of field: ",
@@ -426,7 +426,7 @@ fn bindgen_test_layout___locale_struct() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__locale_struct>())).__ctype_toupper as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<__locale_struct>())).__ctype_toupper as *const _ as usize },
         120usize,
         concat!(
             "Offset of field: ",
@@ -436,7 +436,7 @@ fn bindgen_test_layout___locale_struct() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__locale_struct>())).__names as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<__locale_struct>())).__names as *const _ as usize },
         128usize,
         concat!(
             "Offset of field: ",
@@ -450,257 +450,257 @@ pub type __locale_t = *mut __locale_struct;
 pub type locale_t = __locale_t;
 extern "C" {
     pub fn strcoll_l(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
         __l: locale_t,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strxfrm_l(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
         __n: size_t,
         __l: locale_t,
     ) -> size_t;
 }
 extern "C" {
-    pub fn strdup(__s: *const ::std::os::raw::c_char) -> *mut ::std::os::raw::c_char;
+    pub fn strdup(__s: *const ::core::ffi::c_char) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strndup(
-        __string: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_char;
+        __string: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strchr(
-        __s: *const ::std::os::raw::c_char,
-        __c: ::std::os::raw::c_int,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *const ::core::ffi::c_char,
+        __c: ::core::ffi::c_int,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strrchr(
-        __s: *const ::std::os::raw::c_char,
-        __c: ::std::os::raw::c_int,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *const ::core::ffi::c_char,
+        __c: ::core::ffi::c_int,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strcspn(
-        __s: *const ::std::os::raw::c_char,
-        __reject: *const ::std::os::raw::c_char,
-    ) -> ::std::os::raw::c_ulong;
+        __s: *const ::core::ffi::c_char,
+        __reject: *const ::core::ffi::c_char,
+    ) -> ::core::ffi::c_ulong;
 }
 extern "C" {
     pub fn strspn(
-        __s: *const ::std::os::raw::c_char,
-        __accept: *const ::std::os::raw::c_char,
-    ) -> ::std::os::raw::c_ulong;
+        __s: *const ::core::ffi::c_char,
+        __accept: *const ::core::ffi::c_char,
+    ) -> ::core::ffi::c_ulong;
 }
 extern "C" {
     pub fn strpbrk(
-        __s: *const ::std::os::raw::c_char,
-        __accept: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *const ::core::ffi::c_char,
+        __accept: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strstr(
-        __haystack: *const ::std::os::raw::c_char,
-        __needle: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __haystack: *const ::core::ffi::c_char,
+        __needle: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strtok(
-        __s: *mut ::std::os::raw::c_char,
-        __delim: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *mut ::core::ffi::c_char,
+        __delim: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn __strtok_r(
-        __s: *mut ::std::os::raw::c_char,
-        __delim: *const ::std::os::raw::c_char,
-        __save_ptr: *mut *mut ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *mut ::core::ffi::c_char,
+        __delim: *const ::core::ffi::c_char,
+        __save_ptr: *mut *mut ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn strtok_r(
-        __s: *mut ::std::os::raw::c_char,
-        __delim: *const ::std::os::raw::c_char,
-        __save_ptr: *mut *mut ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *mut ::core::ffi::c_char,
+        __delim: *const ::core::ffi::c_char,
+        __save_ptr: *mut *mut ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
-    pub fn strlen(__s: *const ::std::os::raw::c_char) -> ::std::os::raw::c_ulong;
+    pub fn strlen(__s: *const ::core::ffi::c_char) -> ::core::ffi::c_ulong;
 }
 extern "C" {
-    pub fn strnlen(__string: *const ::std::os::raw::c_char, __maxlen: size_t) -> size_t;
+    pub fn strnlen(__string: *const ::core::ffi::c_char, __maxlen: size_t) -> size_t;
 }
 extern "C" {
-    pub fn strerror(__errnum: ::std::os::raw::c_int) -> *mut ::std::os::raw::c_char;
+    pub fn strerror(__errnum: ::core::ffi::c_int) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     #[link_name = "\u{1}__xpg_strerror_r"]
     pub fn strerror_r(
-        __errnum: ::std::os::raw::c_int,
-        __buf: *mut ::std::os::raw::c_char,
+        __errnum: ::core::ffi::c_int,
+        __buf: *mut ::core::ffi::c_char,
         __buflen: size_t,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strerror_l(
-        __errnum: ::std::os::raw::c_int,
+        __errnum: ::core::ffi::c_int,
         __l: locale_t,
-    ) -> *mut ::std::os::raw::c_char;
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn bcmp(
-        __s1: *const ::std::os::raw::c_void,
-        __s2: *const ::std::os::raw::c_void,
+        __s1: *const ::core::ffi::c_void,
+        __s2: *const ::core::ffi::c_void,
         __n: size_t,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn bcopy(
-        __src: *const ::std::os::raw::c_void,
-        __dest: *mut ::std::os::raw::c_void,
+        __src: *const ::core::ffi::c_void,
+        __dest: *mut ::core::ffi::c_void,
         __n: size_t,
     );
 }
 extern "C" {
-    pub fn bzero(__s: *mut ::std::os::raw::c_void, __n: ::std::os::raw::c_ulong);
+    pub fn bzero(__s: *mut ::core::ffi::c_void, __n: ::core::ffi::c_ulong);
 }
 extern "C" {
     pub fn index(
-        __s: *const ::std::os::raw::c_char,
-        __c: ::std::os::raw::c_int,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *const ::core::ffi::c_char,
+        __c: ::core::ffi::c_int,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn rindex(
-        __s: *const ::std::os::raw::c_char,
-        __c: ::std::os::raw::c_int,
-    ) -> *mut ::std::os::raw::c_char;
+        __s: *const ::core::ffi::c_char,
+        __c: ::core::ffi::c_int,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
-    pub fn ffs(__i: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn ffs(__i: ::core::ffi::c_int) -> ::core::ffi::c_int;
 }
 extern "C" {
-    pub fn ffsl(__l: ::std::os::raw::c_long) -> ::std::os::raw::c_int;
+    pub fn ffsl(__l: ::core::ffi::c_long) -> ::core::ffi::c_int;
 }
 extern "C" {
-    pub fn ffsll(__ll: ::std::os::raw::c_longlong) -> ::std::os::raw::c_int;
+    pub fn ffsll(__ll: ::core::ffi::c_longlong) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strcasecmp(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
-    ) -> ::std::os::raw::c_int;
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strncasecmp(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> ::std::os::raw::c_int;
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strcasecmp_l(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
         __loc: locale_t,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn strncasecmp_l(
-        __s1: *const ::std::os::raw::c_char,
-        __s2: *const ::std::os::raw::c_char,
+        __s1: *const ::core::ffi::c_char,
+        __s2: *const ::core::ffi::c_char,
         __n: size_t,
         __loc: locale_t,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
-    pub fn explicit_bzero(__s: *mut ::std::os::raw::c_void, __n: size_t);
+    pub fn explicit_bzero(__s: *mut ::core::ffi::c_void, __n: size_t);
 }
 extern "C" {
     pub fn strsep(
-        __stringp: *mut *mut ::std::os::raw::c_char,
-        __delim: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __stringp: *mut *mut ::core::ffi::c_char,
+        __delim: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
-    pub fn strsignal(__sig: ::std::os::raw::c_int) -> *mut ::std::os::raw::c_char;
+    pub fn strsignal(__sig: ::core::ffi::c_int) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn __stpcpy(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn stpcpy(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-    ) -> *mut ::std::os::raw::c_char;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn __stpncpy(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
         __n: size_t,
-    ) -> *mut ::std::os::raw::c_char;
+    ) -> *mut ::core::ffi::c_char;
 }
 extern "C" {
     pub fn stpncpy(
-        __dest: *mut ::std::os::raw::c_char,
-        __src: *const ::std::os::raw::c_char,
-        __n: ::std::os::raw::c_ulong,
-    ) -> *mut ::std::os::raw::c_char;
-}
-pub type __u_char = ::std::os::raw::c_uchar;
-pub type __u_short = ::std::os::raw::c_ushort;
-pub type __u_int = ::std::os::raw::c_uint;
-pub type __u_long = ::std::os::raw::c_ulong;
-pub type __int8_t = ::std::os::raw::c_schar;
-pub type __uint8_t = ::std::os::raw::c_uchar;
-pub type __int16_t = ::std::os::raw::c_short;
-pub type __uint16_t = ::std::os::raw::c_ushort;
-pub type __int32_t = ::std::os::raw::c_int;
-pub type __uint32_t = ::std::os::raw::c_uint;
-pub type __int64_t = ::std::os::raw::c_long;
-pub type __uint64_t = ::std::os::raw::c_ulong;
-pub type __quad_t = ::std::os::raw::c_long;
-pub type __u_quad_t = ::std::os::raw::c_ulong;
-pub type __intmax_t = ::std::os::raw::c_long;
-pub type __uintmax_t = ::std::os::raw::c_ulong;
-pub type __dev_t = ::std::os::raw::c_ulong;
-pub type __uid_t = ::std::os::raw::c_uint;
-pub type __gid_t = ::std::os::raw::c_uint;
-pub type __ino_t = ::std::os::raw::c_ulong;
-pub type __ino64_t = ::std::os::raw::c_ulong;
-pub type __mode_t = ::std::os::raw::c_uint;
-pub type __nlink_t = ::std::os::raw::c_ulong;
-pub type __off_t = ::std::os::raw::c_long;
-pub type __off64_t = ::std::os::raw::c_long;
-pub type __pid_t = ::std::os::raw::c_int;
+        __dest: *mut ::core::ffi::c_char,
+        __src: *const ::core::ffi::c_char,
+        __n: ::core::ffi::c_ulong,
+    ) -> *mut ::core::ffi::c_char;
+}
+pub type __u_char = ::core::ffi::c_uchar;
+pub type __u_short = ::core::ffi::c_ushort;
+pub type __u_int = ::core::ffi::c_uint;
+pub type __u_long = ::core::ffi::c_ulong;
+pub type __int8_t = ::core::ffi::c_schar;
+pub type __uint8_t = ::core::ffi::c_uchar;
+pub type __int16_t = ::core::ffi::c_short;
+pub type __uint16_t = ::core::ffi::c_ushort;
+pub type __int32_t = ::core::ffi::c_int;
+pub type __uint32_t = ::core::ffi::c_uint;
+pub type __int64_t = ::core::ffi::c_long;
+pub type __uint64_t = ::core::ffi::c_ulong;
+pub type __quad_t = ::core::ffi::c_long;
+pub type __u_quad_t = ::core::ffi::c_ulong;
+pub type __intmax_t = ::core::ffi::c_long;
+pub type __uintmax_t = ::core::ffi::c_ulong;
+pub type __dev_t = ::core::ffi::c_ulong;
+pub type __uid_t = ::core::ffi::c_uint;
+pub type __gid_t = ::core::ffi::c_uint;
+pub type __ino_t = ::core::ffi::c_ulong;
+pub type __ino64_t = ::core::ffi::c_ulong;
+pub type __mode_t = ::core::ffi::c_uint;
+pub type __nlink_t = ::core::ffi::c_ulong;
+pub type __off_t = ::core::ffi::c_long;
+pub type __off64_t = ::core::ffi::c_long;
+pub type __pid_t = ::core::ffi::c_int;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct __fsid_t {
-    pub __val: [::std::os::raw::c_int; 2usize],
+    pub __val: [::core::ffi::c_int; 2usize],
 }
 #[allow(deref_nullptr)]
 #[test]
 fn bindgen_test_layout___fsid_t() {
     assert_eq!(
-        ::std::mem::size_of::<__fsid_t>(),
+        ::core::mem::size_of::<__fsid_t>(),
         8usize,
         concat!("Size of: ", stringify!(__fsid_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<__fsid_t>(),
+        ::core::mem::align_of::<__fsid_t>(),
         4usize,
         concat!("Alignment of ", stringify!(__fsid_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__fsid_t>())).__val as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<__fsid_t>())).__val as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -710,49 +710,49 @@ fn bindgen_test_layout___fsid_t() {
         )
     );
 }
-pub type __clock_t = ::std::os::raw::c_long;
-pub type __rlim_t = ::std::os::raw::c_ulong;
-pub type __rlim64_t = ::std::os::raw::c_ulong;
-pub type __id_t = ::std::os::raw::c_uint;
-pub type __time_t = ::std::os::raw::c_long;
-pub type __useconds_t = ::std::os::raw::c_uint;
-pub type __suseconds_t = ::std::os::raw::c_long;
-pub type __daddr_t = ::std::os::raw::c_int;
-pub type __key_t = ::std::os::raw::c_int;
-pub type __clockid_t = ::std::os::raw::c_int;
-pub type __timer_t = *mut ::std::os::raw::c_void;
-pub type __blksize_t = ::std::os::raw::c_long;
-pub type __blkcnt_t = ::std::os::raw::c_long;
-pub type __blkcnt64_t = ::std::os::raw::c_long;
-pub type __fsblkcnt_t = ::std::os::raw::c_ulong;
-pub type __fsblkcnt64_t = ::std::os::raw::c_ulong;
-pub type __fsfilcnt_t = ::std::os::raw::c_ulong;
-pub type __fsfilcnt64_t = ::std::os::raw::c_ulong;
-pub type __fsword_t = ::std::os::raw::c_long;
-pub type __ssize_t = ::std::os::raw::c_long;
-pub type __syscall_slong_t = ::std::os::raw::c_long;
-pub type __syscall_ulong_t = ::std::os::raw::c_ulong;
+pub type __clock_t = ::core::ffi::c_long;
+pub type __rlim_t = ::core::ffi::c_ulong;
+pub type __rlim64_t = ::core::ffi::c_ulong;
+pub type __id_t = ::core::ffi::c_uint;
+pub type __time_t = ::core::ffi::c_long;
+pub type __useconds_t = ::core::ffi::c_uint;
+pub type __suseconds_t = ::core::ffi::c_long;
+pub type __daddr_t = ::core::ffi::c_int;
+pub type __key_t = ::core::ffi::c_int;
+pub type __clockid_t = ::core::ffi::c_int;
+pub type __timer_t = *mut ::core::ffi::c_void;
+pub type __blksize_t = ::core::ffi::c_long;
+pub type __blkcnt_t = ::core::ffi::c_long;
+pub type __blkcnt64_t = ::core::ffi::c_long;
+pub type __fsblkcnt_t = ::core::ffi::c_ulong;
+pub type __fsblkcnt64_t = ::core::ffi::c_ulong;
+pub type __fsfilcnt_t = ::core::ffi::c_ulong;
+pub type __fsfilcnt64_t = ::core::ffi::c_ulong;
+pub type __fsword_t = ::core::ffi::c_long;
+pub type __ssize_t = ::core::ffi::c_long;
+pub type __syscall_slong_t = ::core::ffi::c_long;
+pub type __syscall_ulong_t = ::core::ffi::c_ulong;
 pub type __loff_t = __off64_t;
-pub type __caddr_t = *mut ::std::os::raw::c_char;
-pub type __intptr_t = ::std::os::raw::c_long;
-pub type __socklen_t = ::std::os::raw::c_uint;
-pub type __sig_atomic_t = ::std::os::raw::c_int;
-pub type int_least8_t = ::std::os::raw::c_schar;
-pub type int_least16_t = ::std::os::raw::c_short;
-pub type int_least32_t = ::std::os::raw::c_int;
-pub type int_least64_t = ::std::os::raw::c_long;
-pub type uint_least8_t = ::std::os::raw::c_uchar;
-pub type uint_least16_t = ::std::os::raw::c_ushort;
-pub type uint_least32_t = ::std::os::raw::c_uint;
-pub type uint_least64_t = ::std::os::raw::c_ulong;
-pub type int_fast8_t = ::std::os::raw::c_schar;
-pub type int_fast16_t = ::std::os::raw::c_long;
-pub type int_fast32_t = ::std::os::raw::c_long;
-pub type int_fast64_t = ::std::os::raw::c_long;
-pub type uint_fast8_t = ::std::os::raw::c_uchar;
-pub type uint_fast16_t = ::std::os::raw::c_ulong;
-pub type uint_fast32_t = ::std::os::raw::c_ulong;
-pub type uint_fast64_t = ::std::os::raw::c_ulong;
+pub type __caddr_t = *mut ::core::ffi::c_char;
+pub type __intptr_t = ::core::ffi::c_long;
+pub type __socklen_t = ::core::ffi::c_uint;
+pub type __sig_atomic_t = ::core::ffi::c_int;
+pub type int_least8_t = ::core::ffi::c_schar;
+pub type int_least16_t = ::core::ffi::c_short;
+pub type int_least32_t = ::core::ffi::c_int;
+pub type int_least64_t = ::core::ffi::c_long;
+pub type uint_least8_t = ::core::ffi::c_uchar;
+pub type uint_least16_t = ::core::ffi::c_ushort;
+pub type uint_least32_t = ::core::ffi::c_uint;
+pub type uint_least64_t = ::core::ffi::c_ulong;
+pub type int_fast8_t = ::core::ffi::c_schar;
+pub type int_fast16_t = ::core::ffi::c_long;
+pub type int_fast32_t = ::core::ffi::c_long;
+pub type int_fast64_t = ::core::ffi::c_long;
+pub type uint_fast8_t = ::core::ffi::c_uchar;
+pub type uint_fast16_t = ::core::ffi::c_ulong;
+pub type uint_fast32_t = ::core::ffi::c_ulong;
+pub type uint_fast64_t = ::core::ffi::c_ulong;
 pub type intmax_t = __intmax_t;
 pub type uintmax_t = __uintmax_t;
 #[doc = "!< Function succeeded."]
@@ -827,7 +827,7 @@ pub const ATCA_STATUS_ATCA_HEALTH_TEST_ERROR: ATCA_STATUS = 250;
 pub const ATCA_STATUS_ATCA_ALLOC_FAILURE: ATCA_STATUS = 251;
 #[doc = "!< Use flags on the device indicates its consumed fully"]
 pub const ATCA_STATUS_ATCA_USE_FLAGS_CONSUMED: ATCA_STATUS = 252;
-pub type ATCA_STATUS = ::std::os::raw::c_uint;
+pub type ATCA_STATUS = ::core::ffi::c_uint;
 pub const ATCADeviceType_ATSHA204A: ATCADeviceType = 0;
 pub const ATCADeviceType_ATECC108A: ATCADeviceType = 1;
 pub const ATCADeviceType_ATECC508A: ATCADeviceType = 2;
@@ -835,7 +835,7 @@ pub const ATCADeviceType_ATECC608A: ATCADeviceType = 3;
 pub const ATCADeviceType_ATSHA206A: ATCADeviceType = 4;
 pub const ATCADeviceType_ATCA_DEV_UNKNOWN: ATCADeviceType = 32;
 #[doc = " \\brief The supported Device type in Cryptoauthlib library"]
-pub type ATCADeviceType = ::std::os::raw::c_uint;
+pub type ATCADeviceType = ::core::ffi::c_uint;
 #[doc = " \\brief atca_command is the C object backing ATCACommand."]
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -848,17 +848,17 @@ pub struct atca_command {
 #[test]
 fn bindgen_test_layout_atca_command() {
     assert_eq!(
-        ::std::mem::size_of::<atca_command>(),
+        ::core::mem::size_of::<atca_command>(),
         8usize,
         concat!("Size of: ", stringify!(atca_command))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_command>(),
+        ::core::mem::align_of::<atca_command>(),
         4usize,
         concat!("Alignment of ", stringify!(atca_command))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_command>())).dt as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_command>())).dt as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -868,7 +868,7 @@ fn bindgen_test_layout_atca_command() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_command>())).clock_divider as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_command>())).clock_divider as *const _ as usize },
         4usize,
         concat!(
             "Offset of field: ",
@@ -879,7 +879,7 @@ fn bindgen_test_layout_atca_command() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<atca_command>())).execution_time_msec as *const _ as usize
+            &(*(::core::ptr::null::<atca_command>())).execution_time_msec as *const _ as usize
         },
         6usize,
         concat!(
@@ -915,17 +915,17 @@ pub struct ATCAPacket {
 #[test]
 fn bindgen_test_layout_ATCAPacket() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAPacket>(),
+        ::core::mem::size_of::<ATCAPacket>(),
         200usize,
         concat!("Size of: ", stringify!(ATCAPacket))
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAPacket>(),
+        ::core::mem::align_of::<ATCAPacket>(),
         2usize,
         concat!("Alignment of ", stringify!(ATCAPacket))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>()))._reserved as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>()))._reserved as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -935,7 +935,7 @@ fn bindgen_test_layout_ATCAPacket() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>())).txsize as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>())).txsize as *const _ as usize },
         1usize,
         concat!(
             "Offset of field: ",
@@ -945,7 +945,7 @@ fn bindgen_test_layout_ATCAPacket() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>())).opcode as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>())).opcode as *const _ as usize },
         2usize,
         concat!(
             "Offset of field: ",
@@ -955,7 +955,7 @@ fn bindgen_test_layout_ATCAPacket() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>())).param1 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>())).param1 as *const _ as usize },
         3usize,
         concat!(
             "Offset of field: ",
@@ -965,7 +965,7 @@ fn bindgen_test_layout_ATCAPacket() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>())).param2 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>())).param2 as *const _ as usize },
         4usize,
         concat!(
             "Offset of field: ",
@@ -975,7 +975,7 @@ fn bindgen_test_layout_ATCAPacket() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>())).data as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>())).data as *const _ as usize },
         6usize,
         concat!(
             "Offset of field: ",
@@ -985,7 +985,7 @@ fn bindgen_test_layout_ATCAPacket() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAPacket>())).execTime as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAPacket>())).execTime as *const _ as usize },
         198usize,
         concat!(
             "Offset of field: ",
@@ -1100,12 +1100,12 @@ pub const ATCAIfaceType_ATCA_SPI_IFACE: ATCAIfaceType = 3;
 pub const ATCAIfaceType_ATCA_HID_IFACE: ATCAIfaceType = 4;
 pub const ATCAIfaceType_ATCA_CUSTOM_IFACE: ATCAIfaceType = 5;
 pub const ATCAIfaceType_ATCA_UNKNOWN_IFACE: ATCAIfaceType = 6;
-pub type ATCAIfaceType = ::std::os::raw::c_uint;
+pub type ATCAIfaceType = ::core::ffi::c_uint;
 pub const ATCAKitType_ATCA_KIT_AUTO_IFACE: ATCAKitType = 0;
 pub const ATCAKitType_ATCA_KIT_I2C_IFACE: ATCAKitType = 1;
 pub const ATCAKitType_ATCA_KIT_SWI_IFACE: ATCAKitType = 2;
 pub const ATCAKitType_ATCA_KIT_UNKNOWN_IFACE: ATCAKitType = 3;
-pub type ATCAKitType = ::std::os::raw::c_uint;
+pub type ATCAKitType = ::core::ffi::c_uint;
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct ATCAIfaceCfg {
@@ -1113,8 +1113,8 @@ pub struct ATCAIfaceCfg {
     pub devtype: ATCADeviceType,
     pub __bindgen_anon_1: ATCAIfaceCfg__bindgen_ty_1,
     pub wake_delay: u16,
-    pub rx_retries: ::std::os::raw::c_int,
-    pub cfg_data: *mut ::std::os::raw::c_void,
+    pub rx_retries: ::core::ffi::c_int,
+    pub cfg_data: *mut ::core::ffi::c_void,
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -1136,7 +1136,7 @@ pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1 {
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>(),
+        ::core::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>(),
         8usize,
         concat!(
             "Size of: ",
@@ -1144,7 +1144,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1() {
         )
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>(),
+        ::core::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>(),
         4usize,
         concat!(
             "Alignment of ",
@@ -1153,7 +1153,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>())).slave_address
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>())).slave_address
                 as *const _ as usize
         },
         0usize,
@@ -1166,7 +1166,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>())).bus as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>())).bus as *const _
                 as usize
         },
         1usize,
@@ -1179,7 +1179,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>())).baud as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_1>())).baud as *const _
                 as usize
         },
         4usize,
@@ -1200,7 +1200,7 @@ pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2 {
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2>(),
+        ::core::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2>(),
         1usize,
         concat!(
             "Size of: ",
@@ -1208,7 +1208,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2() {
         )
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2>(),
+        ::core::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2>(),
         1usize,
         concat!(
             "Alignment of ",
@@ -1217,7 +1217,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2>())).bus as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2>())).bus as *const _
                 as usize
         },
         0usize,
@@ -1232,7 +1232,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2() {
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3 {
-    pub port: ::std::os::raw::c_int,
+    pub port: ::core::ffi::c_int,
     pub baud: u32,
     pub wordsize: u8,
     pub parity: u8,
@@ -1242,7 +1242,7 @@ pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3 {
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>(),
+        ::core::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>(),
         12usize,
         concat!(
             "Size of: ",
@@ -1250,7 +1250,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
         )
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>(),
+        ::core::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>(),
         4usize,
         concat!(
             "Alignment of ",
@@ -1259,7 +1259,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).port as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).port as *const _
                 as usize
         },
         0usize,
@@ -1272,7 +1272,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).baud as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).baud as *const _
                 as usize
         },
         4usize,
@@ -1285,7 +1285,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).wordsize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).wordsize
                 as *const _ as usize
         },
         8usize,
@@ -1298,7 +1298,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).parity as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).parity as *const _
                 as usize
         },
         9usize,
@@ -1311,7 +1311,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).stopbits
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3>())).stopbits
                 as *const _ as usize
         },
         10usize,
@@ -1326,7 +1326,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3() {
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4 {
-    pub idx: ::std::os::raw::c_int,
+    pub idx: ::core::ffi::c_int,
     pub dev_interface: ATCAKitType,
     pub dev_identity: u8,
     pub vid: u32,
@@ -1337,7 +1337,7 @@ pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4 {
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>(),
+        ::core::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>(),
         24usize,
         concat!(
             "Size of: ",
@@ -1345,7 +1345,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
         )
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>(),
+        ::core::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>(),
         4usize,
         concat!(
             "Alignment of ",
@@ -1354,7 +1354,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).idx as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).idx as *const _
                 as usize
         },
         0usize,
@@ -1367,7 +1367,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).dev_interface
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).dev_interface
                 as *const _ as usize
         },
         4usize,
@@ -1380,7 +1380,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).dev_identity
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).dev_identity
                 as *const _ as usize
         },
         8usize,
@@ -1393,7 +1393,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).vid as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).vid as *const _
                 as usize
         },
         12usize,
@@ -1406,7 +1406,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).pid as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).pid as *const _
                 as usize
         },
         16usize,
@@ -1419,7 +1419,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).packetsize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4>())).packetsize
                 as *const _ as usize
         },
         20usize,
@@ -1434,47 +1434,47 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4() {
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5 {
-    pub halinit: ::std::option::Option<
+    pub halinit: ::core::option::Option<
         unsafe extern "C" fn(
-            hal: *mut ::std::os::raw::c_void,
-            cfg: *mut ::std::os::raw::c_void,
+            hal: *mut ::core::ffi::c_void,
+            cfg: *mut ::core::ffi::c_void,
         ) -> ATCA_STATUS,
     >,
-    pub halpostinit: ::std::option::Option<
-        unsafe extern "C" fn(iface: *mut ::std::os::raw::c_void) -> ATCA_STATUS,
+    pub halpostinit: ::core::option::Option<
+        unsafe extern "C" fn(iface: *mut ::core::ffi::c_void) -> ATCA_STATUS,
     >,
-    pub halsend: ::std::option::Option<
+    pub halsend: ::core::option::Option<
         unsafe extern "C" fn(
-            iface: *mut ::std::os::raw::c_void,
+            iface: *mut ::core::ffi::c_void,
             txdata: *mut u8,
-            txlength: ::std::os::raw::c_int,
+            txlength: ::core::ffi::c_int,
         ) -> ATCA_STATUS,
     >,
-    pub halreceive: ::std::option::Option<
+    pub halreceive: ::core::option::Option<
         unsafe extern "C" fn(
-            iface: *mut ::std::os::raw::c_void,
+            iface: *mut ::core::ffi::c_void,
             rxdata: *mut u8,
             rxlength: *mut u16,
         ) -> ATCA_STATUS,
     >,
-    pub halwake: ::std::option::Option<
-        unsafe extern "C" fn(iface: *mut ::std::os::raw::c_void) -> ATCA_STATUS,
+    pub halwake: ::core::option::Option<
+        unsafe extern "C" fn(iface: *mut ::core::ffi::c_void) -> ATCA_STATUS,
     >,
-    pub halidle: ::std::option::Option<
-        unsafe extern "C" fn(iface: *mut ::std::os::raw::c_void) -> ATCA_STATUS,
+    pub halidle: ::core::option::Option<
+        unsafe extern "C" fn(iface: *mut ::core::ffi::c_void) -> ATCA_STATUS,
     >,
-    pub halsleep: ::std::option::Option<
-        unsafe extern "C" fn(iface: *mut ::std::os::raw::c_void) -> ATCA_STATUS,
+    pub halsleep: ::core::option::Option<
+        unsafe extern "C" fn(iface: *mut ::core::ffi::c_void) -> ATCA_STATUS,
     >,
-    pub halrelease: ::std::option::Option<
-        unsafe extern "C" fn(hal_data: *mut ::std::os::raw::c_void) -> ATCA_STATUS,
+    pub halrelease: ::core::option::Option<
+        unsafe extern "C" fn(hal_data: *mut ::core::ffi::c_void) -> ATCA_STATUS,
     >,
 }
 #[allow(deref_nullptr)]
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>(),
+        ::core::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>(),
         64usize,
         concat!(
             "Size of: ",
@@ -1482,7 +1482,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
         )
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>(),
+        ::core::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>(),
         8usize,
         concat!(
             "Alignment of ",
@@ -1491,7 +1491,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halinit as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halinit as *const _
                 as usize
         },
         0usize,
@@ -1504,7 +1504,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halpostinit
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halpostinit
                 as *const _ as usize
         },
         8usize,
@@ -1517,7 +1517,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halsend as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halsend as *const _
                 as usize
         },
         16usize,
@@ -1530,7 +1530,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halreceive
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halreceive
                 as *const _ as usize
         },
         24usize,
@@ -1543,7 +1543,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halwake as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halwake as *const _
                 as usize
         },
         32usize,
@@ -1556,7 +1556,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halidle as *const _
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halidle as *const _
                 as usize
         },
         40usize,
@@ -1569,7 +1569,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halsleep
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halsleep
                 as *const _ as usize
         },
         48usize,
@@ -1582,7 +1582,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halrelease
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5>())).halrelease
                 as *const _ as usize
         },
         56usize,
@@ -1598,18 +1598,18 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5() {
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1>(),
+        ::core::mem::size_of::<ATCAIfaceCfg__bindgen_ty_1>(),
         64usize,
         concat!("Size of: ", stringify!(ATCAIfaceCfg__bindgen_ty_1))
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1>(),
+        ::core::mem::align_of::<ATCAIfaceCfg__bindgen_ty_1>(),
         8usize,
         concat!("Alignment of ", stringify!(ATCAIfaceCfg__bindgen_ty_1))
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcai2c as *const _ as usize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcai2c as *const _ as usize
         },
         0usize,
         concat!(
@@ -1621,7 +1621,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcaswi as *const _ as usize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcaswi as *const _ as usize
         },
         0usize,
         concat!(
@@ -1633,7 +1633,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcauart as *const _ as usize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcauart as *const _ as usize
         },
         0usize,
         concat!(
@@ -1645,7 +1645,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcahid as *const _ as usize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcahid as *const _ as usize
         },
         0usize,
         concat!(
@@ -1657,7 +1657,7 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcacustom as *const _ as usize
+            &(*(::core::ptr::null::<ATCAIfaceCfg__bindgen_ty_1>())).atcacustom as *const _ as usize
         },
         0usize,
         concat!(
@@ -1672,17 +1672,17 @@ fn bindgen_test_layout_ATCAIfaceCfg__bindgen_ty_1() {
 #[test]
 fn bindgen_test_layout_ATCAIfaceCfg() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAIfaceCfg>(),
+        ::core::mem::size_of::<ATCAIfaceCfg>(),
         88usize,
         concat!("Size of: ", stringify!(ATCAIfaceCfg))
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAIfaceCfg>(),
+        ::core::mem::align_of::<ATCAIfaceCfg>(),
         8usize,
         concat!("Alignment of ", stringify!(ATCAIfaceCfg))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAIfaceCfg>())).iface_type as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAIfaceCfg>())).iface_type as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1692,7 +1692,7 @@ fn bindgen_test_layout_ATCAIfaceCfg() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAIfaceCfg>())).devtype as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAIfaceCfg>())).devtype as *const _ as usize },
         4usize,
         concat!(
             "Offset of field: ",
@@ -1702,7 +1702,7 @@ fn bindgen_test_layout_ATCAIfaceCfg() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAIfaceCfg>())).wake_delay as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAIfaceCfg>())).wake_delay as *const _ as usize },
         72usize,
         concat!(
             "Offset of field: ",
@@ -1712,7 +1712,7 @@ fn bindgen_test_layout_ATCAIfaceCfg() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAIfaceCfg>())).rx_retries as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAIfaceCfg>())).rx_retries as *const _ as usize },
         76usize,
         concat!(
             "Offset of field: ",
@@ -1722,7 +1722,7 @@ fn bindgen_test_layout_ATCAIfaceCfg() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAIfaceCfg>())).cfg_data as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAIfaceCfg>())).cfg_data as *const _ as usize },
         80usize,
         concat!(
             "Offset of field: ",
@@ -1740,43 +1740,43 @@ pub type ATCAIface = *mut atca_iface;
 pub struct atca_iface {
     pub mType: ATCAIfaceType,
     pub mIfaceCFG: *mut ATCAIfaceCfg,
-    pub atinit: ::std::option::Option<
+    pub atinit: ::core::option::Option<
         unsafe extern "C" fn(
-            hal: *mut ::std::os::raw::c_void,
+            hal: *mut ::core::ffi::c_void,
             arg1: *mut ATCAIfaceCfg,
         ) -> ATCA_STATUS,
     >,
-    pub atpostinit: ::std::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
-    pub atsend: ::std::option::Option<
+    pub atpostinit: ::core::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
+    pub atsend: ::core::option::Option<
         unsafe extern "C" fn(
             hal: ATCAIface,
             txdata: *mut u8,
-            txlength: ::std::os::raw::c_int,
+            txlength: ::core::ffi::c_int,
         ) -> ATCA_STATUS,
     >,
-    pub atreceive: ::std::option::Option<
+    pub atreceive: ::core::option::Option<
         unsafe extern "C" fn(hal: ATCAIface, rxdata: *mut u8, rxlength: *mut u16) -> ATCA_STATUS,
     >,
-    pub atwake: ::std::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
-    pub atidle: ::std::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
-    pub atsleep: ::std::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
-    pub hal_data: *mut ::std::os::raw::c_void,
+    pub atwake: ::core::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
+    pub atidle: ::core::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
+    pub atsleep: ::core::option::Option<unsafe extern "C" fn(hal: ATCAIface) -> ATCA_STATUS>,
+    pub hal_data: *mut ::core::ffi::c_void,
 }
 #[allow(deref_nullptr)]
 #[test]
 fn bindgen_test_layout_atca_iface() {
     assert_eq!(
-        ::std::mem::size_of::<atca_iface>(),
+        ::core::mem::size_of::<atca_iface>(),
         80usize,
         concat!("Size of: ", stringify!(atca_iface))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_iface>(),
+        ::core::mem::align_of::<atca_iface>(),
         8usize,
         concat!("Alignment of ", stringify!(atca_iface))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).mType as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).mType as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1786,7 +1786,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).mIfaceCFG as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).mIfaceCFG as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -1796,7 +1796,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atinit as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atinit as *const _ as usize },
         16usize,
         concat!(
             "Offset of field: ",
@@ -1806,7 +1806,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atpostinit as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atpostinit as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -1816,7 +1816,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atsend as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atsend as *const _ as usize },
         32usize,
         concat!(
             "Offset of field: ",
@@ -1826,7 +1826,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atreceive as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atreceive as *const _ as usize },
         40usize,
         concat!(
             "Offset of field: ",
@@ -1836,7 +1836,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atwake as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atwake as *const _ as usize },
         48usize,
         concat!(
             "Offset of field: ",
@@ -1846,7 +1846,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atidle as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atidle as *const _ as usize },
         56usize,
         concat!(
             "Offset of field: ",
@@ -1856,7 +1856,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).atsleep as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).atsleep as *const _ as usize },
         64usize,
         concat!(
             "Offset of field: ",
@@ -1866,7 +1866,7 @@ fn bindgen_test_layout_atca_iface() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_iface>())).hal_data as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_iface>())).hal_data as *const _ as usize },
         72usize,
         concat!(
             "Offset of field: ",
@@ -1898,7 +1898,7 @@ extern "C" {
     pub fn atsend(
         ca_iface: ATCAIface,
         txdata: *mut u8,
-        txlength: ::std::os::raw::c_int,
+        txlength: ::core::ffi::c_int,
     ) -> ATCA_STATUS;
 }
 extern "C" {
@@ -1917,7 +1917,7 @@ extern "C" {
     pub fn atgetifacecfg(ca_iface: ATCAIface) -> *mut ATCAIfaceCfg;
 }
 extern "C" {
-    pub fn atgetifacehaldat(ca_iface: ATCAIface) -> *mut ::std::os::raw::c_void;
+    pub fn atgetifacehaldat(ca_iface: ATCAIface) -> *mut ::core::ffi::c_void;
 }
 #[doc = " \\brief an intermediary data structure to allow the HAL layer to point the standard API functions"]
 #[doc = "used by the upper layers to the HAL implementation for the interface.  This isolates the upper layers"]
@@ -1925,46 +1925,46 @@ extern "C" {
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct ATCAHAL_t {
-    pub halinit: ::std::option::Option<
+    pub halinit: ::core::option::Option<
         unsafe extern "C" fn(
-            hal: *mut ::std::os::raw::c_void,
+            hal: *mut ::core::ffi::c_void,
             cfg: *mut ATCAIfaceCfg,
         ) -> ATCA_STATUS,
     >,
-    pub halpostinit: ::std::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
-    pub halsend: ::std::option::Option<
+    pub halpostinit: ::core::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
+    pub halsend: ::core::option::Option<
         unsafe extern "C" fn(
             iface: ATCAIface,
             txdata: *mut u8,
-            txlength: ::std::os::raw::c_int,
+            txlength: ::core::ffi::c_int,
         ) -> ATCA_STATUS,
     >,
-    pub halreceive: ::std::option::Option<
+    pub halreceive: ::core::option::Option<
         unsafe extern "C" fn(iface: ATCAIface, rxdata: *mut u8, rxlength: *mut u16) -> ATCA_STATUS,
     >,
-    pub halwake: ::std::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
-    pub halidle: ::std::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
-    pub halsleep: ::std::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
-    pub halrelease: ::std::option::Option<
-        unsafe extern "C" fn(hal_data: *mut ::std::os::raw::c_void) -> ATCA_STATUS,
+    pub halwake: ::core::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
+    pub halidle: ::core::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
+    pub halsleep: ::core::option::Option<unsafe extern "C" fn(iface: ATCAIface) -> ATCA_STATUS>,
+    pub halrelease: ::core::option::Option<
+        unsafe extern "C" fn(hal_data: *mut ::core::ffi::c_void) -> ATCA_STATUS,
     >,
-    pub hal_data: *mut ::std::os::raw::c_void,
+    pub hal_data: *mut ::core::ffi::c_void,
 }
 #[allow(deref_nullptr)]
 #[test]
 fn bindgen_test_layout_ATCAHAL_t() {
     assert_eq!(
-        ::std::mem::size_of::<ATCAHAL_t>(),
+        ::core::mem::size_of::<ATCAHAL_t>(),
         72usize,
         concat!("Size of: ", stringify!(ATCAHAL_t))
     );
     assert_eq!(
-        ::std::mem::align_of::<ATCAHAL_t>(),
+        ::core::mem::align_of::<ATCAHAL_t>(),
         8usize,
         concat!("Alignment of ", stringify!(ATCAHAL_t))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halinit as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halinit as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -1974,7 +1974,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halpostinit as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halpostinit as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -1984,7 +1984,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halsend as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halsend as *const _ as usize },
         16usize,
         concat!(
             "Offset of field: ",
@@ -1994,7 +1994,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halreceive as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halreceive as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -2004,7 +2004,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halwake as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halwake as *const _ as usize },
         32usize,
         concat!(
             "Offset of field: ",
@@ -2014,7 +2014,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halidle as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halidle as *const _ as usize },
         40usize,
         concat!(
             "Offset of field: ",
@@ -2024,7 +2024,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halsleep as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halsleep as *const _ as usize },
         48usize,
         concat!(
             "Offset of field: ",
@@ -2034,7 +2034,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).halrelease as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).halrelease as *const _ as usize },
         56usize,
         concat!(
             "Offset of field: ",
@@ -2044,7 +2044,7 @@ fn bindgen_test_layout_ATCAHAL_t() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<ATCAHAL_t>())).hal_data as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<ATCAHAL_t>())).hal_data as *const _ as usize },
         64usize,
         concat!(
             "Offset of field: ",
@@ -2060,15 +2060,15 @@ extern "C" {
 extern "C" {
     pub fn hal_iface_release(
         arg1: ATCAIfaceType,
-        hal_data: *mut ::std::os::raw::c_void,
+        hal_data: *mut ::core::ffi::c_void,
     ) -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn hal_check_wake(response: *const u8, response_size: ::std::os::raw::c_int)
+    pub fn hal_check_wake(response: *const u8, response_size: ::core::ffi::c_int)
         -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn hal_i2c_init(hal: *mut ::std::os::raw::c_void, cfg: *mut ATCAIfaceCfg) -> ATCA_STATUS;
+    pub fn hal_i2c_init(hal: *mut ::core::ffi::c_void, cfg: *mut ATCAIfaceCfg) -> ATCA_STATUS;
 }
 extern "C" {
     pub fn hal_i2c_post_init(iface: ATCAIface) -> ATCA_STATUS;
@@ -2077,7 +2077,7 @@ extern "C" {
     pub fn hal_i2c_send(
         iface: ATCAIface,
         txdata: *mut u8,
-        txlength: ::std::os::raw::c_int,
+        txlength: ::core::ffi::c_int,
     ) -> ATCA_STATUS;
 }
 extern "C" {
@@ -2093,19 +2093,19 @@ extern "C" {
     pub fn hal_i2c_sleep(iface: ATCAIface) -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn hal_i2c_release(hal_data: *mut ::std::os::raw::c_void) -> ATCA_STATUS;
+    pub fn hal_i2c_release(hal_data: *mut ::core::ffi::c_void) -> ATCA_STATUS;
 }
 extern "C" {
     pub fn hal_i2c_discover_buses(
-        i2c_buses: *mut ::std::os::raw::c_int,
-        max_buses: ::std::os::raw::c_int,
+        i2c_buses: *mut ::core::ffi::c_int,
+        max_buses: ::core::ffi::c_int,
     ) -> ATCA_STATUS;
 }
 extern "C" {
     pub fn hal_i2c_discover_devices(
-        bus_num: ::std::os::raw::c_int,
+        bus_num: ::core::ffi::c_int,
         cfg: *mut ATCAIfaceCfg,
-        found: *mut ::std::os::raw::c_int,
+        found: *mut ::core::ffi::c_int,
     ) -> ATCA_STATUS;
 }
 extern "C" {
@@ -2127,18 +2127,18 @@ extern "C" {
 extern "C" {
     #[doc = " \\brief Optional hal interfaces"]
     pub fn hal_create_mutex(
-        ppMutex: *mut *mut ::std::os::raw::c_void,
-        pName: *mut ::std::os::raw::c_char,
+        ppMutex: *mut *mut ::core::ffi::c_void,
+        pName: *mut ::core::ffi::c_char,
     ) -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn hal_destroy_mutex(pMutex: *mut ::std::os::raw::c_void) -> ATCA_STATUS;
+    pub fn hal_destroy_mutex(pMutex: *mut ::core::ffi::c_void) -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn hal_lock_mutex(pMutex: *mut ::std::os::raw::c_void) -> ATCA_STATUS;
+    pub fn hal_lock_mutex(pMutex: *mut ::core::ffi::c_void) -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn hal_unlock_mutex(pMutex: *mut ::std::os::raw::c_void) -> ATCA_STATUS;
+    pub fn hal_unlock_mutex(pMutex: *mut ::core::ffi::c_void) -> ATCA_STATUS;
 }
 #[doc = " \\brief atca_device is the C object backing ATCADevice.  See the"]
 #[doc = "         atca_device.h file for details on the ATCADevice methods."]
@@ -2154,17 +2154,17 @@ pub struct atca_device {
 #[test]
 fn bindgen_test_layout_atca_device() {
     assert_eq!(
-        ::std::mem::size_of::<atca_device>(),
+        ::core::mem::size_of::<atca_device>(),
         16usize,
         concat!("Size of: ", stringify!(atca_device))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_device>(),
+        ::core::mem::align_of::<atca_device>(),
         8usize,
         concat!("Alignment of ", stringify!(atca_device))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_device>())).mCommands as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_device>())).mCommands as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -2174,7 +2174,7 @@ fn bindgen_test_layout_atca_device() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_device>())).mIface as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_device>())).mIface as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -2237,17 +2237,17 @@ pub struct atcac_sha2_256_ctx {
 #[test]
 fn bindgen_test_layout_atcac_sha2_256_ctx() {
     assert_eq!(
-        ::std::mem::size_of::<atcac_sha2_256_ctx>(),
+        ::core::mem::size_of::<atcac_sha2_256_ctx>(),
         192usize,
         concat!("Size of: ", stringify!(atcac_sha2_256_ctx))
     );
     assert_eq!(
-        ::std::mem::align_of::<atcac_sha2_256_ctx>(),
+        ::core::mem::align_of::<atcac_sha2_256_ctx>(),
         4usize,
         concat!("Alignment of ", stringify!(atcac_sha2_256_ctx))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atcac_sha2_256_ctx>())).pad as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atcac_sha2_256_ctx>())).pad as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -2258,33 +2258,33 @@ fn bindgen_test_layout_atcac_sha2_256_ctx() {
     );
 }
 extern "C" {
-    pub fn atcac_sw_sha2_256_init(ctx: *mut atcac_sha2_256_ctx) -> ::std::os::raw::c_int;
+    pub fn atcac_sw_sha2_256_init(ctx: *mut atcac_sha2_256_ctx) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn atcac_sw_sha2_256_update(
         ctx: *mut atcac_sha2_256_ctx,
         data: *const u8,
         data_size: size_t,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn atcac_sw_sha2_256_finish(
         ctx: *mut atcac_sha2_256_ctx,
         digest: *mut u8,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub fn atcac_sw_sha2_256(
         data: *const u8,
         data_size: size_t,
         digest: *mut u8,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     pub static mut _gDevice: ATCADevice;
 }
 extern "C" {
-    pub fn atcab_version(ver_str: *mut ::std::os::raw::c_char) -> ATCA_STATUS;
+    pub fn atcab_version(ver_str: *mut ::core::ffi::c_char) -> ATCA_STATUS;
 }
 extern "C" {
     pub fn atcab_init(cfg: *mut ATCAIfaceCfg) -> ATCA_STATUS;
@@ -2316,7 +2316,7 @@ extern "C" {
 extern "C" {
     pub fn atcab_cfg_discover(
         cfg_array: *mut ATCAIfaceCfg,
-        max: ::std::os::raw::c_int,
+        max: ::core::ffi::c_int,
     ) -> ATCA_STATUS;
 }
 extern "C" {
@@ -2332,7 +2332,7 @@ extern "C" {
     pub fn atcab_get_zone_size(zone: u8, slot: u16, size: *mut size_t) -> ATCA_STATUS;
 }
 extern "C" {
-    pub static mut atca_basic_aes_gcm_version: *const ::std::os::raw::c_char;
+    pub static mut atca_basic_aes_gcm_version: *const ::core::ffi::c_char;
 }
 #[doc = " Context structure for AES GCM operations."]
 #[repr(C)]
@@ -2367,17 +2367,17 @@ pub struct atca_aes_gcm_ctx {
 #[test]
 fn bindgen_test_layout_atca_aes_gcm_ctx() {
     assert_eq!(
-        ::std::mem::size_of::<atca_aes_gcm_ctx>(),
+        ::core::mem::size_of::<atca_aes_gcm_ctx>(),
         128usize,
         concat!("Size of: ", stringify!(atca_aes_gcm_ctx))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_aes_gcm_ctx>(),
+        ::core::mem::align_of::<atca_aes_gcm_ctx>(),
         4usize,
         concat!("Alignment of ", stringify!(atca_aes_gcm_ctx))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).key_id as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).key_id as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -2387,7 +2387,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).key_block as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).key_block as *const _ as usize },
         2usize,
         concat!(
             "Offset of field: ",
@@ -2397,7 +2397,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).cb as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).cb as *const _ as usize },
         3usize,
         concat!(
             "Offset of field: ",
@@ -2407,7 +2407,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).data_size as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).data_size as *const _ as usize },
         20usize,
         concat!(
             "Offset of field: ",
@@ -2417,7 +2417,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).aad_size as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).aad_size as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -2427,7 +2427,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).h as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).h as *const _ as usize },
         28usize,
         concat!(
             "Offset of field: ",
@@ -2437,7 +2437,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).j0 as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).j0 as *const _ as usize },
         44usize,
         concat!(
             "Offset of field: ",
@@ -2447,7 +2447,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).y as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).y as *const _ as usize },
         60usize,
         concat!(
             "Offset of field: ",
@@ -2457,7 +2457,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).partial_aad as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).partial_aad as *const _ as usize },
         76usize,
         concat!(
             "Offset of field: ",
@@ -2468,7 +2468,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).partial_aad_size as *const _ as usize
+            &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).partial_aad_size as *const _ as usize
         },
         92usize,
         concat!(
@@ -2479,7 +2479,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).enc_cb as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).enc_cb as *const _ as usize },
         96usize,
         concat!(
             "Offset of field: ",
@@ -2490,7 +2490,7 @@ fn bindgen_test_layout_atca_aes_gcm_ctx() {
     );
     assert_eq!(
         unsafe {
-            &(*(::std::ptr::null::<atca_aes_gcm_ctx>())).ciphertext_block as *const _ as usize
+            &(*(::core::ptr::null::<atca_aes_gcm_ctx>())).ciphertext_block as *const _ as usize
         },
         112usize,
         concat!(
@@ -2596,17 +2596,17 @@ pub struct atca_aes_cbc_ctx {
 #[test]
 fn bindgen_test_layout_atca_aes_cbc_ctx() {
     assert_eq!(
-        ::std::mem::size_of::<atca_aes_cbc_ctx>(),
+        ::core::mem::size_of::<atca_aes_cbc_ctx>(),
         20usize,
         concat!("Size of: ", stringify!(atca_aes_cbc_ctx))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_aes_cbc_ctx>(),
+        ::core::mem::align_of::<atca_aes_cbc_ctx>(),
         2usize,
         concat!("Alignment of ", stringify!(atca_aes_cbc_ctx))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_cbc_ctx>())).key_id as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_cbc_ctx>())).key_id as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -2616,7 +2616,7 @@ fn bindgen_test_layout_atca_aes_cbc_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_cbc_ctx>())).key_block as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_cbc_ctx>())).key_block as *const _ as usize },
         2usize,
         concat!(
             "Offset of field: ",
@@ -2626,7 +2626,7 @@ fn bindgen_test_layout_atca_aes_cbc_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_cbc_ctx>())).ciphertext as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_cbc_ctx>())).ciphertext as *const _ as usize },
         3usize,
         concat!(
             "Offset of field: ",
@@ -2673,17 +2673,17 @@ pub struct atca_aes_cmac_ctx {
 #[test]
 fn bindgen_test_layout_atca_aes_cmac_ctx() {
     assert_eq!(
-        ::std::mem::size_of::<atca_aes_cmac_ctx>(),
+        ::core::mem::size_of::<atca_aes_cmac_ctx>(),
         40usize,
         concat!("Size of: ", stringify!(atca_aes_cmac_ctx))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_aes_cmac_ctx>(),
+        ::core::mem::align_of::<atca_aes_cmac_ctx>(),
         4usize,
         concat!("Alignment of ", stringify!(atca_aes_cmac_ctx))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_cmac_ctx>())).cbc_ctx as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_cmac_ctx>())).cbc_ctx as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -2693,7 +2693,7 @@ fn bindgen_test_layout_atca_aes_cmac_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_cmac_ctx>())).block_size as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_cmac_ctx>())).block_size as *const _ as usize },
         20usize,
         concat!(
             "Offset of field: ",
@@ -2703,7 +2703,7 @@ fn bindgen_test_layout_atca_aes_cmac_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_cmac_ctx>())).block as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_cmac_ctx>())).block as *const _ as usize },
         24usize,
         concat!(
             "Offset of field: ",
@@ -2751,17 +2751,17 @@ pub struct atca_aes_ctr_ctx {
 #[test]
 fn bindgen_test_layout_atca_aes_ctr_ctx() {
     assert_eq!(
-        ::std::mem::size_of::<atca_aes_ctr_ctx>(),
+        ::core::mem::size_of::<atca_aes_ctr_ctx>(),
         20usize,
         concat!("Size of: ", stringify!(atca_aes_ctr_ctx))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_aes_ctr_ctx>(),
+        ::core::mem::align_of::<atca_aes_ctr_ctx>(),
         2usize,
         concat!("Alignment of ", stringify!(atca_aes_ctr_ctx))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_ctr_ctx>())).key_id as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_ctr_ctx>())).key_id as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -2771,7 +2771,7 @@ fn bindgen_test_layout_atca_aes_ctr_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_ctr_ctx>())).key_block as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_ctr_ctx>())).key_block as *const _ as usize },
         2usize,
         concat!(
             "Offset of field: ",
@@ -2781,7 +2781,7 @@ fn bindgen_test_layout_atca_aes_ctr_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_ctr_ctx>())).cb as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_ctr_ctx>())).cb as *const _ as usize },
         3usize,
         concat!(
             "Offset of field: ",
@@ -2791,7 +2791,7 @@ fn bindgen_test_layout_atca_aes_ctr_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_aes_ctr_ctx>())).counter_size as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_aes_ctr_ctx>())).counter_size as *const _ as usize },
         19usize,
         concat!(
             "Offset of field: ",
@@ -3094,17 +3094,17 @@ pub struct atca_sha256_ctx {
 #[test]
 fn bindgen_test_layout_atca_sha256_ctx() {
     assert_eq!(
-        ::std::mem::size_of::<atca_sha256_ctx>(),
+        ::core::mem::size_of::<atca_sha256_ctx>(),
         136usize,
         concat!("Size of: ", stringify!(atca_sha256_ctx))
     );
     assert_eq!(
-        ::std::mem::align_of::<atca_sha256_ctx>(),
+        ::core::mem::align_of::<atca_sha256_ctx>(),
         4usize,
         concat!("Alignment of ", stringify!(atca_sha256_ctx))
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_sha256_ctx>())).total_msg_size as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_sha256_ctx>())).total_msg_size as *const _ as usize },
         0usize,
         concat!(
             "Offset of field: ",
@@ -3114,7 +3114,7 @@ fn bindgen_test_layout_atca_sha256_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_sha256_ctx>())).block_size as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_sha256_ctx>())).block_size as *const _ as usize },
         4usize,
         concat!(
             "Offset of field: ",
@@ -3124,7 +3124,7 @@ fn bindgen_test_layout_atca_sha256_ctx() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::std::ptr::null::<atca_sha256_ctx>())).block as *const _ as usize },
+        unsafe { &(*(::core::ptr::null::<atca_sha256_ctx>())).block as *const _ as usize },
         8usize,
         concat!(
             "Offset of field: ",
@@ -3332,7 +3332,7 @@ extern "C" {
     pub fn atcab_bin2hex(
         bin: *const u8,
         bin_size: size_t,
-        hex: *mut ::std::os::raw::c_char,
+        hex: *mut ::core::ffi::c_char,
         hex_size: *mut size_t,
     ) -> ATCA_STATUS;
 }
@@ -3340,7 +3340,7 @@ extern "C" {
     pub fn atcab_bin2hex_(
         bin: *const u8,
         bin_size: size_t,
-        hex: *mut ::std::os::raw::c_char,
+        hex: *mut ::core::ffi::c_char,
         hex_size: *mut size_t,
         is_pretty: bool,
         is_space: bool,
@@ -3349,7 +3349,7 @@ extern "C" {
 }
 extern "C" {
     pub fn atcab_hex2bin(
-        ascii_hex: *const ::std::os::raw::c_char,
+        ascii_hex: *const ::core::ffi::c_char,
         ascii_hex_len: size_t,
         binary: *mut u8,
         bin_len: *mut size_t,
@@ -3357,7 +3357,7 @@ extern "C" {
 }
 extern "C" {
     pub fn atcab_hex2bin_(
-        hex: *const ::std::os::raw::c_char,
+        hex: *const ::core::ffi::c_char,
         hex_size: size_t,
         bin: *mut u8,
         bin_size: *mut size_t,
@@ -3369,48 +3369,48 @@ extern "C" {
 }
 extern "C" {
     pub fn atcab_printbin_label(
-        label: *const ::std::os::raw::c_char,
+        label: *const ::core::ffi::c_char,
         binary: *mut u8,
         bin_len: size_t,
     ) -> ATCA_STATUS;
 }
 extern "C" {
     pub fn packHex(
-        ascii_hex: *const ::std::os::raw::c_char,
+        ascii_hex: *const ::core::ffi::c_char,
         ascii_hex_len: size_t,
-        packed_hex: *mut ::std::os::raw::c_char,
+        packed_hex: *mut ::core::ffi::c_char,
         packed_len: *mut size_t,
     ) -> ATCA_STATUS;
 }
 extern "C" {
-    pub fn isDigit(c: ::std::os::raw::c_char) -> bool;
+    pub fn isDigit(c: ::core::ffi::c_char) -> bool;
 }
 extern "C" {
-    pub fn isWhiteSpace(c: ::std::os::raw::c_char) -> bool;
+    pub fn isWhiteSpace(c: ::core::ffi::c_char) -> bool;
 }
 extern "C" {
-    pub fn isAlpha(c: ::std::os::raw::c_char) -> bool;
+    pub fn isAlpha(c: ::core::ffi::c_char) -> bool;
 }
 extern "C" {
-    pub fn isHexAlpha(c: ::std::os::raw::c_char) -> bool;
+    pub fn isHexAlpha(c: ::core::ffi::c_char) -> bool;
 }
 extern "C" {
-    pub fn isHex(c: ::std::os::raw::c_char) -> bool;
+    pub fn isHex(c: ::core::ffi::c_char) -> bool;
 }
 extern "C" {
-    pub fn isHexDigit(c: ::std::os::raw::c_char) -> bool;
+    pub fn isHexDigit(c: ::core::ffi::c_char) -> bool;
 }
 extern "C" {
-    pub fn isBase64(c: ::std::os::raw::c_char, rules: *const u8) -> bool;
+    pub fn isBase64(c: ::core::ffi::c_char, rules: *const u8) -> bool;
 }
 extern "C" {
-    pub fn isBase64Digit(c: ::std::os::raw::c_char, rules: *const u8) -> bool;
+    pub fn isBase64Digit(c: ::core::ffi::c_char, rules: *const u8) -> bool;
 }
 extern "C" {
-    pub fn base64Index(c: ::std::os::raw::c_char, rules: *const u8) -> u8;
+    pub fn base64Index(c: ::core::ffi::c_char, rules: *const u8) -> u8;
 }
 extern "C" {
-    pub fn base64Char(id: u8, rules: *const u8) -> ::std::os::raw::c_char;
+    pub fn base64Char(id: u8, rules: *const u8) -> ::core::ffi::c_char;
 }
 extern "C" {
     pub static mut atcab_b64rules_default: [u8; 4usize];
@@ -3423,7 +3423,7 @@ extern "C" {
 }
 extern "C" {
     pub fn atcab_base64decode_(
-        encoded: *const ::std::os::raw::c_char,
+        encoded: *const ::core::ffi::c_char,
         encoded_size: size_t,
         data: *mut u8,
         data_size: *mut size_t,
@@ -3432,7 +3432,7 @@ extern "C" {
 }
 extern "C" {
     pub fn atcab_base64decode(
-        encoded: *const ::std::os::raw::c_char,
+        encoded: *const ::core::ffi::c_char,
         encoded_size: size_t,
         data: *mut u8,
         data_size: *mut size_t,
@@ -3442,7 +3442,7 @@ extern "C" {
     pub fn atcab_base64encode_(
         data: *const u8,
         data_size: size_t,
-        encoded: *mut ::std::os::raw::c_char,
+        encoded: *mut ::core::ffi::c_char,
         encoded_size: *mut size_t,
         rules: *const u8,
     ) -> ATCA_STATUS;
@@ -3451,7 +3451,7 @@ extern "C" {
     pub fn atcab_base64encode(
         data: *const u8,
         data_size: size_t,
-        encoded: *mut ::std::os::raw::c_char,
+        encoded: *mut ::core::ffi::c_char,
         encoded_size: *mut size_t,
     ) -> ATCA_STATUS;
 }
@@ -0,0 +1,172 @@
+// Criterion benchmarks of the commands most sensitive to the overhead of the wrapper
+// (copies of the buffers, locking), run against SoftwareBackend, AteccSimulator
+// and, if one is configured, an ATECC chip.
+//
+// Usage: cargo bench --features bench
+// The chip is described by the file named by ATECC_CONFIG and the other ATECC_*
+// environment variables, as read by Config::from_env(). Keys are never generated
+// on the chip: signing uses the private key already stored in ATECC_BENCH_ECC_SLOT
+// (slot 0 by default) and AES uses a key loaded into TempKey.
+
+use std::env;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_cryptoauthlib::config::Config;
+use rust_cryptoauthlib::{
+    setup_atecc_device, AtcaIfaceCfg, AtcaStatus, AteccDevice, CipherAlgorithm, CipherParam,
+    KeyType, VerifyMode, ATCA_AES_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT,
+};
+
+const DEFAULT_ECC_SLOT: u8 = 0;
+/// Slot number selecting TempKey as the AES key
+const TEMP_KEY_SLOT: u8 = ATCA_ATECC_SLOTS_COUNT;
+const SHA_SIZES: [usize; 3] = [64, 1024, 4096];
+const AES_SIZE: usize = 4096;
+
+type CipherMode = fn(CipherParam) -> CipherAlgorithm;
+
+/// A device to benchmark and the slot of its private key
+struct Backend {
+    name: &'static str,
+    device: AteccDevice,
+    ecc_slot: u8,
+}
+
+fn software_backend(name: &'static str, iface_type: &str) -> Backend {
+    let iface_cfg = AtcaIfaceCfg::default()
+        .set_iface_type(iface_type.to_owned())
+        .set_devtype("atecc608a".to_owned());
+    let device = setup_atecc_device(iface_cfg).expect("Could not create a software backend");
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, DEFAULT_ECC_SLOT),
+        AtcaStatus::AtcaSuccess
+    );
+    Backend {
+        name,
+        device,
+        ecc_slot: DEFAULT_ECC_SLOT,
+    }
+}
+
+/// The chip described by the environment, if there is one
+fn hardware_backend() -> Option<Backend> {
+    if env::var("ATECC_CONFIG").is_err() && env::var("ATECC_IFACE").is_err() {
+        return None;
+    }
+    let iface_cfg = Config::from_env()
+        .and_then(|config| config.iface_cfg())
+        .unwrap_or_else(|err| panic!("Invalid configuration of the chip: {}", err));
+    let device = setup_atecc_device(iface_cfg)
+        .unwrap_or_else(|err| panic!("Could not set up the chip: {}", err));
+    let ecc_slot = match env::var("ATECC_BENCH_ECC_SLOT") {
+        Ok(slot) => slot
+            .parse()
+            .expect("ATECC_BENCH_ECC_SLOT is not a slot number"),
+        Err(_) => DEFAULT_ECC_SLOT,
+    };
+    Some(Backend {
+        name: "hardware",
+        device,
+        ecc_slot,
+    })
+}
+
+fn backends() -> Vec<Backend> {
+    let mut backends = vec![
+        software_backend("software", "software"),
+        software_backend("simulator", "simulator"),
+    ];
+    backends.extend(hardware_backend());
+    backends
+}
+
+fn bench_random(c: &mut Criterion, backend: &Backend) {
+    let mut rand_out = [0; 32];
+    c.bench_function(&format!("{}/random", backend.name), |b| {
+        b.iter(|| backend.device.random_into(&mut rand_out))
+    });
+}
+
+fn bench_sha(c: &mut Criterion, backend: &Backend) {
+    let mut group = c.benchmark_group(format!("{}/sha", backend.name));
+    for size in SHA_SIZES.iter() {
+        let message = vec![0x5A; *size];
+        group.throughput(Throughput::Bytes(*size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &message, |b, message| {
+            b.iter(|| backend.device.sha256(message))
+        });
+    }
+    group.finish();
+}
+
+fn bench_sign_verify(c: &mut Criterion, backend: &Backend) {
+    let digest = [0xA5; 32];
+    let (public_key, signature) = match (
+        backend.device.public_key(backend.ecc_slot),
+        backend.device.sign_digest(backend.ecc_slot, &digest),
+    ) {
+        (Ok(public_key), Ok(signature)) => (public_key, signature),
+        _ => {
+            eprintln!(
+                "{}: no private key in slot {}, sign and verify skipped",
+                backend.name, backend.ecc_slot
+            );
+            return;
+        }
+    };
+    c.bench_function(&format!("{}/sign", backend.name), |b| {
+        b.iter(|| backend.device.sign_digest(backend.ecc_slot, &digest))
+    });
+    c.bench_function(&format!("{}/verify", backend.name), |b| {
+        b.iter(|| {
+            backend.device.verify_hash(
+                VerifyMode::External(public_key.to_vec()),
+                &digest,
+                &signature,
+            )
+        })
+    });
+}
+
+fn bench_aes(c: &mut Criterion, backend: &Backend) {
+    if !backend.device.is_aes_enabled() {
+        return;
+    }
+    let param = || CipherParam {
+        iv: Some([0x0F; ATCA_AES_KEY_SIZE]),
+        counter_size: Some(4),
+        key: Some(vec![0x2B; ATCA_AES_KEY_SIZE]),
+    };
+    let modes: [(&str, CipherMode); 5] = [
+        ("ecb", CipherAlgorithm::Ecb),
+        ("cbc", CipherAlgorithm::Cbc),
+        ("ctr", CipherAlgorithm::Ctr),
+        ("cfb", CipherAlgorithm::Cfb),
+        ("ofb", CipherAlgorithm::Ofb),
+    ];
+    let mut group = c.benchmark_group(format!("{}/aes", backend.name));
+    group.throughput(Throughput::Bytes(AES_SIZE as u64));
+    for (name, mode) in modes.iter() {
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                let mut data = vec![0x3C; AES_SIZE];
+                backend
+                    .device
+                    .cipher_encrypt(mode(param()), TEMP_KEY_SLOT, &mut data)
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_backends(c: &mut Criterion) {
+    for backend in backends().iter() {
+        bench_random(c, backend);
+        bench_sha(c, backend);
+        bench_sign_verify(c, backend);
+        bench_aes(c, backend);
+    }
+}
+
+criterion_group!(benches, bench_backends);
+criterion_main!(benches);
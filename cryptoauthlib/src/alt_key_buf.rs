@@ -0,0 +1,72 @@
+use super::{AtcaDeviceType, AtcaStatus, AteccDeviceTrait, KdfAlgorithm, KdfSource, KdfTarget};
+use super::{NonceTarget, ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_KEY_SIZE};
+use alloc::vec::Vec;
+
+/// Number of 16-byte AES keys the Alternate Key Buffer holds
+const ALT_KEY_BUF_AES_KEYS: u8 = (ATCA_KEY_SIZE / ATCA_AES_KEY_SIZE) as u8;
+
+/// Loads a 32-byte key into the Alternate Key Buffer of an ATECC608x,
+/// with a Nonce command in pass-through mode. The key is kept in the chip
+/// until it goes to sleep and is used by alt_key_buf_aes_encrypt_block()
+/// and alt_key_buf_kdf() without occupying a slot or TempKey
+pub fn load_alt_key_buf(device: &dyn AteccDeviceTrait, key: &[u8]) -> AtcaStatus {
+    if device.get_device_type() != AtcaDeviceType::ATECC608A {
+        return AtcaStatus::AtcaBadParam;
+    }
+    if key.len() != ATCA_KEY_SIZE {
+        return AtcaStatus::AtcaInvalidSize;
+    }
+    device.nonce(NonceTarget::AltKeyBuf, key)
+} // load_alt_key_buf()
+
+/// AES-128 encryption of a 16-byte block with a key held in the Alternate Key Buffer,
+/// 'key_block' selects the first (0) or the second (1) half of the buffer.
+/// The AES command cannot read the buffer, the block is encrypted by the KDF command
+/// in its AES mode, so the result is returned encrypted with the IO protection key
+/// (and decrypted on the host side) if the chip requires encrypted KDF output
+pub fn alt_key_buf_aes_encrypt_block(
+    device: &dyn AteccDeviceTrait,
+    key_block: u8,
+    input: &[u8],
+) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+    if key_block >= ALT_KEY_BUF_AES_KEYS {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if input.len() != ATCA_AES_DATA_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let algorithm = KdfAlgorithm::Aes {
+        key_location: key_block,
+    };
+    let output = alt_key_buf_kdf(device, algorithm, KdfTarget::Output, input)?;
+    if output.len() != ATCA_AES_DATA_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let mut block = [0; ATCA_AES_DATA_SIZE];
+    block.copy_from_slice(&output);
+    Ok(block)
+} // alt_key_buf_aes_encrypt_block()
+
+/// Derives a key with the KDF command from the key held in the Alternate Key Buffer,
+/// as kdf() does for KdfSource::AltKeyBuf. The buffer holds 32 bytes, so the PRF
+/// source key cannot be longer and the AES key must be one of its two halves
+pub fn alt_key_buf_kdf(
+    device: &dyn AteccDeviceTrait,
+    algorithm: KdfAlgorithm,
+    target: KdfTarget,
+    message: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    if device.get_device_type() != AtcaDeviceType::ATECC608A {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    match algorithm {
+        KdfAlgorithm::Prf { key_length, .. } if key_length > ATCA_KEY_SIZE => {
+            return Err(AtcaStatus::AtcaInvalidSize)
+        }
+        KdfAlgorithm::Aes { key_location } if key_location >= ALT_KEY_BUF_AES_KEYS => {
+            return Err(AtcaStatus::AtcaBadParam)
+        }
+        _ => (),
+    }
+    device.kdf(algorithm, KdfSource::AltKeyBuf, target, message)
+} // alt_key_buf_kdf()
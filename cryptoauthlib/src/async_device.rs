@@ -0,0 +1,172 @@
+use futures_channel::oneshot;
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+
+use super::{AtcaStatus, AteccDevice, AteccDeviceTrait, EcdhOutput, KeyType, SignMode, VerifyMode};
+
+type Job = Box<dyn FnOnce(&dyn AteccDeviceTrait) + Send>;
+
+/// ATECC device for async code. The commands, which take up to tens of milliseconds,
+/// are executed one by one on a worker thread owning the device,
+/// so they do not block the executor the futures are polled by.
+/// The futures do not depend on any particular async runtime.
+/// If the worker is gone, the operations fail with AtcaFuncFail.
+pub struct AsyncAteccDevice {
+    jobs: Mutex<Option<Sender<Job>>>,
+    worker: Option<JoinHandle<AteccDevice>>,
+}
+
+impl AsyncAteccDevice {
+    /// Moves the device to a new worker thread
+    pub fn new(device: AteccDevice) -> AsyncAteccDevice {
+        let (jobs, queue) = channel::<Job>();
+        let worker = thread::spawn(move || {
+            for job in queue {
+                job(device.as_ref());
+            }
+            device
+        });
+        AsyncAteccDevice {
+            jobs: Mutex::new(Some(jobs)),
+            worker: Some(worker),
+        }
+    } // AsyncAteccDevice::new()
+
+    /// Stops the worker thread and returns the device
+    pub fn into_inner(mut self) -> Result<AteccDevice, AtcaStatus> {
+        self.stop().ok_or(AtcaStatus::AtcaFuncFail)
+    } // AsyncAteccDevice::into_inner()
+
+    fn stop(&mut self) -> Option<AteccDevice> {
+        if let Ok(mut jobs) = self.jobs.lock() {
+            jobs.take();
+        }
+        self.worker.take()?.join().ok()
+    }
+
+    /// Runs an operation on the device on the worker thread
+    pub async fn execute<T, F>(&self, operation: F) -> Result<T, AtcaStatus>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn AteccDeviceTrait) -> T + Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+        let job: Job = Box::new(move |device| {
+            let _ = result_tx.send(operation(device));
+        });
+        {
+            let jobs = self.jobs.lock().map_err(|_| AtcaStatus::AtcaFuncFail)?;
+            match jobs.as_ref() {
+                Some(jobs) => jobs.send(job).map_err(|_| AtcaStatus::AtcaFuncFail)?,
+                None => return Err(AtcaStatus::AtcaFuncFail),
+            }
+        }
+        result_rx.await.map_err(|_| AtcaStatus::AtcaFuncFail)
+    } // AsyncAteccDevice::execute()
+
+    /// Request ATECC to generate a vector of random bytes
+    pub async fn random(&self) -> Result<Vec<u8>, AtcaStatus> {
+        self.execute(|device| {
+            let mut rand_out = Vec::new();
+            status_result(device.random(&mut rand_out), rand_out)
+        })
+        .await?
+    } // AsyncAteccDevice::random()
+
+    /// Request ATECC to compute a message hash (SHA256)
+    pub async fn sha(&self, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let message = message.to_vec();
+        self.execute(move |device| {
+            let mut digest = Vec::new();
+            status_result(device.sha(message, &mut digest), digest)
+        })
+        .await?
+    } // AsyncAteccDevice::sha()
+
+    /// Request ATECC to generate a cryptographic key
+    pub async fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        self.execute(move |device| device.gen_key(key_type, slot_id))
+            .await
+            .unwrap_or_else(|err| err)
+    } // AsyncAteccDevice::gen_key()
+
+    /// Returns the public key of the private key stored in the given slot
+    /// or the public key stored in the slot
+    pub async fn get_public_key(&self, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.execute(move |device| {
+            let mut public_key = Vec::new();
+            status_result(device.get_public_key(slot_id, &mut public_key), public_key)
+        })
+        .await?
+    } // AsyncAteccDevice::get_public_key()
+
+    /// Request ATECC to generate an ECDSA signature
+    pub async fn sign_hash(&self, mode: SignMode, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.execute(move |device| {
+            let mut signature = Vec::new();
+            status_result(device.sign_hash(mode, slot_id, &mut signature), signature)
+        })
+        .await?
+    } // AsyncAteccDevice::sign_hash()
+
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot
+    pub async fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let message = message.to_vec();
+        self.execute(move |device| device.sign_message(slot_id, &message))
+            .await?
+    } // AsyncAteccDevice::sign_message()
+
+    /// Request ATECC to verify ECDSA signature
+    pub async fn verify_hash(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let (hash, signature) = (hash.to_vec(), signature.to_vec());
+        self.execute(move |device| device.verify_hash(mode, &hash, &signature))
+            .await?
+    } // AsyncAteccDevice::verify_hash()
+
+    /// Request ATECC to perform ECDH key agreement with a private key from a slot
+    pub async fn ecdh(&self, slot_id: u8, public_key: &[u8]) -> Result<EcdhOutput, AtcaStatus> {
+        let public_key = public_key.to_vec();
+        self.execute(move |device| device.ecdh(slot_id, &public_key, None))
+            .await?
+    } // AsyncAteccDevice::ecdh()
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset
+    pub async fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.execute(move |device| device.read_slot_data(slot_id, offset, len))
+            .await?
+    } // AsyncAteccDevice::read_slot_data()
+
+    /// Request ATECC to write data to a slot, starting at the given byte offset
+    pub async fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        let data = data.to_vec();
+        self.execute(move |device| device.write_slot_data(slot_id, offset, &data))
+            .await
+            .unwrap_or_else(|err| err)
+    } // AsyncAteccDevice::write_slot_data()
+}
+
+impl Drop for AsyncAteccDevice {
+    /// Waits for the queued operations, the device is dropped with the worker thread
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn status_result(status: AtcaStatus, output: Vec<u8>) -> Result<Vec<u8>, AtcaStatus> {
+    match status {
+        AtcaStatus::AtcaSuccess => Ok(output),
+        err => Err(err),
+    }
+}
@@ -1,4 +1,9 @@
-use super::{AtcaDeviceType, AtcaIface, AtcaIfaceCfg, AtcaIfaceI2c, AtcaIfaceType};
+use super::{
+    AtcaConfigError, AtcaDeviceType, AtcaIface, AtcaIfaceCfg, AtcaIfaceHid, AtcaIfaceI2c,
+    AtcaIfaceSwi, AtcaIfaceType, AtcaIfaceUart, AtcaKitType, AtcaPowerPolicy, AtcaUartParity,
+};
+use super::{ATCA_KIT_HID_PACKET_SIZE, ATCA_KIT_HID_PID, ATCA_KIT_HID_VID, ATCA_KIT_UART_BAUD};
+use alloc::string::String;
 use log::error;
 
 impl Default for AtcaIfaceCfg {
@@ -9,6 +14,10 @@ impl Default for AtcaIfaceCfg {
             iface: None,
             wake_delay: 0u16,
             rx_retries: 0i32,
+            power_policy: AtcaPowerPolicy::Idle,
+            lazy_init: false,
+            #[cfg(feature = "remote")]
+            remote_address: None,
         }
     }
 }
@@ -21,12 +30,27 @@ impl Default for AtcaIface {
     }
 }
 
-impl Default for AtcaIfaceI2c {
-    fn default() -> AtcaIfaceI2c {
-        AtcaIfaceI2c {
-            slave_address: 0u8,
-            bus: 0u8,
-            baud: 0u32,
+impl Default for AtcaIfaceHid {
+    fn default() -> AtcaIfaceHid {
+        AtcaIfaceHid {
+            idx: 0i32,
+            dev_interface: AtcaKitType::AtcaKitAutoIface,
+            dev_identity: 0u8,
+            vid: ATCA_KIT_HID_VID,
+            pid: ATCA_KIT_HID_PID,
+            packetsize: ATCA_KIT_HID_PACKET_SIZE,
+        }
+    }
+}
+
+impl Default for AtcaIfaceUart {
+    fn default() -> AtcaIfaceUart {
+        AtcaIfaceUart {
+            port: 0i32,
+            baud: ATCA_KIT_UART_BAUD,
+            wordsize: 8u8,
+            parity: AtcaUartParity::None,
+            stopbits: 1u8,
         }
     }
 }
@@ -35,6 +59,17 @@ impl AtcaIfaceCfg {
     pub fn set_iface_type(mut self, iface_type: String) -> AtcaIfaceCfg {
         self.iface_type = match iface_type.as_str() {
             "i2c" => AtcaIfaceType::AtcaI2cIface,
+            "swi" => AtcaIfaceType::AtcaSwiIface,
+            "kit-hid" => AtcaIfaceType::AtcaHidIface,
+            "kit-uart" => AtcaIfaceType::AtcaUartIface,
+            #[cfg(feature = "i2c-rust")]
+            "i2c-rust" => AtcaIfaceType::AtcaI2cRustIface,
+            #[cfg(feature = "rustcrypto-backend")]
+            "software" => AtcaIfaceType::AtcaSoftwareIface,
+            #[cfg(feature = "rustcrypto-backend")]
+            "simulator" => AtcaIfaceType::AtcaSimulatorIface,
+            #[cfg(feature = "remote")]
+            "remote" => AtcaIfaceType::AtcaRemoteIface,
             "test-interface" => AtcaIfaceType::AtcaTestIface,
             _ => {
                 error!("Unsupported ATCA interface type {}", iface_type);
@@ -65,10 +100,78 @@ impl AtcaIfaceCfg {
         self.rx_retries = rx_retries;
         self
     }
+    pub fn set_power_policy(mut self, power_policy: AtcaPowerPolicy) -> AtcaIfaceCfg {
+        self.power_policy = power_policy;
+        self
+    }
+    /// With lazy initialization AteccDevice::new() does not read the serial number,
+    /// the options and the configuration of the slots, they are read by the first
+    /// command that needs them. Commands like random() or sha() can then be executed
+    /// as soon as possible after boot
+    pub fn set_lazy_init(mut self, lazy_init: bool) -> AtcaIfaceCfg {
+        self.lazy_init = lazy_init;
+        self
+    }
     pub fn set_iface(mut self, iface: AtcaIface) -> AtcaIfaceCfg {
         self.iface = Some(iface);
         self
     }
+    #[cfg(feature = "remote")]
+    pub fn set_remote_address(mut self, remote_address: std::net::SocketAddr) -> AtcaIfaceCfg {
+        self.remote_address = Some(remote_address);
+        self
+    }
+    /// Checks that the configuration is complete and consistent, to end the chain of setters.
+    /// The names given to set_iface_type() and set_devtype() are not kept,
+    /// those that are not supported have been logged
+    pub fn build(self) -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        if self.devtype == AtcaDeviceType::AtcaDevUnknown {
+            return Err(AtcaConfigError::UnknownDeviceType(String::new()));
+        }
+        match self.iface_type {
+            AtcaIfaceType::AtcaUnknownIface => {
+                return Err(AtcaConfigError::UnknownIfaceType(String::new()))
+            }
+            AtcaIfaceType::AtcaI2cIface | AtcaIfaceType::AtcaI2cRustIface => {
+                let i2c = unsafe { self.iface()?.atcai2c };
+                if i2c.slave_address == 0 || i2c.slave_address & 0x01 != 0 {
+                    return Err(AtcaConfigError::InvalidSetting(
+                        "slave_address",
+                        format!(
+                            "{:#04X} is not an 8-bit address (0xC0 for 0x60)",
+                            i2c.slave_address
+                        ),
+                    ));
+                }
+                if i2c.baud == 0 {
+                    return Err(AtcaConfigError::MissingSetting("baud"));
+                }
+                if self.wake_delay == 0 {
+                    return Err(AtcaConfigError::MissingSetting("wake_delay"));
+                }
+            }
+            AtcaIfaceType::AtcaSwiIface | AtcaIfaceType::AtcaHidIface => {
+                self.iface()?;
+            }
+            AtcaIfaceType::AtcaUartIface => {
+                let uart = unsafe { self.iface()?.atcauart };
+                if uart.baud == 0 {
+                    return Err(AtcaConfigError::MissingSetting("baud"));
+                }
+            }
+            #[cfg(feature = "remote")]
+            AtcaIfaceType::AtcaRemoteIface => {
+                self.remote_address
+                    .ok_or(AtcaConfigError::MissingSetting("remote_address"))?;
+            }
+            _ => (),
+        }
+        Ok(self)
+    }
+    /// The settings of the interface, that the interface type needs
+    fn iface(&self) -> Result<AtcaIface, AtcaConfigError> {
+        self.iface.ok_or(AtcaConfigError::MissingSetting("iface"))
+    }
 }
 
 impl AtcaIface {
@@ -76,6 +179,18 @@ impl AtcaIface {
         self.atcai2c = atcai2c;
         self
     }
+    pub fn set_atcaswi(mut self, atcaswi: AtcaIfaceSwi) -> AtcaIface {
+        self.atcaswi = atcaswi;
+        self
+    }
+    pub fn set_atcahid(mut self, atcahid: AtcaIfaceHid) -> AtcaIface {
+        self.atcahid = atcahid;
+        self
+    }
+    pub fn set_atcauart(mut self, atcauart: AtcaIfaceUart) -> AtcaIface {
+        self.atcauart = atcauart;
+        self
+    }
 }
 
 impl AtcaIfaceI2c {
@@ -92,3 +207,75 @@ impl AtcaIfaceI2c {
         self
     }
 }
+
+impl AtcaIfaceSwi {
+    pub fn set_bus(mut self, bus: u8) -> AtcaIfaceSwi {
+        self.bus = bus;
+        self
+    }
+}
+
+impl AtcaIfaceHid {
+    pub fn set_idx(mut self, idx: i32) -> AtcaIfaceHid {
+        self.idx = idx;
+        self
+    }
+    pub fn set_dev_interface(mut self, dev_interface: AtcaKitType) -> AtcaIfaceHid {
+        self.dev_interface = dev_interface;
+        self
+    }
+    pub fn set_dev_identity(mut self, dev_identity: u8) -> AtcaIfaceHid {
+        self.dev_identity = dev_identity;
+        self
+    }
+    pub fn set_vid(mut self, vid: u32) -> AtcaIfaceHid {
+        self.vid = vid;
+        self
+    }
+    pub fn set_pid(mut self, pid: u32) -> AtcaIfaceHid {
+        self.pid = pid;
+        self
+    }
+    pub fn set_packetsize(mut self, packetsize: u32) -> AtcaIfaceHid {
+        self.packetsize = packetsize;
+        self
+    }
+}
+
+impl AtcaIfaceUart {
+    pub fn set_port(mut self, port: i32) -> AtcaIfaceUart {
+        self.port = port;
+        self
+    }
+    /// Sets the port from its name: CryptoAuthLib identifies serial ports by number,
+    /// so the number the name ends with is used ("/dev/ttyACM1" and "COM1" give 1)
+    pub fn set_port_name(mut self, port_name: &str) -> AtcaIfaceUart {
+        let digits = port_name.len()
+            - port_name
+                .chars()
+                .rev()
+                .take_while(|x| x.is_ascii_digit())
+                .count();
+        match port_name[digits..].parse::<i32>() {
+            Ok(port) => self.port = port,
+            Err(_) => error!("Unsupported serial port name {}", port_name),
+        }
+        self
+    }
+    pub fn set_baud(mut self, baud: u32) -> AtcaIfaceUart {
+        self.baud = baud;
+        self
+    }
+    pub fn set_wordsize(mut self, wordsize: u8) -> AtcaIfaceUart {
+        self.wordsize = wordsize;
+        self
+    }
+    pub fn set_parity(mut self, parity: AtcaUartParity) -> AtcaIfaceUart {
+        self.parity = parity;
+        self
+    }
+    pub fn set_stopbits(mut self, stopbits: u8) -> AtcaIfaceUart {
+        self.stopbits = stopbits;
+        self
+    }
+}
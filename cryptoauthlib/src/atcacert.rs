@@ -0,0 +1,656 @@
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+
+use super::der::{read_element, write_element, ASN1_BIT_STRING, ASN1_SEQUENCE};
+use super::{signature_from_der, signature_to_der};
+use super::{AtcaStatus, AteccDeviceTrait, CertDateFormat, CertDef, CertLoc, CertSnSource};
+use super::{
+    ATCA_ATECC_PUB_KEY_SIZE, ATCA_COMP_CERT_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
+
+// Layout of a compressed certificate
+const COMP_CERT_ENC_DATES_IDX: usize = ATCA_SIG_SIZE;
+const COMP_CERT_SIGNER_ID_IDX: usize = COMP_CERT_ENC_DATES_IDX + ENC_DATES_SIZE;
+const COMP_CERT_TEMPLATE_CHAIN_ID_IDX: usize = COMP_CERT_SIGNER_ID_IDX + SIGNER_ID_SIZE;
+const COMP_CERT_SN_SOURCE_FORMAT_IDX: usize = COMP_CERT_TEMPLATE_CHAIN_ID_IDX + 1;
+const COMP_CERT_FORMAT_VERSION: u8 = 0x00;
+const ENC_DATES_SIZE: usize = 3;
+const SIGNER_ID_SIZE: usize = 2;
+
+const ENC_DATES_MIN_YEAR: u16 = 2000;
+const ENC_DATES_MAX_YEARS: u16 = 31;
+const KEY_ID_SIZE: usize = 20;
+const SEC1_TAG_UNCOMPRESSED: u8 = 0x04;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct CertDate {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+/// Reads the compressed certificate described by 'cert_def' from the device
+/// and reconstructs the full X.509 certificate (DER).
+/// The public key of the certificate authority is needed only when
+/// the template contains the authority key identifier.
+pub fn cert_read(
+    device: &dyn AteccDeviceTrait,
+    cert_def: &CertDef,
+    ca_public_key: Option<&[u8]>,
+) -> Result<Vec<u8>, AtcaStatus> {
+    let loc = cert_def.comp_cert_dev_loc;
+    let comp_cert = device.read_slot_data(loc.slot_id, loc.offset, ATCA_COMP_CERT_SIZE)?;
+
+    let mut public_key: Vec<u8> = Vec::new();
+    let result = device.get_public_key(cert_def.public_key_slot, &mut public_key);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+
+    let serial_number = cert_serial_number(device, cert_def, &comp_cert, &public_key)?;
+    cert_build(
+        cert_def,
+        &comp_cert,
+        &public_key,
+        &serial_number,
+        ca_public_key,
+    )
+} // cert_read()
+
+/// Reads the certificate of the key in 'slot_id' of a pre-provisioned part.
+/// Its definition is picked from 'cert_defs': the one for the slot whose template ID,
+/// chain ID and serial number source are those of the compressed certificate
+/// found on the device, so that the validity dates are read in the format
+/// of that template. The format of the certificate the chip itself checks
+/// when a public key is validated is told by ConfigZone::x509_format().
+/// AtcaBadParam is returned if no definition matches
+pub fn get_certificate(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    cert_defs: &[CertDef],
+    ca_public_key: Option<&[u8]>,
+) -> Result<Vec<u8>, AtcaStatus> {
+    let slot_cert_defs = cert_defs
+        .iter()
+        .filter(|cert_def| cert_def.public_key_slot == slot_id);
+    for cert_def in slot_cert_defs {
+        let loc = cert_def.comp_cert_dev_loc;
+        let comp_cert = device.read_slot_data(loc.slot_id, loc.offset, ATCA_COMP_CERT_SIZE)?;
+        if comp_cert_check(cert_def, &comp_cert).is_ok() {
+            return cert_read(device, cert_def, ca_public_key);
+        }
+    }
+    Err(AtcaStatus::AtcaBadParam)
+} // get_certificate()
+
+/// Compresses a full X.509 certificate (DER) matching 'cert_def' and stores it on the device,
+/// together with its serial number, if that is not derived from other data
+pub fn cert_write(
+    device: &dyn AteccDeviceTrait,
+    cert_def: &CertDef,
+    cert: &[u8],
+) -> Result<(), AtcaStatus> {
+    let comp_cert = cert_compress(cert_def, cert)?;
+
+    if cert_def.sn_source == CertSnSource::Stored {
+        let loc = cert_def.cert_sn_dev_loc.ok_or(AtcaStatus::AtcaBadParam)?;
+        let serial_number = get_element(cert, &cert_def.std_cert_elements.cert_sn)?;
+        if serial_number.len() != loc.count {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let result = device.write_slot_data(loc.slot_id, loc.offset, serial_number);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+    }
+
+    let loc = cert_def.comp_cert_dev_loc;
+    match device.write_slot_data(loc.slot_id, loc.offset, &comp_cert) {
+        AtcaStatus::AtcaSuccess => Ok(()),
+        err => Err(err),
+    }
+} // cert_write()
+
+/// Extracts the compressed certificate (72 bytes) from a full X.509 certificate (DER)
+/// matching 'cert_def'
+pub fn cert_compress(cert_def: &CertDef, cert: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    let elements = &cert_def.std_cert_elements;
+    let mut comp_cert: Vec<u8> = vec![0x00; ATCA_COMP_CERT_SIZE];
+
+    comp_cert[..ATCA_SIG_SIZE].copy_from_slice(&get_signature(cert, &elements.signature)?);
+
+    if elements.issue_date.count > 0 {
+        let issue_date = date_parse(
+            get_element(cert, &elements.issue_date)?,
+            cert_def.issue_date_format,
+        )?;
+        let expire_date = match elements.expire_date.count {
+            0 => max_date(cert_def.expire_date_format),
+            _ => date_parse(
+                get_element(cert, &elements.expire_date)?,
+                cert_def.expire_date_format,
+            )?,
+        };
+        let enc_dates = dates_encode(&issue_date, &expire_date, cert_def.expire_date_format)?;
+        comp_cert[COMP_CERT_ENC_DATES_IDX..COMP_CERT_SIGNER_ID_IDX].copy_from_slice(&enc_dates);
+    }
+
+    if elements.signer_id.count > 0 {
+        let signer_id = signer_id_parse(get_element(cert, &elements.signer_id)?)?;
+        comp_cert[COMP_CERT_SIGNER_ID_IDX..COMP_CERT_TEMPLATE_CHAIN_ID_IDX]
+            .copy_from_slice(&signer_id);
+    }
+
+    comp_cert[COMP_CERT_TEMPLATE_CHAIN_ID_IDX] =
+        ((cert_def.template_id & 0x0F) << 4) | (cert_def.chain_id & 0x0F);
+    comp_cert[COMP_CERT_SN_SOURCE_FORMAT_IDX] =
+        ((cert_def.sn_source as u8) << 4) | COMP_CERT_FORMAT_VERSION;
+    Ok(comp_cert)
+} // cert_compress()
+
+/// Reconstructs a full X.509 certificate (DER) from the template of 'cert_def',
+/// the compressed certificate and the data that is not part of it
+pub(crate) fn cert_build(
+    cert_def: &CertDef,
+    comp_cert: &[u8],
+    public_key: &[u8],
+    serial_number: &[u8],
+    ca_public_key: Option<&[u8]>,
+) -> Result<Vec<u8>, AtcaStatus> {
+    comp_cert_check(cert_def, comp_cert)?;
+    if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let elements = &cert_def.std_cert_elements;
+    let mut cert = cert_def.template.clone();
+
+    set_element(&mut cert, &elements.public_key, public_key)?;
+
+    let (issue_date, expire_date) = dates_decode(
+        &comp_cert[COMP_CERT_ENC_DATES_IDX..COMP_CERT_SIGNER_ID_IDX],
+        cert_def.expire_date_format,
+    );
+    if elements.issue_date.count > 0 {
+        let issue_date = date_format(&issue_date, cert_def.issue_date_format)?;
+        set_element(&mut cert, &elements.issue_date, &issue_date)?;
+    }
+    if elements.expire_date.count > 0 {
+        let expire_date = date_format(&expire_date, cert_def.expire_date_format)?;
+        set_element(&mut cert, &elements.expire_date, &expire_date)?;
+    }
+
+    if elements.signer_id.count > 0 {
+        let signer_id = &comp_cert[COMP_CERT_SIGNER_ID_IDX..COMP_CERT_TEMPLATE_CHAIN_ID_IDX];
+        let signer_id = format!("{:02X}{:02X}", signer_id[0], signer_id[1]);
+        set_element(&mut cert, &elements.signer_id, signer_id.as_bytes())?;
+    }
+
+    if elements.cert_sn.count > 0 {
+        set_element(&mut cert, &elements.cert_sn, serial_number)?;
+    }
+
+    if elements.subj_key_id.count > 0 {
+        set_element(&mut cert, &elements.subj_key_id, &key_id(public_key))?;
+    }
+    if elements.auth_key_id.count > 0 {
+        let ca_public_key = ca_public_key.ok_or(AtcaStatus::AtcaBadParam)?;
+        if ca_public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        set_element(&mut cert, &elements.auth_key_id, &key_id(ca_public_key))?;
+    }
+
+    set_signature(&mut cert, &elements.signature, &comp_cert[..ATCA_SIG_SIZE])?;
+    Ok(cert)
+} // cert_build()
+
+/// Determines the serial number of a certificate according to its source
+pub(crate) fn cert_serial_number(
+    device: &dyn AteccDeviceTrait,
+    cert_def: &CertDef,
+    comp_cert: &[u8],
+    public_key: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    comp_cert_check(cert_def, comp_cert)?;
+
+    let mut message = match cert_def.sn_source {
+        CertSnSource::Stored => {
+            let loc = cert_def.cert_sn_dev_loc.ok_or(AtcaStatus::AtcaBadParam)?;
+            return device.read_slot_data(loc.slot_id, loc.offset, loc.count);
+        }
+        CertSnSource::DeviceSn => return Ok(device.get_serial_number().to_vec()),
+        CertSnSource::SignerId => {
+            return Ok(comp_cert[COMP_CERT_SIGNER_ID_IDX..COMP_CERT_TEMPLATE_CHAIN_ID_IDX].to_vec())
+        }
+        CertSnSource::PubKeyHash | CertSnSource::PubKeyHashPos | CertSnSource::PubKeyHashRaw => {
+            public_key.to_vec()
+        }
+        CertSnSource::DeviceSnHash
+        | CertSnSource::DeviceSnHashPos
+        | CertSnSource::DeviceSnHashRaw => device.get_serial_number().to_vec(),
+    };
+
+    let sn_size = cert_def.std_cert_elements.cert_sn.count;
+    if (sn_size == 0) || (sn_size > ATCA_SHA2_256_DIGEST_SIZE) {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    message.extend_from_slice(&comp_cert[COMP_CERT_ENC_DATES_IDX..COMP_CERT_SIGNER_ID_IDX]);
+    let mut serial_number: Vec<u8> = Vec::new();
+    let result = device.sha(message, &mut serial_number);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    serial_number.truncate(sn_size);
+
+    match cert_def.sn_source {
+        // Serial number must be a positive integer
+        CertSnSource::PubKeyHash | CertSnSource::DeviceSnHash => serial_number[0] &= 0x7F,
+        // ...and its size must not be reduced by DER encoding
+        CertSnSource::PubKeyHashPos | CertSnSource::DeviceSnHashPos => {
+            serial_number[0] = (serial_number[0] & 0x7F) | 0x40
+        }
+        _ => (),
+    }
+    Ok(serial_number)
+} // cert_serial_number()
+
+/// Returns the template ID, the chain ID and the serial number source
+/// stored in a compressed certificate
+pub(crate) fn comp_cert_ids(comp_cert: &[u8]) -> Result<(u8, u8, CertSnSource), AtcaStatus> {
+    if comp_cert.len() != ATCA_COMP_CERT_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let template_chain_id = comp_cert[COMP_CERT_TEMPLATE_CHAIN_ID_IDX];
+    let sn_source = match comp_cert[COMP_CERT_SN_SOURCE_FORMAT_IDX] >> 4 {
+        0x0 => CertSnSource::Stored,
+        0x8 => CertSnSource::DeviceSn,
+        0x9 => CertSnSource::SignerId,
+        0xA => CertSnSource::PubKeyHash,
+        0xB => CertSnSource::DeviceSnHash,
+        0xC => CertSnSource::PubKeyHashPos,
+        0xD => CertSnSource::DeviceSnHashPos,
+        0xE => CertSnSource::PubKeyHashRaw,
+        0xF => CertSnSource::DeviceSnHashRaw,
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+    Ok((template_chain_id >> 4, template_chain_id & 0x0F, sn_source))
+} // comp_cert_ids()
+
+/// Checks whether the compressed certificate was created for the given definition
+fn comp_cert_check(cert_def: &CertDef, comp_cert: &[u8]) -> Result<(), AtcaStatus> {
+    if comp_cert.len() != ATCA_COMP_CERT_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let template_chain_id = comp_cert[COMP_CERT_TEMPLATE_CHAIN_ID_IDX];
+    let sn_source_format = comp_cert[COMP_CERT_SN_SOURCE_FORMAT_IDX];
+    if ((template_chain_id >> 4) != (cert_def.template_id & 0x0F))
+        || ((template_chain_id & 0x0F) != (cert_def.chain_id & 0x0F))
+        || ((sn_source_format >> 4) != cert_def.sn_source as u8)
+        || ((sn_source_format & 0x0F) != COMP_CERT_FORMAT_VERSION)
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok(())
+} // comp_cert_check()
+
+fn get_element<'a>(cert: &'a [u8], loc: &CertLoc) -> Result<&'a [u8], AtcaStatus> {
+    match cert.get(loc.offset..loc.offset + loc.count) {
+        Some(element) => Ok(element),
+        None => Err(AtcaStatus::AtcaBadParam),
+    }
+}
+
+fn set_element(cert: &mut [u8], loc: &CertLoc, data: &[u8]) -> Result<(), AtcaStatus> {
+    if data.len() != loc.count {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    match cert.get_mut(loc.offset..loc.offset + loc.count) {
+        Some(element) => {
+            element.copy_from_slice(data);
+            Ok(())
+        }
+        None => Err(AtcaStatus::AtcaBadParam),
+    }
+}
+
+/// Reads the raw signature from the BIT STRING at the end of a certificate
+fn get_signature(cert: &[u8], loc: &CertLoc) -> Result<Vec<u8>, AtcaStatus> {
+    let data = cert.get(loc.offset..).ok_or(AtcaStatus::AtcaBadParam)?;
+    match read_element(ASN1_BIT_STRING, data)? {
+        (content, _) if content.first() == Some(&0x00) => signature_from_der(&content[1..]),
+        _ => Err(AtcaStatus::AtcaBadParam),
+    }
+}
+
+/// Replaces the signature at the end of a certificate. The size of its DER encoding
+/// depends on the values of R and S, so the certificate length is also updated.
+fn set_signature(cert: &mut Vec<u8>, loc: &CertLoc, signature: &[u8]) -> Result<(), AtcaStatus> {
+    // The certificate length must be encoded on two bytes, as it is for all real certificates
+    if (cert.len() < 4) || (cert[0] != ASN1_SEQUENCE) || (cert[1] != 0x82) {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if (loc.offset < 4) || (loc.offset > cert.len()) {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let mut signature_bits: Vec<u8> = vec![0x00];
+    signature_bits.extend_from_slice(&signature_to_der(signature)?);
+    cert.truncate(loc.offset);
+    cert.extend_from_slice(&write_element(ASN1_BIT_STRING, &signature_bits));
+
+    let length = cert.len() - 4;
+    cert[2] = (length >> 8) as u8;
+    cert[3] = length as u8;
+    Ok(())
+}
+
+fn signer_id_parse(signer_id: &[u8]) -> Result<[u8; SIGNER_ID_SIZE], AtcaStatus> {
+    let value = std::str::from_utf8(signer_id)
+        .ok()
+        .filter(|x| x.len() == 2 * SIGNER_ID_SIZE)
+        .and_then(|x| u16::from_str_radix(x, 16).ok())
+        .ok_or(AtcaStatus::AtcaBadParam)?;
+    Ok(value.to_be_bytes())
+}
+
+/// Key identifier as calculated by Microchip templates: SHA1 of the SEC1 encoded public key
+fn key_id(public_key: &[u8]) -> [u8; KEY_ID_SIZE] {
+    let mut message: Vec<u8> = vec![SEC1_TAG_UNCOMPRESSED];
+    message.extend_from_slice(public_key);
+    sha1(&message)
+}
+
+/// Encodes the issue date and the validity period as stored in a compressed certificate
+fn dates_encode(
+    issue_date: &CertDate,
+    expire_date: &CertDate,
+    expire_date_format: CertDateFormat,
+) -> Result<[u8; ENC_DATES_SIZE], AtcaStatus> {
+    if (issue_date.year < ENC_DATES_MIN_YEAR)
+        || (issue_date.year > ENC_DATES_MIN_YEAR + ENC_DATES_MAX_YEARS)
+        || (issue_date.minute != 0)
+        || (issue_date.second != 0)
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let expire_years = match *expire_date == max_date(expire_date_format) {
+        true => 0,
+        false => {
+            let years = expire_date.year.saturating_sub(issue_date.year);
+            let expected = CertDate {
+                year: expire_date.year,
+                ..*issue_date
+            };
+            if (years == 0) || (years > ENC_DATES_MAX_YEARS) || (*expire_date != expected) {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            years as u8
+        }
+    };
+
+    let year = (issue_date.year - ENC_DATES_MIN_YEAR) as u8;
+    Ok([
+        (year << 3) | (issue_date.month >> 1),
+        ((issue_date.month & 0x01) << 7) | (issue_date.day << 2) | (issue_date.hour >> 3),
+        ((issue_date.hour & 0x07) << 5) | expire_years,
+    ])
+}
+
+/// Decodes the issue and expire dates stored in a compressed certificate
+fn dates_decode(enc_dates: &[u8], expire_date_format: CertDateFormat) -> (CertDate, CertDate) {
+    let issue_date = CertDate {
+        year: ENC_DATES_MIN_YEAR + (enc_dates[0] >> 3) as u16,
+        month: ((enc_dates[0] & 0x07) << 1) | (enc_dates[1] >> 7),
+        day: (enc_dates[1] >> 2) & 0x1F,
+        hour: ((enc_dates[1] & 0x03) << 3) | (enc_dates[2] >> 5),
+        minute: 0,
+        second: 0,
+    };
+    let expire_date = match enc_dates[2] & 0x1F {
+        // No expiration
+        0 => max_date(expire_date_format),
+        years => CertDate {
+            year: issue_date.year + years as u16,
+            ..issue_date
+        },
+    };
+    (issue_date, expire_date)
+}
+
+/// The latest date that can be expressed in the given format,
+/// used for certificates that do not expire
+fn max_date(format: CertDateFormat) -> CertDate {
+    match format {
+        CertDateFormat::Rfc5280Utc => CertDate {
+            year: 2049,
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59,
+            second: 59,
+        },
+        CertDateFormat::PosixUint32Be => CertDate {
+            year: 2106,
+            month: 2,
+            day: 7,
+            hour: 6,
+            minute: 28,
+            second: 15,
+        },
+        CertDateFormat::Iso8601Sep | CertDateFormat::Rfc5280Gen => CertDate {
+            year: 9999,
+            month: 12,
+            day: 31,
+            hour: 23,
+            minute: 59,
+            second: 59,
+        },
+    }
+}
+
+fn date_format(date: &CertDate, format: CertDateFormat) -> Result<Vec<u8>, AtcaStatus> {
+    let formatted = match format {
+        CertDateFormat::Iso8601Sep => format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            date.year, date.month, date.day, date.hour, date.minute, date.second
+        ),
+        CertDateFormat::Rfc5280Utc => {
+            if (date.year < 1950) || (date.year > 2049) {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            format!(
+                "{:02}{:02}{:02}{:02}{:02}{:02}Z",
+                date.year % 100,
+                date.month,
+                date.day,
+                date.hour,
+                date.minute,
+                date.second
+            )
+        }
+        CertDateFormat::Rfc5280Gen => format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}Z",
+            date.year, date.month, date.day, date.hour, date.minute, date.second
+        ),
+        CertDateFormat::PosixUint32Be => {
+            let days = days_from_civil(date.year as i64, date.month as i64, date.day as i64);
+            let seconds = days * 86400
+                + date.hour as i64 * 3600
+                + date.minute as i64 * 60
+                + date.second as i64;
+            match u32::try_from(seconds) {
+                Ok(val) => return Ok(val.to_be_bytes().to_vec()),
+                Err(_) => return Err(AtcaStatus::AtcaBadParam),
+            }
+        }
+    };
+    Ok(formatted.into_bytes())
+}
+
+fn date_parse(data: &[u8], format: CertDateFormat) -> Result<CertDate, AtcaStatus> {
+    let date = match format {
+        CertDateFormat::Iso8601Sep => {
+            if (data.len() != 20)
+                || (data[4] != b'-')
+                || (data[7] != b'-')
+                || (data[10] != b'T')
+                || (data[13] != b':')
+                || (data[16] != b':')
+                || (data[19] != b'Z')
+            {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            CertDate {
+                year: parse_digits(&data[0..4])?,
+                month: parse_digits(&data[5..7])? as u8,
+                day: parse_digits(&data[8..10])? as u8,
+                hour: parse_digits(&data[11..13])? as u8,
+                minute: parse_digits(&data[14..16])? as u8,
+                second: parse_digits(&data[17..19])? as u8,
+            }
+        }
+        CertDateFormat::Rfc5280Utc => {
+            if (data.len() != 13) || (data[12] != b'Z') {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            let year = parse_digits(&data[0..2])?;
+            CertDate {
+                year: if year < 50 { 2000 + year } else { 1900 + year },
+                month: parse_digits(&data[2..4])? as u8,
+                day: parse_digits(&data[4..6])? as u8,
+                hour: parse_digits(&data[6..8])? as u8,
+                minute: parse_digits(&data[8..10])? as u8,
+                second: parse_digits(&data[10..12])? as u8,
+            }
+        }
+        CertDateFormat::Rfc5280Gen => {
+            if (data.len() != 15) || (data[14] != b'Z') {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            CertDate {
+                year: parse_digits(&data[0..4])?,
+                month: parse_digits(&data[4..6])? as u8,
+                day: parse_digits(&data[6..8])? as u8,
+                hour: parse_digits(&data[8..10])? as u8,
+                minute: parse_digits(&data[10..12])? as u8,
+                second: parse_digits(&data[12..14])? as u8,
+            }
+        }
+        CertDateFormat::PosixUint32Be => {
+            if data.len() != 4 {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            let seconds = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as i64;
+            let (year, month, day) = civil_from_days(seconds / 86400);
+            CertDate {
+                year: year as u16,
+                month: month as u8,
+                day: day as u8,
+                hour: ((seconds % 86400) / 3600) as u8,
+                minute: ((seconds % 3600) / 60) as u8,
+                second: (seconds % 60) as u8,
+            }
+        }
+    };
+
+    if (date.month < 1)
+        || (date.month > 12)
+        || (date.day < 1)
+        || (date.day > 31)
+        || (date.hour > 23)
+        || (date.minute > 59)
+        || (date.second > 59)
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok(date)
+}
+
+fn parse_digits(data: &[u8]) -> Result<u16, AtcaStatus> {
+    data.iter().try_fold(0u16, |acc, &x| match x {
+        b'0'..=b'9' => Ok(acc * 10 + (x - b'0') as u16),
+        _ => Err(AtcaStatus::AtcaBadParam),
+    })
+}
+
+/// Number of days since 1970-01-01 for a date of the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date of the proleptic Gregorian calendar for a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = (month_index + 2) % 12 + 1;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// SHA1 digest, needed only for key identifiers and certificate thumbprints,
+/// which ATECC cannot calculate
+pub(crate) fn sha1(data: &[u8]) -> [u8; KEY_ID_SIZE] {
+    let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0x00);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (idx, word) in block.chunks(4).enumerate() {
+            w[idx] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for idx in 16..80 {
+            w[idx] = (w[idx - 3] ^ w[idx - 8] ^ w[idx - 14] ^ w[idx - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (idx, word) in w.iter().enumerate() {
+            let (f, k) = match idx {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e].iter()) {
+            *value = value.wrapping_add(*add);
+        }
+    }
+
+    let mut digest = [0u8; KEY_ID_SIZE];
+    for (chunk, value) in digest.chunks_mut(4).zip(state.iter()) {
+        chunk.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
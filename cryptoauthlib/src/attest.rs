@@ -0,0 +1,76 @@
+use super::ATCA_SHA2_256_DIGEST_SIZE;
+use super::{AtcaStatus, AteccDeviceTrait, AteccKeySlots, KeyAttestation, VerifyMode};
+use alloc::vec::Vec;
+
+/// Context string opening the message signed by attest_key()
+const KEY_ATTESTATION_CONTEXT: &[u8] = b"ATECC key attestation";
+
+/// Signs the public key of the P256 private key stored in 'subject_slot',
+/// together with the serial number of the device and the digest of its configuration zone,
+/// with the private key stored in 'attestation_slot'.
+/// Anyone trusting the attestation key learns that the subject key is held by this device
+/// and, from the configuration, whether it could have been written from outside
+pub fn attest_key(
+    device: &dyn AteccDeviceTrait,
+    subject_slot: u8,
+    attestation_slot: u8,
+) -> Result<KeyAttestation, AtcaStatus> {
+    if subject_slot == attestation_slot {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let subject = device.ecc_key(subject_slot)?;
+    let attestation_key = device.ecc_key(attestation_slot)?;
+
+    let mut config_data: Vec<u8> = Vec::new();
+    let result = device.read_config_zone(&mut config_data);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    let mut attestation = KeyAttestation {
+        subject_slot,
+        serial_number: device.get_serial_number(),
+        config_digest: sha256(device, config_data)?,
+        public_key: subject.public_key()?,
+        signature: Vec::new(),
+    };
+    attestation.signature = attestation_key.sign_message(&key_attestation_message(&attestation))?;
+    Ok(attestation)
+} // attest_key()
+
+/// Verifies a statement made by attest_key() with the P256 public key (X || Y)
+/// of the attestation key, hashing and verification are done by ATECC.
+/// Checking the serial number and the configuration digest against the expected ones
+/// is left to the caller
+pub fn verify_key_attestation(
+    device: &dyn AteccDeviceTrait,
+    attestation_public_key: &[u8],
+    attestation: &KeyAttestation,
+) -> Result<bool, AtcaStatus> {
+    let digest = sha256(device, key_attestation_message(attestation))?;
+    device.verify_hash(
+        VerifyMode::External(attestation_public_key.to_vec()),
+        &digest,
+        &attestation.signature,
+    )
+} // verify_key_attestation()
+
+/// Message signed by attest_key(), for verifiers without ATECC: context string ||
+/// subject slot || serial number || configuration digest || public key.
+/// The signature is made over SHA256 of the message
+pub fn key_attestation_message(attestation: &KeyAttestation) -> Vec<u8> {
+    let mut message: Vec<u8> = KEY_ATTESTATION_CONTEXT.to_vec();
+    message.push(attestation.subject_slot);
+    message.extend_from_slice(&attestation.serial_number);
+    message.extend_from_slice(&attestation.config_digest);
+    message.extend_from_slice(&attestation.public_key);
+    message
+} // key_attestation_message()
+
+/// SHA256 calculated by ATECC
+fn sha256(device: &dyn AteccDeviceTrait, message: Vec<u8>) -> Result<Vec<u8>, AtcaStatus> {
+    let mut digest: Vec<u8> = Vec::with_capacity(ATCA_SHA2_256_DIGEST_SIZE);
+    match device.sha(message, &mut digest) {
+        AtcaStatus::AtcaSuccess => Ok(digest),
+        err => Err(err),
+    }
+} // sha256()
@@ -0,0 +1,365 @@
+// Command line access to an ATECC chip, for provisioning and quick checks.
+//
+// Usage: atecc-cli [--config FILE] COMMAND [ARGS]
+// The chip is described by FILE (default: the file named by ATECC_CONFIG, or config.toml),
+// in the format of the config.toml of the crate, with its settings overridden
+// by the other ATECC_* environment variables.
+//
+// Commands:
+//   info                           device type, serial number, revision and lock state
+//   read-config [FILE]             configuration zone, written to FILE or dumped as hex
+//   describe [--json]              interpretation of the configuration zone, as text or JSON
+//   write-config FILE [--lock] [--dry-run]
+//                                  writes the configuration zone from a binary FILE,
+//                                  checks it and locks it if asked; --dry-run shows the changes
+//   write-otp OFFSET DATA          writes the OTP zone, or verifies it once the data zone is locked
+//   lock config|data               locks a zone, this cannot be undone
+//   gen-key SLOT                   generates a P-256 private key, prints its public key
+//   get-pubkey SLOT                prints the public key of a slot
+//   sign SLOT FILE                 signs the SHA256 digest of FILE, prints R || S
+//   verify SLOT|PUBKEY FILE SIG    verifies SIG over the SHA256 digest of FILE
+//   random [COUNT]                 prints COUNT random bytes (default 32)
+//   provision MANIFEST             personalizes the chip as described by MANIFEST
+// Keys and signatures are printed and given as hex strings.
+
+use std::env;
+use std::fs;
+use std::process::exit;
+
+use rust_cryptoauthlib::config::Config;
+use rust_cryptoauthlib::provision::{provision, write_verify_lock, Manifest};
+use rust_cryptoauthlib::{
+    config_report, setup_atecc_device, write_otp_zone_verified, AtcaStatus, AteccDevice,
+    InfoCmdType, KeyType, ProvisioningDevice, VerifyMode, ATCA_ATECC_PUB_KEY_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
+
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+const DEFAULT_RANDOM_COUNT: usize = 32;
+const HEX_DUMP_LINE: usize = 16;
+
+const USAGE: &str = "Usage: atecc-cli [--config FILE] COMMAND [ARGS]
+Commands:
+  info
+  read-config [FILE]
+  describe [--json]
+  write-config FILE [--lock] [--dry-run]
+  write-otp OFFSET DATA
+  lock config|data
+  gen-key SLOT
+  get-pubkey SLOT
+  sign SLOT FILE
+  verify SLOT|PUBKEY FILE SIGNATURE
+  random [COUNT]
+  provision MANIFEST";
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    exit(2)
+}
+
+fn parse_u8(arg: &str) -> Option<u8> {
+    match arg.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => arg.parse().ok(),
+    }
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Bytes of a hex string, None if it is not valid (the last pair of an odd length is missing)
+fn from_hex(arg: &str) -> Option<Vec<u8>> {
+    (0..arg.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(arg.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+fn status_result(status: AtcaStatus) -> Result<(), String> {
+    match status {
+        AtcaStatus::AtcaSuccess => Ok(()),
+        err => Err(err.to_string()),
+    }
+}
+
+fn slot_arg(args: &[String], idx: usize) -> u8 {
+    match args.get(idx) {
+        Some(arg) => parse_u8(arg).unwrap_or_else(|| {
+            eprintln!("Invalid slot: {}", arg);
+            exit(2)
+        }),
+        None => usage(),
+    }
+}
+
+fn file_arg(args: &[String], idx: usize) -> &str {
+    args.get(idx).map(String::as_str).unwrap_or_else(|| usage())
+}
+
+/// SHA256 digest of a file, computed by the chip
+fn file_digest(
+    device: &AteccDevice,
+    path: &str,
+) -> Result<[u8; ATCA_SHA2_256_DIGEST_SIZE], String> {
+    let message = fs::read(path).map_err(|err| format!("{}: {}", path, err))?;
+    device.sha256(&message).map_err(|err| err.to_string())
+}
+
+fn info(device: &AteccDevice) -> Result<(), String> {
+    let revision = device
+        .info_cmd(InfoCmdType::Revision)
+        .map_err(|err| err.to_string())?;
+    println!("Device type:          {}", device.get_device_type());
+    println!(
+        "Serial number:        {}",
+        to_hex(&device.get_serial_number())
+    );
+    println!("Revision:             {}", to_hex(&revision));
+    println!("Config zone locked:   {}", device.is_configuration_locked());
+    println!("Data zone locked:     {}", device.is_data_zone_locked());
+    println!("AES enabled:          {}", device.is_aes_enabled());
+    Ok(())
+}
+
+fn read_config(device: &AteccDevice, path: Option<&String>) -> Result<(), String> {
+    let mut config_data = Vec::new();
+    status_result(device.read_config_zone(&mut config_data))?;
+    match path {
+        Some(path) => fs::write(path, &config_data).map_err(|err| format!("{}: {}", path, err)),
+        None => {
+            for (line, chunk) in config_data.chunks(HEX_DUMP_LINE).enumerate() {
+                println!("{:02x}: {}", line * HEX_DUMP_LINE, to_hex(chunk));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn describe(device: &AteccDevice, format: Option<&String>) -> Result<(), String> {
+    let report = config_report(device.as_ref()).map_err(|err| err.to_string())?;
+    match format.map(String::as_str) {
+        Some("--json") => println!("{}", report.to_json()),
+        Some(_) => usage(),
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+fn write_config(device: &AteccDevice, path: &str, flags: &[String]) -> Result<(), String> {
+    let mut lock = false;
+    let mut dry_run = false;
+    for flag in flags.iter() {
+        match flag.as_str() {
+            "--lock" => lock = true,
+            "--dry-run" => dry_run = true,
+            _ => usage(),
+        }
+    }
+    let config_data = fs::read(path).map_err(|err| format!("{}: {}", path, err))?;
+    let device = ProvisioningDevice::new(device.as_ref());
+    let report =
+        write_verify_lock(&device, &config_data, lock, dry_run).map_err(|err| err.to_string())?;
+    for change in report.changes.iter() {
+        println!(
+            "{:02x}: {:02x} -> {:02x}",
+            change.offset, change.current, change.wanted
+        );
+    }
+    for change in report.ignored.iter() {
+        println!(
+            "{:02x}: {:02x} -> {:02x} (read-only, left as it is)",
+            change.offset, change.current, change.wanted
+        );
+    }
+    for step in report.steps.iter() {
+        let irreversible = match report.irreversible.contains(step) {
+            true => ", cannot be undone",
+            false => "",
+        };
+        match dry_run {
+            true => println!("Would {}{}", step, irreversible),
+            false => println!("Done: {}{}", step, irreversible),
+        }
+    }
+    Ok(())
+}
+
+fn write_otp(device: &AteccDevice, offset: &str, data: &str) -> Result<(), String> {
+    let offset = offset
+        .parse()
+        .map_err(|_| format!("Invalid offset: {}", offset))?;
+    let data = from_hex(data).ok_or_else(|| format!("Invalid data: {}", data))?;
+    let device = ProvisioningDevice::new(device.as_ref());
+    let report = write_otp_zone_verified(&device, offset, &data);
+    println!("Bytes written:  {}", report.written);
+    match report.verified {
+        Some(true) => println!("Read back:      matches"),
+        Some(false) => {
+            let mismatches: Vec<String> = report
+                .mismatches
+                .iter()
+                .map(|offset| offset.to_string())
+                .collect();
+            println!("Read back:      differs at {}", mismatches.join(", "));
+        }
+        None => println!("Read back:      not before the data zone is locked"),
+    }
+    status_result(report.status)
+}
+
+fn lock(device: &AteccDevice, zone: &str) -> Result<(), String> {
+    let device = ProvisioningDevice::new(device.as_ref());
+    match zone {
+        "config" => status_result(device.lock_config_zone()),
+        "data" => status_result(device.lock_data_zone()),
+        _ => usage(),
+    }
+}
+
+fn gen_key(device: &AteccDevice, slot_id: u8) -> Result<(), String> {
+    status_result(device.gen_key(KeyType::P256EccKey, slot_id))?;
+    get_pubkey(device, slot_id)
+}
+
+fn get_pubkey(device: &AteccDevice, slot_id: u8) -> Result<(), String> {
+    let public_key = device.public_key(slot_id).map_err(|err| err.to_string())?;
+    println!("{}", to_hex(&public_key));
+    Ok(())
+}
+
+fn sign(device: &AteccDevice, slot_id: u8, path: &str) -> Result<(), String> {
+    let digest = file_digest(device, path)?;
+    let signature = device
+        .sign_digest(slot_id, &digest)
+        .map_err(|err| err.to_string())?;
+    println!("{}", to_hex(&signature));
+    Ok(())
+}
+
+fn verify(device: &AteccDevice, key: &str, path: &str, signature: &str) -> Result<(), String> {
+    let mode = match from_hex(key) {
+        Some(public_key) if public_key.len() == ATCA_ATECC_PUB_KEY_SIZE => {
+            VerifyMode::External(public_key)
+        }
+        _ => match parse_u8(key) {
+            Some(slot_id) => VerifyMode::Internal(slot_id),
+            None => return Err(format!("Neither a slot nor a public key: {}", key)),
+        },
+    };
+    let signature = match from_hex(signature) {
+        Some(signature) if signature.len() == ATCA_SIG_SIZE => signature,
+        _ => return Err(format!("Invalid signature: {}", signature)),
+    };
+    let digest = file_digest(device, path)?;
+    match device.verify_hash(mode, &digest, &signature) {
+        Ok(true) => {
+            println!("Signature is valid");
+            Ok(())
+        }
+        Ok(false) => Err("Signature is not valid".to_owned()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn random(device: &AteccDevice, count: usize) -> Result<(), String> {
+    let mut rand_out = vec![0; count];
+    status_result(device.random_fill(&mut rand_out))?;
+    println!("{}", to_hex(&rand_out));
+    Ok(())
+}
+
+fn provision_manifest(device: &AteccDevice, path: &str) -> Result<(), String> {
+    let manifest = Manifest::from_file(path).map_err(|err| err.to_string())?;
+    let device = ProvisioningDevice::new(device.as_ref());
+    let report = provision(&device, &manifest, |progress| {
+        let outcome = match (progress.status, progress.skipped) {
+            (AtcaStatus::AtcaSuccess, true) => "skipped".to_owned(),
+            (AtcaStatus::AtcaSuccess, false) => "done".to_owned(),
+            (err, _) => err.to_string(),
+        };
+        println!(
+            "[{}/{}] {}: {}",
+            progress.index, progress.total, progress.step, outcome
+        );
+    })
+    .map_err(|err| err.to_string())?;
+    for (slot_id, public_key) in report.public_keys.iter() {
+        println!("Public key of slot {}: {}", slot_id, to_hex(public_key));
+    }
+    Ok(())
+}
+
+fn run(device: &AteccDevice, args: &[String]) -> Result<(), String> {
+    match args[0].as_str() {
+        "info" => info(device),
+        "read-config" => read_config(device, args.get(1)),
+        "describe" => describe(device, args.get(1)),
+        "write-config" => write_config(device, file_arg(args, 1), &args[2..]),
+        "write-otp" => write_otp(device, file_arg(args, 1), file_arg(args, 2)),
+        "lock" => lock(device, file_arg(args, 1)),
+        "gen-key" => gen_key(device, slot_arg(args, 1)),
+        "get-pubkey" => get_pubkey(device, slot_arg(args, 1)),
+        "sign" => sign(device, slot_arg(args, 1), file_arg(args, 2)),
+        "verify" => verify(
+            device,
+            file_arg(args, 1),
+            file_arg(args, 2),
+            file_arg(args, 3),
+        ),
+        "random" => {
+            let count = match args.get(1) {
+                Some(arg) => arg.parse().unwrap_or_else(|_| {
+                    eprintln!("Invalid count: {}", arg);
+                    exit(2)
+                }),
+                None => DEFAULT_RANDOM_COUNT,
+            };
+            random(device, count)
+        }
+        "provision" => provision_manifest(device, file_arg(args, 1)),
+        _ => usage(),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let config_file = match args.first().map(String::as_str) {
+        Some("--config") if args.len() > 1 => {
+            let config_file = args.remove(1);
+            args.remove(0);
+            config_file
+        }
+        Some("--config") => usage(),
+        _ => env::var("ATECC_CONFIG").unwrap_or_else(|_| DEFAULT_CONFIG_FILE.to_owned()),
+    };
+    if args.is_empty() {
+        usage();
+    }
+
+    let iface_cfg = match Config::from_file(&config_file)
+        .and_then(|config| config.with_env())
+        .and_then(|config| config.iface_cfg())
+    {
+        Ok(iface_cfg) => iface_cfg,
+        Err(err) => {
+            eprintln!("Invalid configuration: {}", err);
+            exit(2)
+        }
+    };
+    let device = match setup_atecc_device(iface_cfg) {
+        Ok(device) => device,
+        Err(err) => {
+            eprintln!("Device setup failed: {}", err);
+            exit(1)
+        }
+    };
+
+    let result = run(&device, &args);
+    device.release();
+    if let Err(err) = result {
+        eprintln!("{} failed: {}", args[0], err);
+        exit(1)
+    }
+}
@@ -0,0 +1,76 @@
+// Server giving remote clients (AteccRemoteDevice) access to an ATECC chip on an I2C bus.
+//
+// Usage: atecc-remote-server [LISTEN_ADDRESS] [I2C_BUS] [I2C_ADDRESS]
+// Defaults: 127.0.0.1:7445, bus 1, address 0xC0 (8-bit), device type atecc608a.
+// The device type can be changed with the ATECC_DEVICE_TYPE environment variable.
+
+use std::env;
+use std::net::TcpListener;
+use std::process::exit;
+
+use rust_cryptoauthlib::{remote_serve, setup_atecc_device, AtcaIface, AtcaIfaceCfg, AtcaIfaceI2c};
+
+const DEFAULT_LISTEN_ADDRESS: &str = "127.0.0.1:7445";
+const DEFAULT_I2C_BUS: u8 = 1;
+const DEFAULT_I2C_ADDRESS: u8 = 0xC0;
+const DEFAULT_DEVICE_TYPE: &str = "atecc608a";
+
+fn parse_u8(arg: &str) -> Option<u8> {
+    match arg.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => arg.parse().ok(),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let listen_address = args
+        .first()
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_LISTEN_ADDRESS);
+    let bus = match args.get(1) {
+        Some(arg) => parse_u8(arg).unwrap_or_else(|| {
+            eprintln!("Invalid I2C bus: {}", arg);
+            exit(2)
+        }),
+        None => DEFAULT_I2C_BUS,
+    };
+    let slave_address = match args.get(2) {
+        Some(arg) => parse_u8(arg).unwrap_or_else(|| {
+            eprintln!("Invalid I2C address: {}", arg);
+            exit(2)
+        }),
+        None => DEFAULT_I2C_ADDRESS,
+    };
+    let device_type =
+        env::var("ATECC_DEVICE_TYPE").unwrap_or_else(|_| DEFAULT_DEVICE_TYPE.to_owned());
+
+    let iface_cfg = AtcaIfaceCfg::default()
+        .set_iface_type("i2c".to_owned())
+        .set_devtype(device_type)
+        .set_wake_delay(1600)
+        .set_rx_retries(20)
+        .set_iface(
+            AtcaIface::default().set_atcai2c(
+                AtcaIfaceI2c::default()
+                    .set_slave_address(slave_address)
+                    .set_bus(bus)
+                    .set_baud(400000),
+            ),
+        );
+    let device = match setup_atecc_device(iface_cfg) {
+        Ok(device) => device,
+        Err(err) => {
+            eprintln!("Device setup failed: {}", err);
+            exit(1)
+        }
+    };
+
+    let result = TcpListener::bind(listen_address)
+        .and_then(|listener| remote_serve(device.as_ref(), &listener));
+    device.release();
+    if let Err(err) = result {
+        eprintln!("Server failed: {}", err);
+        exit(1)
+    }
+}
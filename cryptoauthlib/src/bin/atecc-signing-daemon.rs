@@ -0,0 +1,86 @@
+// Signing daemon sharing an ATECC chip on an I2C bus among the processes of the host
+// (clients use AteccDaemonClient).
+//
+// Usage: atecc-signing-daemon [SOCKET_PATH] [I2C_BUS] [I2C_ADDRESS]
+// Defaults: /tmp/atecc.sock, bus 1, address 0xC0 (8-bit), device type atecc608a.
+// The device type can be changed with the ATECC_DEVICE_TYPE environment variable.
+// Processes allowed to use the chip are selected with the permissions of the socket file.
+
+use std::env;
+use std::fs;
+use std::os::unix::net::UnixListener;
+use std::path::Path;
+use std::process::exit;
+use std::sync::Arc;
+
+use rust_cryptoauthlib::{daemon_serve, setup_atecc_device, AtcaIface, AtcaIfaceCfg, AtcaIfaceI2c};
+
+const DEFAULT_SOCKET_PATH: &str = "/tmp/atecc.sock";
+const DEFAULT_I2C_BUS: u8 = 1;
+const DEFAULT_I2C_ADDRESS: u8 = 0xC0;
+const DEFAULT_DEVICE_TYPE: &str = "atecc608a";
+
+fn parse_u8(arg: &str) -> Option<u8> {
+    match arg.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16).ok(),
+        None => arg.parse().ok(),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let socket_path = Path::new(
+        args.first()
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_SOCKET_PATH),
+    );
+    let bus = match args.get(1) {
+        Some(arg) => parse_u8(arg).unwrap_or_else(|| {
+            eprintln!("Invalid I2C bus: {}", arg);
+            exit(2)
+        }),
+        None => DEFAULT_I2C_BUS,
+    };
+    let slave_address = match args.get(2) {
+        Some(arg) => parse_u8(arg).unwrap_or_else(|| {
+            eprintln!("Invalid I2C address: {}", arg);
+            exit(2)
+        }),
+        None => DEFAULT_I2C_ADDRESS,
+    };
+    let device_type =
+        env::var("ATECC_DEVICE_TYPE").unwrap_or_else(|_| DEFAULT_DEVICE_TYPE.to_owned());
+
+    let iface_cfg = AtcaIfaceCfg::default()
+        .set_iface_type("i2c".to_owned())
+        .set_devtype(device_type)
+        .set_wake_delay(1600)
+        .set_rx_retries(20)
+        .set_iface(
+            AtcaIface::default().set_atcai2c(
+                AtcaIfaceI2c::default()
+                    .set_slave_address(slave_address)
+                    .set_bus(bus)
+                    .set_baud(400000),
+            ),
+        );
+    let device = match setup_atecc_device(iface_cfg) {
+        Ok(device) => Arc::new(device),
+        Err(err) => {
+            eprintln!("Device setup failed: {}", err);
+            exit(1)
+        }
+    };
+
+    // A socket file left by a previous instance would make bind() fail
+    if socket_path.exists() {
+        let _ = fs::remove_file(socket_path);
+    }
+    let result = UnixListener::bind(socket_path)
+        .and_then(|listener| daemon_serve(Arc::clone(&device), &listener));
+    device.release();
+    if let Err(err) = result {
+        eprintln!("Daemon failed: {}", err);
+        exit(1)
+    }
+}
@@ -0,0 +1,127 @@
+use super::atcacert::sha1;
+use super::der::{
+    base64_encode, pem_encode, read_element, ASN1_CONTEXT_0, ASN1_INTEGER, ASN1_SEQUENCE,
+};
+use super::tng::name_common_name;
+use super::ATCA_SHA2_256_DIGEST_SIZE;
+use super::{AtcaStatus, AteccDeviceTrait, AwsIotRegistration, AzureX509Identity};
+use alloc::{string::String, vec::Vec};
+
+const PEM_LABEL_CERTIFICATE: &str = "CERTIFICATE";
+/// Key name Azure DPS expects in SAS tokens of individual and group enrollments
+const AZURE_DPS_KEY_NAME: &str = "registration";
+
+/// Prepares the registration of a device certificate (DER) with AWS IoT
+/// without registering its CA (multi-account registration, RegisterCertificateWithoutCA):
+/// the certificate in PEM and the certificate ID AWS IoT will assign to it.
+/// The certificate is usually read from the device with cert_read() or tng_get_cert_chain().
+pub fn aws_iot_registration(
+    device: &dyn AteccDeviceTrait,
+    cert: &[u8],
+) -> Result<AwsIotRegistration, AtcaStatus> {
+    Ok(AwsIotRegistration {
+        certificate_pem: pem_encode(cert, PEM_LABEL_CERTIFICATE),
+        certificate_id: to_hex(&sha256(device, cert)?).to_lowercase(),
+    })
+} // aws_iot_registration()
+
+/// Returns what is needed to enroll a device authenticating with an X.509 certificate (DER)
+/// in Azure DPS or to register it in IoT Hub: the registration ID and the thumbprints
+pub fn azure_x509_identity(
+    device: &dyn AteccDeviceTrait,
+    cert: &[u8],
+) -> Result<AzureX509Identity, AtcaStatus> {
+    Ok(AzureX509Identity {
+        registration_id: cert_subject_common_name(cert)?,
+        thumbprint_sha1: to_hex(&sha1(cert)),
+        thumbprint_sha256: to_hex(&sha256(device, cert)?),
+    })
+} // azure_x509_identity()
+
+/// Computes an Azure shared access signature token for the resource URI
+/// (e.g. "<hub>.azure-devices.net/devices/<device id>"), valid until 'expiry'
+/// (seconds since the Unix epoch). The HMAC-SHA256 is calculated by ATECC
+/// with the symmetric key (32 bytes) stored in the slot.
+pub fn azure_sas_token(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    resource_uri: &str,
+    expiry: u64,
+    key_name: Option<&str>,
+) -> Result<String, AtcaStatus> {
+    let resource_uri = url_encode(resource_uri);
+    let string_to_sign = format!("{}\n{}", resource_uri, expiry);
+    let signature = device.sha_hmac(string_to_sign.as_bytes(), slot_id)?;
+
+    let mut token = format!(
+        "SharedAccessSignature sr={}&sig={}&se={}",
+        resource_uri,
+        url_encode(&base64_encode(&signature)),
+        expiry
+    );
+    if let Some(key_name) = key_name {
+        token.push_str(&format!("&skn={}", url_encode(key_name)));
+    }
+    Ok(token)
+} // azure_sas_token()
+
+/// Computes the SAS token a device presents to Azure DPS to register
+/// with a symmetric key enrollment, the key being stored in the slot
+pub fn azure_dps_sas_token(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    id_scope: &str,
+    registration_id: &str,
+    expiry: u64,
+) -> Result<String, AtcaStatus> {
+    let resource_uri = format!("{}/registrations/{}", id_scope, registration_id);
+    azure_sas_token(
+        device,
+        slot_id,
+        &resource_uri,
+        expiry,
+        Some(AZURE_DPS_KEY_NAME),
+    )
+} // azure_dps_sas_token()
+
+/// Returns the subject common name of a certificate (DER)
+pub(crate) fn cert_subject_common_name(cert: &[u8]) -> Result<String, AtcaStatus> {
+    let (cert, _) = read_element(ASN1_SEQUENCE, cert)?;
+    let (tbs_cert, _) = read_element(ASN1_SEQUENCE, cert)?;
+    let (_, rest) = read_element(ASN1_CONTEXT_0, tbs_cert)?;
+    let (_, rest) = read_element(ASN1_INTEGER, rest)?;
+    let (_, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (_, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (_, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (subject, _) = read_element(ASN1_SEQUENCE, rest)?;
+    match name_common_name(subject)? {
+        Some(name) => String::from_utf8(name.to_vec()).map_err(|_| AtcaStatus::AtcaBadParam),
+        None => Err(AtcaStatus::AtcaBadParam),
+    }
+}
+
+/// Percent-encodes all characters except the unreserved ones (RFC 3986)
+pub(crate) fn url_encode(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for x in value.bytes() {
+        match x {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                result.push(x as char)
+            }
+            _ => result.push_str(&format!("%{:02X}", x)),
+        }
+    }
+    result
+}
+
+fn sha256(device: &dyn AteccDeviceTrait, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    let mut digest: Vec<u8> = Vec::with_capacity(ATCA_SHA2_256_DIGEST_SIZE);
+    match device.sha(data.to_vec(), &mut digest) {
+        AtcaStatus::AtcaSuccess => Ok(digest),
+        err => Err(err),
+    }
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|x| format!("{:02X}", x)).collect()
+}
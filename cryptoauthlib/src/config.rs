@@ -0,0 +1,382 @@
+// Configuration of the interface and the device read from a file, in the format
+// of the config.toml used by the tests:
+//
+// [device]
+// device_type = "atecc608a"
+// iface_type = "i2c"
+// wake_delay = 1500
+// rx_retries = 20
+//
+// [interface]
+// slave_address = 0xC0
+// bus = 1
+// baud = 400000
+//
+// The same tables can be written in JSON and YAML (features "json-config" and
+// "yaml-config"), which fleet tooling generates more readily than TOML.
+//
+// The settings can be overridden with environment variables, so that a container
+// can be pointed at another chip without a new configuration file:
+// ATECC_CONFIG (the file), ATECC_DEVICE_TYPE, ATECC_IFACE, ATECC_WAKE_DELAY,
+// ATECC_RX_RETRIES, ATECC_ADDRESS, ATECC_BUS, ATECC_BAUD, ATECC_PORT
+// and ATECC_REMOTE_ADDRESS.
+
+use std::convert::TryFrom;
+use std::env;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::{
+    AtcaConfigError, AtcaDeviceType, AtcaIface, AtcaIfaceCfg, AtcaIfaceHid, AtcaIfaceI2c,
+    AtcaIfaceSwi, AtcaIfaceType, AtcaIfaceUart,
+};
+use super::{ATCA_KIT_UART_BAUD, ATCA_SWI_WAKE_DELAY};
+
+/// Format of a configuration file
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ConfigFormat {
+    Toml,
+    #[cfg(feature = "json-config")]
+    Json,
+    #[cfg(feature = "yaml-config")]
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Tells the format of a file from its extension: .toml, .json, .yaml or .yml
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<ConfigFormat, AtcaConfigError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => Ok(ConfigFormat::Toml),
+            #[cfg(feature = "json-config")]
+            "json" => Ok(ConfigFormat::Json),
+            #[cfg(feature = "yaml-config")]
+            "yaml" | "yml" => Ok(ConfigFormat::Yaml),
+            _ => Err(AtcaConfigError::Io(format!(
+                "{}: unsupported configuration format",
+                path.display()
+            ))),
+        }
+    } // ConfigFormat::from_path()
+}
+
+/// Deserializes a configuration, or a description of another kind, from a string
+pub(crate) fn parse<T: DeserializeOwned>(
+    contents: &str,
+    format: ConfigFormat,
+) -> Result<T, AtcaConfigError> {
+    match format {
+        ConfigFormat::Toml => {
+            toml::from_str(contents).map_err(|err| AtcaConfigError::Parse(err.to_string()))
+        }
+        #[cfg(feature = "json-config")]
+        ConfigFormat::Json => {
+            serde_json::from_str(contents).map_err(|err| AtcaConfigError::Parse(err.to_string()))
+        }
+        #[cfg(feature = "yaml-config")]
+        ConfigFormat::Yaml => {
+            serde_yaml::from_str(contents).map_err(|err| AtcaConfigError::Parse(err.to_string()))
+        }
+    }
+} // parse()
+
+/// Deserializes a file in the format told by its extension
+pub(crate) fn parse_file<T: DeserializeOwned>(path: &Path) -> Result<T, AtcaConfigError> {
+    let format = ConfigFormat::from_path(path)?;
+    let contents = read_to_string(path)
+        .map_err(|err| AtcaConfigError::Io(format!("{}: {}", path.display(), err)))?;
+    parse(&contents, format)
+} // parse_file()
+
+/// Contents of a configuration file
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The [device] table
+    pub device: Device,
+    /// The [interface] table, not needed by "test-interface" and the software interfaces
+    pub interface: Option<Interface>,
+}
+
+/// The chip and the interface type
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Device {
+    /// "atecc608a", "atecc508a" or one of the test devices
+    pub device_type: String,
+    /// "i2c", "i2c-rust", "swi", "kit-hid", "kit-uart", "software", "simulator",
+    /// "remote" or "test-interface"
+    pub iface_type: String,
+    /// Delay of the wake-up in microseconds
+    pub wake_delay: Option<u16>,
+    /// Number of retries of receiving a response
+    pub rx_retries: Option<i32>,
+}
+
+/// Settings of the interface, the ones used depend on the interface type
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Interface {
+    /// I2C address in the 8-bit form (e.g. 0xC0), the device identity for "kit-hid"
+    pub slave_address: Option<u8>,
+    /// I2C or SWI bus number
+    pub bus: Option<u8>,
+    /// I2C or UART baud rate
+    pub baud: Option<u32>,
+    /// Serial port of "kit-uart", e.g. "/dev/ttyACM0"
+    pub port: Option<String>,
+    /// Address of the server of "remote", e.g. "192.168.0.10:8451"
+    pub address: Option<String>,
+}
+
+impl Config {
+    /// Reads the configuration from a file in the format told by its extension
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Config, AtcaConfigError> {
+        parse_file(path.as_ref())
+    } // Config::from_file()
+
+    /// Reads the configuration from a string in the given format
+    pub fn from_config_str(
+        contents: &str,
+        format: ConfigFormat,
+    ) -> Result<Config, AtcaConfigError> {
+        parse(contents, format)
+    } // Config::from_config_str()
+
+    /// Reads the configuration from a TOML file
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Config, AtcaConfigError> {
+        let path = path.as_ref();
+        let contents = read_to_string(path)
+            .map_err(|err| AtcaConfigError::Io(format!("{}: {}", path.display(), err)))?;
+        Config::from_toml_str(&contents)
+    } // Config::from_toml_file()
+
+    /// Reads the configuration from a TOML string
+    pub fn from_toml_str(contents: &str) -> Result<Config, AtcaConfigError> {
+        parse(contents, ConfigFormat::Toml)
+    } // Config::from_toml_str()
+
+    /// Overrides the settings with the ATECC_* environment variables that are set
+    pub fn with_env(self) -> Result<Config, AtcaConfigError> {
+        self.with_overrides(|name| env::var(name).ok())
+    } // Config::with_env()
+
+    /// Overrides the settings with the variables given by 'lookup'
+    pub(crate) fn with_overrides<F>(mut self, lookup: F) -> Result<Config, AtcaConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(device_type) = lookup("ATECC_DEVICE_TYPE") {
+            self.device.device_type = device_type;
+        }
+        if let Some(iface_type) = lookup("ATECC_IFACE") {
+            self.device.iface_type = iface_type;
+        }
+        if let Some(wake_delay) = lookup("ATECC_WAKE_DELAY") {
+            self.device.wake_delay = Some(env_number(&wake_delay, "ATECC_WAKE_DELAY")?);
+        }
+        if let Some(rx_retries) = lookup("ATECC_RX_RETRIES") {
+            self.device.rx_retries = Some(env_number(&rx_retries, "ATECC_RX_RETRIES")?);
+        }
+        let mut interface = self.interface.take().unwrap_or_default();
+        if let Some(slave_address) = lookup("ATECC_ADDRESS") {
+            interface.slave_address = Some(env_number(&slave_address, "ATECC_ADDRESS")?);
+        }
+        if let Some(bus) = lookup("ATECC_BUS") {
+            interface.bus = Some(env_number(&bus, "ATECC_BUS")?);
+        }
+        if let Some(baud) = lookup("ATECC_BAUD") {
+            interface.baud = Some(env_number(&baud, "ATECC_BAUD")?);
+        }
+        if let Some(port) = lookup("ATECC_PORT") {
+            interface.port = Some(port);
+        }
+        if let Some(address) = lookup("ATECC_REMOTE_ADDRESS") {
+            interface.address = Some(address);
+        }
+        if interface != Interface::default() {
+            self.interface = Some(interface);
+        }
+        Ok(self)
+    } // Config::with_overrides()
+
+    /// Reads the file named by ATECC_CONFIG, if it is set, and overrides its settings
+    /// with the other ATECC_* environment variables
+    pub fn from_env() -> Result<Config, AtcaConfigError> {
+        Config::from_overrides(|name| env::var(name).ok())
+    } // Config::from_env()
+
+    pub(crate) fn from_overrides<F>(lookup: F) -> Result<Config, AtcaConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let config = match lookup("ATECC_CONFIG") {
+            Some(path) => Config::from_file(path)?,
+            None => Config::default(),
+        }
+        .with_overrides(lookup)?;
+        if config.device.device_type.is_empty() {
+            return Err(AtcaConfigError::MissingSetting("ATECC_DEVICE_TYPE"));
+        }
+        if config.device.iface_type.is_empty() {
+            return Err(AtcaConfigError::MissingSetting("ATECC_IFACE"));
+        }
+        Ok(config)
+    } // Config::from_overrides()
+
+    /// Checks the configuration and converts it into the configuration of the interface
+    pub fn iface_cfg(&self) -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        let device = &self.device;
+        let iface_cfg = AtcaIfaceCfg::default()
+            .set_iface_type(device.iface_type.clone())
+            .set_devtype(device.device_type.clone());
+        if iface_cfg.devtype == AtcaDeviceType::AtcaDevUnknown {
+            return Err(AtcaConfigError::UnknownDeviceType(
+                device.device_type.clone(),
+            ));
+        }
+        let iface_cfg = match iface_cfg.iface_type {
+            AtcaIfaceType::AtcaI2cIface | AtcaIfaceType::AtcaI2cRustIface => {
+                let interface = self.interface()?;
+                let slave_address = required(interface.slave_address, "interface.slave_address")?;
+                if slave_address & 0x01 != 0 {
+                    return Err(AtcaConfigError::InvalidSetting(
+                        "interface.slave_address",
+                        format!(
+                            "{:#04X} is odd, the address is in the 8-bit form (0xC0 for 0x60)",
+                            slave_address
+                        ),
+                    ));
+                }
+                iface_cfg
+                    .set_wake_delay(required(device.wake_delay, "device.wake_delay")?)
+                    .set_rx_retries(required(device.rx_retries, "device.rx_retries")?)
+                    .set_iface(
+                        AtcaIface::default().set_atcai2c(
+                            AtcaIfaceI2c::default()
+                                .set_slave_address(slave_address)
+                                .set_bus(required(interface.bus, "interface.bus")?)
+                                .set_baud(required(interface.baud, "interface.baud")?),
+                        ),
+                    )
+            }
+            AtcaIfaceType::AtcaSwiIface => {
+                let interface = self.interface()?;
+                iface_cfg
+                    .set_wake_delay(device.wake_delay.unwrap_or(ATCA_SWI_WAKE_DELAY))
+                    .set_rx_retries(required(device.rx_retries, "device.rx_retries")?)
+                    .set_iface(AtcaIface::default().set_atcaswi(
+                        AtcaIfaceSwi::default().set_bus(required(interface.bus, "interface.bus")?),
+                    ))
+            }
+            AtcaIfaceType::AtcaHidIface => iface_cfg
+                .set_wake_delay(required(device.wake_delay, "device.wake_delay")?)
+                .set_rx_retries(required(device.rx_retries, "device.rx_retries")?)
+                .set_iface(
+                    AtcaIface::default().set_atcahid(
+                        AtcaIfaceHid::default().set_dev_identity(
+                            self.interface
+                                .as_ref()
+                                .and_then(|interface| interface.slave_address)
+                                .unwrap_or(0),
+                        ),
+                    ),
+                ),
+            AtcaIfaceType::AtcaUartIface => {
+                let interface = self.interface()?;
+                let port = required(interface.port.as_ref(), "interface.port")?;
+                iface_cfg
+                    .set_wake_delay(required(device.wake_delay, "device.wake_delay")?)
+                    .set_rx_retries(required(device.rx_retries, "device.rx_retries")?)
+                    .set_iface(
+                        AtcaIface::default().set_atcauart(
+                            AtcaIfaceUart::default()
+                                .set_port_name(port)
+                                .set_baud(interface.baud.unwrap_or(ATCA_KIT_UART_BAUD)),
+                        ),
+                    )
+            }
+            #[cfg(feature = "remote")]
+            AtcaIfaceType::AtcaRemoteIface => {
+                let interface = self.interface()?;
+                let address = required(interface.address.as_ref(), "interface.address")?;
+                let address = address.parse().map_err(|err| {
+                    AtcaConfigError::InvalidSetting("interface.address", format!("{}", err))
+                })?;
+                iface_cfg.set_remote_address(address)
+            }
+            AtcaIfaceType::AtcaUnknownIface => {
+                return Err(AtcaConfigError::UnknownIfaceType(device.iface_type.clone()))
+            }
+            _ => iface_cfg,
+        };
+        Ok(iface_cfg)
+    } // Config::iface_cfg()
+
+    /// The [interface] table, for the interface types that need it
+    fn interface(&self) -> Result<&Interface, AtcaConfigError> {
+        self.interface
+            .as_ref()
+            .ok_or(AtcaConfigError::MissingSetting("interface"))
+    } // Config::interface()
+}
+
+/// A number set by an environment variable, decimal or hexadecimal with the 0x prefix
+fn env_number<T: TryFrom<u64>>(value: &str, name: &'static str) -> Result<T, AtcaConfigError> {
+    let value = value.trim();
+    let number = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse::<u64>(),
+    }
+    .map_err(|err| AtcaConfigError::InvalidSetting(name, format!("'{}': {}", value, err)))?;
+    T::try_from(number)
+        .map_err(|_| AtcaConfigError::InvalidSetting(name, format!("{} is out of range", value)))
+} // env_number()
+
+/// A setting that the interface type needs
+fn required<T>(value: Option<T>, name: &'static str) -> Result<T, AtcaConfigError> {
+    value.ok_or(AtcaConfigError::MissingSetting(name))
+} // required()
+
+impl AtcaIfaceCfg {
+    /// Reads the configuration of the interface from a TOML, JSON or YAML file,
+    /// the format is told by the extension of the file
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        Config::from_file(path)?.iface_cfg()
+    } // AtcaIfaceCfg::from_config_file()
+
+    /// Reads the configuration of the interface from a string in the given format
+    pub fn from_config_str(
+        contents: &str,
+        format: ConfigFormat,
+    ) -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        Config::from_config_str(contents, format)?.iface_cfg()
+    } // AtcaIfaceCfg::from_config_str()
+
+    /// Reads the configuration of the interface from the file named by ATECC_CONFIG,
+    /// if it is set, with its settings overridden by the other ATECC_* environment variables
+    pub fn from_env() -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        Config::from_env()?.iface_cfg()
+    } // AtcaIfaceCfg::from_env()
+
+    /// Reads the configuration of the interface from a TOML file,
+    /// in the format of the config.toml of the crate
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        Config::from_toml_file(path)?.iface_cfg()
+    } // AtcaIfaceCfg::from_toml_file()
+
+    /// Reads the configuration of the interface from a TOML string
+    pub fn from_toml_str(contents: &str) -> Result<AtcaIfaceCfg, AtcaConfigError> {
+        Config::from_toml_str(contents)?.iface_cfg()
+    } // AtcaIfaceCfg::from_toml_str()
+}
@@ -0,0 +1,341 @@
+use super::hw_impl::atcab_get_config_from_config_zone;
+use super::{
+    AtcaDeviceType, AtcaSlot, AtcaStatus, AteccDeviceTrait, ChipOptions, ConfigReport, KeyType,
+    OtpMode, SlotConfig, WriteConfig,
+};
+use super::{ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_SERIAL_NUM_SIZE, ATCA_WORD_SIZE};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+const IDX_REVISION: usize = 4;
+const IDX_SERIAL_NUMBER_END: usize = 8;
+const IDX_AES_ENABLE: usize = 13;
+const IDX_I2C_ENABLE: usize = 14;
+const IDX_I2C_ADDRESS: usize = 16;
+const IDX_OTP_MODE: usize = 18;
+const IDX_CHIP_MODE: usize = 19;
+const IDX_LOCK_VALUE: usize = 86;
+const IDX_LOCK_CONFIG: usize = 87;
+const IDX_CHIP_OPTIONS: usize = 90;
+const IDX_X509_FORMAT: usize = 92;
+/// Value of the lock bytes of a zone that is not locked
+const ZONE_UNLOCKED: u8 = 0x55;
+
+/// Reads the configuration zone and the monotonic counters of the device
+/// and interprets them: chip options, lock state, OTP mode and the policy of every slot.
+/// Meant for support tickets and audits, like the configuration dumps of Microchip tools
+pub fn config_report(device: &dyn AteccDeviceTrait) -> Result<ConfigReport, AtcaStatus> {
+    let mut config_data: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut config_data) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    let mut report = ConfigReport::from_config_zone(device.get_device_type(), &config_data)?;
+    report.counters = [device.counter_read(0).ok(), device.counter_read(1).ok()];
+    Ok(report)
+} // config_report()
+
+impl ConfigReport {
+    /// Interprets a configuration zone read from a chip of the given type, e.g. a dump
+    /// attached to a support ticket. The counters are not part of it and are left unknown
+    pub fn from_config_zone(
+        device_type: AtcaDeviceType,
+        config_data: &[u8],
+    ) -> Result<ConfigReport, AtcaStatus> {
+        if config_data.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let is_608 = device_type == AtcaDeviceType::ATECC608A;
+
+        let mut serial_number = [0; ATCA_SERIAL_NUM_SIZE];
+        serial_number[..IDX_REVISION].copy_from_slice(&config_data[..IDX_REVISION]);
+        serial_number[IDX_REVISION..]
+            .copy_from_slice(&config_data[IDX_SERIAL_NUMBER_END..IDX_SERIAL_NUMBER_END + 5]);
+        let mut revision = [0; ATCA_WORD_SIZE];
+        revision.copy_from_slice(&config_data[IDX_REVISION..IDX_SERIAL_NUMBER_END]);
+        let mut x509_format = [0; ATCA_WORD_SIZE];
+        x509_format.copy_from_slice(&config_data[IDX_X509_FORMAT..IDX_X509_FORMAT + 4]);
+
+        let mut slots: Vec<AtcaSlot> = Vec::new();
+        atcab_get_config_from_config_zone(config_data, &mut slots);
+        let count_match_key = match is_608 {
+            true => slots
+                .iter()
+                .find(|slot| slot.config.count_match)
+                .map(|slot| slot.id),
+            false => {
+                // CountMatch of ATECC608x takes the place of OTPmode of the older chips
+                for slot in slots.iter_mut() {
+                    slot.config.count_match = false;
+                }
+                None
+            }
+        };
+        let otp_mode = match (is_608, config_data[IDX_OTP_MODE]) {
+            (true, _) => None,
+            (false, 0xAA) => Some(OtpMode::ReadOnly),
+            (false, 0x55) => Some(OtpMode::Consumption),
+            (false, 0x00) => Some(OtpMode::Legacy),
+            (false, value) => Some(OtpMode::Rfu(value)),
+        };
+
+        Ok(ConfigReport {
+            device_type,
+            serial_number,
+            revision,
+            config_zone_locked: config_data[IDX_LOCK_CONFIG] != ZONE_UNLOCKED,
+            data_zone_locked: config_data[IDX_LOCK_VALUE] != ZONE_UNLOCKED,
+            i2c_enabled: (config_data[IDX_I2C_ENABLE] & 1) != 0,
+            i2c_address: config_data[IDX_I2C_ADDRESS],
+            chip_mode: config_data[IDX_CHIP_MODE],
+            chip_options: chip_options(config_data, is_608),
+            otp_mode,
+            count_match_key,
+            counters: [None, None],
+            x509_format,
+            slots,
+        })
+    } // ConfigReport::from_config_zone()
+
+    /// The report as a JSON object, enumerations given by the names of their variants
+    pub fn to_json(&self) -> String {
+        let options = &self.chip_options;
+        let slots: Vec<String> = self.slots.iter().map(slot_json).collect();
+        format!(
+            concat!(
+                r#"{{"device_type":"{}","serial_number":"{}","revision":"{}","#,
+                r#""config_zone_locked":{},"data_zone_locked":{},"i2c_enabled":{},"#,
+                r#""i2c_address":{},"chip_mode":{},"chip_options":{{"aes_enabled":{},"#,
+                r#""kdf_aes_enabled":{},"io_key_enabled":{},"io_key_in_slot":{},"#,
+                r#""ecdh_output_protection":"{:?}","kdf_output_protection":"{:?}"}},"#,
+                r#""otp_mode":{},"count_match_key":{},"counters":[{},{}],"#,
+                r#""x509_format":"{}","slots":[{}]}}"#
+            ),
+            self.device_type,
+            to_hex(&self.serial_number),
+            to_hex(&self.revision),
+            self.config_zone_locked,
+            self.data_zone_locked,
+            self.i2c_enabled,
+            self.i2c_address,
+            self.chip_mode,
+            options.aes_enabled,
+            options.kdf_aes_enabled,
+            options.io_key_enabled,
+            options.io_key_in_slot,
+            options.ecdh_output_protection,
+            options.kdf_output_protection,
+            json_option(self.otp_mode.map(|mode| format!(r#""{:?}""#, mode))),
+            json_option(self.count_match_key),
+            json_option(self.counters[0]),
+            json_option(self.counters[1]),
+            to_hex(&self.x509_format),
+            slots.join(",")
+        )
+    } // ConfigReport::to_json()
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = &self.chip_options;
+        writeln!(f, "Device type:            {}", self.device_type)?;
+        writeln!(f, "Serial number:          {}", to_hex(&self.serial_number))?;
+        writeln!(f, "Revision:               {}", to_hex(&self.revision))?;
+        writeln!(f, "Config zone locked:     {}", self.config_zone_locked)?;
+        writeln!(f, "Data zone locked:       {}", self.data_zone_locked)?;
+        match self.i2c_enabled {
+            true => writeln!(
+                f,
+                "Interface:              I2C, address 0x{:02X}",
+                self.i2c_address
+            )?,
+            false => writeln!(f, "Interface:              SWI")?,
+        }
+        writeln!(f, "Chip mode:              0x{:02X}", self.chip_mode)?;
+        writeln!(f, "AES enabled:            {}", options.aes_enabled)?;
+        writeln!(f, "KDF AES enabled:        {}", options.kdf_aes_enabled)?;
+        match options.io_key_enabled {
+            true => writeln!(f, "IO protection key:      slot {}", options.io_key_in_slot)?,
+            false => writeln!(f, "IO protection key:      disabled")?,
+        }
+        writeln!(
+            f,
+            "ECDH output:            {:?}",
+            options.ecdh_output_protection
+        )?;
+        writeln!(
+            f,
+            "KDF output:             {:?}",
+            options.kdf_output_protection
+        )?;
+        if let Some(otp_mode) = self.otp_mode {
+            writeln!(f, "OTP mode:               {:?}", otp_mode)?;
+        }
+        match self.count_match_key {
+            Some(slot_id) => writeln!(f, "Counter match:          key in slot {}", slot_id)?,
+            None => writeln!(f, "Counter match:          disabled")?,
+        }
+        for (counter_id, counter) in self.counters.iter().enumerate() {
+            match counter {
+                Some(value) => writeln!(f, "Counter {}:              {}", counter_id, value)?,
+                None => writeln!(f, "Counter {}:              unknown", counter_id)?,
+            }
+        }
+        writeln!(f, "X509 format:            {}", to_hex(&self.x509_format))?;
+        for slot in self.slots.iter() {
+            writeln!(f, "{}", slot)?;
+        }
+        Ok(())
+    }
+}
+
+/// ChipOptions as stored in the configuration zone. The options other than AES
+/// are those of ATECC608x, the older chips do not have them
+fn chip_options(config_data: &[u8], is_608: bool) -> ChipOptions {
+    const IO_KEY_EN_POS: u8 = 1;
+    const KDF_AES_EN_POS: u8 = 2;
+
+    let mut chip_options = ChipOptions {
+        aes_enabled: is_608 && (config_data[IDX_AES_ENABLE] & 1) != 0,
+        ..Default::default()
+    };
+    if is_608 {
+        let options = config_data[IDX_CHIP_OPTIONS];
+        let protection = config_data[IDX_CHIP_OPTIONS + 1];
+        chip_options.io_key_enabled = ((options >> IO_KEY_EN_POS) & 1) != 0;
+        chip_options.kdf_aes_enabled = ((options >> KDF_AES_EN_POS) & 1) != 0;
+        chip_options.io_key_in_slot = (protection >> 4) & 0b00001111;
+        chip_options.ecdh_output_protection = (protection & 0b00000011).into();
+        chip_options.kdf_output_protection = ((protection >> 2) & 0b00000011).into();
+    }
+    chip_options
+}
+
+impl fmt::Display for SlotConfig {
+    /// One line summarizing the key type and the policy of the slot,
+    /// e.g. "P256, private, secret, write=Encrypt(key 6), read=never, lockable"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let attr = &self.ecc_key_attr;
+        let key_type = match self.key_type {
+            KeyType::P256EccKey => "P256",
+            KeyType::Aes => "AES",
+            KeyType::ShaOrText => "SHA/text",
+            KeyType::Rfu => "RFU",
+        };
+        write!(f, "{}", key_type)?;
+        let private_key = (KeyType::P256EccKey == self.key_type) && attr.is_private;
+        if private_key {
+            write!(f, ", private")?;
+        }
+        if self.is_secret {
+            write!(f, ", secret")?;
+        }
+        match self.write_config {
+            WriteConfig::Always => write!(f, ", write=always")?,
+            WriteConfig::PubInvalid => write!(f, ", write=pub-invalid")?,
+            WriteConfig::Never => write!(f, ", write=never")?,
+            WriteConfig::Encrypt => write!(f, ", write=Encrypt(key {})", self.write_key)?,
+            WriteConfig::Rfu => write!(f, ", write=rfu")?,
+        }
+        if private_key || (self.is_secret && !self.read_key.encrypt_read) {
+            write!(f, ", read=never")?;
+        } else if self.read_key.encrypt_read {
+            write!(f, ", read=Encrypt(key {})", self.read_key.slot_number)?;
+        } else {
+            write!(f, ", read=always")?;
+        }
+        let flags = [
+            (private_key && attr.ext_sign, "ext-sign"),
+            (private_key && attr.int_sign, "int-sign"),
+            (private_key && attr.ecdh_operation, "ecdh"),
+            (
+                private_key && attr.ecdh_operation && attr.ecdh_secret_out,
+                "ecdh-secret-out",
+            ),
+            (self.limited_use, "limited-use"),
+            (self.count_match, "count-match"),
+            (self.no_mac, "no-mac"),
+            (self.persistent_disable, "persistent-disable"),
+            (self.req_random, "req-random"),
+        ];
+        for (_, name) in flags.iter().filter(|(set, _)| *set) {
+            write!(f, ", {}", name)?;
+        }
+        if self.req_auth {
+            write!(f, ", auth(key {})", self.auth_key)?;
+        }
+        if self.lockable {
+            write!(f, ", lockable")?;
+        }
+        if self.pub_info {
+            write!(f, ", pub-info")?;
+        }
+        if self.x509id != 0 {
+            write!(f, ", x509 format {}", self.x509id)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AtcaSlot {
+    /// The policy of the slot preceded by its number and followed by its lock state
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Slot {:>2}: {}", self.id, self.config)?;
+        if self.is_locked {
+            write!(f, ", locked")?;
+        }
+        Ok(())
+    }
+}
+
+fn slot_json(slot: &AtcaSlot) -> String {
+    let config = &slot.config;
+    let attr = &config.ecc_key_attr;
+    format!(
+        concat!(
+            r#"{{"id":{},"key_type":"{:?}","write_config":"{:?}","write_key":{},"#,
+            r#""read_key":{},"encrypt_read":{},"is_secret":{},"is_private":{},"#,
+            r#""ext_sign":{},"int_sign":{},"ecdh_operation":{},"ecdh_secret_out":{},"#,
+            r#""limited_use":{},"count_match":{},"no_mac":{},"persistent_disable":{},"#,
+            r#""req_auth":{},"auth_key":{},"req_random":{},"lockable":{},"pub_info":{},"#,
+            r#""x509id":{},"is_locked":{}}}"#
+        ),
+        slot.id,
+        config.key_type,
+        config.write_config,
+        config.write_key,
+        config.read_key.slot_number,
+        config.read_key.encrypt_read,
+        config.is_secret,
+        attr.is_private,
+        attr.ext_sign,
+        attr.int_sign,
+        attr.ecdh_operation,
+        attr.ecdh_secret_out,
+        config.limited_use,
+        config.count_match,
+        config.no_mac,
+        config.persistent_disable,
+        config.req_auth,
+        config.auth_key,
+        config.req_random,
+        config.lockable,
+        config.pub_info,
+        config.x509id,
+        slot.is_locked
+    )
+}
+
+fn json_option<T: fmt::Display>(value: Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("null"),
+    }
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,455 @@
+use super::hw_impl::atcab_get_config_from_config_zone;
+use super::{AtcaDeviceType, AtcaSlot, AtcaStatus, ChipOptions, ConfigZone, ConfigZoneBuilder};
+use super::{KeyType, OtpMode, OutputProtectionState, SlotConfig, WriteConfig, X509Format};
+use super::{
+    ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ATSHA_CONFIG_BUFFER_SIZE,
+    ATCA_SERIAL_NUM_SIZE, ATCA_WORD_SIZE,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+const IDX_REVISION: usize = 4;
+const IDX_SERIAL_NUMBER_END: usize = 8;
+const IDX_AES_ENABLE: usize = 13;
+const IDX_I2C_ENABLE: usize = 14;
+const IDX_I2C_ADDRESS: usize = 16;
+const IDX_COUNT_MATCH: usize = 18;
+const IDX_CHIP_MODE: usize = 19;
+const IDX_SLOT_CONFIG: usize = 20;
+const IDX_COUNTERS: usize = 52;
+const IDX_USE_LOCK: usize = 68;
+const IDX_VOLATILE_KEY_PERMISSION: usize = 69;
+const IDX_SECURE_BOOT: usize = 70;
+const IDX_KDF_IV_LOC: usize = 72;
+const IDX_KDF_IV_STR: usize = 73;
+const IDX_RESERVED_75: usize = 75;
+const IDX_USER_EXTRA: usize = 84;
+const IDX_USER_EXTRA_ADD: usize = 85;
+const IDX_LOCK_VALUE: usize = 86;
+const IDX_LOCK_CONFIG: usize = 87;
+const IDX_SLOT_LOCKED: usize = 88;
+const IDX_CHIP_OPTIONS: usize = 90;
+const IDX_X509_FORMAT: usize = 92;
+const IDX_KEY_CONFIG: usize = 96;
+/// Size of one monotonic counter in the configuration zone
+const COUNTER_SIZE: usize = 8;
+/// Value of the lock bytes of a zone that is not locked
+const ZONE_UNLOCKED: u8 = 0x55;
+/// Default I2C addresses, in the 8-bit form
+const ATECC_DEFAULT_I2C_ADDRESS: u8 = 0xC0;
+const ATSHA_DEFAULT_I2C_ADDRESS: u8 = 0xC8;
+/// x509id: bits 14-15 of KeyConfig
+const KEY_CONFIG_X509ID_SHIFT: u16 = 14;
+
+impl TryFrom<&[u8]> for ConfigZone {
+    type Error = AtcaStatus;
+
+    /// Splits a configuration zone image, as read by read_config_zone(), into its fields
+    fn try_from(config_data: &[u8]) -> Result<Self, Self::Error> {
+        if config_data.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let word = |idx: usize| u16::from_le_bytes([config_data[idx], config_data[idx + 1]]);
+        let words = |idx: usize| {
+            let mut words = [0; ATCA_ATECC_SLOTS_COUNT as usize];
+            for (pos, value) in words.iter_mut().enumerate() {
+                *value = word(idx + 2 * pos);
+            }
+            words
+        };
+
+        let mut zone = ConfigZone {
+            serial_number: [0; ATCA_SERIAL_NUM_SIZE],
+            revision: [0; IDX_SERIAL_NUMBER_END - IDX_REVISION],
+            aes_enable: config_data[IDX_AES_ENABLE],
+            i2c_enable: config_data[IDX_I2C_ENABLE],
+            reserved_15: config_data[IDX_I2C_ENABLE + 1],
+            i2c_address: config_data[IDX_I2C_ADDRESS],
+            reserved_17: config_data[IDX_I2C_ADDRESS + 1],
+            count_match: config_data[IDX_COUNT_MATCH],
+            chip_mode: config_data[IDX_CHIP_MODE],
+            slot_config: words(IDX_SLOT_CONFIG),
+            counters: [[0; COUNTER_SIZE]; 2],
+            use_lock: config_data[IDX_USE_LOCK],
+            volatile_key_permission: config_data[IDX_VOLATILE_KEY_PERMISSION],
+            secure_boot: word(IDX_SECURE_BOOT),
+            kdf_iv_loc: config_data[IDX_KDF_IV_LOC],
+            kdf_iv_str: [config_data[IDX_KDF_IV_STR], config_data[IDX_KDF_IV_STR + 1]],
+            reserved_75: [0; IDX_USER_EXTRA - IDX_RESERVED_75],
+            user_extra: config_data[IDX_USER_EXTRA],
+            user_extra_add: config_data[IDX_USER_EXTRA_ADD],
+            lock_value: config_data[IDX_LOCK_VALUE],
+            lock_config: config_data[IDX_LOCK_CONFIG],
+            slot_locked: word(IDX_SLOT_LOCKED),
+            chip_options: word(IDX_CHIP_OPTIONS),
+            x509_format: [0; IDX_KEY_CONFIG - IDX_X509_FORMAT],
+            key_config: words(IDX_KEY_CONFIG),
+        };
+        zone.serial_number[..IDX_REVISION].copy_from_slice(&config_data[..IDX_REVISION]);
+        zone.serial_number[IDX_REVISION..]
+            .copy_from_slice(&config_data[IDX_SERIAL_NUMBER_END..IDX_AES_ENABLE]);
+        zone.revision
+            .copy_from_slice(&config_data[IDX_REVISION..IDX_SERIAL_NUMBER_END]);
+        for (pos, counter) in zone.counters.iter_mut().enumerate() {
+            let start = IDX_COUNTERS + pos * COUNTER_SIZE;
+            counter.copy_from_slice(&config_data[start..start + COUNTER_SIZE]);
+        }
+        zone.reserved_75
+            .copy_from_slice(&config_data[IDX_RESERVED_75..IDX_USER_EXTRA]);
+        zone.x509_format
+            .copy_from_slice(&config_data[IDX_X509_FORMAT..IDX_KEY_CONFIG]);
+        Ok(zone)
+    } // ConfigZone::try_from()
+}
+
+impl ConfigZone {
+    /// The configuration zone image the fields make up, byte for byte the one
+    /// they were taken from
+    pub fn to_bytes(&self) -> [u8; ATCA_ATECC_CONFIG_BUFFER_SIZE] {
+        let mut config_data = [0; ATCA_ATECC_CONFIG_BUFFER_SIZE];
+        let mut put_words = |idx: usize, words: &[u16]| {
+            for (pos, value) in words.iter().enumerate() {
+                config_data[idx + 2 * pos..idx + 2 * pos + 2].copy_from_slice(&value.to_le_bytes());
+            }
+        };
+        put_words(IDX_SLOT_CONFIG, &self.slot_config);
+        put_words(IDX_SECURE_BOOT, &[self.secure_boot]);
+        put_words(IDX_SLOT_LOCKED, &[self.slot_locked, self.chip_options]);
+        put_words(IDX_KEY_CONFIG, &self.key_config);
+
+        config_data[..IDX_REVISION].copy_from_slice(&self.serial_number[..IDX_REVISION]);
+        config_data[IDX_REVISION..IDX_SERIAL_NUMBER_END].copy_from_slice(&self.revision);
+        config_data[IDX_SERIAL_NUMBER_END..IDX_AES_ENABLE]
+            .copy_from_slice(&self.serial_number[IDX_REVISION..]);
+        config_data[IDX_AES_ENABLE] = self.aes_enable;
+        config_data[IDX_I2C_ENABLE] = self.i2c_enable;
+        config_data[IDX_I2C_ENABLE + 1] = self.reserved_15;
+        config_data[IDX_I2C_ADDRESS] = self.i2c_address;
+        config_data[IDX_I2C_ADDRESS + 1] = self.reserved_17;
+        config_data[IDX_COUNT_MATCH] = self.count_match;
+        config_data[IDX_CHIP_MODE] = self.chip_mode;
+        for (pos, counter) in self.counters.iter().enumerate() {
+            let start = IDX_COUNTERS + pos * COUNTER_SIZE;
+            config_data[start..start + COUNTER_SIZE].copy_from_slice(counter);
+        }
+        config_data[IDX_USE_LOCK] = self.use_lock;
+        config_data[IDX_VOLATILE_KEY_PERMISSION] = self.volatile_key_permission;
+        config_data[IDX_KDF_IV_LOC] = self.kdf_iv_loc;
+        config_data[IDX_KDF_IV_STR..IDX_RESERVED_75].copy_from_slice(&self.kdf_iv_str);
+        config_data[IDX_RESERVED_75..IDX_USER_EXTRA].copy_from_slice(&self.reserved_75);
+        config_data[IDX_USER_EXTRA] = self.user_extra;
+        config_data[IDX_USER_EXTRA_ADD] = self.user_extra_add;
+        config_data[IDX_LOCK_VALUE] = self.lock_value;
+        config_data[IDX_LOCK_CONFIG] = self.lock_config;
+        config_data[IDX_X509_FORMAT..IDX_KEY_CONFIG].copy_from_slice(&self.x509_format);
+        config_data
+    } // ConfigZone::to_bytes()
+
+    pub fn is_config_zone_locked(&self) -> bool {
+        self.lock_config != ZONE_UNLOCKED
+    }
+
+    pub fn is_data_zone_locked(&self) -> bool {
+        self.lock_value != ZONE_UNLOCKED
+    }
+
+    /// Configuration and lock state of every slot, as get_config() gives them
+    pub fn slots(&self) -> Vec<AtcaSlot> {
+        let mut slots: Vec<AtcaSlot> = Vec::new();
+        atcab_get_config_from_config_zone(&self.to_bytes(), &mut slots);
+        slots
+    }
+
+    /// The X509format entry the x509id of 'slot_id' selects
+    pub fn x509_format(&self, slot_id: u8) -> Result<X509Format, AtcaStatus> {
+        let key_config = self
+            .key_config
+            .get(slot_id as usize)
+            .ok_or(AtcaStatus::AtcaInvalidId)?;
+        let x509id = (key_config >> KEY_CONFIG_X509ID_SHIFT) & 0x03;
+        Ok(X509Format::from(self.x509_format[x509id as usize]))
+    }
+}
+
+impl From<u8> for X509Format {
+    fn from(format: u8) -> Self {
+        X509Format {
+            public_position: format & 0x0F,
+            template_length: format >> 4,
+        }
+    }
+}
+
+impl X509Format {
+    /// Public keys in the slots using this entry can be validated only
+    /// by certificates of the given template length
+    pub fn is_restricted(&self) -> bool {
+        self.template_length != 0
+    }
+}
+
+impl ConfigZoneBuilder {
+    /// A chip of 'device_type' on I2C at its default address,
+    /// every slot holding data written and read in the clear
+    pub fn new(device_type: AtcaDeviceType) -> ConfigZoneBuilder {
+        ConfigZoneBuilder {
+            device_type,
+            i2c_enable: true,
+            i2c_address: match device_type {
+                AtcaDeviceType::ATSHA204A => ATSHA_DEFAULT_I2C_ADDRESS,
+                _ => ATECC_DEFAULT_I2C_ADDRESS,
+            },
+            chip_mode: 0x00,
+            chip_options: ChipOptions::default(),
+            otp_mode: None,
+            x509_format: [0; ATCA_WORD_SIZE],
+            slots: Vec::new(),
+        }
+    }
+
+    /// false: the chip communicates over SWI
+    pub fn set_i2c_enable(mut self, i2c_enable: bool) -> ConfigZoneBuilder {
+        self.i2c_enable = i2c_enable;
+        self
+    }
+
+    /// I2C address in the 8-bit form
+    pub fn set_i2c_address(mut self, i2c_address: u8) -> ConfigZoneBuilder {
+        self.i2c_address = i2c_address;
+        self
+    }
+
+    pub fn set_chip_mode(mut self, chip_mode: u8) -> ConfigZoneBuilder {
+        self.chip_mode = chip_mode;
+        self
+    }
+
+    /// ATECC608x only, the older chips must keep the default options
+    pub fn set_chip_options(mut self, chip_options: ChipOptions) -> ConfigZoneBuilder {
+        self.chip_options = chip_options;
+        self
+    }
+
+    /// Not for ATECC608x, which has no OTP modes. The default is OtpMode::ReadOnly
+    pub fn set_otp_mode(mut self, otp_mode: OtpMode) -> ConfigZoneBuilder {
+        self.otp_mode = Some(otp_mode);
+        self
+    }
+
+    pub fn set_x509_format(mut self, x509_format: [u8; ATCA_WORD_SIZE]) -> ConfigZoneBuilder {
+        self.x509_format = x509_format;
+        self
+    }
+
+    /// Configuration of a slot, replacing the one given before.
+    /// The ReadKey bits of SlotConfig hold 'ecc_key_attr' for a private key
+    /// and 'read_key.slot_number' otherwise, the other one is not used.
+    /// WriteConfig::Never is written as 0x2, which still lets GenKey create
+    /// a private key, and WriteConfig::Encrypt as 0x4
+    pub fn set_slot(mut self, slot_id: u8, config: SlotConfig) -> ConfigZoneBuilder {
+        self.slots.retain(|(id, _)| *id != slot_id);
+        self.slots.push((slot_id, config));
+        self
+    }
+
+    /// The configuration zone image, AtcaBadParam if the configuration breaks a rule
+    /// of the datasheet: a setting the chip does not have, bits out of range
+    /// or settings that depend on each other, e.g. a private key that is not secret
+    pub fn build(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let (size, is_608) = match self.device_type {
+            AtcaDeviceType::ATSHA204A => (ATCA_ATSHA_CONFIG_BUFFER_SIZE, false),
+            AtcaDeviceType::ATECC108A | AtcaDeviceType::ATECC508A => {
+                (ATCA_ATECC_CONFIG_BUFFER_SIZE, false)
+            }
+            AtcaDeviceType::ATECC608A => (ATCA_ATECC_CONFIG_BUFFER_SIZE, true),
+            _ => return Err(AtcaStatus::AtcaBadParam),
+        };
+        let is_sha = size == ATCA_ATSHA_CONFIG_BUFFER_SIZE;
+        if (!is_608 && (self.chip_options != ChipOptions::default()))
+            || (is_608 && self.otp_mode.is_some())
+            || (is_sha && (self.x509_format != [0; ATCA_WORD_SIZE]))
+        {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        let mut slots = [data_slot_config(); ATCA_ATECC_SLOTS_COUNT as usize];
+        for (slot_id, config) in self.slots.iter() {
+            match slots.get_mut(*slot_id as usize) {
+                Some(slot) => *slot = *config,
+                None => return Err(AtcaStatus::AtcaInvalidId),
+            }
+        }
+        let mut count_match_key: Option<u8> = None;
+        for (slot_id, config) in slots.iter().enumerate() {
+            check_slot_config(slot_id as u8, config, is_608, is_sha)?;
+            if config.count_match {
+                if count_match_key.is_some() {
+                    return Err(AtcaStatus::AtcaBadParam);
+                }
+                count_match_key = Some(slot_id as u8);
+            }
+        }
+
+        let mut config_data = vec![0; size];
+        config_data[IDX_I2C_ENABLE] = self.i2c_enable as u8;
+        config_data[IDX_I2C_ADDRESS] = self.i2c_address;
+        config_data[IDX_COUNT_MATCH] = match (is_608, count_match_key) {
+            (true, Some(slot_id)) => (slot_id << 4) | 0x01,
+            (true, None) => 0x00,
+            (false, _) => otp_mode_byte(self.otp_mode.unwrap_or(OtpMode::ReadOnly)),
+        };
+        config_data[IDX_CHIP_MODE] = self.chip_mode;
+        for (slot_id, config) in slots.iter().enumerate() {
+            let pos = IDX_SLOT_CONFIG + 2 * slot_id;
+            config_data[pos..pos + 2].copy_from_slice(&slot_config_word(config).to_le_bytes());
+        }
+        // Counters of ATECC, UseFlag and UpdateCount of ATSHA204A, with their initial values
+        for (pos, byte) in config_data[IDX_COUNTERS..IDX_USE_LOCK]
+            .iter_mut()
+            .enumerate()
+        {
+            *byte = match is_sha {
+                true if pos % 2 == 0 => 0xFF,
+                true => 0x00,
+                false if pos % COUNTER_SIZE < COUNTER_SIZE / 2 => 0xFF,
+                false => 0x00,
+            };
+        }
+        if !is_608 {
+            // LastKeyUse, no use of slot 15 counted yet
+            for byte in config_data[IDX_USE_LOCK..IDX_USER_EXTRA].iter_mut() {
+                *byte = 0xFF;
+            }
+        }
+        config_data[IDX_LOCK_VALUE] = ZONE_UNLOCKED;
+        config_data[IDX_LOCK_CONFIG] = ZONE_UNLOCKED;
+        if is_sha {
+            return Ok(config_data);
+        }
+
+        if is_608 {
+            config_data[IDX_AES_ENABLE] = self.chip_options.aes_enabled as u8;
+            let options = chip_options_word(&self.chip_options)?;
+            config_data[IDX_CHIP_OPTIONS..IDX_X509_FORMAT].copy_from_slice(&options.to_le_bytes());
+        }
+        config_data[IDX_SLOT_LOCKED] = 0xFF;
+        config_data[IDX_SLOT_LOCKED + 1] = 0xFF;
+        config_data[IDX_X509_FORMAT..IDX_KEY_CONFIG].copy_from_slice(&self.x509_format);
+        for (slot_id, config) in slots.iter().enumerate() {
+            let pos = IDX_KEY_CONFIG + 2 * slot_id;
+            config_data[pos..pos + 2].copy_from_slice(&key_config_word(config).to_le_bytes());
+        }
+        Ok(config_data)
+    } // ConfigZoneBuilder::build()
+}
+
+/// A slot holding data written and read in the clear
+fn data_slot_config() -> SlotConfig {
+    SlotConfig {
+        key_type: KeyType::ShaOrText,
+        write_config: WriteConfig::Always,
+        ..Default::default()
+    }
+}
+
+/// Checks the settings of a slot against each other and against the chip
+fn check_slot_config(
+    slot_id: u8,
+    config: &SlotConfig,
+    is_608: bool,
+    is_sha: bool,
+) -> Result<(), AtcaStatus> {
+    let attr = &config.ecc_key_attr;
+    let invalid = (config.key_type == KeyType::Rfu)
+        || (config.write_config == WriteConfig::Rfu)
+        || (config.write_key > 0x0F)
+        || (config.auth_key > 0x0F)
+        || (config.read_key.slot_number > 0x0F)
+        || (config.x509id > 0x03)
+        || (attr.is_private && (config.key_type != KeyType::P256EccKey))
+        || (attr.is_private && !config.is_secret)
+        || (attr.is_private && attr.ecdh_operation && attr.ecdh_secret_out && ((slot_id & 1) != 0))
+        || (config.read_key.encrypt_read && !config.is_secret)
+        || (!config.req_auth && (config.auth_key != 0))
+        || (!is_608 && ((config.key_type == KeyType::Aes) || config.count_match))
+        || (is_sha
+            && ((config.key_type != KeyType::ShaOrText)
+                || attr.is_private
+                || config.pub_info
+                || config.lockable
+                || config.req_random
+                || config.req_auth
+                || config.persistent_disable
+                || (config.x509id != 0)));
+    match invalid {
+        true => Err(AtcaStatus::AtcaBadParam),
+        false => Ok(()),
+    }
+}
+
+fn slot_config_word(config: &SlotConfig) -> u16 {
+    let attr = &config.ecc_key_attr;
+    let read_key = match attr.is_private {
+        true => {
+            (attr.ext_sign as u8)
+                | ((attr.int_sign as u8) << 1)
+                | ((attr.ecdh_operation as u8) << 2)
+                | ((attr.ecdh_secret_out as u8) << 3)
+        }
+        false => config.read_key.slot_number,
+    };
+    let write_config: u8 = match config.write_config {
+        WriteConfig::Always => 0x0,
+        WriteConfig::PubInvalid => 0x1,
+        WriteConfig::Never | WriteConfig::Rfu => 0x2,
+        WriteConfig::Encrypt => 0x4,
+    };
+    let low = read_key
+        | ((config.no_mac as u8) << 4)
+        | ((config.limited_use as u8) << 5)
+        | ((config.read_key.encrypt_read as u8) << 6)
+        | ((config.is_secret as u8) << 7);
+    let high = config.write_key | (write_config << 4);
+    u16::from_le_bytes([low, high])
+}
+
+fn key_config_word(config: &SlotConfig) -> u16 {
+    let key_type: u8 = match config.key_type {
+        KeyType::P256EccKey => 4,
+        KeyType::Aes => 6,
+        KeyType::ShaOrText | KeyType::Rfu => 7,
+    };
+    let low = (config.ecc_key_attr.is_private as u8)
+        | ((config.pub_info as u8) << 1)
+        | (key_type << 2)
+        | ((config.lockable as u8) << 5)
+        | ((config.req_random as u8) << 6)
+        | ((config.req_auth as u8) << 7);
+    let high = config.auth_key | ((config.persistent_disable as u8) << 4) | (config.x509id << 6);
+    u16::from_le_bytes([low, high])
+}
+
+/// ChipOptions of ATECC608x, the IO protection key slot and the output protection states
+fn chip_options_word(options: &ChipOptions) -> Result<u16, AtcaStatus> {
+    if (options.io_key_in_slot > 0x0F)
+        || (options.ecdh_output_protection == OutputProtectionState::Invalid)
+        || (options.kdf_output_protection == OutputProtectionState::Invalid)
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let low = ((options.io_key_enabled as u8) << 1) | ((options.kdf_aes_enabled as u8) << 2);
+    let high = (options.ecdh_output_protection as u8)
+        | ((options.kdf_output_protection as u8) << 2)
+        | (options.io_key_in_slot << 4);
+    Ok(u16::from_le_bytes([low, high]))
+}
+
+fn otp_mode_byte(otp_mode: OtpMode) -> u8 {
+    match otp_mode {
+        OtpMode::ReadOnly => 0xAA,
+        OtpMode::Consumption => 0x55,
+        OtpMode::Legacy => 0x00,
+        OtpMode::Rfu(value) => value,
+    }
+}
@@ -1,11 +1,15 @@
 /// ATECC/ATSHA EEPROM block size
 pub const ATCA_BLOCK_SIZE: usize = cryptoauthlib_sys::ATCA_BLOCK_SIZE as usize;
+/// ATECC/ATSHA EEPROM word size
+pub const ATCA_WORD_SIZE: usize = cryptoauthlib_sys::ATCA_WORD_SIZE as usize;
 /// Number of random bytes generated by atcab_random call
 pub const ATCA_RANDOM_BUFFER_SIZE: usize = ATCA_BLOCK_SIZE;
 /// Size of a configuration buffer size in ATSHA 204A/206A
 pub const ATCA_ATSHA_CONFIG_BUFFER_SIZE: usize = cryptoauthlib_sys::ATCA_SHA_CONFIG_SIZE as usize;
 /// Size of a configuration buffer size in ATECCx08
 pub const ATCA_ATECC_CONFIG_BUFFER_SIZE: usize = cryptoauthlib_sys::ATCA_ECC_CONFIG_SIZE as usize;
+/// Size of the OTP zone of ATECCx08
+pub const ATCA_OTP_SIZE: usize = cryptoauthlib_sys::ATCA_OTP_SIZE as usize;
 pub const ATCA_ZONE_CONFIG: u8 = 0x00;
 pub const ATCA_ZONE_OTP: u8 = 0x01;
 pub const ATCA_ZONE_DATA: u8 = 0x02;
@@ -15,6 +19,17 @@ pub const ATCA_LOCK_ZONE_DATA: u8 = 0x01;
 pub const ATCA_ATECC_SLOTS_COUNT: u8 = 16;
 /// Minimum ATECC slot number where a public ECC key can be stored
 pub const ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY: u8 = 8;
+/// Time (in microseconds) between the wake token and the first command on SWI (tWHI),
+/// used when the interface configuration does not set a wake delay
+pub const ATCA_SWI_WAKE_DELAY: u16 = 1500;
+/// USB vendor ID of Microchip (Atmel) development kits
+pub const ATCA_KIT_HID_VID: u32 = 0x03EB;
+/// USB product ID of the AT88CK590 and CryptoTrust kits
+pub const ATCA_KIT_HID_PID: u32 = 0x2312;
+/// Size of a HID report of the kits
+pub const ATCA_KIT_HID_PACKET_SIZE: u32 = 64;
+/// Default baud rate of the serial port of UART kits
+pub const ATCA_KIT_UART_BAUD: u32 = 115200;
 /// Chip serial number size
 pub const ATCA_SERIAL_NUM_SIZE: usize = cryptoauthlib_sys::ATCA_SERIAL_NUM_SIZE as usize;
 /// ATECC/ATSHA temporary key slot identifier
@@ -32,9 +47,41 @@ pub const ATCA_SHA2_256_DIGEST_SIZE: usize = cryptoauthlib_sys::ATCA_SHA2_256_DI
 pub const ATCA_ATECC_PRIV_KEY_SIZE: usize = cryptoauthlib_sys::ATCA_PRIV_KEY_SIZE as usize;
 /// Public key size (in bytes) for elliptic curve P256 supported by ATECC
 pub const ATCA_ATECC_PUB_KEY_SIZE: usize = cryptoauthlib_sys::ATCA_PUB_KEY_SIZE as usize;
+/// Size (in bytes) of a public key in the SEC1 compressed encoding (0x02/0x03 || X)
+pub const ATCA_SEC1_COMPRESSED_PUB_KEY_SIZE: usize = ATCA_ATECC_PUB_KEY_SIZE / 2 + 1;
+/// Size (in bytes) of a public key in the SEC1 uncompressed encoding (0x04 || X || Y)
+pub const ATCA_SEC1_UNCOMPRESSED_PUB_KEY_SIZE: usize = ATCA_ATECC_PUB_KEY_SIZE + 1;
 /// Nonce NumIn size for random modes
 pub const ATCA_NONCE_NUMIN_SIZE: usize = cryptoauthlib_sys::NONCE_NUMIN_SIZE as usize;
 /// Nonce NumIn size for 32-byte pass-through mode
 pub const ATCA_NONCE_SIZE: usize = cryptoauthlib_sys::OUTNONCE_SIZE as usize;
 /// Size (in bytes) of ECDSA signature
 pub const ATCA_SIG_SIZE: usize = cryptoauthlib_sys::ATCA_SIG_SIZE as usize;
+/// Maximum size (in bytes) of a DER encoded ECDSA signature
+pub const ATCA_SIG_DER_MAX_SIZE: usize = ATCA_SIG_SIZE + 8;
+/// Size (in bytes) of ECDH premaster secret
+pub const ATCA_ECDH_PMS_SIZE: usize = ATCA_KEY_SIZE;
+/// Size (in bytes) by which a message grows when encrypted by ecies_encrypt()
+pub const ATCA_ECIES_OVERHEAD: usize =
+    ATCA_ATECC_PUB_KEY_SIZE + ATCA_AES_GCM_IV_STD_LENGTH + ATCA_AES_DATA_SIZE;
+/// Size (in bytes) of a compressed certificate
+pub const ATCA_COMP_CERT_SIZE: usize = 72;
+/// The largest value of a monotonic counter, it cannot be incremented any further
+pub const ATCA_COUNTER_MAX_VALUE: u32 = 2_097_151;
+/// Monotonic counter incremented by every use of a limited-use key
+pub const ATCA_LIMITED_USE_COUNTER_ID: u8 = 0;
+/// Slot of ATSHA206A holding the derived key, the one MAC authenticates with
+pub const ATCA_SHA206A_SYMMETRIC_KEY_SLOT: u8 = 0x07;
+/// Sign command mode for sign_base(): sign the message in TempKey, generated inside the chip
+/// (GenDig, GenKey)
+pub const ATCA_SIGN_MODE_INTERNAL: u8 = 0x00;
+/// Sign command mode bit for sign_base(): internal message, the signature is to be used
+/// with Verify(Invalidate)
+pub const ATCA_SIGN_MODE_INVALIDATE: u8 = 0x01;
+/// Sign command mode bit for sign_base(): ATECC608x reads the external message
+/// from the Message Digest Buffer instead of TempKey
+pub const ATCA_SIGN_MODE_SOURCE_MSGDIGBUF: u8 = 0x20;
+/// Sign command mode bit for sign_base(): internal message with the full serial number
+pub const ATCA_SIGN_MODE_INCLUDE_SN: u8 = 0x40;
+/// Sign command mode bit for sign_base(): sign an external message loaded by nonce()
+pub const ATCA_SIGN_MODE_EXTERNAL: u8 = 0x80;
@@ -0,0 +1,254 @@
+use super::{AtcaStatus, AteccDeviceTrait, VerifyMode};
+use super::{ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE};
+use alloc::vec::Vec;
+
+// CBOR major types (RFC 8949), shifted into the initial byte
+const CBOR_UNSIGNED: u8 = 0x00;
+const CBOR_NEGATIVE: u8 = 0x20;
+const CBOR_BYTE_STRING: u8 = 0x40;
+const CBOR_TEXT_STRING: u8 = 0x60;
+const CBOR_ARRAY: u8 = 0x80;
+const CBOR_MAP: u8 = 0xA0;
+const CBOR_TAG: u8 = 0xC0;
+
+/// CBOR tag of a COSE_Sign1 structure (RFC 8152)
+const COSE_SIGN1_TAG: u64 = 18;
+/// Header label of the algorithm
+const COSE_HEADER_ALG: u64 = 1;
+/// Header label of the key identifier
+const COSE_HEADER_KID: u64 = 4;
+/// Algorithm ES256 (-7) as the CBOR argument of a negative integer
+const COSE_ALG_ES256: u64 = 6;
+/// Context string of the structure signed for COSE_Sign1
+const COSE_SIGN1_CONTEXT: &str = "Signature1";
+
+/// Creates a tagged COSE_Sign1 structure (ES256) holding the payload,
+/// signed by ATECC with the P256 private key stored in the slot.
+/// The key identifier, if given, is placed in the unprotected header.
+/// External AAD is signed along with the payload, but is not a part of the structure.
+pub fn cose_sign1_sign(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    payload: &[u8],
+    kid: Option<&[u8]>,
+    external_aad: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    let protected = protected_header();
+    let signature =
+        device.sign_message(slot_id, &sig_structure(&protected, external_aad, payload))?;
+    Ok(cose_sign1_encode(&protected, kid, payload, &signature))
+} // cose_sign1_sign()
+
+/// Verifies a COSE_Sign1 structure (ES256, tagged or not) with a P256 public key (X || Y),
+/// hashing and verification are done by ATECC.
+/// Returns the payload of a valid structure and None if the signature does not match.
+pub fn cose_sign1_verify(
+    device: &dyn AteccDeviceTrait,
+    cose_sign1: &[u8],
+    public_key: &[u8],
+    external_aad: &[u8],
+) -> Result<Option<Vec<u8>>, AtcaStatus> {
+    let (protected, payload, signature) = cose_sign1_decode(cose_sign1)?;
+
+    let mut digest: Vec<u8> = Vec::with_capacity(ATCA_SHA2_256_DIGEST_SIZE);
+    let result = device.sha(sig_structure(protected, external_aad, payload), &mut digest);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    match device.verify_hash(
+        VerifyMode::External(public_key.to_vec()),
+        &digest,
+        signature,
+    )? {
+        true => Ok(Some(payload.to_vec())),
+        false => Ok(None),
+    }
+} // cose_sign1_verify()
+
+/// Encodes the protected header: a map with the algorithm (ES256)
+pub(crate) fn protected_header() -> Vec<u8> {
+    let mut result = cbor_head(CBOR_MAP, 1);
+    result.extend_from_slice(&cbor_head(CBOR_UNSIGNED, COSE_HEADER_ALG));
+    result.extend_from_slice(&cbor_head(CBOR_NEGATIVE, COSE_ALG_ES256));
+    result
+}
+
+/// Encodes Sig_structure, the data signed for COSE_Sign1:
+/// [ "Signature1", protected, external_aad, payload ]
+pub(crate) fn sig_structure(protected: &[u8], external_aad: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut result = cbor_head(CBOR_ARRAY, 4);
+    result.extend_from_slice(&cbor_string(
+        CBOR_TEXT_STRING,
+        COSE_SIGN1_CONTEXT.as_bytes(),
+    ));
+    result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, protected));
+    result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, external_aad));
+    result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, payload));
+    result
+}
+
+/// Encodes a tagged COSE_Sign1: 18([ protected, unprotected, payload, signature ])
+pub(crate) fn cose_sign1_encode(
+    protected: &[u8],
+    kid: Option<&[u8]>,
+    payload: &[u8],
+    signature: &[u8],
+) -> Vec<u8> {
+    let mut result = cbor_head(CBOR_TAG, COSE_SIGN1_TAG);
+    result.extend_from_slice(&cbor_head(CBOR_ARRAY, 4));
+    result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, protected));
+    match kid {
+        Some(kid) => {
+            result.extend_from_slice(&cbor_head(CBOR_MAP, 1));
+            result.extend_from_slice(&cbor_head(CBOR_UNSIGNED, COSE_HEADER_KID));
+            result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, kid));
+        }
+        None => result.extend_from_slice(&cbor_head(CBOR_MAP, 0)),
+    }
+    result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, payload));
+    result.extend_from_slice(&cbor_string(CBOR_BYTE_STRING, signature));
+    result
+}
+
+/// Encoded protected header, payload and signature of a COSE_Sign1
+type CoseSign1Parts<'a> = (&'a [u8], &'a [u8], &'a [u8]);
+
+/// Splits a COSE_Sign1 into the encoded protected header, the payload and the signature.
+/// The protected header must select ES256, a detached payload is not supported.
+pub(crate) fn cose_sign1_decode(data: &[u8]) -> Result<CoseSign1Parts<'_>, AtcaStatus> {
+    let mut pos: usize = 0;
+    let (mut major, mut value) = cbor_read_head(data, &mut pos)?;
+    if (CBOR_TAG, COSE_SIGN1_TAG) == (major, value) {
+        let head = cbor_read_head(data, &mut pos)?;
+        major = head.0;
+        value = head.1;
+    }
+    if (CBOR_ARRAY, 4) != (major, value) {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let protected = cbor_read_bytes(data, &mut pos)?;
+    cbor_skip(data, &mut pos)?;
+    let payload = cbor_read_bytes(data, &mut pos)?;
+    let signature = cbor_read_bytes(data, &mut pos)?;
+    if pos != data.len() || signature.len() != ATCA_SIG_SIZE || !is_es256(protected)? {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok((protected, payload, signature))
+}
+
+/// Checks that the encoded protected header selects the ES256 algorithm
+fn is_es256(protected: &[u8]) -> Result<bool, AtcaStatus> {
+    let mut pos: usize = 0;
+    let (major, count) = cbor_read_head(protected, &mut pos)?;
+    if CBOR_MAP != major {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let mut result = false;
+    for _ in 0..count {
+        let label_pos = pos;
+        if (CBOR_UNSIGNED, COSE_HEADER_ALG) == cbor_read_head(protected, &mut pos)? {
+            result = (CBOR_NEGATIVE, COSE_ALG_ES256) == cbor_read_head(protected, &mut pos)?;
+        } else {
+            pos = label_pos;
+            cbor_skip(protected, &mut pos)?;
+            cbor_skip(protected, &mut pos)?;
+        }
+    }
+    Ok(result && pos == protected.len())
+}
+
+/// Encodes the initial byte of a CBOR item with its argument
+fn cbor_head(major: u8, value: u64) -> Vec<u8> {
+    match value {
+        0..=23 => vec![major | value as u8],
+        24..=0xFF => vec![major | 24, value as u8],
+        0x100..=0xFFFF => {
+            let mut result = vec![major | 25];
+            result.extend_from_slice(&(value as u16).to_be_bytes());
+            result
+        }
+        0x1_0000..=0xFFFF_FFFF => {
+            let mut result = vec![major | 26];
+            result.extend_from_slice(&(value as u32).to_be_bytes());
+            result
+        }
+        _ => {
+            let mut result = vec![major | 27];
+            result.extend_from_slice(&value.to_be_bytes());
+            result
+        }
+    }
+}
+
+/// Encodes a byte or text string
+fn cbor_string(major: u8, content: &[u8]) -> Vec<u8> {
+    let mut result = cbor_head(major, content.len() as u64);
+    result.extend_from_slice(content);
+    result
+}
+
+/// Reads the major type and the argument of the item at 'pos'
+/// (indefinite lengths are not supported), moves 'pos' past the head
+fn cbor_read_head(data: &[u8], pos: &mut usize) -> Result<(u8, u64), AtcaStatus> {
+    let initial = *data.get(*pos).ok_or(AtcaStatus::AtcaBadParam)?;
+    let size: usize = match initial & 0x1F {
+        0..=23 => 0,
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+    let argument = data
+        .get((*pos + 1)..(*pos + 1 + size))
+        .ok_or(AtcaStatus::AtcaBadParam)?;
+    *pos += 1 + size;
+    let value = match size {
+        0 => (initial & 0x1F) as u64,
+        _ => argument.iter().fold(0u64, |acc, &x| (acc << 8) | x as u64),
+    };
+    Ok((initial & 0xE0, value))
+}
+
+/// Reads the content of a byte string
+fn cbor_read_bytes<'a>(data: &'a [u8], pos: &mut usize) -> Result<&'a [u8], AtcaStatus> {
+    match cbor_read_head(data, pos)? {
+        (CBOR_BYTE_STRING, len) => {
+            let start = *pos;
+            let content = data
+                .get(start..)
+                .and_then(|rest| rest.get(..len as usize))
+                .ok_or(AtcaStatus::AtcaBadParam)?;
+            *pos += content.len();
+            Ok(content)
+        }
+        _ => Err(AtcaStatus::AtcaBadParam),
+    }
+}
+
+/// Moves 'pos' past the item at 'pos', including nested items
+fn cbor_skip(data: &[u8], pos: &mut usize) -> Result<(), AtcaStatus> {
+    let (major, value) = cbor_read_head(data, pos)?;
+    match major {
+        CBOR_BYTE_STRING | CBOR_TEXT_STRING => {
+            if (data.len() - *pos) < value as usize {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            *pos += value as usize;
+        }
+        CBOR_ARRAY => {
+            for _ in 0..value {
+                cbor_skip(data, pos)?;
+            }
+        }
+        CBOR_MAP => {
+            for _ in 0..value {
+                cbor_skip(data, pos)?;
+                cbor_skip(data, pos)?;
+            }
+        }
+        CBOR_TAG => cbor_skip(data, pos)?,
+        _ => (),
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use super::ATCA_SHA2_256_DIGEST_SIZE;
+use super::{AtcaStatus, AteccDeviceTrait, CounterSignature, VerifyMode};
+use alloc::vec::Vec;
+
+/// Context string opening the message signed by sign_hash_with_counter(),
+/// so that the signature cannot be taken for one of a plain digest
+const COUNTER_SIGNATURE_CONTEXT: &[u8] = b"ATECC counter signature";
+
+/// Increments a monotonic counter and signs the digest together with the new value
+/// of the counter, using the P256 private key stored in the slot.
+/// A verifier keeping the last value it has accepted rejects any signature
+/// with a value that is not greater, so old signatures cannot be replayed.
+/// The counter is incremented even if the signing fails
+pub fn sign_hash_with_counter(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    counter_id: u8,
+    hash: &[u8],
+) -> Result<CounterSignature, AtcaStatus> {
+    if hash.len() != ATCA_SHA2_256_DIGEST_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let counter_value = device.counter_increment(counter_id)?;
+    let signature = device.sign_message(
+        slot_id,
+        &counter_signed_message(counter_id, counter_value, hash),
+    )?;
+    Ok(CounterSignature {
+        counter_id,
+        counter_value,
+        signature,
+    })
+} // sign_hash_with_counter()
+
+/// Verifies a signature made by sign_hash_with_counter() with a P256 public key (X || Y),
+/// hashing and verification are done by ATECC.
+/// Checking that the counter value has grown is left to the caller
+pub fn verify_hash_with_counter(
+    device: &dyn AteccDeviceTrait,
+    public_key: &[u8],
+    hash: &[u8],
+    counter_signature: &CounterSignature,
+) -> Result<bool, AtcaStatus> {
+    if hash.len() != ATCA_SHA2_256_DIGEST_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let message = counter_signed_message(
+        counter_signature.counter_id,
+        counter_signature.counter_value,
+        hash,
+    );
+    let mut digest: Vec<u8> = Vec::with_capacity(ATCA_SHA2_256_DIGEST_SIZE);
+    let result = device.sha(message, &mut digest);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    device.verify_hash(
+        VerifyMode::External(public_key.to_vec()),
+        &digest,
+        &counter_signature.signature,
+    )
+} // verify_hash_with_counter()
+
+/// Message signed by sign_hash_with_counter(), for verifiers without ATECC:
+/// context string || counter id || counter value (32-bit big-endian) || digest.
+/// The signature is made over SHA256 of the message
+pub fn counter_signed_message(counter_id: u8, counter_value: u32, hash: &[u8]) -> Vec<u8> {
+    let mut message: Vec<u8> = COUNTER_SIGNATURE_CONTEXT.to_vec();
+    message.push(counter_id);
+    message.extend_from_slice(&counter_value.to_be_bytes());
+    message.extend_from_slice(hash);
+    message
+} // counter_signed_message()
@@ -0,0 +1,172 @@
+use alloc::{string::String, vec::Vec};
+use std::net::IpAddr;
+
+use super::der::{
+    pem_encode, write_constructed, write_element, ASN1_BIT_STRING, ASN1_BOOLEAN, ASN1_CONTEXT_0,
+    ASN1_INTEGER, ASN1_OCTET_STRING, ASN1_OID, ASN1_PRINTABLE_STRING, ASN1_SEQUENCE, ASN1_SET,
+    ASN1_UTF8_STRING, OID_ECDSA_WITH_SHA256, OID_EC_PUBLIC_KEY, OID_PRIME256V1,
+};
+use super::signature::signature_to_der;
+use super::ATCA_ATECC_PUB_KEY_SIZE;
+use super::{AtcaStatus, AteccDeviceTrait, CsrExtension, CsrParam, DnAttribute, SubjectAltName};
+
+const PEM_LABEL_CSR: &str = "CERTIFICATE REQUEST";
+/// DER encoded OID 1.2.840.113549.1.9.14 (PKCS#9 extensionRequest)
+const OID_EXTENSION_REQUEST: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x0E];
+/// DER encoded OID 2.5.29.17 (subjectAltName)
+const OID_SUBJECT_ALT_NAME: &[u8] = &[0x55, 0x1D, 0x11];
+
+// Context-specific tags of the GeneralName choices
+const GENERAL_NAME_EMAIL: u8 = 0x81;
+const GENERAL_NAME_DNS: u8 = 0x82;
+const GENERAL_NAME_URI: u8 = 0x86;
+const GENERAL_NAME_IP: u8 = 0x87;
+
+/// Builds a PKCS#10 certificate signing request for the P256 key stored in the given slot.
+/// The request is signed by ATECC (the private key never leaves the chip)
+/// and is returned in the DER encoding.
+pub fn generate_csr(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    param: &CsrParam,
+) -> Result<Vec<u8>, AtcaStatus> {
+    let mut public_key: Vec<u8> = Vec::new();
+    let result = device.get_public_key(slot_id, &mut public_key);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+
+    let request_info = csr_request_info(&public_key, param)?;
+    let signature = signature_to_der(&device.sign_message(slot_id, &request_info)?)?;
+
+    let mut signature_bits: Vec<u8> = vec![0x00];
+    signature_bits.extend_from_slice(&signature);
+    Ok(write_constructed(
+        ASN1_SEQUENCE,
+        &[
+            request_info,
+            write_constructed(
+                ASN1_SEQUENCE,
+                &[write_element(ASN1_OID, OID_ECDSA_WITH_SHA256)],
+            ),
+            write_element(ASN1_BIT_STRING, &signature_bits),
+        ],
+    ))
+} // generate_csr()
+
+/// Converts a DER encoded certificate signing request into the PEM format
+pub fn csr_to_pem(csr: &[u8]) -> String {
+    pem_encode(csr, PEM_LABEL_CSR)
+} // csr_to_pem()
+
+/// Encodes the 'CertificationRequestInfo' structure, which is the signed part of a CSR
+fn csr_request_info(public_key: &[u8], param: &CsrParam) -> Result<Vec<u8>, AtcaStatus> {
+    if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    let subject: Vec<Vec<u8>> = param
+        .subject
+        .iter()
+        .map(|(attribute, value)| {
+            let (oid, string_type) = dn_attribute_encoding(*attribute);
+            write_constructed(
+                ASN1_SET,
+                &[write_constructed(
+                    ASN1_SEQUENCE,
+                    &[
+                        write_element(ASN1_OID, &oid),
+                        write_element(string_type, value.as_bytes()),
+                    ],
+                )],
+            )
+        })
+        .collect();
+
+    let mut public_key_bits: Vec<u8> = vec![0x00, 0x04];
+    public_key_bits.extend_from_slice(public_key);
+    let subject_public_key_info = write_constructed(
+        ASN1_SEQUENCE,
+        &[
+            write_constructed(
+                ASN1_SEQUENCE,
+                &[
+                    write_element(ASN1_OID, OID_EC_PUBLIC_KEY),
+                    write_element(ASN1_OID, OID_PRIME256V1),
+                ],
+            ),
+            write_element(ASN1_BIT_STRING, &public_key_bits),
+        ],
+    );
+
+    let mut extensions: Vec<Vec<u8>> = Vec::new();
+    if !param.subject_alt_names.is_empty() {
+        extensions.push(extension_encoding(&CsrExtension {
+            oid: OID_SUBJECT_ALT_NAME.to_vec(),
+            is_critical: false,
+            value: write_constructed(
+                ASN1_SEQUENCE,
+                &param
+                    .subject_alt_names
+                    .iter()
+                    .map(general_name_encoding)
+                    .collect::<Vec<Vec<u8>>>(),
+            ),
+        }));
+    }
+    extensions.extend(param.extensions.iter().map(extension_encoding));
+
+    let attributes: Vec<Vec<u8>> = match extensions.is_empty() {
+        true => Vec::new(),
+        false => vec![write_constructed(
+            ASN1_SEQUENCE,
+            &[
+                write_element(ASN1_OID, OID_EXTENSION_REQUEST),
+                write_constructed(ASN1_SET, &[write_constructed(ASN1_SEQUENCE, &extensions)]),
+            ],
+        )],
+    };
+
+    Ok(write_constructed(
+        ASN1_SEQUENCE,
+        &[
+            write_element(ASN1_INTEGER, &[0x00]),
+            write_constructed(ASN1_SEQUENCE, &subject),
+            subject_public_key_info,
+            write_constructed(ASN1_CONTEXT_0, &attributes),
+        ],
+    ))
+} // csr_request_info()
+
+/// Returns the OID and the string type used for a distinguished name attribute
+fn dn_attribute_encoding(attribute: DnAttribute) -> ([u8; 3], u8) {
+    match attribute {
+        DnAttribute::CommonName => ([0x55, 0x04, 0x03], ASN1_UTF8_STRING),
+        DnAttribute::SerialNumber => ([0x55, 0x04, 0x05], ASN1_PRINTABLE_STRING),
+        DnAttribute::Country => ([0x55, 0x04, 0x06], ASN1_PRINTABLE_STRING),
+        DnAttribute::Locality => ([0x55, 0x04, 0x07], ASN1_UTF8_STRING),
+        DnAttribute::StateOrProvince => ([0x55, 0x04, 0x08], ASN1_UTF8_STRING),
+        DnAttribute::Organization => ([0x55, 0x04, 0x0A], ASN1_UTF8_STRING),
+        DnAttribute::OrganizationalUnit => ([0x55, 0x04, 0x0B], ASN1_UTF8_STRING),
+    }
+} // dn_attribute_encoding()
+
+fn general_name_encoding(name: &SubjectAltName) -> Vec<u8> {
+    match name {
+        SubjectAltName::Dns(dns) => write_element(GENERAL_NAME_DNS, dns.as_bytes()),
+        SubjectAltName::Email(email) => write_element(GENERAL_NAME_EMAIL, email.as_bytes()),
+        SubjectAltName::Uri(uri) => write_element(GENERAL_NAME_URI, uri.as_bytes()),
+        SubjectAltName::Ip(IpAddr::V4(ip)) => write_element(GENERAL_NAME_IP, &ip.octets()),
+        SubjectAltName::Ip(IpAddr::V6(ip)) => write_element(GENERAL_NAME_IP, &ip.octets()),
+    }
+} // general_name_encoding()
+
+fn extension_encoding(extension: &CsrExtension) -> Vec<u8> {
+    let mut elements: Vec<Vec<u8>> = vec![write_element(ASN1_OID, &extension.oid)];
+    // The default value (false) is not encoded in DER
+    if extension.is_critical {
+        elements.push(write_element(ASN1_BOOLEAN, &[0xFF]));
+    }
+    elements.push(write_element(ASN1_OCTET_STRING, &extension.value));
+    write_constructed(ASN1_SEQUENCE, &elements)
+} // extension_encoding()
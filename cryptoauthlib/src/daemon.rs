@@ -0,0 +1,94 @@
+// Signing daemon: a process owning the ATECC device serves other processes
+// of the host over a unix domain socket. The frames and the encoding of requests and
+// responses are those of the remote backend, limited to the operations below.
+
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use super::remote::{
+    serve_stream, transact, verify_hash_request, WireReader, WireWriter, OP_GET_PUBLIC_KEY,
+    OP_RANDOM, OP_SIGN_HASH, OP_SIGN_MESSAGE, OP_VERIFY_HASH,
+};
+use super::{AtcaStatus, AteccDevice, VerifyMode};
+
+/// Operations available to the clients of the daemon
+fn is_daemon_operation(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        OP_RANDOM | OP_GET_PUBLIC_KEY | OP_SIGN_HASH | OP_SIGN_MESSAGE | OP_VERIFY_HASH
+    )
+}
+
+/// Serves the clients connecting to the listener, each connection in its own thread.
+/// Commands of concurrent clients are serialized by the device.
+/// Access to the daemon is controlled by the permissions of the socket file.
+/// Returns only if accepting a connection fails
+pub fn daemon_serve(device: Arc<AteccDevice>, listener: &UnixListener) -> std::io::Result<()> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let device = Arc::clone(&device);
+        thread::spawn(move || {
+            if let Err(err) =
+                serve_stream(device.as_ref().as_ref(), &mut stream, is_daemon_operation)
+            {
+                log::error!("Signing daemon connection failed: {}", err);
+            }
+        });
+    }
+    Ok(())
+} // daemon_serve()
+
+/// Handle of a process using the device through the signing daemon
+pub struct AteccDaemonClient {
+    stream: Mutex<UnixStream>,
+}
+
+impl AteccDaemonClient {
+    /// Connects to the daemon listening on the socket file
+    pub fn connect<P: AsRef<Path>>(socket_path: P) -> Result<AteccDaemonClient, AtcaStatus> {
+        let stream = UnixStream::connect(socket_path).map_err(|_| AtcaStatus::AtcaCommFail)?;
+        Ok(AteccDaemonClient {
+            stream: Mutex::new(stream),
+        })
+    } // AteccDaemonClient::connect()
+
+    fn call(&self, request: WireWriter) -> Result<Vec<u8>, AtcaStatus> {
+        let mut stream = self.stream.lock().map_err(|_| AtcaStatus::AtcaFuncFail)?;
+        transact(&mut *stream, request)
+    }
+
+    /// Generates a vector of random bytes
+    pub fn random(&self) -> Result<Vec<u8>, AtcaStatus> {
+        self.call(WireWriter::new(OP_RANDOM))
+    } // AteccDaemonClient::random()
+
+    /// Returns the public key of the private key stored in the given slot
+    /// or the public key stored in the slot
+    pub fn get_public_key(&self, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.call(WireWriter::new(OP_GET_PUBLIC_KEY).u8(slot_id))
+    } // AteccDaemonClient::get_public_key()
+
+    /// Signs a SHA256 digest with the private key stored in the given slot
+    pub fn sign_hash(&self, slot_id: u8, hash: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.call(WireWriter::new(OP_SIGN_HASH).u8(slot_id).bytes(hash))
+    } // AteccDaemonClient::sign_hash()
+
+    /// Hashes a message (SHA256) and signs the digest with the private key stored in the given slot
+    pub fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.call(WireWriter::new(OP_SIGN_MESSAGE).u8(slot_id).bytes(message))
+    } // AteccDaemonClient::sign_message()
+
+    /// Verifies a signature of a SHA256 digest with an external public key
+    /// or with the public key stored in a slot. The Mac modes are not supported
+    pub fn verify_hash(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let payload = self.call(verify_hash_request(mode, hash, signature)?)?;
+        Ok(WireReader::new(&payload).u8()? != 0)
+    } // AteccDaemonClient::verify_hash()
+}
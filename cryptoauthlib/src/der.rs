@@ -0,0 +1,180 @@
+use super::AtcaStatus;
+use alloc::{string::String, vec::Vec};
+
+pub(crate) const ASN1_BOOLEAN: u8 = 0x01;
+pub(crate) const ASN1_INTEGER: u8 = 0x02;
+pub(crate) const ASN1_BIT_STRING: u8 = 0x03;
+pub(crate) const ASN1_OCTET_STRING: u8 = 0x04;
+pub(crate) const ASN1_OID: u8 = 0x06;
+pub(crate) const ASN1_UTF8_STRING: u8 = 0x0C;
+pub(crate) const ASN1_PRINTABLE_STRING: u8 = 0x13;
+pub(crate) const ASN1_UTC_TIME: u8 = 0x17;
+pub(crate) const ASN1_GENERALIZED_TIME: u8 = 0x18;
+pub(crate) const ASN1_SEQUENCE: u8 = 0x30;
+pub(crate) const ASN1_SET: u8 = 0x31;
+pub(crate) const ASN1_CONTEXT_0: u8 = 0xA0;
+pub(crate) const ASN1_CONTEXT_3: u8 = 0xA3;
+
+/// DER encoded OID 1.2.840.10045.2.1 (id-ecPublicKey)
+pub(crate) const OID_EC_PUBLIC_KEY: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x02, 0x01];
+/// DER encoded OID 1.2.840.10045.3.1.7 (prime256v1, also known as secp256r1 or P256)
+pub(crate) const OID_PRIME256V1: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+/// DER encoded OID 1.2.840.10045.4.3.2 (ecdsa-with-SHA256)
+pub(crate) const OID_ECDSA_WITH_SHA256: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x02];
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Alphabet of the URL and filename safe variant of base64 (RFC 4648, section 5)
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Returns the tag of the first DER element in data, if there is any
+pub(crate) fn peek_tag(data: &[u8]) -> Option<u8> {
+    data.first().copied()
+}
+
+/// Splits off a DER element with the expected tag,
+/// returns its content and the remaining data
+pub(crate) fn read_element(tag: u8, data: &[u8]) -> Result<(&[u8], &[u8]), AtcaStatus> {
+    if data.len() < 2 || data[0] != tag {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let (length, header_size) = match data[1] {
+        len if len < 0x80 => (len as usize, 2),
+        0x81 if data.len() >= 3 && data[2] >= 0x80 => (data[2] as usize, 3),
+        0x82 if data.len() >= 4 && data[2] != 0x00 => {
+            (((data[2] as usize) << 8) | data[3] as usize, 4)
+        }
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+    if data.len() < header_size + length {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok((
+        &data[header_size..header_size + length],
+        &data[header_size + length..],
+    ))
+}
+
+/// Encodes a DER element with the given tag and content
+pub(crate) fn write_element(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut result: Vec<u8> = Vec::with_capacity(content.len() + 4);
+    result.push(tag);
+    match content.len() {
+        len if len < 0x80 => result.push(len as u8),
+        len if len <= 0xFF => result.extend_from_slice(&[0x81, len as u8]),
+        len => result.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]),
+    }
+    result.extend_from_slice(content);
+    result
+}
+
+/// Encodes a DER element whose content is a concatenation of other elements
+pub(crate) fn write_constructed(tag: u8, elements: &[Vec<u8>]) -> Vec<u8> {
+    write_element(tag, &elements.concat())
+}
+
+/// Encodes data as a PEM block with the given label, in lines of 64 characters
+pub(crate) fn pem_encode(der: &[u8], label: &str) -> String {
+    let encoded = base64_encode(der);
+    let mut result = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(64) {
+        result.push_str(&String::from_utf8_lossy(line));
+        result.push('\n');
+    }
+    result.push_str(&format!("-----END {}-----\n", label));
+    result
+}
+
+/// Decodes the base64 content of a PEM block with the given label
+/// ("-----BEGIN <label>-----" ... "-----END <label>-----")
+pub(crate) fn pem_decode(pem: &str, label: &str) -> Result<Vec<u8>, AtcaStatus> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let start = match pem.find(&begin) {
+        Some(idx) => idx + begin.len(),
+        None => return Err(AtcaStatus::AtcaBadParam),
+    };
+    let stop = match pem[start..].find(&end) {
+        Some(idx) => start + idx,
+        None => return Err(AtcaStatus::AtcaBadParam),
+    };
+    base64_decode(&pem[start..stop])
+}
+
+/// Encodes data in base64url, without padding
+pub(crate) fn base64url_encode(data: &[u8]) -> String {
+    base64_encode_with(data, BASE64URL_ALPHABET, false)
+}
+
+/// Decodes base64url data, padding is optional
+pub(crate) fn base64url_decode(data: &str) -> Result<Vec<u8>, AtcaStatus> {
+    base64_decode_with(data, BASE64URL_ALPHABET, false)
+}
+
+/// Encodes data in base64, with padding
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    base64_encode_with(data, BASE64_ALPHABET, true)
+}
+
+/// Decodes base64 data, ignoring whitespace
+fn base64_decode(data: &str) -> Result<Vec<u8>, AtcaStatus> {
+    base64_decode_with(data, BASE64_ALPHABET, true)
+}
+
+fn base64_encode_with(data: &[u8], alphabet: &[u8; 64], padding: bool) -> String {
+    let mut result = String::with_capacity(data.len() * 4 / 3 + 4);
+    for chunk in data.chunks(3) {
+        let block = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (idx, &x)| acc | ((x as u32) << (16 - 8 * idx)));
+        for idx in 0..4 {
+            match idx <= chunk.len() {
+                true => result.push(alphabet[((block >> (18 - 6 * idx)) & 0x3F) as usize] as char),
+                false if padding => result.push('='),
+                false => (),
+            }
+        }
+    }
+    result
+}
+
+fn base64_decode_with(
+    data: &str,
+    alphabet: &[u8; 64],
+    padding_required: bool,
+) -> Result<Vec<u8>, AtcaStatus> {
+    let mut result: Vec<u8> = Vec::with_capacity(data.len() * 3 / 4);
+    let mut accumulator: u32 = 0;
+    let mut bits: u32 = 0;
+    let mut padding: usize = 0;
+
+    for byte in data.bytes().filter(|x| !x.is_ascii_whitespace()) {
+        if byte == b'=' {
+            padding += 1;
+            continue;
+        }
+        let value = match alphabet.iter().position(|&x| x == byte) {
+            Some(val) if padding == 0 => val as u32,
+            _ => return Err(AtcaStatus::AtcaBadParam),
+        };
+        accumulator = (accumulator << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+    // Padding replaces the characters missing from the last 4-character group
+    let is_valid = match (padding, bits) {
+        (0, 0) | (1, 2) | (2, 4) => true,
+        (0, 2) | (0, 4) => !padding_required,
+        _ => false,
+    };
+    if !is_valid {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok(result)
+}
@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+use signature::{Error, Signer};
+
+use super::public_key::{public_key_from_sec1, public_key_to_sec1};
+use super::signature::signature_to_der;
+use super::ATCA_ATECC_PUB_KEY_SIZE;
+use super::{signature_error, AtcaStatus, AteccDeviceTrait, EcdhOutput, KeyType};
+
+/// TLS SignatureScheme of ECDSA P256 with SHA256 (RFC 8446, ecdsa_secp256r1_sha256)
+pub const TLS_ECDSA_SECP256R1_SHA256: u16 = 0x0403;
+
+/// DER encoded ECDSA signature, the form carried by the TLS CertificateVerify message
+#[derive(Clone, Debug, PartialEq)]
+pub struct TlsSignature(Vec<u8>);
+
+impl AsRef<[u8]> for TlsSignature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Signer for TLS stacks built on the RustCrypto 'signature' traits, such as embedded-tls,
+/// which take it (as a SignerMut) to sign the handshake transcript with the client key.
+/// The private key stays in the slot, the transcript is hashed and signed by ATECC.
+pub struct TlsSigner<'a> {
+    device: &'a dyn AteccDeviceTrait,
+    slot_id: u8,
+}
+
+impl<'a> TlsSigner<'a> {
+    /// Creates a signer for the P256 private key stored in the given slot
+    pub fn new(device: &'a dyn AteccDeviceTrait, slot_id: u8) -> Self {
+        TlsSigner { device, slot_id }
+    } // TlsSigner::new()
+
+    /// TLS SignatureScheme of the produced signatures
+    pub fn signature_scheme(&self) -> u16 {
+        TLS_ECDSA_SECP256R1_SHA256
+    }
+}
+
+impl Signer<TlsSignature> for TlsSigner<'_> {
+    /// Hashes the message (SHA256) and signs the digest, both on the chip
+    fn try_sign(&self, msg: &[u8]) -> Result<TlsSignature, Error> {
+        let signature = self
+            .device
+            .sign_message(self.slot_id, msg)
+            .map_err(signature_error)?;
+        let signature = signature_to_der(&signature).map_err(signature_error)?;
+        Ok(TlsSignature(signature))
+    } // TlsSigner::try_sign()
+}
+
+/// ECDHE key share of a TLS handshake (P256, the 'secp256r1' group)
+/// with the private key kept in a slot of ATECC.
+/// The slot must allow ECDH and return the premaster secret to the host,
+/// which the TLS stack then feeds into its key schedule.
+pub struct TlsKeyExchange<'a> {
+    device: &'a dyn AteccDeviceTrait,
+    slot_id: u8,
+    public_key: Vec<u8>,
+}
+
+impl<'a> TlsKeyExchange<'a> {
+    /// Generates a new (ephemeral) key pair in the slot, replacing the key it held
+    pub fn generate(device: &'a dyn AteccDeviceTrait, slot_id: u8) -> Result<Self, AtcaStatus> {
+        match device.gen_key(KeyType::P256EccKey, slot_id) {
+            AtcaStatus::AtcaSuccess => Self::from_slot(device, slot_id),
+            err => Err(err),
+        }
+    } // TlsKeyExchange::generate()
+
+    /// Uses the key pair already present in the slot (static ECDH)
+    pub fn from_slot(device: &'a dyn AteccDeviceTrait, slot_id: u8) -> Result<Self, AtcaStatus> {
+        let mut public_key: Vec<u8> = Vec::with_capacity(ATCA_ATECC_PUB_KEY_SIZE);
+        match device.get_public_key(slot_id, &mut public_key) {
+            AtcaStatus::AtcaSuccess => Ok(TlsKeyExchange {
+                device,
+                slot_id,
+                public_key,
+            }),
+            err => Err(err),
+        }
+    } // TlsKeyExchange::from_slot()
+
+    /// Key share to send to the peer: an uncompressed SEC1 point
+    pub fn public_key(&self) -> Result<Vec<u8>, AtcaStatus> {
+        public_key_to_sec1(&self.public_key)
+    } // TlsKeyExchange::public_key()
+
+    /// Computes the shared secret (the X coordinate of the shared point)
+    /// from the key share of the peer, an uncompressed SEC1 point
+    pub fn shared_secret(&self, peer_public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let peer_public_key = public_key_from_sec1(peer_public_key)?;
+        match self.device.ecdh(self.slot_id, &peer_public_key, None)? {
+            EcdhOutput::PreMasterSecret(secret) => Ok(secret),
+            _ => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // TlsKeyExchange::shared_secret()
+}
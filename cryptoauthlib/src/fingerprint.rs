@@ -0,0 +1,76 @@
+use super::{AtcaDeviceType, AtcaStatus, AteccDeviceTrait};
+use super::{ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_SHA2_256_DIGEST_SIZE};
+use alloc::vec::Vec;
+
+/// Context string opening the message hashed by fingerprint()
+const FINGERPRINT_CONTEXT: &[u8] = b"ATECC fingerprint";
+const IDX_REVISION: usize = 4;
+const IDX_REVISION_END: usize = 8;
+/// Counter[0] and Counter[1], changed by counter_increment()
+const COUNTERS: (usize, usize) = (52, 68);
+/// LastKeyUse of ATECC508A and older chips, changed by the uses of limited-use keys
+const LAST_KEY_USE: (usize, usize) = (68, 84);
+
+/// Identifier of the device for inventories: SHA256 of the serial number, the revision,
+/// the digest of the configuration zone and the public keys (X || Y) of the given slots,
+/// in that order, all calculated by ATECC.
+/// The bytes of the configuration zone the chip changes as it is used (the monotonic
+/// counters and, on the older chips, LastKeyUse) are left out of the digest,
+/// so the fingerprint stays the same until the chip is configured again or new keys
+/// are generated or imported into the given slots: a swapped or re-provisioned
+/// secure element gets another fingerprint
+pub fn fingerprint(
+    device: &dyn AteccDeviceTrait,
+    slots: &[u8],
+) -> Result<[u8; ATCA_SHA2_256_DIGEST_SIZE], AtcaStatus> {
+    let mut config_data: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut config_data) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    if config_data.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let mut message: Vec<u8> = FINGERPRINT_CONTEXT.to_vec();
+    message.extend_from_slice(&device.get_serial_number());
+    message.extend_from_slice(&config_data[IDX_REVISION..IDX_REVISION_END]);
+
+    let mut volatile: Vec<(usize, usize)> = vec![COUNTERS];
+    if device.get_device_type() != AtcaDeviceType::ATECC608A {
+        volatile.push(LAST_KEY_USE);
+    }
+    for (start, end) in volatile.iter() {
+        for byte in config_data[*start..*end].iter_mut() {
+            *byte = 0;
+        }
+    }
+    message.extend_from_slice(&sha256(device, config_data)?);
+
+    for slot_id in slots.iter() {
+        let mut public_key: Vec<u8> = Vec::new();
+        match device.get_public_key(*slot_id, &mut public_key) {
+            AtcaStatus::AtcaSuccess => (),
+            err => return Err(err),
+        }
+        message.push(*slot_id);
+        message.extend_from_slice(&public_key);
+    }
+    sha256(device, message)
+} // fingerprint()
+
+/// SHA256 calculated by ATECC
+fn sha256(
+    device: &dyn AteccDeviceTrait,
+    message: Vec<u8>,
+) -> Result<[u8; ATCA_SHA2_256_DIGEST_SIZE], AtcaStatus> {
+    let mut digest: Vec<u8> = Vec::with_capacity(ATCA_SHA2_256_DIGEST_SIZE);
+    match device.sha(message, &mut digest) {
+        AtcaStatus::AtcaSuccess if digest.len() == ATCA_SHA2_256_DIGEST_SIZE => {
+            let mut result = [0; ATCA_SHA2_256_DIGEST_SIZE];
+            result.copy_from_slice(&digest);
+            Ok(result)
+        }
+        AtcaStatus::AtcaSuccess => Err(AtcaStatus::AtcaInvalidSize),
+        err => Err(err),
+    }
+} // sha256()
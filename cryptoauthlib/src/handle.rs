@@ -0,0 +1,842 @@
+#[cfg(test)]
+use cryptoauthlib_sys::{atca_aes_cbc_ctx_t, atca_aes_ctr_ctx_t};
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::key_slot::key_uses_left;
+use super::sync::{with_wait_limit, WaitLimit};
+use super::{
+    AeadAlgorithm, AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaDeviceType, AtcaSha256Ctx, AtcaSlot,
+    AtcaStatus, AteccDevice, AteccDeviceTrait, CipherAlgorithm, EcdhOutput, GenDigZone,
+    InfoCmdType, IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget, KeyType,
+    KeyWrapAlgorithm, NonceTarget, OutputProtectionState, SignMode, SignatureFormat, TempKeyState,
+    TlsFinishedLabel, VerifyMode,
+};
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_RANDOM_BUFFER_SIZE, ATCA_SERIAL_NUM_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
+
+/// Interval between attempts to take the turn of a command
+/// that waits with a timeout or a cancellation token
+const TURN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+struct AteccShared {
+    device: AteccDevice,
+    /// Held for the duration of a command, or of a sequence of commands
+    /// executed through an AteccSession
+    commands: Mutex<()>,
+}
+
+/// Shared access to an ATECC device: a handle is cheap to clone and can be sent
+/// to and used by any number of threads. Commands issued through handles are serialized.
+/// Operations made of several commands (e.g. sha_start(), sha_update(), sha_finish()
+/// or the streaming AES modes) should be run within a session, so that commands
+/// of other threads are not interleaved with them.
+/// The device is released when the last handle is dropped.
+/// A command waits for the commands of the other threads without a limit,
+/// unless the handle is given a timeout or a cancellation token.
+/// The limit also bounds the wait for CryptoAuthLib, which is shared
+/// by all the devices, but a CryptoAuthLib call already in progress
+/// (e.g. blocked on the bus) cannot be interrupted.
+#[derive(Clone)]
+pub struct AteccHandle {
+    shared: Arc<AteccShared>,
+    /// The longest time a command waits for its turn
+    timeout: Option<Duration>,
+    /// Cancels the commands waiting for their turn
+    cancellation: Option<AtcaCancellationToken>,
+    /// Checks that a limited-use key has uses left before signing with it
+    check_key_uses: bool,
+}
+
+impl AteccHandle {
+    /// Creates the first handle of the device
+    pub fn new(device: AteccDevice) -> AteccHandle {
+        AteccHandle {
+            shared: Arc::new(AteccShared {
+                device,
+                commands: Mutex::new(()),
+            }),
+            timeout: None,
+            cancellation: None,
+            check_key_uses: false,
+        }
+    } // AteccHandle::new()
+
+    /// A handle of the same device whose commands wait for their turn at most
+    /// for the given time, then they fail with AtcaTimeout. The time covers both
+    /// the commands of the other handles and the lock of CryptoAuthLib held by
+    /// the commands of other devices. That lock is taken for each CryptoAuthLib call,
+    /// so a command made of several calls can time out between two of them.
+    /// A CryptoAuthLib call that has been made
+    /// (a command sent to the chip) is not interrupted, not even the one
+    /// of the timed-out command: the timeout applies only to the waiting
+    /// behind a hung bus or an unresponsive chip used by another thread
+    pub fn with_timeout(&self, timeout: Duration) -> AteccHandle {
+        AteccHandle {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    } // AteccHandle::with_timeout()
+
+    /// A handle of the same device whose commands waiting for their turn
+    /// (or for the lock of CryptoAuthLib) fail with AtcaCancelled once the token
+    /// is cancelled. A CryptoAuthLib call already in progress is not interrupted
+    pub fn with_cancellation(&self, token: AtcaCancellationToken) -> AteccHandle {
+        AteccHandle {
+            cancellation: Some(token),
+            ..self.clone()
+        }
+    } // AteccHandle::with_cancellation()
+
+    /// A handle of the same device whose signing commands first read the counter
+    /// of the limited-use keys. When the key has no uses left, they fail with
+    /// AtcaKeyUsesExhausted instead of the AtcaExecutionError reported by the chip
+    pub fn with_key_use_check(&self) -> AteccHandle {
+        AteccHandle {
+            check_key_uses: true,
+            ..self.clone()
+        }
+    } // AteccHandle::with_key_use_check()
+
+    /// Gives exclusive access to the device until the session is dropped.
+    /// Commands issued through the other handles wait for the end of the session,
+    /// the ones issued through this handle (and not the session) block forever
+    pub fn session(&self) -> AteccSession<'_> {
+        AteccSession {
+            device: self.shared.device.as_ref(),
+            _guard: self.lock(),
+        }
+    } // AteccHandle::session()
+
+    /// As session(), but the session waits for its turn only as long
+    /// as the timeout and the cancellation token of the handle allow
+    pub fn try_session(&self) -> Result<AteccSession<'_>, AtcaStatus> {
+        Ok(AteccSession {
+            device: self.shared.device.as_ref(),
+            _guard: self.wait_turn(&self.wait_limit())?,
+        })
+    } // AteccHandle::try_session()
+
+    /// Number of handles of the device
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.shared)
+    } // AteccHandle::handle_count()
+
+    fn lock(&self) -> MutexGuard<'_, ()> {
+        // The mutex does not protect any data, a panic of another thread does not matter
+        self.shared
+            .commands
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Limit of the waits of a command starting now, from the timeout
+    /// and the cancellation token of the handle
+    fn wait_limit(&self) -> WaitLimit {
+        WaitLimit {
+            deadline: self.timeout.map(|timeout| Instant::now() + timeout),
+            cancelled: self
+                .cancellation
+                .as_ref()
+                .map(|token| token.cancelled.clone()),
+        }
+    }
+
+    /// Takes the turn of a command, giving up on the timeout or the cancellation
+    fn wait_turn(&self, limit: &WaitLimit) -> Result<MutexGuard<'_, ()>, AtcaStatus> {
+        if limit.is_unbounded() {
+            return Ok(self.lock());
+        }
+        loop {
+            if limit.is_cancelled() {
+                return Err(AtcaStatus::AtcaCancelled);
+            }
+            match self.shared.commands.try_lock() {
+                Ok(guard) => return Ok(guard),
+                Err(TryLockError::Poisoned(err)) => return Ok(err.into_inner()),
+                Err(TryLockError::WouldBlock) => (),
+            }
+            if limit.is_expired() {
+                return Err(AtcaStatus::AtcaTimeout);
+            }
+            thread::sleep(TURN_POLL_INTERVAL);
+        }
+    }
+
+    fn serialized<T, F>(&self, operation: F) -> T
+    where
+        T: CommandOutput,
+        F: FnOnce(&dyn AteccDeviceTrait) -> T,
+    {
+        // The same limit bounds the wait for the lock of CryptoAuthLib within the command
+        let limit = self.wait_limit();
+        match self.wait_turn(&limit) {
+            Ok(_guard) => with_wait_limit(limit, || operation(self.shared.device.as_ref())),
+            Err(err) => T::from_status(err),
+        }
+    }
+
+    /// As serialized(), for a command using the key stored in the slot.
+    /// The uses left are checked within the same turn as the command
+    fn serialized_key_use<T, F>(&self, slot_id: u8, operation: F) -> T
+    where
+        T: CommandOutput,
+        F: FnOnce(&dyn AteccDeviceTrait) -> T,
+    {
+        self.serialized(|device| {
+            if self.check_key_uses {
+                match key_uses_left(device, slot_id) {
+                    Ok(Some(0)) => return T::from_status(AtcaStatus::AtcaKeyUsesExhausted),
+                    Ok(_) => (),
+                    Err(err) => return T::from_status(err),
+                }
+            }
+            operation(device)
+        })
+    }
+
+    /// The getters of the device properties cannot report a failure,
+    /// so they wait for their turn without a limit
+    fn serialized_unbounded<T, F>(&self, operation: F) -> T
+    where
+        F: FnOnce(&dyn AteccDeviceTrait) -> T,
+    {
+        let _guard = self.lock();
+        operation(self.shared.device.as_ref())
+    }
+}
+
+/// Cancels the commands of the handles it has been given to, see AteccHandle::with_cancellation().
+/// Clones of a token share its state, so one can be kept by the thread that cancels
+/// and another one by the thread issuing the commands
+#[derive(Clone, Debug, Default)]
+pub struct AtcaCancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl AtcaCancellationToken {
+    /// Creates a token that is not cancelled
+    pub fn new() -> AtcaCancellationToken {
+        AtcaCancellationToken::default()
+    } // AtcaCancellationToken::new()
+
+    /// Cancels the commands waiting for their turn and all the following ones
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    } // AtcaCancellationToken::cancel()
+
+    /// Tells if the token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    } // AtcaCancellationToken::is_cancelled()
+
+    /// Lets the commands run again
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::SeqCst);
+    } // AtcaCancellationToken::reset()
+}
+
+/// Result of a command that can tell a command did not get its turn
+trait CommandOutput {
+    fn from_status(status: AtcaStatus) -> Self;
+}
+
+impl CommandOutput for AtcaStatus {
+    fn from_status(status: AtcaStatus) -> Self {
+        status
+    }
+}
+
+impl<T> CommandOutput for Result<T, AtcaStatus> {
+    fn from_status(status: AtcaStatus) -> Self {
+        Err(status)
+    }
+}
+
+/// Exclusive access to a shared device, see AteccHandle::session()
+pub struct AteccSession<'a> {
+    device: &'a (dyn AteccDeviceTrait + Send + Sync),
+    _guard: MutexGuard<'a, ()>,
+}
+
+impl<'a> Deref for AteccSession<'a> {
+    type Target = dyn AteccDeviceTrait + Send + Sync + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        self.device
+    }
+}
+
+impl AteccDeviceTrait for AteccHandle {
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized(|device| device.random(rand_out))
+    }
+
+    fn random_into(&self, rand_out: &mut [u8; ATCA_RANDOM_BUFFER_SIZE]) -> AtcaStatus {
+        self.serialized(|device| device.random_into(rand_out))
+    }
+
+    fn random_fill(&self, dest: &mut [u8]) -> AtcaStatus {
+        self.serialized(|device| device.random_fill(dest))
+    }
+
+    fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized(|device| device.sha(message, digest))
+    }
+
+    fn sha_into(&self, message: &[u8], digest: &mut [u8; ATCA_SHA2_256_DIGEST_SIZE]) -> AtcaStatus {
+        self.serialized(|device| device.sha_into(message, digest))
+    }
+
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        self.serialized(|device| device.sha_start())
+    }
+
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        self.serialized(|device| device.sha_update(ctx, data))
+    }
+
+    fn sha_finish(&self, ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.sha_finish(ctx))
+    }
+
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.sha_hmac(message, slot_id))
+    }
+
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        self.serialized(|device| device.nonce(target, data))
+    }
+
+    fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized(|device| device.nonce_rand(host_nonce, rand_out))
+    }
+
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        self.serialized(|device| device.gen_dig(zone, slot_id, other_data))
+    }
+
+    fn tempkey_state(&self) -> TempKeyState {
+        self.serialized_unbounded(|device| device.tempkey_state())
+    }
+
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.mac(slot_id, challenge))
+    }
+
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.check_mac(slot_id, challenge, response, other_data))
+    }
+
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.serialized(|device| device.counter_read(counter_id))
+    }
+
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.serialized(|device| device.counter_increment(counter_id))
+    }
+
+    fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        self.serialized(|device| device.gen_key(key_type, slot_id))
+    }
+
+    fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.serialized(|device| device.import_key(key_type, key_data, slot_id))
+    }
+
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.serialized(|device| device.import_private_key_der(key_data, slot_id))
+    }
+
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus {
+        self.serialized(|device| device.import_private_key_pem(key_data, slot_id))
+    }
+
+    fn export_key(&self, key_type: KeyType, key_data: &mut Vec<u8>, slot_id: u8) -> AtcaStatus {
+        self.serialized(|device| device.export_key(key_type, key_data, slot_id))
+    }
+
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.serialized(|device| device.write_slot_data(slot_id, offset, data))
+    }
+
+    fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.read_slot_data(slot_id, offset, len))
+    }
+
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.serialized(|device| device.write_otp_zone(offset, data))
+    }
+
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.read_otp_zone(offset, len))
+    }
+
+    fn get_public_key(&self, slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized(|device| device.get_public_key(slot_id, public_key))
+    }
+
+    fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized_key_use(slot_id, |device| device.sign_hash(mode, slot_id, signature))
+    }
+
+    fn sign_hash_into(
+        &self,
+        slot_id: u8,
+        hash: &[u8; ATCA_SHA2_256_DIGEST_SIZE],
+        signature: &mut [u8; ATCA_SIG_SIZE],
+    ) -> AtcaStatus {
+        self.serialized_key_use(slot_id, |device| {
+            device.sign_hash_into(slot_id, hash, signature)
+        })
+    }
+
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized_key_use(slot_id, |device| device.sign_message(slot_id, message))
+    }
+
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized_key_use(slot_id, |device| device.sign_base(mode, slot_id))
+    }
+
+    fn verify_hash(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.verify_hash(mode, hash, signature))
+    }
+
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized_key_use(slot_id, |device| {
+            device.sign_hash_formatted(mode, slot_id, format)
+        })
+    }
+
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.verify_hash_formatted(mode, hash, signature, format))
+    }
+
+    fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        self.serialized(|device| device.ecdh(slot_id, public_key, session))
+    }
+
+    fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.kdf(algorithm, source, target, message))
+    }
+
+    fn open_io_protection_session(&self, io_key: &[u8]) -> Result<IoProtectionSession, AtcaStatus> {
+        self.serialized(|device| device.open_io_protection_session(io_key))
+    }
+
+    fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.ecdh_io_protected(session, slot_id, public_key))
+    }
+
+    fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.verify_hash_io_protected(session, mode, hash, signature))
+    }
+
+    fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus {
+        self.serialized(|device| device.import_aes_key_io_protected(session, key, slot_id))
+    }
+
+    fn cipher_encrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        self.serialized(|device| device.cipher_encrypt(algorithm, slot_id, data))
+    }
+
+    fn cipher_decrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        self.serialized(|device| device.cipher_decrypt(algorithm, slot_id, data))
+    }
+
+    fn aead_encrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.aead_encrypt(algorithm, slot_id, data))
+    }
+
+    fn aead_decrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.aead_decrypt(algorithm, slot_id, data))
+    }
+
+    fn gcm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.serialized(|device| device.gcm_init(slot_id, nonce, key))
+    }
+
+    fn gcm_update_aad(&self, ctx: AtcaAesGcmCtx, data: &[u8]) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.serialized(|device| device.gcm_update_aad(ctx, data))
+    }
+
+    fn gcm_encrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.serialized(|device| device.gcm_encrypt_update(ctx, data))
+    }
+
+    fn gcm_decrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.serialized(|device| device.gcm_decrypt_update(ctx, data))
+    }
+
+    fn gcm_encrypt_finish(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.gcm_encrypt_finish(ctx, tag_length))
+    }
+
+    fn gcm_decrypt_finish(&self, ctx: AtcaAesGcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.gcm_decrypt_finish(ctx, tag))
+    }
+
+    fn ccm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.serialized(|device| {
+            device.ccm_init(slot_id, nonce, key, aad_length, text_length, tag_length)
+        })
+    }
+
+    fn ccm_update_aad(&self, ctx: AtcaAesCcmCtx, data: &[u8]) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.serialized(|device| device.ccm_update_aad(ctx, data))
+    }
+
+    fn ccm_encrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.serialized(|device| device.ccm_encrypt_update(ctx, data))
+    }
+
+    fn ccm_decrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.serialized(|device| device.ccm_decrypt_update(ctx, data))
+    }
+
+    fn ccm_encrypt_finish(&self, ctx: AtcaAesCcmCtx) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.ccm_encrypt_finish(ctx))
+    }
+
+    fn ccm_decrypt_finish(&self, ctx: AtcaAesCcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.ccm_decrypt_finish(ctx, tag))
+    }
+
+    fn tls_derive_keys(
+        &self,
+        slot_id: u8,
+        server_public_key: &[u8],
+        client_random: &[u8],
+        server_random: &[u8],
+        key_block_length: usize,
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| {
+            device.tls_derive_keys(
+                slot_id,
+                server_public_key,
+                client_random,
+                server_random,
+                key_block_length,
+                session,
+            )
+        })
+    }
+
+    fn tls_verify_data(
+        &self,
+        label: TlsFinishedLabel,
+        handshake_hash: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.tls_verify_data(label, handshake_hash, session))
+    }
+
+    fn ecies_encrypt(&self, public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.ecies_encrypt(public_key, plaintext))
+    }
+
+    fn ecies_decrypt(&self, slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.ecies_decrypt(slot_id, data))
+    }
+
+    fn wrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.wrap_key(algorithm, slot_id, key))
+    }
+
+    fn unwrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.unwrap_key(algorithm, slot_id, wrapped_key))
+    }
+
+    fn get_device_type(&self) -> AtcaDeviceType {
+        self.serialized_unbounded(|device| device.get_device_type())
+    }
+
+    fn is_configuration_locked(&self) -> bool {
+        self.serialized_unbounded(|device| device.is_configuration_locked())
+    }
+
+    fn is_data_zone_locked(&self) -> bool {
+        self.serialized_unbounded(|device| device.is_data_zone_locked())
+    }
+
+    fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus {
+        self.serialized(|device| device.get_config(atca_slots))
+    }
+
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized(|device| device.read_config_zone(config_data))
+    }
+
+    fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        self.serialized(|device| device.write_config_zone(config_data))
+    }
+
+    fn lock_config_zone(&self) -> AtcaStatus {
+        self.serialized(|device| device.lock_config_zone())
+    }
+
+    fn lock_data_zone(&self) -> AtcaStatus {
+        self.serialized(|device| device.lock_data_zone())
+    }
+
+    fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
+        self.serialized(|device| device.info_cmd(command))
+    }
+
+    fn add_access_key(&self, slot_id: u8, encryption_key: &[u8]) -> AtcaStatus {
+        self.serialized(|device| device.add_access_key(slot_id, encryption_key))
+    }
+
+    fn flush_access_keys(&self) -> AtcaStatus {
+        self.serialized(|device| device.flush_access_keys())
+    }
+
+    fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus {
+        self.serialized(|device| device.set_host_nonce(num_in))
+    }
+
+    fn get_serial_number(&self) -> [u8; ATCA_SERIAL_NUM_SIZE] {
+        self.serialized_unbounded(|device| device.get_serial_number())
+    }
+
+    fn is_aes_enabled(&self) -> bool {
+        self.serialized_unbounded(|device| device.is_aes_enabled())
+    }
+
+    fn is_kdf_aes_enabled(&self) -> bool {
+        self.serialized_unbounded(|device| device.is_kdf_aes_enabled())
+    }
+
+    fn is_io_protection_key_enabled(&self) -> bool {
+        self.serialized_unbounded(|device| device.is_io_protection_key_enabled())
+    }
+
+    fn get_ecdh_output_protection_state(&self) -> OutputProtectionState {
+        self.serialized_unbounded(|device| device.get_ecdh_output_protection_state())
+    }
+
+    fn get_kdf_output_protection_state(&self) -> OutputProtectionState {
+        self.serialized_unbounded(|device| device.get_kdf_output_protection_state())
+    }
+
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.serialized(|device| device.aes_encrypt_block(key_id, key_block, input))
+    }
+
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.serialized(|device| device.aes_decrypt_block(key_id, key_block, input))
+    }
+
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.self_test())
+    }
+
+    fn wake(&self) -> AtcaStatus {
+        self.serialized(|device| device.wake())
+    }
+
+    fn idle(&self) -> AtcaStatus {
+        self.serialized(|device| device.idle())
+    }
+
+    fn sleep(&self) -> AtcaStatus {
+        self.serialized(|device| device.sleep())
+    }
+
+    fn is_disconnected(&self) -> bool {
+        self.serialized_unbounded(|device| device.is_disconnected())
+    }
+
+    fn reconnect(&self) -> AtcaStatus {
+        self.serialized(|device| device.reconnect())
+    }
+
+    fn refresh(&self) -> AtcaStatus {
+        self.serialized(|device| device.refresh())
+    }
+
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        self.serialized(|device| device.refresh_lock_state())
+    }
+
+    fn release(&self) -> AtcaStatus {
+        self.serialized(|device| device.release())
+    }
+
+    #[cfg(test)]
+    fn read_zone(
+        &self,
+        zone: u8,
+        slot: u16,
+        block: u8,
+        offset: u8,
+        data: &mut Vec<u8>,
+        len: u8,
+    ) -> AtcaStatus {
+        self.serialized(|device| device.read_zone(zone, slot, block, offset, data, len))
+    }
+
+    #[cfg(test)]
+    fn cmp_config_zone(&self, config_data: &mut [u8]) -> Result<bool, AtcaStatus> {
+        self.serialized(|device| device.cmp_config_zone(config_data))
+    }
+
+    #[cfg(test)]
+    fn get_access_key(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        self.serialized(|device| device.get_access_key(slot_id, key))
+    }
+
+    #[cfg(test)]
+    fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.serialized(|device| device.aes_ctr_init(slot_id, counter_size, iv))
+    }
+
+    #[cfg(test)]
+    fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.serialized(|device| device.aes_ctr_increment(ctx))
+    }
+
+    #[cfg(test)]
+    fn aes_cbc_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        self.serialized(|device| device.aes_cbc_init(slot_id, iv))
+    }
+}
@@ -0,0 +1,36 @@
+use super::{AtcaStatus, AteccDeviceTrait, HealthReport, InfoCmdType, ATCA_RANDOM_BUFFER_SIZE};
+use alloc::vec::Vec;
+
+/// Checks that the chip responds and works: wakes it up, reads its revision,
+/// optionally runs the SelfTest command (ATECC608x only, it takes a while)
+/// and generates a sample random number. All the checks are made even if one fails,
+/// so the report tells everything that is wrong.
+/// It is meant to be called from the liveness probes of applications.
+pub fn health_check(device: &dyn AteccDeviceTrait, run_self_test: bool) -> HealthReport {
+    let wake = device.wake();
+    let revision = device.info_cmd(InfoCmdType::Revision);
+    let self_test = if run_self_test {
+        Some(device.self_test())
+    } else {
+        None
+    };
+    let mut rand_out = Vec::new();
+    let random = match device.random(&mut rand_out) {
+        AtcaStatus::AtcaSuccess if rand_out.len() != ATCA_RANDOM_BUFFER_SIZE => {
+            AtcaStatus::AtcaInvalidSize
+        }
+        status => status,
+    };
+
+    let is_healthy = wake == AtcaStatus::AtcaSuccess
+        && revision.is_ok()
+        && matches!(self_test, None | Some(Ok(true)))
+        && random == AtcaStatus::AtcaSuccess;
+    HealthReport {
+        wake,
+        revision,
+        self_test,
+        random,
+        is_healthy,
+    }
+} // health_check()
@@ -0,0 +1,630 @@
+use alloc::vec::Vec;
+use std::cmp::min;
+use std::convert::TryFrom;
+
+use super::super::constant_time_eq;
+use super::ModePrimitives;
+use super::{AeadParam, AtcaAesCcmCtx, AtcaStatus, KeyType, NonceTarget, SlotId};
+
+use super::{ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_NONCE_SIZE};
+
+use cryptoauthlib_sys::{atca_aes_cbc_ctx_t, atca_aes_cmac_ctx_t};
+
+const MAX_IV_SIZE: usize = 13;
+const MIN_IV_SIZE: usize = 7;
+const MAX_TAG_SIZE: usize = ATCA_AES_DATA_SIZE;
+const MIN_TAG_SIZE: usize = 4;
+const MAX_AAD_SIZE: usize = 0xFEFF; // RFC-3610 -> (2^16) - (2^8) - 1;
+
+/// function that performs encryption in AES CCM mode
+pub(crate) fn encrypt_aes_ccm<D: ModePrimitives>(
+    device: &D,
+    aead_param: AeadParam,
+    slot_id: u8,
+    data: &mut [u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    let mut ctx: AtcaAesCcmCtx = common_aes_ccm(device, aead_param, slot_id, data)?;
+    ctx = aes_ccm_update(device, ctx, data, true)?;
+
+    let result = aes_ccm_finish(device, ctx)?;
+    Ok(result)
+} // encrypt_aes_ccm()
+
+/// function that performs decryption in AES CCM mode
+pub(crate) fn decrypt_aes_ccm<D: ModePrimitives>(
+    device: &D,
+    aead_param: AeadParam,
+    slot_id: u8,
+    data: &mut [u8],
+) -> Result<bool, AtcaStatus> {
+    let tag_to_check: Vec<u8>;
+
+    if let Some(val) = aead_param.tag.clone() {
+        tag_to_check = val;
+    } else {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let mut ctx: AtcaAesCcmCtx = common_aes_ccm(device, aead_param, slot_id, data)?;
+    ctx = aes_ccm_update(device, ctx, data, false)?;
+
+    let result = aes_ccm_decrypt_finish(device, ctx, &tag_to_check)?;
+    Ok(result)
+} // decrypt_aes_ccm()
+
+/// function that starts a streaming AES CCM operation;
+/// CCM needs the total lengths of the additional data and the text up front
+pub(crate) fn ccm_init<D: ModePrimitives>(
+    device: &D,
+    slot_id: u8,
+    nonce: &[u8],
+    key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    aad_size: usize,
+    text_size: usize,
+    tag_length: u8,
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    if device.check_that_configuration_is_not_locked(true) {
+        return Err(AtcaStatus::AtcaNotLocked);
+    }
+    if !device.chip_options()?.aes_enabled {
+        // If chip does not support AES hardware encryption, the operation cannot be performed
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let key_slot = SlotId::try_from(slot_id)?;
+    if let SlotId::Slot(slot) = key_slot {
+        if device.slot_config(slot)?.key_type != KeyType::Aes {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+    }
+    if key_slot.is_temp_key() && key.is_none() {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if (nonce.len() < MIN_IV_SIZE || nonce.len() > MAX_IV_SIZE)
+        || !(MIN_TAG_SIZE..=MAX_TAG_SIZE).contains(&(tag_length as usize))
+        || !tag_length.is_multiple_of(2)
+        || (aad_size > MAX_AAD_SIZE)
+    {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    if let Some(val) = key {
+        let mut key: Vec<u8> = val.to_vec();
+        key.resize_with(ATCA_NONCE_SIZE, || 0x00);
+        let result = device.nonce(NonceTarget::TempKey, &key);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+    }
+
+    aes_ccm_init(
+        device,
+        slot_id,
+        nonce,
+        aad_size,
+        text_size,
+        tag_length as usize,
+    )
+} // ccm_init()
+
+/// function that feeds the next chunk of additional authenticated data
+/// into a streaming AES CCM operation
+pub(crate) fn ccm_update_aad<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    data: &[u8],
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    if ctx.data_size > 0 {
+        // Additional data is not accepted once the text processing has started
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    aes_ccm_aad_update(device, ctx, data)
+} // ccm_update_aad()
+
+/// function that encrypts (in place) the next chunk of data of a streaming AES CCM operation
+pub(crate) fn ccm_encrypt_update<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    data: &mut [u8],
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    if (ctx.data_size as usize) + data.len() > ctx.text_size {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    aes_ccm_update(device, ctx, data, true)
+} // ccm_encrypt_update()
+
+/// function that decrypts (in place) the next chunk of data of a streaming AES CCM operation
+pub(crate) fn ccm_decrypt_update<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    data: &mut [u8],
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    if (ctx.data_size as usize) + data.len() > ctx.text_size {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    aes_ccm_update(device, ctx, data, false)
+} // ccm_decrypt_update()
+
+/// function that completes a streaming AES CCM encryption and returns the tag
+pub(crate) fn ccm_encrypt_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+) -> Result<Vec<u8>, AtcaStatus> {
+    let ctx = ccm_finish_check(device, ctx)?;
+    aes_ccm_finish(device, ctx)
+} // ccm_encrypt_finish()
+
+/// function that completes a streaming AES CCM decryption and verifies the tag
+pub(crate) fn ccm_decrypt_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    tag: &[u8],
+) -> Result<bool, AtcaStatus> {
+    let ctx = ccm_finish_check(device, ctx)?;
+    aes_ccm_decrypt_finish(device, ctx, tag)
+} // ccm_decrypt_finish()
+
+/// a helper function checking that the whole declared text has been processed;
+/// it also completes the additional data if no text has been provided
+fn ccm_finish_check<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    if (ctx.data_size as usize) != ctx.text_size {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    aes_ccm_aad_finish(device, ctx)
+} // ccm_finish_check()
+
+/// a helper function implementing common functionality for AES CCM encryption and decryption
+fn common_aes_ccm<D: ModePrimitives>(
+    device: &D,
+    aead_param: AeadParam,
+    slot_id: u8,
+    data: &mut [u8],
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    let key_slot = SlotId::try_from(slot_id)?;
+    if let SlotId::Slot(slot) = key_slot {
+        if device.slot_config(slot)?.key_type != KeyType::Aes {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+    }
+    if key_slot.is_temp_key() && aead_param.key.is_none()
+        || (aead_param.tag_length.is_some() && aead_param.tag.is_some())
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if (data.is_empty() && aead_param.additional_data.is_none())
+        || (aead_param.nonce.len() < MIN_IV_SIZE || aead_param.nonce.len() > MAX_IV_SIZE)
+        || (aead_param.tag_length.is_some()
+            && ((aead_param.tag_length < Some(MIN_TAG_SIZE as u8))
+                || (aead_param.tag_length > Some(MAX_TAG_SIZE as u8))
+                || !aead_param.tag_length.unwrap().is_multiple_of(2)))
+        || (aead_param.tag.is_some()
+            && ((aead_param.tag.as_ref().unwrap().len() < MIN_TAG_SIZE)
+                || (aead_param.tag.as_ref().unwrap().len() > MAX_TAG_SIZE)
+                || !aead_param.tag.as_ref().unwrap().len().is_multiple_of(2)))
+    {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    let mut tag_length: usize = ATCA_AES_DATA_SIZE;
+    if let Some(val) = &aead_param.tag_length {
+        tag_length = *val as usize
+    } else if let Some(val) = &aead_param.tag {
+        tag_length = val.len();
+    }
+
+    if let Some(val) = &aead_param.key {
+        let mut key: Vec<u8> = val.to_vec();
+        key.resize_with(ATCA_NONCE_SIZE, || 0x00);
+        let result = device.nonce(NonceTarget::TempKey, &key);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+    }
+
+    let iv: Vec<u8> = aead_param.nonce;
+    let mut additional_data_size: usize = 0;
+    if let Some(val) = &aead_param.additional_data {
+        additional_data_size = val.len();
+        if additional_data_size > MAX_AAD_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+    };
+    let data_size = data.len();
+    let mut ctx: AtcaAesCcmCtx = aes_ccm_init(
+        device,
+        slot_id,
+        &iv,
+        additional_data_size,
+        data_size,
+        tag_length,
+    )?;
+
+    if let Some(data_to_sign) = &aead_param.additional_data {
+        ctx = aes_ccm_aad_update(device, ctx, data_to_sign)?;
+    }
+
+    Ok(ctx)
+} // common_aes_ccm
+
+/// Initialize context for AES CCM operation with an existing IV, which
+/// is common when starting a decrypt operation
+fn aes_ccm_init<D: ModePrimitives>(
+    device: &D,
+    slot_id: u8,
+    iv: &[u8],
+    aad_size: usize,
+    text_size: usize,
+    tag_size: usize,
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    // Length/nonce field specifications according to rfc3610.
+    if iv.is_empty() || iv.len() < 7 || iv.len() > 13 {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    // Auth field specifications according to rfc3610.
+    if !(3..=ATCA_AES_DATA_SIZE).contains(&tag_size) || !tag_size.is_multiple_of(2) {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    // First block B of 16 bytes consisting of flags, nonce and l(m).
+    let mut b: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+    let mut counter: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+    let mut ctx: AtcaAesCcmCtx = AtcaAesCcmCtx {
+        iv_size: iv.len() as u8,
+        ..Default::default()
+    };
+
+    // --------------------- Init sequence for authentication .......................//
+    // Encoding the number of bytes in auth field.
+    let m = ((tag_size - 2) / 2) as u8;
+    // Encoding the number of bytes in length field.
+    let l = (ATCA_AES_DATA_SIZE - iv.len() - 1 - 1) as u8;
+
+    // Store M value in ctx for later use.
+    ctx.m = m;
+
+    //   ----------------------
+    //   Bit Number   Contents
+    //   ----------   ----------------------
+    //   7            Reserved (always zero)
+    //   6            Adata
+    //   5 ... 3      M'
+    //   2 ... 0      L'
+    //   -----------------------
+    // Formatting flag field
+    b[0] = l | (m << 3) | (((aad_size > 0) as u8) << 6);
+
+    //   ----------------------
+    //   Octet Number   Contents
+    //   ------------   ---------
+    //   0              Flags
+    //   1 ... 15-L     Nonce N
+    //   16-L ... 15    l(m)
+    //   -----------------------
+
+    // Copying the IV into the nonce field.
+    b[1..=iv.len()].clone_from_slice(iv);
+
+    // Update length field in B0 block.
+    let mut size_left: usize = text_size;
+
+    for i in 0..=l {
+        b[(15 - i) as usize] = (size_left & 0xFF) as u8;
+        size_left >>= 8;
+    }
+
+    // Init CBC-MAC context
+    ctx.cbc_mac_ctx = aes_cbcmac_init(slot_id);
+
+    // Processing initial block B0 through CBC-MAC.
+    ctx.cbc_mac_ctx = aes_cbcmac_update(device, ctx.cbc_mac_ctx, &b)?;
+
+    if aad_size > 0 {
+        // Loading AAD size in ctx buffer.
+        ctx.partial_aad[0] = ((aad_size >> 8) & 0xFF) as u8;
+        ctx.partial_aad[1] = (aad_size & 0xFF) as u8;
+        ctx.partial_aad_size = 2;
+    }
+
+    // --------------------- Init sequence for encryption/decryption .......................//
+    ctx.text_size = text_size;
+
+    //   ----------------------
+    //   Bit Number   Contents
+    //   ----------   ----------------------
+    //   7            Reserved (always zero)
+    //   6            Reserved (always zero)
+    //   5 ... 3      Zero
+    //   2 ... 0      L'
+    //   -----------------------
+
+    // Updating Flags field
+    counter[0] = l;
+    //   ----------------------
+    //   Octet Number   Contents
+    //   ------------   ---------
+    //   0              Flags
+    //   1 ... 15-L     Nonce N
+    //   16-L ... 15    Counter i
+    //   -----------------------
+    // Formatting to get the initial counter value
+    counter[1..=iv.len()].clone_from_slice(iv);
+    ctx.counter[..].copy_from_slice(&counter);
+
+    // Init CTR mode context with the counter value obtained from previous step.
+    let counter_size: u8 = (ATCA_AES_DATA_SIZE - iv.len() - 1) as u8;
+    ctx.ctr_ctx = device.aes_ctr_init(slot_id, counter_size, &counter)?;
+
+    // Increment the counter to skip the first block, first will be later reused to get tag.
+    ctx.ctr_ctx = device.aes_ctr_increment(ctx.ctr_ctx)?;
+    Ok(ctx)
+} // aes_ccm_init()
+
+/// Process data using CCM mode and a key within the ATECC608A device.
+/// aes_ccm_init() should be called before the first use of this function.
+fn aes_ccm_update<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    data: &mut [u8],
+    is_encrypt: bool,
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    let mut temp_ctx = ctx;
+    temp_ctx = aes_ccm_aad_finish(device, temp_ctx)?;
+
+    if data.is_empty() {
+        // Nothing to do
+        return Ok(temp_ctx);
+    }
+
+    let mut data_idx: usize = 0;
+    let input_size: usize = data.len();
+
+    while data_idx < input_size {
+        if temp_ctx.data_size.is_multiple_of(ATCA_AES_DATA_SIZE as u32) {
+            // Need to calculate next encrypted counter block
+            temp_ctx.enc_cb = device.aes_encrypt_block(
+                temp_ctx.ctr_ctx.key_id,
+                temp_ctx.ctr_ctx.key_block,
+                &temp_ctx.ctr_ctx.cb,
+            )?;
+
+            // Increment counter
+            temp_ctx.ctr_ctx = device.aes_ctr_increment(temp_ctx.ctr_ctx)?;
+        }
+
+        // Process data with current encrypted counter block
+        let start_idx = (temp_ctx.data_size as usize) % ATCA_AES_DATA_SIZE;
+        let end_idx = min(ATCA_AES_DATA_SIZE, start_idx + input_size - data_idx);
+        for idx in start_idx..end_idx {
+            // Save the current ciphertext block depending on whether this is an encrypt or decrypt operation
+            if is_encrypt {
+                temp_ctx.ciphertext_block[idx] = data[data_idx]
+            }
+
+            data[data_idx] ^= temp_ctx.enc_cb[idx];
+
+            if !is_encrypt {
+                temp_ctx.ciphertext_block[idx] = data[data_idx]
+            }
+
+            temp_ctx.data_size += 1;
+            data_idx += 1;
+        }
+
+        if temp_ctx.data_size.is_multiple_of(ATCA_AES_DATA_SIZE as u32) {
+            // Adding data to CBC-MAC to calculate tag
+            temp_ctx.cbc_mac_ctx =
+                aes_cbcmac_update(device, temp_ctx.cbc_mac_ctx, &temp_ctx.ciphertext_block[..])?;
+        }
+    }
+
+    Ok(temp_ctx)
+} // aes_ccm_update()
+
+/// Complete a CCM decrypt operation authenticating provided tag
+#[inline]
+fn aes_ccm_decrypt_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    tag: &[u8],
+) -> Result<bool, AtcaStatus> {
+    let val = aes_ccm_finish(device, ctx)?;
+    Ok(constant_time_eq(tag, &val))
+} // aes_ccm_decrypt_finish()
+
+/// Complete a CCM operation returning the authentication tag
+fn aes_ccm_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+) -> Result<Vec<u8>, AtcaStatus> {
+    // Finish and get the tag
+    let mut tag: Vec<u8> = vec![0x00; ATCA_AES_DATA_SIZE];
+    let mut t: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+    let mut buffer: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+    let mut temp_ctx = ctx;
+
+    let end_idx = (temp_ctx.data_size as usize) % ATCA_AES_DATA_SIZE;
+    if end_idx != 0 {
+        buffer[..end_idx].copy_from_slice(&temp_ctx.ciphertext_block[..end_idx]);
+
+        // Adding data to CBC-MAC to calculate tag
+        temp_ctx.cbc_mac_ctx = aes_cbcmac_update(device, temp_ctx.cbc_mac_ctx, &buffer)?;
+    }
+
+    // Update tag size
+    let tag_size = ((temp_ctx.m * 2) + 2) as usize;
+
+    let val = aes_cbcmac_finish(temp_ctx.cbc_mac_ctx, tag_size)?;
+    t[..val.len()].copy_from_slice(&val[..val.len()]);
+
+    // Encrypt the CBC-MAC value with the first counter block
+    let u = device.aes_encrypt_block(
+        temp_ctx.ctr_ctx.key_id,
+        temp_ctx.ctr_ctx.key_block,
+        &temp_ctx.counter,
+    )?;
+
+    tag.iter_mut()
+        .zip(u.iter().zip(t.iter()))
+        .for_each(|(out, (x1, x2))| *out = *x1 ^ *x2);
+    tag.resize(tag_size, 0x00);
+    tag.shrink_to_fit();
+
+    Ok(tag)
+} // aes_ccm_finish()
+
+/// Process Additional Authenticated Data (AAD) using CCM mode and a
+/// key within the ATECC608A device
+fn aes_ccm_aad_update<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+    data: &[u8],
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    if data.is_empty() {
+        return Ok(ctx);
+    };
+
+    let mut temp_ctx: AtcaAesCcmCtx = ctx;
+    let mut aad_size: usize = data.len();
+    let copy_size: usize = min(aad_size, ATCA_AES_DATA_SIZE - temp_ctx.partial_aad_size);
+
+    // Copy data into current block
+    let start_pos = temp_ctx.partial_aad_size;
+    let end_pos = min(ATCA_AES_DATA_SIZE, start_pos + copy_size);
+    temp_ctx.partial_aad[start_pos..end_pos].clone_from_slice(&data[..copy_size]);
+
+    if temp_ctx.partial_aad_size + aad_size < ATCA_AES_DATA_SIZE {
+        // Not enough data to finish off the current block
+        temp_ctx.partial_aad_size += aad_size;
+        return Ok(temp_ctx);
+    }
+
+    // Process the current block
+    temp_ctx.cbc_mac_ctx = aes_cbcmac_update(device, temp_ctx.cbc_mac_ctx, &temp_ctx.partial_aad)?;
+
+    // Process any additional blocks
+    aad_size -= copy_size; // Adjust to the remaining aad bytes
+    let block_count = aad_size / ATCA_AES_DATA_SIZE;
+    if block_count > 0 {
+        temp_ctx.cbc_mac_ctx = aes_cbcmac_update(
+            device,
+            temp_ctx.cbc_mac_ctx,
+            &data[copy_size..((block_count * ATCA_AES_DATA_SIZE) + copy_size)],
+        )?;
+    }
+
+    // Save any remaining data
+    temp_ctx.partial_aad_size = aad_size % ATCA_AES_DATA_SIZE;
+    let start_pos = copy_size + (block_count * ATCA_AES_DATA_SIZE);
+    temp_ctx.partial_aad[..temp_ctx.partial_aad_size]
+        .clone_from_slice(&data[start_pos..(start_pos + temp_ctx.partial_aad_size)]);
+
+    Ok(temp_ctx)
+} // aes_ccm_aad_update()
+
+/// Finish processing Additional Authenticated Data (AAD) using CCM mode
+fn aes_ccm_aad_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesCcmCtx,
+) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+    // Pad and process any incomplete aad data blocks
+    let mut temp_ctx = ctx;
+
+    if temp_ctx.partial_aad_size > 0 {
+        let mut buffer: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        buffer[..temp_ctx.partial_aad_size]
+            .copy_from_slice(&temp_ctx.partial_aad[..temp_ctx.partial_aad_size]);
+
+        temp_ctx.cbc_mac_ctx = aes_cbcmac_update(device, temp_ctx.cbc_mac_ctx, &buffer)?;
+
+        // Reset ctx partial aad size variable
+        temp_ctx.partial_aad_size = 0
+    }
+
+    Ok(temp_ctx)
+} // aes_ccm_aad_finish()
+
+// -----------------------------------------------------------
+// Auxiliary functions
+// -----------------------------------------------------------
+
+/// Initialize context for AES CBC-MAC operation
+fn aes_cbcmac_init(slot_id: u8) -> atca_aes_cmac_ctx_t {
+    let slot = SlotId::try_from(slot_id).map_or(slot_id as u16, SlotId::key_id);
+
+    atca_aes_cmac_ctx_t {
+        cbc_ctx: atca_aes_cbc_ctx_t {
+            key_id: slot,
+            key_block: 0x00,
+            ciphertext: [0x00; ATCA_AES_DATA_SIZE],
+        },
+        block_size: 0,
+        block: [0x00; ATCA_AES_DATA_SIZE],
+    }
+} // aes_cbcmac_init()
+
+/// Calculate AES CBC-MAC with key stored within ECC608A device.
+/// aes_cbcmac_init() should be called before the first use of this function.
+fn aes_cbcmac_update<D: ModePrimitives>(
+    device: &D,
+    ctx: atca_aes_cmac_ctx_t,
+    data: &[u8],
+) -> Result<atca_aes_cmac_ctx_t, AtcaStatus> {
+    if data.is_empty() {
+        // Nothing to do
+        return Ok(ctx);
+    }
+
+    // Process full blocks of data with AES-CBC
+    let mut temp_ctx = ctx;
+    let mut idx: usize = 0;
+    let mut buffer: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+
+    for i in 0..(data.len() / ATCA_AES_DATA_SIZE) {
+        let start_pos = i * ATCA_AES_DATA_SIZE;
+        let end_pos = start_pos + ATCA_AES_DATA_SIZE;
+        idx += 1;
+
+        temp_ctx.cbc_ctx = device.aes_cbc_encrypt_block(
+            temp_ctx.cbc_ctx,
+            &data[start_pos..end_pos],
+            &mut buffer,
+        )?;
+    }
+
+    // Store incomplete block to context structure
+    let start_pos = idx * ATCA_AES_DATA_SIZE;
+    match start_pos < data.len() {
+        true => {
+            temp_ctx.block_size = (data.len() - start_pos) as u32;
+            temp_ctx.block[..(temp_ctx.block_size as usize)]
+                .copy_from_slice(&data[start_pos..(start_pos + temp_ctx.block_size as usize)]);
+        }
+        false => temp_ctx.block_size = 0,
+    }
+
+    Ok(temp_ctx)
+} // aes_cbcmac_update()
+
+/// Finish a CBC-MAC operation returning the CBC-MAC value. If the data
+/// provided to the aes_cbcmac_update() function has incomplete
+/// block this function will return an error code
+fn aes_cbcmac_finish(ctx: atca_aes_cmac_ctx_t, tag_size: usize) -> Result<Vec<u8>, AtcaStatus> {
+    let mut tag: Vec<u8> = vec![0x00; ATCA_AES_DATA_SIZE];
+    if tag_size > ATCA_AES_DATA_SIZE {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    // Check for incomplete data block
+    if ctx.block_size != 0 {
+        return Err(AtcaStatus::AtcaInvalidSize); // Returns INVALID_SIZE if incomplete blocks are present
+    }
+
+    // All processing is already done, copying the mac to result buffer
+    tag[..tag_size].copy_from_slice(&ctx.cbc_ctx.ciphertext[..tag_size]);
+    tag.resize(tag_size, 0x00);
+    tag.shrink_to_fit();
+    Ok(tag)
+} // aes_cbcmac_finish()
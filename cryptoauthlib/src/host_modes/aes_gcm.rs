@@ -0,0 +1,293 @@
+use alloc::vec::Vec;
+use std::cmp::min;
+use std::convert::TryFrom;
+
+use super::ModePrimitives;
+use super::{AeadParam, AtcaAesGcmCtx, AtcaStatus, CipherOperation, KeyType, NonceTarget, SlotId};
+
+use super::{ATCA_AES_DATA_SIZE, ATCA_AES_GCM_IV_STD_LENGTH, ATCA_AES_KEY_SIZE, ATCA_NONCE_SIZE};
+
+use cryptoauthlib_sys::atca_aes_gcm_ctx_t;
+
+const MAX_GCM_TAG_SIZE: usize = ATCA_AES_DATA_SIZE;
+const MIN_GCM_TAG_SIZE: usize = 12;
+
+/// function that performs encryption in AES GCM mode
+pub(crate) fn encrypt_aes_gcm<D: ModePrimitives>(
+    device: &D,
+    aead_param: AeadParam,
+    slot_id: u8,
+    data: &mut [u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    let mut tag_length: u8 = ATCA_AES_DATA_SIZE as u8;
+    if let Some(val) = &aead_param.tag_length {
+        tag_length = *val
+    };
+
+    let mut ctx: atca_aes_gcm_ctx_t = common_aes_gcm(device, aead_param, slot_id, data)?;
+
+    if !data.is_empty() {
+        let mut start_pos: usize = 0;
+        let mut shift: usize = min(data.len(), ATCA_AES_DATA_SIZE);
+
+        while shift > 0 {
+            let block = &data[start_pos..(start_pos + shift)];
+            let mut encr_block: [u8; ATCA_AES_DATA_SIZE] = [0; ATCA_AES_DATA_SIZE];
+
+            ctx = device.aes_gcm_encrypt_update(ctx, block, &mut encr_block)?;
+            data[start_pos..(shift + start_pos)].clone_from_slice(&encr_block[..shift]);
+
+            start_pos += shift;
+            let remaining_bytes = data.len() - start_pos;
+            match 0 == remaining_bytes {
+                true => shift = 0,
+                false => {
+                    if remaining_bytes < ATCA_AES_DATA_SIZE {
+                        shift = remaining_bytes
+                    }
+                }
+            }
+        }
+    }
+
+    let tag = device.aes_gcm_encrypt_finish(ctx, tag_length)?;
+    Ok(tag)
+}
+
+/// function that performs decryption in AES GCM mode
+pub(crate) fn decrypt_aes_gcm<D: ModePrimitives>(
+    device: &D,
+    aead_param: AeadParam,
+    slot_id: u8,
+    data: &mut [u8],
+) -> Result<bool, AtcaStatus> {
+    let tag_to_check: Vec<u8>;
+
+    if let Some(val) = aead_param.tag.clone() {
+        tag_to_check = val;
+    } else {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let mut ctx: atca_aes_gcm_ctx_t = common_aes_gcm(device, aead_param, slot_id, data)?;
+
+    if !data.is_empty() {
+        let mut start_pos: usize = 0;
+        let mut shift: usize = min(data.len(), ATCA_AES_DATA_SIZE);
+
+        while shift > 0 {
+            let block = &data[start_pos..(start_pos + shift)];
+            let mut encr_block: [u8; ATCA_AES_DATA_SIZE] = [0; ATCA_AES_DATA_SIZE];
+
+            ctx = device.aes_gcm_decrypt_update(ctx, block, &mut encr_block)?;
+            data[start_pos..(shift + start_pos)].clone_from_slice(&encr_block[..shift]);
+
+            start_pos += shift;
+            let remaining_bytes = data.len() - start_pos;
+            match 0 == remaining_bytes {
+                true => shift = 0,
+                false => {
+                    if remaining_bytes < ATCA_AES_DATA_SIZE {
+                        shift = remaining_bytes
+                    }
+                }
+            }
+        }
+    }
+
+    let is_verified = device.aes_gcm_decrypt_finish(ctx, &tag_to_check)?;
+    Ok(is_verified)
+}
+
+/// function that starts a streaming AES GCM operation
+pub(crate) fn gcm_init<D: ModePrimitives>(
+    device: &D,
+    slot_id: u8,
+    nonce: &[u8],
+    key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+    if device.check_that_configuration_is_not_locked(true) {
+        return Err(AtcaStatus::AtcaNotLocked);
+    }
+    if !device.chip_options()?.aes_enabled {
+        // If chip does not support AES hardware encryption, the operation cannot be performed
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    const MAX_IV_SIZE: usize = ATCA_AES_DATA_SIZE - 1;
+    const MIN_IV_SIZE: usize = ATCA_AES_GCM_IV_STD_LENGTH;
+
+    let key_slot = SlotId::try_from(slot_id)?;
+    if let SlotId::Slot(slot) = key_slot {
+        if device.slot_config(slot)?.key_type != KeyType::Aes {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+    }
+    if key_slot.is_temp_key() && key.is_none() {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if nonce.len() < MIN_IV_SIZE || nonce.len() > MAX_IV_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    if let Some(val) = key {
+        let mut key: Vec<u8> = val.to_vec();
+        key.resize(ATCA_NONCE_SIZE, 0x00);
+        let result = device.nonce(NonceTarget::TempKey, &key);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+    }
+
+    let ctx = device.aes_gcm_init(slot_id, nonce)?;
+    Ok(AtcaAesGcmCtx { ctx })
+} // gcm_init()
+
+/// function that feeds the next chunk of additional authenticated data
+/// into a streaming AES GCM operation
+pub(crate) fn gcm_update_aad<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesGcmCtx,
+    data: &[u8],
+) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+    let mut ctx = ctx.ctx;
+    for block in data.chunks(ATCA_AES_DATA_SIZE) {
+        ctx = device.aes_gcm_aad_update(ctx, block)?;
+    }
+    Ok(AtcaAesGcmCtx { ctx })
+} // gcm_update_aad()
+
+/// function that encrypts (in place) the next chunk of data of a streaming AES GCM operation
+pub(crate) fn gcm_encrypt_update<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesGcmCtx,
+    data: &mut [u8],
+) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+    gcm_update_common(device, ctx, data, CipherOperation::Encrypt)
+} // gcm_encrypt_update()
+
+/// function that decrypts (in place) the next chunk of data of a streaming AES GCM operation
+pub(crate) fn gcm_decrypt_update<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesGcmCtx,
+    data: &mut [u8],
+) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+    gcm_update_common(device, ctx, data, CipherOperation::Decrypt)
+} // gcm_decrypt_update()
+
+/// a helper function that splits a chunk of data into blocks
+/// and passes them through the AES GCM engine
+fn gcm_update_common<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesGcmCtx,
+    data: &mut [u8],
+    operation: CipherOperation,
+) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+    let mut ctx = ctx.ctx;
+    for block in data.chunks_mut(ATCA_AES_DATA_SIZE) {
+        let mut out_block: [u8; ATCA_AES_DATA_SIZE] = [0; ATCA_AES_DATA_SIZE];
+        ctx = match operation {
+            CipherOperation::Encrypt => {
+                device.aes_gcm_encrypt_update(ctx, block, &mut out_block)?
+            }
+            CipherOperation::Decrypt => {
+                device.aes_gcm_decrypt_update(ctx, block, &mut out_block)?
+            }
+        };
+        let len = block.len();
+        block.clone_from_slice(&out_block[..len]);
+    }
+    Ok(AtcaAesGcmCtx { ctx })
+} // gcm_update_common()
+
+/// function that completes a streaming AES GCM encryption and returns the tag
+pub(crate) fn gcm_encrypt_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesGcmCtx,
+    tag_length: u8,
+) -> Result<Vec<u8>, AtcaStatus> {
+    if !(MIN_GCM_TAG_SIZE..=MAX_GCM_TAG_SIZE).contains(&(tag_length as usize)) {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    device.aes_gcm_encrypt_finish(ctx.ctx, tag_length)
+} // gcm_encrypt_finish()
+
+/// function that completes a streaming AES GCM decryption and verifies the tag
+pub(crate) fn gcm_decrypt_finish<D: ModePrimitives>(
+    device: &D,
+    ctx: AtcaAesGcmCtx,
+    tag: &[u8],
+) -> Result<bool, AtcaStatus> {
+    if !(MIN_GCM_TAG_SIZE..=MAX_GCM_TAG_SIZE).contains(&tag.len()) {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    device.aes_gcm_decrypt_finish(ctx.ctx, tag)
+} // gcm_decrypt_finish()
+
+/// a helper function implementing common functionality for AES GCM encryption and decryption
+fn common_aes_gcm<D: ModePrimitives>(
+    device: &D,
+    aead_param: AeadParam,
+    slot_id: u8,
+    data: &mut [u8],
+) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+    const MAX_IV_SIZE: usize = ATCA_AES_DATA_SIZE - 1;
+    const MIN_IV_SIZE: usize = ATCA_AES_GCM_IV_STD_LENGTH;
+    const MAX_TAG_SIZE: usize = ATCA_AES_DATA_SIZE;
+    const MIN_TAG_SIZE: usize = 12;
+
+    let key_slot = SlotId::try_from(slot_id)?;
+    if let SlotId::Slot(slot) = key_slot {
+        if device.slot_config(slot)?.key_type != KeyType::Aes {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+    }
+    if key_slot.is_temp_key() && aead_param.key.is_none()
+        || (aead_param.tag_length.is_some() && aead_param.tag.is_some())
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if (data.is_empty() && aead_param.additional_data.is_none())
+        || (aead_param.nonce.len() < MIN_IV_SIZE || aead_param.nonce.len() > MAX_IV_SIZE)
+        || (aead_param.tag_length.is_some()
+            && ((aead_param.tag_length < Some(MIN_TAG_SIZE as u8))
+                || (aead_param.tag_length > Some(MAX_TAG_SIZE as u8))))
+        || (aead_param.tag.is_some()
+            && ((aead_param.tag.as_ref().unwrap().len() < MIN_TAG_SIZE)
+                || (aead_param.tag.as_ref().unwrap().len() > MAX_TAG_SIZE)))
+    {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    if let Some(val) = &aead_param.key {
+        let mut key: Vec<u8> = val.to_vec();
+        key.resize(ATCA_NONCE_SIZE, 0x00);
+        let result = device.nonce(NonceTarget::TempKey, &key);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+    }
+
+    let iv: Vec<u8> = aead_param.nonce;
+    let mut ctx = device.aes_gcm_init(slot_id, &iv)?;
+
+    if let Some(data_to_sign) = &aead_param.additional_data {
+        let mut start_pos: usize = 0;
+        let mut shift: usize = min(data_to_sign.len(), ATCA_AES_DATA_SIZE);
+        while shift > 0 {
+            let block = &data_to_sign[start_pos..(start_pos + shift)];
+            ctx = device.aes_gcm_aad_update(ctx, block)?;
+            start_pos += shift;
+            let remaining_bytes = data_to_sign.len() - start_pos;
+            match 0 == remaining_bytes {
+                true => shift = 0,
+                false => {
+                    if remaining_bytes < ATCA_AES_DATA_SIZE {
+                        shift = remaining_bytes
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(ctx)
+}
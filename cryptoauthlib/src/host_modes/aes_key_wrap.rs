@@ -0,0 +1,206 @@
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+
+use super::ModePrimitives;
+use super::{AtcaStatus, CipherOperation, KeyWrapAlgorithm};
+
+use super::ATCA_AES_DATA_SIZE;
+
+/// Size (in bytes) of a semiblock processed by the key wrap algorithms
+const KW_SEMIBLOCK_SIZE: usize = 8;
+/// Default initial value for AES Key Wrap (RFC 3394)
+const KW_DEFAULT_IV: [u8; KW_SEMIBLOCK_SIZE] = [0xA6; KW_SEMIBLOCK_SIZE];
+/// Constant part of the alternative initial value for AES Key Wrap with Padding (RFC 5649)
+const KWP_AIV_PREFIX: [u8; 4] = [0xA6, 0x59, 0x59, 0xA6];
+
+/// Function that wraps (encrypts) a key with the AES key stored in the given slot,
+/// according to RFC 3394 or RFC 5649
+pub(crate) fn wrap_key<D: ModePrimitives>(
+    device: &D,
+    algorithm: KeyWrapAlgorithm,
+    slot_id: u8,
+    key: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    let slot = key_wrap_common(device, slot_id, key.len())?;
+
+    match algorithm {
+        KeyWrapAlgorithm::Kw => {
+            if (key.len() < (2 * KW_SEMIBLOCK_SIZE)) || !key.len().is_multiple_of(KW_SEMIBLOCK_SIZE)
+            {
+                return Err(AtcaStatus::AtcaInvalidSize);
+            }
+            aes_kw_wrap(device, slot, &KW_DEFAULT_IV, key)
+        }
+        KeyWrapAlgorithm::Kwp => {
+            let mli = u32::try_from(key.len()).map_err(|_| AtcaStatus::AtcaInvalidSize)?;
+            let mut aiv: [u8; KW_SEMIBLOCK_SIZE] = [0; KW_SEMIBLOCK_SIZE];
+            aiv[..4].copy_from_slice(&KWP_AIV_PREFIX);
+            aiv[4..].copy_from_slice(&mli.to_be_bytes());
+
+            let padded_len = key.len().div_ceil(KW_SEMIBLOCK_SIZE) * KW_SEMIBLOCK_SIZE;
+            let mut padded: Vec<u8> = key.to_vec();
+            padded.resize(padded_len, 0x00);
+
+            if KW_SEMIBLOCK_SIZE == padded_len {
+                let mut block: Vec<u8> = aiv.to_vec();
+                block.extend_from_slice(&padded);
+                let encr_block = device.aes_encrypt_block(slot, 0, &block)?;
+                Ok(encr_block.to_vec())
+            } else {
+                aes_kw_wrap(device, slot, &aiv, &padded)
+            }
+        }
+    }
+} // wrap_key()
+
+/// Function that unwraps (decrypts) a key with the AES key stored in the given slot,
+/// according to RFC 3394 or RFC 5649.
+/// Returns AtcaCheckMacVerifyFailed if the integrity check of the wrapped key fails.
+pub(crate) fn unwrap_key<D: ModePrimitives>(
+    device: &D,
+    algorithm: KeyWrapAlgorithm,
+    slot_id: u8,
+    wrapped_key: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    let slot = key_wrap_common(device, slot_id, wrapped_key.len())?;
+    if (wrapped_key.len() < (2 * KW_SEMIBLOCK_SIZE))
+        || !wrapped_key.len().is_multiple_of(KW_SEMIBLOCK_SIZE)
+    {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    match algorithm {
+        KeyWrapAlgorithm::Kw => {
+            if wrapped_key.len() < (3 * KW_SEMIBLOCK_SIZE) {
+                return Err(AtcaStatus::AtcaInvalidSize);
+            }
+            let (iv, key) = aes_kw_unwrap(device, slot, wrapped_key)?;
+            if iv != KW_DEFAULT_IV {
+                return Err(AtcaStatus::AtcaCheckMacVerifyFailed);
+            }
+            Ok(key)
+        }
+        KeyWrapAlgorithm::Kwp => {
+            let (aiv, padded) = if (2 * KW_SEMIBLOCK_SIZE) == wrapped_key.len() {
+                let block = device.aes_decrypt_block(slot, 0, wrapped_key)?;
+                let mut aiv: [u8; KW_SEMIBLOCK_SIZE] = [0; KW_SEMIBLOCK_SIZE];
+                aiv.copy_from_slice(&block[..KW_SEMIBLOCK_SIZE]);
+                (aiv, block[KW_SEMIBLOCK_SIZE..].to_vec())
+            } else {
+                aes_kw_unwrap(device, slot, wrapped_key)?
+            };
+
+            let mut mli_bytes: [u8; 4] = [0; 4];
+            mli_bytes.copy_from_slice(&aiv[4..]);
+            let mli = u32::from_be_bytes(mli_bytes) as usize;
+
+            if (aiv[..4] != KWP_AIV_PREFIX)
+                || (mli > padded.len())
+                || (mli <= (padded.len() - KW_SEMIBLOCK_SIZE))
+                || padded[mli..].iter().any(|byte| *byte != 0x00)
+            {
+                return Err(AtcaStatus::AtcaCheckMacVerifyFailed);
+            }
+            Ok(padded[..mli].to_vec())
+        }
+    }
+} // unwrap_key()
+
+/// A helper function for the wrap_key() and unwrap_key() methods,
+/// pre-checking input parameters and returning the key identifier for AES commands
+fn key_wrap_common<D: ModePrimitives>(
+    device: &D,
+    slot_id: u8,
+    data_size: usize,
+) -> Result<u16, AtcaStatus> {
+    if device.check_that_configuration_is_not_locked(true) {
+        return Err(AtcaStatus::AtcaNotLocked);
+    }
+    if !device.chip_options()?.aes_enabled {
+        // If chip does not support AES hardware encryption, the operation cannot be performed
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    device.cipher_aes_common(slot_id, data_size, None)
+} // key_wrap_common()
+
+/// Wrapping function W() from RFC 3394, section 2.2.1
+fn aes_kw_wrap<D: ModePrimitives>(
+    device: &D,
+    slot: u16,
+    iv: &[u8; KW_SEMIBLOCK_SIZE],
+    data: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    let n = data.len() / KW_SEMIBLOCK_SIZE;
+    let mut a: [u8; KW_SEMIBLOCK_SIZE] = *iv;
+    let mut r: Vec<u8> = data.to_vec();
+
+    for j in 0..6 {
+        for i in 0..n {
+            let pos = i * KW_SEMIBLOCK_SIZE;
+            let mut block: [u8; ATCA_AES_DATA_SIZE] = [0; ATCA_AES_DATA_SIZE];
+            block[..KW_SEMIBLOCK_SIZE].copy_from_slice(&a);
+            block[KW_SEMIBLOCK_SIZE..].copy_from_slice(&r[pos..(pos + KW_SEMIBLOCK_SIZE)]);
+
+            let b = kw_block(device, slot, &block, CipherOperation::Encrypt)?;
+            a.copy_from_slice(&b[..KW_SEMIBLOCK_SIZE]);
+            xor_counter(&mut a, ((n * j) + i + 1) as u64);
+            r[pos..(pos + KW_SEMIBLOCK_SIZE)].copy_from_slice(&b[KW_SEMIBLOCK_SIZE..]);
+        }
+    }
+
+    let mut result: Vec<u8> = a.to_vec();
+    result.extend_from_slice(&r);
+    Ok(result)
+} // aes_kw_wrap()
+
+/// Unwrapping function W^-1() from RFC 3394, section 2.2.2.
+/// Returns the recovered initial value and the unwrapped data
+fn aes_kw_unwrap<D: ModePrimitives>(
+    device: &D,
+    slot: u16,
+    data: &[u8],
+) -> Result<([u8; KW_SEMIBLOCK_SIZE], Vec<u8>), AtcaStatus> {
+    let n = (data.len() / KW_SEMIBLOCK_SIZE) - 1;
+    let mut a: [u8; KW_SEMIBLOCK_SIZE] = [0; KW_SEMIBLOCK_SIZE];
+    a.copy_from_slice(&data[..KW_SEMIBLOCK_SIZE]);
+    let mut r: Vec<u8> = data[KW_SEMIBLOCK_SIZE..].to_vec();
+
+    for j in (0..6).rev() {
+        for i in (0..n).rev() {
+            let pos = i * KW_SEMIBLOCK_SIZE;
+            xor_counter(&mut a, ((n * j) + i + 1) as u64);
+            let mut block: [u8; ATCA_AES_DATA_SIZE] = [0; ATCA_AES_DATA_SIZE];
+            block[..KW_SEMIBLOCK_SIZE].copy_from_slice(&a);
+            block[KW_SEMIBLOCK_SIZE..].copy_from_slice(&r[pos..(pos + KW_SEMIBLOCK_SIZE)]);
+
+            let b = kw_block(device, slot, &block, CipherOperation::Decrypt)?;
+            a.copy_from_slice(&b[..KW_SEMIBLOCK_SIZE]);
+            r[pos..(pos + KW_SEMIBLOCK_SIZE)].copy_from_slice(&b[KW_SEMIBLOCK_SIZE..]);
+        }
+    }
+
+    Ok((a, r))
+} // aes_kw_unwrap()
+
+/// A helper function that encrypts or decrypts a single AES block with the chip
+#[inline]
+fn kw_block<D: ModePrimitives>(
+    device: &D,
+    slot: u16,
+    block: &[u8],
+    operation: CipherOperation,
+) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+    const BLOCK_IDX: u8 = 0;
+    match operation {
+        CipherOperation::Encrypt => device.aes_encrypt_block(slot, BLOCK_IDX, block),
+        CipherOperation::Decrypt => device.aes_decrypt_block(slot, BLOCK_IDX, block),
+    }
+} // kw_block()
+
+/// XOR of a semiblock with a 64-bit big-endian counter value
+#[inline]
+fn xor_counter(semiblock: &mut [u8; KW_SEMIBLOCK_SIZE], t: u64) {
+    for (byte, t_byte) in semiblock.iter_mut().zip(t.to_be_bytes().iter()) {
+        *byte ^= t_byte;
+    }
+} // xor_counter()
@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+
+use super::aes_gcm::{gcm_decrypt_finish, gcm_decrypt_update, gcm_update_aad};
+use super::aes_gcm::{gcm_encrypt_finish, gcm_encrypt_update};
+use super::{
+    AtcaAesGcmCtx, AtcaDeviceType, AtcaStatus, KdfAlgorithm, KdfSource, KdfTarget, SlotId,
+};
+use super::{ModePrimitives, ECDH_MODE_COPY_TEMP_KEY};
+use super::{ECDH_MODE_SOURCE_EEPROM_SLOT, ECDH_MODE_SOURCE_TEMPKEY};
+
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_GCM_IV_STD_LENGTH, ATCA_ATECC_PUB_KEY_SIZE,
+    ATCA_ATECC_SLOTS_COUNT, ATCA_ECIES_OVERHEAD,
+};
+
+/// Function that encrypts data so that only the owner of the private key
+/// matching 'public_key' can decrypt it.
+/// An ephemeral key pair is generated in TempKey, the premaster secret agreed by ECDH
+/// with the peer public key is turned into an AES key by the KDF command in HKDF mode
+/// (the ephemeral public key being the message) and the data is encrypted in AES GCM mode,
+/// with the ephemeral public key as the additional authenticated data.
+/// None of the secrets leaves the chip. The result has the format:
+/// ephemeral public key (64 bytes) || IV (12 bytes) || ciphertext || tag (16 bytes)
+pub(crate) fn ecies_encrypt<D: ModePrimitives>(
+    device: &D,
+    public_key: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    ecies_parameters_check(device)?;
+    if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    // The IV is drawn before TempKey is loaded, so that the key held there
+    // is not invalidated by the Random command
+    let mut iv: Vec<u8> = Vec::new();
+    let result = device.random(&mut iv);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    iv.truncate(ATCA_AES_GCM_IV_STD_LENGTH);
+
+    let ephemeral_key = device.gen_temp_key()?;
+
+    device.ecdh_without_output(
+        ECDH_MODE_SOURCE_TEMPKEY | ECDH_MODE_COPY_TEMP_KEY,
+        ATCA_ATECC_SLOTS_COUNT,
+        public_key,
+    )?;
+    ecies_derive_key(device, &ephemeral_key)?;
+
+    let mut data = plaintext.to_vec();
+    let mut ctx = AtcaAesGcmCtx {
+        ctx: device.aes_gcm_init(ATCA_ATECC_SLOTS_COUNT, &iv)?,
+    };
+    ctx = gcm_update_aad(device, ctx, &ephemeral_key)?;
+    ctx = gcm_encrypt_update(device, ctx, &mut data)?;
+    let tag = gcm_encrypt_finish(device, ctx, ATCA_AES_DATA_SIZE as u8)?;
+
+    let mut result: Vec<u8> = Vec::with_capacity(plaintext.len() + ATCA_ECIES_OVERHEAD);
+    result.extend_from_slice(&ephemeral_key);
+    result.extend_from_slice(&iv);
+    result.extend_from_slice(&data);
+    result.extend_from_slice(&tag);
+    Ok(result)
+} // ecies_encrypt()
+
+/// Function that decrypts data produced by ecies_encrypt() for the public key
+/// matching the private key stored in the given slot.
+/// If the authenticity of the data cannot be confirmed, AtcaCheckMacVerifyFailed is returned.
+pub(crate) fn ecies_decrypt<D: ModePrimitives>(
+    device: &D,
+    slot_id: u8,
+    data: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    ecies_parameters_check(device)?;
+    SlotId::new(slot_id)?;
+    if data.len() < ATCA_ECIES_OVERHEAD {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    let (ephemeral_key, rest) = data.split_at(ATCA_ATECC_PUB_KEY_SIZE);
+    let (iv, rest) = rest.split_at(ATCA_AES_GCM_IV_STD_LENGTH);
+    let (ciphertext, tag) = rest.split_at(rest.len() - ATCA_AES_DATA_SIZE);
+
+    device.ecdh_parameters_check(slot_id, ephemeral_key)?;
+    device.ecdh_without_output(
+        ECDH_MODE_SOURCE_EEPROM_SLOT | ECDH_MODE_COPY_TEMP_KEY,
+        slot_id,
+        ephemeral_key,
+    )?;
+    ecies_derive_key(device, ephemeral_key)?;
+
+    let mut plaintext = ciphertext.to_vec();
+    let mut ctx = AtcaAesGcmCtx {
+        ctx: device.aes_gcm_init(ATCA_ATECC_SLOTS_COUNT, iv)?,
+    };
+    ctx = gcm_update_aad(device, ctx, ephemeral_key)?;
+    ctx = gcm_decrypt_update(device, ctx, &mut plaintext)?;
+    match gcm_decrypt_finish(device, ctx, tag)? {
+        true => Ok(plaintext),
+        false => Err(AtcaStatus::AtcaCheckMacVerifyFailed),
+    }
+} // ecies_decrypt()
+
+/// A helper function checking whether the chip is able to perform ECIES operations
+fn ecies_parameters_check<D: ModePrimitives>(device: &D) -> Result<(), AtcaStatus> {
+    if device.check_that_configuration_is_not_locked(true) {
+        return Err(AtcaStatus::AtcaNotLocked);
+    }
+    if (device.get_device_type() != AtcaDeviceType::ATECC608A)
+        || !device.chip_options()?.aes_enabled
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok(())
+} // ecies_parameters_check()
+
+/// A helper function that replaces the premaster secret held in TempKey
+/// with the AES key derived from it
+fn ecies_derive_key<D: ModePrimitives>(device: &D, ephemeral_key: &[u8]) -> Result<(), AtcaStatus> {
+    device.kdf_common(
+        KdfAlgorithm::Hkdf,
+        KdfSource::TempKey,
+        KdfTarget::TempKey,
+        ephemeral_key,
+        None,
+    )?;
+    Ok(())
+} // ecies_derive_key()
@@ -0,0 +1,169 @@
+// Modes of operation that run on the host around the commands of the chip:
+// AES CCM, AES GCM, AES key wrap, the TLS 1.2 PRF and ECIES.
+// They are written once against ModePrimitives, which both AteccDevice
+// and SoftwareBackend implement with their own AES, ECDH and KDF commands.
+
+use alloc::vec::Vec;
+
+use cryptoauthlib_sys::{atca_aes_cbc_ctx_t, atca_aes_ctr_ctx_t, atca_aes_gcm_ctx_t};
+
+use super::{
+    AeadParam, AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaDeviceType, AtcaStatus, ChipOptions,
+    CipherOperation, IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget, KeyType,
+    KeyWrapAlgorithm, NonceTarget, SlotConfig, SlotId, TlsFinishedLabel,
+};
+
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_GCM_IV_STD_LENGTH, ATCA_AES_KEY_SIZE, ATCA_ATECC_PUB_KEY_SIZE,
+    ATCA_ATECC_SLOTS_COUNT, ATCA_ECIES_OVERHEAD, ATCA_KEY_SIZE, ATCA_NONCE_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE,
+};
+
+mod aes_ccm;
+mod aes_gcm;
+mod aes_key_wrap;
+mod ecies;
+mod tls;
+
+pub(crate) use aes_ccm::{ccm_decrypt_finish, ccm_decrypt_update, ccm_encrypt_finish};
+pub(crate) use aes_ccm::{ccm_encrypt_update, ccm_init, ccm_update_aad};
+pub(crate) use aes_ccm::{decrypt_aes_ccm, encrypt_aes_ccm};
+pub(crate) use aes_gcm::{decrypt_aes_gcm, encrypt_aes_gcm, gcm_init, gcm_update_aad};
+pub(crate) use aes_gcm::{gcm_decrypt_finish, gcm_decrypt_update};
+pub(crate) use aes_gcm::{gcm_encrypt_finish, gcm_encrypt_update};
+pub(crate) use aes_key_wrap::{unwrap_key, wrap_key};
+pub(crate) use ecies::{ecies_decrypt, ecies_encrypt};
+pub(crate) use tls::{tls_derive_keys, tls_verify_data};
+
+// ECDH command mode bits (ATECC608x)
+pub(crate) const ECDH_MODE_SOURCE_EEPROM_SLOT: u8 = 0x00;
+pub(crate) const ECDH_MODE_SOURCE_TEMPKEY: u8 = 0x01;
+pub(crate) const ECDH_MODE_COPY_TEMP_KEY: u8 = 0x08;
+
+/// Commands of a backend the host-side modes are built on
+pub(crate) trait ModePrimitives {
+    /// Type of the chip
+    fn get_device_type(&self) -> AtcaDeviceType;
+
+    /// True if the configuration zone (or, for 'both', the data zone too) is not locked
+    fn check_that_configuration_is_not_locked(&self, both: bool) -> bool;
+
+    /// Options of the chip read from its configuration zone
+    fn chip_options(&self) -> Result<ChipOptions, AtcaStatus>;
+
+    /// Configuration of a slot read from the configuration zone
+    fn slot_config(&self, slot_id: u8) -> Result<SlotConfig, AtcaStatus>;
+
+    /// Random command
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus;
+
+    /// Nonce command in pass-through mode
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus;
+
+    /// Checks the parameters of an AES operation and returns the key identifier
+    fn cipher_aes_common(
+        &self,
+        slot_id: u8,
+        data_size: usize,
+        key: Option<Vec<u8>>,
+    ) -> Result<u16, AtcaStatus>;
+
+    /// Encrypts a single block with the AES key of a slot or TempKey
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus>;
+
+    /// Decrypts a single block with the AES key of a slot or TempKey
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus>;
+
+    /// Starts AES CTR mode with the given initial counter block
+    fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus>;
+
+    /// Increments the counter block of AES CTR mode
+    fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus>;
+
+    /// Encrypts a single block in AES CBC mode
+    fn aes_cbc_encrypt_block(
+        &self,
+        ctx: atca_aes_cbc_ctx_t,
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus>;
+
+    /// Starts AES GCM mode with the given IV
+    fn aes_gcm_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_gcm_ctx_t, AtcaStatus>;
+
+    /// Feeds at most one block of additional authenticated data into AES GCM mode
+    fn aes_gcm_aad_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus>;
+
+    /// Encrypts at most one block in AES GCM mode
+    fn aes_gcm_encrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        encrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus>;
+
+    /// Decrypts at most one block in AES GCM mode
+    fn aes_gcm_decrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        decrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus>;
+
+    /// Completes an AES GCM encryption and returns the tag
+    fn aes_gcm_encrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus>;
+
+    /// Completes an AES GCM decryption and verifies the tag
+    fn aes_gcm_decrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag: &[u8],
+    ) -> Result<bool, AtcaStatus>;
+
+    /// Checks the parameters of an ECDH command
+    fn ecdh_parameters_check(&self, slot_id: u8, public_key: &[u8]) -> Result<(), AtcaStatus>;
+
+    /// ECDH command leaving the premaster secret in the chip
+    fn ecdh_without_output(
+        &self,
+        mode: u8,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus>;
+
+    /// KDF command, the output is encrypted with 'io_key' if it is given
+    fn kdf_common(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+        io_key: Option<&[u8]>,
+    ) -> Result<Vec<u8>, AtcaStatus>;
+
+    /// Generates an ephemeral P256 key pair in TempKey and returns its public key
+    fn gen_temp_key(&self) -> Result<Vec<u8>, AtcaStatus>;
+}
@@ -0,0 +1,128 @@
+use alloc::vec::Vec;
+
+use super::{
+    AtcaDeviceType, AtcaStatus, IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget,
+    TlsFinishedLabel,
+};
+use super::{ModePrimitives, ECDH_MODE_COPY_TEMP_KEY};
+use super::{ECDH_MODE_SOURCE_EEPROM_SLOT, ECDH_MODE_SOURCE_TEMPKEY};
+
+use super::{ATCA_ATECC_SLOTS_COUNT, ATCA_KEY_SIZE, ATCA_SHA2_256_DIGEST_SIZE};
+
+const TLS_RANDOM_SIZE: usize = 32;
+const TLS_MASTER_SECRET_SIZE: usize = 48;
+/// Maximum size (in bytes) of the key block a single KDF command can produce
+const TLS_MAX_KEY_BLOCK_SIZE: usize = 64;
+const TLS_VERIFY_DATA_SIZE: usize = 12;
+const TLS_LABEL_MASTER_SECRET: &[u8] = b"master secret";
+const TLS_LABEL_KEY_EXPANSION: &[u8] = b"key expansion";
+const TLS_LABEL_CLIENT_FINISHED: &[u8] = b"client finished";
+const TLS_LABEL_SERVER_FINISHED: &[u8] = b"server finished";
+
+/// Function that derives TLS 1.2 session keys (ATECC608A only).
+/// ECDH of the private key from the slot (or TempKey, for slot ATCA_ATECC_SLOTS_COUNT)
+/// with the server ephemeral public key leaves the premaster secret in TempKey,
+/// from which the master secret is derived into TempKey by the KDF command in PRF mode.
+/// The key block ('key_block_length' bytes, at most 64) is then derived from the master secret
+/// and returned, encrypted on the way out with the IO protection key of the session
+/// (or, without a session, as required by the 'kdf_output_protection' setting).
+/// The master secret is left in TempKey for tls_verify_data().
+pub(crate) fn tls_derive_keys<D: ModePrimitives>(
+    device: &D,
+    slot_id: u8,
+    server_public_key: &[u8],
+    client_random: &[u8],
+    server_random: &[u8],
+    key_block_length: usize,
+    session: Option<&IoProtectionSession>,
+) -> Result<Vec<u8>, AtcaStatus> {
+    tls_parameters_check(device)?;
+    device.ecdh_parameters_check(slot_id, server_public_key)?;
+    if (client_random.len() != TLS_RANDOM_SIZE)
+        || (server_random.len() != TLS_RANDOM_SIZE)
+        || (0 == key_block_length)
+        || (key_block_length > TLS_MAX_KEY_BLOCK_SIZE)
+    {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    let source = match slot_id {
+        ATCA_ATECC_SLOTS_COUNT => ECDH_MODE_SOURCE_TEMPKEY,
+        _ => ECDH_MODE_SOURCE_EEPROM_SLOT,
+    };
+    device.ecdh_without_output(source | ECDH_MODE_COPY_TEMP_KEY, slot_id, server_public_key)?;
+
+    let mut message: Vec<u8> = TLS_LABEL_MASTER_SECRET.to_vec();
+    message.extend_from_slice(client_random);
+    message.extend_from_slice(server_random);
+    device.kdf_common(
+        KdfAlgorithm::Prf {
+            key_length: ATCA_KEY_SIZE,
+            target_length: TLS_MAX_KEY_BLOCK_SIZE,
+        },
+        KdfSource::TempKey,
+        KdfTarget::TempKey,
+        &message,
+        None,
+    )?;
+
+    let mut message: Vec<u8> = TLS_LABEL_KEY_EXPANSION.to_vec();
+    message.extend_from_slice(server_random);
+    message.extend_from_slice(client_random);
+    let mut key_block = device.kdf_common(
+        KdfAlgorithm::Prf {
+            key_length: TLS_MASTER_SECRET_SIZE,
+            target_length: TLS_MAX_KEY_BLOCK_SIZE,
+        },
+        KdfSource::TempKey,
+        KdfTarget::Output,
+        &message,
+        session.map(|val| val.io_key()),
+    )?;
+    key_block.truncate(key_block_length);
+    Ok(key_block)
+} // tls_derive_keys()
+
+/// Function that calculates verify_data of a TLS 1.2 Finished message from
+/// the hash of the handshake messages and the master secret left in TempKey
+/// by tls_derive_keys(). The output is protected like the key block.
+pub(crate) fn tls_verify_data<D: ModePrimitives>(
+    device: &D,
+    label: TlsFinishedLabel,
+    handshake_hash: &[u8],
+    session: Option<&IoProtectionSession>,
+) -> Result<Vec<u8>, AtcaStatus> {
+    tls_parameters_check(device)?;
+    if handshake_hash.len() != ATCA_SHA2_256_DIGEST_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    let mut message: Vec<u8> = match label {
+        TlsFinishedLabel::Client => TLS_LABEL_CLIENT_FINISHED.to_vec(),
+        TlsFinishedLabel::Server => TLS_LABEL_SERVER_FINISHED.to_vec(),
+    };
+    message.extend_from_slice(handshake_hash);
+    let mut verify_data = device.kdf_common(
+        KdfAlgorithm::Prf {
+            key_length: TLS_MASTER_SECRET_SIZE,
+            target_length: ATCA_KEY_SIZE,
+        },
+        KdfSource::TempKey,
+        KdfTarget::Output,
+        &message,
+        session.map(|val| val.io_key()),
+    )?;
+    verify_data.truncate(TLS_VERIFY_DATA_SIZE);
+    Ok(verify_data)
+} // tls_verify_data()
+
+/// A helper function checking whether the chip is able to derive TLS keys
+fn tls_parameters_check<D: ModePrimitives>(device: &D) -> Result<(), AtcaStatus> {
+    if device.check_that_configuration_is_not_locked(true) {
+        return Err(AtcaStatus::AtcaNotLocked);
+    }
+    if device.get_device_type() != AtcaDeviceType::ATECC608A {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok(())
+} // tls_parameters_check()
@@ -1,17 +1,21 @@
+use alloc::{boxed::Box, vec::Vec};
 use std::cmp::min;
+use std::convert::TryFrom;
 use std::mem::MaybeUninit;
 
 use super::{
     AtcaStatus, AteccDevice, CipherOperation, CipherParam, FeedbackMode, KeyType, NonceTarget,
+    SlotId,
 };
 
-use super::{
-    ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ATECC_TEMPKEY_KEYID,
-    ATCA_NONCE_SIZE,
-};
+use super::{ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_NONCE_SIZE};
 
 use cryptoauthlib_sys::atca_aes_cbc_ctx_t;
 use cryptoauthlib_sys::atca_aes_ctr_ctx_t;
+use zeroize::Zeroizing;
+
+/// Number of AES blocks processed under a single lock of the API
+const AES_BATCH_BLOCKS: usize = 16;
 
 impl AteccDevice {
     /// Function that performs encryption/decryption in AES ECB mode
@@ -19,42 +23,43 @@ impl AteccDevice {
         &self,
         cipher_param: CipherParam,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
         operation: CipherOperation,
     ) -> AtcaStatus {
         const BLOCK_IDX: u8 = 0;
 
-        let slot: u16;
-        match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
-            Ok(val) => slot = val,
+        let slot: u16 = match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
+            Ok(val) => val,
             Err(err) => return err,
-        }
+        };
 
-        if (data.len() % ATCA_AES_DATA_SIZE) != 0x00 {
+        if !data.len().is_multiple_of(ATCA_AES_DATA_SIZE) {
             return AtcaStatus::AtcaInvalidSize;
         }
 
-        for idx in 0..(data.len() / ATCA_AES_DATA_SIZE) {
-            let start_pos = idx * ATCA_AES_DATA_SIZE;
-            let end_pos = start_pos + ATCA_AES_DATA_SIZE;
-
+        self.aes_batched(data.len() / ATCA_AES_DATA_SIZE, |idx| {
+            let block = &mut data[(idx * ATCA_AES_DATA_SIZE)..((idx + 1) * ATCA_AES_DATA_SIZE)];
+            let mut input: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+            input.clone_from_slice(block);
             match operation {
-                CipherOperation::Encrypt => {
-                    match self.aes_encrypt_block(slot, BLOCK_IDX, &data[start_pos..end_pos]) {
-                        Ok(encr_block) => data[start_pos..end_pos].clone_from_slice(&encr_block),
-                        Err(err) => return err,
-                    }
-                }
-                CipherOperation::Decrypt => {
-                    match self.aes_decrypt_block(slot, BLOCK_IDX, &data[start_pos..end_pos]) {
-                        Ok(decr_block) => data[start_pos..end_pos].clone_from_slice(&decr_block),
-                        Err(err) => return err,
-                    }
-                }
+                CipherOperation::Encrypt => unsafe {
+                    cryptoauthlib_sys::atcab_aes_encrypt(
+                        slot,
+                        BLOCK_IDX,
+                        input.as_ptr(),
+                        block.as_mut_ptr(),
+                    )
+                },
+                CipherOperation::Decrypt => unsafe {
+                    cryptoauthlib_sys::atcab_aes_decrypt(
+                        slot,
+                        BLOCK_IDX,
+                        input.as_ptr(),
+                        block.as_mut_ptr(),
+                    )
+                },
             }
-        }
-
-        AtcaStatus::AtcaSuccess
+        })
     } // AteccDevice::cipher_aes_ecb()
 
     /// Function that performs encryption/decryption in AES CBC mode
@@ -62,7 +67,7 @@ impl AteccDevice {
         &self,
         cipher_param: CipherParam,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
         operation: CipherOperation,
     ) -> AtcaStatus {
         match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
@@ -70,7 +75,7 @@ impl AteccDevice {
             Err(err) => return err,
         }
 
-        if (data.len() % ATCA_AES_DATA_SIZE) != 0x00 {
+        if !data.len().is_multiple_of(ATCA_AES_DATA_SIZE) {
             return AtcaStatus::AtcaInvalidSize;
         }
         if cipher_param.iv.is_none() {
@@ -83,30 +88,28 @@ impl AteccDevice {
             Err(err) => return err,
         }
 
-        let mut block: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
-
-        for idx in 0..(data.len() / ATCA_AES_DATA_SIZE) {
-            let start_pos = idx * ATCA_AES_DATA_SIZE;
-            let end_pos = start_pos + ATCA_AES_DATA_SIZE;
-
+        // CBC chains the blocks through the context, they are processed one after another
+        self.aes_batched(data.len() / ATCA_AES_DATA_SIZE, |idx| {
+            let block = &mut data[(idx * ATCA_AES_DATA_SIZE)..((idx + 1) * ATCA_AES_DATA_SIZE)];
+            let mut input: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+            input.clone_from_slice(block);
             match operation {
-                CipherOperation::Encrypt => {
-                    match self.aes_cbc_encrypt_block(ctx, &data[start_pos..end_pos], &mut block) {
-                        Ok(val) => ctx = val,
-                        Err(err) => return err,
-                    }
-                }
-                CipherOperation::Decrypt => {
-                    match self.aes_cbc_decrypt_block(ctx, &data[start_pos..end_pos], &mut block) {
-                        Ok(val) => ctx = val,
-                        Err(err) => return err,
-                    }
-                }
+                CipherOperation::Encrypt => unsafe {
+                    cryptoauthlib_sys::atcab_aes_cbc_encrypt_block(
+                        &mut ctx,
+                        input.as_ptr(),
+                        block.as_mut_ptr(),
+                    )
+                },
+                CipherOperation::Decrypt => unsafe {
+                    cryptoauthlib_sys::atcab_aes_cbc_decrypt_block(
+                        &mut ctx,
+                        input.as_ptr(),
+                        block.as_mut_ptr(),
+                    )
+                },
             }
-            data[start_pos..end_pos].clone_from_slice(&block)
-        }
-
-        AtcaStatus::AtcaSuccess
+        })
     } // AteccDevice::cipher_aes_cbc()
 
     /// Function that performs encryption/decryption in AES CBC with PKCS#7 padding mode
@@ -150,7 +153,7 @@ impl AteccDevice {
         &self,
         cipher_param: CipherParam,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
     ) -> AtcaStatus {
         match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
             Ok(_) => (),
@@ -175,32 +178,22 @@ impl AteccDevice {
             Err(err) => return err,
         }
 
-        let mut input: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
-        let mut output: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
-        let mut start_pos: usize = 0;
-        let mut shift: usize = min(data.len(), ATCA_AES_DATA_SIZE);
-
-        while shift > 0 {
-            input[..shift].clone_from_slice(&data[start_pos..(start_pos + shift)]);
-
-            match self.aes_ctr_block(ctx, &input, &mut output) {
-                Ok(val) => ctx = val,
-                Err(err) => return err,
-            }
-
-            data[start_pos..(start_pos + shift)].clone_from_slice(&output[..shift]);
-
-            start_pos += shift;
-            let remaining_bytes = data.len() - start_pos;
-            match 0 == remaining_bytes {
-                true => shift = 0,
-                false => {
-                    if remaining_bytes < ATCA_AES_DATA_SIZE {
-                        shift = remaining_bytes
-                    }
-                }
-            }
+        // The keystream does not depend on the data, the counter blocks are encrypted
+        // first and the data is XOR-ed with the keystream once the chip is released
+        let blocks = data.len().div_ceil(ATCA_AES_DATA_SIZE);
+        let mut keystream = Zeroizing::new(vec![0x00; blocks * ATCA_AES_DATA_SIZE]);
+        let zeros: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        let result = self.aes_batched(blocks, |idx| unsafe {
+            cryptoauthlib_sys::atcab_aes_ctr_block(
+                &mut ctx,
+                zeros.as_ptr(),
+                keystream[(idx * ATCA_AES_DATA_SIZE)..].as_mut_ptr(),
+            )
+        });
+        if AtcaStatus::AtcaSuccess != result {
+            return result;
         }
+        xor_keystream(data, &keystream);
 
         AtcaStatus::AtcaSuccess
     } // AteccDevice::cipher_aes_ctr()
@@ -210,7 +203,7 @@ impl AteccDevice {
         &self,
         cipher_param: CipherParam,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
         operation: CipherOperation,
     ) -> AtcaStatus {
         self.cipher_aes_feedback(cipher_param, slot_id, data, operation, FeedbackMode::Cfb)
@@ -221,7 +214,7 @@ impl AteccDevice {
         &self,
         cipher_param: CipherParam,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
         operation: CipherOperation,
     ) -> AtcaStatus {
         self.cipher_aes_feedback(cipher_param, slot_id, data, operation, FeedbackMode::Ofb)
@@ -232,80 +225,75 @@ impl AteccDevice {
         &self,
         cipher_param: CipherParam,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
         operation: CipherOperation,
         mode: FeedbackMode,
     ) -> AtcaStatus {
         const BLOCK_IDX: u8 = 0;
 
-        let slot: u16;
-        match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
-            Ok(val) => slot = val,
+        let slot: u16 = match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
+            Ok(val) => val,
             Err(err) => return err,
-        }
+        };
         if cipher_param.iv.is_none() {
             return AtcaStatus::AtcaBadParam;
         }
 
-        let mut block: Vec<u8> = vec![0x00; ATCA_AES_DATA_SIZE];
-        let mut start_pos: usize = 0;
-        let mut shift: usize = min(data.len(), ATCA_AES_DATA_SIZE);
-        block.clone_from_slice(&cipher_param.iv.unwrap());
-
-        while shift > 0 {
-            if CipherOperation::Encrypt == operation {
-                match self.aes_encrypt_block(slot, BLOCK_IDX, &block) {
-                    Ok(encr_block) => {
-                        block = encr_block
-                            .iter()
-                            .zip(data[start_pos..(shift + start_pos)].iter())
-                            .map(|(&x1, &x2)| x1 ^ x2)
-                            .collect();
-                        data[start_pos..(shift + start_pos)].clone_from_slice(&block);
-                        if FeedbackMode::Ofb == mode && (ATCA_AES_DATA_SIZE == shift) {
-                            block[..shift].clone_from_slice(&encr_block[..shift])
-                        }
-                    }
-                    Err(err) => return err,
-                }
-            } else {
-                match self.aes_encrypt_block(slot, BLOCK_IDX, &block) {
-                    Ok(mut decr_block) => {
-                        if FeedbackMode::Cfb == mode {
-                            block[..shift].clone_from_slice(&data[start_pos..(shift + start_pos)]);
-                            decr_block
-                                .iter_mut()
-                                .zip(block.iter())
-                                .for_each(|(x1, x2)| *x1 ^= *x2);
-                            data[start_pos..(shift + start_pos)]
-                                .clone_from_slice(&decr_block[..shift]);
-                        } else {
-                            block = decr_block
-                                .iter()
-                                .zip(data[start_pos..(shift + start_pos)].iter())
-                                .map(|(&x1, &x2)| x1 ^ x2)
-                                .collect();
-                            data[start_pos..(shift + start_pos)].clone_from_slice(&block);
-                            if ATCA_AES_DATA_SIZE == shift {
-                                block.clone_from_slice(&decr_block)
-                            }
-                        }
-                    }
-                    Err(err) => return err,
+        let iv = cipher_param.iv.unwrap();
+        if iv.len() != ATCA_AES_DATA_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        let blocks = data.len().div_ceil(ATCA_AES_DATA_SIZE);
+
+        // CFB encryption feeds each ciphertext block back into the cipher,
+        // the blocks are processed one after another
+        if FeedbackMode::Cfb == mode && CipherOperation::Encrypt == operation {
+            return self.aes_batched(blocks, |idx| {
+                let mut input: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+                match idx {
+                    0 => input.clone_from_slice(&iv),
+                    _ => input.clone_from_slice(
+                        &data[((idx - 1) * ATCA_AES_DATA_SIZE)..(idx * ATCA_AES_DATA_SIZE)],
+                    ),
                 }
+                let mut output: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+                let result = unsafe {
+                    cryptoauthlib_sys::atcab_aes_encrypt(
+                        slot,
+                        BLOCK_IDX,
+                        input.as_ptr(),
+                        output.as_mut_ptr(),
+                    )
+                };
+                xor_keystream(&mut data[(idx * ATCA_AES_DATA_SIZE)..], &output);
+                result
+            });
+        }
+
+        // In OFB mode and in CFB decryption the keystream is known in advance:
+        // it is the chain of encrypted IV or the encrypted ciphertext blocks
+        let mut keystream = Zeroizing::new(vec![0x00; blocks * ATCA_AES_DATA_SIZE]);
+        let result = self.aes_batched(blocks, |idx| {
+            let mut input: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+            let previous = ((idx.max(1) - 1) * ATCA_AES_DATA_SIZE)..(idx * ATCA_AES_DATA_SIZE);
+            match (idx, mode) {
+                (0, _) => input.clone_from_slice(&iv),
+                (_, FeedbackMode::Ofb) => input.clone_from_slice(&keystream[previous]),
+                (_, FeedbackMode::Cfb) => input.clone_from_slice(&data[previous]),
             }
-
-            start_pos += shift;
-            let remaining_bytes = data.len() - start_pos;
-            match 0 == remaining_bytes {
-                true => shift = 0,
-                false => {
-                    if remaining_bytes < ATCA_AES_DATA_SIZE {
-                        shift = remaining_bytes
-                    }
-                }
+            unsafe {
+                cryptoauthlib_sys::atcab_aes_encrypt(
+                    slot,
+                    BLOCK_IDX,
+                    input.as_ptr(),
+                    keystream[(idx * ATCA_AES_DATA_SIZE)..].as_mut_ptr(),
+                )
             }
+        });
+        if AtcaStatus::AtcaSuccess != result {
+            return result;
         }
+        xor_keystream(data, &keystream);
 
         AtcaStatus::AtcaSuccess
     } // AteccDevice::cipher_aes_feedback()
@@ -322,10 +310,7 @@ impl AteccDevice {
         if iv.len() != ATCA_AES_DATA_SIZE {
             return Err(AtcaStatus::AtcaInvalidSize);
         }
-        let mut slot = slot_id as u16;
-        if slot_id == ATCA_ATECC_SLOTS_COUNT {
-            slot = ATCA_ATECC_TEMPKEY_KEYID;
-        }
+        let slot = SlotId::try_from(slot_id)?.key_id();
 
         let ctx: atca_aes_ctr_ctx_t = {
             let ctx = MaybeUninit::<atca_aes_ctr_ctx_t>::zeroed();
@@ -333,11 +318,7 @@ impl AteccDevice {
         };
         let ctx_ptr = Box::into_raw(Box::new(ctx));
 
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_aes_ctr_init(
                 ctx_ptr,
                 slot,
@@ -350,7 +331,7 @@ impl AteccDevice {
         match result {
             AtcaStatus::AtcaSuccess => Ok({
                 let result = unsafe { *ctx_ptr };
-                unsafe { Box::from_raw(ctx_ptr) };
+                unsafe { drop(Box::from_raw(ctx_ptr)) };
                 result
             }),
             _ => Err(result),
@@ -364,16 +345,11 @@ impl AteccDevice {
     ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
         let ctx_ptr = Box::into_raw(Box::new(ctx));
 
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_aes_ctr_increment(ctx_ptr)
-        });
+        let result =
+            self.atcab_call(|| unsafe { cryptoauthlib_sys::atcab_aes_ctr_increment(ctx_ptr) });
 
         let ctx = unsafe { *ctx_ptr };
-        unsafe { Box::from_raw(ctx_ptr) };
+        unsafe { drop(Box::from_raw(ctx_ptr)) };
 
         match result {
             AtcaStatus::AtcaSuccess => Ok(ctx),
@@ -381,33 +357,6 @@ impl AteccDevice {
         }
     } // AteccDevice::aes_ctr_increment()
 
-    /// Process a block of data using CTR mode and a key within the device.
-    /// aes_ctr_init() should be called before the first use of this function.
-    pub(crate) fn aes_ctr_block(
-        &self,
-        ctx: atca_aes_ctr_ctx_t,
-        input: &[u8],
-        output: &mut [u8],
-    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
-        let ctx_ptr = Box::into_raw(Box::new(ctx));
-
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_aes_ctr_block(ctx_ptr, input.as_ptr(), output.as_mut_ptr())
-        });
-
-        let ctx = unsafe { *ctx_ptr };
-        unsafe { Box::from_raw(ctx_ptr) };
-
-        match result {
-            AtcaStatus::AtcaSuccess => Ok(ctx),
-            _ => Err(result),
-        }
-    } // AteccDevice::aes_ctr_block()
-
     /// Initialize context for AES CBC operation.
     pub(crate) fn aes_cbc_init(
         &self,
@@ -419,10 +368,7 @@ impl AteccDevice {
         if iv.len() != ATCA_AES_DATA_SIZE {
             return Err(AtcaStatus::AtcaInvalidSize);
         }
-        let mut slot = slot_id as u16;
-        if slot_id == ATCA_ATECC_SLOTS_COUNT {
-            slot = ATCA_ATECC_TEMPKEY_KEYID;
-        }
+        let slot = SlotId::try_from(slot_id)?.key_id();
 
         let ctx: atca_aes_cbc_ctx_t = {
             let ctx = MaybeUninit::<atca_aes_cbc_ctx_t>::zeroed();
@@ -430,18 +376,14 @@ impl AteccDevice {
         };
         let ctx_ptr = Box::into_raw(Box::new(ctx));
 
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_aes_cbc_init(ctx_ptr, slot, BLOCK_IDX, iv.as_ptr())
         });
 
         match result {
             AtcaStatus::AtcaSuccess => Ok({
                 let result = unsafe { *ctx_ptr };
-                unsafe { Box::from_raw(ctx_ptr) };
+                unsafe { drop(Box::from_raw(ctx_ptr)) };
                 result
             }),
             _ => Err(result),
@@ -458,11 +400,7 @@ impl AteccDevice {
     ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
         let ctx_ptr = Box::into_raw(Box::new(ctx));
 
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_aes_cbc_encrypt_block(
                 ctx_ptr,
                 plaintext.as_ptr(),
@@ -471,7 +409,7 @@ impl AteccDevice {
         });
 
         let ctx = unsafe { *ctx_ptr };
-        unsafe { Box::from_raw(ctx_ptr) };
+        unsafe { drop(Box::from_raw(ctx_ptr)) };
 
         match result {
             AtcaStatus::AtcaSuccess => Ok(ctx),
@@ -479,37 +417,6 @@ impl AteccDevice {
         }
     } // AteccDevice::aes_cbc_encrypt_block()
 
-    /// Decrypt a block of data using CBC mode and a key within the device.
-    /// aes_cbc_init() should be called before the first use of this function.
-    pub(crate) fn aes_cbc_decrypt_block(
-        &self,
-        ctx: atca_aes_cbc_ctx_t,
-        ciphertext: &[u8],
-        plaintext: &mut [u8],
-    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
-        let ctx_ptr = Box::into_raw(Box::new(ctx));
-
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_aes_cbc_decrypt_block(
-                ctx_ptr,
-                ciphertext.as_ptr(),
-                plaintext.as_mut_ptr(),
-            )
-        });
-
-        let ctx = unsafe { *ctx_ptr };
-        unsafe { Box::from_raw(ctx_ptr) };
-
-        match result {
-            AtcaStatus::AtcaSuccess => Ok(ctx),
-            _ => Err(result),
-        }
-    } // AteccDevice::aes_cbc_decrypt_block()
-
     /// Perform an AES-128 encrypt operation with a key in the device
     pub(crate) fn aes_encrypt_block(
         &self,
@@ -523,11 +430,7 @@ impl AteccDevice {
 
         let mut ciphertext: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
 
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_aes_encrypt(
                 key_id,
                 key_block,
@@ -555,11 +458,7 @@ impl AteccDevice {
 
         let mut plaintext: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
 
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_aes_decrypt(
                 key_id,
                 key_block,
@@ -574,21 +473,48 @@ impl AteccDevice {
         }
     } // AteccDevice::aes_decrypt_block()
 
+    /// Runs the command 'op' for 'count' subsequent AES blocks. Up to AES_BATCH_BLOCKS
+    /// commands are sent under a single lock of the API, so the chip is woken up
+    /// and put back to sleep once per batch rather than once per block,
+    /// while other threads still get their turn between the batches
+    fn aes_batched<F>(&self, count: usize, mut op: F) -> AtcaStatus
+    where
+        F: FnMut(usize) -> cryptoauthlib_sys::ATCA_STATUS,
+    {
+        let mut idx: usize = 0;
+        while idx < count {
+            let end = min(count, idx + AES_BATCH_BLOCKS);
+            let _guard = match self.api_mutex.lock() {
+                Ok(guard) => guard,
+                Err(err) => return err,
+            };
+            while idx < end {
+                let result = AtcaStatus::from(op(idx));
+                if AtcaStatus::AtcaSuccess != result {
+                    return result;
+                }
+                idx += 1;
+            }
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // AteccDevice::aes_batched()
+
     /// A helper function that implements common input parameter tests
     /// and set for AES cipher modes
-    fn cipher_aes_common(
+    pub(crate) fn cipher_aes_common(
         &self,
         slot_id: u8,
         data_size: usize,
         key: Option<Vec<u8>>,
     ) -> Result<u16, AtcaStatus> {
-        if (slot_id > ATCA_ATECC_SLOTS_COUNT)
-            || ((slot_id < ATCA_ATECC_SLOTS_COUNT)
-                && (self.slots[slot_id as usize].config.key_type != KeyType::Aes))
-        {
-            return Err(AtcaStatus::AtcaInvalidId);
+        let key_slot = SlotId::try_from(slot_id)?;
+        if let SlotId::Slot(slot) = key_slot {
+            if self.slot_config(slot)?.key_type != KeyType::Aes {
+                return Err(AtcaStatus::AtcaInvalidId);
+            }
         }
-        if (ATCA_ATECC_SLOTS_COUNT == slot_id)
+        if key_slot.is_temp_key()
             && (key.is_none()
                 || (key.is_some() && (key.as_ref().unwrap().len() != ATCA_AES_KEY_SIZE)))
         {
@@ -598,9 +524,7 @@ impl AteccDevice {
             return Err(AtcaStatus::AtcaInvalidSize);
         }
 
-        let mut slot = slot_id as u16;
-        if slot_id == ATCA_ATECC_SLOTS_COUNT {
-            slot = ATCA_ATECC_TEMPKEY_KEYID;
+        if key_slot.is_temp_key() {
             if let Some(val) = &key {
                 let mut key: Vec<u8> = val.to_vec();
                 key.resize_with(ATCA_NONCE_SIZE, || 0x00);
@@ -611,6 +535,13 @@ impl AteccDevice {
             }
         }
 
-        Ok(slot)
+        Ok(key_slot.key_id())
     } // AteccDevice::cipher_aes_common()
 }
+
+/// XORs 'data' with the beginning of 'keystream'
+fn xor_keystream(data: &mut [u8], keystream: &[u8]) {
+    data.iter_mut()
+        .zip(keystream.iter())
+        .for_each(|(x1, x2)| *x1 ^= *x2);
+} // xor_keystream()
@@ -0,0 +1,112 @@
+use super::{AtcaDeviceType, AtcaStatus, AteccDevice, DigestBuffer, NonceTarget};
+use super::{ATCA_ATECC_PUB_KEY_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE};
+use std::ptr;
+
+// Sign command mode bits
+const SIGN_MODE_EXTERNAL: u8 = 0x80;
+// Verify command mode bits
+const VERIFY_MODE_STORED: u8 = 0x00;
+const VERIFY_MODE_EXTERNAL: u8 = 0x02;
+// Message source bits, the same for Sign and Verify
+const MODE_SOURCE_TEMPKEY: u8 = 0x00;
+const MODE_SOURCE_MSGDIGBUF: u8 = 0x20;
+/// KeyID of the Verify command in External mode: the public key is a P256 key
+const VERIFY_KEY_P256: u16 = 0x0004;
+
+impl AteccDevice {
+    /// Function that signs a 32-byte hash with the private key stored in 'slot_id',
+    /// the hash is loaded into 'buffer' with a Nonce command in pass-through mode
+    /// and the Sign command is told to take it from there.
+    /// The Message Digest Buffer is found in ATECC608x only
+    pub(crate) fn sign_hash_via(
+        &self,
+        buffer: DigestBuffer,
+        hash: &[u8],
+        slot_id: u8,
+        signature: &mut [u8],
+    ) -> AtcaStatus {
+        let (target, source) = match self.digest_buffer_bits(buffer) {
+            Ok(bits) => bits,
+            Err(err) => return err,
+        };
+        if (hash.len() != ATCA_SHA2_256_DIGEST_SIZE) || (signature.len() != ATCA_SIG_SIZE) {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        // Both commands are executed under one lock, so that no other command
+        // can change the buffer in between
+        let _guard = match self.api_mutex.lock() {
+            Ok(guard) => guard,
+            Err(err) => return err,
+        };
+        let result = AtcaStatus::from(unsafe {
+            cryptoauthlib_sys::atcab_nonce_load(target as u8, hash.as_ptr(), hash.len() as u16)
+        });
+        if AtcaStatus::AtcaSuccess != result {
+            return result;
+        }
+        AtcaStatus::from(unsafe {
+            cryptoauthlib_sys::atcab_sign_base(
+                SIGN_MODE_EXTERNAL | source,
+                slot_id as u16,
+                signature.as_mut_ptr(),
+            )
+        })
+    } // AteccDevice::sign_hash_via()
+
+    /// Function that verifies a signature of a 32-byte hash loaded into 'buffer',
+    /// with 'public_key' or, if None, the public key stored in 'slot_id'
+    pub(crate) fn verify_hash_via(
+        &self,
+        buffer: DigestBuffer,
+        public_key: Option<&[u8]>,
+        slot_id: u8,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let (target, source) = self.digest_buffer_bits(buffer)?;
+        if (hash.len() != ATCA_SHA2_256_DIGEST_SIZE) || (signature.len() != ATCA_SIG_SIZE) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let (mode, key_id, public_key_ptr) = match public_key {
+            Some(key) if key.len() == ATCA_ATECC_PUB_KEY_SIZE => {
+                (VERIFY_MODE_EXTERNAL, VERIFY_KEY_P256, key.as_ptr())
+            }
+            Some(_) => return Err(AtcaStatus::AtcaInvalidId),
+            None => (VERIFY_MODE_STORED, slot_id as u16, ptr::null()),
+        };
+
+        let _guard = self.api_mutex.lock()?;
+        let result = AtcaStatus::from(unsafe {
+            cryptoauthlib_sys::atcab_nonce_load(target as u8, hash.as_ptr(), hash.len() as u16)
+        });
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+        let result = AtcaStatus::from(unsafe {
+            cryptoauthlib_sys::atcab_verify(
+                mode | source,
+                key_id,
+                signature.as_ptr(),
+                public_key_ptr,
+                ptr::null(),
+                ptr::null_mut(),
+            )
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(true),
+            AtcaStatus::AtcaCheckMacVerifyFailed => Ok(false),
+            _ => Err(result),
+        }
+    } // AteccDevice::verify_hash_via()
+
+    /// Nonce target and message source bits selecting 'buffer'
+    fn digest_buffer_bits(&self, buffer: DigestBuffer) -> Result<(NonceTarget, u8), AtcaStatus> {
+        match buffer {
+            DigestBuffer::TempKey => Ok((NonceTarget::TempKey, MODE_SOURCE_TEMPKEY)),
+            DigestBuffer::MsgDigBuf if self.get_device_type() == AtcaDeviceType::ATECC608A => {
+                Ok((NonceTarget::MsgDigBuf, MODE_SOURCE_MSGDIGBUF))
+            }
+            DigestBuffer::MsgDigBuf => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // AteccDevice::digest_buffer_bits()
+}
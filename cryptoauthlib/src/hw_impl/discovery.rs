@@ -0,0 +1,102 @@
+// Discovery of the chips on an I2C bus, for installers who do not know which address
+// the chips of a board have been configured with.
+
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+use std::ptr;
+use std::sync::atomic::AtomicPtr;
+
+use super::super::{AtcaDiscoveredDevice, AtcaIface, AtcaIfaceI2c};
+use super::{
+    AtcaDeviceType, AtcaIfaceCfg, AtcaIfaceType, AtcaPowerPolicy, AtcaStatus, ATCA_WORD_SIZE,
+};
+use super::{AtcaIfaceId, AtcabApiMutex, ATECC_RESOURCE_MANAGER};
+
+/// The 8-bit forms of the 7-bit addresses not reserved by the I2C specification
+const FIRST_ADDRESS: u8 = 0x08 << 1;
+const LAST_ADDRESS: u8 = 0x77 << 1;
+/// Bus speed every chip supports
+const PROBE_BAUD: u32 = 100_000;
+const PROBE_WAKE_DELAY: u16 = 1600;
+/// Few retries, so that addresses without a chip are passed quickly
+const PROBE_RX_RETRIES: i32 = 2;
+/// Position of the device ID in the revision returned by the Info command
+const REVISION_DEVICE_ID_IDX: usize = 2;
+
+/// Probes the addresses of an I2C bus with a wake-up and the Info command
+/// and returns the chips that have responded, with their type and revision.
+/// Addresses used by devices that have already been set up are skipped
+pub fn discover(bus: u8) -> Vec<AtcaDiscoveredDevice> {
+    (FIRST_ADDRESS..=LAST_ADDRESS)
+        .step_by(2)
+        .filter_map(|slave_address| probe(bus, slave_address))
+        .collect()
+} // discover()
+
+/// Type of a chip, as told by the device ID in its revision
+pub(crate) fn device_type_from_revision(revision: &[u8]) -> AtcaDeviceType {
+    match revision.get(REVISION_DEVICE_ID_IDX) {
+        Some(0x00) => AtcaDeviceType::ATSHA204A,
+        Some(0x10) => AtcaDeviceType::ATECC108A,
+        Some(0x50) => AtcaDeviceType::ATECC508A,
+        Some(0x60) => AtcaDeviceType::ATECC608A,
+        _ => AtcaDeviceType::AtcaDevUnknown,
+    }
+} // device_type_from_revision()
+
+fn probe(bus: u8, slave_address: u8) -> Option<AtcaDiscoveredDevice> {
+    let iface_cfg = AtcaIfaceCfg {
+        iface_type: AtcaIfaceType::AtcaI2cIface,
+        devtype: AtcaDeviceType::ATECC608A,
+        ..AtcaIfaceCfg::default()
+    }
+    .set_wake_delay(PROBE_WAKE_DELAY)
+    .set_rx_retries(PROBE_RX_RETRIES)
+    .set_iface(
+        AtcaIface::default().set_atcai2c(
+            AtcaIfaceI2c::default()
+                .set_slave_address(slave_address)
+                .set_bus(bus)
+                .set_baud(PROBE_BAUD),
+        ),
+    );
+    let iface_id = AtcaIfaceId::from(&iface_cfg);
+    if !ATECC_RESOURCE_MANAGER.lock().ok()?.acquire(iface_id) {
+        return None;
+    }
+    let revision = read_revision(iface_cfg);
+    ATECC_RESOURCE_MANAGER.lock().ok()?.release(iface_id);
+
+    let revision = revision?;
+    Some(AtcaDiscoveredDevice {
+        slave_address,
+        device_type: device_type_from_revision(&revision),
+        revision,
+    })
+} // probe()
+
+/// Sets up a CryptoAuthLib device only for the Info command and releases it
+fn read_revision(iface_cfg: AtcaIfaceCfg) -> Option<[u8; ATCA_WORD_SIZE]> {
+    let mut cfg = cryptoauthlib_sys::ATCAIfaceCfg::try_from(iface_cfg).ok()?;
+    let mut revision = [0; ATCA_WORD_SIZE];
+    // With no device selected, atcab_init() does not release the device of another instance
+    let api_mutex = AtcabApiMutex {
+        device: AtomicPtr::new(ptr::null_mut()),
+        power_policy: AtcaPowerPolicy::Idle,
+    };
+    let result = AtcaStatus::from(unsafe {
+        let _guard = api_mutex.lock_power_mode().ok()?;
+        let result = match cryptoauthlib_sys::atcab_init(&mut cfg) {
+            cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS => {
+                cryptoauthlib_sys::atcab_info(revision.as_mut_ptr())
+            }
+            err => err,
+        };
+        cryptoauthlib_sys::atcab_release();
+        result
+    });
+    match result {
+        AtcaStatus::AtcaSuccess => Some(revision),
+        _ => None,
+    }
+} // read_revision()
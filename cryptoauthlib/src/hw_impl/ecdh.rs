@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+use std::ptr;
+use zeroize::Zeroizing;
+
+use super::super::host_modes::ECDH_MODE_SOURCE_TEMPKEY;
+use super::super::host_modes::{ECDH_MODE_COPY_TEMP_KEY, ECDH_MODE_SOURCE_EEPROM_SLOT};
+use super::{
+    AtcaDeviceType, AtcaStatus, AteccDevice, EcdhOutput, IoProtectionSession, KeyType,
+    OutputProtectionState,
+};
+
+use super::{ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ECDH_PMS_SIZE, ATCA_KEY_SIZE};
+
+// ECDH command mode bit (ATECC608x), the others are shared with the host-side modes
+const ECDH_MODE_COPY_COMPATIBLE: u8 = 0x00;
+
+impl AteccDevice {
+    /// Function that performs ECDH key agreement with a private key stored
+    /// in a slot (or in TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT),
+    /// honoring the 'ecdh_output_protection' setting of the chip.
+    /// When encrypted output is required, the IO protection key from the session
+    /// (or the one registered with add_access_key() for slot ATCA_ATECC_SLOTS_COUNT) is used
+    /// to decrypt the premaster secret. When output outside the chip is forbidden,
+    /// or no IO protection key is available, the premaster secret is kept in TempKey.
+    pub(crate) fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        self.ecdh_parameters_check(slot_id, public_key)?;
+
+        let source = match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => ECDH_MODE_SOURCE_TEMPKEY,
+            _ => ECDH_MODE_SOURCE_EEPROM_SLOT,
+        };
+        // Slot configured to store the master secret in the next slot
+        if (slot_id < ATCA_ATECC_SLOTS_COUNT)
+            && self.slot_config(slot_id)?.ecc_key_attr.ecdh_secret_out
+        {
+            self.ecdh_without_output(source | ECDH_MODE_COPY_COMPATIBLE, slot_id, public_key)?;
+            return Ok(EcdhOutput::Slot(slot_id + 1));
+        }
+
+        let protection = match self.get_device_type() {
+            AtcaDeviceType::ATECC608A => self.chip_options()?.ecdh_output_protection,
+            _ => OutputProtectionState::ClearTextAllowed,
+        };
+
+        match protection {
+            OutputProtectionState::ClearTextAllowed => match session {
+                Some(val) => self
+                    .ecdh_with_io_key(slot_id, public_key, val.io_key())
+                    .map(EcdhOutput::PreMasterSecret),
+                None => self
+                    .ecdh_clear(slot_id, public_key)
+                    .map(EcdhOutput::PreMasterSecret),
+            },
+            OutputProtectionState::EncryptedOutputOnly => {
+                let io_key = match session {
+                    Some(val) => Some(Zeroizing::new(val.io_key().to_vec())),
+                    None => {
+                        let mut key = Zeroizing::new(Vec::with_capacity(ATCA_KEY_SIZE));
+                        match self.get_access_key(ATCA_ATECC_SLOTS_COUNT, &mut key) {
+                            AtcaStatus::AtcaSuccess => Some(key),
+                            _ => None,
+                        }
+                    }
+                };
+                match io_key {
+                    Some(key) => self
+                        .ecdh_with_io_key(slot_id, public_key, &key)
+                        .map(EcdhOutput::PreMasterSecret),
+                    None => {
+                        self.ecdh_without_output(
+                            source | ECDH_MODE_COPY_TEMP_KEY,
+                            slot_id,
+                            public_key,
+                        )?;
+                        Ok(EcdhOutput::TempKey)
+                    }
+                }
+            }
+            OutputProtectionState::ForbiddenOutputOutsideChip => {
+                self.ecdh_without_output(source | ECDH_MODE_COPY_TEMP_KEY, slot_id, public_key)?;
+                Ok(EcdhOutput::TempKey)
+            }
+            _ => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // AteccDevice::ecdh()
+
+    /// A helper function for the ECDH methods, pre-checking combinations of input parameters
+    pub(crate) fn ecdh_parameters_check(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus> {
+        if slot_id > ATCA_ATECC_SLOTS_COUNT {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+        if slot_id < ATCA_ATECC_SLOTS_COUNT {
+            let slot_data = self.slot_config(slot_id)?;
+            if (slot_data.key_type != KeyType::P256EccKey)
+                || !slot_data.ecc_key_attr.is_private
+                || !slot_data.ecc_key_attr.ecdh_operation
+            {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+        }
+        if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        Ok(())
+    } // AteccDevice::ecdh_parameters_check()
+
+    /// A helper function that executes ECDH command with the premaster secret
+    /// returned in clear text
+    fn ecdh_clear(&self, slot_id: u8, public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let mut pms: Vec<u8> = vec![0; ATCA_ECDH_PMS_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            match slot_id {
+                ATCA_ATECC_SLOTS_COUNT => {
+                    cryptoauthlib_sys::atcab_ecdh_tempkey(public_key.as_ptr(), pms.as_mut_ptr())
+                }
+                _ => cryptoauthlib_sys::atcab_ecdh(
+                    slot_id as u16,
+                    public_key.as_ptr(),
+                    pms.as_mut_ptr(),
+                ),
+            }
+        });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(pms),
+            _ => Err(result),
+        }
+    } // AteccDevice::ecdh_clear()
+
+    /// A helper function that executes ECDH command with the premaster secret
+    /// kept inside the chip (in TempKey or in a slot, depending on the mode)
+    pub(crate) fn ecdh_without_output(
+        &self,
+        mode: u8,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus> {
+        let key_id: u16 = match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => 0x0000,
+            _ => slot_id as u16,
+        };
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_ecdh_base(
+                mode,
+                key_id,
+                public_key.as_ptr(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(()),
+            _ => Err(result),
+        }
+    } // AteccDevice::ecdh_without_output()
+}
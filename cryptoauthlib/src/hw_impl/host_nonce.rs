@@ -0,0 +1,71 @@
+// Host nonce (NumIn) of the Nonce command preceding an encrypted read or write.
+// CryptoAuthLib leaves it to the caller; a constant one lets whoever can replay
+// the traffic on the bus reuse the random number of the chip, so a fresh one
+// is taken for every command unless the caller has supplied it.
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use rand::{distributions::Standard, Rng};
+
+use super::{AtcaStatus, AteccDevice, ATCA_NONCE_NUMIN_SIZE};
+
+impl AteccDevice {
+    /// Function that sets the host nonce used by the next encrypted read or write
+    /// of a slot, None discards the one set before
+    pub(crate) fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus {
+        let num_in = match num_in {
+            Some(val) if val.len() != ATCA_NONCE_NUMIN_SIZE => return AtcaStatus::AtcaInvalidSize,
+            Some(val) => {
+                let mut nonce = [0; ATCA_NONCE_NUMIN_SIZE];
+                nonce.copy_from_slice(val);
+                Some(nonce)
+            }
+            None => None,
+        };
+        *self
+            .host_nonce
+            .lock()
+            .expect("Could not lock 'host_nonce' mutex") = num_in;
+        AtcaStatus::AtcaSuccess
+    } // AteccDevice::set_host_nonce()
+
+    /// Host nonce for an encrypted read or write: the one set by the caller,
+    /// which is used once, or a random one.
+    /// It must be taken before the atcab API mutex is locked
+    pub(super) fn host_nonce(&self) -> Result<[u8; ATCA_NONCE_NUMIN_SIZE], AtcaStatus> {
+        let supplied = self
+            .host_nonce
+            .lock()
+            .expect("Could not lock 'host_nonce' mutex")
+            .take();
+        match supplied {
+            Some(num_in) => Ok(num_in),
+            None => {
+                let random = self.random_host_nonce()?;
+                let mut num_in = [0; ATCA_NONCE_NUMIN_SIZE];
+                num_in.copy_from_slice(&random[..ATCA_NONCE_NUMIN_SIZE]);
+                Ok(num_in)
+            }
+        }
+    } // AteccDevice::host_nonce()
+
+    #[cfg(feature = "std")]
+    fn random_host_nonce(&self) -> Result<Vec<u8>, AtcaStatus> {
+        Ok(rand::thread_rng()
+            .sample_iter(Standard)
+            .take(ATCA_NONCE_NUMIN_SIZE)
+            .collect())
+    }
+
+    /// Without std there is no random number generator on the host side,
+    /// the TRNG of ATECC is used instead
+    #[cfg(not(feature = "std"))]
+    fn random_host_nonce(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let mut num_in = Vec::new();
+        match self.random(&mut num_in) {
+            AtcaStatus::AtcaSuccess if num_in.len() >= ATCA_NONCE_NUMIN_SIZE => Ok(num_in),
+            AtcaStatus::AtcaSuccess => Err(AtcaStatus::AtcaInvalidSize),
+            err => Err(err),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// Chips on kits and USB adapters come and go with the cable. A chip is taken as gone
+// when a few commands in a row have failed to reach it, reconnect() brings it back
+// with the interface configuration stored in the device.
+
+use std::sync::atomic::Ordering;
+
+use super::{AtcaIfaceType, AtcaStatus, AteccDevice, ATCA_SERIAL_NUM_SIZE};
+
+/// Number of commands in a row failing to reach the chip, after which it is taken as gone
+const DISCONNECT_THRESHOLD: usize = 3;
+
+/// Statuses of commands that have not reached the chip or got no response from it
+fn is_comm_failure(status: AtcaStatus) -> bool {
+    matches!(
+        status,
+        AtcaStatus::AtcaCommFail
+            | AtcaStatus::AtcaWakeFailed
+            | AtcaStatus::AtcaTimeout
+            | AtcaStatus::AtcaRxFail
+            | AtcaStatus::AtcaRxTimeout
+            | AtcaStatus::AtcaRxNoResponse
+            | AtcaStatus::AtcaTxFail
+            | AtcaStatus::AtcaTxTimeout
+            | AtcaStatus::AtcaTooManyCommRetries
+            | AtcaStatus::AtcaNoDevices
+    )
+}
+
+impl AteccDevice {
+    /// Counts the commands in a row that have failed to reach the chip,
+    /// any response of the chip, even an error, resets the count
+    pub(super) fn count_comm_failure(&self, status: AtcaStatus) {
+        if is_comm_failure(status) {
+            self.comm_failures.fetch_add(1, Ordering::SeqCst);
+        } else {
+            self.comm_failures.store(0, Ordering::SeqCst);
+        }
+    } // AteccDevice::count_comm_failure()
+
+    /// Tells if the last few commands have all failed to reach the chip
+    pub(super) fn is_disconnected(&self) -> bool {
+        self.comm_failures.load(Ordering::SeqCst) >= DISCONNECT_THRESHOLD
+    } // AteccDevice::is_disconnected()
+
+    /// Re-runs atcab_init() with the interface configuration of the device.
+    /// The configuration and the access keys kept by the device stay valid
+    /// as long as the chip is the same one, so its serial number is checked
+    pub(super) fn reconnect(&self) -> AtcaStatus {
+        if self.released.load(Ordering::SeqCst) {
+            return AtcaStatus::AtcaBadParam;
+        }
+        // The HAL of a transport is released along with the CryptoAuthLib device,
+        // a transport has to restore its bus itself
+        if self.iface_id.iface_type == AtcaIfaceType::AtcaCustomIface {
+            return AtcaStatus::AtcaUnimplemented;
+        }
+        let result = AtcaStatus::from(unsafe {
+            let _guard = self
+                .api_mutex
+                .lock_power_mode()
+                .expect("Could not lock atcab API mutex");
+            // With the device of this instance selected, atcab_init() releases it
+            // and creates a new one in its place
+            let result = cryptoauthlib_sys::atcab_init(self.iface_cfg_ptr.ptr);
+            self.api_mutex
+                .device
+                .store(cryptoauthlib_sys::_gDevice, Ordering::SeqCst);
+            result
+        });
+        if result != AtcaStatus::AtcaSuccess {
+            return result;
+        }
+
+        let mut serial_number = [0; ATCA_SERIAL_NUM_SIZE];
+        let result = self.read_serial_number(&mut serial_number);
+        if result != AtcaStatus::AtcaSuccess {
+            return result;
+        }
+        // A configuration not read yet is read from the chip found now
+        if let Some(chip_config) = self.chip_config.get() {
+            if serial_number != chip_config.serial_number {
+                return AtcaStatus::AtcaInvalidId;
+            }
+        }
+        self.comm_failures.store(0, Ordering::SeqCst);
+        AtcaStatus::AtcaSuccess
+    } // AteccDevice::reconnect()
+}
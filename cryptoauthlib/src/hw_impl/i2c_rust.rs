@@ -0,0 +1,254 @@
+// CryptoAuth I2C protocol spoken directly over Linux /dev/i2c-* with i2cdev.
+// The functions are registered with CryptoAuthLib as a custom HAL
+// (ATCA_CUSTOM_IFACE), so only the C I2C HAL of the library is replaced:
+// command building, CRC and execution still go through the C CryptoAuthLib,
+// which therefore remains a build and link dependency.
+
+use std::os::raw::{c_int, c_void};
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::Duration;
+
+use cryptoauthlib_sys::ATCA_STATUS;
+use i2cdev::core::I2CDevice;
+use i2cdev::linux::LinuxI2CDevice;
+
+/// Word address values prepended to the data written to the chip
+const I2C_WORD_ADDRESS_RESET: u8 = 0x00;
+const I2C_WORD_ADDRESS_SLEEP: u8 = 0x01;
+const I2C_WORD_ADDRESS_IDLE: u8 = 0x02;
+const I2C_WORD_ADDRESS_COMMAND: u8 = 0x03;
+/// Response of the chip to a wake token
+const WAKE_RESPONSE: [u8; 4] = [0x04, 0x11, 0x33, 0x43];
+/// Time between attempts to read a response the chip is still computing
+const RX_RETRY_INTERVAL: Duration = Duration::from_micros(100);
+
+/// Settings of the bus of a device
+#[derive(Copy, Clone)]
+struct I2cRustCfg {
+    bus: u8,
+    slave_address: u8,
+    wake_delay: u16,
+    rx_retries: i32,
+}
+
+/// The HAL of one device. CryptoAuthLib passes the interface of the device
+/// to the HAL functions, the device is found by the address of its interface
+/// configuration, which stays in place until the AteccDevice is released
+struct I2cRustHal {
+    iface_cfg: usize,
+    cfg: I2cRustCfg,
+    device: Option<LinuxI2CDevice>,
+}
+
+lazy_static! {
+    static ref I2C_RUST_HALS: Mutex<Vec<I2cRustHal>> = Mutex::new(Vec::new());
+}
+
+/// Stores the settings used by the HAL when CryptoAuthLib initializes
+/// the device with the interface configuration 'iface_cfg'
+pub(crate) fn configure(
+    iface_cfg: *const cryptoauthlib_sys::ATCAIfaceCfg,
+    bus: u8,
+    slave_address: u8,
+    wake_delay: u16,
+    rx_retries: i32,
+) {
+    let mut hals = I2C_RUST_HALS.lock().expect("Could not lock I2C HAL mutex");
+    hals.retain(|hal| hal.iface_cfg != iface_cfg as usize);
+    hals.push(I2cRustHal {
+        iface_cfg: iface_cfg as usize,
+        cfg: I2cRustCfg {
+            bus,
+            slave_address,
+            wake_delay,
+            rx_retries,
+        },
+        device: None,
+    });
+} // configure()
+
+/// Forgets the device with the interface configuration 'iface_cfg' and closes its bus
+pub(crate) fn unconfigure(iface_cfg: *const cryptoauthlib_sys::ATCAIfaceCfg) {
+    if let Ok(mut hals) = I2C_RUST_HALS.lock() {
+        hals.retain(|hal| hal.iface_cfg != iface_cfg as usize);
+    }
+} // unconfigure()
+
+/// The set of HAL functions to be placed in the 'atcacustom' interface configuration
+pub(crate) fn custom_iface() -> cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5 {
+    cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5 {
+        halinit: Some(hal_init),
+        halpostinit: Some(hal_post_init),
+        halsend: Some(hal_send),
+        halreceive: Some(hal_receive),
+        halwake: Some(hal_wake),
+        halidle: Some(hal_idle),
+        halsleep: Some(hal_sleep),
+        halrelease: Some(hal_release),
+    }
+} // custom_iface()
+
+/// Runs an operation on the opened bus of the device of the CryptoAuthLib interface 'iface'
+unsafe fn with_device<T, F>(iface: *mut c_void, operation: F) -> Result<T, ATCA_STATUS>
+where
+    F: FnOnce(&mut LinuxI2CDevice, I2cRustCfg) -> Result<T, ATCA_STATUS>,
+{
+    let iface = iface as cryptoauthlib_sys::ATCAIface;
+    if iface.is_null() {
+        return Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM);
+    }
+    let iface_cfg = (*iface).mIfaceCFG as usize;
+    let mut hals = I2C_RUST_HALS
+        .lock()
+        .map_err(|_| cryptoauthlib_sys::ATCA_STATUS_ATCA_FUNC_FAIL)?;
+    let hal = hals
+        .iter_mut()
+        .find(|hal| hal.iface_cfg == iface_cfg)
+        .ok_or(cryptoauthlib_sys::ATCA_STATUS_ATCA_COMM_FAIL)?;
+    let cfg = hal.cfg;
+    match hal.device.as_mut() {
+        Some(device) => operation(device, cfg),
+        None => Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_COMM_FAIL),
+    }
+}
+
+/// Writes the data to the chip
+unsafe fn write(iface: *mut c_void, data: &[u8]) -> ATCA_STATUS {
+    let result = with_device(iface, |device, _| {
+        device
+            .write(data)
+            .map_err(|_| cryptoauthlib_sys::ATCA_STATUS_ATCA_TX_FAIL)
+    });
+    match result {
+        Ok(()) => cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS,
+        Err(err) => err,
+    }
+}
+
+/// Opens the bus of the device with the interface configuration 'iface_cfg'.
+/// A bus opened before, when CryptoAuthLib initializes the device again, is replaced
+unsafe extern "C" fn hal_init(_hal: *mut c_void, iface_cfg: *mut c_void) -> ATCA_STATUS {
+    let mut hals = match I2C_RUST_HALS.lock() {
+        Ok(hals) => hals,
+        Err(_) => return cryptoauthlib_sys::ATCA_STATUS_ATCA_FUNC_FAIL,
+    };
+    let hal = match hals
+        .iter_mut()
+        .find(|hal| hal.iface_cfg == iface_cfg as usize)
+    {
+        Some(hal) => hal,
+        None => return cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM,
+    };
+    let cfg = hal.cfg;
+    hal.device = None;
+    // The configured address is the 8-bit one, as for the C HAL
+    let path = format!("/dev/i2c-{}", cfg.bus);
+    match LinuxI2CDevice::new(&path, (cfg.slave_address >> 1) as u16) {
+        Ok(device) => {
+            hal.device = Some(device);
+            cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS
+        }
+        Err(_) => cryptoauthlib_sys::ATCA_STATUS_ATCA_COMM_FAIL,
+    }
+}
+
+unsafe extern "C" fn hal_post_init(_iface: *mut c_void) -> ATCA_STATUS {
+    cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS
+}
+
+/// Sends a command packet. Its first byte is reserved by CryptoAuthLib
+/// for the word address, 'txlength' does not count it.
+unsafe extern "C" fn hal_send(iface: *mut c_void, txdata: *mut u8, txlength: c_int) -> ATCA_STATUS {
+    if txdata.is_null() || txlength < 0 {
+        return cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM;
+    }
+    let packet = std::slice::from_raw_parts_mut(txdata, txlength as usize + 1);
+    packet[0] = I2C_WORD_ADDRESS_COMMAND;
+    write(iface, packet)
+}
+
+/// Reads a response: the count byte first (the chip does not acknowledge
+/// while it is busy), then the rest of the response
+unsafe extern "C" fn hal_receive(
+    iface: *mut c_void,
+    rxdata: *mut u8,
+    rxlength: *mut u16,
+) -> ATCA_STATUS {
+    if rxdata.is_null() || rxlength.is_null() || *rxlength < 1 {
+        return cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM;
+    }
+    let buffer = std::slice::from_raw_parts_mut(rxdata, *rxlength as usize);
+    let result = with_device(iface, |device, cfg| {
+        let mut retries = cfg.rx_retries;
+        let mut count = [0u8; 1];
+        while device.read(&mut count).is_err() {
+            if retries <= 0 {
+                return Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_RX_NO_RESPONSE);
+            }
+            retries -= 1;
+            sleep(RX_RETRY_INTERVAL);
+        }
+        let count = count[0] as usize;
+        if count < 4 {
+            return Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_INVALID_SIZE);
+        }
+        if count > buffer.len() {
+            return Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_SMALL_BUFFER);
+        }
+        buffer[0] = count as u8;
+        device
+            .read(&mut buffer[1..count])
+            .map_err(|_| cryptoauthlib_sys::ATCA_STATUS_ATCA_RX_FAIL)?;
+        Ok(count as u16)
+    });
+    match result {
+        Ok(count) => {
+            *rxlength = count;
+            cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS
+        }
+        Err(err) => err,
+    }
+}
+
+/// Wakes the chip: a 0x00 byte is written to the chip's own address, which a
+/// sleeping chip does not acknowledge, then, after the wake delay, the wake
+/// response is checked
+unsafe extern "C" fn hal_wake(iface: *mut c_void) -> ATCA_STATUS {
+    let result = with_device(iface, |device, cfg| {
+        let _ = device.write(&[I2C_WORD_ADDRESS_RESET]);
+        sleep(Duration::from_micros(cfg.wake_delay as u64));
+
+        let mut response = [0u8; 4];
+        let mut retries = cfg.rx_retries;
+        while device.read(&mut response).is_err() {
+            if retries <= 0 {
+                return Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_WAKE_FAILED);
+            }
+            retries -= 1;
+            sleep(RX_RETRY_INTERVAL);
+        }
+        match response == WAKE_RESPONSE {
+            true => Ok(()),
+            false => Err(cryptoauthlib_sys::ATCA_STATUS_ATCA_WAKE_FAILED),
+        }
+    });
+    match result {
+        Ok(()) => cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS,
+        Err(err) => err,
+    }
+}
+
+unsafe extern "C" fn hal_idle(iface: *mut c_void) -> ATCA_STATUS {
+    write(iface, &[I2C_WORD_ADDRESS_IDLE])
+}
+
+unsafe extern "C" fn hal_sleep(iface: *mut c_void) -> ATCA_STATUS {
+    write(iface, &[I2C_WORD_ADDRESS_SLEEP])
+}
+
+/// Nothing tells the device 'hal_data' belongs to, the bus is closed
+/// by unconfigure() when the AteccDevice is released
+unsafe extern "C" fn hal_release(_hal_data: *mut c_void) -> ATCA_STATUS {
+    cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS
+}
@@ -0,0 +1,220 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use rand::{distributions::Standard, Rng};
+
+use super::{
+    AtcaDeviceType, AtcaStatus, AteccDevice, IoProtectionSession, KeyType, SlotId, VerifyMode,
+    WriteConfig,
+};
+
+use super::{
+    ATCA_AES_KEY_SIZE, ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_BLOCK_SIZE,
+    ATCA_ECDH_PMS_SIZE, ATCA_KEY_SIZE, ATCA_NONCE_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
+
+impl AteccDevice {
+    /// Function that opens an IO protection session with the ATECC608x chip.
+    /// The key must be the same as the one stored in the slot
+    /// indicated by 'io_key_in_slot' in ChipOptions.
+    pub(crate) fn open_io_protection_session(
+        &self,
+        io_key: &[u8],
+    ) -> Result<IoProtectionSession, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if (self.get_device_type() != AtcaDeviceType::ATECC608A)
+            || !self.chip_options()?.io_key_enabled
+        {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        if io_key.len() != ATCA_KEY_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        Ok(IoProtectionSession::new(
+            self.chip_options()?.io_key_in_slot,
+            io_key,
+        ))
+    } // AteccDevice::open_io_protection_session()
+
+    /// Function that performs ECDH key agreement with a private key stored
+    /// in a slot (or in TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT).
+    /// The premaster secret is transmitted encrypted with the IO protection key
+    /// and returned to the caller after decryption on the host side.
+    pub(crate) fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        self.ecdh_parameters_check(slot_id, public_key)?;
+
+        self.ecdh_with_io_key(slot_id, public_key, session.io_key())
+    } // AteccDevice::ecdh_io_protected()
+
+    /// A helper function that executes ECDH command with the premaster secret
+    /// encrypted by the chip with the given IO protection key and decrypted on the host side
+    pub(crate) fn ecdh_with_io_key(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        io_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let mut pms: Vec<u8> = vec![0; ATCA_ECDH_PMS_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            match slot_id {
+                ATCA_ATECC_SLOTS_COUNT => cryptoauthlib_sys::atcab_ecdh_tempkey_ioenc(
+                    public_key.as_ptr(),
+                    pms.as_mut_ptr(),
+                    io_key.as_ptr(),
+                ),
+                _ => cryptoauthlib_sys::atcab_ecdh_ioenc(
+                    slot_id as u16,
+                    public_key.as_ptr(),
+                    pms.as_mut_ptr(),
+                    io_key.as_ptr(),
+                ),
+            }
+        });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(pms),
+            _ => Err(result),
+        }
+    } // AteccDevice::ecdh_with_io_key()
+
+    /// Function that verifies an ECDSA signature and additionally checks
+    /// the validation MAC calculated by the chip with the IO protection key,
+    /// so that the verification result cannot be forged on the bus.
+    /// Only 'External' and 'Internal' verification modes are accepted.
+    pub(crate) fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if (signature.len() != ATCA_SIG_SIZE) || (hash.len() != ATCA_SHA2_256_DIGEST_SIZE) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        };
+
+        let num_in = self.system_nonce()?;
+        let mut is_verified: bool = false;
+
+        let result = match mode {
+            VerifyMode::Internal(slot_number) => {
+                SlotId::new(slot_number)?;
+                self.atcab_call(|| unsafe {
+                    cryptoauthlib_sys::atcab_verify_stored_mac(
+                        hash.as_ptr(),
+                        signature.as_ptr(),
+                        slot_number as u16,
+                        num_in.as_ptr(),
+                        session.io_key().as_ptr(),
+                        &mut is_verified,
+                    )
+                })
+            }
+            VerifyMode::External(public_key) => {
+                if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+                    return Err(AtcaStatus::AtcaInvalidSize);
+                }
+                self.atcab_call(|| unsafe {
+                    cryptoauthlib_sys::atcab_verify_extern_mac(
+                        hash.as_ptr(),
+                        signature.as_ptr(),
+                        public_key.as_ptr(),
+                        num_in.as_ptr(),
+                        session.io_key().as_ptr(),
+                        &mut is_verified,
+                    )
+                })
+            }
+            _ => return Err(AtcaStatus::AtcaBadParam),
+        };
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(is_verified),
+            _ => Err(result),
+        }
+    } // AteccDevice::verify_hash_io_protected()
+
+    /// System nonce for the verification MAC, generated on the host side
+    #[cfg(feature = "std")]
+    fn system_nonce(&self) -> Result<Vec<u8>, AtcaStatus> {
+        Ok(rand::thread_rng()
+            .sample_iter(Standard)
+            .take(ATCA_NONCE_SIZE)
+            .collect())
+    }
+
+    /// System nonce for the verification MAC. Without std there is no random number
+    /// generator on the host side, the TRNG of ATECC is used instead
+    #[cfg(not(feature = "std"))]
+    fn system_nonce(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let mut num_in = Vec::new();
+        match self.random(&mut num_in) {
+            AtcaStatus::AtcaSuccess => {
+                num_in.truncate(ATCA_NONCE_SIZE);
+                Ok(num_in)
+            }
+            err => Err(err),
+        }
+    }
+
+    /// Function that loads an AES key into a slot whose write key
+    /// is the IO protection key. The key is transmitted encrypted.
+    pub(crate) fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let slot_data = match self.slot_config(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+        if !self.chip_options().unwrap_or_default().aes_enabled
+            || (slot_data.key_type != KeyType::Aes)
+            || (slot_data.write_config != WriteConfig::Encrypt)
+            || (slot_data.write_key != session.io_key_slot())
+        {
+            return AtcaStatus::AtcaBadParam;
+        }
+        if key.len() != ATCA_AES_KEY_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        const BLOCK_IDX: u8 = 0;
+        let mut data: [u8; ATCA_BLOCK_SIZE] = [0; ATCA_BLOCK_SIZE];
+        data[..ATCA_AES_KEY_SIZE].copy_from_slice(key);
+        let num_in = match self.host_nonce() {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_write_enc(
+                slot_id as u16,
+                BLOCK_IDX,
+                data.as_ptr(),
+                session.io_key().as_ptr(),
+                session.io_key_slot() as u16,
+                num_in.as_ptr(),
+            )
+        })
+    } // AteccDevice::import_aes_key_io_protected()
+}
@@ -0,0 +1,221 @@
+use super::{
+    AtcaDeviceType, AtcaStatus, AteccDevice, KdfAlgorithm, KdfSource, KdfTarget,
+    OutputProtectionState, SlotId,
+};
+use alloc::vec::Vec;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_BLOCK_SIZE, ATCA_KEY_SIZE, ATCA_NONCE_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE,
+};
+
+// KDF command mode bits
+const KDF_MODE_SOURCE_TEMPKEY: u8 = 0x00;
+const KDF_MODE_SOURCE_TEMPKEY_UP: u8 = 0x01;
+const KDF_MODE_SOURCE_SLOT: u8 = 0x02;
+const KDF_MODE_SOURCE_ALTKEYBUF: u8 = 0x03;
+const KDF_MODE_TARGET_TEMPKEY: u8 = 0x00;
+const KDF_MODE_TARGET_TEMPKEY_UP: u8 = 0x04;
+const KDF_MODE_TARGET_SLOT: u8 = 0x08;
+const KDF_MODE_TARGET_ALTKEYBUF: u8 = 0x0C;
+const KDF_MODE_TARGET_OUTPUT: u8 = 0x10;
+const KDF_MODE_TARGET_OUTPUT_ENC: u8 = 0x14;
+const KDF_MODE_ALG_PRF: u8 = 0x00;
+const KDF_MODE_ALG_AES: u8 = 0x20;
+const KDF_MODE_ALG_HKDF: u8 = 0x40;
+// KDF command details bits
+const KDF_DETAILS_PRF_TARGET_LEN_64: u32 = 0x0000_0100;
+const KDF_DETAILS_HKDF_MSG_LOC_INPUT: u32 = 0x0000_0002;
+const KDF_DETAILS_MSG_LEN_SHIFT: u32 = 24;
+/// Maximum size (in bytes) of a message for PRF and HKDF algorithms
+const KDF_MAX_MSG_SIZE: usize = 128;
+/// Maximum size (in bytes) of a key derived by the KDF command
+const KDF_MAX_OUT_SIZE: usize = 64;
+/// Part of the output nonce used to decrypt a single block of protected output
+const KDF_OUT_NONCE_BLOCK_SIZE: usize = 16;
+
+impl AteccDevice {
+    /// Function that executes the KDF command on the ATECC608x chip.
+    /// For 'KdfTarget::Output' the derived key is returned; if the 'kdf_output_protection'
+    /// setting of the chip requires encrypted output, the key is decrypted on the host side
+    /// with the IO protection key registered by add_access_key() for slot ATCA_ATECC_SLOTS_COUNT.
+    /// For other targets the derived key stays in the chip and an empty vector is returned.
+    pub(crate) fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.kdf_common(algorithm, source, target, message, None)
+    } // AteccDevice::kdf()
+
+    /// A helper function executing the KDF command. If 'io_key' is given, output
+    /// of the derived key is always encrypted with it, otherwise the encryption
+    /// follows the 'kdf_output_protection' setting of the chip.
+    pub(crate) fn kdf_common(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+        io_key: Option<&[u8]>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if self.get_device_type() != AtcaDeviceType::ATECC608A {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        let (alg_mode, mut details, out_size) = match algorithm {
+            KdfAlgorithm::Prf {
+                key_length,
+                target_length,
+            } => {
+                let key_len_bits: u32 = match key_length {
+                    16 => 0,
+                    32 => 1,
+                    48 => 2,
+                    64 => 3,
+                    _ => return Err(AtcaStatus::AtcaInvalidSize),
+                };
+                let target_len_bits: u32 = match target_length {
+                    32 => 0,
+                    64 => KDF_DETAILS_PRF_TARGET_LEN_64,
+                    _ => return Err(AtcaStatus::AtcaInvalidSize),
+                };
+                (
+                    KDF_MODE_ALG_PRF,
+                    key_len_bits | target_len_bits,
+                    target_length,
+                )
+            }
+            KdfAlgorithm::Aes { key_location } => {
+                if key_location > 3 {
+                    return Err(AtcaStatus::AtcaBadParam);
+                }
+                if message.len() != ATCA_AES_DATA_SIZE {
+                    return Err(AtcaStatus::AtcaInvalidSize);
+                }
+                (KDF_MODE_ALG_AES, key_location as u32, ATCA_AES_DATA_SIZE)
+            }
+            KdfAlgorithm::Hkdf => (
+                KDF_MODE_ALG_HKDF,
+                KDF_DETAILS_HKDF_MSG_LOC_INPUT,
+                ATCA_SHA2_256_DIGEST_SIZE,
+            ),
+        };
+        if KDF_MODE_ALG_AES != alg_mode {
+            if message.len() > KDF_MAX_MSG_SIZE {
+                return Err(AtcaStatus::AtcaInvalidSize);
+            }
+            details |= (message.len() as u32) << KDF_DETAILS_MSG_LEN_SHIFT;
+        }
+
+        let mut key_id: u16 = 0;
+        let source_mode = match source {
+            KdfSource::TempKey => KDF_MODE_SOURCE_TEMPKEY,
+            KdfSource::TempKeyUpper => KDF_MODE_SOURCE_TEMPKEY_UP,
+            KdfSource::AltKeyBuf => KDF_MODE_SOURCE_ALTKEYBUF,
+            KdfSource::Slot(slot_id) => {
+                SlotId::new(slot_id)?;
+                key_id |= slot_id as u16;
+                KDF_MODE_SOURCE_SLOT
+            }
+        };
+
+        let protection = self.chip_options()?.kdf_output_protection;
+        let target_mode = match target {
+            KdfTarget::TempKey => KDF_MODE_TARGET_TEMPKEY,
+            KdfTarget::TempKeyUpper => KDF_MODE_TARGET_TEMPKEY_UP,
+            KdfTarget::AltKeyBuf => KDF_MODE_TARGET_ALTKEYBUF,
+            KdfTarget::Slot(slot_id) => {
+                SlotId::new(slot_id)?;
+                key_id |= (slot_id as u16) << 8;
+                KDF_MODE_TARGET_SLOT
+            }
+            KdfTarget::Output => match protection {
+                OutputProtectionState::ClearTextAllowed if io_key.is_some() => {
+                    KDF_MODE_TARGET_OUTPUT_ENC
+                }
+                OutputProtectionState::ClearTextAllowed => KDF_MODE_TARGET_OUTPUT,
+                OutputProtectionState::EncryptedOutputOnly => KDF_MODE_TARGET_OUTPUT_ENC,
+                _ => return Err(AtcaStatus::AtcaBadParam),
+            },
+        };
+
+        // the IO protection key must be known before the command is executed
+        let mut io_key = Zeroizing::new(io_key.map(|val| val.to_vec()).unwrap_or_default());
+        if (KDF_MODE_TARGET_OUTPUT_ENC == target_mode) && io_key.is_empty() {
+            let result = self.get_access_key(ATCA_ATECC_SLOTS_COUNT, &mut io_key);
+            if AtcaStatus::AtcaSuccess != result {
+                return Err(result);
+            }
+        }
+
+        let mut out_data: [u8; KDF_MAX_OUT_SIZE] = [0; KDF_MAX_OUT_SIZE];
+        let mut out_nonce: [u8; ATCA_NONCE_SIZE] = [0; ATCA_NONCE_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_kdf(
+                alg_mode | source_mode | target_mode,
+                key_id,
+                details,
+                message.as_ptr(),
+                out_data.as_mut_ptr(),
+                out_nonce.as_mut_ptr(),
+            )
+        });
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+
+        let output = match target_mode {
+            KDF_MODE_TARGET_OUTPUT => Ok(out_data[..out_size].to_vec()),
+            KDF_MODE_TARGET_OUTPUT_ENC => {
+                let mut data = out_data[..out_size].to_vec();
+                io_decrypt(&io_key, &out_nonce, &mut data).map(|_| data)
+            }
+            _ => Ok(Vec::new()),
+        };
+        out_data.zeroize();
+        output
+    } // AteccDevice::kdf_common()
+}
+
+/// Function that decrypts data encrypted by the chip with the IO protection key.
+/// Each 32-byte block of data is XOR-ed with SHA256(io_key || out_nonce[16 bytes]),
+/// where consecutive blocks use consecutive 16-byte parts of the output nonce.
+fn io_decrypt(io_key: &[u8], out_nonce: &[u8], data: &mut [u8]) -> Result<(), AtcaStatus> {
+    if (io_key.len() != ATCA_KEY_SIZE)
+        || (data.len() > ((out_nonce.len() / KDF_OUT_NONCE_BLOCK_SIZE) * ATCA_BLOCK_SIZE))
+    {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+
+    for (idx, block) in data.chunks_mut(ATCA_BLOCK_SIZE).enumerate() {
+        let nonce_start = idx * KDF_OUT_NONCE_BLOCK_SIZE;
+        let mut hash_input = Zeroizing::new(io_key.to_vec());
+        hash_input
+            .extend_from_slice(&out_nonce[nonce_start..(nonce_start + KDF_OUT_NONCE_BLOCK_SIZE)]);
+
+        let mut key = Zeroizing::new([0; ATCA_SHA2_256_DIGEST_SIZE]);
+        let result = unsafe {
+            cryptoauthlib_sys::atcac_sw_sha2_256(
+                hash_input.as_ptr(),
+                hash_input.len() as cryptoauthlib_sys::size_t,
+                key.as_mut_ptr(),
+            )
+        };
+        if 0 != result {
+            return Err(AtcaStatus::AtcaFuncFail);
+        }
+
+        for (byte, key_byte) in block.iter_mut().zip(key.iter()) {
+            *byte ^= key_byte;
+        }
+    }
+
+    Ok(())
+} // io_decrypt()
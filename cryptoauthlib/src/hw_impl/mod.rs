@@ -1,81 +1,264 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
 use std::cell::RefCell;
-use std::collections::HashMap;
 use std::convert::{From, TryFrom};
 use std::ptr;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU32, AtomicUsize, Ordering};
+
+#[cfg(feature = "std")]
+use super::sync::wait_limit;
+use super::sync::{FairMutex, FairMutexGuard, Mutex, OnceCell};
+use zeroize::{Zeroize, Zeroizing};
 
-#[cfg(test)]
-use cryptoauthlib_sys::atca_aes_cbc_ctx_t;
-#[cfg(test)]
-use cryptoauthlib_sys::atca_aes_ctr_ctx_t;
+use cryptoauthlib_sys::{atca_aes_cbc_ctx_t, atca_aes_ctr_ctx_t, atca_aes_gcm_ctx_t};
 
+use super::host_modes::{self, ModePrimitives};
+use super::secure_session::{MAC_MODE_CHALLENGE, MAC_MODE_TEMPKEY};
+use super::signature::check_sign_base_mode;
+use super::temp_key::{aead_key_given, cipher_key_given, kdf_temp_key_size};
+use super::temp_key::{sign_digest_buffer, verify_digest_buffer};
+use super::ATCA_CHECK_MAC_OTHER_DATA_SIZE;
+use super::{
+    check_otp_range, convert_signature, private_key_from_der, private_key_from_pem,
+    signature_to_der,
+};
 use super::{
-    AeadAlgorithm, AeadParam, AtcaAesCcmCtx, AtcaDeviceType, AtcaIfaceCfg, AtcaIfaceCfgPtrWrapper,
-    AtcaIfaceType, AtcaSlot, AtcaSlotCapacity, AtcaStatus, AteccDeviceTrait, ChipOptions,
-    CipherAlgorithm, CipherOperation, CipherParam, EccKeyAttr, FeedbackMode, InfoCmdType, KeyType,
-    NonceTarget, OutputProtectionState, ReadKey, SignMode, SlotConfig, VerifyMode, WriteConfig,
+    AeadAlgorithm, AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaDeviceType, AtcaIfaceCfg,
+    AtcaIfaceCfgPtrWrapper, AtcaIfaceType, AtcaKitType, AtcaPowerPolicy, AtcaSha256Ctx, AtcaSlot,
+    AtcaSlotCapacity, AtcaStatus, AtcaUartParity, AteccDeviceTrait, ChipOptions, CipherAlgorithm,
+    CipherOperation, CipherParam, DigestBuffer, EccKeyAttr, EcdhOutput, FeedbackMode, GenDigZone,
+    InfoCmdType, IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget, KeyType,
+    KeyWrapAlgorithm, NonceTarget, OutputProtectionState, ReadKey, SignMode, SignatureFormat,
+    SlotConfig, SlotId, TempKeySource, TempKeyState, TlsFinishedLabel, VerifyMode, WriteConfig,
 };
 use super::{
-    ATCA_AES_DATA_SIZE, ATCA_AES_GCM_IV_STD_LENGTH, ATCA_AES_KEY_SIZE,
-    ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY, ATCA_ATECC_PRIV_KEY_SIZE,
-    ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ATECC_TEMPKEY_KEYID,
-    ATCA_ATSHA_CONFIG_BUFFER_SIZE, ATCA_BLOCK_SIZE, ATCA_KEY_SIZE, ATCA_LOCK_ZONE_CONFIG,
-    ATCA_LOCK_ZONE_DATA, ATCA_NONCE_NUMIN_SIZE, ATCA_NONCE_SIZE, ATCA_RANDOM_BUFFER_SIZE,
-    ATCA_SERIAL_NUM_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE, ATCA_ZONE_CONFIG,
-    ATCA_ZONE_DATA,
+    ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_ATECC_CONFIG_BUFFER_SIZE,
+    ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY, ATCA_ATECC_PRIV_KEY_SIZE, ATCA_ATECC_PUB_KEY_SIZE,
+    ATCA_ATECC_SLOTS_COUNT, ATCA_ATECC_TEMPKEY_KEYID, ATCA_ATSHA_CONFIG_BUFFER_SIZE,
+    ATCA_BLOCK_SIZE, ATCA_ECDH_PMS_SIZE, ATCA_KEY_SIZE, ATCA_LOCK_ZONE_CONFIG, ATCA_LOCK_ZONE_DATA,
+    ATCA_NONCE_NUMIN_SIZE, ATCA_NONCE_SIZE, ATCA_RANDOM_BUFFER_SIZE, ATCA_SERIAL_NUM_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE, ATCA_SWI_WAKE_DELAY, ATCA_WORD_SIZE,
+    ATCA_ZONE_CONFIG, ATCA_ZONE_DATA, ATCA_ZONE_OTP,
 };
 
-mod aes_ccm;
+/// HMAC result is returned only, not kept in TempKey or the message digest buffer
+const SHA_MODE_TARGET_OUT_ONLY: u8 = 0xC0;
+
 mod aes_cipher;
 mod aes_gcm;
 mod c2rust;
+mod digest_buffer;
+mod discovery;
+mod ecdh;
+mod host_nonce;
+mod hotplug;
+#[cfg(feature = "i2c-rust")]
+mod i2c_rust;
+mod io_protection;
+mod kdf;
 mod rust2c;
+mod sha206a;
+mod temp_key;
+mod trace;
+mod transport;
+mod watchdog;
+
+use trace::Command;
+
+pub use discovery::discover;
+pub use transport::Transport;
+
+/// Identity of the interface a chip is attached to: its type and its instance
+/// (bus and address, serial port, kit index and chip identity)
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) struct AtcaIfaceId {
+    iface_type: AtcaIfaceType,
+    instance: (i32, u32),
+}
 
-struct AteccResourceManager {
-    ref_counter: u8,
+impl Default for AtcaIfaceId {
+    fn default() -> AtcaIfaceId {
+        AtcaIfaceId {
+            iface_type: AtcaIfaceType::AtcaUnknownIface,
+            instance: (0, 0),
+        }
+    }
+}
+
+impl std::fmt::Debug for AtcaIfaceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{:?}", self.iface_type, self.instance)
+    }
+}
+
+impl From<&AtcaIfaceCfg> for AtcaIfaceId {
+    fn from(iface_cfg: &AtcaIfaceCfg) -> AtcaIfaceId {
+        // A chip on an I2C bus is the same one whichever HAL reaches it
+        let iface_type = match iface_cfg.iface_type {
+            AtcaIfaceType::AtcaI2cRustIface => AtcaIfaceType::AtcaI2cIface,
+            iface_type => iface_type,
+        };
+        let instance = match (iface_type, iface_cfg.iface) {
+            (AtcaIfaceType::AtcaI2cIface, Some(iface)) => {
+                let atcai2c = unsafe { iface.atcai2c };
+                (atcai2c.bus as i32, atcai2c.slave_address as u32)
+            }
+            (AtcaIfaceType::AtcaSwiIface, Some(iface)) => (unsafe { iface.atcaswi }.bus as i32, 0),
+            (AtcaIfaceType::AtcaUartIface, Some(iface)) => (unsafe { iface.atcauart }.port, 0),
+            (AtcaIfaceType::AtcaHidIface, Some(iface)) => {
+                let atcahid = unsafe { iface.atcahid };
+                (atcahid.idx, atcahid.dev_identity as u32)
+            }
+            _ => (0, 0),
+        };
+        AtcaIfaceId {
+            iface_type,
+            instance,
+        }
+    }
 }
 
-lazy_static! {
-    static ref ATECC_RESOURCE_MANAGER: Mutex<AteccResourceManager> =
-        Mutex::new(AteccResourceManager { ref_counter: 0 });
+/// Keeps track of the interfaces with a chip in use, a chip can be used
+/// by a single AteccDevice instance at a time
+struct AteccResourceManager {
+    devices: Vec<AtcaIfaceId>,
 }
 
+static ATECC_RESOURCE_MANAGER: Mutex<AteccResourceManager> = Mutex::new(AteccResourceManager {
+    devices: Vec::new(),
+});
+/// The atcab_* API of CryptoAuthLib works on the device selected by the global _gDevice.
+/// The CryptoAuthLib bundled with cryptoauthlib-sys (3.1.0) has no per-device calib_* API,
+/// so each device keeps its own handle, put into _gDevice under this lock,
+/// and calls for all the devices are serialized. The lock is held only for the duration
+/// of a single atcab_* call and handed out in FIFO order, so a long stream of commands
+/// from one thread does not hold off quick requests from the others
+static ATCAB_API_MUTEX: FairMutex<()> = FairMutex::new(());
+
 impl AteccResourceManager {
-    // Aquire an acceptance to create an ATECC instance
-    fn acquire(&mut self) -> bool {
-        if self.ref_counter == 0 {
-            self.ref_counter = 1;
-            true
-        } else {
+    // Aquire an acceptance to create an ATECC instance for the chip on the interface
+    fn acquire(&mut self, iface_id: AtcaIfaceId) -> bool {
+        if self.devices.contains(&iface_id) {
             false
+        } else {
+            self.devices.push(iface_id);
+            true
         }
     }
 
-    // Release a reservation of an ATECC instance
-    fn release(&mut self) -> bool {
-        if self.ref_counter == 1 {
-            self.ref_counter = 0;
-            true
-        } else {
-            false
+    // Release a reservation of an ATECC instance for the chip on the interface
+    fn release(&mut self, iface_id: AtcaIfaceId) -> bool {
+        match self.devices.iter().position(|id| *id == iface_id) {
+            Some(idx) => {
+                self.devices.remove(idx);
+                true
+            }
+            None => false,
         }
     }
 }
 
+/// A lock of the atcab_* API, selecting the device the calls made under it are sent to
+#[derive(Debug)]
+struct AtcabApiMutex {
+    /// CryptoAuthLib device handle, created by atcab_init()
+    device: AtomicPtr<cryptoauthlib_sys::atca_device>,
+    /// Power mode the chip is put into when a command releases the lock
+    power_policy: AtcaPowerPolicy,
+}
+
+unsafe impl Send for AtcabApiMutex {}
+unsafe impl Sync for AtcabApiMutex {}
+
+impl AtcabApiMutex {
+    /// Locks the API for a single call. CryptoAuthLib puts the chip into the idle mode
+    /// after each command, with AtcaPowerPolicy::Sleep it is then put to sleep
+    /// as the lock is released. With std, the wait is limited as set on the calling thread
+    /// (the timeout and the cancellation token of the AteccHandle running the command),
+    /// the lock is not taken then and AtcaTimeout or AtcaCancelled is returned
+    fn lock(&self) -> Result<AtcabApiGuard, AtcaStatus> {
+        #[cfg(feature = "std")]
+        let guard = ATCAB_API_MUTEX.lock_bounded(&wait_limit())?;
+        #[cfg(not(feature = "std"))]
+        let guard = ATCAB_API_MUTEX.lock();
+        let guard = guard.map_err(|_| AtcaStatus::AtcaFuncFail)?;
+        Ok(self.select(guard, self.power_policy == AtcaPowerPolicy::Sleep))
+    }
+
+    /// Locks the API for a call that sets the power mode itself or releases the device.
+    /// The wait is not limited, such a call is not given up
+    fn lock_power_mode(&self) -> Result<AtcabApiGuard, AtcaStatus> {
+        let guard = ATCAB_API_MUTEX
+            .lock()
+            .map_err(|_| AtcaStatus::AtcaFuncFail)?;
+        Ok(self.select(guard, false))
+    }
+
+    /// Selects the device of this instance for the calls made under the lock
+    fn select(&self, guard: FairMutexGuard<'static, ()>, sleep: bool) -> AtcabApiGuard {
+        unsafe { cryptoauthlib_sys::_gDevice = self.device.load(Ordering::SeqCst) };
+        AtcabApiGuard {
+            _guard: guard,
+            sleep,
+        }
+    }
+}
+
+/// A lock of the atcab_* API, taken by AtcabApiMutex::lock()
+struct AtcabApiGuard {
+    _guard: FairMutexGuard<'static, ()>,
+    sleep: bool,
+}
+
+impl Drop for AtcabApiGuard {
+    /// Puts the chip to sleep, if the power policy says so, before the lock is released
+    fn drop(&mut self) {
+        if self.sleep {
+            unsafe { cryptoauthlib_sys::atcab_sleep() };
+        }
+    }
+}
+
+/// Configuration of the chip that does not change once the configuration zone is locked
+#[derive(Debug)]
+struct ChipConfig {
+    serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
+    chip_options: ChipOptions,
+    slots: Vec<AtcaSlot>,
+}
+
 /// An ATECC cryptochip context holder.
 #[derive(Debug)]
 pub struct AteccDevice {
     /// Interface configuration to be stored on a heap to avoid side effects of
     /// Rust and C interoperability
     iface_cfg_ptr: AtcaIfaceCfgPtrWrapper,
+    /// Interface the chip is attached to
+    iface_id: AtcaIfaceId,
     /// A mutex to ensure a mutual access from different threads to an ATECC instance
-    api_mutex: Mutex<()>,
-    serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
-    config_zone_locked: bool,
-    data_zone_locked: bool,
-    chip_options: ChipOptions,
-    access_keys: Mutex<RefCell<HashMap<u8, [u8; ATCA_KEY_SIZE]>>>,
-    slots: Vec<AtcaSlot>,
+    /// (and to CryptoAuthLib, shared by all instances)
+    api_mutex: AtcabApiMutex,
+    /// Device type known to CryptoAuthLib, read at initialization and by refresh()
+    device_type: AtomicU32,
+    /// Lock state of the zones, read at initialization and by refresh_lock_state()
+    config_zone_locked: AtomicBool,
+    data_zone_locked: AtomicBool,
+    /// Serial number, options and slot configuration, read at initialization
+    /// or, with lazy initialization, on the first use
+    chip_config: OnceCell<ChipConfig>,
+    access_keys: Mutex<RefCell<BTreeMap<u8, Zeroizing<[u8; ATCA_KEY_SIZE]>>>>,
+    /// Set by release(), or as long as there is nothing to release
+    released: AtomicBool,
+    /// Number of the last commands that have all failed to reach the chip
+    comm_failures: AtomicUsize,
+    /// What the commands executed so far have done with TempKey
+    temp_key: Mutex<TempKeyState>,
+    /// Host nonce supplied by set_host_nonce() for the next encrypted read or write
+    host_nonce: Mutex<Option<[u8; ATCA_NONCE_NUMIN_SIZE]>>,
 }
 
 impl Default for AteccDevice {
@@ -84,13 +267,29 @@ impl Default for AteccDevice {
             iface_cfg_ptr: AtcaIfaceCfgPtrWrapper {
                 ptr: std::ptr::null_mut(),
             },
-            api_mutex: Mutex::new(()),
-            serial_number: [0; ATCA_SERIAL_NUM_SIZE],
-            config_zone_locked: false,
-            data_zone_locked: false,
-            chip_options: Default::default(),
-            access_keys: Mutex::new(RefCell::new(HashMap::new())),
-            slots: Vec::new(),
+            iface_id: AtcaIfaceId::default(),
+            api_mutex: AtcabApiMutex {
+                device: AtomicPtr::new(ptr::null_mut()),
+                power_policy: AtcaPowerPolicy::Idle,
+            },
+            device_type: AtomicU32::new(cryptoauthlib_sys::ATCADeviceType_ATCA_DEV_UNKNOWN),
+            config_zone_locked: AtomicBool::new(false),
+            data_zone_locked: AtomicBool::new(false),
+            chip_config: OnceCell::new(),
+            access_keys: Mutex::new(RefCell::new(BTreeMap::new())),
+            released: AtomicBool::new(true),
+            comm_failures: AtomicUsize::new(0),
+            temp_key: Mutex::new(TempKeyState::default()),
+            host_nonce: Mutex::new(None),
+        }
+    }
+}
+
+impl Drop for AteccDevice {
+    /// Releases the device, unless release() has already been called
+    fn drop(&mut self) {
+        if !self.released.load(Ordering::SeqCst) {
+            self.release();
         }
     }
 }
@@ -99,15 +298,61 @@ impl AteccDeviceTrait for AteccDevice {
     /// Request ATECC to generate a vector of random bytes
     /// Trait implementation
     fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
-        self.random(rand_out)
+        let result = self.traced(Command::Random, None, || self.random(rand_out));
+        self.temp_key_lost(result, "random")
     } // AteccDevice::random()
 
+    /// Request ATECC to generate random bytes into a buffer of the caller
+    /// Trait implementation
+    fn random_into(&self, rand_out: &mut [u8; ATCA_RANDOM_BUFFER_SIZE]) -> AtcaStatus {
+        let result = self.traced(Command::RandomInto, None, || self.random_into(rand_out));
+        self.temp_key_lost(result, "random_into")
+    } // AteccDevice::random_into()
+
     /// Request ATECC to compute a message hash (SHA256)
     /// Trait implementation
     fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
-        self.sha(message, digest)
+        let result = self.traced(Command::Sha, None, || self.sha(message, digest));
+        self.temp_key_lost_before_608(result, "sha")
     } // AteccDevice::sha()
 
+    /// Request ATECC to compute a message hash (SHA256) into a buffer of the caller
+    /// Trait implementation
+    fn sha_into(&self, message: &[u8], digest: &mut [u8; ATCA_SHA2_256_DIGEST_SIZE]) -> AtcaStatus {
+        let result = self.traced(Command::ShaInto, None, || self.sha_into(message, digest));
+        self.temp_key_lost_before_608(result, "sha_into")
+    } // AteccDevice::sha_into()
+
+    /// Starts a SHA256 digest calculated by ATECC in several steps
+    /// Trait implementation
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        let result = self.traced(Command::ShaStart, None, || self.sha_start());
+        self.temp_key_lost_before_608(result, "sha_start")
+    } // AteccDevice::sha_start()
+
+    /// Feeds the next part of a message into a SHA256 digest calculation
+    /// Trait implementation
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        let result = self.traced(Command::ShaUpdate, None, || self.sha_update(ctx, data));
+        self.temp_key_lost_before_608(result, "sha_update")
+    } // AteccDevice::sha_update()
+
+    /// Completes a SHA256 digest calculation and returns the digest
+    /// Trait implementation
+    fn sha_finish(&self, ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::ShaFinish, None, || self.sha_finish(ctx));
+        self.temp_key_lost_before_608(result, "sha_finish")
+    } // AteccDevice::sha_finish()
+
+    /// Request ATECC to compute HMAC-SHA256 of a message with the key stored in a slot
+    /// Trait implementation
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::ShaHmac, Some(slot_id.into()), || {
+            self.sha_hmac(message, slot_id)
+        });
+        self.temp_key_lost_before_608(result, "sha_hmac")
+    } // AteccDevice::sha_hmac()
+
     /// Execute a Nonce command in pass-through mode to load one of the
     /// device's internal buffers with a fixed value.
     /// For the ATECC608A, available targets are TempKey (32 or 64 bytes), Message
@@ -115,48 +360,216 @@ impl AteccDeviceTrait for AteccDevice {
     /// all other devices, only TempKey (32 bytes) is available.
     /// Trait implementation
     fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
-        self.nonce(target, data)
+        let result = self.traced(Command::Nonce, None, || self.nonce(target, data));
+        match target {
+            NonceTarget::TempKey => self.temp_key_loaded(result, TempKeySource::Nonce, data.len()),
+            _ => result,
+        }
     } // AteccDevice::nonce()
 
     /// Execute a Nonce command to generate a random nonce combining a host
     /// nonce and a device random number.
     /// Trait implementation
     fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
-        self.nonce_rand(host_nonce, rand_out)
+        let result = self.traced(Command::NonceRand, None, || {
+            self.nonce_rand(host_nonce, rand_out)
+        });
+        self.temp_key_loaded(result, TempKeySource::RandomNonce, ATCA_NONCE_SIZE)
     } // AteccDevice::nonce_rand()
 
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    /// Trait implementation
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        let result = self.traced(Command::GenDig, Some(slot_id.into()), || {
+            self.gen_dig(zone, slot_id, other_data)
+        });
+        self.temp_key_loaded(result, TempKeySource::GenDig, ATCA_KEY_SIZE)
+    } // AteccDevice::gen_dig()
+
+    /// What the host knows about TempKey, tracked from the commands executed so far
+    /// Trait implementation
+    fn tempkey_state(&self) -> TempKeyState {
+        self.tempkey_state()
+    } // AteccDevice::tempkey_state()
+
+    /// Execute a MAC command over the key stored in a slot and a challenge or TempKey
+    /// Trait implementation
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::Mac, Some(slot_id.into()), || {
+            self.mac(slot_id, challenge)
+        })
+    } // AteccDevice::mac()
+
+    /// Execute a CheckMac command to verify a MAC calculated by the host
+    /// Trait implementation
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.traced(Command::CheckMac, Some(slot_id.into()), || {
+            self.check_mac(slot_id, challenge, response, other_data)
+        })
+    } // AteccDevice::check_mac()
+
+    /// Request ATECC to read the value of one of the two monotonic counters
+    /// Trait implementation
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.traced(Command::CounterRead, None, || self.counter_read(counter_id))
+    } // AteccDevice::counter_read()
+
+    /// Request ATECC to increment one of the two monotonic counters
+    /// Trait implementation
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.traced(Command::CounterIncrement, None, || {
+            self.counter_increment(counter_id)
+        })
+    } // AteccDevice::counter_increment()
+
     /// Request ATECC to generate a cryptographic key
     /// Trait implementation
     fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
-        self.gen_key(key_type, slot_id)
+        let result = self.traced(Command::GenKey, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.gen_key(key_type, slot_id))
+        });
+        match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => {
+                self.temp_key_loaded(result, TempKeySource::GenKey, ATCA_KEY_SIZE)
+            }
+            _ => result,
+        }
     } // AteccDevice::gen_key()
 
     /// Request ATECC to import a cryptographic key
     /// Trait implementation
     fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
-        self.import_key(key_type, key_data, slot_id)
+        let result = self.traced(Command::ImportKey, Some(slot_id.into()), || {
+            self.import_key(key_type, key_data, slot_id)
+        });
+        match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => {
+                self.temp_key_loaded(result, TempKeySource::Nonce, ATCA_NONCE_SIZE)
+            }
+            _ => result,
+        }
     } // AteccDevice::import_key()
 
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1)
+    /// Trait implementation
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.traced(Command::ImportPrivateKeyDer, Some(slot_id.into()), || {
+            self.import_private_key_der(key_data, slot_id)
+        })
+    } // AteccDevice::import_private_key_der()
+
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    /// Trait implementation
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus {
+        self.traced(Command::ImportPrivateKeyPem, Some(slot_id.into()), || {
+            self.import_private_key_pem(key_data, slot_id)
+        })
+    } // AteccDevice::import_private_key_pem()
+
     /// Request ATECC to export a cryptographic key
     /// Trait implementation
     fn export_key(&self, key_type: KeyType, key_data: &mut Vec<u8>, slot_id: u8) -> AtcaStatus {
-        self.export_key(key_type, key_data, slot_id)
+        self.traced(Command::ExportKey, Some(slot_id.into()), || {
+            self.export_key(key_type, key_data, slot_id)
+        })
     } // AteccDevice::export_key()
 
+    /// Request ATECC to write data to a slot, starting at the given byte offset
+    /// Trait implementation
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.traced(Command::WriteSlotData, Some(slot_id.into()), || {
+            self.write_slot_data(slot_id, offset, data)
+        })
+    } // AteccDevice::write_slot_data()
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset
+    /// Trait implementation
+    fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::ReadSlotData, Some(slot_id.into()), || {
+            self.read_slot_data(slot_id, offset, len)
+        })
+    } // AteccDevice::read_slot_data()
+
+    /// Request ATECC to write the OTP zone, starting at the given byte offset
+    /// Trait implementation
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.traced(Command::WriteOtpZone, None, || {
+            self.write_otp_zone(offset, data)
+        })
+    } // AteccDevice::write_otp_zone()
+
+    /// Request ATECC to read the OTP zone, starting at the given byte offset
+    /// Trait implementation
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::ReadOtpZone, None, || {
+            self.read_otp_zone(offset, len)
+        })
+    } // AteccDevice::read_otp_zone()
+
     /// Depending on the socket configuration, this function calculates
     /// public key based on an existing private key in the socket
     /// or exports the public key directly
     /// Trait implementation
     fn get_public_key(&self, slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus {
-        self.get_public_key(slot_id, public_key)
+        self.traced(Command::GetPublicKey, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.get_public_key(slot_id, public_key))
+        })
     } // AteccDevice::get_public_key()
 
     /// Request ATECC to generate an ECDSA signature
     /// Trait implementation
     fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
-        self.sign_hash(mode, slot_id, signature)
+        let result = self.traced(Command::SignHash, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.sign_hash(mode.clone(), slot_id, signature))
+        });
+        self.temp_key_lost_to_digest(result, sign_digest_buffer(&mode), "sign_hash")
     } // AteccDevice::sign_hash()
 
+    /// Request ATECC to sign a SHA256 digest into a buffer of the caller
+    /// Trait implementation
+    fn sign_hash_into(
+        &self,
+        slot_id: u8,
+        hash: &[u8; ATCA_SHA2_256_DIGEST_SIZE],
+        signature: &mut [u8; ATCA_SIG_SIZE],
+    ) -> AtcaStatus {
+        let result = self.traced(Command::SignHashInto, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.sign_hash_into(slot_id, hash, signature))
+        });
+        self.temp_key_lost_before_608(result, "sign_hash_into")
+    } // AteccDevice::sign_hash_into()
+
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot
+    /// Trait implementation
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::SignMessage, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.sign_message(slot_id, message))
+        });
+        self.temp_key_lost_before_608(result, "sign_message")
+    } // AteccDevice::sign_message()
+
+    /// Request ATECC to execute a Sign command in the given mode
+    /// Trait implementation
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::SignBase, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.sign_base(mode, slot_id))
+        });
+        self.temp_key_lost_before_608(result, "sign_base")
+    } // AteccDevice::sign_base()
+
     /// Request ATECC to verify ECDSA signature
     /// Trait implementation
     fn verify_hash(
@@ -165,9 +578,132 @@ impl AteccDeviceTrait for AteccDevice {
         hash: &[u8],
         signature: &[u8],
     ) -> Result<bool, AtcaStatus> {
-        self.verify_hash(mode, hash, signature)
+        let result = self.traced(Command::VerifyHash, None, || {
+            self.watchdog_guarded(|| self.verify_hash(mode.clone(), hash, signature))
+        });
+        self.temp_key_lost_to_digest(result, verify_digest_buffer(&mode), "verify_hash")
     } // AteccDevice::verify_hash()
 
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    /// Trait implementation
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::SignHashFormatted, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.sign_hash_formatted(mode.clone(), slot_id, format))
+        });
+        self.temp_key_lost_to_digest(result, sign_digest_buffer(&mode), "sign_hash_formatted")
+    } // AteccDevice::sign_hash_formatted()
+
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    /// Trait implementation
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        let result = self.traced(Command::VerifyHashFormatted, None, || {
+            self.watchdog_guarded(|| {
+                self.verify_hash_formatted(mode.clone(), hash, signature, format)
+            })
+        });
+        self.temp_key_lost_to_digest(result, verify_digest_buffer(&mode), "verify_hash_formatted")
+    } // AteccDevice::verify_hash_formatted()
+
+    /// Request ATECC to perform ECDH key agreement, honoring the output protection settings
+    /// Trait implementation
+    fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        let result = self.traced(Command::Ecdh, Some(slot_id.into()), || {
+            self.watchdog_guarded(|| self.ecdh(slot_id, public_key, session))
+        });
+        match result {
+            Ok(EcdhOutput::TempKey) => {
+                self.temp_key_loaded(result, TempKeySource::Ecdh, ATCA_KEY_SIZE)
+            }
+            _ => result,
+        }
+    } // AteccDevice::ecdh()
+
+    /// Request ATECC608x to derive a key with the KDF command
+    /// Trait implementation
+    fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::Kdf, None, || {
+            self.kdf(algorithm, source, target, message)
+        });
+        match kdf_temp_key_size(algorithm, target) {
+            Some(size) => self.temp_key_loaded(result, TempKeySource::Kdf, size),
+            None => result,
+        }
+    } // AteccDevice::kdf()
+
+    /// Request ATECC608x to open an IO protection session with the given IO protection key
+    /// Trait implementation
+    fn open_io_protection_session(&self, io_key: &[u8]) -> Result<IoProtectionSession, AtcaStatus> {
+        self.traced(Command::OpenIoProtectionSession, None, || {
+            self.open_io_protection_session(io_key)
+        })
+    } // AteccDevice::open_io_protection_session()
+
+    /// Request ATECC to perform ECDH key agreement with the premaster secret
+    /// transmitted encrypted with the IO protection key
+    /// Trait implementation
+    fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::EcdhIoProtected, Some(slot_id.into()), || {
+            self.ecdh_io_protected(session, slot_id, public_key)
+        })
+    } // AteccDevice::ecdh_io_protected()
+
+    /// Request ATECC to verify ECDSA signature, with the result
+    /// authenticated by a MAC calculated with the IO protection key
+    /// Trait implementation
+    fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.traced(Command::VerifyHashIoProtected, None, || {
+            self.verify_hash_io_protected(session, mode, hash, signature)
+        })
+    } // AteccDevice::verify_hash_io_protected()
+
+    /// Request ATECC to import an AES key to a slot protected by the IO protection key
+    /// Trait implementation
+    fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus {
+        self.traced(
+            Command::ImportAesKeyIoProtected,
+            Some(slot_id.into()),
+            || self.import_aes_key_io_protected(session, key, slot_id),
+        )
+    } // AteccDevice::import_aes_key_io_protected()
+
     /// Data encryption function in AES unauthenticated cipher alhorithms modes
     /// Trait implementation
     fn cipher_encrypt(
@@ -176,7 +712,11 @@ impl AteccDeviceTrait for AteccDevice {
         slot_id: u8,
         data: &mut Vec<u8>,
     ) -> AtcaStatus {
-        self.cipher_encrypt(algorithm, slot_id, data)
+        let key_given = cipher_key_given(&algorithm);
+        let result = self.traced(Command::CipherEncrypt, Some(slot_id.into()), || {
+            self.cipher_encrypt(algorithm, slot_id, data)
+        });
+        self.temp_key_loaded_by_aes(result, slot_id, key_given)
     } // AteccDevice::cipher_encrypt()
 
     /// Data decryption function in AES unauthenticated cipher alhorithms modes
@@ -187,7 +727,11 @@ impl AteccDeviceTrait for AteccDevice {
         slot_id: u8,
         data: &mut Vec<u8>,
     ) -> AtcaStatus {
-        self.cipher_decrypt(algorithm, slot_id, data)
+        let key_given = cipher_key_given(&algorithm);
+        let result = self.traced(Command::CipherDecrypt, Some(slot_id.into()), || {
+            self.cipher_decrypt(algorithm, slot_id, data)
+        });
+        self.temp_key_loaded_by_aes(result, slot_id, key_given)
     } // AteccDevice::cipher_decrypt()
 
     /// Data encryption function in AES AEAD (authenticated encryption with associated data) modes
@@ -198,7 +742,11 @@ impl AteccDeviceTrait for AteccDevice {
         slot_id: u8,
         data: &mut Vec<u8>,
     ) -> Result<Vec<u8>, AtcaStatus> {
-        self.aead_encrypt(algorithm, slot_id, data)
+        let key_given = aead_key_given(&algorithm);
+        let result = self.traced(Command::AeadEncrypt, Some(slot_id.into()), || {
+            self.aead_encrypt(algorithm, slot_id, data)
+        });
+        self.temp_key_loaded_by_aes(result, slot_id, key_given)
     } // AteccDevice::aead_encrypt()
 
     /// Data decryption function in AES AEAD (authenticated encryption with associated data) modes
@@ -209,9 +757,234 @@ impl AteccDeviceTrait for AteccDevice {
         slot_id: u8,
         data: &mut Vec<u8>,
     ) -> Result<bool, AtcaStatus> {
-        self.aead_decrypt(algorithm, slot_id, data)
+        let key_given = aead_key_given(&algorithm);
+        let result = self.traced(Command::AeadDecrypt, Some(slot_id.into()), || {
+            self.aead_decrypt(algorithm, slot_id, data)
+        });
+        self.temp_key_loaded_by_aes(result, slot_id, key_given)
     } // AteccDevice::aead_decrypt()
 
+    /// Starts a streaming AES GCM encryption or decryption
+    /// Trait implementation
+    fn gcm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        let result = self.traced(Command::GcmInit, Some(slot_id.into()), || {
+            host_modes::gcm_init(self, slot_id, nonce, key)
+        });
+        self.temp_key_loaded_by_aes(result, slot_id, key.is_some())
+    } // AteccDevice::gcm_init()
+
+    /// Feeds the next chunk of additional authenticated data into a streaming AES GCM operation
+    /// Trait implementation
+    fn gcm_update_aad(&self, ctx: AtcaAesGcmCtx, data: &[u8]) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.traced(Command::GcmUpdateAad, None, || {
+            host_modes::gcm_update_aad(self, ctx, data)
+        })
+    } // AteccDevice::gcm_update_aad()
+
+    /// Encrypts (in place) the next chunk of a streaming AES GCM encryption
+    /// Trait implementation
+    fn gcm_encrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.traced(Command::GcmEncryptUpdate, None, || {
+            host_modes::gcm_encrypt_update(self, ctx, data)
+        })
+    } // AteccDevice::gcm_encrypt_update()
+
+    /// Decrypts (in place) the next chunk of a streaming AES GCM decryption
+    /// Trait implementation
+    fn gcm_decrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.traced(Command::GcmDecryptUpdate, None, || {
+            host_modes::gcm_decrypt_update(self, ctx, data)
+        })
+    } // AteccDevice::gcm_decrypt_update()
+
+    /// Completes a streaming AES GCM encryption and returns the tag
+    /// Trait implementation
+    fn gcm_encrypt_finish(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::GcmEncryptFinish, None, || {
+            host_modes::gcm_encrypt_finish(self, ctx, tag_length)
+        })
+    } // AteccDevice::gcm_encrypt_finish()
+
+    /// Completes a streaming AES GCM decryption and verifies the tag
+    /// Trait implementation
+    fn gcm_decrypt_finish(&self, ctx: AtcaAesGcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        self.traced(Command::GcmDecryptFinish, None, || {
+            host_modes::gcm_decrypt_finish(self, ctx, tag)
+        })
+    } // AteccDevice::gcm_decrypt_finish()
+
+    /// Starts a streaming AES CCM encryption or decryption
+    /// Trait implementation
+    fn ccm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        let result = self.traced(Command::CcmInit, Some(slot_id.into()), || {
+            host_modes::ccm_init(
+                self,
+                slot_id,
+                nonce,
+                key,
+                aad_length,
+                text_length,
+                tag_length,
+            )
+        });
+        self.temp_key_loaded_by_aes(result, slot_id, key.is_some())
+    } // AteccDevice::ccm_init()
+
+    /// Feeds the next chunk of additional authenticated data into a streaming AES CCM operation
+    /// Trait implementation
+    fn ccm_update_aad(&self, ctx: AtcaAesCcmCtx, data: &[u8]) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.traced(Command::CcmUpdateAad, None, || {
+            host_modes::ccm_update_aad(self, ctx, data)
+        })
+    } // AteccDevice::ccm_update_aad()
+
+    /// Encrypts (in place) the next chunk of a streaming AES CCM encryption
+    /// Trait implementation
+    fn ccm_encrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.traced(Command::CcmEncryptUpdate, None, || {
+            host_modes::ccm_encrypt_update(self, ctx, data)
+        })
+    } // AteccDevice::ccm_encrypt_update()
+
+    /// Decrypts (in place) the next chunk of a streaming AES CCM decryption
+    /// Trait implementation
+    fn ccm_decrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.traced(Command::CcmDecryptUpdate, None, || {
+            host_modes::ccm_decrypt_update(self, ctx, data)
+        })
+    } // AteccDevice::ccm_decrypt_update()
+
+    /// Completes a streaming AES CCM encryption and returns the tag
+    /// Trait implementation
+    fn ccm_encrypt_finish(&self, ctx: AtcaAesCcmCtx) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::CcmEncryptFinish, None, || {
+            host_modes::ccm_encrypt_finish(self, ctx)
+        })
+    } // AteccDevice::ccm_encrypt_finish()
+
+    /// Completes a streaming AES CCM decryption and verifies the tag
+    /// Trait implementation
+    fn ccm_decrypt_finish(&self, ctx: AtcaAesCcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        self.traced(Command::CcmDecryptFinish, None, || {
+            host_modes::ccm_decrypt_finish(self, ctx, tag)
+        })
+    } // AteccDevice::ccm_decrypt_finish()
+
+    /// Derives TLS 1.2 session keys
+    /// Trait implementation
+    fn tls_derive_keys(
+        &self,
+        slot_id: u8,
+        server_public_key: &[u8],
+        client_random: &[u8],
+        server_random: &[u8],
+        key_block_length: usize,
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::TlsDeriveKeys, Some(slot_id.into()), || {
+            host_modes::tls_derive_keys(
+                self,
+                slot_id,
+                server_public_key,
+                client_random,
+                server_random,
+                key_block_length,
+                session,
+            )
+        });
+        self.temp_key_loaded(result, TempKeySource::Kdf, ATCA_KEY_SIZE)
+    } // AteccDevice::tls_derive_keys()
+
+    /// Calculates verify_data of a TLS 1.2 Finished message
+    /// Trait implementation
+    fn tls_verify_data(
+        &self,
+        label: TlsFinishedLabel,
+        handshake_hash: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::TlsVerifyData, None, || {
+            host_modes::tls_verify_data(self, label, handshake_hash, session)
+        })
+    } // AteccDevice::tls_verify_data()
+
+    /// Encrypts data for the owner of the given public key
+    /// Trait implementation
+    fn ecies_encrypt(&self, public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::EciesEncrypt, None, || {
+            host_modes::ecies_encrypt(self, public_key, plaintext)
+        });
+        self.temp_key_loaded(result, TempKeySource::Kdf, ATCA_KEY_SIZE)
+    } // AteccDevice::ecies_encrypt()
+
+    /// Decrypts data produced by ecies_encrypt() with the private key stored in the given slot
+    /// Trait implementation
+    fn ecies_decrypt(&self, slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.traced(Command::EciesDecrypt, Some(slot_id.into()), || {
+            host_modes::ecies_decrypt(self, slot_id, data)
+        });
+        self.temp_key_loaded(result, TempKeySource::Kdf, ATCA_KEY_SIZE)
+    } // AteccDevice::ecies_decrypt()
+
+    /// Wrap a key with the AES key stored in a slot (RFC 3394 / RFC 5649)
+    /// Trait implementation
+    fn wrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::WrapKey, Some(slot_id.into()), || {
+            host_modes::wrap_key(self, algorithm, slot_id, key)
+        })
+    } // AteccDevice::wrap_key()
+
+    /// Unwrap a key with the AES key stored in a slot (RFC 3394 / RFC 5649)
+    /// Trait implementation
+    fn unwrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.traced(Command::UnwrapKey, Some(slot_id.into()), || {
+            host_modes::unwrap_key(self, algorithm, slot_id, wrapped_key)
+        })
+    } // AteccDevice::unwrap_key()
+
     /// Request ATECC to return own device type
     /// Trait implementation
     fn get_device_type(&self) -> AtcaDeviceType {
@@ -222,14 +995,14 @@ impl AteccDeviceTrait for AteccDevice {
     /// If true, a chip can be used for cryptographic operations
     /// Trait implementation
     fn is_configuration_locked(&self) -> bool {
-        self.config_zone_locked
+        self.config_zone_locked.load(Ordering::SeqCst)
     } // AteccDevice::is_configuration_locked()
 
     /// Request ATECC to check if its Data Zone is locked.
     /// If true, a chip can be used for cryptographic operations
     /// Trait implementation
     fn is_data_zone_locked(&self) -> bool {
-        self.data_zone_locked
+        self.data_zone_locked.load(Ordering::SeqCst)
     } // AteccDevice::is_data_zone_locked()
 
     /// Returns a structure containing configuration data read from ATECC
@@ -239,10 +1012,40 @@ impl AteccDeviceTrait for AteccDevice {
         self.get_config(atca_slots)
     } // AteccDevice::get_config()
 
+    /// Request ATECC to read and return own configuration zone.
+    /// Note: this function returns raw data, function get_config(..) implements a more
+    /// structured return.
+    /// Trait implementation
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        self.traced(Command::ReadConfigZone, None, || {
+            self.read_config_zone(config_data)
+        })
+    } // AteccDevice::read_config_zone()
+
+    /// Request ATECC to write its whole configuration zone
+    /// Trait implementation
+    fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        self.traced(Command::WriteConfigZone, None, || {
+            self.write_config_zone(config_data)
+        })
+    } // AteccDevice::write_config_zone()
+
+    /// Request ATECC to lock its configuration zone
+    /// Trait implementation
+    fn lock_config_zone(&self) -> AtcaStatus {
+        self.traced(Command::LockConfigZone, None, || self.lock_config_zone())
+    } // AteccDevice::lock_config_zone()
+
+    /// Request ATECC to lock its data zone
+    /// Trait implementation
+    fn lock_data_zone(&self) -> AtcaStatus {
+        self.traced(Command::LockDataZone, None, || self.lock_data_zone())
+    } // AteccDevice::lock_data_zone()
+
     /// Command accesses some static or dynamic information from the ATECC chip
     /// Trait implementation
     fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
-        self.info_cmd(command)
+        self.traced(Command::InfoCmd, None, || self.info_cmd(command))
     } // AteccDevice::info_cmd()
 
     /// A function that adds an access key for securely reading or writing data
@@ -260,51 +1063,136 @@ impl AteccDeviceTrait for AteccDevice {
         self.flush_access_keys()
     } // AteccDevice::flush_access_keys()
 
+    /// Sets the host nonce of the Nonce command that precedes the next
+    /// encrypted read or write of a slot
+    /// Trait implementation
+    fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus {
+        self.set_host_nonce(num_in)
+    } // AteccDevice::set_host_nonce()
+
     /// Get serial number of the ATECC device
     /// Trait implementation
     fn get_serial_number(&self) -> [u8; ATCA_SERIAL_NUM_SIZE] {
-        self.serial_number
+        self.chip_config()
+            .map(|config| config.serial_number)
+            .unwrap_or_default()
     } // AteccDevice::get_serial_number()
 
     /// Checks if the chip supports AES encryption
     /// (only relevant for the ATECC608x chip)
     /// Trait implementation
     fn is_aes_enabled(&self) -> bool {
-        self.chip_options.aes_enabled
+        self.chip_options().unwrap_or_default().aes_enabled
     } // AteccDevice::is_aes_enabled()
 
     /// Checks if the chip supports AES for KDF operations
     /// (only relevant for the ATECC608x chip)
     /// Trait implementation
     fn is_kdf_aes_enabled(&self) -> bool {
-        self.chip_options.kdf_aes_enabled
+        self.chip_options().unwrap_or_default().kdf_aes_enabled
     } // AteccDevice::is_kdf_aes_enabled()
 
     /// Checks whether transmission between chip and host is to be encrypted
     /// (IO encryption is only possible for ATECC608x chip)
     /// Trait implementation
     fn is_io_protection_key_enabled(&self) -> bool {
-        self.chip_options.io_key_enabled
+        self.chip_options().unwrap_or_default().io_key_enabled
     } // AteccDevice::is_io_protection_key_enabled()
 
     ///
     /// (only relevant for the ATECC608x chip)
     /// Trait implementation
     fn get_ecdh_output_protection_state(&self) -> OutputProtectionState {
-        self.chip_options.ecdh_output_protection
+        self.chip_options()
+            .unwrap_or_default()
+            .ecdh_output_protection
     } // AteccDevice::get_ecdh_output_protection_state()
 
     ///
     /// (only relevant for the ATECC608x chip)
     /// Trait implementation
     fn get_kdf_output_protection_state(&self) -> OutputProtectionState {
-        self.chip_options.kdf_output_protection
+        self.chip_options()
+            .unwrap_or_default()
+            .kdf_output_protection
     } // AteccDevice::get_kdf_output_protection_state()
 
+    /// Perform an AES-128 encrypt operation with a key in the device
+    /// Trait implementation
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_encrypt_block(key_id, key_block, input)
+    }
+    /// Perform an AES-128 decrypt operation with a key in the device
+    /// Trait implementation
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.traced(Command::AesDecryptBlock, Some(key_id), || {
+            self.aes_decrypt_block(key_id, key_block, input)
+        })
+    }
+
+    /// Request ATECC608x to run the SelfTest command
+    /// Trait implementation
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        self.invalidate_temp_key("self_test");
+        self.traced(Command::SelfTest, None, || {
+            self.watchdog_guarded(|| self.self_test())
+        })
+    }
+    /// Wakes the chip up
+    /// Trait implementation
+    fn wake(&self) -> AtcaStatus {
+        self.traced(Command::Wake, None, || self.wake())
+    }
+    /// Puts the chip into the idle mode
+    /// Trait implementation
+    fn idle(&self) -> AtcaStatus {
+        self.traced(Command::Idle, None, || self.idle())
+    }
+    /// Puts the chip into the sleep mode
+    /// Trait implementation
+    fn sleep(&self) -> AtcaStatus {
+        let result = self.traced(Command::Sleep, None, || self.sleep());
+        self.temp_key_lost(result, "sleep")
+    }
+    /// Tells if the chip seems to be gone
+    /// Trait implementation
+    fn is_disconnected(&self) -> bool {
+        self.is_disconnected()
+    }
+    /// Re-initializes the communication with the chip
+    /// Trait implementation
+    fn reconnect(&self) -> AtcaStatus {
+        let result = self.traced(Command::Reconnect, None, || self.reconnect());
+        self.temp_key_lost(result, "reconnect")
+    }
+    /// Reads again the state cached at initialization
+    /// Trait implementation
+    fn refresh(&self) -> AtcaStatus {
+        self.traced(Command::Refresh, None, || self.refresh())
+    }
+    /// Reads again the lock state of the zones
+    /// Trait implementation
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        self.traced(Command::RefreshLockState, None, || {
+            self.refresh_lock_state()
+        })
+    }
+
     /// ATECC device instance destructor
     /// Trait implementation
     fn release(&self) -> AtcaStatus {
-        self.release()
+        self.invalidate_temp_key("release");
+        self.traced(Command::Release, None, || self.release())
     } // AteccDevice::release()
 
     //--------------------------------------------------
@@ -327,15 +1215,6 @@ impl AteccDeviceTrait for AteccDevice {
     ) -> AtcaStatus {
         self.read_zone(zone, slot, block, offset, data, len)
     } // AteccDevice::read_zone()
-    /// Request ATECC to read and return own configuration zone.
-    /// Note: this function returns raw data, function get_config(..) implements a more
-    /// structured return.
-    /// Trait implementation
-    #[cfg(test)]
-    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
-        self.read_config_zone(config_data)
-    } // AteccDevice::read_config_zone()
-
     /// Compare internal config zone contents vs. config_data.
     /// Diagnostic function.
     /// Trait implementation
@@ -351,9 +1230,66 @@ impl AteccDeviceTrait for AteccDevice {
     fn get_access_key(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
         self.get_access_key(slot_id, key)
     } // AteccDevice::get_access_key()
-    /// Perform an AES-128 encrypt operation with a key in the device
+    /// Initialize context for AES CTR operation with an existing IV
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.aes_ctr_init(slot_id, counter_size, iv)
+    }
+    /// Increments AES CTR counter value
     /// Trait implementation
     #[cfg(test)]
+    fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.aes_ctr_increment(ctx)
+    }
+    /// Initialize context for AES CBC operation.
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_cbc_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        self.aes_cbc_init(slot_id, iv)
+    }
+}
+
+/// Commands the host-side modes of operation are built on
+impl ModePrimitives for AteccDevice {
+    fn get_device_type(&self) -> AtcaDeviceType {
+        self.get_device_type()
+    }
+
+    fn check_that_configuration_is_not_locked(&self, both: bool) -> bool {
+        self.check_that_configuration_is_not_locked(both)
+    }
+
+    fn chip_options(&self) -> Result<ChipOptions, AtcaStatus> {
+        self.chip_options()
+    }
+
+    fn slot_config(&self, slot_id: u8) -> Result<SlotConfig, AtcaStatus> {
+        self.slot_config(slot_id)
+    }
+
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.random(rand_out)
+    }
+
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        self.nonce(target, data)
+    }
+
+    fn cipher_aes_common(
+        &self,
+        slot_id: u8,
+        data_size: usize,
+        key: Option<Vec<u8>>,
+    ) -> Result<u16, AtcaStatus> {
+        self.cipher_aes_common(slot_id, data_size, key)
+    }
+
     fn aes_encrypt_block(
         &self,
         key_id: u16,
@@ -362,9 +1298,7 @@ impl AteccDeviceTrait for AteccDevice {
     ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
         self.aes_encrypt_block(key_id, key_block, input)
     }
-    /// Perform an AES-128 decrypt operation with a key in the device
-    /// Trait implementation
-    #[cfg(test)]
+
     fn aes_decrypt_block(
         &self,
         key_id: u16,
@@ -373,9 +1307,7 @@ impl AteccDeviceTrait for AteccDevice {
     ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
         self.aes_decrypt_block(key_id, key_block, input)
     }
-    /// Initialize context for AES CTR operation with an existing IV
-    /// Trait implementation
-    #[cfg(test)]
+
     fn aes_ctr_init(
         &self,
         slot_id: u8,
@@ -384,48 +1316,175 @@ impl AteccDeviceTrait for AteccDevice {
     ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
         self.aes_ctr_init(slot_id, counter_size, iv)
     }
-    /// Increments AES CTR counter value
-    /// Trait implementation
-    #[cfg(test)]
+
     fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
         self.aes_ctr_increment(ctx)
     }
-    /// Initialize context for AES CBC operation.
-    /// Trait implementation
-    #[cfg(test)]
-    fn aes_cbc_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
-        self.aes_cbc_init(slot_id, iv)
+
+    fn aes_cbc_encrypt_block(
+        &self,
+        ctx: atca_aes_cbc_ctx_t,
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        self.aes_cbc_encrypt_block(ctx, plaintext, ciphertext)
+    }
+
+    fn aes_gcm_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_init(slot_id, iv)
+    }
+
+    fn aes_gcm_aad_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_aad_update(ctx, data)
+    }
+
+    fn aes_gcm_encrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        encrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_encrypt_update(ctx, data, encrypted)
+    }
+
+    fn aes_gcm_decrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        decrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_decrypt_update(ctx, data, decrypted)
+    }
+
+    fn aes_gcm_encrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.aes_gcm_encrypt_finish(ctx, tag_length)
+    }
+
+    fn aes_gcm_decrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.aes_gcm_decrypt_finish(ctx, tag)
+    }
+
+    fn ecdh_parameters_check(&self, slot_id: u8, public_key: &[u8]) -> Result<(), AtcaStatus> {
+        self.ecdh_parameters_check(slot_id, public_key)
+    }
+
+    fn ecdh_without_output(
+        &self,
+        mode: u8,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus> {
+        self.ecdh_without_output(mode, slot_id, public_key)
+    }
+
+    fn kdf_common(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+        io_key: Option<&[u8]>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.kdf_common(algorithm, source, target, message, io_key)
+    }
+
+    fn gen_temp_key(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let mut public_key: Vec<u8> = vec![0; ATCA_ATECC_PUB_KEY_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_genkey(ATCA_ATECC_TEMPKEY_KEYID, public_key.as_mut_ptr())
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(public_key),
+            _ => Err(result),
+        }
     }
 }
 
 /// Implementation of CryptoAuth Library API Rust wrapper calls
 impl AteccDevice {
+    /// ATECC device instance constructor for a chip attached to a bus
+    /// the given transport carries the commands over
+    pub fn with_transport(
+        r_iface_cfg: AtcaIfaceCfg,
+        transport: Box<dyn Transport>,
+    ) -> Result<AteccDevice, String> {
+        let r_iface_cfg = AtcaIfaceCfg {
+            iface_type: AtcaIfaceType::AtcaCustomIface,
+            iface: None,
+            ..r_iface_cfg
+        };
+        if !transport::register(transport) {
+            return Err(AtcaStatus::AtcaAllocFailure.to_string());
+        }
+        let result = AteccDevice::new(r_iface_cfg);
+        if result.is_err() {
+            transport::unregister();
+        }
+        result
+    } // AteccDevice::with_transport()
+
     /// ATECC device instance constructor
     pub fn new(r_iface_cfg: AtcaIfaceCfg) -> Result<AteccDevice, String> {
-        if !ATECC_RESOURCE_MANAGER.lock().unwrap().acquire() {
+        let iface_id = AtcaIfaceId::from(&r_iface_cfg);
+        if !ATECC_RESOURCE_MANAGER.lock().unwrap().acquire(iface_id) {
             return Err(AtcaStatus::AtcaAllocFailure.to_string());
         }
         let iface_cfg = Box::new(
             match cryptoauthlib_sys::ATCAIfaceCfg::try_from(r_iface_cfg) {
                 Ok(x) => x,
                 Err(()) => {
-                    ATECC_RESOURCE_MANAGER.lock().unwrap().release();
+                    ATECC_RESOURCE_MANAGER.lock().unwrap().release(iface_id);
                     return Err(AtcaStatus::AtcaBadParam.to_string());
                 }
             },
         );
         let mut atecc_device = AteccDevice::default();
+        atecc_device.iface_id = iface_id;
 
         let iface_cfg_raw_ptr: *mut cryptoauthlib_sys::ATCAIfaceCfg = Box::into_raw(iface_cfg);
         // From now on iface_cfg is consumed and iface_cfg_ptr must be stored to be released
         // when no longer needed.
 
+        // The Rust I2C HAL finds the settings of the device by its interface configuration
+        #[cfg(feature = "i2c-rust")]
+        if let (AtcaIfaceType::AtcaI2cRustIface, Some(iface)) =
+            (r_iface_cfg.iface_type, r_iface_cfg.iface)
+        {
+            let atcai2c = unsafe { iface.atcai2c };
+            i2c_rust::configure(
+                iface_cfg_raw_ptr,
+                atcai2c.bus,
+                atcai2c.slave_address,
+                r_iface_cfg.wake_delay,
+                r_iface_cfg.rx_retries,
+            );
+        }
+
+        // With no device selected, atcab_init() does not release the device
+        // of another instance, the handle of the new one is taken over from _gDevice
         let result = AtcaStatus::from(unsafe {
             let _guard = atecc_device
                 .api_mutex
                 .lock()
                 .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_init(iface_cfg_raw_ptr)
+            let result = cryptoauthlib_sys::atcab_init(iface_cfg_raw_ptr);
+            atecc_device
+                .api_mutex
+                .device
+                .store(cryptoauthlib_sys::_gDevice, Ordering::SeqCst);
+            result
         });
 
         atecc_device.iface_cfg_ptr = match result {
@@ -434,108 +1493,143 @@ impl AteccDevice {
             },
             _ => {
                 // Here init failed so no need to call a proper release
-                ATECC_RESOURCE_MANAGER.lock().unwrap().release();
-                unsafe { Box::from_raw(iface_cfg_raw_ptr) };
-                return Err(result.to_string());
-            }
-        };
-
-        // atecc_device.api_mutex is already initialized
-        // from now on it is safe to call atecc_device.release();
-
-        atecc_device.serial_number = {
-            let mut number: [u8; ATCA_SERIAL_NUM_SIZE] = [0; ATCA_SERIAL_NUM_SIZE];
-            let result = atecc_device.read_serial_number(&mut number);
-            match result {
-                AtcaStatus::AtcaSuccess => number,
-                _ => {
-                    atecc_device.release();
-                    return Err(result.to_string());
-                }
-            }
-        };
-
-        atecc_device.slots = {
-            let mut atca_slots = Vec::new();
-            let result = atecc_device.get_config_from_chip(&mut atca_slots);
-            match result {
-                AtcaStatus::AtcaSuccess => atca_slots,
-                _ => {
-                    atecc_device.release();
-                    return Err(result.to_string());
-                }
+                ATECC_RESOURCE_MANAGER.lock().unwrap().release(iface_id);
+                #[cfg(feature = "i2c-rust")]
+                i2c_rust::unconfigure(iface_cfg_raw_ptr);
+                unsafe { drop(Box::from_raw(iface_cfg_raw_ptr)) };
+                return Err(result.to_string());
             }
         };
 
-        atecc_device.config_zone_locked = {
-            match atecc_device.is_locked(ATCA_LOCK_ZONE_CONFIG) {
-                Ok(is_locked) => is_locked,
-                Err(err) => {
-                    atecc_device.release();
-                    return Err(err.to_string());
-                }
-            }
-        };
+        // atecc_device.api_mutex is already initialized
+        // from now on it is safe to call atecc_device.release();
+        atecc_device.released.store(false, Ordering::SeqCst);
+        atecc_device.api_mutex.power_policy = r_iface_cfg.power_policy;
 
-        atecc_device.data_zone_locked = {
-            match atecc_device.is_locked(ATCA_LOCK_ZONE_DATA) {
-                Ok(is_locked) => is_locked,
-                Err(err) => {
-                    atecc_device.release();
-                    return Err(err.to_string());
-                }
-            }
-        };
+        let result = atecc_device.refresh();
+        if result != AtcaStatus::AtcaSuccess {
+            atecc_device.release();
+            return Err(result.to_string());
+        }
 
-        atecc_device.chip_options = {
-            match atecc_device.get_chip_options_data_from_chip() {
+        // With lazy initialization the serial number, the options and the configuration
+        // of the slots are read from the chip when one of them is first needed
+        if !r_iface_cfg.lazy_init {
+            let chip_config = match atecc_device.read_chip_config() {
                 Ok(val) => val,
                 Err(err) => {
                     atecc_device.release();
                     return Err(err.to_string());
                 }
+            };
+            if let Err(err) = atecc_device.check_device_type(&chip_config.chip_options) {
+                atecc_device.release();
+                return Err(err);
             }
-        };
+            atecc_device.chip_config.set(chip_config);
+        }
+
+        Ok(atecc_device)
+    } // AteccDevice::new()
+
+    /// Reads the serial number, the options and the configuration of the slots from the chip
+    fn read_chip_config(&self) -> Result<ChipConfig, AtcaStatus> {
+        let mut serial_number: [u8; ATCA_SERIAL_NUM_SIZE] = [0; ATCA_SERIAL_NUM_SIZE];
+        let result = self.read_serial_number(&mut serial_number);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+
+        let mut slots = Vec::new();
+        let result = self.get_config_from_chip(&mut slots);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+
+        Ok(ChipConfig {
+            serial_number,
+            chip_options: self.get_chip_options_data_from_chip()?,
+            slots,
+        })
+    } // AteccDevice::read_chip_config()
 
-        let chip_type = atecc_device.get_device_type();
+    /// Checks that the options of the chip match the device type of the configuration
+    fn check_device_type(&self, chip_options: &ChipOptions) -> Result<(), String> {
+        let chip_type = self.get_device_type();
         let err_str = "\n\n\u{001b}[1m\u{001b}[33mcheck if 'device_type' is correct in \
         'config.toml' file, because chip on the bus seems to be";
-        if atecc_device.chip_options.aes_enabled && (chip_type != AtcaDeviceType::ATECC608A) {
-            atecc_device.release();
+        if chip_options.aes_enabled && (chip_type != AtcaDeviceType::ATECC608A) {
             return Err(format!(
                 "{} type ATECC608x,\nand you have chosen \u{001b}[31m{}\u{001b}[33m !\u{001b}[0m\n\n",
-                err_str.to_string(),
-                chip_type.to_string()
+                err_str,
+                chip_type
             ));
         }
-        if !atecc_device.chip_options.aes_enabled && (chip_type == AtcaDeviceType::ATECC608A) {
-            atecc_device.release();
+        if !chip_options.aes_enabled && (chip_type == AtcaDeviceType::ATECC608A) {
             return Err(format!(
                 "{} of a different type than the \u{001b}[31mATECC608x\u{001b}[33m you selected !\u{001b}[0m\n\n",
-                err_str.to_string()
+                err_str
             ));
         }
-
-        Ok(atecc_device)
-    } // AteccDevice::new()
+        Ok(())
+    } // AteccDevice::check_device_type()
+
+    /// Configuration of the chip, read now if it has not been read at initialization.
+    /// A device type not matching the chip is reported as AtcaBadParam
+    fn chip_config(&self) -> Result<&ChipConfig, AtcaStatus> {
+        self.chip_config.get_or_try_init(|| {
+            let chip_config = self.read_chip_config()?;
+            match self.check_device_type(&chip_config.chip_options) {
+                Ok(()) => Ok(chip_config),
+                Err(_) => Err(AtcaStatus::AtcaBadParam),
+            }
+        })
+    } // AteccDevice::chip_config()
+
+    /// Configuration of the given slot
+    fn slot_config(&self, slot_id: u8) -> Result<SlotConfig, AtcaStatus> {
+        self.chip_config()?
+            .slots
+            .get(slot_id as usize)
+            .map(|slot| slot.config)
+            .ok_or(AtcaStatus::AtcaInvalidId)
+    } // AteccDevice::slot_config()
+
+    /// Options supported by the chip
+    fn chip_options(&self) -> Result<ChipOptions, AtcaStatus> {
+        Ok(self.chip_config()?.chip_options)
+    } // AteccDevice::chip_options()
 
     /// Request ATECC to generate a vector of random bytes
     fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        let mut random = [0; ATCA_RANDOM_BUFFER_SIZE];
+        let result = self.random_into(&mut random);
+        if AtcaStatus::AtcaSuccess == result {
+            *rand_out = random.to_vec();
+        }
+        result
+    } // AteccDevice::random()
+
+    /// Request ATECC to generate random bytes into a buffer of the caller
+    fn random_into(&self, rand_out: &mut [u8; ATCA_RANDOM_BUFFER_SIZE]) -> AtcaStatus {
         if self.check_that_configuration_is_not_locked(false) {
             return AtcaStatus::AtcaNotLocked;
         }
-        rand_out.resize(ATCA_RANDOM_BUFFER_SIZE, 0);
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_random(rand_out.as_mut_ptr())
-        })
-    } // AteccDevice::random()
+        self.atcab_call(|| unsafe { cryptoauthlib_sys::atcab_random(rand_out.as_mut_ptr()) })
+    } // AteccDevice::random_into()
 
     /// Request ATECC to compute a message hash (SHA256)
     fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
+        let mut result_digest = [0; ATCA_SHA2_256_DIGEST_SIZE];
+        let result = self.sha_into(&message, &mut result_digest);
+        if AtcaStatus::AtcaSuccess == result {
+            *digest = result_digest.to_vec();
+        }
+        result
+    } // AteccDevice::sha()
+
+    /// Request ATECC to compute a message hash (SHA256) into a buffer of the caller
+    fn sha_into(&self, message: &[u8], digest: &mut [u8; ATCA_SHA2_256_DIGEST_SIZE]) -> AtcaStatus {
         if self.check_that_configuration_is_not_locked(false) {
             return AtcaStatus::AtcaNotLocked;
         }
@@ -543,17 +1637,83 @@ impl AteccDevice {
             Ok(val) => val,
             Err(_) => return AtcaStatus::AtcaBadParam,
         };
-
-        digest.resize(ATCA_SHA2_256_DIGEST_SIZE, 0);
-
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_sha(length, message.as_ptr(), digest.as_mut_ptr())
         })
-    } // AteccDevice::sha()
+    } // AteccDevice::sha_into()
+
+    /// Starts a SHA256 digest calculated by ATECC in several steps
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(false) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+
+        let mut ctx = AtcaSha256Ctx::default();
+        let result =
+            self.atcab_call(|| unsafe { cryptoauthlib_sys::atcab_hw_sha2_256_init(&mut ctx.ctx) });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(ctx),
+            _ => Err(result),
+        }
+    } // AteccDevice::sha_start()
+
+    /// Feeds the next part of a message into a SHA256 digest calculation.
+    /// Complete blocks are processed by ATECC, the rest is kept in the context
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        let mut ctx = ctx;
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_hw_sha2_256_update(
+                &mut ctx.ctx,
+                data.as_ptr(),
+                data.len() as cryptoauthlib_sys::size_t,
+            )
+        });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(ctx),
+            _ => Err(result),
+        }
+    } // AteccDevice::sha_update()
+
+    /// Completes a SHA256 digest calculation and returns the digest
+    fn sha_finish(&self, ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        let mut ctx = ctx;
+        let mut digest: Vec<u8> = vec![0x00; ATCA_SHA2_256_DIGEST_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_hw_sha2_256_finish(&mut ctx.ctx, digest.as_mut_ptr())
+        });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(digest),
+            _ => Err(result),
+        }
+    } // AteccDevice::sha_finish()
+
+    /// Request ATECC to compute HMAC-SHA256 of a message with the 32-byte key stored in a slot,
+    /// the key never leaves the chip
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(false) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+
+        let mut digest: Vec<u8> = vec![0x00; ATCA_SHA2_256_DIGEST_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_sha_hmac(
+                message.as_ptr(),
+                message.len() as cryptoauthlib_sys::size_t,
+                slot_id as u16,
+                digest.as_mut_ptr(),
+                SHA_MODE_TARGET_OUT_ONLY,
+            )
+        });
+
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(digest),
+            _ => Err(result),
+        }
+    } // AteccDevice::sha_hmac()
 
     /// Execute a Nonce command in pass-through mode to load one of the
     /// device's internal buffers with a fixed value.
@@ -570,19 +1730,14 @@ impl AteccDevice {
         let no_len_32: bool = data.len() != ATCA_NONCE_SIZE;
         let no_len_64: bool = data.len() != (2 * ATCA_NONCE_SIZE);
 
-        if !dev_type_608 && alt_key_buff
-            || alt_key_buff && no_len_32
+        if alt_key_buff && (!dev_type_608 || no_len_32)
             || !dev_type_608 && !no_len_64
             || no_len_32 && no_len_64
             || !no_len_32 && !no_len_64
         {
             return AtcaStatus::AtcaInvalidSize;
         }
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_nonce_load(target as u8, data.as_ptr(), data.len() as u16)
         })
     } // AteccDevice::nonce()
@@ -596,41 +1751,153 @@ impl AteccDevice {
 
         rand_out.resize(ATCA_RANDOM_BUFFER_SIZE, 0);
 
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_nonce_rand(host_nonce.as_ptr(), rand_out.as_mut_ptr())
         })
     } // AteccDevice::nonce_rand()
 
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        let slot = match SlotId::new(slot_id) {
+            Ok(slot) => slot,
+            Err(err) => return err,
+        };
+        let other_data_size = match zone {
+            GenDigZone::Data => 0,
+            GenDigZone::SharedNonce => ATCA_KEY_SIZE,
+        };
+        if other_data.len() != other_data_size {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        let other_data_ptr = match other_data.is_empty() {
+            true => ptr::null(),
+            false => other_data.as_ptr(),
+        };
+
+        self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_gendig(
+                zone as u8,
+                slot.key_id(),
+                other_data_ptr,
+                other_data.len() as u8,
+            )
+        })
+    } // AteccDevice::gen_dig()
+
+    /// Execute a MAC command over the key stored in a slot and a challenge or TempKey
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        let slot = SlotId::new(slot_id)?;
+        let (mode, challenge_ptr) = match challenge {
+            None => (MAC_MODE_TEMPKEY, ptr::null()),
+            Some(challenge) if challenge.len() == ATCA_KEY_SIZE => {
+                (MAC_MODE_CHALLENGE, challenge.as_ptr())
+            }
+            Some(_) => return Err(AtcaStatus::AtcaInvalidSize),
+        };
+        let mut digest: Vec<u8> = vec![0; ATCA_SHA2_256_DIGEST_SIZE];
+
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_mac(mode, slot.key_id(), challenge_ptr, digest.as_mut_ptr())
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(digest),
+            _ => Err(result),
+        }
+    } // AteccDevice::mac()
+
+    /// Execute a CheckMac command to verify a MAC calculated by the host
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let slot = SlotId::new(slot_id)?;
+        if response.len() != ATCA_SHA2_256_DIGEST_SIZE
+            || other_data.len() != ATCA_CHECK_MAC_OTHER_DATA_SIZE
+        {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        // CheckMac always sends a challenge, ignored by the chip in the TempKey mode
+        let no_challenge: [u8; ATCA_KEY_SIZE] = [0; ATCA_KEY_SIZE];
+        let (mode, challenge) = match challenge {
+            None => (MAC_MODE_TEMPKEY, &no_challenge[..]),
+            Some(challenge) if challenge.len() == ATCA_KEY_SIZE => (MAC_MODE_CHALLENGE, challenge),
+            Some(_) => return Err(AtcaStatus::AtcaInvalidSize),
+        };
+
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_checkmac(
+                mode,
+                slot.key_id(),
+                challenge.as_ptr(),
+                response.as_ptr(),
+                other_data.as_ptr(),
+            )
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(true),
+            AtcaStatus::AtcaCheckMacVerifyFailed => Ok(false),
+            _ => Err(result),
+        }
+    } // AteccDevice::check_mac()
+
+    /// Request ATECC to read the value of one of the two monotonic counters
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        if counter_id > 1 {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        let mut counter_value: u32 = 0;
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_counter_read(counter_id as u16, &mut counter_value)
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(counter_value),
+            _ => Err(result),
+        }
+    } // AteccDevice::counter_read()
+
+    /// Request ATECC to increment one of the two monotonic counters
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        if counter_id > 1 {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        let mut counter_value: u32 = 0;
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_counter_increment(counter_id as u16, &mut counter_value)
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(counter_value),
+            _ => Err(result),
+        }
+    } // AteccDevice::counter_increment()
+
     /// Request ATECC to generate a cryptographic key
     fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
         if self.check_that_configuration_is_not_locked(false) {
             return AtcaStatus::AtcaNotLocked;
         }
 
-        if let Err(err) = self.encryption_key_setup_parameters_check(key_type, slot_id) {
-            return err;
-        }
-
-        let slot = match slot_id {
-            ATCA_ATECC_SLOTS_COUNT => ATCA_ATECC_TEMPKEY_KEYID,
-            _ => slot_id as u16,
+        let key_slot = match self.encryption_key_setup_parameters_check(key_type, slot_id) {
+            Ok(key_slot) => key_slot,
+            Err(err) => return err,
         };
 
+        let slot = key_slot.key_id();
+
         match key_type {
             KeyType::P256EccKey => {
-                if !self.slots[slot_id as usize].config.is_secret {
+                let slot_data = match self.slot_config(slot_id) {
+                    Ok(val) => val,
+                    Err(err) => return err,
+                };
+                if !slot_data.is_secret {
                     return AtcaStatus::AtcaBadParam;
                 }
-                AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
-                    cryptoauthlib_sys::atcab_genkey(slot, ptr::null_mut() as *mut u8)
+                self.atcab_call(|| unsafe {
+                    cryptoauthlib_sys::atcab_genkey(slot, ptr::null_mut())
                 })
             }
             KeyType::Aes => {
@@ -645,10 +1912,14 @@ impl AteccDevice {
                 if key.len() < ATCA_BLOCK_SIZE {
                     key.resize(ATCA_BLOCK_SIZE, 0);
                 }
-                if slot != ATCA_ATECC_TEMPKEY_KEYID {
+                if !key_slot.is_temp_key() {
                     const BLOCK_IDX: u8 = 0;
                     const OFFSET: u8 = 0;
-                    match self.slots[slot_id as usize].config.write_config {
+                    let slot_data = match self.slot_config(slot_id) {
+                        Ok(val) => val,
+                        Err(err) => return err,
+                    };
+                    match slot_data.write_config {
                         WriteConfig::Always => self.write_zone(
                             ATCA_ZONE_DATA,
                             slot,
@@ -658,7 +1929,10 @@ impl AteccDevice {
                             ATCA_BLOCK_SIZE as u8,
                         ),
                         WriteConfig::Encrypt => {
-                            let num_in: [u8; ATCA_NONCE_NUMIN_SIZE] = [0; ATCA_NONCE_NUMIN_SIZE];
+                            let num_in = match self.host_nonce() {
+                                Ok(val) => val,
+                                Err(err) => return err,
+                            };
                             self.write_slot_with_encryption(slot, BLOCK_IDX, &key, &num_in)
                         }
                         _ => AtcaStatus::AtcaBadParam,
@@ -676,22 +1950,23 @@ impl AteccDevice {
         if self.check_that_configuration_is_not_locked(true) {
             return AtcaStatus::AtcaNotLocked;
         }
-        if let Err(err) = self.encryption_key_setup_parameters_check(key_type, slot_id) {
-            return err;
-        }
+        let key_slot = match self.encryption_key_setup_parameters_check(key_type, slot_id) {
+            Ok(key_slot) => key_slot,
+            Err(err) => return err,
+        };
 
         if ((key_type == KeyType::Aes) && (key_data.len() != ATCA_AES_KEY_SIZE))
             || ((key_type == KeyType::P256EccKey)
                 && !((key_data.len() == ATCA_ATECC_PRIV_KEY_SIZE)
                     || (key_data.len() == ATCA_ATECC_PUB_KEY_SIZE)))
+            || ((key_type == KeyType::ShaOrText)
+                && (key_data.is_empty()
+                    || (key_data.len() > self.get_slot_capacity(slot_id).bytes as usize)))
         {
             return AtcaStatus::AtcaInvalidSize;
         }
 
-        let slot = match slot_id {
-            ATCA_ATECC_SLOTS_COUNT => ATCA_ATECC_TEMPKEY_KEYID,
-            _ => slot_id as u16,
-        };
+        let slot = key_slot.key_id();
 
         match key_type {
             KeyType::P256EccKey => match key_data.len() {
@@ -700,31 +1975,25 @@ impl AteccDevice {
                         return AtcaStatus::AtcaInvalidId;
                     }
 
-                    AtcaStatus::from(unsafe {
-                        let _guard = self
-                            .api_mutex
-                            .lock()
-                            .expect("Could not lock atcab API mutex");
+                    self.atcab_call(|| unsafe {
                         cryptoauthlib_sys::atcab_write_pubkey(slot, key_data.as_ptr())
                     })
                 }
                 _ => {
-                    let mut temp_key: Vec<u8> = vec![0; 4];
+                    let mut temp_key = Zeroizing::new(vec![0; 4]);
                     temp_key.extend_from_slice(key_data);
 
-                    if let Some(write_key_idx) = self.get_write_key_idx(slot_id as u8) {
-                        let mut write_key = vec![0; ATCA_KEY_SIZE];
+                    if let Some(write_key_idx) = self.get_write_key_idx(slot_id) {
+                        let mut write_key = Zeroizing::new(vec![0; ATCA_KEY_SIZE]);
                         let result = self.get_access_key(write_key_idx, &mut write_key);
 
                         if AtcaStatus::AtcaSuccess == result {
-                            let mut num_in: [u8; ATCA_NONCE_NUMIN_SIZE] =
-                                [0; ATCA_NONCE_NUMIN_SIZE];
-
-                            AtcaStatus::from(unsafe {
-                                let _guard = self
-                                    .api_mutex
-                                    .lock()
-                                    .expect("Could not lock atcab API mutex");
+                            let mut num_in = match self.host_nonce() {
+                                Ok(val) => val,
+                                Err(err) => return err,
+                            };
+
+                            self.atcab_call(|| unsafe {
                                 cryptoauthlib_sys::atcab_priv_write(
                                     slot,
                                     temp_key.as_ptr(),
@@ -742,14 +2011,18 @@ impl AteccDevice {
                 }
             },
             KeyType::Aes => {
-                let mut temp_key: Vec<u8> = key_data.to_vec();
+                let mut temp_key = Zeroizing::new(key_data.to_vec());
                 temp_key.resize(ATCA_BLOCK_SIZE, 0);
 
-                if slot != ATCA_ATECC_TEMPKEY_KEYID {
+                if !key_slot.is_temp_key() {
                     const BLOCK_IDX: u8 = 0;
                     const OFFSET: u8 = 0;
 
-                    match self.slots[slot as usize].config.write_config {
+                    let slot_data = match self.slot_config(slot_id) {
+                        Ok(val) => val,
+                        Err(err) => return err,
+                    };
+                    match slot_data.write_config {
                         WriteConfig::Always => self.write_zone(
                             ATCA_ZONE_DATA,
                             slot,
@@ -759,7 +2032,10 @@ impl AteccDevice {
                             ATCA_BLOCK_SIZE as u8,
                         ),
                         WriteConfig::Encrypt => {
-                            let num_in: [u8; ATCA_NONCE_NUMIN_SIZE] = [0; ATCA_NONCE_NUMIN_SIZE];
+                            let num_in = match self.host_nonce() {
+                                Ok(val) => val,
+                                Err(err) => return err,
+                            };
                             self.write_slot_with_encryption(slot, BLOCK_IDX, &temp_key, &num_in)
                         }
                         _ => AtcaStatus::AtcaBadParam,
@@ -768,11 +2044,36 @@ impl AteccDevice {
                     self.nonce(NonceTarget::TempKey, &temp_key)
                 }
             }
-            KeyType::ShaOrText => AtcaStatus::AtcaUnimplemented,
+            KeyType::ShaOrText => self.write_sha_or_text_key_to_slot(slot_id, key_data),
             _ => AtcaStatus::AtcaBadParam,
         }
     } // AteccDevice::import_key()
 
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1).
+    /// The key is written with the same encrypted PrivWrite command as by import_key().
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        match private_key_from_der(key_data) {
+            Ok(mut private_key) => {
+                let result = self.import_key(KeyType::P256EccKey, &private_key, slot_id);
+                private_key.iter_mut().for_each(|x| *x = 0x00);
+                result
+            }
+            Err(err) => err,
+        }
+    } // AteccDevice::import_private_key_der()
+
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus {
+        match private_key_from_pem(key_data) {
+            Ok(mut private_key) => {
+                let result = self.import_key(KeyType::P256EccKey, &private_key, slot_id);
+                private_key.iter_mut().for_each(|x| *x = 0x00);
+                result
+            }
+            Err(err) => err,
+        }
+    } // AteccDevice::import_private_key_pem()
+
     /// Request ATECC to export a cryptographic key.
     /// For key type 'ShaOrText', the amount of data returned is as large as
     /// size of the given buffer 'key_data', but when this size is greater than
@@ -782,9 +2083,9 @@ impl AteccDevice {
         if self.check_that_configuration_is_not_locked(true) {
             return AtcaStatus::AtcaNotLocked;
         }
-        if slot_id >= ATCA_ATECC_SLOTS_COUNT {
-            return AtcaStatus::AtcaInvalidId;
-        };
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
         match key_type {
             KeyType::P256EccKey => self.get_public_key(slot_id, key_data),
             KeyType::Aes => self.read_aes_key_from_slot(slot_id, key_data),
@@ -793,6 +2094,149 @@ impl AteccDevice {
         }
     } // AteccDevice::export_key()
 
+    /// Request ATECC to write data to a slot, starting at the given byte offset.
+    /// Depending on the slot configuration, data is written in clear text
+    /// or encrypted with the write key previously set by add_access_key().
+    /// Clear text writes must be aligned to 4-byte words, encrypted writes
+    /// (and writes to secret slots) to 32-byte blocks. The last block of a slot
+    /// may be written partially, it is then padded with zeros.
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let granularity = match self.get_slot_write_granularity(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        let capacity = self.get_slot_capacity(slot_id).bytes as usize;
+        if data.is_empty() || (offset > capacity) || (data.len() > (capacity - offset)) {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        let end = offset + data.len();
+        if !offset.is_multiple_of(granularity)
+            || (!data.len().is_multiple_of(granularity) && (end != capacity))
+        {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        let mut position = offset;
+        while position < end {
+            let block_offset = position % ATCA_BLOCK_SIZE;
+            let len = std::cmp::min(ATCA_BLOCK_SIZE - block_offset, end - position);
+            let chunk = &data[(position - offset)..(position - offset + len)];
+
+            let result = self.write_slot_block(
+                slot_id,
+                (position / ATCA_BLOCK_SIZE) as u8,
+                block_offset,
+                chunk,
+            );
+            if AtcaStatus::AtcaSuccess != result {
+                return result;
+            }
+            position += len;
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // AteccDevice::write_slot_data()
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset.
+    /// Depending on the slot configuration, data is read in clear text
+    /// or encrypted with the read key previously set by add_access_key().
+    /// Slots that are secret and not configured for encrypted reads cannot be read.
+    fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+        let slot_data = self.slot_config(slot_id)?;
+        if slot_data.is_secret && !slot_data.read_key.encrypt_read {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        let capacity = self.get_slot_capacity(slot_id).bytes as usize;
+        if (len == 0) || (offset > capacity) || (len > (capacity - offset)) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        let mut data: Vec<u8> = Vec::with_capacity(len);
+        let end = offset + len;
+        let mut position = offset;
+        while position < end {
+            let block_offset = position % ATCA_BLOCK_SIZE;
+            let chunk_len = std::cmp::min(ATCA_BLOCK_SIZE - block_offset, end - position);
+
+            let chunk = self.read_slot_block(
+                slot_id,
+                (position / ATCA_BLOCK_SIZE) as u8,
+                block_offset,
+                chunk_len,
+            )?;
+            data.extend_from_slice(&chunk);
+            position += chunk_len;
+        }
+
+        Ok(data)
+    } // AteccDevice::read_slot_data()
+
+    /// Request ATECC to write the OTP zone, starting at the given byte offset.
+    /// Writes must be aligned to 4-byte words. The zone can be written only
+    /// after the configuration zone has been locked and before the data zone is locked
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        if !self.is_configuration_locked() {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if self.is_data_zone_locked() {
+            return AtcaStatus::AtcaDataZoneLocked;
+        }
+        if let Err(err) = check_otp_range(offset, data.len()) {
+            return err;
+        }
+
+        self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_write_bytes_zone(
+                ATCA_ZONE_OTP,
+                0,
+                offset as cryptoauthlib_sys::size_t,
+                data.as_ptr(),
+                data.len() as cryptoauthlib_sys::size_t,
+            )
+        })
+    } // AteccDevice::write_otp_zone()
+
+    /// Request ATECC to read the OTP zone, starting at the given byte offset.
+    /// Reads must be aligned to 4-byte words, the zone can be read once the data zone is locked
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        if !self.is_data_zone_locked() {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        check_otp_range(offset, len)?;
+
+        let mut data: Vec<u8> = vec![0; len];
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_read_bytes_zone(
+                ATCA_ZONE_OTP,
+                0,
+                offset as cryptoauthlib_sys::size_t,
+                data.as_mut_ptr(),
+                len as cryptoauthlib_sys::size_t,
+            )
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(data),
+            err => Err(err),
+        }
+    } // AteccDevice::read_otp_zone()
+
     /// Depending on the socket configuration, this function calculates
     /// public key based on an existing private key in the socket
     /// or exports the public key directly
@@ -800,23 +2244,21 @@ impl AteccDevice {
         if self.check_that_configuration_is_not_locked(true) {
             return AtcaStatus::AtcaNotLocked;
         }
-        if self.slots[slot_id as usize].config.key_type != KeyType::P256EccKey {
+        let slot_data = match self.slot_config(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+        if slot_data.key_type != KeyType::P256EccKey {
             return AtcaStatus::AtcaBadParam;
         }
         public_key.resize(ATCA_ATECC_PUB_KEY_SIZE, 0);
 
-        if self.slots[slot_id as usize].config.is_secret {
-            if self.slots[slot_id as usize].config.pub_info
-                && self.slots[slot_id as usize].config.ecc_key_attr.is_private
-            {
-                AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
+        if slot_data.is_secret {
+            if slot_data.pub_info && slot_data.ecc_key_attr.is_private {
+                self.atcab_call(|| unsafe {
                     cryptoauthlib_sys::atcab_get_pubkey(slot_id as u16, public_key.as_mut_ptr())
                 })
-            } else if self.slots[slot_id as usize].config.read_key.encrypt_read {
+            } else if slot_data.read_key.encrypt_read {
                 if slot_id < ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY {
                     AtcaStatus::AtcaInvalidId
                 } else {
@@ -828,15 +2270,11 @@ impl AteccDevice {
             } else {
                 AtcaStatus::AtcaBadParam
             }
-        } else if self.slots[slot_id as usize].config.write_config == WriteConfig::Always {
+        } else if slot_data.write_config == WriteConfig::Always {
             if slot_id < ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY {
                 AtcaStatus::AtcaInvalidId
             } else {
-                AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
+                self.atcab_call(|| unsafe {
                     cryptoauthlib_sys::atcab_read_pubkey(slot_id as u16, public_key.as_mut_ptr())
                 })
             }
@@ -850,8 +2288,8 @@ impl AteccDevice {
         if self.check_that_configuration_is_not_locked(true) {
             return AtcaStatus::AtcaNotLocked;
         }
-        if slot_id >= ATCA_ATECC_SLOTS_COUNT {
-            return AtcaStatus::AtcaInvalidId;
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
         }
         signature.resize(ATCA_SIG_SIZE, 0);
         match mode {
@@ -859,17 +2297,70 @@ impl AteccDevice {
             // private key in the specified slot. The message to be signed
             // will be loaded into the Message Digest Buffer to the
             // ATECC608A device or TempKey for other devices.
-            SignMode::External(hash) => AtcaStatus::from(unsafe {
-                let _guard = self
-                    .api_mutex
-                    .lock()
-                    .expect("Could not lock atcab API mutex");
+            SignMode::External(hash) => self.atcab_call(|| unsafe {
                 cryptoauthlib_sys::atcab_sign(slot_id as u16, hash.as_ptr(), signature.as_mut_ptr())
             }),
+            // As above, the message is loaded into the buffer chosen by the caller
+            SignMode::ExternalVia(buffer, hash) => {
+                self.sign_hash_via(buffer, &hash, slot_id, signature)
+            }
             _ => AtcaStatus::AtcaUnimplemented,
         }
     } // AteccDevice::sign_hash()
 
+    /// Request ATECC to sign a SHA256 digest into a buffer of the caller
+    fn sign_hash_into(
+        &self,
+        slot_id: u8,
+        hash: &[u8; ATCA_SHA2_256_DIGEST_SIZE],
+        signature: &mut [u8; ATCA_SIG_SIZE],
+    ) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_sign(slot_id as u16, hash.as_ptr(), signature.as_mut_ptr())
+        })
+    } // AteccDevice::sign_hash_into()
+
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot.
+    /// The message is hashed in parts, so its size is not limited by the SHA command.
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        SlotId::new(slot_id)?;
+
+        let mut ctx = self.sha_start()?;
+        ctx = self.sha_update(ctx, message)?;
+        let digest = self.sha_finish(ctx)?;
+
+        let mut signature: Vec<u8> = Vec::new();
+        match self.sign_hash(SignMode::External(digest), slot_id, &mut signature) {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            err => Err(err),
+        }
+    } // AteccDevice::sign_message()
+
+    /// Request ATECC to execute a Sign command in the given mode
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+        check_sign_base_mode(self.get_device_type(), mode)?;
+
+        let mut signature: Vec<u8> = vec![0; ATCA_SIG_SIZE];
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_sign_base(mode, slot_id as u16, signature.as_mut_ptr())
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            _ => Err(result),
+        }
+    } // AteccDevice::sign_base()
+
     /// Request ATECC to verify ECDSA signature
     fn verify_hash(
         &self,
@@ -884,22 +2375,14 @@ impl AteccDevice {
             return Err(AtcaStatus::AtcaInvalidSize);
         };
         let mut is_verified: bool = false;
-        let result: AtcaStatus;
-
-        match mode {
+        let result: AtcaStatus = match mode {
             // Executes the Verify command, which verifies a signature (ECDSA
             // verify operation) with a public key stored in the device. The
             // message to be signed will be loaded into the Message Digest Buffer
             // to the ATECC608A device or TempKey for other devices.
             VerifyMode::Internal(slot_number) => {
-                if slot_number >= ATCA_ATECC_SLOTS_COUNT {
-                    return Err(AtcaStatus::AtcaInvalidId);
-                }
-                result = AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
+                SlotId::new(slot_number)?;
+                self.atcab_call(|| unsafe {
                     cryptoauthlib_sys::atcab_verify_stored(
                         hash.as_ptr(),
                         signature.as_ptr(),
@@ -917,11 +2400,7 @@ impl AteccDevice {
                 if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
                     return Err(AtcaStatus::AtcaInvalidId);
                 }
-                result = AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
+                self.atcab_call(|| unsafe {
                     cryptoauthlib_sys::atcab_verify_extern(
                         hash.as_ptr(),
                         signature.as_ptr(),
@@ -930,8 +2409,16 @@ impl AteccDevice {
                     )
                 })
             }
+            // As above, the message is loaded into the buffer chosen by the caller
+            VerifyMode::ExternalVia(buffer, public_key) => {
+                return self.verify_hash_via(buffer, Some(&public_key), 0, hash, signature);
+            }
+            VerifyMode::InternalVia(buffer, slot_number) => {
+                SlotId::new(slot_number)?;
+                return self.verify_hash_via(buffer, None, slot_number, hash, signature);
+            }
             _ => return Err(AtcaStatus::AtcaUnimplemented),
-        }
+        };
 
         match result {
             AtcaStatus::AtcaSuccess => Ok(is_verified),
@@ -939,6 +2426,35 @@ impl AteccDevice {
         }
     } // AteccDevice::verify_hash()
 
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let mut signature: Vec<u8> = Vec::new();
+        match self.sign_hash(mode, slot_id, &mut signature) {
+            AtcaStatus::AtcaSuccess => match format {
+                SignatureFormat::Raw => Ok(signature),
+                SignatureFormat::Der => signature_to_der(&signature),
+            },
+            err => Err(err),
+        }
+    } // AteccDevice::sign_hash_formatted()
+
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        let signature = convert_signature(signature, format, SignatureFormat::Raw)?;
+        self.verify_hash(mode, hash, &signature)
+    } // AteccDevice::verify_hash_formatted()
+
     /// Data encryption function in AES unauthenticated cipher alhorithms modes
     fn cipher_encrypt(
         &self,
@@ -1016,7 +2532,7 @@ impl AteccDevice {
         &self,
         algorithm: AeadAlgorithm,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
     ) -> Result<Vec<u8>, AtcaStatus> {
         if self.check_that_configuration_is_not_locked(true) {
             return Err(AtcaStatus::AtcaNotLocked);
@@ -1027,8 +2543,12 @@ impl AteccDevice {
         }
 
         match algorithm {
-            AeadAlgorithm::Ccm(aead_param) => self.encrypt_aes_ccm(aead_param, slot_id, data),
-            AeadAlgorithm::Gcm(aead_param) => self.encrypt_aes_gcm(aead_param, slot_id, data),
+            AeadAlgorithm::Ccm(aead_param) => {
+                host_modes::encrypt_aes_ccm(self, aead_param, slot_id, data)
+            }
+            AeadAlgorithm::Gcm(aead_param) => {
+                host_modes::encrypt_aes_gcm(self, aead_param, slot_id, data)
+            }
         }
     } // AteccDevice::aead_encrypt()
 
@@ -1037,7 +2557,7 @@ impl AteccDevice {
         &self,
         algorithm: AeadAlgorithm,
         slot_id: u8,
-        data: &mut Vec<u8>,
+        data: &mut [u8],
     ) -> Result<bool, AtcaStatus> {
         if self.check_that_configuration_is_not_locked(true) {
             return Err(AtcaStatus::AtcaNotLocked);
@@ -1048,32 +2568,117 @@ impl AteccDevice {
         }
 
         match algorithm {
-            AeadAlgorithm::Ccm(aead_param) => self.decrypt_aes_ccm(aead_param, slot_id, data),
-            AeadAlgorithm::Gcm(aead_param) => self.decrypt_aes_gcm(aead_param, slot_id, data),
+            AeadAlgorithm::Ccm(aead_param) => {
+                host_modes::decrypt_aes_ccm(self, aead_param, slot_id, data)
+            }
+            AeadAlgorithm::Gcm(aead_param) => {
+                host_modes::decrypt_aes_gcm(self, aead_param, slot_id, data)
+            }
         }
     } // AteccDevice::aead_decrypt()
 
     /// Request ATECC to return own device type
     fn get_device_type(&self) -> AtcaDeviceType {
-        AtcaDeviceType::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_get_device_type()
-        })
+        AtcaDeviceType::from(self.device_type.load(Ordering::SeqCst))
     } // AteccDevice::get_device_type()
 
+    /// Reads again the device type known to CryptoAuthLib and the lock state of the zones
+    fn refresh(&self) -> AtcaStatus {
+        let device_type = match self.api_mutex.lock() {
+            Ok(_guard) => unsafe { cryptoauthlib_sys::atcab_get_device_type() },
+            Err(err) => return err,
+        };
+        self.device_type.store(device_type, Ordering::SeqCst);
+        self.refresh_lock_state()
+    } // AteccDevice::refresh()
+
+    /// Reads again the lock state of the configuration and data zones from the chip.
+    /// The cached state is left as it was if it cannot be read
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        let config_zone_locked = match self.is_locked(ATCA_LOCK_ZONE_CONFIG) {
+            Ok(is_locked) => is_locked,
+            Err(err) => return err,
+        };
+        let data_zone_locked = match self.is_locked(ATCA_LOCK_ZONE_DATA) {
+            Ok(is_locked) => is_locked,
+            Err(err) => return err,
+        };
+        self.config_zone_locked
+            .store(config_zone_locked, Ordering::SeqCst);
+        self.data_zone_locked
+            .store(data_zone_locked, Ordering::SeqCst);
+        AtcaStatus::AtcaSuccess
+    } // AteccDevice::refresh_lock_state()
+
     /// Returns a structure containing configuration data read from ATECC
     /// during initialization of the AteccDevice object.
     fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus {
         atca_slots.clear();
-        for idx in 0..self.slots.len() {
-            atca_slots.push(self.slots[idx])
+        match self.chip_config() {
+            Ok(config) => {
+                atca_slots.extend_from_slice(&config.slots);
+                AtcaStatus::AtcaSuccess
+            }
+            Err(err) => err,
         }
-        AtcaStatus::AtcaSuccess
     } // AteccDevice::get_config()
 
+    /// Request ATECC to read and return own configuration zone.
+    /// Note: this function returns raw data, function get_config(..) implements a more
+    /// structured return value.
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        config_data.resize(self.get_config_buffer_size(), 0);
+
+        self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_read_config_zone(config_data.as_mut_ptr())
+        })
+    } // AteccDevice::read_config_zone()
+
+    /// Request ATECC to write its whole configuration zone.
+    /// The configuration of the slots cached by this object is not updated,
+    /// the device has to be set up again once the zones are locked
+    fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        if config_data.len() != self.get_config_buffer_size() {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        if self.is_configuration_locked() {
+            return AtcaStatus::AtcaConfigZoneLocked;
+        }
+
+        self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_write_config_zone(config_data.as_ptr())
+        })
+    } // AteccDevice::write_config_zone()
+
+    /// Request ATECC to lock its configuration zone
+    fn lock_config_zone(&self) -> AtcaStatus {
+        if self.is_configuration_locked() {
+            return AtcaStatus::AtcaConfigZoneLocked;
+        }
+
+        let result = self.atcab_call(|| unsafe { cryptoauthlib_sys::atcab_lock_config_zone() });
+        match result {
+            AtcaStatus::AtcaSuccess => self.refresh_lock_state(),
+            _ => result,
+        }
+    } // AteccDevice::lock_config_zone()
+
+    /// Request ATECC to lock its data zone
+    fn lock_data_zone(&self) -> AtcaStatus {
+        if !self.is_configuration_locked() {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if self.is_data_zone_locked() {
+            return AtcaStatus::AtcaDataZoneLocked;
+        }
+
+        let result = self.atcab_call(|| unsafe { cryptoauthlib_sys::atcab_lock_data_zone() });
+        match result {
+            AtcaStatus::AtcaSuccess => self.refresh_lock_state(),
+            _ => result,
+        }
+    } // AteccDevice::lock_data_zone()
+
     /// Command accesses some static or dynamic information from the ATECC chip
     fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
         let mut out_data: Vec<u8> = vec![0; 4];
@@ -1083,11 +2688,7 @@ impl AteccDevice {
             InfoCmdType::State => (),
             _ => return Err(AtcaStatus::AtcaUnimplemented),
         }
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_info_base(command as u8, param2, out_data.as_mut_ptr())
         });
         match result {
@@ -1118,7 +2719,7 @@ impl AteccDevice {
         match access_keys_obj {
             Err(_) => AtcaStatus::AtcaFuncFail,
             Ok(mut access_keys) => {
-                let mut key_arr: [u8; ATCA_KEY_SIZE] = [0; ATCA_KEY_SIZE];
+                let mut key_arr = Zeroizing::new([0; ATCA_KEY_SIZE]);
                 key_arr.copy_from_slice(&access_key[0..]);
                 access_keys.insert(slot_id, key_arr);
                 AtcaStatus::AtcaSuccess
@@ -1140,31 +2741,94 @@ impl AteccDevice {
             Err(_) => AtcaStatus::AtcaFuncFail,
             Ok(mut access_keys) => {
                 access_keys.clear();
-                access_keys.shrink_to_fit();
                 AtcaStatus::AtcaSuccess
             }
         }
     } // AteccDevice::flush_access_keys()
 
+    /// Request ATECC608x to run the SelfTest command over all its functional blocks
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        // RNG, ECDSA, ECDH, AES and SHA tests
+        const SELFTEST_MODE_ALL: u8 = 0x3B;
+        if self.get_device_type() != AtcaDeviceType::ATECC608A {
+            return Err(AtcaStatus::AtcaBadOpcode);
+        }
+        // Bits of the tests that have failed
+        let mut failed_tests: u8 = 0;
+        let result = self.atcab_call(|| unsafe {
+            cryptoauthlib_sys::atcab_selftest(SELFTEST_MODE_ALL, 0, &mut failed_tests)
+        });
+        match result {
+            AtcaStatus::AtcaSuccess => Ok(failed_tests == 0),
+            // The chip reports a failed test with an error status as well
+            AtcaStatus::AtcaStatusSelftestError => Ok(false),
+            _ => Err(result),
+        }
+    } // AteccDevice::self_test()
+
+    /// Wakes the chip up
+    fn wake(&self) -> AtcaStatus {
+        AtcaStatus::from(unsafe {
+            let _guard = self
+                .api_mutex
+                .lock_power_mode()
+                .expect("Could not lock atcab API mutex");
+            cryptoauthlib_sys::atcab_wakeup()
+        })
+    } // AteccDevice::wake()
+
+    /// Puts the chip into the idle mode
+    fn idle(&self) -> AtcaStatus {
+        AtcaStatus::from(unsafe {
+            let _guard = self
+                .api_mutex
+                .lock_power_mode()
+                .expect("Could not lock atcab API mutex");
+            cryptoauthlib_sys::atcab_idle()
+        })
+    } // AteccDevice::idle()
+
+    /// Puts the chip into the sleep mode
+    fn sleep(&self) -> AtcaStatus {
+        AtcaStatus::from(unsafe {
+            let _guard = self
+                .api_mutex
+                .lock_power_mode()
+                .expect("Could not lock atcab API mutex");
+            cryptoauthlib_sys::atcab_sleep()
+        })
+    } // AteccDevice::sleep()
+
     /// ATECC device instance destructor
     // Requests:
     // 1. Internal rust-cryptoauthlib resource manager to release structure instance
     // 2. The structure itself to free the heap allocacted data
     // 3. CryptoAuthLib to release the ATECC device
+    // Only the first call releases anything, the following ones fail with AtcaBadParam.
     fn release(&self) -> AtcaStatus {
-        if !ATECC_RESOURCE_MANAGER.lock().unwrap().release() {
+        if self.released.swap(true, Ordering::SeqCst) {
             return AtcaStatus::AtcaBadParam;
         }
-        AtcaStatus::from(unsafe {
+        if !ATECC_RESOURCE_MANAGER
+            .lock()
+            .unwrap()
+            .release(self.iface_id)
+        {
+            return AtcaStatus::AtcaBadParam;
+        }
+        let result = AtcaStatus::from(unsafe {
             let _guard = self
                 .api_mutex
-                .lock()
+                .lock_power_mode()
                 .expect("Could not lock atcab API mutex");
-            // Restore iface_cfg from iface_cfg_ptr for the boxed structure to be released
-            // at the end.
-            Box::from_raw(self.iface_cfg_ptr.ptr);
             cryptoauthlib_sys::atcab_release()
-        })
+        });
+        // Restore iface_cfg from iface_cfg_ptr for the boxed structure to be released,
+        // once CryptoAuthLib no longer refers to it
+        #[cfg(feature = "i2c-rust")]
+        i2c_rust::unconfigure(self.iface_cfg_ptr.ptr);
+        unsafe { drop(Box::from_raw(self.iface_cfg_ptr.ptr)) };
+        result
     } // AteccDevice::release()
 
     //--------------------------------------------------
@@ -1185,30 +2849,11 @@ impl AteccDevice {
     ) -> AtcaStatus {
         data.resize(len as usize, 0);
 
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_read_zone(zone, slot, block, offset, data.as_mut_ptr(), len)
         })
     } // AteccDevice::read_zone()
 
-    /// Request ATECC to read and return own configuration zone.
-    /// Note: this function returns raw data, function get_config(..) implements a more
-    /// structured return value.
-    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
-        config_data.resize(self.get_config_buffer_size(), 0);
-
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_read_config_zone(config_data.as_mut_ptr())
-        })
-    } // AteccDevice::read_config_zone()
-
     /// Compare internal config zone contents vs. config_data.
     /// Diagnostic function.
     #[allow(dead_code)]
@@ -1218,11 +2863,7 @@ impl AteccDevice {
             return Err(AtcaStatus::AtcaBadParam);
         }
         let mut same_config: bool = false;
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        let result = self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_cmp_config_zone(config_data.as_mut_ptr(), &mut same_config)
         });
         if AtcaStatus::AtcaSuccess == result {
@@ -1270,39 +2911,45 @@ impl AteccDevice {
         const BLOCK_IDX: u8 = 0;
         const OFFSET: u8 = 0;
 
-        let slot_data = self.slots[slot_id as usize].config;
+        let slot_data = match self.slot_config(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
         if KeyType::Aes != slot_data.key_type {
             return AtcaStatus::AtcaBadParam;
         }
 
         let mut data_block: [u8; ATCA_BLOCK_SIZE] = [0; ATCA_BLOCK_SIZE];
-        let result: AtcaStatus;
-
-        if slot_data.is_secret && slot_data.read_key.encrypt_read {
-            let num_in: [u8; ATCA_NONCE_NUMIN_SIZE] = [0; ATCA_NONCE_NUMIN_SIZE];
-            result =
-                self.read_slot_with_encryption(slot_id as u16, BLOCK_IDX, &mut data_block, &num_in);
+        let result = if slot_data.is_secret && slot_data.read_key.encrypt_read {
+            let num_in = match self.host_nonce() {
+                Ok(val) => val,
+                Err(err) => return err,
+            };
+            self.read_slot_with_encryption(slot_id as u16, BLOCK_IDX, &mut data_block, &num_in)
         } else {
-            result = self.read_zone(
+            self.read_zone(
                 ATCA_ZONE_DATA,
                 slot_id as u16,
                 BLOCK_IDX,
                 OFFSET,
                 &mut data_block.to_vec(),
                 ATCA_BLOCK_SIZE as u8,
-            );
-        }
+            )
+        };
         if AtcaStatus::AtcaSuccess == result {
-            *key = data_block.to_vec();
-            key.resize(ATCA_AES_KEY_SIZE, 0);
+            *key = data_block[..ATCA_AES_KEY_SIZE].to_vec();
         }
+        data_block.zeroize();
 
         result
     } // AteccDevice::read_aes_key_from_slot()
 
     /// Function that reads a key of the 'ShaOrText' type from the indicated slot
-    fn read_sha_or_text_key_from_slot(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
-        let slot_data = self.slots[slot_id as usize].config;
+    fn read_sha_or_text_key_from_slot(&self, slot_id: u8, key: &mut [u8]) -> AtcaStatus {
+        let slot_data = match self.slot_config(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
         if KeyType::ShaOrText != slot_data.key_type {
             return AtcaStatus::AtcaBadParam;
         }
@@ -1313,35 +2960,58 @@ impl AteccDevice {
         AtcaStatus::AtcaUnimplemented
     } // AteccDevice::read_sha_or_text_key_from_slot()
 
+    /// Function that writes a key of the 'ShaOrText' type to the indicated slot.
+    /// The key is padded with zeros to the write granularity of the slot
+    /// (4 bytes for clear text writes, 32 bytes for secret or encrypted slots),
+    /// but never beyond the slot capacity.
+    fn write_sha_or_text_key_to_slot(&self, slot_id: u8, key: &[u8]) -> AtcaStatus {
+        let slot_data = match self.slot_config(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+        if KeyType::ShaOrText != slot_data.key_type {
+            return AtcaStatus::AtcaBadParam;
+        }
+        let granularity = match self.get_slot_write_granularity(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        let capacity = self.get_slot_capacity(slot_id).bytes as usize;
+        let padded_len = key.len().div_ceil(granularity) * granularity;
+        let mut data: Vec<u8> = key.to_vec();
+        data.resize(std::cmp::min(padded_len, capacity), 0);
+
+        self.write_slot_data(slot_id, 0, &data)
+    } // AteccDevice::write_sha_or_text_key_to_slot()
+
     /// A helper function for the gen_key() and import_key() methods,
     /// pre-checking combinations of input parameters
     fn encryption_key_setup_parameters_check(
         &self,
         key_type: KeyType,
         slot_id: u8,
-    ) -> Result<(), AtcaStatus> {
-        if slot_id > ATCA_ATECC_SLOTS_COUNT {
-            return Err(AtcaStatus::AtcaInvalidId);
-        }
-        // First condition is a special situation when
-        // an AES key can be generated in an ATECC TempKey slot.
-        if ((slot_id == ATCA_ATECC_SLOTS_COUNT) && (key_type != KeyType::Aes))
-            || ((key_type == KeyType::Aes) && !self.chip_options.aes_enabled)
-            || ((slot_id < ATCA_ATECC_SLOTS_COUNT)
-                && (key_type != self.slots[slot_id as usize].config.key_type))
+    ) -> Result<SlotId, AtcaStatus> {
+        let key_slot = SlotId::try_from(slot_id)?;
+        // TempKey can hold an AES key only
+        let slot_key_type = match key_slot {
+            SlotId::Slot(slot) => self.slot_config(slot)?.key_type,
+            SlotId::TempKey => KeyType::Aes,
+        };
+        if (key_type != slot_key_type)
+            || ((key_type == KeyType::Aes) && !self.chip_options()?.aes_enabled)
         {
             return Err(AtcaStatus::AtcaBadParam);
         }
-        Ok(())
+        Ok(key_slot)
     } // AteccDevice::encryption_key_setup_parameters_check()
 
     /// A helper function for the add_access_key() and get_access_key()
     /// methods, pre-checking combinations of input parameters
     fn access_key_setup_parameters_check(&self, slot_id: u8) -> Result<(), AtcaStatus> {
-        if (slot_id > ATCA_ATECC_SLOTS_COUNT) ||
-            // special condition for the key encrypting IO transmission between host and cryptochip 
-            ((slot_id == ATCA_ATECC_SLOTS_COUNT) &&
-            (self.get_device_type() != AtcaDeviceType::ATECC608A))
+        // TempKey stands for the key encrypting IO transmission between host and cryptochip
+        if SlotId::try_from(slot_id)?.is_temp_key()
+            && (self.get_device_type() != AtcaDeviceType::ATECC608A)
         {
             return Err(AtcaStatus::AtcaInvalidId);
         }
@@ -1377,8 +3047,8 @@ impl AteccDevice {
     /// required for operation of encrypted write to the given socket
     /// or value 'None' when such an operation cannot be performed for the given socket
     fn get_write_key_idx(&self, slot_id: u8) -> Option<u8> {
-        if slot_id < ATCA_ATECC_SLOTS_COUNT {
-            let slot_data = self.slots[slot_id as usize].config;
+        if let SlotId::Slot(slot) = SlotId::try_from(slot_id).ok()? {
+            let slot_data = self.slot_config(slot).ok()?;
             if slot_data.write_config == WriteConfig::Encrypt {
                 Some(slot_data.write_key)
             } else {
@@ -1393,8 +3063,8 @@ impl AteccDevice {
     /// required for operation of encrypted reading from the given socket
     /// or value 'None' when such an operation cannot be performed for the given socket
     fn get_read_key_idx(&self, slot_id: u8) -> Option<u8> {
-        if slot_id < ATCA_ATECC_SLOTS_COUNT {
-            let slot_data = self.slots[slot_id as usize].config;
+        if let SlotId::Slot(slot) = SlotId::try_from(slot_id).ok()? {
+            let slot_data = self.slot_config(slot).ok()?;
             if slot_data.read_key.encrypt_read
                 && slot_data.is_secret
                 && !slot_data.ecc_key_attr.is_private
@@ -1408,11 +3078,22 @@ impl AteccDevice {
         }
     } // AteccDevice::get_read_key_idx()
 
+    /// Makes a call of the atcab_* API under its lock, see AtcabApiMutex::lock()
+    fn atcab_call<F>(&self, call: F) -> AtcaStatus
+    where
+        F: FnOnce() -> cryptoauthlib_sys::ATCA_STATUS,
+    {
+        match self.api_mutex.lock() {
+            Ok(_guard) => AtcaStatus::from(call()),
+            Err(err) => err,
+        }
+    } // AteccDevice::atcab_call()
+
     /// A helper function that checks locking of configuration and data zones on the ATECC chip.
     #[inline]
     fn check_that_configuration_is_not_locked(&self, both: bool) -> bool {
         let mut result: bool = false;
-        if (!self.data_zone_locked && both) || !self.config_zone_locked {
+        if (!self.is_data_zone_locked() && both) || !self.is_configuration_locked() {
             result = true
         }
         result
@@ -1421,13 +3102,8 @@ impl AteccDevice {
     /// A function that reads the configuration zone to check if the specified zone is locked
     fn is_locked(&self, zone: u8) -> Result<bool, AtcaStatus> {
         let mut is_locked: bool = false;
-        let result = AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
-            cryptoauthlib_sys::atcab_is_locked(zone, &mut is_locked)
-        });
+        let result =
+            self.atcab_call(|| unsafe { cryptoauthlib_sys::atcab_is_locked(zone, &mut is_locked) });
         match result {
             AtcaStatus::AtcaSuccess => Ok(is_locked),
             _ => Err(result),
@@ -1498,6 +3174,12 @@ impl AteccDevice {
         }
         atca_slots.clear();
         atcab_get_config_from_config_zone(&config_data, atca_slots);
+        if self.get_device_type() != AtcaDeviceType::ATECC608A {
+            // CountMatch of ATECC608x takes the place of OTPmode of the older chips
+            for slot in atca_slots.iter_mut() {
+                slot.config.count_match = false;
+            }
+        }
         AtcaStatus::AtcaSuccess
     } // AteccDevice::get_config_from_chip()
 
@@ -1514,11 +3196,7 @@ impl AteccDevice {
 
     /// Request ATECC to read 9 byte serial number of the device from the config zone
     fn read_serial_number(&self, serial_number: &mut [u8; ATCA_SERIAL_NUM_SIZE]) -> AtcaStatus {
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_read_serial_number(serial_number.as_mut_ptr())
         })
     } // AteccDevice::read_serial_number()
@@ -1541,15 +3219,11 @@ impl AteccDevice {
         }
 
         if let Some(read_key_idx) = self.get_read_key_idx(slot as u8) {
-            let mut read_key = vec![0; ATCA_KEY_SIZE];
+            let mut read_key = Zeroizing::new(vec![0; ATCA_KEY_SIZE]);
             let result = self.get_access_key(read_key_idx, &mut read_key);
 
             if AtcaStatus::AtcaSuccess == result {
-                AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
+                self.atcab_call(|| unsafe {
                     cryptoauthlib_sys::atcab_read_enc(
                         slot,
                         block,
@@ -1579,15 +3253,226 @@ impl AteccDevice {
     ) -> AtcaStatus {
         data.resize(len as usize, 0);
 
-        AtcaStatus::from(unsafe {
-            let _guard = self
-                .api_mutex
-                .lock()
-                .expect("Could not lock atcab API mutex");
+        self.atcab_call(|| unsafe {
             cryptoauthlib_sys::atcab_write_zone(zone, slot, block, offset, data.as_mut_ptr(), len)
         })
     } // AteccDevice::write_zone()
 
+    /// Function that writes data to a single block of the given slot.
+    /// A full block (or a block written to a slot that only accepts 32-byte writes)
+    /// is sent with one command, padded with zeros if necessary.
+    /// Otherwise data is written word by word (4 bytes each) starting at 'block_offset'.
+    fn write_slot_block(
+        &self,
+        slot_id: u8,
+        block: u8,
+        block_offset: usize,
+        data: &[u8],
+    ) -> AtcaStatus {
+        let slot = slot_id as u16;
+        let slot_data = match self.slot_config(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+        let granularity = match self.get_slot_write_granularity(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        if (block_offset + data.len()) > ATCA_BLOCK_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        if (ATCA_BLOCK_SIZE == data.len()) || (ATCA_BLOCK_SIZE == granularity) {
+            if block_offset != 0 {
+                return AtcaStatus::AtcaInvalidSize;
+            }
+            let mut data_block: Vec<u8> = data.to_vec();
+            data_block.resize(ATCA_BLOCK_SIZE, 0);
+
+            return match slot_data.write_config {
+                WriteConfig::Encrypt => {
+                    let num_in = match self.host_nonce() {
+                        Ok(val) => val,
+                        Err(err) => return err,
+                    };
+                    self.write_slot_with_encryption(slot, block, &data_block, &num_in)
+                }
+                _ => {
+                    const OFFSET: u8 = 0;
+                    self.write_zone(
+                        ATCA_ZONE_DATA,
+                        slot,
+                        block,
+                        OFFSET,
+                        &mut data_block,
+                        ATCA_BLOCK_SIZE as u8,
+                    )
+                }
+            };
+        }
+
+        // Words are written one command each, more than two of them within
+        // a full-size block are merged into the block contents instead
+        let capacity = self.get_slot_capacity(slot_id);
+        let full_block = (block < (capacity.blocks - 1))
+            || (ATCA_BLOCK_SIZE == capacity.last_block_bytes as usize);
+        if full_block && (data.len() > (2 * ATCA_WORD_SIZE)) && self.is_data_zone_locked() {
+            return self.merge_slot_block(slot, block, block_offset, data);
+        }
+
+        let first_word = block_offset / ATCA_WORD_SIZE;
+        for (idx, chunk) in data.chunks(ATCA_WORD_SIZE).enumerate() {
+            let mut word: Vec<u8> = chunk.to_vec();
+            word.resize(ATCA_WORD_SIZE, 0);
+            let result = self.write_zone(
+                ATCA_ZONE_DATA,
+                slot,
+                block,
+                (first_word + idx) as u8,
+                &mut word,
+                ATCA_WORD_SIZE as u8,
+            );
+            if AtcaStatus::AtcaSuccess != result {
+                return result;
+            }
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // AteccDevice::write_slot_block()
+
+    /// Function that writes a part of a block of a clear text slot with two commands
+    /// regardless of the size of the part: the block is read, the data is put in place
+    /// (the last word padded with zeros) and the whole block is written back.
+    /// Both commands are sent under a single lock of the API, nothing else
+    /// can be written to the block in between. Requires the data zone to be locked,
+    /// 'block_offset' and the size of 'data' are checked by the caller.
+    fn merge_slot_block(
+        &self,
+        slot: u16,
+        block: u8,
+        block_offset: usize,
+        data: &[u8],
+    ) -> AtcaStatus {
+        const OFFSET: u8 = 0;
+
+        let words_len = data.len().div_ceil(ATCA_WORD_SIZE) * ATCA_WORD_SIZE;
+        let mut data_block: [u8; ATCA_BLOCK_SIZE] = [0; ATCA_BLOCK_SIZE];
+
+        let _guard = match self.api_mutex.lock() {
+            Ok(guard) => guard,
+            Err(err) => return err,
+        };
+        let result = AtcaStatus::from(unsafe {
+            cryptoauthlib_sys::atcab_read_zone(
+                ATCA_ZONE_DATA,
+                slot,
+                block,
+                OFFSET,
+                data_block.as_mut_ptr(),
+                ATCA_BLOCK_SIZE as u8,
+            )
+        });
+        if AtcaStatus::AtcaSuccess != result {
+            return result;
+        }
+        data_block[block_offset..(block_offset + words_len)].fill(0);
+        data_block[block_offset..(block_offset + data.len())].clone_from_slice(data);
+        AtcaStatus::from(unsafe {
+            cryptoauthlib_sys::atcab_write_zone(
+                ATCA_ZONE_DATA,
+                slot,
+                block,
+                OFFSET,
+                data_block.as_ptr(),
+                ATCA_BLOCK_SIZE as u8,
+            )
+        })
+    } // AteccDevice::merge_slot_block()
+
+    /// Function that reads data from a single block of the given slot.
+    /// A whole block is read with one command, either in clear text or encrypted,
+    /// unless it is the last, shorter block of a slot - then it is read word by word.
+    fn read_slot_block(
+        &self,
+        slot_id: u8,
+        block: u8,
+        block_offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let slot = slot_id as u16;
+        let slot_data = self.slot_config(slot_id)?;
+        let capacity = self.get_slot_capacity(slot_id);
+        if block >= capacity.blocks {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+        let block_len = match block == (capacity.blocks - 1) {
+            true => capacity.last_block_bytes as usize,
+            false => ATCA_BLOCK_SIZE,
+        };
+        if (block_offset + len) > block_len {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        if slot_data.is_secret && slot_data.read_key.encrypt_read {
+            let num_in = self.host_nonce()?;
+            let mut data_block: [u8; ATCA_BLOCK_SIZE] = [0; ATCA_BLOCK_SIZE];
+            let result = self.read_slot_with_encryption(slot, block, &mut data_block, &num_in);
+            if AtcaStatus::AtcaSuccess != result {
+                return Err(result);
+            }
+            return Ok(data_block[block_offset..(block_offset + len)].to_vec());
+        }
+
+        let mut data_block: Vec<u8> = Vec::new();
+        if ATCA_BLOCK_SIZE == block_len {
+            const OFFSET: u8 = 0;
+            let result = self.read_zone(
+                ATCA_ZONE_DATA,
+                slot,
+                block,
+                OFFSET,
+                &mut data_block,
+                ATCA_BLOCK_SIZE as u8,
+            );
+            if AtcaStatus::AtcaSuccess != result {
+                return Err(result);
+            }
+            return Ok(data_block[block_offset..(block_offset + len)].to_vec());
+        }
+
+        let first_word = block_offset / ATCA_WORD_SIZE;
+        let last_word = (block_offset + len).div_ceil(ATCA_WORD_SIZE);
+        for word_idx in first_word..last_word {
+            let mut word: Vec<u8> = Vec::new();
+            let result = self.read_zone(
+                ATCA_ZONE_DATA,
+                slot,
+                block,
+                word_idx as u8,
+                &mut word,
+                ATCA_WORD_SIZE as u8,
+            );
+            if AtcaStatus::AtcaSuccess != result {
+                return Err(result);
+            }
+            data_block.extend_from_slice(&word);
+        }
+        let start = block_offset - (first_word * ATCA_WORD_SIZE);
+        Ok(data_block[start..(start + len)].to_vec())
+    } // AteccDevice::read_slot_block()
+
+    /// A helper function that returns the smallest amount of data (in bytes)
+    /// that can be written to the given slot with a single Write command
+    fn get_slot_write_granularity(&self, slot_id: u8) -> Result<usize, AtcaStatus> {
+        let slot_data = self.slot_config(slot_id)?;
+        match slot_data.write_config {
+            WriteConfig::Always if !slot_data.is_secret => Ok(ATCA_WORD_SIZE),
+            WriteConfig::Always | WriteConfig::Encrypt => Ok(ATCA_BLOCK_SIZE),
+            _ => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // AteccDevice::get_slot_write_granularity()
+
     /// Generic function that writes encrypted data to the chip
     fn write_slot_with_encryption(
         &self,
@@ -1606,15 +3491,11 @@ impl AteccDevice {
         }
 
         if let Some(write_key_idx) = self.get_write_key_idx(slot as u8) {
-            let mut write_key = vec![0; ATCA_KEY_SIZE];
+            let mut write_key = Zeroizing::new(vec![0; ATCA_KEY_SIZE]);
             let result = self.get_access_key(write_key_idx, &mut write_key);
 
             if AtcaStatus::AtcaSuccess == result {
-                AtcaStatus::from(unsafe {
-                    let _guard = self
-                        .api_mutex
-                        .lock()
-                        .expect("Could not lock atcab API mutex");
+                self.atcab_call(|| unsafe {
                     cryptoauthlib_sys::atcab_write_enc(
                         slot,
                         block,
@@ -1666,9 +3547,14 @@ fn atcab_get_key_type(data: u8) -> KeyType {
 }
 
 pub fn atcab_get_config_from_config_zone(config_data: &[u8], atca_slots: &mut Vec<AtcaSlot>) {
+    const IDX_COUNT_MATCH: usize = 18;
     const IDX_SLOT_LOCKED: usize = 88;
     const IDX_SLOT_CONFIG: usize = 20;
     const IDX_KEY_CONFIG: usize = 96;
+    let count_match_key = match atcab_get_bit_value(config_data[IDX_COUNT_MATCH], 0) {
+        true => Some(config_data[IDX_COUNT_MATCH] >> 4),
+        false => None,
+    };
     for idx in 0..ATCA_ATECC_SLOTS_COUNT {
         let slot_cfg_pos = IDX_SLOT_CONFIG + (idx * 2) as usize;
         let key_cfg_pos = IDX_KEY_CONFIG + (idx * 2) as usize;
@@ -1693,6 +3579,7 @@ pub fn atcab_get_config_from_config_zone(config_data: &[u8], atca_slots: &mut Ve
             write_key: config_data[slot_cfg_pos + 1] & 0b00001111,
             is_secret: atcab_get_bit_value(config_data[slot_cfg_pos], 7),
             limited_use: atcab_get_bit_value(config_data[slot_cfg_pos], 5),
+            count_match: count_match_key == Some(idx),
             no_mac: atcab_get_bit_value(config_data[slot_cfg_pos], 4),
             persistent_disable: atcab_get_bit_value(config_data[key_cfg_pos + 1], 4),
             req_auth: atcab_get_bit_value(config_data[key_cfg_pos], 7),
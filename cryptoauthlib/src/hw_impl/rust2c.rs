@@ -17,19 +17,159 @@ impl TryFrom<super::AtcaIfaceCfg> for cryptoauthlib_sys::ATCAIfaceCfg {
                     None => return Err(()),
                 },
             },
+            super::AtcaIfaceType::AtcaSwiIface => cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1 {
+                atcaswi: match rust_iface_cfg.iface {
+                    Some(x) => cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_2 {
+                        bus: unsafe { x.atcaswi.bus },
+                    },
+                    None => return Err(()),
+                },
+            },
+            super::AtcaIfaceType::AtcaHidIface => cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1 {
+                atcahid: match rust_iface_cfg.iface {
+                    Some(x) => {
+                        let hid = unsafe { x.atcahid };
+                        cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_4 {
+                            idx: hid.idx,
+                            dev_interface: cryptoauthlib_sys::ATCAKitType::from(hid.dev_interface),
+                            dev_identity: hid.dev_identity,
+                            vid: hid.vid,
+                            pid: hid.pid,
+                            packetsize: hid.packetsize,
+                        }
+                    }
+                    None => return Err(()),
+                },
+            },
+            super::AtcaIfaceType::AtcaUartIface => cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1 {
+                atcauart: match rust_iface_cfg.iface {
+                    Some(x) => {
+                        let uart = unsafe { x.atcauart };
+                        cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_3 {
+                            port: uart.port,
+                            baud: uart.baud,
+                            wordsize: uart.wordsize,
+                            parity: match uart.parity {
+                                super::AtcaUartParity::Even => 0,
+                                super::AtcaUartParity::Odd => 1,
+                                super::AtcaUartParity::None => 2,
+                            },
+                            stopbits: uart.stopbits,
+                        }
+                    }
+                    None => return Err(()),
+                },
+            },
+            #[cfg(feature = "i2c-rust")]
+            super::AtcaIfaceType::AtcaI2cRustIface => match rust_iface_cfg.iface {
+                Some(_) => cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1 {
+                    atcacustom: super::i2c_rust::custom_iface(),
+                },
+                None => return Err(()),
+            },
+            super::AtcaIfaceType::AtcaCustomIface => {
+                cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1 {
+                    atcacustom: super::transport::custom_iface(),
+                }
+            }
             _ => return Err(()),
         }; // match rust_iface_cfg.iface_type
         Ok(cryptoauthlib_sys::ATCAIfaceCfg {
             iface_type: cryptoauthlib_sys::ATCAIfaceType::from(rust_iface_cfg.iface_type),
             devtype: cryptoauthlib_sys::ATCADeviceType::from(rust_iface_cfg.devtype),
             __bindgen_anon_1: atca_iface,
-            wake_delay: rust_iface_cfg.wake_delay,
+            wake_delay: match (rust_iface_cfg.iface_type, rust_iface_cfg.wake_delay) {
+                (super::AtcaIfaceType::AtcaSwiIface, 0) => super::ATCA_SWI_WAKE_DELAY,
+                (_, wake_delay) => wake_delay,
+            },
             rx_retries: rust_iface_cfg.rx_retries,
             cfg_data: std::ptr::null_mut(),
         }) // return Some
     } // pub fn r2c_atca_iface_cfg
 }
 
+impl From<super::AtcaStatus> for cryptoauthlib_sys::ATCA_STATUS {
+    fn from(atca_status: super::AtcaStatus) -> Self {
+        match atca_status {
+            super::AtcaStatus::AtcaSuccess => cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS,
+            super::AtcaStatus::AtcaConfigZoneLocked => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_CONFIG_ZONE_LOCKED
+            }
+            super::AtcaStatus::AtcaDataZoneLocked => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_DATA_ZONE_LOCKED
+            }
+            super::AtcaStatus::AtcaWakeFailed => cryptoauthlib_sys::ATCA_STATUS_ATCA_WAKE_FAILED,
+            super::AtcaStatus::AtcaCheckMacVerifyFailed => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_CHECKMAC_VERIFY_FAILED
+            }
+            super::AtcaStatus::AtcaParseError => cryptoauthlib_sys::ATCA_STATUS_ATCA_PARSE_ERROR,
+            super::AtcaStatus::AtcaStatusCrc => cryptoauthlib_sys::ATCA_STATUS_ATCA_STATUS_CRC,
+            super::AtcaStatus::AtcaStatusUnknown => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_STATUS_UNKNOWN
+            }
+            super::AtcaStatus::AtcaStatusEcc => cryptoauthlib_sys::ATCA_STATUS_ATCA_STATUS_ECC,
+            super::AtcaStatus::AtcaStatusSelftestError => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_STATUS_SELFTEST_ERROR
+            }
+            super::AtcaStatus::AtcaFuncFail => cryptoauthlib_sys::ATCA_STATUS_ATCA_FUNC_FAIL,
+            super::AtcaStatus::AtcaGenFail => cryptoauthlib_sys::ATCA_STATUS_ATCA_GEN_FAIL,
+            super::AtcaStatus::AtcaBadParam => cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM,
+            super::AtcaStatus::AtcaInvalidId => cryptoauthlib_sys::ATCA_STATUS_ATCA_INVALID_ID,
+            super::AtcaStatus::AtcaInvalidSize => cryptoauthlib_sys::ATCA_STATUS_ATCA_INVALID_SIZE,
+            super::AtcaStatus::AtcaRxCrcError => cryptoauthlib_sys::ATCA_STATUS_ATCA_RX_CRC_ERROR,
+            super::AtcaStatus::AtcaRxFail => cryptoauthlib_sys::ATCA_STATUS_ATCA_RX_FAIL,
+            super::AtcaStatus::AtcaRxNoResponse => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_RX_NO_RESPONSE
+            }
+            super::AtcaStatus::AtcaResyncWithWakeup => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_RESYNC_WITH_WAKEUP
+            }
+            super::AtcaStatus::AtcaParityError => cryptoauthlib_sys::ATCA_STATUS_ATCA_PARITY_ERROR,
+            super::AtcaStatus::AtcaTxTimeout => cryptoauthlib_sys::ATCA_STATUS_ATCA_TX_TIMEOUT,
+            super::AtcaStatus::AtcaRxTimeout => cryptoauthlib_sys::ATCA_STATUS_ATCA_RX_TIMEOUT,
+            super::AtcaStatus::AtcaTooManyCommRetries => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_TOO_MANY_COMM_RETRIES
+            }
+            super::AtcaStatus::AtcaSmallBuffer => cryptoauthlib_sys::ATCA_STATUS_ATCA_SMALL_BUFFER,
+            super::AtcaStatus::AtcaCommFail => cryptoauthlib_sys::ATCA_STATUS_ATCA_COMM_FAIL,
+            super::AtcaStatus::AtcaTimeout => cryptoauthlib_sys::ATCA_STATUS_ATCA_TIMEOUT,
+            super::AtcaStatus::AtcaBadOpcode => cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_OPCODE,
+            super::AtcaStatus::AtcaWakeSuccess => cryptoauthlib_sys::ATCA_STATUS_ATCA_WAKE_SUCCESS,
+            super::AtcaStatus::AtcaExecutionError => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_EXECUTION_ERROR
+            }
+            super::AtcaStatus::AtcaUnimplemented => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_UNIMPLEMENTED
+            }
+            super::AtcaStatus::AtcaAssertFailure => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_ASSERT_FAILURE
+            }
+            super::AtcaStatus::AtcaTxFail => cryptoauthlib_sys::ATCA_STATUS_ATCA_TX_FAIL,
+            super::AtcaStatus::AtcaNotLocked => cryptoauthlib_sys::ATCA_STATUS_ATCA_NOT_LOCKED,
+            super::AtcaStatus::AtcaNoDevices => cryptoauthlib_sys::ATCA_STATUS_ATCA_NO_DEVICES,
+            super::AtcaStatus::AtcaHealthTestError => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_HEALTH_TEST_ERROR
+            }
+            super::AtcaStatus::AtcaAllocFailure => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_ALLOC_FAILURE
+            }
+            super::AtcaStatus::AtcaUseFlagsConsumed => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_USE_FLAGS_CONSUMED
+            }
+            super::AtcaStatus::AtcaUnknown | super::AtcaStatus::AtcaCancelled => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_GEN_FAIL
+            }
+            super::AtcaStatus::AtcaWatchdogExpired => cryptoauthlib_sys::ATCA_STATUS_ATCA_TIMEOUT,
+            super::AtcaStatus::AtcaKeyUsesExhausted => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_EXECUTION_ERROR
+            }
+            super::AtcaStatus::AtcaUnsupportedByDevice => {
+                cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_OPCODE
+            }
+        }
+    }
+}
+
 impl From<super::AtcaIfaceType> for cryptoauthlib_sys::ATCAIfaceType {
     fn from(rust_iface_type: super::AtcaIfaceType) -> Self {
         match rust_iface_type {
@@ -38,7 +178,7 @@ impl From<super::AtcaIfaceType> for cryptoauthlib_sys::ATCAIfaceType {
             super::AtcaIfaceType::AtcaUartIface => cryptoauthlib_sys::ATCAIfaceType_ATCA_UART_IFACE,
             super::AtcaIfaceType::AtcaSpiIface => cryptoauthlib_sys::ATCAIfaceType_ATCA_SPI_IFACE,
             super::AtcaIfaceType::AtcaHidIface => cryptoauthlib_sys::ATCAIfaceType_ATCA_HID_IFACE,
-            super::AtcaIfaceType::AtcaCustomIface => {
+            super::AtcaIfaceType::AtcaCustomIface | super::AtcaIfaceType::AtcaI2cRustIface => {
                 cryptoauthlib_sys::ATCAIfaceType_ATCA_CUSTOM_IFACE
             }
             _ => cryptoauthlib_sys::ATCAIfaceType_ATCA_UNKNOWN_IFACE,
@@ -46,6 +186,22 @@ impl From<super::AtcaIfaceType> for cryptoauthlib_sys::ATCAIfaceType {
     }
 }
 
+impl From<super::AtcaKitType> for cryptoauthlib_sys::ATCAKitType {
+    fn from(rust_kit_type: super::AtcaKitType) -> Self {
+        match rust_kit_type {
+            super::AtcaKitType::AtcaKitAutoIface => {
+                cryptoauthlib_sys::ATCAKitType_ATCA_KIT_AUTO_IFACE
+            }
+            super::AtcaKitType::AtcaKitI2cIface => {
+                cryptoauthlib_sys::ATCAKitType_ATCA_KIT_I2C_IFACE
+            }
+            super::AtcaKitType::AtcaKitSwiIface => {
+                cryptoauthlib_sys::ATCAKitType_ATCA_KIT_SWI_IFACE
+            }
+        }
+    }
+}
+
 impl From<super::AtcaDeviceType> for cryptoauthlib_sys::ATCADeviceType {
     fn from(rust_iface_devtype: super::AtcaDeviceType) -> Self {
         match rust_iface_devtype {
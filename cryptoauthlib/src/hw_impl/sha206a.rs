@@ -0,0 +1,36 @@
+// ATSHA206A is a two-key authentication chip for consumables: MAC computes the response
+// to a challenge with a key stored in the chip.
+// It has no random number generator, no SHA engine, no ECC and no AES,
+// so most of AteccDeviceTrait is refused before anything is sent to the chip.
+
+use super::trace::Command;
+use super::{AtcaDeviceType, AteccDevice};
+
+impl AteccDevice {
+    /// Tells whether the chip can execute the operation of AteccDeviceTrait
+    pub(super) fn is_supported_by_device(&self, command: Command) -> bool {
+        match self.get_device_type() {
+            AtcaDeviceType::ATSHA206A => matches!(
+                command,
+                Command::GenDig
+                    | Command::Idle
+                    | Command::InfoCmd
+                    | Command::LockConfigZone
+                    | Command::LockDataZone
+                    | Command::Mac
+                    | Command::Nonce
+                    | Command::ReadConfigZone
+                    | Command::ReadSlotData
+                    | Command::Reconnect
+                    | Command::Refresh
+                    | Command::RefreshLockState
+                    | Command::Release
+                    | Command::Sleep
+                    | Command::Wake
+                    | Command::WriteConfigZone
+                    | Command::WriteSlotData
+            ),
+            _ => true,
+        }
+    } // AteccDevice::is_supported_by_device()
+}
@@ -0,0 +1,98 @@
+// Host-side tracking of TempKey. The chip does not tell what TempKey holds,
+// so the trait implementation records what the commands it executes do with it
+// and tempkey_state() can tell why a command relying on TempKey has failed.
+
+use super::trace::CommandStatus;
+use super::{AtcaDeviceType, AtcaPowerPolicy, AtcaStatus, AteccDevice, DigestBuffer};
+use super::{TempKeySource, TempKeyState, ATCA_ATECC_SLOTS_COUNT, ATCA_NONCE_SIZE};
+
+impl AteccDevice {
+    /// What the host knows about TempKey
+    pub(super) fn tempkey_state(&self) -> TempKeyState {
+        *self
+            .temp_key
+            .lock()
+            .expect("Could not lock 'temp_key' mutex")
+    } // AteccDevice::tempkey_state()
+
+    /// Records that a command has loaded TempKey, if it has succeeded.
+    /// With AtcaPowerPolicy::Sleep the chip is put to sleep after every command,
+    /// so TempKey is lost right away
+    pub(super) fn temp_key_loaded<T: CommandStatus>(
+        &self,
+        result: T,
+        source: TempKeySource,
+        size: usize,
+    ) -> T {
+        if AtcaStatus::AtcaSuccess == result.status() {
+            let mut temp_key = self
+                .temp_key
+                .lock()
+                .expect("Could not lock 'temp_key' mutex");
+            temp_key.load(source, size);
+            if AtcaPowerPolicy::Sleep == self.api_mutex.power_policy {
+                temp_key.invalidate("sleep");
+            }
+        }
+        result
+    } // AteccDevice::temp_key_loaded()
+
+    /// Records that an AES operation using TempKey (slot ATCA_ATECC_SLOTS_COUNT) has loaded
+    /// the key given with it, if any
+    pub(super) fn temp_key_loaded_by_aes<T: CommandStatus>(
+        &self,
+        result: T,
+        slot_id: u8,
+        key_given: bool,
+    ) -> T {
+        match (ATCA_ATECC_SLOTS_COUNT == slot_id) && key_given {
+            true => self.temp_key_loaded(result, TempKeySource::Nonce, ATCA_NONCE_SIZE),
+            false => result,
+        }
+    } // AteccDevice::temp_key_loaded_by_aes()
+
+    /// Records that a command has invalidated or overwritten TempKey, if it has succeeded
+    pub(super) fn temp_key_lost<T: CommandStatus>(&self, result: T, command: &'static str) -> T {
+        if AtcaStatus::AtcaSuccess == result.status() {
+            self.invalidate_temp_key(command);
+        }
+        result
+    } // AteccDevice::temp_key_lost()
+
+    /// As temp_key_lost(), for the commands that pass their data through TempKey
+    /// on the chips older than ATECC608x: the message of Sign and Verify, which ATECC608x
+    /// loads into the Message Digest Buffer, and the context of SHA, which it keeps apart
+    pub(super) fn temp_key_lost_before_608<T: CommandStatus>(
+        &self,
+        result: T,
+        command: &'static str,
+    ) -> T {
+        match self.get_device_type() {
+            AtcaDeviceType::ATECC608A => result,
+            _ => self.temp_key_lost(result, command),
+        }
+    } // AteccDevice::temp_key_lost_before_608()
+
+    /// As temp_key_lost_before_608(), for Sign and Verify with the buffer of the hash
+    /// chosen by the caller: TempKey is overwritten on every chip if it is chosen
+    pub(super) fn temp_key_lost_to_digest<T: CommandStatus>(
+        &self,
+        result: T,
+        buffer: Option<DigestBuffer>,
+        command: &'static str,
+    ) -> T {
+        match buffer {
+            Some(DigestBuffer::TempKey) => self.temp_key_lost(result, command),
+            Some(DigestBuffer::MsgDigBuf) => result,
+            None => self.temp_key_lost_before_608(result, command),
+        }
+    } // AteccDevice::temp_key_lost_to_digest()
+
+    /// Records that TempKey has been lost, whatever the result of the command
+    pub(super) fn invalidate_temp_key(&self, command: &'static str) {
+        self.temp_key
+            .lock()
+            .expect("Could not lock 'temp_key' mutex")
+            .invalidate(command);
+    } // AteccDevice::invalidate_temp_key()
+}
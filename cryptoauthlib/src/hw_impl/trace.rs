@@ -0,0 +1,265 @@
+// Log records and metrics of the commands executed by the chip, so that failures
+// in the field can be diagnosed without instrumenting CryptoAuthLib itself.
+
+use std::fmt;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use log::{log, log_enabled, Level};
+
+#[cfg(feature = "std")]
+use super::super::metrics;
+use super::{AtcaStatus, AteccDevice};
+
+/// Result of a command, telling the status it has completed with
+pub(super) trait CommandStatus {
+    fn status(&self) -> AtcaStatus;
+    /// Result of a command that has failed with the given status
+    fn failed(status: AtcaStatus) -> Self;
+}
+
+impl CommandStatus for AtcaStatus {
+    fn status(&self) -> AtcaStatus {
+        *self
+    }
+
+    fn failed(status: AtcaStatus) -> Self {
+        status
+    }
+}
+
+impl<T> CommandStatus for Result<T, AtcaStatus> {
+    fn status(&self) -> AtcaStatus {
+        match self {
+            Ok(_) => AtcaStatus::AtcaSuccess,
+            Err(err) => *err,
+        }
+    }
+
+    fn failed(status: AtcaStatus) -> Self {
+        Err(status)
+    }
+}
+
+/// Operation of AteccDeviceTrait executed through traced(), named after its function
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(super) enum Command {
+    Random,
+    RandomInto,
+    Sha,
+    ShaInto,
+    ShaStart,
+    ShaUpdate,
+    ShaFinish,
+    ShaHmac,
+    Nonce,
+    NonceRand,
+    GenDig,
+    Mac,
+    CheckMac,
+    CounterRead,
+    CounterIncrement,
+    GenKey,
+    ImportKey,
+    ImportPrivateKeyDer,
+    ImportPrivateKeyPem,
+    ExportKey,
+    WriteSlotData,
+    ReadSlotData,
+    WriteOtpZone,
+    ReadOtpZone,
+    GetPublicKey,
+    SignHash,
+    SignHashInto,
+    SignMessage,
+    SignBase,
+    VerifyHash,
+    SignHashFormatted,
+    VerifyHashFormatted,
+    Ecdh,
+    Kdf,
+    OpenIoProtectionSession,
+    EcdhIoProtected,
+    VerifyHashIoProtected,
+    ImportAesKeyIoProtected,
+    CipherEncrypt,
+    CipherDecrypt,
+    AeadEncrypt,
+    AeadDecrypt,
+    GcmInit,
+    GcmUpdateAad,
+    GcmEncryptUpdate,
+    GcmDecryptUpdate,
+    GcmEncryptFinish,
+    GcmDecryptFinish,
+    CcmInit,
+    CcmUpdateAad,
+    CcmEncryptUpdate,
+    CcmDecryptUpdate,
+    CcmEncryptFinish,
+    CcmDecryptFinish,
+    TlsDeriveKeys,
+    TlsVerifyData,
+    EciesEncrypt,
+    EciesDecrypt,
+    WrapKey,
+    UnwrapKey,
+    ReadConfigZone,
+    WriteConfigZone,
+    LockConfigZone,
+    LockDataZone,
+    InfoCmd,
+    AesDecryptBlock,
+    SelfTest,
+    Wake,
+    Idle,
+    Sleep,
+    Reconnect,
+    Refresh,
+    RefreshLockState,
+    Release,
+}
+
+impl Command {
+    /// Name of the function, as printed in the log records and passed to the metrics sink
+    fn name(self) -> &'static str {
+        match self {
+            Command::Random => "random",
+            Command::RandomInto => "random_into",
+            Command::Sha => "sha",
+            Command::ShaInto => "sha_into",
+            Command::ShaStart => "sha_start",
+            Command::ShaUpdate => "sha_update",
+            Command::ShaFinish => "sha_finish",
+            Command::ShaHmac => "sha_hmac",
+            Command::Nonce => "nonce",
+            Command::NonceRand => "nonce_rand",
+            Command::GenDig => "gen_dig",
+            Command::Mac => "mac",
+            Command::CheckMac => "check_mac",
+            Command::CounterRead => "counter_read",
+            Command::CounterIncrement => "counter_increment",
+            Command::GenKey => "gen_key",
+            Command::ImportKey => "import_key",
+            Command::ImportPrivateKeyDer => "import_private_key_der",
+            Command::ImportPrivateKeyPem => "import_private_key_pem",
+            Command::ExportKey => "export_key",
+            Command::WriteSlotData => "write_slot_data",
+            Command::ReadSlotData => "read_slot_data",
+            Command::WriteOtpZone => "write_otp_zone",
+            Command::ReadOtpZone => "read_otp_zone",
+            Command::GetPublicKey => "get_public_key",
+            Command::SignHash => "sign_hash",
+            Command::SignHashInto => "sign_hash_into",
+            Command::SignMessage => "sign_message",
+            Command::SignBase => "sign_base",
+            Command::VerifyHash => "verify_hash",
+            Command::SignHashFormatted => "sign_hash_formatted",
+            Command::VerifyHashFormatted => "verify_hash_formatted",
+            Command::Ecdh => "ecdh",
+            Command::Kdf => "kdf",
+            Command::OpenIoProtectionSession => "open_io_protection_session",
+            Command::EcdhIoProtected => "ecdh_io_protected",
+            Command::VerifyHashIoProtected => "verify_hash_io_protected",
+            Command::ImportAesKeyIoProtected => "import_aes_key_io_protected",
+            Command::CipherEncrypt => "cipher_encrypt",
+            Command::CipherDecrypt => "cipher_decrypt",
+            Command::AeadEncrypt => "aead_encrypt",
+            Command::AeadDecrypt => "aead_decrypt",
+            Command::GcmInit => "gcm_init",
+            Command::GcmUpdateAad => "gcm_update_aad",
+            Command::GcmEncryptUpdate => "gcm_encrypt_update",
+            Command::GcmDecryptUpdate => "gcm_decrypt_update",
+            Command::GcmEncryptFinish => "gcm_encrypt_finish",
+            Command::GcmDecryptFinish => "gcm_decrypt_finish",
+            Command::CcmInit => "ccm_init",
+            Command::CcmUpdateAad => "ccm_update_aad",
+            Command::CcmEncryptUpdate => "ccm_encrypt_update",
+            Command::CcmDecryptUpdate => "ccm_decrypt_update",
+            Command::CcmEncryptFinish => "ccm_encrypt_finish",
+            Command::CcmDecryptFinish => "ccm_decrypt_finish",
+            Command::TlsDeriveKeys => "tls_derive_keys",
+            Command::TlsVerifyData => "tls_verify_data",
+            Command::EciesEncrypt => "ecies_encrypt",
+            Command::EciesDecrypt => "ecies_decrypt",
+            Command::WrapKey => "wrap_key",
+            Command::UnwrapKey => "unwrap_key",
+            Command::ReadConfigZone => "read_config_zone",
+            Command::WriteConfigZone => "write_config_zone",
+            Command::LockConfigZone => "lock_config_zone",
+            Command::LockDataZone => "lock_data_zone",
+            Command::InfoCmd => "info_cmd",
+            Command::AesDecryptBlock => "aes_decrypt_block",
+            Command::SelfTest => "self_test",
+            Command::Wake => "wake",
+            Command::Idle => "idle",
+            Command::Sleep => "sleep",
+            Command::Reconnect => "reconnect",
+            Command::Refresh => "refresh",
+            Command::RefreshLockState => "refresh_lock_state",
+            Command::Release => "release",
+        }
+    }
+}
+
+/// Slot a command uses, if any, as printed in the log records
+struct SlotLabel(Option<u16>);
+
+impl fmt::Display for SlotLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(slot_id) => write!(f, " (slot {})", slot_id),
+            None => Ok(()),
+        }
+    }
+}
+
+impl AteccDevice {
+    /// Executes a command and logs its name, the slot it uses, its duration (with std)
+    /// and its status. Failed commands are logged with the 'warn' level, the other ones
+    /// with the 'debug' level. With std the command is also passed to the metrics sink.
+    /// The status is counted in to tell if the chip has been disconnected.
+    /// A command the chip cannot execute is not executed at all
+    pub(super) fn traced<T, F>(&self, command: Command, slot_id: Option<u16>, execute: F) -> T
+    where
+        T: CommandStatus,
+        F: FnOnce() -> T,
+    {
+        #[cfg(feature = "std")]
+        let start = Instant::now();
+        let result = match self.is_supported_by_device(command) {
+            true => execute(),
+            false => T::failed(AtcaStatus::AtcaUnsupportedByDevice),
+        };
+        let status = result.status();
+        #[cfg(feature = "std")]
+        let latency = start.elapsed();
+        #[cfg(feature = "std")]
+        metrics::record_command(command.name(), status, latency);
+        self.count_comm_failure(status);
+        let level = match status {
+            AtcaStatus::AtcaSuccess => Level::Debug,
+            _ => Level::Warn,
+        };
+        if log_enabled!(level) {
+            #[cfg(feature = "std")]
+            log!(
+                level,
+                "ATECC {}{}: {} in {:?}",
+                command.name(),
+                SlotLabel(slot_id),
+                status,
+                latency
+            );
+            #[cfg(not(feature = "std"))]
+            log!(
+                level,
+                "ATECC {}{}: {}",
+                command.name(),
+                SlotLabel(slot_id),
+                status
+            );
+        }
+        result
+    } // AteccDevice::traced()
+}
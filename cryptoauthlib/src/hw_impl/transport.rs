@@ -0,0 +1,142 @@
+// CryptoAuth protocol carried by a bus implemented outside of the crate.
+// The functions are registered with CryptoAuthLib as a custom HAL
+// (ATCA_CUSTOM_IFACE) and forward each call to the registered Transport.
+
+use alloc::boxed::Box;
+use std::ffi::{c_int, c_void};
+
+use cryptoauthlib_sys::ATCA_STATUS;
+
+use super::super::sync::Mutex;
+use super::AtcaStatus;
+
+/// A bus the chip is attached to, for buses that CryptoAuthLib has no HAL for:
+/// I2C-over-USB bridges, FPGA softcores, multiplexed buses...
+/// It is registered with setup_atecc_device_with_transport() and is used by the device
+/// until the device is released. The commands are serialized, so a transport is called
+/// by a single thread at a time.
+pub trait Transport: Send {
+    /// Sends a command packet to the chip: the count byte, the opcode, the parameters,
+    /// the data and the CRC. A bus that needs a word address, such as I2C,
+    /// prepends it itself
+    fn send(&mut self, packet: &[u8]) -> AtcaStatus;
+    /// Receives a response of the chip into the buffer and returns its length.
+    /// The response starts with its count byte, a transport polls the chip
+    /// as long as it is busy executing the command
+    fn receive(&mut self, buffer: &mut [u8]) -> Result<usize, AtcaStatus>;
+    /// Wakes the chip up and checks its wake response
+    fn wake(&mut self) -> AtcaStatus;
+    /// Puts the chip into the idle mode, TempKey and the other volatile state is kept
+    fn idle(&mut self) -> AtcaStatus;
+    /// Puts the chip into the sleep mode, the volatile state is lost
+    fn sleep(&mut self) -> AtcaStatus;
+}
+
+/// The transport of the device, the HAL supports one device at a time
+/// as does the Rust I2C HAL
+static TRANSPORT: Mutex<Option<Box<dyn Transport>>> = Mutex::new(None);
+
+/// Stores the transport used by the HAL, unless there is one used by another device
+pub(crate) fn register(transport: Box<dyn Transport>) -> bool {
+    let mut registered = TRANSPORT.lock().expect("Could not lock transport mutex");
+    if registered.is_some() {
+        return false;
+    }
+    *registered = Some(transport);
+    true
+} // register()
+
+/// Drops the transport, e.g. when the device using it failed to initialize
+pub(crate) fn unregister() {
+    let mut registered = TRANSPORT.lock().expect("Could not lock transport mutex");
+    *registered = None;
+} // unregister()
+
+/// The set of HAL functions to be placed in the 'atcacustom' interface configuration
+pub(crate) fn custom_iface() -> cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5 {
+    cryptoauthlib_sys::ATCAIfaceCfg__bindgen_ty_1__bindgen_ty_5 {
+        halinit: Some(hal_init),
+        halpostinit: Some(hal_post_init),
+        halsend: Some(hal_send),
+        halreceive: Some(hal_receive),
+        halwake: Some(hal_wake),
+        halidle: Some(hal_idle),
+        halsleep: Some(hal_sleep),
+        halrelease: Some(hal_release),
+    }
+} // custom_iface()
+
+/// Runs an operation on the registered transport
+fn with_transport<F>(operation: F) -> ATCA_STATUS
+where
+    F: FnOnce(&mut dyn Transport) -> AtcaStatus,
+{
+    let mut registered = match TRANSPORT.lock() {
+        Ok(registered) => registered,
+        Err(_) => return cryptoauthlib_sys::ATCA_STATUS_ATCA_FUNC_FAIL,
+    };
+    match registered.as_mut() {
+        Some(transport) => operation(transport.as_mut()).into(),
+        None => cryptoauthlib_sys::ATCA_STATUS_ATCA_COMM_FAIL,
+    }
+}
+
+unsafe extern "C" fn hal_init(_hal: *mut c_void, _cfg: *mut c_void) -> ATCA_STATUS {
+    with_transport(|_| AtcaStatus::AtcaSuccess)
+}
+
+unsafe extern "C" fn hal_post_init(_iface: *mut c_void) -> ATCA_STATUS {
+    cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS
+}
+
+/// Sends a command packet. Its first byte is reserved by CryptoAuthLib
+/// for the word address, 'txlength' does not count it.
+unsafe extern "C" fn hal_send(
+    _iface: *mut c_void,
+    txdata: *mut u8,
+    txlength: c_int,
+) -> ATCA_STATUS {
+    if txdata.is_null() || txlength < 0 {
+        return cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM;
+    }
+    let packet = std::slice::from_raw_parts(txdata.add(1), txlength as usize);
+    with_transport(|transport| transport.send(packet))
+}
+
+/// Receives a response, 'rxlength' is the size of the buffer on input
+/// and the length of the response on output
+unsafe extern "C" fn hal_receive(
+    _iface: *mut c_void,
+    rxdata: *mut u8,
+    rxlength: *mut u16,
+) -> ATCA_STATUS {
+    if rxdata.is_null() || rxlength.is_null() || *rxlength < 1 {
+        return cryptoauthlib_sys::ATCA_STATUS_ATCA_BAD_PARAM;
+    }
+    let buffer = std::slice::from_raw_parts_mut(rxdata, *rxlength as usize);
+    with_transport(|transport| match transport.receive(buffer) {
+        Ok(count) if count > buffer.len() => AtcaStatus::AtcaSmallBuffer,
+        Ok(count) => {
+            *rxlength = count as u16;
+            AtcaStatus::AtcaSuccess
+        }
+        Err(err) => err,
+    })
+}
+
+unsafe extern "C" fn hal_wake(_iface: *mut c_void) -> ATCA_STATUS {
+    with_transport(|transport| transport.wake())
+}
+
+unsafe extern "C" fn hal_idle(_iface: *mut c_void) -> ATCA_STATUS {
+    with_transport(|transport| transport.idle())
+}
+
+unsafe extern "C" fn hal_sleep(_iface: *mut c_void) -> ATCA_STATUS {
+    with_transport(|transport| transport.sleep())
+}
+
+unsafe extern "C" fn hal_release(_hal_data: *mut c_void) -> ATCA_STATUS {
+    unregister();
+    cryptoauthlib_sys::ATCA_STATUS_ATCA_SUCCESS
+}
@@ -0,0 +1,53 @@
+// Long commands of the ATECC608 (GenKey, Sign, Verify, ECDH) take a good part of the
+// watchdog window, which runs from the wake-up of the chip (about 1.3 s by default).
+// When the watchdog expires the chip goes to sleep in the middle of a command,
+// and CryptoAuthLib only sees a response that never comes.
+
+use super::trace::CommandStatus;
+use super::{AtcaStatus, AteccDevice};
+
+/// How many times a command is executed again after the watchdog has expired
+const WATCHDOG_RETRIES: usize = 1;
+
+/// Statuses reported when the chip has gone to sleep: there is no response at all,
+/// or the chip has been woken up by the command itself and responds with the wake token
+fn is_watchdog_symptom(status: AtcaStatus) -> bool {
+    matches!(
+        status,
+        AtcaStatus::AtcaTimeout
+            | AtcaStatus::AtcaRxTimeout
+            | AtcaStatus::AtcaRxNoResponse
+            | AtcaStatus::AtcaWakeSuccess
+    )
+}
+
+impl AteccDevice {
+    /// Executes a long command with the whole watchdog window available.
+    /// The chip may have been left awake, e.g. by wake(), so it is first put into
+    /// the idle mode and the command wakes it up again. If the chip does not respond
+    /// to the command, but does respond to a wake-up, the watchdog has put it to sleep:
+    /// the command is executed again and if that fails too, AtcaWatchdogExpired is returned.
+    /// A command must be complete in itself to be executed this way, as TempKey is lost
+    pub(super) fn watchdog_guarded<T, F>(&self, mut execute: F) -> T
+    where
+        T: CommandStatus,
+        F: FnMut() -> T,
+    {
+        self.idle();
+        let mut result = execute();
+        let mut retries = 0;
+        while is_watchdog_symptom(result.status()) {
+            if self.wake() != AtcaStatus::AtcaSuccess {
+                // The chip is not there, it is not the watchdog
+                return result;
+            }
+            if retries == WATCHDOG_RETRIES {
+                self.idle();
+                return T::failed(AtcaStatus::AtcaWatchdogExpired);
+            }
+            retries += 1;
+            result = execute();
+        }
+        result
+    } // AteccDevice::watchdog_guarded()
+}
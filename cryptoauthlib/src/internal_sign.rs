@@ -0,0 +1,71 @@
+use super::{AtcaStatus, SignEcdsaParam, TempKeyFlags};
+use super::{ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_KEY_SIZE};
+use alloc::vec::Vec;
+
+/// Size of the message the Sign command generates in its internal mode
+const INTERNAL_SIGN_MESSAGE_SIZE: usize = 55;
+const SIGN_OPCODE: u8 = 0x41;
+const SIGN_MODE_INVALIDATE: u8 = 0x01;
+const SIGN_MODE_INCLUDE_SN: u8 = 0x40;
+const IDX_SN_0_3: usize = 0;
+const IDX_SN_4_8: usize = 8;
+const IDX_SLOT_CONFIG: usize = 20;
+const IDX_SLOT_LOCKED: usize = 88;
+const IDX_KEY_CONFIG: usize = 96;
+
+/// Rebuilds the 55-byte message ATECC signs with SignMode::Internal, for a verifier
+/// holding the configuration zone of the device but not the device itself:
+/// TempKey || opcode || mode || KeyID || SlotConfig and KeyConfig of the slot TempKey
+/// was computed from || TempKeyFlags || 0x0000 || SN[8] || SN[4:7] (zeros unless
+/// the full serial number is included) || SN[0:1] || slot not locked || invalidate || 0x000000.
+/// TempKey is the value the verifier computes itself, from the GenDig or GenKey
+/// command that has loaded it. The signature is made over SHA256 of the message
+pub fn internal_sign_message(
+    config_data: &[u8],
+    slot_id: u8,
+    param: &SignEcdsaParam,
+    temp_key: &[u8],
+    flags: &TempKeyFlags,
+) -> Result<Vec<u8>, AtcaStatus> {
+    if (config_data.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE) || (temp_key.len() != ATCA_KEY_SIZE) {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    if (slot_id >= ATCA_ATECC_SLOTS_COUNT) || (flags.key_id >= ATCA_ATECC_SLOTS_COUNT) {
+        return Err(AtcaStatus::AtcaInvalidId);
+    }
+    let mut mode: u8 = 0x00;
+    if param.is_invalidate {
+        mode |= SIGN_MODE_INVALIDATE;
+    }
+    if param.is_full_sn {
+        mode |= SIGN_MODE_INCLUDE_SN;
+    }
+    let key_id = flags.key_id as usize;
+    let slot_config = IDX_SLOT_CONFIG + (2 * key_id);
+    let key_config = IDX_KEY_CONFIG + (2 * key_id);
+    let slot_locked = (config_data[IDX_SLOT_LOCKED + (key_id / 8)] & (1 << (key_id % 8))) == 0;
+
+    let mut message: Vec<u8> = Vec::with_capacity(INTERNAL_SIGN_MESSAGE_SIZE);
+    message.extend_from_slice(temp_key);
+    message.extend_from_slice(&[SIGN_OPCODE, mode, slot_id, 0x00]);
+    message.extend_from_slice(&config_data[slot_config..(slot_config + 2)]);
+    message.extend_from_slice(&config_data[key_config..(key_config + 2)]);
+    message.push(
+        flags.key_id
+            | ((flags.source_flag as u8) << 4)
+            | ((flags.gen_dig_data as u8) << 5)
+            | ((flags.gen_key_data as u8) << 6)
+            | ((flags.no_mac_flag as u8) << 7),
+    );
+    message.extend_from_slice(&[0x00, 0x00]);
+    message.push(config_data[IDX_SN_4_8 + 4]);
+    match param.is_full_sn {
+        true => message.extend_from_slice(&config_data[IDX_SN_4_8..(IDX_SN_4_8 + 4)]),
+        false => message.extend_from_slice(&[0x00; 4]),
+    }
+    message.extend_from_slice(&config_data[IDX_SN_0_3..(IDX_SN_0_3 + 2)]);
+    message.push(!slot_locked as u8);
+    message.push(param.is_invalidate as u8);
+    message.extend_from_slice(&[0x00; 3]);
+    Ok(message)
+} // internal_sign_message()
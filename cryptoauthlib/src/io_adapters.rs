@@ -0,0 +1,130 @@
+use std::io::{self, Read, Write};
+
+use super::ATCA_AES_KEY_SIZE;
+use super::{AtcaAesGcmCtx, AtcaSha256Ctx, AtcaStatus, AteccDeviceTrait};
+
+/// Writer calculating a SHA256 digest of all data written into it.
+/// The digest is computed by ATECC, see sha_start() for the limitations
+/// that apply while the calculation is in progress.
+pub struct HashingWriter<'a> {
+    device: &'a dyn AteccDeviceTrait,
+    ctx: AtcaSha256Ctx,
+}
+
+impl<'a> HashingWriter<'a> {
+    /// Starts a new digest calculation
+    pub fn new(device: &'a dyn AteccDeviceTrait) -> Result<HashingWriter<'a>, AtcaStatus> {
+        let ctx = device.sha_start()?;
+        Ok(HashingWriter { device, ctx })
+    }
+
+    /// Completes the calculation and returns the digest of all written data
+    pub fn finish(self) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.sha_finish(self.ctx)
+    }
+}
+
+impl<'a> Write for HashingWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.ctx = self.device.sha_update(self.ctx, buf).map_err(to_io_error)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writer encrypting all data written into it in AES GCM mode
+/// and passing the ciphertext to the inner writer
+pub struct EncryptingWriter<'a, W: Write> {
+    device: &'a dyn AteccDeviceTrait,
+    ctx: AtcaAesGcmCtx,
+    inner: W,
+}
+
+impl<'a, W: Write> EncryptingWriter<'a, W> {
+    /// Starts a new encryption with the key from the given slot (or an external key
+    /// for slot ATCA_ATECC_SLOTS_COUNT), authenticating also the additional data
+    pub fn new(
+        device: &'a dyn AteccDeviceTrait,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        additional_data: &[u8],
+        inner: W,
+    ) -> Result<EncryptingWriter<'a, W>, AtcaStatus> {
+        let mut ctx = device.gcm_init(slot_id, nonce, key)?;
+        ctx = device.gcm_update_aad(ctx, additional_data)?;
+        Ok(EncryptingWriter { device, ctx, inner })
+    }
+
+    /// Completes the encryption, returns the inner writer and a tag of the requested length
+    pub fn finish(self, tag_length: u8) -> Result<(W, Vec<u8>), AtcaStatus> {
+        let tag = self.device.gcm_encrypt_finish(self.ctx, tag_length)?;
+        Ok((self.inner, tag))
+    }
+}
+
+impl<'a, W: Write> Write for EncryptingWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut data = buf.to_vec();
+        self.ctx = self
+            .device
+            .gcm_encrypt_update(self.ctx, &mut data)
+            .map_err(to_io_error)?;
+        self.inner.write_all(&data)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader decrypting AES GCM ciphertext obtained from the inner reader.
+/// The plaintext returned by read() is not authenticated until finish()
+/// confirms the tag, so it should not be acted upon before then.
+pub struct DecryptingReader<'a, R: Read> {
+    device: &'a dyn AteccDeviceTrait,
+    ctx: AtcaAesGcmCtx,
+    inner: R,
+}
+
+impl<'a, R: Read> DecryptingReader<'a, R> {
+    /// Starts a new decryption with the key from the given slot (or an external key
+    /// for slot ATCA_ATECC_SLOTS_COUNT), authenticating also the additional data
+    pub fn new(
+        device: &'a dyn AteccDeviceTrait,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        additional_data: &[u8],
+        inner: R,
+    ) -> Result<DecryptingReader<'a, R>, AtcaStatus> {
+        let mut ctx = device.gcm_init(slot_id, nonce, key)?;
+        ctx = device.gcm_update_aad(ctx, additional_data)?;
+        Ok(DecryptingReader { device, ctx, inner })
+    }
+
+    /// Completes the decryption, returns the inner reader and the result of tag verification
+    pub fn finish(self, tag: &[u8]) -> Result<(R, bool), AtcaStatus> {
+        let is_verified = self.device.gcm_decrypt_finish(self.ctx, tag)?;
+        Ok((self.inner, is_verified))
+    }
+}
+
+impl<'a, R: Read> Read for DecryptingReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.ctx = self
+            .device
+            .gcm_decrypt_update(self.ctx, &mut buf[..len])
+            .map_err(to_io_error)?;
+        Ok(len)
+    }
+}
+
+fn to_io_error(status: AtcaStatus) -> io::Error {
+    io::Error::other(status)
+}
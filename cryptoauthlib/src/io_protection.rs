@@ -0,0 +1,69 @@
+use super::{AtcaDeviceType, AtcaStatus, AteccDeviceTrait, ConfigReport, KeyType};
+use super::{ATCA_ATECC_SLOTS_COUNT, ATCA_KEY_SIZE};
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+
+/// Stores the IO protection key of an ATECC608x in the slot its ChipOptions name,
+/// 'slot_id', which is checked against the configuration: the IO protection must be enabled
+/// and the slot must hold a secret 32-byte key. The key is 'key' or, if None,
+/// generated by the random number generator of the chip.
+/// The key is then registered with add_access_key() both for the slot, so that the slots
+/// whose read or write key it is can be accessed, and for ATCA_ATECC_SLOTS_COUNT,
+/// where ecdh() and kdf() look for the IO protection key.
+/// As other slot writes, it needs both zones locked; the slot is written in clear text
+/// or encrypted, as its write configuration tells. The key is returned, for the host
+/// to keep: it cannot be read back from the chip
+pub fn provision_io_protection_key(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    key: Option<&[u8]>,
+) -> Result<Zeroizing<[u8; ATCA_KEY_SIZE]>, AtcaStatus> {
+    if device.get_device_type() != AtcaDeviceType::ATECC608A {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let mut config_data: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut config_data) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    let config = ConfigReport::from_config_zone(device.get_device_type(), &config_data)?;
+    if !config.config_zone_locked || !config.data_zone_locked {
+        return Err(AtcaStatus::AtcaNotLocked);
+    }
+    if !config.chip_options.io_key_enabled || (config.chip_options.io_key_in_slot != slot_id) {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    match config.slots.get(slot_id as usize) {
+        Some(slot) if (slot.config.key_type == KeyType::ShaOrText) && slot.config.is_secret => (),
+        Some(_) => return Err(AtcaStatus::AtcaBadParam),
+        None => return Err(AtcaStatus::AtcaInvalidId),
+    }
+
+    let mut io_key = Zeroizing::new([0; ATCA_KEY_SIZE]);
+    match key {
+        Some(key) if key.len() == ATCA_KEY_SIZE => io_key.copy_from_slice(key),
+        Some(_) => return Err(AtcaStatus::AtcaInvalidSize),
+        None => {
+            let mut random: Zeroizing<Vec<u8>> = Zeroizing::new(Vec::new());
+            match device.random(&mut random) {
+                AtcaStatus::AtcaSuccess if random.len() >= ATCA_KEY_SIZE => {
+                    io_key.copy_from_slice(&random[..ATCA_KEY_SIZE])
+                }
+                AtcaStatus::AtcaSuccess => return Err(AtcaStatus::AtcaInvalidSize),
+                err => return Err(err),
+            }
+        }
+    }
+
+    match device.write_slot_data(slot_id, 0, &io_key[..]) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    for access_slot in [slot_id, ATCA_ATECC_SLOTS_COUNT].iter() {
+        match device.add_access_key(*access_slot, &io_key[..]) {
+            AtcaStatus::AtcaSuccess => (),
+            err => return Err(err),
+        }
+    }
+    Ok(io_key)
+} // provision_io_protection_key()
@@ -0,0 +1,111 @@
+use super::der::{base64url_decode, base64url_encode};
+use super::signature::convert_signature;
+use super::{AtcaStatus, AteccDeviceTrait, SignatureFormat, VerifyMode};
+use super::{ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE};
+use alloc::{string::String, vec::Vec};
+
+/// JOSE header of a JWT signed with ECDSA P256 and SHA256
+const JWT_HEADER_ES256: &str = r#"{"alg":"ES256","typ":"JWT"}"#;
+
+/// Builds the JSON claims set expected by Google Cloud IoT Core and similar services:
+/// the audience (e.g. the project ID) and the times the token is issued at
+/// and expires at, in seconds since the Unix epoch
+pub fn jwt_claims(audience: &str, issued_at: u64, expires_at: u64) -> String {
+    format!(
+        r#"{{"aud":"{}","iat":{},"exp":{}}}"#,
+        json_escape(audience),
+        issued_at,
+        expires_at
+    )
+} // jwt_claims()
+
+/// Builds the part of an ES256 JWT that is signed:
+/// base64url(header) '.' base64url(claims), the claims being a JSON object
+pub fn jwt_signing_input(claims: &str) -> String {
+    format!(
+        "{}.{}",
+        base64url_encode(JWT_HEADER_ES256.as_bytes()),
+        base64url_encode(claims.as_bytes())
+    )
+} // jwt_signing_input()
+
+/// Completes a JWT by appending the signature of its signing input.
+/// The signature may be raw (R || S) or DER encoded, JWT carries it raw.
+pub fn jwt_encode(
+    signing_input: &str,
+    signature: &[u8],
+    format: SignatureFormat,
+) -> Result<String, AtcaStatus> {
+    let signature = convert_signature(signature, format, SignatureFormat::Raw)?;
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        base64url_encode(&signature)
+    ))
+} // jwt_encode()
+
+/// Produces an ES256 JWT with the given claims (a JSON object),
+/// signed by ATECC with the P256 private key stored in the slot
+pub fn jwt_sign(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+    claims: &str,
+) -> Result<String, AtcaStatus> {
+    let signing_input = jwt_signing_input(claims);
+    let signature = device.sign_message(slot_id, signing_input.as_bytes())?;
+    jwt_encode(&signing_input, &signature, SignatureFormat::Raw)
+} // jwt_sign()
+
+/// Verifies the signature of an ES256 JWT with a P256 public key (X || Y),
+/// hashing and verification are done by ATECC.
+/// Returns claims (JSON) of a valid token and None if the signature does not match.
+pub fn jwt_verify(
+    device: &dyn AteccDeviceTrait,
+    token: &str,
+    public_key: &[u8],
+) -> Result<Option<String>, AtcaStatus> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let header = base64url_decode(parts[0])?;
+    let is_es256 = String::from_utf8_lossy(&header)
+        .split_whitespace()
+        .collect::<String>()
+        .contains(r#""alg":"ES256""#);
+    let claims =
+        String::from_utf8(base64url_decode(parts[1])?).map_err(|_| AtcaStatus::AtcaBadParam)?;
+    let signature = base64url_decode(parts[2])?;
+    if !is_es256 || signature.len() != ATCA_SIG_SIZE {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let signing_input = &token[..(parts[0].len() + 1 + parts[1].len())];
+    let mut digest: Vec<u8> = Vec::with_capacity(ATCA_SHA2_256_DIGEST_SIZE);
+    let result = device.sha(signing_input.as_bytes().to_vec(), &mut digest);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    match device.verify_hash(
+        VerifyMode::External(public_key.to_vec()),
+        &digest,
+        &signature,
+    )? {
+        true => Ok(Some(claims)),
+        false => Ok(None),
+    }
+} // jwt_verify()
+
+/// Escapes a string to be placed between quotes in JSON
+fn json_escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for x in value.chars() {
+        match x {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            x if (x as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", x as u32)),
+            x => result.push(x),
+        }
+    }
+    result
+}
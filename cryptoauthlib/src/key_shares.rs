@@ -0,0 +1,39 @@
+use super::constant_time_eq;
+use super::{AtcaStatus, AteccDeviceTrait, KeyType};
+use alloc::vec;
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+
+/// Imports a key held in split knowledge: each operator of a key ceremony brings one share
+/// and the key is the XOR of all of them, so that no one ever holds it whole.
+/// The shares are combined on the host in a buffer overwritten with zeros afterwards,
+/// in time independent of their contents, then the key is imported by import_key(),
+/// encrypted with the write key of the slot if its configuration requires it
+/// (the write key registered with add_access_key()).
+/// At least two shares of the same length are needed. Shares that cancel each other out,
+/// giving a key of all zeros, are rejected
+pub fn import_key_xor_shares(
+    device: &dyn AteccDeviceTrait,
+    key_type: KeyType,
+    slot_id: u8,
+    shares: &[&[u8]],
+) -> AtcaStatus {
+    let len = match shares.first() {
+        Some(share) if shares.len() >= 2 => share.len(),
+        _ => return AtcaStatus::AtcaBadParam,
+    };
+    if shares.iter().any(|share| share.len() != len) {
+        return AtcaStatus::AtcaInvalidSize;
+    }
+
+    let mut key: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; len]);
+    for share in shares.iter() {
+        for (key_byte, share_byte) in key.iter_mut().zip(share.iter()) {
+            *key_byte ^= share_byte;
+        }
+    }
+    if constant_time_eq(&key, &vec![0; len]) {
+        return AtcaStatus::AtcaBadParam;
+    }
+    device.import_key(key_type, &key, slot_id)
+} // import_key_xor_shares()
@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+
+use super::{
+    AeadAlgorithm, AtcaSlot, AtcaStatus, AteccDeviceTrait, CipherAlgorithm, EcdhOutput,
+    IoProtectionSession, KeyType, SignMode, SlotId, WriteConfig,
+};
+#[cfg(feature = "std")]
+use super::{ATCA_COUNTER_MAX_VALUE, ATCA_LIMITED_USE_COUNTER_ID};
+
+/// Typed handles to the slots of a device, checked against the slot configuration
+/// when they are created. Implemented for every AteccDeviceTrait implementation
+/// (including AteccDevice), so that 'device.ecc_key(slot)?' is available
+/// once this trait is in scope
+pub trait AteccKeySlots: AteccDeviceTrait {
+    /// Handle to a slot holding a P256 private key
+    fn ecc_key(&self, slot_id: u8) -> Result<EccKeySlot<'_, Self>, AtcaStatus>;
+    /// Handle to a slot holding an AES key
+    fn aes_key(&self, slot_id: u8) -> Result<AesKeySlot<'_, Self>, AtcaStatus>;
+    /// Handle to a slot holding a SHA key or data
+    fn data_slot(&self, slot_id: u8) -> Result<DataSlot<'_, Self>, AtcaStatus>;
+}
+
+impl<D: AteccDeviceTrait + ?Sized> AteccKeySlots for D {
+    fn ecc_key(&self, slot_id: u8) -> Result<EccKeySlot<'_, Self>, AtcaStatus> {
+        let slot = slot_config(self, slot_id, KeyType::P256EccKey)?;
+        match slot.config.ecc_key_attr.is_private {
+            true => Ok(EccKeySlot { device: self, slot }),
+            false => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // AteccKeySlots::ecc_key()
+
+    fn aes_key(&self, slot_id: u8) -> Result<AesKeySlot<'_, Self>, AtcaStatus> {
+        let slot = slot_config(self, slot_id, KeyType::Aes)?;
+        Ok(AesKeySlot { device: self, slot })
+    } // AteccKeySlots::aes_key()
+
+    fn data_slot(&self, slot_id: u8) -> Result<DataSlot<'_, Self>, AtcaStatus> {
+        let slot = slot_config(self, slot_id, KeyType::ShaOrText)?;
+        Ok(DataSlot { device: self, slot })
+    } // AteccKeySlots::data_slot()
+}
+
+/// Configuration of the slot, AtcaBadParam if it is not meant for the given type of key
+fn slot_config<D: AteccDeviceTrait + ?Sized>(
+    device: &D,
+    slot_id: u8,
+    key_type: KeyType,
+) -> Result<AtcaSlot, AtcaStatus> {
+    SlotId::new(slot_id)?;
+    let slot = find_slot(device, slot_id)?.ok_or(AtcaStatus::AtcaInvalidId)?;
+    match slot.config.key_type == key_type {
+        true => Ok(slot),
+        false => Err(AtcaStatus::AtcaBadParam),
+    }
+} // slot_config()
+
+/// Configuration of the slot, None if the device does not report such a slot
+fn find_slot<D: AteccDeviceTrait + ?Sized>(
+    device: &D,
+    slot_id: u8,
+) -> Result<Option<AtcaSlot>, AtcaStatus> {
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    match device.get_config(&mut slots) {
+        AtcaStatus::AtcaSuccess => Ok(slots.into_iter().find(|slot| slot.id == slot_id)),
+        err => Err(err),
+    }
+} // find_slot()
+
+/// Number of uses left of the key stored in the slot, None if its uses are not limited.
+/// Every use of a limited-use key increments the monotonic counter ATCA_LIMITED_USE_COUNTER_ID,
+/// the key cannot be used any more once the counter has reached ATCA_COUNTER_MAX_VALUE
+#[cfg(feature = "std")]
+pub(crate) fn key_uses_left<D: AteccDeviceTrait + ?Sized>(
+    device: &D,
+    slot_id: u8,
+) -> Result<Option<u32>, AtcaStatus> {
+    match find_slot(device, slot_id)? {
+        Some(slot) if slot.config.limited_use => {
+            let counter = device.counter_read(ATCA_LIMITED_USE_COUNTER_ID)?;
+            Ok(Some(ATCA_COUNTER_MAX_VALUE.saturating_sub(counter)))
+        }
+        _ => Ok(None),
+    }
+} // key_uses_left()
+
+/// Slot holding a P256 private key. Only the operations using
+/// a private key (key generation, signing, ECDH, ECIES) are available
+pub struct EccKeySlot<'a, D: AteccDeviceTrait + ?Sized> {
+    device: &'a D,
+    slot: AtcaSlot,
+}
+
+impl<D: AteccDeviceTrait + ?Sized> EccKeySlot<'_, D> {
+    /// Number of the slot
+    pub fn slot_id(&self) -> u8 {
+        self.slot.id
+    }
+
+    /// Configuration of the slot, as read when the handle was created
+    pub fn slot(&self) -> &AtcaSlot {
+        &self.slot
+    }
+
+    /// Generates a new key pair in the slot
+    pub fn gen_key(&self) -> AtcaStatus {
+        self.device.gen_key(KeyType::P256EccKey, self.slot.id)
+    }
+
+    /// Imports a private key encoded in DER (PKCS#8 or SEC1) into the slot
+    pub fn import_der(&self, key_data: &[u8]) -> AtcaStatus {
+        self.device.import_private_key_der(key_data, self.slot.id)
+    }
+
+    /// Raw X || Y public key matching the private key in the slot
+    pub fn public_key(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let mut public_key: Vec<u8> = Vec::new();
+        match self.device.get_public_key(self.slot.id, &mut public_key) {
+            AtcaStatus::AtcaSuccess => Ok(public_key),
+            err => Err(err),
+        }
+    } // EccKeySlot::public_key()
+
+    /// Signs a SHA256 digest, the signature is returned as R || S
+    pub fn sign_hash(&self, hash: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let mut signature: Vec<u8> = Vec::new();
+        match self.device.sign_hash(
+            SignMode::External(hash.to_vec()),
+            self.slot.id,
+            &mut signature,
+        ) {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            err => Err(err),
+        }
+    } // EccKeySlot::sign_hash()
+
+    /// Hashes a message (SHA256) and signs the digest
+    pub fn sign_message(&self, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.sign_message(self.slot.id, message)
+    }
+
+    /// ECDH key agreement with the peer's public key
+    pub fn ecdh(
+        &self,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        self.device.ecdh(self.slot.id, public_key, session)
+    }
+
+    /// Decrypts data encrypted with ecies_encrypt() for the public key of the slot
+    pub fn ecies_decrypt(&self, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.ecies_decrypt(self.slot.id, data)
+    }
+}
+
+/// Slot holding an AES key. Only the AES operations are available
+pub struct AesKeySlot<'a, D: AteccDeviceTrait + ?Sized> {
+    device: &'a D,
+    slot: AtcaSlot,
+}
+
+impl<D: AteccDeviceTrait + ?Sized> AesKeySlot<'_, D> {
+    /// Number of the slot
+    pub fn slot_id(&self) -> u8 {
+        self.slot.id
+    }
+
+    /// Configuration of the slot, as read when the handle was created
+    pub fn slot(&self) -> &AtcaSlot {
+        &self.slot
+    }
+
+    /// Imports an AES key into the slot
+    pub fn import_key(&self, key: &[u8]) -> AtcaStatus {
+        self.device.import_key(KeyType::Aes, key, self.slot.id)
+    }
+
+    /// Encrypts data in one of the unauthenticated AES modes
+    pub fn encrypt(&self, algorithm: CipherAlgorithm, data: &mut Vec<u8>) -> AtcaStatus {
+        self.device.cipher_encrypt(algorithm, self.slot.id, data)
+    }
+
+    /// Decrypts data in one of the unauthenticated AES modes
+    pub fn decrypt(&self, algorithm: CipherAlgorithm, data: &mut Vec<u8>) -> AtcaStatus {
+        self.device.cipher_decrypt(algorithm, self.slot.id, data)
+    }
+
+    /// Encrypts data in one of the AEAD modes and returns the tag
+    pub fn aead_encrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        data: &mut Vec<u8>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.aead_encrypt(algorithm, self.slot.id, data)
+    }
+
+    /// Decrypts data in one of the AEAD modes, 'false' if the tag does not match
+    pub fn aead_decrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        data: &mut Vec<u8>,
+    ) -> Result<bool, AtcaStatus> {
+        self.device.aead_decrypt(algorithm, self.slot.id, data)
+    }
+}
+
+/// Slot holding a SHA key or data. Only reading, writing and HMAC are available
+pub struct DataSlot<'a, D: AteccDeviceTrait + ?Sized> {
+    device: &'a D,
+    slot: AtcaSlot,
+}
+
+impl<D: AteccDeviceTrait + ?Sized> DataSlot<'_, D> {
+    /// Number of the slot
+    pub fn slot_id(&self) -> u8 {
+        self.slot.id
+    }
+
+    /// Configuration of the slot, as read when the handle was created
+    pub fn slot(&self) -> &AtcaSlot {
+        &self.slot
+    }
+
+    /// Checks if the Write command may modify the slot (in clear text or encrypted)
+    pub fn is_writable(&self) -> bool {
+        matches!(
+            self.slot.config.write_config,
+            WriteConfig::Always | WriteConfig::Encrypt
+        )
+    }
+
+    /// Reads data from the slot, starting at the given byte offset
+    pub fn read(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.read_slot_data(self.slot.id, offset, len)
+    }
+
+    /// Writes data to the slot, starting at the given byte offset.
+    /// AtcaBadParam is returned without reaching the chip if the slot is not writable
+    pub fn write(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        match self.is_writable() {
+            true => self.device.write_slot_data(self.slot.id, offset, data),
+            false => AtcaStatus::AtcaBadParam,
+        }
+    } // DataSlot::write()
+
+    /// HMAC-SHA256 of a message with the key stored in the slot
+    pub fn hmac(&self, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.sha_hmac(message, self.slot.id)
+    }
+}
@@ -1,27 +1,231 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::upper_case_acronyms)]
 
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
+// The paths of the core library are the std ones, also in the code generated by strum_macros
+#[cfg(not(feature = "std"))]
+extern crate core as std;
 #[macro_use]
 extern crate strum_macros; // 0.10.0
+#[cfg(feature = "i2c-rust")]
 #[macro_use]
 extern crate lazy_static;
 
+use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use rand::RngCore;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
 include!("types.rs");
 include!("constants.rs");
 
+mod alt_key_buf;
+#[cfg(feature = "async")]
+mod async_device;
 mod atca_iface_cfg;
+mod atcacert;
+mod attest;
+mod cloud;
+#[cfg(feature = "config")]
+pub mod config;
+mod config_report;
+mod config_zone;
+mod cose;
+mod counter_sign;
+mod csr;
+#[cfg(all(unix, feature = "daemon"))]
+mod daemon;
+mod der;
+#[cfg(feature = "embedded-tls")]
+mod embedded_tls;
+mod fingerprint;
+#[cfg(feature = "std")]
+mod handle;
+mod health;
+mod host_modes;
 mod hw_impl;
+mod internal_sign;
+#[cfg(feature = "std")]
+mod io_adapters;
+mod io_protection;
+mod jwt;
+mod key_shares;
+mod key_slot;
+mod limited_use;
+mod lock_state;
+#[cfg(feature = "std")]
+mod metrics;
+mod otp;
+#[cfg(feature = "parsec")]
+mod parsec;
+mod private_key;
+#[cfg(feature = "config")]
+pub mod provision;
+mod public_key;
+#[cfg(feature = "remote")]
+mod remote;
+mod rng;
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto;
+#[cfg(feature = "rustcrypto-backend")]
+mod rustcrypto_impl;
+#[cfg(feature = "rustls")]
+mod rustls;
+mod secure_session;
+mod sha206a;
+mod signature;
+mod signature_slot;
+mod slot_id;
+mod slot_report;
 mod sw_impl;
+mod sync;
+mod temp_key;
+mod tng;
 #[cfg(test)]
 mod unit_tests;
 
 #[cfg(test)]
 use cryptoauthlib_sys::atca_aes_cbc_ctx_t;
 
+pub use alt_key_buf::{alt_key_buf_aes_encrypt_block, alt_key_buf_kdf, load_alt_key_buf};
+#[cfg(feature = "async")]
+pub use async_device::AsyncAteccDevice;
+pub use atcacert::{cert_compress, cert_read, cert_write, get_certificate};
+pub use attest::{attest_key, key_attestation_message, verify_key_attestation};
+pub use cloud::{aws_iot_registration, azure_dps_sas_token, azure_sas_token, azure_x509_identity};
+pub use config_report::config_report;
+pub use cose::{cose_sign1_sign, cose_sign1_verify};
+pub use counter_sign::{counter_signed_message, sign_hash_with_counter, verify_hash_with_counter};
+pub use csr::{csr_to_pem, generate_csr};
+#[cfg(all(unix, feature = "daemon"))]
+pub use daemon::{daemon_serve, AteccDaemonClient};
+#[cfg(feature = "embedded-tls")]
+pub use embedded_tls::{TlsKeyExchange, TlsSignature, TlsSigner, TLS_ECDSA_SECP256R1_SHA256};
+pub use fingerprint::fingerprint;
+#[cfg(feature = "std")]
+pub use handle::{AtcaCancellationToken, AteccHandle, AteccSession};
+pub use health::health_check;
+pub use hw_impl::{discover, Transport};
+pub use internal_sign::internal_sign_message;
+#[cfg(feature = "std")]
+pub use io_adapters::{DecryptingReader, EncryptingWriter, HashingWriter};
+pub use io_protection::provision_io_protection_key;
+pub use jwt::{jwt_claims, jwt_encode, jwt_sign, jwt_signing_input, jwt_verify};
+pub use key_shares::import_key_xor_shares;
+pub use key_slot::{AesKeySlot, AteccKeySlots, DataSlot, EccKeySlot};
+pub use limited_use::limited_use_state;
+pub use lock_state::{AteccLockState, OperationalDevice, ProvisioningDevice};
+#[cfg(feature = "std")]
+pub use metrics::{set_metrics_sink, AtcaCommandMetrics, AtcaCommandStats, MetricsSink};
+pub use otp::write_otp_zone_verified;
+#[cfg(feature = "parsec")]
+pub use parsec::{AteccParsecProvider, ParsecKeyProvider, PARSEC_WIRE_PROTOCOL_VERSION};
+pub use private_key::{private_key_from_der, private_key_from_pem};
+pub use public_key::{
+    public_key_compress, public_key_decompress, public_key_from_sec1, public_key_to_sec1,
+};
+#[cfg(feature = "remote")]
+pub use remote::{remote_serve, remote_serve_connection, AteccRemoteDevice};
+pub use rng::AteccRng;
+#[cfg(feature = "rustcrypto")]
+pub use rustcrypto::{SlotAes, SlotSigner};
+#[cfg(feature = "rustcrypto-backend")]
+pub use rustcrypto_impl::{AteccSimulator, SoftwareBackend, SOFTWARE_BACKEND_CONFIG_ZONE};
+#[cfg(feature = "rustls")]
+pub use rustls::{AteccClientCertResolver, AteccSigningKey};
+pub use secure_session::{SecureSession, ATCA_CHECK_MAC_OTHER_DATA_SIZE};
+pub use sha206a::{sha206a_authenticate, sha206a_use_state};
+pub use signature::{convert_signature, signature_from_der, signature_to_der};
+pub use signature_slot::{load_signature, store_signature};
+pub use slot_report::slot_report;
+pub use sw_impl::{AtcaTestFault, AtcaTestResponse, AtcaTestScript};
+pub use tng::{tng_get_cert_chain, tng_verify_genuine};
+
 pub trait AteccDeviceTrait {
     /// Request ATECC to generate a vector of random bytes
     fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus;
+    /// Request ATECC to generate random bytes into a buffer of the caller.
+    /// Backends talking to a chip do not allocate anything on the heap
+    fn random_into(&self, rand_out: &mut [u8; ATCA_RANDOM_BUFFER_SIZE]) -> AtcaStatus {
+        let mut random: Vec<u8> = Vec::new();
+        match self.random(&mut random) {
+            AtcaStatus::AtcaSuccess if random.len() != rand_out.len() => {
+                AtcaStatus::AtcaInvalidSize
+            }
+            AtcaStatus::AtcaSuccess => {
+                rand_out.copy_from_slice(&random);
+                AtcaStatus::AtcaSuccess
+            }
+            err => err,
+        }
+    }
+    /// Fills a buffer of any length with random bytes, ATECC being requested
+    /// for as many blocks of ATCA_RANDOM_BUFFER_SIZE bytes as needed
+    fn random_fill(&self, dest: &mut [u8]) -> AtcaStatus {
+        let mut block = Zeroizing::new([0; ATCA_RANDOM_BUFFER_SIZE]);
+        for chunk in dest.chunks_mut(ATCA_RANDOM_BUFFER_SIZE) {
+            match self.random_into(&mut block) {
+                AtcaStatus::AtcaSuccess => chunk.copy_from_slice(&block[..chunk.len()]),
+                err => return err,
+            }
+        }
+        AtcaStatus::AtcaSuccess
+    }
+    /// Fills a buffer of any length with random bytes of ATECC mixed (XOR)
+    /// with random bytes of the host, so that the output is not weaker than either source
+    #[cfg(feature = "std")]
+    fn random_fill_mixed(&self, dest: &mut [u8]) -> AtcaStatus {
+        let result = self.random_fill(dest);
+        if AtcaStatus::AtcaSuccess != result {
+            return result;
+        }
+        let mut host_block = Zeroizing::new([0; ATCA_RANDOM_BUFFER_SIZE]);
+        let mut host_rng = rand::thread_rng();
+        for chunk in dest.chunks_mut(ATCA_RANDOM_BUFFER_SIZE) {
+            host_rng.fill_bytes(&mut host_block[..]);
+            for (byte, host_byte) in chunk.iter_mut().zip(host_block.iter()) {
+                *byte ^= host_byte;
+            }
+        }
+        AtcaStatus::AtcaSuccess
+    }
     /// Request ATECC to compute a message hash (SHA256)
     fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus;
+    /// Request ATECC to compute a message hash (SHA256) into a buffer of the caller.
+    /// Backends talking to a chip do not allocate anything on the heap
+    fn sha_into(&self, message: &[u8], digest: &mut [u8; ATCA_SHA2_256_DIGEST_SIZE]) -> AtcaStatus {
+        let mut result_digest: Vec<u8> = Vec::new();
+        match self.sha(message.to_vec(), &mut result_digest) {
+            AtcaStatus::AtcaSuccess if result_digest.len() != digest.len() => {
+                AtcaStatus::AtcaInvalidSize
+            }
+            AtcaStatus::AtcaSuccess => {
+                digest.copy_from_slice(&result_digest);
+                AtcaStatus::AtcaSuccess
+            }
+            err => err,
+        }
+    }
+    /// Request ATECC to compute a message hash (SHA256), returned as a fixed-size array
+    fn sha256(&self, message: &[u8]) -> Result<[u8; ATCA_SHA2_256_DIGEST_SIZE], AtcaStatus> {
+        let mut digest = [0; ATCA_SHA2_256_DIGEST_SIZE];
+        match self.sha_into(message, &mut digest) {
+            AtcaStatus::AtcaSuccess => Ok(digest),
+            err => Err(err),
+        }
+    }
+    /// Starts a SHA256 digest of a message that is fed to ATECC in several parts.
+    /// The intermediate state is kept in ATECC, so no other command using
+    /// the SHA engine may be issued until sha_finish() is called
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus>;
+    /// Feeds the next part of a message into a SHA256 digest calculation
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus>;
+    /// Completes a SHA256 digest calculation and returns the digest
+    fn sha_finish(&self, ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus>;
+    /// Request ATECC to compute HMAC-SHA256 of a message with the 32-byte key stored in a slot
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus>;
     /// Execute a Nonce command in pass-through mode to load one of the
     /// device's internal buffers with a fixed value.
     /// For the ATECC608A, available targets are TempKey (32 or 64 bytes), Message
@@ -31,20 +235,131 @@ pub trait AteccDeviceTrait {
     /// Execute a Nonce command to generate a random nonce combining a host
     /// nonce and a device random number.
     fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus;
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    /// TempKey must have been set by a Nonce command before
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus;
+    /// What the host knows about TempKey: the command that has loaded it, the size
+    /// of its contents and whether a later command has invalidated or overwritten it.
+    /// Commands relying on TempKey (GenDig, MAC or CheckMac without a challenge,
+    /// AES or KDF with a TempKey source...) fail when it is not valid, this tells why
+    fn tempkey_state(&self) -> TempKeyState;
+    /// Execute a MAC command: SHA256 of the key stored in a slot followed by
+    /// the 32-byte challenge or, if there is none, by TempKey
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus>;
+    /// Execute a CheckMac command to verify a MAC calculated by the host over the key
+    /// stored in a slot followed by the 32-byte challenge or, if there is none, by TempKey.
+    /// 'other_data' (13 bytes) stands for the MAC command parameters used by the host.
+    /// 'false' is returned if the response does not match
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus>;
+    /// Request ATECC to read the value of one of the two monotonic counters.
+    /// Counter ATCA_LIMITED_USE_COUNTER_ID counts the uses of the limited-use keys
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus>;
+    /// Request ATECC to increment one of the two monotonic counters and return its new value.
+    /// A counter that has reached ATCA_COUNTER_MAX_VALUE cannot be incremented
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus>;
     /// Request ATECC to generate a cryptographic key
     fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus;
     /// Request ATECC to import a cryptographic key
     fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus;
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1)
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus;
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus;
     /// Request ATECC to export a cryptographic key.
     /// For cryptographic security reasons,
     /// with KeyType = P256EccKey this function exports only public key
     fn export_key(&self, key_type: KeyType, key_data: &mut Vec<u8>, slot_id: u8) -> AtcaStatus;
+    /// Request ATECC to write data to a slot, starting at the given byte offset.
+    /// Depending on the slot configuration, data is written in clear text
+    /// or encrypted with the write key previously set by add_access_key().
+    /// Clear text writes must be aligned to 4-byte words, encrypted writes to 32-byte blocks.
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus;
+    /// Request ATECC to read data from a slot, starting at the given byte offset.
+    /// Depending on the slot configuration, data is read in clear text
+    /// or encrypted with the read key previously set by add_access_key().
+    fn read_slot_data(&self, slot_id: u8, offset: usize, len: usize)
+        -> Result<Vec<u8>, AtcaStatus>;
+    /// Request ATECC to write the OTP zone, starting at the given byte offset.
+    /// Writes must be aligned to 4-byte words. The zone can be written only
+    /// after the configuration zone has been locked and before the data zone is locked
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus;
+    /// Request ATECC to read the OTP zone, starting at the given byte offset.
+    /// Reads must be aligned to 4-byte words, the zone can be read once the data zone is locked
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus>;
     /// Depending on the socket configuration, this function calculates
     /// public key based on an existing private key in the socket
     /// or exports the public key directly
     fn get_public_key(&self, slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus;
+    /// Public key (X || Y) of a slot, as get_public_key(), returned as a fixed-size array
+    fn public_key(&self, slot_id: u8) -> Result<[u8; ATCA_ATECC_PUB_KEY_SIZE], AtcaStatus> {
+        let mut result_key: Vec<u8> = Vec::new();
+        match self.get_public_key(slot_id, &mut result_key) {
+            AtcaStatus::AtcaSuccess if result_key.len() != ATCA_ATECC_PUB_KEY_SIZE => {
+                Err(AtcaStatus::AtcaInvalidSize)
+            }
+            AtcaStatus::AtcaSuccess => {
+                let mut public_key = [0; ATCA_ATECC_PUB_KEY_SIZE];
+                public_key.copy_from_slice(&result_key);
+                Ok(public_key)
+            }
+            err => Err(err),
+        }
+    }
     /// Request ATECC to generate an ECDSA signature
     fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus;
+    /// Request ATECC to sign a SHA256 digest, the signature (R || S) is written
+    /// into a buffer of the caller. Backends talking to a chip do not allocate anything on the heap
+    fn sign_hash_into(
+        &self,
+        slot_id: u8,
+        hash: &[u8; ATCA_SHA2_256_DIGEST_SIZE],
+        signature: &mut [u8; ATCA_SIG_SIZE],
+    ) -> AtcaStatus {
+        let mut result_signature: Vec<u8> = Vec::new();
+        match self.sign_hash(
+            SignMode::External(hash.to_vec()),
+            slot_id,
+            &mut result_signature,
+        ) {
+            AtcaStatus::AtcaSuccess if result_signature.len() != signature.len() => {
+                AtcaStatus::AtcaInvalidSize
+            }
+            AtcaStatus::AtcaSuccess => {
+                signature.copy_from_slice(&result_signature);
+                AtcaStatus::AtcaSuccess
+            }
+            err => err,
+        }
+    }
+    /// Request ATECC to sign a SHA256 digest, the signature (R || S) is returned
+    /// as a fixed-size array
+    fn sign_digest(
+        &self,
+        slot_id: u8,
+        hash: &[u8; ATCA_SHA2_256_DIGEST_SIZE],
+    ) -> Result<[u8; ATCA_SIG_SIZE], AtcaStatus> {
+        let mut signature = [0; ATCA_SIG_SIZE];
+        match self.sign_hash_into(slot_id, hash, &mut signature) {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            err => Err(err),
+        }
+    }
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus>;
+    /// Request ATECC to execute a Sign command in the given mode (ATCA_SIGN_MODE_* bits)
+    /// with the private key stored in the given slot, for the modes SignMode does not model.
+    /// The message is whatever the previous commands have left in TempKey or the Message
+    /// Digest Buffer. Combinations of bits the chip does not accept are rejected
+    /// with AtcaBadParam before anything is sent
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus>;
     /// Request ATECC to verify ECDSA signature
     fn verify_hash(
         &self,
@@ -52,6 +367,73 @@ pub trait AteccDeviceTrait {
         hash: &[u8],
         signature: &[u8],
     ) -> Result<bool, AtcaStatus>;
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus>;
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus>;
+    /// Request ATECC to perform ECDH key agreement with a private key from a slot
+    /// (or TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT).
+    /// Depending on the 'ecdh_output_protection' setting of the chip and on the slot
+    /// configuration, the premaster secret is returned to the host (decrypted with the IO
+    /// protection key, if required) or kept in TempKey or in a slot; the result tells which.
+    fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus>;
+    /// Request ATECC608x to derive a key with the KDF command.
+    /// For 'KdfTarget::Output' the derived key is returned in clear text - if the chip
+    /// requires encrypted KDF output, it is decrypted with the IO protection key
+    /// registered by add_access_key() for slot ATCA_ATECC_SLOTS_COUNT.
+    /// For other targets the derived key stays in the chip and an empty vector is returned.
+    fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus>;
+    /// Request ATECC608x to open an IO protection session with the given IO protection key.
+    /// The key must match the one stored in the slot indicated by ChipOptions.io_key_in_slot
+    fn open_io_protection_session(&self, io_key: &[u8]) -> Result<IoProtectionSession, AtcaStatus>;
+    /// Request ATECC to perform ECDH key agreement with a private key from a slot
+    /// (or TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT).
+    /// The premaster secret is transmitted encrypted with the IO protection key
+    fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus>;
+    /// Request ATECC to verify ECDSA signature, with the result
+    /// authenticated by a MAC calculated with the IO protection key
+    fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus>;
+    /// Request ATECC to import an AES key to a slot whose write key is the IO protection key.
+    /// The key is transmitted encrypted
+    fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus;
     /// Data encryption function in AES unauthenticated cipher alhorithms modes
     fn cipher_encrypt(
         &self,
@@ -80,6 +462,109 @@ pub trait AteccDeviceTrait {
         slot_id: u8,
         data: &mut Vec<u8>,
     ) -> Result<bool, AtcaStatus>;
+    /// Starts a streaming AES GCM encryption or decryption with the key stored in the given slot
+    /// or, for slot ATCA_ATECC_SLOTS_COUNT, with an external key loaded into TempKey
+    fn gcm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus>;
+    /// Feeds the next chunk of additional authenticated data into a streaming AES GCM operation.
+    /// All additional data must be supplied before the first chunk of text
+    fn gcm_update_aad(&self, ctx: AtcaAesGcmCtx, data: &[u8]) -> Result<AtcaAesGcmCtx, AtcaStatus>;
+    /// Encrypts (in place) the next chunk of a streaming AES GCM encryption
+    fn gcm_encrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus>;
+    /// Decrypts (in place) the next chunk of a streaming AES GCM decryption
+    fn gcm_decrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus>;
+    /// Completes a streaming AES GCM encryption and returns a tag of the requested length
+    fn gcm_encrypt_finish(&self, ctx: AtcaAesGcmCtx, tag_length: u8)
+        -> Result<Vec<u8>, AtcaStatus>;
+    /// Completes a streaming AES GCM decryption, returns true if the tag has been verified
+    fn gcm_decrypt_finish(&self, ctx: AtcaAesGcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus>;
+    /// Starts a streaming AES CCM encryption or decryption with the key stored in the given slot
+    /// or, for slot ATCA_ATECC_SLOTS_COUNT, with an external key loaded into TempKey.
+    /// CCM requires the total lengths of the additional data and of the text to be known up front
+    fn ccm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus>;
+    /// Feeds the next chunk of additional authenticated data into a streaming AES CCM operation.
+    /// All additional data must be supplied before the first chunk of text
+    fn ccm_update_aad(&self, ctx: AtcaAesCcmCtx, data: &[u8]) -> Result<AtcaAesCcmCtx, AtcaStatus>;
+    /// Encrypts (in place) the next chunk of a streaming AES CCM encryption
+    fn ccm_encrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus>;
+    /// Decrypts (in place) the next chunk of a streaming AES CCM decryption
+    fn ccm_decrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus>;
+    /// Completes a streaming AES CCM encryption and returns the tag of the length given to ccm_init()
+    fn ccm_encrypt_finish(&self, ctx: AtcaAesCcmCtx) -> Result<Vec<u8>, AtcaStatus>;
+    /// Completes a streaming AES CCM decryption, returns true if the tag has been verified
+    fn ccm_decrypt_finish(&self, ctx: AtcaAesCcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus>;
+    /// Derives TLS 1.2 session keys on ATECC608A: ECDH of the private key from the slot
+    /// (or TempKey) with the server ephemeral public key, followed by the TLS PRF run
+    /// by the KDF command. The premaster and master secrets never leave the chip, the key block
+    /// (up to 64 bytes) is returned IO-protected with the session key, if a session is given
+    fn tls_derive_keys(
+        &self,
+        slot_id: u8,
+        server_public_key: &[u8],
+        client_random: &[u8],
+        server_random: &[u8],
+        key_block_length: usize,
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus>;
+    /// Calculates verify_data of a TLS 1.2 Finished message with the master secret
+    /// left in TempKey by the preceding tls_derive_keys() call
+    fn tls_verify_data(
+        &self,
+        label: TlsFinishedLabel,
+        handshake_hash: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus>;
+    /// Encrypts data for the owner of the given public key (ATECC608A only),
+    /// combining ECDH with an ephemeral key, the KDF command and AES GCM.
+    /// The result is self-contained: ephemeral public key || IV || ciphertext || tag
+    fn ecies_encrypt(&self, public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus>;
+    /// Decrypts data produced by ecies_encrypt() with the private key stored in the given slot
+    fn ecies_decrypt(&self, slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus>;
+    /// Wrap (encrypt) a key with the AES key stored in the given slot,
+    /// according to RFC 3394 (AES-KW) or RFC 5649 (AES-KWP)
+    fn wrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus>;
+    /// Unwrap (decrypt) a key with the AES key stored in the given slot,
+    /// according to RFC 3394 (AES-KW) or RFC 5649 (AES-KWP).
+    /// If the integrity check fails, AtcaCheckMacVerifyFailed is returned
+    fn unwrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus>;
     /// Request ATECC to return own device type
     fn get_device_type(&self) -> AtcaDeviceType;
     /// Request ATECC to check if its configuration is locked.
@@ -91,6 +576,19 @@ pub trait AteccDeviceTrait {
     /// Returns a structure containing configuration data read from ATECC
     /// during initialization of the AteccDevice object.
     fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus;
+    /// Request ATECC to read and return own configuration zone.
+    /// Note: this function returns raw data, function get_config(..) implements a more
+    /// structured return value.
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus;
+    /// Request ATECC to write its whole configuration zone, possible only while
+    /// the zone is not locked. Bytes that cannot be written (serial number, revision,
+    /// lock bytes...) keep their values
+    fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus;
+    /// Request ATECC to lock its configuration zone, this cannot be undone
+    fn lock_config_zone(&self) -> AtcaStatus;
+    /// Request ATECC to lock its data zone, which is possible only after the configuration
+    /// zone has been locked. This cannot be undone
+    fn lock_data_zone(&self) -> AtcaStatus;
     /// Command accesses some static or dynamic information from the ATECC chip
     fn info_cmd(&self, _command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus>;
     /// A function that adds an encryption key for securely reading or writing data
@@ -100,6 +598,11 @@ pub trait AteccDeviceTrait {
     /// A function that deletes all encryption keys for secure read or write operations
     /// performed by the ATECCx08 chip
     fn flush_access_keys(&self) -> AtcaStatus;
+    /// Sets the host nonce (NumIn, 20 bytes) of the Nonce command that precedes the next
+    /// encrypted read or write of a slot, the writes of import_key() and gen_key() included.
+    /// It is used once; without it a random host nonce is generated for every such command,
+    /// so that a recorded exchange cannot be replayed. None discards a host nonce not used yet
+    fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus;
     /// Get serial number of the ATECC device
     fn get_serial_number(&self) -> [u8; ATCA_SERIAL_NUM_SIZE];
     /// Checks if the chip supports AES encryption.
@@ -117,7 +620,48 @@ pub trait AteccDeviceTrait {
     /// Function that reads the read security settings of the KDF function from chip
     /// (only relevant for the ATECC608x chip)
     fn get_kdf_output_protection_state(&self) -> OutputProtectionState;
-    /// ATECC device instance destructor
+    /// Perform an AES-128 encrypt operation with a key in the device
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus>;
+    /// Perform an AES-128 decrypt operation with a key in the device
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus>;
+    /// Request ATECC608x to run the SelfTest command over all its functional blocks
+    /// (RNG, ECDSA, ECDH, AES, SHA). Returns false if any of them fails
+    fn self_test(&self) -> Result<bool, AtcaStatus>;
+    /// Wakes the chip up. Commands wake it up themselves, so it is needed only
+    /// to check that the chip responds or to bring it out of the sleep mode in advance
+    fn wake(&self) -> AtcaStatus;
+    /// Puts the chip into the idle mode, keeping TempKey and the other volatile state
+    fn idle(&self) -> AtcaStatus;
+    /// Puts the chip into the sleep mode, drawing the least current.
+    /// The volatile state, e.g. TempKey, is lost
+    fn sleep(&self) -> AtcaStatus;
+    /// Tells if the chip seems to be gone: the last few commands have all failed
+    /// to communicate with it, e.g. because a kit has been unplugged
+    fn is_disconnected(&self) -> bool;
+    /// Re-initializes the communication with the chip with the stored interface
+    /// configuration, keeping the configuration read from the chip and the access keys.
+    /// Fails with AtcaInvalidId if there is another chip on the interface now
+    fn reconnect(&self) -> AtcaStatus;
+    /// Reads again the state of the chip cached when the instance was created:
+    /// the device type and the lock state of the zones.
+    /// Only needed if the chip may have been changed by another tool
+    fn refresh(&self) -> AtcaStatus;
+    /// Reads again the lock state of the configuration and data zones,
+    /// e.g. after they have been locked by another tool
+    fn refresh_lock_state(&self) -> AtcaStatus;
+    /// ATECC device instance destructor.
+    /// It is called when the instance is dropped, if it has not been called before.
+    /// A second call has no effect and returns AtcaBadParam
     fn release(&self) -> AtcaStatus;
 
     //--------------------------------------------------
@@ -137,11 +681,6 @@ pub trait AteccDeviceTrait {
         data: &mut Vec<u8>,
         len: u8,
     ) -> AtcaStatus;
-    /// Request ATECC to read and return own configuration zone.
-    /// Note: this function returns raw data, function get_config(..) implements a more
-    /// structured return value.
-    #[cfg(test)]
-    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus;
     /// Compare internal config zone contents vs. config_data.
     /// Diagnostic function.
     #[cfg(test)]
@@ -151,22 +690,6 @@ pub trait AteccDeviceTrait {
     /// Data is not taken directly from the ATECCx08 chip, but from the AteccDevice structure
     #[cfg(test)]
     fn get_access_key(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus;
-    /// Perform an AES-128 encrypt operation with a key in the device
-    #[cfg(test)]
-    fn aes_encrypt_block(
-        &self,
-        key_id: u16,
-        key_block: u8,
-        input: &[u8],
-    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus>;
-    /// Perform an AES-128 decrypt operation with a key in the device
-    #[cfg(test)]
-    fn aes_decrypt_block(
-        &self,
-        key_id: u16,
-        key_block: u8,
-        input: &[u8],
-    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus>;
     /// Initialize context for AES CTR operation with an existing IV, which
     /// is common when start a decrypt operation
     #[cfg(test)]
@@ -186,26 +709,121 @@ pub trait AteccDeviceTrait {
 
 pub type AteccDevice = Box<dyn AteccDeviceTrait + Send + Sync>;
 
+/// Creates a device of the backend selected by the interface type:
+/// - "software": an ATECC608A emulated by SoftwareBackend,
+/// - "simulator": an ATECC608A simulated by AteccSimulator, configured
+///   as described by SOFTWARE_BACKEND_CONFIG_ZONE,
+/// - "remote": an ATECC of another host, served at the address set by set_remote_address(),
+/// - "test-interface": a test device behaving as its device type tells,
+/// - other interfaces: an ATECC chip accessed by the hardware backend
 pub fn setup_atecc_device(r_iface_cfg: AtcaIfaceCfg) -> Result<AteccDevice, String> {
-    match r_iface_cfg.devtype {
-        AtcaDeviceType::AtcaTestDevSuccess
-        | AtcaDeviceType::AtcaTestDevFail
-        | AtcaDeviceType::AtcaTestDevFailUnimplemented => {
-            match sw_impl::AteccDevice::new(r_iface_cfg) {
+    match r_iface_cfg.iface_type {
+        #[cfg(feature = "rustcrypto-backend")]
+        AtcaIfaceType::AtcaSoftwareIface => Ok(Box::new(SoftwareBackend::new())),
+        #[cfg(feature = "rustcrypto-backend")]
+        AtcaIfaceType::AtcaSimulatorIface => {
+            match AteccSimulator::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE) {
                 Ok(x) => Ok(Box::new(x)),
-                Err(err) => Err(err),
+                Err(err) => Err(format!("Could not create the simulator: {}", err)),
             }
         }
-        AtcaDeviceType::AtcaDevUnknown => {
-            Err(String::from("Attempting to create an unknown device type"))
-        }
-        _ => match hw_impl::AteccDevice::new(r_iface_cfg) {
-            Ok(x) => Ok(Box::new(x)),
-            Err(err) => Err(err),
+        #[cfg(feature = "remote")]
+        AtcaIfaceType::AtcaRemoteIface => match r_iface_cfg.remote_address {
+            Some(address) => match AteccRemoteDevice::connect(address) {
+                Ok(x) => Ok(Box::new(x)),
+                Err(err) => Err(format!("Could not connect to {}: {}", address, err)),
+            },
+            None => Err(String::from("Missing address of the remote device")),
+        },
+        _ => match r_iface_cfg.devtype {
+            AtcaDeviceType::AtcaTestDevSuccess
+            | AtcaDeviceType::AtcaTestDevFail
+            | AtcaDeviceType::AtcaTestDevFailUnimplemented => {
+                match sw_impl::AteccDevice::new(r_iface_cfg) {
+                    Ok(x) => Ok(Box::new(x)),
+                    Err(err) => Err(err),
+                }
+            }
+            AtcaDeviceType::AtcaDevUnknown => {
+                Err(String::from("Attempting to create an unknown device type"))
+            }
+            _ => match r_iface_cfg.build() {
+                Ok(r_iface_cfg) => match hw_impl::AteccDevice::new(r_iface_cfg) {
+                    Ok(x) => Ok(Box::new(x)),
+                    Err(err) => Err(err),
+                },
+                Err(err) => Err(format!("Invalid interface configuration: {}", err)),
+            },
         },
     }
 }
 
+/// Creates a device of the "test-interface" whose commands respond as the script tells
+/// and, once there is no response waiting for a command, as its device type tells.
+/// The script can be extended while the device is in use
+pub fn setup_atecc_test_device(
+    r_iface_cfg: AtcaIfaceCfg,
+    script: AtcaTestScript,
+) -> Result<AteccDevice, String> {
+    match sw_impl::AteccDevice::with_script(r_iface_cfg, script) {
+        Ok(x) => Ok(Box::new(x)),
+        Err(err) => Err(err),
+    }
+}
+
+/// Creates a device of a chip attached to a bus that the given transport carries
+/// the commands over. The interface type and settings of the configuration are not used,
+/// only the device type, the wake delay and the number of receive retries
+pub fn setup_atecc_device_with_transport(
+    r_iface_cfg: AtcaIfaceCfg,
+    transport: Box<dyn Transport>,
+) -> Result<AteccDevice, String> {
+    match hw_impl::AteccDevice::with_transport(r_iface_cfg, transport) {
+        Ok(x) => Ok(Box::new(x)),
+        Err(err) => Err(err),
+    }
+}
+
+/// Error of the traits of the signature crate, it keeps the source only with std
+#[cfg(any(feature = "rustcrypto", feature = "embedded-tls"))]
+fn signature_error<E>(err: E) -> ::signature::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    #[cfg(feature = "std")]
+    return ::signature::Error::from_source(err);
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = err;
+        ::signature::Error::new()
+    }
+}
+
+/// Compares secret-dependent data (MACs, authentication tags, keys) in constant time:
+/// the time taken depends on the lengths of 'a' and 'b' only, never on their contents
+/// or on the position of the first differing byte. Slices of different lengths
+/// are not equal. Every host-side check of such data in the crate is done
+/// with this function, never with '==', and so should the checks of the callers
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Bytes of the configuration zone that the Write command cannot change:
+/// serial number, revision and I2C settings, then UserExtra, UserExtraAdd and the lock bytes
+#[cfg(any(feature = "config", feature = "rustcrypto-backend"))]
+const CONFIG_READ_ONLY_BYTES: [(usize, usize); 2] = [(0, 16), (84, 88)];
+
+/// Checks that an access to the OTP zone is word-aligned and within the zone
+fn check_otp_range(offset: usize, len: usize) -> Result<(), AtcaStatus> {
+    if len == 0 || offset > ATCA_OTP_SIZE || len > (ATCA_OTP_SIZE - offset) {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    if (offset | len) & (ATCA_WORD_SIZE - 1) != 0 {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    Ok(())
+}
+
 impl AtcaSlot {
     pub fn is_valid(self) -> bool {
         // As long as exclusive range is experimental, this should work.
@@ -0,0 +1,80 @@
+use super::{AtcaDeviceType, AtcaStatus, AteccDeviceTrait, ConfigReport, LimitedUseState};
+use super::{ATCA_ATECC_SLOTS_COUNT, ATCA_COUNTER_MAX_VALUE, ATCA_LIMITED_USE_COUNTER_ID};
+use alloc::vec::Vec;
+
+/// LastKeyUse of ATECC508A and older chips, one bit per remaining use of slot 15
+const LAST_KEY_USE: (usize, usize) = (68, 84);
+/// Slot whose uses LastKeyUse counts
+const LAST_KEY_USE_SLOT: u8 = ATCA_ATECC_SLOTS_COUNT - 1;
+/// The CountMatchKey slot holds the match value twice, as two 32-bit words
+const COUNT_MATCH_VALUE_SIZE: usize = 8;
+
+impl LimitedUseState {
+    /// Uses left before the key is exhausted, which cannot be undone
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.used)
+    }
+} // impl LimitedUseState
+
+/// Tells how many times the limited-use key in 'slot_id' has been used and can still be used,
+/// so that an application warns before the key is exhausted for good.
+/// On ATECC608x the uses are counted by the monotonic counter ATCA_LIMITED_USE_COUNTER_ID
+/// and end when it reaches the count match value, if counter match is enabled,
+/// or its maximum value otherwise. On the older chips slot 15 is limited
+/// by the 128 bits of LastKeyUse, the other slots by the same monotonic counter.
+/// The slot must be configured as limited-use, AtcaBadParam is returned otherwise
+pub fn limited_use_state(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+) -> Result<LimitedUseState, AtcaStatus> {
+    let mut config_data: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut config_data) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    let device_type = device.get_device_type();
+    let config = ConfigReport::from_config_zone(device_type, &config_data)?;
+    match config.slots.get(slot_id as usize) {
+        Some(slot) if slot.config.limited_use => (),
+        Some(_) => return Err(AtcaStatus::AtcaBadParam),
+        None => return Err(AtcaStatus::AtcaInvalidId),
+    }
+
+    if (device_type != AtcaDeviceType::ATECC608A) && (slot_id == LAST_KEY_USE_SLOT) {
+        let limit = ((LAST_KEY_USE.1 - LAST_KEY_USE.0) * 8) as u32;
+        let unused: u32 = config_data[LAST_KEY_USE.0..LAST_KEY_USE.1]
+            .iter()
+            .map(|byte| byte.count_ones())
+            .sum();
+        return Ok(LimitedUseState {
+            slot: slot_id,
+            used: limit - unused,
+            limit,
+            count_match: false,
+        });
+    }
+
+    let used = device.counter_read(ATCA_LIMITED_USE_COUNTER_ID)?;
+    let (limit, count_match) = match config.count_match_key {
+        Some(key_slot) => (count_match_value(device, key_slot)?, true),
+        None => (ATCA_COUNTER_MAX_VALUE, false),
+    };
+    Ok(LimitedUseState {
+        slot: slot_id,
+        used,
+        limit,
+        count_match,
+    })
+} // limited_use_state()
+
+/// Reads the match value from the CountMatchKey slot, where it must be stored twice
+fn count_match_value(device: &dyn AteccDeviceTrait, key_slot: u8) -> Result<u32, AtcaStatus> {
+    let data = device.read_slot_data(key_slot, 0, COUNT_MATCH_VALUE_SIZE)?;
+    if data.len() != COUNT_MATCH_VALUE_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    if data[..4] != data[4..] {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    Ok(u32::from_le_bytes([data[0], data[1], data[2], data[3]]))
+} // count_match_value()
@@ -0,0 +1,176 @@
+use alloc::vec::Vec;
+use std::ops::Deref;
+
+use super::{AtcaDeviceType, AtcaSlot, AtcaStatus, AteccDeviceTrait, InfoCmdType, KeyType};
+use super::{ATCA_ATECC_PUB_KEY_SIZE, ATCA_SERIAL_NUM_SIZE};
+
+/// An ATECC device sorted by the lock state of its configuration and data zones,
+/// so that the type of the device tells which operations can be used
+pub enum AteccLockState<'a> {
+    /// The configuration and/or the data zone is not locked
+    Provisioning(ProvisioningDevice<'a>),
+    /// Both zones are locked
+    Operational(OperationalDevice<'a>),
+}
+
+impl<'a> AteccLockState<'a> {
+    /// Sorts the device by the lock state of its zones
+    pub fn new(device: &'a dyn AteccDeviceTrait) -> AteccLockState<'a> {
+        match device.is_configuration_locked() && device.is_data_zone_locked() {
+            true => AteccLockState::Operational(OperationalDevice { device }),
+            false => AteccLockState::Provisioning(ProvisioningDevice { device }),
+        }
+    } // AteccLockState::new()
+
+    /// Returns the device, if it is ready for cryptographic operations.
+    /// Otherwise AtcaNotLocked is returned
+    pub fn operational(self) -> Result<OperationalDevice<'a>, AtcaStatus> {
+        match self {
+            AteccLockState::Operational(device) => Ok(device),
+            AteccLockState::Provisioning(_) => Err(AtcaStatus::AtcaNotLocked),
+        }
+    } // AteccLockState::operational()
+
+    /// Returns the device, if it still is to be provisioned.
+    /// Otherwise AtcaConfigZoneLocked is returned
+    pub fn provisioning(self) -> Result<ProvisioningDevice<'a>, AtcaStatus> {
+        match self {
+            AteccLockState::Provisioning(device) => Ok(device),
+            AteccLockState::Operational(_) => Err(AtcaStatus::AtcaConfigZoneLocked),
+        }
+    } // AteccLockState::provisioning()
+}
+
+/// ATECC device with both zones locked. All the operations of AteccDeviceTrait are available,
+/// none of them fails with AtcaNotLocked because of the state of the zones
+pub struct OperationalDevice<'a> {
+    device: &'a dyn AteccDeviceTrait,
+}
+
+impl<'a> Deref for OperationalDevice<'a> {
+    type Target = dyn AteccDeviceTrait + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        self.device
+    }
+}
+
+/// ATECC device being personalized. Only the operations of a personalization are available:
+/// writing and locking the configuration zone, generating and importing keys, writing
+/// the slots and the OTP zone and locking the data zone.
+/// AteccLockState sorts a device here while one of its zones is not locked.
+/// As a personalization goes on after the lock steps and can be run again
+/// once interrupted, the zones are not checked by new()
+pub struct ProvisioningDevice<'a> {
+    device: &'a dyn AteccDeviceTrait,
+}
+
+impl<'a> ProvisioningDevice<'a> {
+    /// Personalization of the device, whatever the state of its zones
+    pub fn new(device: &'a dyn AteccDeviceTrait) -> ProvisioningDevice<'a> {
+        ProvisioningDevice { device }
+    }
+
+    /// Request ATECC to return own device type
+    pub fn get_device_type(&self) -> AtcaDeviceType {
+        self.device.get_device_type()
+    }
+
+    /// Checks if the configuration zone is locked
+    pub fn is_configuration_locked(&self) -> bool {
+        self.device.is_configuration_locked()
+    }
+
+    /// Checks if the data zone is locked
+    pub fn is_data_zone_locked(&self) -> bool {
+        self.device.is_data_zone_locked()
+    }
+
+    /// Get serial number of the ATECC device
+    pub fn get_serial_number(&self) -> [u8; ATCA_SERIAL_NUM_SIZE] {
+        self.device.get_serial_number()
+    }
+
+    /// Returns the configuration of the slots read from ATECC
+    pub fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus {
+        self.device.get_config(atca_slots)
+    }
+
+    /// Command accesses some static or dynamic information from the ATECC chip
+    pub fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.info_cmd(command)
+    }
+
+    /// Request ATECC to read and return own configuration zone
+    pub fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        self.device.read_config_zone(config_data)
+    }
+
+    /// Request ATECC to write its whole configuration zone, possible only while
+    /// the zone is not locked
+    pub fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        self.device.write_config_zone(config_data)
+    }
+
+    /// Request ATECC to lock its configuration zone, this cannot be undone
+    pub fn lock_config_zone(&self) -> AtcaStatus {
+        self.device.lock_config_zone()
+    }
+
+    /// Request ATECC to generate a cryptographic key (a P256 key pair in a slot
+    /// whose configuration allows it), the configuration zone must be locked
+    pub fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        self.device.gen_key(key_type, slot_id)
+    }
+
+    /// Public key (X || Y) of a slot, it can be read once the data zone is locked
+    pub fn public_key(&self, slot_id: u8) -> Result<[u8; ATCA_ATECC_PUB_KEY_SIZE], AtcaStatus> {
+        self.device.public_key(slot_id)
+    }
+
+    /// Request ATECC to write the OTP zone, starting at the given byte offset,
+    /// after the configuration zone has been locked and before the data zone is locked
+    pub fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.device.write_otp_zone(offset, data)
+    }
+
+    /// Request ATECC to read the OTP zone, once the data zone is locked
+    pub fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.read_otp_zone(offset, len)
+    }
+
+    /// Request ATECC to lock its data zone, this cannot be undone
+    pub fn lock_data_zone(&self) -> AtcaStatus {
+        self.device.lock_data_zone()
+    }
+
+    /// Adds the key encrypting the reads and writes of the slots that require it
+    pub fn add_access_key(&self, slot_id: u8, encryption_key: &[u8]) -> AtcaStatus {
+        self.device.add_access_key(slot_id, encryption_key)
+    }
+
+    /// Deletes the keys added by add_access_key()
+    pub fn flush_access_keys(&self) -> AtcaStatus {
+        self.device.flush_access_keys()
+    }
+
+    /// Request ATECC to import a cryptographic key
+    pub fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.device.import_key(key_type, key_data, slot_id)
+    }
+
+    /// Request ATECC to write data to a slot, starting at the given byte offset
+    pub fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.device.write_slot_data(slot_id, offset, data)
+    }
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset
+    pub fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.device.read_slot_data(slot_id, offset, len)
+    }
+}
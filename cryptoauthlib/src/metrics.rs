@@ -0,0 +1,92 @@
+// Statistics of the commands executed by the chips, for applications exporting
+// the health of their secure elements to monitoring systems (Prometheus, StatsD...).
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use super::AtcaStatus;
+
+/// Receives a record of each command executed by an ATECC chip of the hardware backend.
+/// It is called by the thread that has issued the command, right after the command
+/// completes, so it should return quickly
+pub trait MetricsSink: Send + Sync {
+    /// A command named after its function in AteccDeviceTrait has completed
+    /// with the given status after the given time
+    fn record_command(&self, command: &str, status: AtcaStatus, latency: Duration);
+}
+
+static METRICS_SINK: RwLock<Option<Arc<dyn MetricsSink>>> = RwLock::new(None);
+
+/// Sets the sink receiving the records of the commands of all the devices,
+/// or removes it if 'None' is given
+pub fn set_metrics_sink(sink: Option<Arc<dyn MetricsSink>>) {
+    *METRICS_SINK.write().unwrap_or_else(|err| err.into_inner()) = sink;
+} // set_metrics_sink()
+
+/// Passes the record of a command to the sink, if there is one
+pub(crate) fn record_command(command: &str, status: AtcaStatus, latency: Duration) {
+    let sink = match METRICS_SINK.read() {
+        Ok(sink) => sink.clone(),
+        Err(_) => return,
+    };
+    if let Some(sink) = sink {
+        sink.record_command(command, status, latency);
+    }
+} // record_command()
+
+/// Statistics of a single command
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AtcaCommandStats {
+    /// Number of executions
+    pub count: u64,
+    /// Number of executions that have failed
+    pub failures: u64,
+    /// Total time of all the executions
+    pub total_latency: Duration,
+    /// The longest execution
+    pub max_latency: Duration,
+}
+
+/// A sink counting the executions and the failures of each command
+/// and summing up their latencies, to be read by the exporter of an application
+#[derive(Debug, Default)]
+pub struct AtcaCommandMetrics {
+    commands: Mutex<BTreeMap<String, AtcaCommandStats>>,
+}
+
+impl AtcaCommandMetrics {
+    /// Creates a sink with no commands recorded
+    pub fn new() -> AtcaCommandMetrics {
+        AtcaCommandMetrics::default()
+    } // AtcaCommandMetrics::new()
+
+    /// Statistics of the commands recorded so far, by command name
+    pub fn snapshot(&self) -> BTreeMap<String, AtcaCommandStats> {
+        self.commands
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    } // AtcaCommandMetrics::snapshot()
+
+    /// Forgets the commands recorded so far
+    pub fn reset(&self) {
+        self.commands
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+    } // AtcaCommandMetrics::reset()
+}
+
+impl MetricsSink for AtcaCommandMetrics {
+    fn record_command(&self, command: &str, status: AtcaStatus, latency: Duration) {
+        let mut commands = self.commands.lock().unwrap_or_else(|err| err.into_inner());
+        let stats = commands.entry(command.to_owned()).or_default();
+        stats.count += 1;
+        if status != AtcaStatus::AtcaSuccess {
+            stats.failures += 1;
+        }
+        stats.total_latency += latency;
+        stats.max_latency = stats.max_latency.max(latency);
+    }
+}
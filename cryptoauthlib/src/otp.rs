@@ -0,0 +1,78 @@
+use super::check_otp_range;
+use super::{AtcaStatus, ConfigReport, OtpWriteReport, ProvisioningDevice, ATCA_BLOCK_SIZE};
+use alloc::vec::Vec;
+
+/// Writes 'data' to the OTP zone at the given byte offset, whose mistakes are permanent,
+/// with the checks the OTP zone deserves. The write is word-aligned and within the zone,
+/// it is made block by block, so that the report tells how many bytes a failed write left
+/// in the zone. The chip allows writes only before the data zone is locked
+/// and reads only after, so the contents are verified when the data zone is locked:
+/// nothing is written then, whatever the OTP mode (the consumption mode of the older chips,
+/// deprecated by Microchip, is not supported), and the zone is read back and compared.
+/// A personalization calls it before locking the data zone, then again after to verify
+pub fn write_otp_zone_verified(
+    device: &ProvisioningDevice,
+    offset: usize,
+    data: &[u8],
+) -> OtpWriteReport {
+    let mut report = OtpWriteReport {
+        status: AtcaStatus::AtcaSuccess,
+        otp_mode: None,
+        written: 0,
+        verified: None,
+        mismatches: Vec::new(),
+    };
+    if let Err(err) = check_otp_range(offset, data.len()) {
+        report.status = err;
+        return report;
+    }
+    let mut config_data: Vec<u8> = Vec::new();
+    let config = match device.read_config_zone(&mut config_data) {
+        AtcaStatus::AtcaSuccess => {
+            ConfigReport::from_config_zone(device.get_device_type(), &config_data)
+        }
+        err => Err(err),
+    };
+    match config {
+        Ok(config) => report.otp_mode = config.otp_mode,
+        Err(err) => {
+            report.status = err;
+            return report;
+        }
+    }
+
+    if !device.is_data_zone_locked() {
+        while report.written < data.len() {
+            let position = offset + report.written;
+            let len =
+                (ATCA_BLOCK_SIZE - (position % ATCA_BLOCK_SIZE)).min(data.len() - report.written);
+            match device.write_otp_zone(position, &data[report.written..(report.written + len)]) {
+                AtcaStatus::AtcaSuccess => report.written += len,
+                err => {
+                    report.status = err;
+                    break;
+                }
+            }
+        }
+        return report;
+    }
+
+    match device.read_otp_zone(offset, data.len()) {
+        Ok(contents) => {
+            report.mismatches = contents
+                .iter()
+                .zip(data.iter())
+                .enumerate()
+                .filter(|(_, (read, wanted))| read != wanted)
+                .map(|(idx, _)| offset + idx)
+                .collect();
+            report.verified = Some(report.mismatches.is_empty());
+            if !report.mismatches.is_empty() {
+                // The data zone is locked, the OTP zone cannot be written any more
+                report.status = AtcaStatus::AtcaDataZoneLocked;
+            }
+        }
+        Err(err) => report.status = err,
+    }
+    report
+} // write_otp_zone_verified()
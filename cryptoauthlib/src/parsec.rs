@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::public_key::public_key_to_sec1;
+use super::{AtcaStatus, AteccDevice, KeyType, SignMode, VerifyMode};
+use super::{ATCA_ATECC_PUB_KEY_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE};
+
+/// Version of the Parsec wire protocol the provider answers a ping with
+pub const PARSEC_WIRE_PROTOCOL_VERSION: (u8, u8) = (1, 0);
+
+/// Operations of a Parsec key provider, the Parsec service dispatches
+/// client requests for ECC P256 keys to them. Keys are addressed by name.
+/// Signatures and public keys use the PSA Crypto formats:
+/// a signature is R || S, a public key is an uncompressed SEC1 point.
+pub trait ParsecKeyProvider: Send + Sync {
+    /// Returns the wire protocol version (major, minor)
+    fn ping(&self) -> Result<(u8, u8), AtcaStatus>;
+    /// Generates a P256 key pair named 'key_name'
+    fn generate_key(&self, key_name: &str) -> Result<(), AtcaStatus>;
+    /// Signs a SHA256 digest with the named key
+    fn sign_hash(&self, key_name: &str, hash: &[u8]) -> Result<Vec<u8>, AtcaStatus>;
+    /// Verifies a signature of a SHA256 digest with the named key
+    fn verify_hash(
+        &self,
+        key_name: &str,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus>;
+    /// Returns the public part of the named key
+    fn export_public_key(&self, key_name: &str) -> Result<Vec<u8>, AtcaStatus>;
+}
+
+/// Parsec key provider keeping its keys in ATECC slots.
+/// Generated keys are placed in the first unused slot of the pool given at creation.
+/// The mapping of key names to slots is held in memory only, the Parsec service
+/// persists it and restores it at startup with add_key().
+pub struct AteccParsecProvider {
+    device: Arc<AteccDevice>,
+    key_slots: Vec<u8>,
+    keys: Mutex<HashMap<String, u8>>,
+}
+
+impl AteccParsecProvider {
+    /// Creates a provider generating keys in the given slots,
+    /// the slots must be configured for P256 private keys
+    pub fn new(device: Arc<AteccDevice>, key_slots: &[u8]) -> Self {
+        AteccParsecProvider {
+            device,
+            key_slots: key_slots.to_vec(),
+            keys: Mutex::new(HashMap::new()),
+        }
+    } // AteccParsecProvider::new()
+
+    /// Registers a key already present in the slot under the given name
+    pub fn add_key(&self, key_name: &str, slot_id: u8) -> Result<(), AtcaStatus> {
+        let mut keys = self.keys()?;
+        if keys.contains_key(key_name) || keys.values().any(|slot| *slot == slot_id) {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        keys.insert(key_name.to_owned(), slot_id);
+        Ok(())
+    } // AteccParsecProvider::add_key()
+
+    /// Returns the slot holding the named key
+    pub fn key_slot(&self, key_name: &str) -> Result<u8, AtcaStatus> {
+        match self.keys()?.get(key_name) {
+            Some(slot_id) => Ok(*slot_id),
+            None => Err(AtcaStatus::AtcaInvalidId),
+        }
+    } // AteccParsecProvider::key_slot()
+
+    fn keys(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, u8>>, AtcaStatus> {
+        self.keys.lock().map_err(|_| AtcaStatus::AtcaFuncFail)
+    }
+
+    fn public_key(&self, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        let mut public_key = Vec::with_capacity(ATCA_ATECC_PUB_KEY_SIZE);
+        match self.device.get_public_key(slot_id, &mut public_key) {
+            AtcaStatus::AtcaSuccess => Ok(public_key),
+            err => Err(err),
+        }
+    }
+}
+
+impl ParsecKeyProvider for AteccParsecProvider {
+    fn ping(&self) -> Result<(u8, u8), AtcaStatus> {
+        Ok(PARSEC_WIRE_PROTOCOL_VERSION)
+    }
+
+    fn generate_key(&self, key_name: &str) -> Result<(), AtcaStatus> {
+        let mut keys = self.keys()?;
+        if keys.contains_key(key_name) {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        let slot_id = match self
+            .key_slots
+            .iter()
+            .find(|slot_id| !keys.values().any(|used| used == *slot_id))
+        {
+            Some(slot_id) => *slot_id,
+            None => return Err(AtcaStatus::AtcaAllocFailure),
+        };
+        match self.device.gen_key(KeyType::P256EccKey, slot_id) {
+            AtcaStatus::AtcaSuccess => {
+                keys.insert(key_name.to_owned(), slot_id);
+                Ok(())
+            }
+            err => Err(err),
+        }
+    } // AteccParsecProvider::generate_key()
+
+    fn sign_hash(&self, key_name: &str, hash: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let slot_id = self.key_slot(key_name)?;
+        if hash.len() != ATCA_SHA2_256_DIGEST_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let mut signature = Vec::with_capacity(ATCA_SIG_SIZE);
+        match self
+            .device
+            .sign_hash(SignMode::External(hash.to_vec()), slot_id, &mut signature)
+        {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            err => Err(err),
+        }
+    } // AteccParsecProvider::sign_hash()
+
+    fn verify_hash(
+        &self,
+        key_name: &str,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let public_key = self.public_key(self.key_slot(key_name)?)?;
+        self.device
+            .verify_hash(VerifyMode::External(public_key), hash, signature)
+    } // AteccParsecProvider::verify_hash()
+
+    fn export_public_key(&self, key_name: &str) -> Result<Vec<u8>, AtcaStatus> {
+        let public_key = self.public_key(self.key_slot(key_name)?)?;
+        public_key_to_sec1(&public_key)
+    } // AteccParsecProvider::export_public_key()
+}
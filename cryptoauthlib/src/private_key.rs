@@ -0,0 +1,87 @@
+use super::der::{
+    peek_tag, pem_decode, read_element, ASN1_CONTEXT_0, ASN1_INTEGER, ASN1_OCTET_STRING, ASN1_OID,
+    ASN1_SEQUENCE, OID_EC_PUBLIC_KEY, OID_PRIME256V1,
+};
+use super::AtcaStatus;
+use super::ATCA_ATECC_PRIV_KEY_SIZE;
+use alloc::vec::Vec;
+
+const PKCS8_VERSION: &[u8] = &[0x00];
+const SEC1_VERSION: &[u8] = &[0x01];
+const PEM_LABEL_PKCS8: &str = "PRIVATE KEY";
+const PEM_LABEL_SEC1: &str = "EC PRIVATE KEY";
+
+/// Extracts a P256 private key (the 32-byte scalar accepted by import_key())
+/// from its DER encoding, either PKCS#8 'PrivateKeyInfo' or SEC1 'ECPrivateKey'.
+/// Keys of other algorithms or curves, as well as encrypted keys, are rejected.
+pub fn private_key_from_der(der: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    let (content, rest) = read_element(ASN1_SEQUENCE, der)?;
+    if !rest.is_empty() {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let (version, rest) = read_element(ASN1_INTEGER, content)?;
+    match version {
+        PKCS8_VERSION => pkcs8_private_key(rest),
+        SEC1_VERSION => sec1_private_key(rest, false),
+        _ => Err(AtcaStatus::AtcaBadParam),
+    }
+} // private_key_from_der()
+
+/// Extracts a P256 private key (the 32-byte scalar accepted by import_key())
+/// from a PEM file, labeled either "PRIVATE KEY" (PKCS#8) or "EC PRIVATE KEY" (SEC1)
+pub fn private_key_from_pem(pem: &str) -> Result<Vec<u8>, AtcaStatus> {
+    let der = match pem_decode(pem, PEM_LABEL_SEC1) {
+        Ok(der) => der,
+        Err(_) => pem_decode(pem, PEM_LABEL_PKCS8)?,
+    };
+    private_key_from_der(&der)
+} // private_key_from_pem()
+
+/// Parses the part of 'PrivateKeyInfo' that follows the version
+fn pkcs8_private_key(data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    let (algorithm, rest) = read_element(ASN1_SEQUENCE, data)?;
+    let (algorithm_oid, parameters) = read_element(ASN1_OID, algorithm)?;
+    let (curve_oid, parameters_rest) = read_element(ASN1_OID, parameters)?;
+    if (algorithm_oid != OID_EC_PUBLIC_KEY)
+        || (curve_oid != OID_PRIME256V1)
+        || !parameters_rest.is_empty()
+    {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    // Optional attributes may follow the key, they are of no use for ATECC
+    let (private_key, _attributes) = read_element(ASN1_OCTET_STRING, rest)?;
+    let (content, rest) = read_element(ASN1_SEQUENCE, private_key)?;
+    if !rest.is_empty() {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    match read_element(ASN1_INTEGER, content)? {
+        (SEC1_VERSION, rest) => sec1_private_key(rest, true),
+        _ => Err(AtcaStatus::AtcaBadParam),
+    }
+} // pkcs8_private_key()
+
+/// Parses the part of 'ECPrivateKey' that follows the version.
+/// The curve parameters are optional when the key is wrapped in 'PrivateKeyInfo'.
+fn sec1_private_key(data: &[u8], is_curve_known: bool) -> Result<Vec<u8>, AtcaStatus> {
+    let (scalar, rest) = read_element(ASN1_OCTET_STRING, data)?;
+    if scalar.is_empty() || scalar.len() > ATCA_ATECC_PRIV_KEY_SIZE {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let is_curve_p256 = match peek_tag(rest) {
+        Some(ASN1_CONTEXT_0) => {
+            let (parameters, _public_key) = read_element(ASN1_CONTEXT_0, rest)?;
+            read_element(ASN1_OID, parameters)?.0 == OID_PRIME256V1
+        }
+        _ => is_curve_known,
+    };
+    if !is_curve_p256 {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    // Some encoders strip the leading zeros of the scalar
+    let mut result: Vec<u8> = vec![0x00; ATCA_ATECC_PRIV_KEY_SIZE - scalar.len()];
+    result.extend_from_slice(scalar);
+    Ok(result)
+} // sec1_private_key()
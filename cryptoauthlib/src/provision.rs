@@ -0,0 +1,903 @@
+// Personalization of a chip described by a manifest, in TOML (or JSON and YAML
+// with the features "json-config" and "yaml-config"):
+//
+// config_zone = "01234567..."    # hex, the whole configuration zone
+// lock_config = true
+// otp = "4d414e5546..."          # hex, written at the start of the OTP zone
+// lock_data = true
+//
+// [[access_key]]
+// slot = 6
+// key = "00112233..."            # hex, key encrypting the reads and writes of other slots
+//
+// [[slot]]
+// slot = 0
+// action = "generate"            # a P-256 private key generated by the chip
+//
+// [[slot]]
+// slot = 2
+// action = "import-private-key"
+// file = "device-key.pem"        # PEM or DER
+//
+// [[slot]]
+// slot = 9
+// action = "import-aes-key"      # also "import-public-key", with the hex of X || Y
+// key = "000102030405..."
+//
+// [[slot]]
+// slot = 8
+// action = "write"
+// offset = 0
+// data = "48656c6c6f"
+//
+// [[certificate]]
+// slot = 10
+// file = "device.crt"            # PEM or DER, stored in DER
+//
+// Files are looked up relative to the manifest. The steps run in the order the life cycle
+// of the chip allows: the configuration zone is written and locked, keys are generated,
+// the OTP zone is written, the data zone is locked and the OTP zone and the public keys
+// of the generated keys read back,
+// then the access keys are registered and the other slot contents and certificates are written.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use zeroize::Zeroizing;
+
+use super::config::{parse, parse_file, ConfigFormat};
+use super::der::pem_decode;
+use super::{check_otp_range, private_key_from_der, private_key_from_pem, write_otp_zone_verified};
+use super::{AtcaConfigError, AtcaStatus, KeyType, ProvisioningDevice, CONFIG_READ_ONLY_BYTES};
+use super::{
+    ATCA_AES_KEY_SIZE, ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_ATECC_PUB_KEY_SIZE,
+    ATCA_ATECC_SLOTS_COUNT, ATCA_KEY_SIZE,
+};
+
+/// Personalization of a chip: what is written to it and which zones are locked
+#[derive(Clone, Default)]
+pub struct Manifest {
+    /// Configuration zone written before it is locked
+    pub config_zone: Option<Vec<u8>>,
+    pub lock_config: bool,
+    /// Keys registered with add_access_key() before the slots are written
+    pub access_keys: Vec<AccessKey>,
+    pub slots: Vec<SlotContent>,
+    pub certificates: Vec<Certificate>,
+    /// Contents written at the start of the OTP zone
+    pub otp: Option<Vec<u8>>,
+    pub lock_data: bool,
+}
+
+/// Key encrypting the reads and writes of the slots that name it as their read or write key
+#[derive(Clone)]
+pub struct AccessKey {
+    pub slot: u8,
+    pub key: Zeroizing<Vec<u8>>,
+}
+
+/// Contents of a slot
+#[derive(Clone)]
+pub enum SlotContent {
+    /// A P-256 private key generated by the chip
+    GeneratedKey {
+        slot: u8,
+    },
+    /// A P-256 private key, the 32-byte scalar
+    PrivateKey {
+        slot: u8,
+        key: Zeroizing<Vec<u8>>,
+    },
+    /// A P-256 public key, X || Y
+    PublicKey {
+        slot: u8,
+        key: Vec<u8>,
+    },
+    AesKey {
+        slot: u8,
+        key: Zeroizing<Vec<u8>>,
+    },
+    /// Data written at the given byte offset
+    Data {
+        slot: u8,
+        offset: usize,
+        data: Vec<u8>,
+    },
+}
+
+/// Certificate stored in DER in a data slot
+#[derive(Clone, Debug, PartialEq)]
+pub struct Certificate {
+    pub slot: u8,
+    pub offset: usize,
+    pub der: Vec<u8>,
+}
+
+/// A step of the personalization
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ProvisioningStep {
+    WriteConfigZone,
+    LockConfigZone,
+    GenKey(u8),
+    WriteOtpZone,
+    LockDataZone,
+    AddAccessKey(u8),
+    ImportKey(u8),
+    WriteSlot(u8),
+    WriteCertificate(u8),
+}
+
+impl fmt::Display for ProvisioningStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProvisioningStep::WriteConfigZone => write!(f, "write the configuration zone"),
+            ProvisioningStep::LockConfigZone => write!(f, "lock the configuration zone"),
+            ProvisioningStep::GenKey(slot) => write!(f, "generate the key of slot {}", slot),
+            ProvisioningStep::WriteOtpZone => write!(f, "write the OTP zone"),
+            ProvisioningStep::LockDataZone => write!(f, "lock the data zone"),
+            ProvisioningStep::AddAccessKey(slot) => {
+                write!(f, "add the access key of slot {}", slot)
+            }
+            ProvisioningStep::ImportKey(slot) => write!(f, "import the key of slot {}", slot),
+            ProvisioningStep::WriteSlot(slot) => write!(f, "write slot {}", slot),
+            ProvisioningStep::WriteCertificate(slot) => {
+                write!(f, "write the certificate of slot {}", slot)
+            }
+        }
+    }
+}
+
+/// Outcome of a step, passed to the progress callback of provision()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ProvisioningProgress {
+    pub step: ProvisioningStep,
+    /// Position of the step, starting at 1
+    pub index: usize,
+    /// Number of steps of the manifest
+    pub total: usize,
+    pub status: AtcaStatus,
+    /// The chip was already in the state the step leads to, e.g. a zone already locked
+    pub skipped: bool,
+}
+
+/// Result of a complete personalization
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProvisioningReport {
+    /// Steps executed
+    pub completed: Vec<ProvisioningStep>,
+    /// Steps not needed because the chip was already in the state they lead to
+    pub skipped: Vec<ProvisioningStep>,
+    /// Public keys of the generated private keys, by slot. They can be read only
+    /// once the data zone is locked, so none are reported for a chip left unlocked
+    pub public_keys: Vec<(u8, [u8; ATCA_ATECC_PUB_KEY_SIZE])>,
+}
+
+/// A step failed. The completed steps that could be undone were undone, the others
+/// (generated keys, locks, OTP writes and slots whose contents could not be read) were not
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProvisioningError {
+    pub step: ProvisioningStep,
+    pub status: AtcaStatus,
+    pub rolled_back: Vec<ProvisioningStep>,
+    pub not_rolled_back: Vec<ProvisioningStep>,
+}
+
+impl fmt::Display for ProvisioningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot {}: {}", self.step, self.status)?;
+        if !self.rolled_back.is_empty() {
+            write!(f, ", rolled back: {}", join_steps(&self.rolled_back))?;
+        }
+        if !self.not_rolled_back.is_empty() {
+            write!(
+                f,
+                ", not rolled back: {}",
+                join_steps(&self.not_rolled_back)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A byte of the configuration zone that differs from the one to write
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConfigByteChange {
+    pub offset: usize,
+    pub current: u8,
+    pub wanted: u8,
+}
+
+/// What write_verify_lock() did or, in a dry run, would do
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigWriteReport {
+    pub dry_run: bool,
+    /// Bytes the write changes
+    pub changes: Vec<ConfigByteChange>,
+    /// Bytes that differ but that the Write command cannot change
+    /// (serial number, revision, I2C settings, UserExtra and the lock bytes), left as they are
+    pub ignored: Vec<ConfigByteChange>,
+    /// Steps executed, or that would be executed
+    pub steps: Vec<ProvisioningStep>,
+    /// The steps that cannot be undone
+    pub irreversible: Vec<ProvisioningStep>,
+}
+
+fn join_steps(steps: &[ProvisioningStep]) -> String {
+    steps
+        .iter()
+        .map(|step| step.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl Manifest {
+    /// Reads the manifest from a file in the format told by its extension,
+    /// the files it names are looked up relative to it
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Manifest, AtcaConfigError> {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        parse_file::<ManifestFile>(path)?.into_manifest(base_dir)
+    } // Manifest::from_file()
+
+    /// Reads the manifest from a string in the given format,
+    /// the files it names are looked up relative to 'base_dir'
+    pub fn from_config_str(
+        contents: &str,
+        format: ConfigFormat,
+        base_dir: &Path,
+    ) -> Result<Manifest, AtcaConfigError> {
+        parse::<ManifestFile>(contents, format)?.into_manifest(base_dir)
+    } // Manifest::from_config_str()
+
+    /// Steps of the personalization, in the order provision() executes them
+    pub fn steps(&self) -> Vec<ProvisioningStep> {
+        let mut steps: Vec<ProvisioningStep> = Vec::new();
+        if self.config_zone.is_some() {
+            steps.push(ProvisioningStep::WriteConfigZone);
+        }
+        if self.lock_config {
+            steps.push(ProvisioningStep::LockConfigZone);
+        }
+        for content in self.slots.iter() {
+            if let SlotContent::GeneratedKey { slot } = content {
+                steps.push(ProvisioningStep::GenKey(*slot));
+            }
+        }
+        if self.otp.is_some() {
+            steps.push(ProvisioningStep::WriteOtpZone);
+        }
+        if self.lock_data {
+            steps.push(ProvisioningStep::LockDataZone);
+        }
+        for access_key in self.access_keys.iter() {
+            steps.push(ProvisioningStep::AddAccessKey(access_key.slot));
+        }
+        for content in self.slots.iter() {
+            match content {
+                SlotContent::GeneratedKey { .. } => (),
+                SlotContent::PrivateKey { slot, .. }
+                | SlotContent::PublicKey { slot, .. }
+                | SlotContent::AesKey { slot, .. } => {
+                    steps.push(ProvisioningStep::ImportKey(*slot))
+                }
+                SlotContent::Data { slot, .. } => steps.push(ProvisioningStep::WriteSlot(*slot)),
+            }
+        }
+        for certificate in self.certificates.iter() {
+            steps.push(ProvisioningStep::WriteCertificate(certificate.slot));
+        }
+        steps
+    } // Manifest::steps()
+}
+
+/// Executes the personalization described by the manifest, calling 'progress'
+/// after every step. Steps that the chip has already gone through (zones already locked,
+/// locked configuration and OTP zones equal to the ones of the manifest) are skipped,
+/// so that an interrupted personalization can be run again.
+/// The configuration zone is read back once written, so that it is never locked unchecked,
+/// and the OTP zone and the public keys of the generated keys once the data zone is locked,
+/// as they cannot be read before.
+/// If a step fails, the completed steps are undone where possible, in reverse order:
+/// the configuration zone is written back while it is not locked, the slot contents
+/// that could be read before being overwritten are written back and the access keys
+/// are flushed. Nothing is undone across a lock step, not even when reading back
+/// the OTP zone or the public keys fails once the data zone is locked
+pub fn provision<F>(
+    device: &ProvisioningDevice,
+    manifest: &Manifest,
+    mut progress: F,
+) -> Result<ProvisioningReport, ProvisioningError>
+where
+    F: FnMut(&ProvisioningProgress),
+{
+    let steps = manifest.steps();
+    let total = steps.len();
+    let mut report = ProvisioningReport::default();
+    // Completed steps and what undoes them
+    let mut undo_log: Vec<(ProvisioningStep, Option<Undo>)> = Vec::new();
+
+    let mut generated = manifest.slots.iter().filter_map(|content| match content {
+        SlotContent::GeneratedKey { slot } => Some(*slot),
+        _ => None,
+    });
+    let mut written = manifest
+        .slots
+        .iter()
+        .filter(|content| !matches!(content, SlotContent::GeneratedKey { .. }));
+    let mut access_keys = manifest.access_keys.iter();
+    let mut certificates = manifest.certificates.iter();
+    // Generated keys whose public keys are read once the data zone is locked
+    let mut pending_public_keys: Vec<u8> = Vec::new();
+
+    for (index, step) in steps.iter().enumerate() {
+        let result = match step {
+            ProvisioningStep::WriteConfigZone => match &manifest.config_zone {
+                Some(config_zone) => write_config_zone(device, config_zone),
+                None => Err(AtcaStatus::AtcaBadParam),
+            },
+            ProvisioningStep::LockConfigZone => match device.is_configuration_locked() {
+                true => Ok(Outcome::Skipped),
+                false => status_outcome(device.lock_config_zone(), None),
+            },
+            ProvisioningStep::GenKey(_) => match generated.next() {
+                Some(slot) => gen_key(device, slot, &mut report, &mut pending_public_keys),
+                None => Err(AtcaStatus::AtcaBadParam),
+            },
+            ProvisioningStep::WriteOtpZone => match &manifest.otp {
+                Some(otp) => write_otp_zone(device, otp),
+                None => Err(AtcaStatus::AtcaBadParam),
+            },
+            ProvisioningStep::LockDataZone => match device.is_data_zone_locked() {
+                true => Ok(Outcome::Skipped),
+                false => match device.lock_data_zone() {
+                    AtcaStatus::AtcaSuccess => {
+                        // Nothing is written back into the locked zone,
+                        // even if what is read once it is locked is wrong
+                        forget_undo(&mut undo_log);
+                        read_after_data_lock(
+                            device,
+                            manifest.otp.as_deref(),
+                            &mut pending_public_keys,
+                            &mut report,
+                        )
+                    }
+                    err => Err(err),
+                },
+            },
+            ProvisioningStep::AddAccessKey(_) => match access_keys.next() {
+                Some(access_key) => status_outcome(
+                    device.add_access_key(access_key.slot, &access_key.key),
+                    Some(Undo::AccessKeys),
+                ),
+                None => Err(AtcaStatus::AtcaBadParam),
+            },
+            ProvisioningStep::ImportKey(_) | ProvisioningStep::WriteSlot(_) => {
+                match written.next() {
+                    Some(content) => write_slot_content(device, content),
+                    None => Err(AtcaStatus::AtcaBadParam),
+                }
+            }
+            ProvisioningStep::WriteCertificate(_) => match certificates.next() {
+                Some(certificate) => write_slot_data(
+                    device,
+                    certificate.slot,
+                    certificate.offset,
+                    &certificate.der,
+                ),
+                None => Err(AtcaStatus::AtcaBadParam),
+            },
+        };
+
+        let mut step_progress = ProvisioningProgress {
+            step: *step,
+            index: index + 1,
+            total,
+            status: AtcaStatus::AtcaSuccess,
+            skipped: false,
+        };
+        match result {
+            Ok(Outcome::Skipped) => {
+                step_progress.skipped = true;
+                report.skipped.push(*step);
+            }
+            Ok(Outcome::Done(undo)) => {
+                report.completed.push(*step);
+                if *step == ProvisioningStep::LockConfigZone {
+                    forget_undo(&mut undo_log);
+                }
+                undo_log.push((*step, undo));
+            }
+            Err(err) => {
+                step_progress.status = err;
+                progress(&step_progress);
+                return Err(roll_back(device, *step, err, undo_log));
+            }
+        }
+        progress(&step_progress);
+    }
+
+    Ok(report)
+} // provision()
+
+/// Writes the configuration zone, reads it back to check it, then locks it if 'lock' is set.
+/// With 'dry_run' nothing is written: the report tells which bytes would change
+/// and which steps could not be undone, so that a configuration can be reviewed
+/// before a part is locked with it. The configuration zone is only written if it differs
+/// and is written back if the check fails; a locked configuration zone that differs
+/// from the wanted one is an error with AtcaConfigZoneLocked, also in a dry run
+pub fn write_verify_lock(
+    device: &ProvisioningDevice,
+    config_zone: &[u8],
+    lock: bool,
+    dry_run: bool,
+) -> Result<ConfigWriteReport, ProvisioningError> {
+    let error = |status| ProvisioningError {
+        step: ProvisioningStep::WriteConfigZone,
+        status,
+        rolled_back: Vec::new(),
+        not_rolled_back: Vec::new(),
+    };
+    if config_zone.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE {
+        return Err(error(AtcaStatus::AtcaInvalidSize));
+    }
+    let mut current: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut current) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(error(err)),
+    }
+
+    let mut report = ConfigWriteReport {
+        dry_run,
+        ..Default::default()
+    };
+    for (offset, (current, wanted)) in current.iter().zip(config_zone.iter()).enumerate() {
+        let change = ConfigByteChange {
+            offset,
+            current: *current,
+            wanted: *wanted,
+        };
+        match (current == wanted, is_config_read_only(offset)) {
+            (true, _) => (),
+            (false, true) => report.ignored.push(change),
+            (false, false) => report.changes.push(change),
+        }
+    }
+    let locked = device.is_configuration_locked();
+    if !report.changes.is_empty() {
+        if locked {
+            return Err(error(AtcaStatus::AtcaConfigZoneLocked));
+        }
+        report.steps.push(ProvisioningStep::WriteConfigZone);
+    }
+    if lock && !locked {
+        report.steps.push(ProvisioningStep::LockConfigZone);
+        report.irreversible.push(ProvisioningStep::LockConfigZone);
+    }
+    if dry_run {
+        return Ok(report);
+    }
+
+    let manifest = Manifest {
+        config_zone: match report.changes.is_empty() {
+            true => None,
+            false => Some(config_zone.to_vec()),
+        },
+        lock_config: lock,
+        ..Default::default()
+    };
+    provision(device, &manifest, |_| ())?;
+    Ok(report)
+} // write_verify_lock()
+
+/// What restores the state of the chip before a step
+enum Undo {
+    ConfigZone(Vec<u8>),
+    SlotData {
+        slot: u8,
+        offset: usize,
+        data: Zeroizing<Vec<u8>>,
+    },
+    AccessKeys,
+}
+
+enum Outcome {
+    Done(Option<Undo>),
+    Skipped,
+}
+
+fn status_outcome(status: AtcaStatus, undo: Option<Undo>) -> Result<Outcome, AtcaStatus> {
+    match status {
+        AtcaStatus::AtcaSuccess => Ok(Outcome::Done(undo)),
+        err => Err(err),
+    }
+}
+
+fn is_config_read_only(offset: usize) -> bool {
+    CONFIG_READ_ONLY_BYTES
+        .iter()
+        .any(|(start, end)| (*start..*end).contains(&offset))
+}
+
+/// The configuration zone outside the bytes the Write command cannot change
+fn config_zone_matches(current: &[u8], wanted: &[u8]) -> bool {
+    current.len() == wanted.len()
+        && current
+            .iter()
+            .zip(wanted.iter())
+            .enumerate()
+            .all(|(offset, (current, wanted))| current == wanted || is_config_read_only(offset))
+}
+
+fn write_config_zone(
+    device: &ProvisioningDevice,
+    config_zone: &[u8],
+) -> Result<Outcome, AtcaStatus> {
+    let mut current: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut current) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    if device.is_configuration_locked() {
+        return match config_zone_matches(&current, config_zone) {
+            true => Ok(Outcome::Skipped),
+            false => Err(AtcaStatus::AtcaConfigZoneLocked),
+        };
+    }
+    match device.write_config_zone(config_zone) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    // Read it back, a configuration zone is better not locked before being checked
+    let mut written: Vec<u8> = Vec::new();
+    let status = match device.read_config_zone(&mut written) {
+        AtcaStatus::AtcaSuccess if config_zone_matches(&written, config_zone) => {
+            return Ok(Outcome::Done(Some(Undo::ConfigZone(current))))
+        }
+        AtcaStatus::AtcaSuccess => AtcaStatus::AtcaFuncFail,
+        err => err,
+    };
+    device.write_config_zone(&current);
+    Err(status)
+}
+
+/// Writes the OTP zone, or checks its contents once the data zone is locked
+fn write_otp_zone(device: &ProvisioningDevice, otp: &[u8]) -> Result<Outcome, AtcaStatus> {
+    let report = write_otp_zone_verified(device, 0, otp);
+    match report.status {
+        AtcaStatus::AtcaSuccess if report.written == 0 => Ok(Outcome::Skipped),
+        AtcaStatus::AtcaSuccess => Ok(Outcome::Done(None)),
+        err => Err(err),
+    }
+}
+
+/// Nothing before a lock can be undone any more
+fn forget_undo(undo_log: &mut [(ProvisioningStep, Option<Undo>)]) {
+    for (_, undo) in undo_log.iter_mut() {
+        *undo = None;
+    }
+}
+
+/// Once the data zone is locked, reads back the OTP zone and the public keys
+/// of the generated keys, which could not be read before
+fn read_after_data_lock(
+    device: &ProvisioningDevice,
+    otp: Option<&[u8]>,
+    pending: &mut Vec<u8>,
+    report: &mut ProvisioningReport,
+) -> Result<Outcome, AtcaStatus> {
+    if let Some(otp) = otp {
+        match write_otp_zone_verified(device, 0, otp).status {
+            AtcaStatus::AtcaSuccess => (),
+            err => return Err(err),
+        }
+    }
+    for slot in pending.drain(..) {
+        report.public_keys.push((slot, device.public_key(slot)?));
+    }
+    Ok(Outcome::Done(None))
+}
+
+/// Generates the key of the slot. Its public key cannot be read before the data zone
+/// is locked, until then the slot is added to 'pending'
+fn gen_key(
+    device: &ProvisioningDevice,
+    slot: u8,
+    report: &mut ProvisioningReport,
+    pending: &mut Vec<u8>,
+) -> Result<Outcome, AtcaStatus> {
+    match device.gen_key(KeyType::P256EccKey, slot) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    match device.is_data_zone_locked() {
+        true => report.public_keys.push((slot, device.public_key(slot)?)),
+        false => pending.push(slot),
+    }
+    Ok(Outcome::Done(None))
+}
+
+fn write_slot_content(
+    device: &ProvisioningDevice,
+    content: &SlotContent,
+) -> Result<Outcome, AtcaStatus> {
+    match content {
+        SlotContent::GeneratedKey { .. } => Err(AtcaStatus::AtcaBadParam),
+        SlotContent::PrivateKey { slot, key } | SlotContent::AesKey { slot, key } => {
+            let key_type = match content {
+                SlotContent::AesKey { .. } => KeyType::Aes,
+                _ => KeyType::P256EccKey,
+            };
+            status_outcome(device.import_key(key_type, key, *slot), None)
+        }
+        SlotContent::PublicKey { slot, key } => {
+            status_outcome(device.import_key(KeyType::P256EccKey, key, *slot), None)
+        }
+        SlotContent::Data { slot, offset, data } => write_slot_data(device, *slot, *offset, data),
+    }
+}
+
+/// Writes a slot, keeping its previous contents if the slot can be read
+fn write_slot_data(
+    device: &ProvisioningDevice,
+    slot: u8,
+    offset: usize,
+    data: &[u8],
+) -> Result<Outcome, AtcaStatus> {
+    let undo = device
+        .read_slot_data(slot, offset, data.len())
+        .ok()
+        .map(|previous| Undo::SlotData {
+            slot,
+            offset,
+            data: Zeroizing::new(previous),
+        });
+    status_outcome(device.write_slot_data(slot, offset, data), undo)
+}
+
+/// Undoes the completed steps that can be undone, the most recent first
+fn roll_back(
+    device: &ProvisioningDevice,
+    step: ProvisioningStep,
+    status: AtcaStatus,
+    mut undo_log: Vec<(ProvisioningStep, Option<Undo>)>,
+) -> ProvisioningError {
+    let mut error = ProvisioningError {
+        step,
+        status,
+        rolled_back: Vec::new(),
+        not_rolled_back: Vec::new(),
+    };
+    let mut access_keys_flushed = None;
+    while let Some((completed, undo)) = undo_log.pop() {
+        let result = match undo {
+            Some(Undo::ConfigZone(config_zone)) => device.write_config_zone(&config_zone),
+            Some(Undo::SlotData { slot, offset, data }) => {
+                device.write_slot_data(slot, offset, &data)
+            }
+            Some(Undo::AccessKeys) => {
+                *access_keys_flushed.get_or_insert_with(|| device.flush_access_keys())
+            }
+            None => AtcaStatus::AtcaUnimplemented,
+        };
+        match result {
+            AtcaStatus::AtcaSuccess => error.rolled_back.push(completed),
+            _ => error.not_rolled_back.push(completed),
+        }
+    }
+    error.rolled_back.reverse();
+    error.not_rolled_back.reverse();
+    error
+} // roll_back()
+
+//--------------------------------------------------
+//
+// Manifest file
+//
+//--------------------------------------------------
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestFile {
+    config_zone: Option<String>,
+    #[serde(default)]
+    lock_config: bool,
+    #[serde(default, rename = "access_key")]
+    access_keys: Vec<AccessKeyEntry>,
+    #[serde(default, rename = "slot")]
+    slots: Vec<SlotEntry>,
+    #[serde(default, rename = "certificate")]
+    certificates: Vec<CertificateEntry>,
+    otp: Option<String>,
+    #[serde(default)]
+    lock_data: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AccessKeyEntry {
+    slot: u8,
+    key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case", deny_unknown_fields)]
+enum SlotEntry {
+    Generate {
+        slot: u8,
+    },
+    ImportPrivateKey {
+        slot: u8,
+        file: String,
+    },
+    ImportPublicKey {
+        slot: u8,
+        key: String,
+    },
+    ImportAesKey {
+        slot: u8,
+        key: String,
+    },
+    Write {
+        slot: u8,
+        #[serde(default)]
+        offset: usize,
+        data: String,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CertificateEntry {
+    slot: u8,
+    #[serde(default)]
+    offset: usize,
+    file: String,
+}
+
+impl ManifestFile {
+    /// Decodes and checks the values, reading the files they name
+    fn into_manifest(self, base_dir: &Path) -> Result<Manifest, AtcaConfigError> {
+        let config_zone = match self.config_zone {
+            Some(config_zone) => Some(hex_setting(
+                "config_zone",
+                &config_zone,
+                Some(ATCA_ATECC_CONFIG_BUFFER_SIZE),
+            )?),
+            None => None,
+        };
+        let otp = match self.otp {
+            Some(otp) => {
+                let otp = hex_setting("otp", &otp, None)?;
+                if check_otp_range(0, otp.len()).is_err() {
+                    return Err(AtcaConfigError::InvalidSetting(
+                        "otp",
+                        format!("{} bytes, not words fitting in the OTP zone", otp.len()),
+                    ));
+                }
+                Some(otp)
+            }
+            None => None,
+        };
+
+        let mut access_keys: Vec<AccessKey> = Vec::new();
+        for entry in self.access_keys.into_iter() {
+            access_keys.push(AccessKey {
+                slot: slot_setting(entry.slot)?,
+                key: Zeroizing::new(hex_setting("key", &entry.key, Some(ATCA_KEY_SIZE))?),
+            });
+        }
+
+        let mut slots: Vec<SlotContent> = Vec::new();
+        for entry in self.slots.into_iter() {
+            slots.push(match entry {
+                SlotEntry::Generate { slot } => SlotContent::GeneratedKey {
+                    slot: slot_setting(slot)?,
+                },
+                SlotEntry::ImportPrivateKey { slot, file } => {
+                    let path = base_dir.join(file);
+                    let contents = Zeroizing::new(read_file(&path)?);
+                    let key = match std::str::from_utf8(&contents) {
+                        Ok(pem) if pem.trim_start().starts_with("-----BEGIN") => {
+                            private_key_from_pem(pem)
+                        }
+                        _ => private_key_from_der(&contents),
+                    }
+                    .map_err(|err| {
+                        AtcaConfigError::InvalidSetting(
+                            "file",
+                            format!("{}: {}", path.display(), err),
+                        )
+                    })?;
+                    SlotContent::PrivateKey {
+                        slot: slot_setting(slot)?,
+                        key: Zeroizing::new(key),
+                    }
+                }
+                SlotEntry::ImportPublicKey { slot, key } => SlotContent::PublicKey {
+                    slot: slot_setting(slot)?,
+                    key: hex_setting("key", &key, Some(ATCA_ATECC_PUB_KEY_SIZE))?,
+                },
+                SlotEntry::ImportAesKey { slot, key } => SlotContent::AesKey {
+                    slot: slot_setting(slot)?,
+                    key: Zeroizing::new(hex_setting("key", &key, Some(ATCA_AES_KEY_SIZE))?),
+                },
+                SlotEntry::Write { slot, offset, data } => SlotContent::Data {
+                    slot: slot_setting(slot)?,
+                    offset,
+                    data: hex_setting("data", &data, None)?,
+                },
+            });
+        }
+
+        let mut certificates: Vec<Certificate> = Vec::new();
+        for entry in self.certificates.into_iter() {
+            let path = base_dir.join(entry.file);
+            let contents = read_file(&path)?;
+            let der = match std::str::from_utf8(&contents) {
+                Ok(pem) if pem.trim_start().starts_with("-----BEGIN") => {
+                    pem_decode(pem, "CERTIFICATE").map_err(|err| {
+                        AtcaConfigError::InvalidSetting(
+                            "file",
+                            format!("{}: {}", path.display(), err),
+                        )
+                    })?
+                }
+                _ => contents,
+            };
+            certificates.push(Certificate {
+                slot: slot_setting(entry.slot)?,
+                offset: entry.offset,
+                der,
+            });
+        }
+
+        Ok(Manifest {
+            config_zone,
+            lock_config: self.lock_config,
+            access_keys,
+            slots,
+            certificates,
+            otp,
+            lock_data: self.lock_data,
+        })
+    } // ManifestFile::into_manifest()
+}
+
+fn slot_setting(slot: u8) -> Result<u8, AtcaConfigError> {
+    match slot < ATCA_ATECC_SLOTS_COUNT {
+        true => Ok(slot),
+        false => Err(AtcaConfigError::InvalidSetting(
+            "slot",
+            format!("{} is not a slot number", slot),
+        )),
+    }
+}
+
+/// Bytes given as a hex string, of the given length if there is one
+fn hex_setting(
+    name: &'static str,
+    value: &str,
+    len: Option<usize>,
+) -> Result<Vec<u8>, AtcaConfigError> {
+    let bytes: Option<Vec<u8>> = (0..value.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(value.get(idx..idx + 2)?, 16).ok())
+        .collect();
+    match (bytes, len) {
+        (Some(bytes), Some(len)) if bytes.len() != len => Err(AtcaConfigError::InvalidSetting(
+            name,
+            format!("{} bytes instead of {}", bytes.len(), len),
+        )),
+        (Some(bytes), _) if !bytes.is_empty() => Ok(bytes),
+        _ => Err(AtcaConfigError::InvalidSetting(
+            name,
+            "not a non-empty hex string".to_owned(),
+        )),
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, AtcaConfigError> {
+    fs::read(path).map_err(|err| AtcaConfigError::Io(format!("{}: {}", path.display(), err)))
+}
@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+use std::cmp::Ordering;
+
+use super::AtcaStatus;
+use super::{
+    ATCA_ATECC_PUB_KEY_SIZE, ATCA_SEC1_COMPRESSED_PUB_KEY_SIZE, ATCA_SEC1_UNCOMPRESSED_PUB_KEY_SIZE,
+};
+
+const SEC1_TAG_COMPRESSED_EVEN: u8 = 0x02;
+const SEC1_TAG_COMPRESSED_ODD: u8 = 0x03;
+const SEC1_TAG_UNCOMPRESSED: u8 = 0x04;
+const COORDINATE_SIZE: usize = ATCA_ATECC_PUB_KEY_SIZE / 2;
+
+/// 256-bit number, least significant limb first
+type U256 = [u64; 4];
+
+/// Prime of the field over which curve P256 is defined
+const P256_P: U256 = [
+    0xFFFF_FFFF_FFFF_FFFF,
+    0x0000_0000_FFFF_FFFF,
+    0x0000_0000_0000_0000,
+    0xFFFF_FFFF_0000_0001,
+];
+/// Coefficient 'b' of curve P256 (y^2 = x^3 - 3x + b)
+const P256_B: U256 = [
+    0x3BCE_3C3E_27D2_604B,
+    0x651D_06B0_CC53_B0F6,
+    0xB3EB_BD55_7698_86BC,
+    0x5AC6_35D8_AA3A_93E7,
+];
+/// (p + 1) / 4, the exponent giving a square root modulo p
+const P256_SQRT_EXP: U256 = [
+    0x0000_0000_0000_0000,
+    0x0000_0000_4000_0000,
+    0x4000_0000_0000_0000,
+    0x3FFF_FFFF_C000_0000,
+];
+
+/// Converts a public key in the ATECC format (X || Y) into the SEC1 compressed encoding
+pub fn public_key_compress(public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let (x, y) = public_key.split_at(COORDINATE_SIZE);
+    if !is_on_curve(&u256_from_bytes(x), &u256_from_bytes(y)) {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let mut result: Vec<u8> = Vec::with_capacity(ATCA_SEC1_COMPRESSED_PUB_KEY_SIZE);
+    result.push(match y[COORDINATE_SIZE - 1] & 0x01 {
+        0x00 => SEC1_TAG_COMPRESSED_EVEN,
+        _ => SEC1_TAG_COMPRESSED_ODD,
+    });
+    result.extend_from_slice(x);
+    Ok(result)
+} // public_key_compress()
+
+/// Converts a public key in the SEC1 compressed encoding into the ATECC format (X || Y),
+/// recovering the Y coordinate from the curve equation
+pub fn public_key_decompress(public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    if public_key.len() != ATCA_SEC1_COMPRESSED_PUB_KEY_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let is_odd = match public_key[0] {
+        SEC1_TAG_COMPRESSED_EVEN => false,
+        SEC1_TAG_COMPRESSED_ODD => true,
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+    let x = u256_from_bytes(&public_key[1..]);
+    if u256_cmp(&x, &P256_P) != Ordering::Less {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let y_squared = curve_rhs(&x);
+    let mut y = pow_mod(&y_squared, &P256_SQRT_EXP);
+    if mul_mod(&y, &y) != y_squared {
+        // X is not a coordinate of any point of the curve
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    if ((y[0] & 0x01) == 0x01) != is_odd {
+        y = sub_mod(&[0; 4], &y);
+    }
+
+    let mut result: Vec<u8> = Vec::with_capacity(ATCA_ATECC_PUB_KEY_SIZE);
+    result.extend_from_slice(&public_key[1..]);
+    result.extend_from_slice(&u256_to_bytes(&y));
+    Ok(result)
+} // public_key_decompress()
+
+/// Converts a public key in the ATECC format (X || Y) into the SEC1 uncompressed encoding
+pub fn public_key_to_sec1(public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let mut result: Vec<u8> = Vec::with_capacity(ATCA_SEC1_UNCOMPRESSED_PUB_KEY_SIZE);
+    result.push(SEC1_TAG_UNCOMPRESSED);
+    result.extend_from_slice(public_key);
+    Ok(result)
+} // public_key_to_sec1()
+
+/// Converts a public key in the SEC1 encoding, either compressed or uncompressed,
+/// into the ATECC format (X || Y)
+pub fn public_key_from_sec1(public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    match public_key.len() {
+        ATCA_SEC1_COMPRESSED_PUB_KEY_SIZE => public_key_decompress(public_key),
+        ATCA_SEC1_UNCOMPRESSED_PUB_KEY_SIZE => {
+            if public_key[0] != SEC1_TAG_UNCOMPRESSED {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+            let (x, y) = public_key[1..].split_at(COORDINATE_SIZE);
+            match is_on_curve(&u256_from_bytes(x), &u256_from_bytes(y)) {
+                true => Ok(public_key[1..].to_vec()),
+                false => Err(AtcaStatus::AtcaBadParam),
+            }
+        }
+        _ => Err(AtcaStatus::AtcaInvalidSize),
+    }
+} // public_key_from_sec1()
+
+/// Checks whether the point belongs to curve P256
+fn is_on_curve(x: &U256, y: &U256) -> bool {
+    (u256_cmp(x, &P256_P) == Ordering::Less)
+        && (u256_cmp(y, &P256_P) == Ordering::Less)
+        && (mul_mod(y, y) == curve_rhs(x))
+}
+
+/// Calculates x^3 - 3x + b (mod p)
+fn curve_rhs(x: &U256) -> U256 {
+    let x_cubed = mul_mod(&mul_mod(x, x), x);
+    let three_x = add_mod(&add_mod(x, x), x);
+    add_mod(&sub_mod(&x_cubed, &three_x), &P256_B)
+}
+
+fn u256_from_bytes(bytes: &[u8]) -> U256 {
+    let mut result: U256 = [0; 4];
+    for (idx, chunk) in bytes.rchunks(8).enumerate() {
+        let mut limb = [0u8; 8];
+        limb.copy_from_slice(chunk);
+        result[idx] = u64::from_be_bytes(limb);
+    }
+    result
+}
+
+fn u256_to_bytes(value: &U256) -> Vec<u8> {
+    value
+        .iter()
+        .rev()
+        .flat_map(|limb| limb.to_be_bytes())
+        .collect()
+}
+
+fn u256_cmp(a: &U256, b: &U256) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Adds two numbers, returns the sum modulo 2^256 and the carry
+fn u256_add(a: &U256, b: &U256) -> (U256, bool) {
+    let mut result: U256 = [0; 4];
+    let mut carry = false;
+    for idx in 0..4 {
+        let (sum, c1) = a[idx].overflowing_add(b[idx]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        result[idx] = sum;
+        carry = c1 || c2;
+    }
+    (result, carry)
+}
+
+/// Subtracts two numbers, returns the difference modulo 2^256 and the borrow
+fn u256_sub(a: &U256, b: &U256) -> (U256, bool) {
+    let mut result: U256 = [0; 4];
+    let mut borrow = false;
+    for idx in 0..4 {
+        let (diff, b1) = a[idx].overflowing_sub(b[idx]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        result[idx] = diff;
+        borrow = b1 || b2;
+    }
+    (result, borrow)
+}
+
+fn add_mod(a: &U256, b: &U256) -> U256 {
+    let (sum, carry) = u256_add(a, b);
+    if carry || (u256_cmp(&sum, &P256_P) != Ordering::Less) {
+        u256_sub(&sum, &P256_P).0
+    } else {
+        sum
+    }
+}
+
+fn sub_mod(a: &U256, b: &U256) -> U256 {
+    let (diff, borrow) = u256_sub(a, b);
+    if borrow {
+        u256_add(&diff, &P256_P).0
+    } else {
+        diff
+    }
+}
+
+/// Multiplies two numbers modulo p using the double-and-add method.
+/// It is slow, but public keys are not secret and conversions are rare.
+fn mul_mod(a: &U256, b: &U256) -> U256 {
+    let mut result: U256 = [0; 4];
+    for bit in (0..256).rev() {
+        result = add_mod(&result, &result);
+        if (b[bit / 64] >> (bit % 64)) & 0x01 == 0x01 {
+            result = add_mod(&result, a);
+        }
+    }
+    result
+}
+
+fn pow_mod(base: &U256, exponent: &U256) -> U256 {
+    let mut result: U256 = [1, 0, 0, 0];
+    for bit in (0..256).rev() {
+        result = mul_mod(&result, &result);
+        if (exponent[bit / 64] >> (bit % 64)) & 0x01 == 0x01 {
+            result = mul_mod(&result, base);
+        }
+    }
+    result
+}
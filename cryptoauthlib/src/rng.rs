@@ -0,0 +1,92 @@
+use alloc::vec::Vec;
+use rand::{CryptoRng, Error, RngCore};
+
+use super::{AtcaStatus, AteccDeviceTrait, ATCA_RANDOM_BUFFER_SIZE};
+
+/// Random number generator drawing from the TRNG of ATECC.
+/// The chip returns 32 random bytes per command, they are buffered
+/// so that small requests do not each cost a command.
+/// Note that ATECC returns random numbers only when its configuration zone is locked.
+pub struct AteccRng<'a> {
+    device: &'a dyn AteccDeviceTrait,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<'a> AteccRng<'a> {
+    /// Creates a generator using the given device
+    pub fn new(device: &'a dyn AteccDeviceTrait) -> Self {
+        AteccRng {
+            device,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    } // AteccRng::new()
+
+    /// Fetches the next block of random bytes from the chip
+    fn refill(&mut self) -> Result<(), AtcaStatus> {
+        let result = self.device.random(&mut self.buffer);
+        if AtcaStatus::AtcaSuccess != result {
+            self.buffer.clear();
+            self.position = 0;
+            return Err(result);
+        }
+        self.buffer.truncate(ATCA_RANDOM_BUFFER_SIZE);
+        self.position = 0;
+        Ok(())
+    } // AteccRng::refill()
+}
+
+impl RngCore for AteccRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Panics if ATECC fails to deliver random bytes, use try_fill_bytes() to handle that
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(err) = self.try_fill_bytes(dest) {
+            panic!("ATECC random number generation failed: {}", err);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        let mut filled: usize = 0;
+        while filled < dest.len() {
+            if self.position >= self.buffer.len() {
+                self.refill().map_err(random_error)?;
+            }
+            let size = (dest.len() - filled).min(self.buffer.len() - self.position);
+            dest[filled..(filled + size)]
+                .copy_from_slice(&self.buffer[self.position..(self.position + size)]);
+            // Bytes handed out are not kept around
+            self.buffer[self.position..(self.position + size)]
+                .iter_mut()
+                .for_each(|x| *x = 0x00);
+            self.position += size;
+            filled += size;
+        }
+        Ok(())
+    } // AteccRng::try_fill_bytes()
+}
+
+impl CryptoRng for AteccRng<'_> {}
+
+#[cfg(feature = "std")]
+fn random_error(status: AtcaStatus) -> Error {
+    Error::new(status)
+}
+
+/// Without std the error holds a code only, the one of the status follows Error::CUSTOM_START
+#[cfg(not(feature = "std"))]
+fn random_error(status: AtcaStatus) -> Error {
+    let code = Error::CUSTOM_START + status as u32;
+    Error::from(core::num::NonZeroU32::new(code).expect("Custom error codes are not zero"))
+}
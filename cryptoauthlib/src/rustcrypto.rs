@@ -0,0 +1,156 @@
+use alloc::vec::Vec;
+use cipher::consts::{U1, U16};
+use cipher::inout::InOut;
+use cipher::{
+    Block, BlockBackend, BlockCipher, BlockClosure, BlockDecrypt, BlockEncrypt, BlockSizeUser,
+    ParBlocksSizeUser,
+};
+use p256::ecdsa::Signature;
+use signature::{Error, Signer, Verifier};
+
+use super::{signature_error, AtcaStatus, AteccDeviceTrait, VerifyMode};
+
+/// ECDSA P256 signer and verifier using the key stored in a slot of ATECC,
+/// for crates built on the RustCrypto 'signature' traits.
+/// Signing is done with the private key on the chip, verification
+/// with the public key derived from it when the signer was created.
+pub struct SlotSigner<'a> {
+    device: &'a dyn AteccDeviceTrait,
+    slot_id: u8,
+    public_key: Vec<u8>,
+}
+
+impl<'a> SlotSigner<'a> {
+    /// Creates a signer for the key stored in the given slot
+    pub fn new(device: &'a dyn AteccDeviceTrait, slot_id: u8) -> Result<Self, AtcaStatus> {
+        let mut public_key: Vec<u8> = Vec::new();
+        match device.get_public_key(slot_id, &mut public_key) {
+            AtcaStatus::AtcaSuccess => Ok(SlotSigner {
+                device,
+                slot_id,
+                public_key,
+            }),
+            err => Err(err),
+        }
+    } // SlotSigner::new()
+
+    /// Slot holding the private key
+    pub fn slot_id(&self) -> u8 {
+        self.slot_id
+    }
+
+    /// Raw X || Y public key matching the private key in the slot
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+}
+
+impl Signer<Signature> for SlotSigner<'_> {
+    /// Hashes the message (SHA256) and signs the digest, both on the chip
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature, Error> {
+        let signature = self
+            .device
+            .sign_message(self.slot_id, msg)
+            .map_err(signature_error)?;
+        Signature::from_slice(&signature)
+    } // SlotSigner::try_sign()
+}
+
+impl Verifier<Signature> for SlotSigner<'_> {
+    /// Hashes the message (SHA256) and verifies the signature, both on the chip
+    fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), Error> {
+        let mut digest: Vec<u8> = Vec::new();
+        let result = self.device.sha(msg.to_vec(), &mut digest);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(signature_error(result));
+        }
+        match self.device.verify_hash(
+            VerifyMode::External(self.public_key.clone()),
+            &digest,
+            &signature.to_bytes(),
+        ) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(Error::new()),
+            Err(err) => Err(signature_error(err)),
+        }
+    } // SlotSigner::verify()
+}
+
+/// AES-128 block cipher using a key stored in a slot of ATECC,
+/// for the modes of operation of the RustCrypto 'cipher' crates.
+/// Every block is processed by the chip, the key never leaves it.
+/// The 'cipher' traits cannot report errors, so a failing chip operation panics.
+pub struct SlotAes<'a> {
+    device: &'a dyn AteccDeviceTrait,
+    slot_id: u8,
+    key_block: u8,
+}
+
+impl<'a> SlotAes<'a> {
+    /// Creates a cipher for the AES key at position 'key_block'
+    /// of the given slot (a slot may hold several 16-byte keys)
+    pub fn new(device: &'a dyn AteccDeviceTrait, slot_id: u8, key_block: u8) -> Self {
+        SlotAes {
+            device,
+            slot_id,
+            key_block,
+        }
+    } // SlotAes::new()
+}
+
+impl BlockSizeUser for SlotAes<'_> {
+    type BlockSize = U16;
+}
+
+impl BlockCipher for SlotAes<'_> {}
+
+impl BlockEncrypt for SlotAes<'_> {
+    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut SlotAesBackend {
+            cipher: self,
+            encrypt: true,
+        })
+    }
+}
+
+impl BlockDecrypt for SlotAes<'_> {
+    fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+        f.call(&mut SlotAesBackend {
+            cipher: self,
+            encrypt: false,
+        })
+    }
+}
+
+/// Processes blocks one at a time, as ATECC does
+struct SlotAesBackend<'a, 'b> {
+    cipher: &'a SlotAes<'b>,
+    encrypt: bool,
+}
+
+impl BlockSizeUser for SlotAesBackend<'_, '_> {
+    type BlockSize = U16;
+}
+
+impl ParBlocksSizeUser for SlotAesBackend<'_, '_> {
+    type ParBlocksSize = U1;
+}
+
+impl BlockBackend for SlotAesBackend<'_, '_> {
+    fn proc_block(&mut self, mut block: InOut<'_, '_, Block<Self>>) {
+        let cipher = self.cipher;
+        let key_id = cipher.slot_id as u16;
+        let result = match self.encrypt {
+            true => cipher
+                .device
+                .aes_encrypt_block(key_id, cipher.key_block, block.get_in()),
+            false => cipher
+                .device
+                .aes_decrypt_block(key_id, cipher.key_block, block.get_in()),
+        };
+        match result {
+            Ok(output) => block.get_out().copy_from_slice(&output),
+            Err(err) => panic!("ATECC AES operation failed: {}", err),
+        }
+    } // SlotAesBackend::proc_block()
+}
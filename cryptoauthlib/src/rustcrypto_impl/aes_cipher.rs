@@ -0,0 +1,541 @@
+use alloc::vec::Vec;
+use std::cmp::min;
+use std::convert::TryFrom;
+
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes128;
+
+use super::{get_slot_capacity, SoftwareBackend};
+use super::{AtcaStatus, CipherOperation, CipherParam, FeedbackMode, KeyType, NonceTarget, SlotId};
+
+use super::{ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_NONCE_SIZE};
+
+use cryptoauthlib_sys::atca_aes_cbc_ctx_t;
+use cryptoauthlib_sys::atca_aes_ctr_ctx_t;
+
+impl SoftwareBackend {
+    /// Function that performs encryption/decryption in AES ECB mode
+    pub(crate) fn cipher_aes_ecb(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut [u8],
+        operation: CipherOperation,
+    ) -> AtcaStatus {
+        const BLOCK_IDX: u8 = 0;
+
+        let slot: u16 = match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        if !data.len().is_multiple_of(ATCA_AES_DATA_SIZE) {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        for idx in 0..(data.len() / ATCA_AES_DATA_SIZE) {
+            let start_pos = idx * ATCA_AES_DATA_SIZE;
+            let end_pos = start_pos + ATCA_AES_DATA_SIZE;
+
+            match operation {
+                CipherOperation::Encrypt => {
+                    match self.aes_encrypt_block(slot, BLOCK_IDX, &data[start_pos..end_pos]) {
+                        Ok(encr_block) => data[start_pos..end_pos].clone_from_slice(&encr_block),
+                        Err(err) => return err,
+                    }
+                }
+                CipherOperation::Decrypt => {
+                    match self.aes_decrypt_block(slot, BLOCK_IDX, &data[start_pos..end_pos]) {
+                        Ok(decr_block) => data[start_pos..end_pos].clone_from_slice(&decr_block),
+                        Err(err) => return err,
+                    }
+                }
+            }
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::cipher_aes_ecb()
+
+    /// Function that performs encryption/decryption in AES CBC mode
+    pub(crate) fn cipher_aes_cbc(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut [u8],
+        operation: CipherOperation,
+    ) -> AtcaStatus {
+        match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
+            Ok(_) => (),
+            Err(err) => return err,
+        }
+
+        if !data.len().is_multiple_of(ATCA_AES_DATA_SIZE) {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        if cipher_param.iv.is_none() {
+            return AtcaStatus::AtcaBadParam;
+        }
+
+        let mut ctx: atca_aes_cbc_ctx_t;
+        match self.aes_cbc_init(slot_id, &cipher_param.iv.unwrap()) {
+            Ok(val) => ctx = val,
+            Err(err) => return err,
+        }
+
+        let mut block: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+
+        for idx in 0..(data.len() / ATCA_AES_DATA_SIZE) {
+            let start_pos = idx * ATCA_AES_DATA_SIZE;
+            let end_pos = start_pos + ATCA_AES_DATA_SIZE;
+
+            match operation {
+                CipherOperation::Encrypt => {
+                    match self.aes_cbc_encrypt_block(ctx, &data[start_pos..end_pos], &mut block) {
+                        Ok(val) => ctx = val,
+                        Err(err) => return err,
+                    }
+                }
+                CipherOperation::Decrypt => {
+                    match self.aes_cbc_decrypt_block(ctx, &data[start_pos..end_pos], &mut block) {
+                        Ok(val) => ctx = val,
+                        Err(err) => return err,
+                    }
+                }
+            }
+            data[start_pos..end_pos].clone_from_slice(&block)
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::cipher_aes_cbc()
+
+    /// Function that performs encryption/decryption in AES CBC with PKCS#7 padding mode
+    pub(crate) fn cipher_aes_cbc_pkcs7(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+        operation: CipherOperation,
+    ) -> AtcaStatus {
+        if data.is_empty() {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        if operation == CipherOperation::Encrypt {
+            let padding: u8 = (ATCA_AES_DATA_SIZE - (data.len() % ATCA_AES_DATA_SIZE)) as u8;
+            let extended_data: Vec<u8> = vec![padding; padding as usize];
+
+            data.extend_from_slice(&extended_data);
+        }
+
+        let result = self.cipher_aes_cbc(cipher_param, slot_id, data, operation);
+        if AtcaStatus::AtcaSuccess != result {
+            return result;
+        }
+
+        if operation == CipherOperation::Decrypt {
+            let padding: u8 = data[data.len() - 1];
+            if padding as usize > ATCA_AES_DATA_SIZE {
+                return AtcaStatus::AtcaInvalidSize;
+            }
+            data.resize(data.len() - padding as usize, 0x00);
+            data.shrink_to_fit();
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::cipher_aes_cbc_pkcs7()
+
+    /// Function that performs encryption/decryption in AES CTR mode
+    pub(crate) fn cipher_aes_ctr(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut [u8],
+    ) -> AtcaStatus {
+        match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
+            Ok(_) => (),
+            Err(err) => return err,
+        }
+        if cipher_param.iv.is_none() || cipher_param.counter_size.is_none() {
+            return AtcaStatus::AtcaBadParam;
+        }
+        if cipher_param.counter_size.is_some()
+            && (cipher_param.counter_size.unwrap() > (ATCA_AES_DATA_SIZE as u8))
+        {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        let mut ctx: atca_aes_ctr_ctx_t;
+        match self.aes_ctr_init(
+            slot_id,
+            cipher_param.counter_size.unwrap(),
+            &cipher_param.iv.unwrap(),
+        ) {
+            Ok(val) => ctx = val,
+            Err(err) => return err,
+        }
+
+        let mut input: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        let mut output: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        let mut start_pos: usize = 0;
+        let mut shift: usize = min(data.len(), ATCA_AES_DATA_SIZE);
+
+        while shift > 0 {
+            input[..shift].clone_from_slice(&data[start_pos..(start_pos + shift)]);
+
+            match self.aes_ctr_block(ctx, &input, &mut output) {
+                Ok(val) => ctx = val,
+                Err(err) => return err,
+            }
+
+            data[start_pos..(start_pos + shift)].clone_from_slice(&output[..shift]);
+
+            start_pos += shift;
+            let remaining_bytes = data.len() - start_pos;
+            match 0 == remaining_bytes {
+                true => shift = 0,
+                false => {
+                    if remaining_bytes < ATCA_AES_DATA_SIZE {
+                        shift = remaining_bytes
+                    }
+                }
+            }
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::cipher_aes_ctr()
+
+    /// Function that performs encryption/decryption in AES CFB mode
+    pub(crate) fn cipher_aes_cfb(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut [u8],
+        operation: CipherOperation,
+    ) -> AtcaStatus {
+        self.cipher_aes_feedback(cipher_param, slot_id, data, operation, FeedbackMode::Cfb)
+    } // SoftwareBackend::cipher_aes_cfb()
+
+    /// Function that performs encryption/decryption in AES OFB mode
+    pub(crate) fn cipher_aes_ofb(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut [u8],
+        operation: CipherOperation,
+    ) -> AtcaStatus {
+        self.cipher_aes_feedback(cipher_param, slot_id, data, operation, FeedbackMode::Ofb)
+    } // SoftwareBackend::cipher_aes_ofb()
+
+    /// Function that performs common encryption/decryption in AES cipher feedback modes
+    fn cipher_aes_feedback(
+        &self,
+        cipher_param: CipherParam,
+        slot_id: u8,
+        data: &mut [u8],
+        operation: CipherOperation,
+        mode: FeedbackMode,
+    ) -> AtcaStatus {
+        const BLOCK_IDX: u8 = 0;
+
+        let slot: u16 = match self.cipher_aes_common(slot_id, data.len(), cipher_param.key) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+        if cipher_param.iv.is_none() {
+            return AtcaStatus::AtcaBadParam;
+        }
+
+        let mut block: Vec<u8> = vec![0x00; ATCA_AES_DATA_SIZE];
+        let mut start_pos: usize = 0;
+        let mut shift: usize = min(data.len(), ATCA_AES_DATA_SIZE);
+        block.clone_from_slice(&cipher_param.iv.unwrap());
+
+        while shift > 0 {
+            if CipherOperation::Encrypt == operation {
+                match self.aes_encrypt_block(slot, BLOCK_IDX, &block) {
+                    Ok(encr_block) => {
+                        block = encr_block
+                            .iter()
+                            .zip(data[start_pos..(shift + start_pos)].iter())
+                            .map(|(&x1, &x2)| x1 ^ x2)
+                            .collect();
+                        data[start_pos..(shift + start_pos)].clone_from_slice(&block);
+                        if FeedbackMode::Ofb == mode && (ATCA_AES_DATA_SIZE == shift) {
+                            block[..shift].clone_from_slice(&encr_block[..shift])
+                        }
+                    }
+                    Err(err) => return err,
+                }
+            } else {
+                match self.aes_encrypt_block(slot, BLOCK_IDX, &block) {
+                    Ok(mut decr_block) => {
+                        if FeedbackMode::Cfb == mode {
+                            block[..shift].clone_from_slice(&data[start_pos..(shift + start_pos)]);
+                            decr_block
+                                .iter_mut()
+                                .zip(block.iter())
+                                .for_each(|(x1, x2)| *x1 ^= *x2);
+                            data[start_pos..(shift + start_pos)]
+                                .clone_from_slice(&decr_block[..shift]);
+                        } else {
+                            block = decr_block
+                                .iter()
+                                .zip(data[start_pos..(shift + start_pos)].iter())
+                                .map(|(&x1, &x2)| x1 ^ x2)
+                                .collect();
+                            data[start_pos..(shift + start_pos)].clone_from_slice(&block);
+                            if ATCA_AES_DATA_SIZE == shift {
+                                block.clone_from_slice(&decr_block)
+                            }
+                        }
+                    }
+                    Err(err) => return err,
+                }
+            }
+
+            start_pos += shift;
+            let remaining_bytes = data.len() - start_pos;
+            match 0 == remaining_bytes {
+                true => shift = 0,
+                false => {
+                    if remaining_bytes < ATCA_AES_DATA_SIZE {
+                        shift = remaining_bytes
+                    }
+                }
+            }
+        }
+
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::cipher_aes_feedback()
+
+    /// Initialize context for AES CTR operation with an existing IV
+    pub(crate) fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        const BLOCK_IDX: u8 = 0;
+
+        if iv.len() != ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        if (counter_size as usize) > ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        let slot = SlotId::try_from(slot_id)?.key_id();
+
+        let mut cb: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        cb.copy_from_slice(iv);
+
+        Ok(atca_aes_ctr_ctx_t {
+            key_id: slot,
+            key_block: BLOCK_IDX,
+            cb,
+            counter_size,
+        })
+    } // SoftwareBackend::aes_ctr_init()
+
+    /// Increments AES CTR counter value.
+    /// The counter occupies the last 'counter_size' bytes of the counter block
+    /// and is incremented as a big-endian number, its overflow is an error
+    pub(crate) fn aes_ctr_increment(
+        &self,
+        ctx: atca_aes_ctr_ctx_t,
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        let mut ctx = ctx;
+        let counter_start = ATCA_AES_DATA_SIZE - (ctx.counter_size as usize);
+
+        for byte in ctx.cb[counter_start..].iter_mut().rev() {
+            *byte = byte.wrapping_add(1);
+            if *byte != 0x00 {
+                return Ok(ctx);
+            }
+        }
+
+        Err(AtcaStatus::AtcaGenFail)
+    } // SoftwareBackend::aes_ctr_increment()
+
+    /// Process a block of data using CTR mode and a key within the device.
+    /// aes_ctr_init() should be called before the first use of this function.
+    pub(crate) fn aes_ctr_block(
+        &self,
+        ctx: atca_aes_ctr_ctx_t,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        let encr_block = self.aes_encrypt_block(ctx.key_id, ctx.key_block, &ctx.cb)?;
+        output
+            .iter_mut()
+            .zip(input.iter().zip(encr_block.iter()))
+            .for_each(|(out, (x1, x2))| *out = *x1 ^ *x2);
+
+        self.aes_ctr_increment(ctx)
+    } // SoftwareBackend::aes_ctr_block()
+
+    /// Initialize context for AES CBC operation.
+    pub(crate) fn aes_cbc_init(
+        &self,
+        slot_id: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        const BLOCK_IDX: u8 = 0;
+
+        if iv.len() != ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let slot = SlotId::try_from(slot_id)?.key_id();
+
+        let mut ciphertext: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        ciphertext.copy_from_slice(iv);
+
+        Ok(atca_aes_cbc_ctx_t {
+            key_id: slot,
+            key_block: BLOCK_IDX,
+            ciphertext,
+        })
+    } // SoftwareBackend::aes_cbc_init()
+
+    /// Encrypt a block of data using CBC mode and a key within the device.
+    /// aes_cbc_init() should be called before the first use of this function.
+    pub(crate) fn aes_cbc_encrypt_block(
+        &self,
+        ctx: atca_aes_cbc_ctx_t,
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        let mut ctx = ctx;
+        let mut block: [u8; ATCA_AES_DATA_SIZE] = ctx.ciphertext;
+        block
+            .iter_mut()
+            .zip(plaintext.iter())
+            .for_each(|(x1, x2)| *x1 ^= *x2);
+
+        ctx.ciphertext = self.aes_encrypt_block(ctx.key_id, ctx.key_block, &block)?;
+        ciphertext.clone_from_slice(&ctx.ciphertext);
+
+        Ok(ctx)
+    } // SoftwareBackend::aes_cbc_encrypt_block()
+
+    /// Decrypt a block of data using CBC mode and a key within the device.
+    /// aes_cbc_init() should be called before the first use of this function.
+    pub(crate) fn aes_cbc_decrypt_block(
+        &self,
+        ctx: atca_aes_cbc_ctx_t,
+        ciphertext: &[u8],
+        plaintext: &mut [u8],
+    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        let mut ctx = ctx;
+        let block = self.aes_decrypt_block(ctx.key_id, ctx.key_block, ciphertext)?;
+        plaintext
+            .iter_mut()
+            .zip(block.iter().zip(ctx.ciphertext.iter()))
+            .for_each(|(out, (x1, x2))| *out = *x1 ^ *x2);
+
+        ctx.ciphertext.clone_from_slice(ciphertext);
+        Ok(ctx)
+    } // SoftwareBackend::aes_cbc_decrypt_block()
+
+    /// Perform an AES-128 encrypt operation with a key in the device
+    pub(crate) fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        if input.len() != ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        let mut block = GenericArray::clone_from_slice(input);
+        self.aes_cipher(key_id, key_block)?
+            .encrypt_block(&mut block);
+
+        Ok(block.into())
+    } // SoftwareBackend::aes_encrypt_block()
+
+    /// Perform an AES-128 decrypt operation with a key in the device
+    pub(crate) fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        if input.len() != ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        let mut block = GenericArray::clone_from_slice(input);
+        self.aes_cipher(key_id, key_block)?
+            .decrypt_block(&mut block);
+
+        Ok(block.into())
+    } // SoftwareBackend::aes_decrypt_block()
+
+    /// A helper function that sets up AES-128 with the key taken
+    /// from the 16-byte block 'key_block' of a slot or of TempKey
+    fn aes_cipher(&self, key_id: u16, key_block: u8) -> Result<Aes128, AtcaStatus> {
+        const MAX_KEY_BLOCK: u8 = 3;
+
+        if key_block > MAX_KEY_BLOCK {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        let start_pos = (key_block as usize) * ATCA_AES_KEY_SIZE;
+        let end_pos = start_pos + ATCA_AES_KEY_SIZE;
+
+        let state = self.state();
+        let key: &[u8] = match SlotId::from_key_id(key_id) {
+            Ok(SlotId::TempKey) => &state.temp_key[start_pos..end_pos],
+            Ok(SlotId::Slot(slot)) => {
+                if (self.slots[slot as usize].config.key_type != KeyType::Aes)
+                    || (end_pos > get_slot_capacity(slot).bytes as usize)
+                {
+                    // The AES command refuses slots that do not hold AES keys
+                    return Err(AtcaStatus::AtcaExecutionError);
+                }
+                &state.data_zone[slot as usize][start_pos..end_pos]
+            }
+            Err(_) => return Err(AtcaStatus::AtcaBadParam),
+        };
+
+        Ok(Aes128::new(GenericArray::from_slice(key)))
+    } // SoftwareBackend::aes_cipher()
+
+    /// A helper function that implements common input parameter tests
+    /// and set for AES cipher modes
+    pub(crate) fn cipher_aes_common(
+        &self,
+        slot_id: u8,
+        data_size: usize,
+        key: Option<Vec<u8>>,
+    ) -> Result<u16, AtcaStatus> {
+        let key_slot = SlotId::try_from(slot_id)?;
+        if let SlotId::Slot(slot) = key_slot {
+            if self.slots[slot as usize].config.key_type != KeyType::Aes {
+                return Err(AtcaStatus::AtcaInvalidId);
+            }
+        }
+        if key_slot.is_temp_key()
+            && (key.is_none()
+                || (key.is_some() && (key.as_ref().unwrap().len() != ATCA_AES_KEY_SIZE)))
+        {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        if 0 == data_size {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        if key_slot.is_temp_key() {
+            if let Some(val) = &key {
+                let mut key: Vec<u8> = val.to_vec();
+                key.resize_with(ATCA_NONCE_SIZE, || 0x00);
+                let result = self.nonce(NonceTarget::TempKey, &key);
+                if AtcaStatus::AtcaSuccess != result {
+                    return Err(result);
+                }
+            }
+        }
+
+        Ok(key_slot.key_id())
+    } // SoftwareBackend::cipher_aes_common()
+}
@@ -0,0 +1,217 @@
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+
+use ghash::universal_hash::{KeyInit, UniversalHash};
+use ghash::GHash;
+
+use super::constant_time_eq;
+use super::SoftwareBackend;
+use super::{AtcaAesGcmCtx, AtcaStatus, CipherOperation, SlotId};
+
+use super::{ATCA_AES_DATA_SIZE, ATCA_AES_GCM_IV_STD_LENGTH};
+
+use cryptoauthlib_sys::atca_aes_gcm_ctx_t;
+
+impl SoftwareBackend {
+    /// Initialize context for AES GCM operation with an existing IV, which
+    /// is common when starting a decrypt operation
+    pub(crate) fn aes_gcm_init(
+        &self,
+        slot_id: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        const BLOCK_IDX: u8 = 0;
+
+        let slot = SlotId::try_from(slot_id)?.key_id();
+
+        let mut ctx: atca_aes_gcm_ctx_t = AtcaAesGcmCtx::default().ctx;
+        ctx.key_id = slot;
+        ctx.key_block = BLOCK_IDX;
+        ctx.h = self.aes_encrypt_block(slot, BLOCK_IDX, &[0x00; ATCA_AES_DATA_SIZE])?;
+
+        if iv.len() == ATCA_AES_GCM_IV_STD_LENGTH {
+            ctx.j0[..ATCA_AES_GCM_IV_STD_LENGTH].copy_from_slice(iv);
+            ctx.j0[ATCA_AES_DATA_SIZE - 1] = 0x01;
+        } else {
+            let mut j0: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+            for block in iv.chunks(ATCA_AES_DATA_SIZE) {
+                ghash_block(&ctx.h, &mut j0, block);
+            }
+            let mut len_block: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+            len_block[8..].copy_from_slice(&((iv.len() as u64) * 8).to_be_bytes());
+            ghash_block(&ctx.h, &mut j0, &len_block);
+            ctx.j0 = j0;
+        }
+
+        ctx.cb = ctx.j0;
+        gcm_inc32(&mut ctx.cb);
+
+        Ok(ctx)
+    }
+
+    /// Process Additional Authenticated Data (AAD) using GCM mode and a
+    /// key within the ATECC608 device
+    pub(crate) fn aes_gcm_aad_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        if data.len() > ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        if ctx.data_size > 0 {
+            // AAD must be processed before the data
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        let mut ctx = ctx;
+        for byte in data {
+            ctx.partial_aad[ctx.partial_aad_size as usize] = *byte;
+            ctx.partial_aad_size += 1;
+            if ctx.partial_aad_size as usize == ATCA_AES_DATA_SIZE {
+                let block = ctx.partial_aad;
+                ghash_block(&ctx.h, &mut ctx.y, &block);
+                ctx.partial_aad_size = 0;
+            }
+        }
+        ctx.aad_size += data.len() as u32;
+
+        Ok(ctx)
+    } // SoftwareBackend::aes_gcm_aad_update()
+
+    /// Encrypt data using GCM mode and a key within the ATECC608 device.
+    /// aes_gcm_init() should be called before the first use of this function.
+    pub(crate) fn aes_gcm_encrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        encrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_update(ctx, data, encrypted, CipherOperation::Encrypt)
+    } // SoftwareBackend::aes_gcm_encrypt_update()
+
+    /// Decrypt data using GCM mode and a key within the ATECC608 device.
+    /// aes_gcm_init() should be called before the first use of this function
+    pub(crate) fn aes_gcm_decrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        encrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_update(ctx, data, encrypted, CipherOperation::Decrypt)
+    } // SoftwareBackend::aes_gcm_decrypt_update()
+
+    /// Encrypts or decrypts data with the AES CTR engine of the GCM context
+    /// and feeds the ciphertext into GHASH
+    fn aes_gcm_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        output: &mut [u8; ATCA_AES_DATA_SIZE],
+        operation: CipherOperation,
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        if data.len() > ATCA_AES_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        let mut ctx = gcm_aad_finish(ctx);
+        *output = [0; ATCA_AES_DATA_SIZE];
+
+        for (idx, byte) in data.iter().enumerate() {
+            let block_pos = (ctx.data_size as usize) % ATCA_AES_DATA_SIZE;
+            if 0 == block_pos {
+                ctx.enc_cb = self.aes_encrypt_block(ctx.key_id, ctx.key_block, &ctx.cb)?;
+                gcm_inc32(&mut ctx.cb);
+                ctx.ciphertext_block = [0x00; ATCA_AES_DATA_SIZE];
+            }
+            output[idx] = byte ^ ctx.enc_cb[block_pos];
+            ctx.ciphertext_block[block_pos] = match operation {
+                CipherOperation::Encrypt => output[idx],
+                CipherOperation::Decrypt => *byte,
+            };
+            ctx.data_size += 1;
+            if (ctx.data_size as usize).is_multiple_of(ATCA_AES_DATA_SIZE) {
+                let block = ctx.ciphertext_block;
+                ghash_block(&ctx.h, &mut ctx.y, &block);
+            }
+        }
+
+        Ok(ctx)
+    } // SoftwareBackend::aes_gcm_update()
+
+    /// Complete a GCM encrypt operation returning the authentication tag
+    pub(crate) fn aes_gcm_encrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let tag = self.aes_gcm_tag(ctx)?;
+        Ok(tag[..tag_length as usize].to_vec())
+    } // SoftwareBackend::aes_gcm_encrypt_finish()
+
+    /// Complete a GCM decrypt operation verifying the authentication tag
+    pub(crate) fn aes_gcm_decrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let expected_tag = self.aes_gcm_tag(ctx)?;
+        Ok(constant_time_eq(&expected_tag[..tag.len()], tag))
+    } // SoftwareBackend::aes_gcm_decrypt_finish()
+
+    /// Calculates the authentication tag of a GCM operation:
+    /// AES(J0) XOR GHASH(AAD || ciphertext || lengths)
+    fn aes_gcm_tag(&self, ctx: atca_aes_gcm_ctx_t) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        let mut ctx = gcm_aad_finish(ctx);
+
+        if !(ctx.data_size as usize).is_multiple_of(ATCA_AES_DATA_SIZE) {
+            let block = ctx.ciphertext_block;
+            ghash_block(&ctx.h, &mut ctx.y, &block);
+        }
+
+        let mut len_block: [u8; ATCA_AES_DATA_SIZE] = [0x00; ATCA_AES_DATA_SIZE];
+        len_block[..8].copy_from_slice(&((ctx.aad_size as u64) * 8).to_be_bytes());
+        len_block[8..].copy_from_slice(&((ctx.data_size as u64) * 8).to_be_bytes());
+        ghash_block(&ctx.h, &mut ctx.y, &len_block);
+
+        let mut tag = self.aes_encrypt_block(ctx.key_id, ctx.key_block, &ctx.j0)?;
+        tag.iter_mut()
+            .zip(ctx.y.iter())
+            .for_each(|(x1, x2)| *x1 ^= *x2);
+
+        Ok(tag)
+    } // SoftwareBackend::aes_gcm_tag()
+}
+
+/// Processes the remaining partial block of AAD, when the data or the tag
+/// of a GCM operation are about to be processed
+fn gcm_aad_finish(ctx: atca_aes_gcm_ctx_t) -> atca_aes_gcm_ctx_t {
+    let mut ctx = ctx;
+    if ctx.partial_aad_size > 0 {
+        let block = ctx.partial_aad;
+        ghash_block(&ctx.h, &mut ctx.y, &block[..ctx.partial_aad_size as usize]);
+        ctx.partial_aad_size = 0;
+    }
+    ctx
+} // gcm_aad_finish()
+
+/// GHASH step: y = (y XOR block) * H, a block shorter than 16 bytes is padded with zeros
+fn ghash_block(h: &[u8; ATCA_AES_DATA_SIZE], y: &mut [u8; ATCA_AES_DATA_SIZE], block: &[u8]) {
+    let mut input: [u8; ATCA_AES_DATA_SIZE] = *y;
+    input
+        .iter_mut()
+        .zip(block.iter())
+        .for_each(|(x1, x2)| *x1 ^= *x2);
+
+    let mut ghash = GHash::new(h.into());
+    ghash.update(&[input.into()]);
+    y.copy_from_slice(&ghash.finalize());
+} // ghash_block()
+
+/// Increments the rightmost 32 bits of a counter block, modulo 2^32
+fn gcm_inc32(cb: &mut [u8; ATCA_AES_DATA_SIZE]) {
+    let mut counter: [u8; 4] = [0x00; 4];
+    counter.copy_from_slice(&cb[(ATCA_AES_DATA_SIZE - 4)..]);
+    let counter = u32::from_be_bytes(counter).wrapping_add(1);
+    cb[(ATCA_AES_DATA_SIZE - 4)..].copy_from_slice(&counter.to_be_bytes());
+} // gcm_inc32()
@@ -0,0 +1,171 @@
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+
+use p256::ecdh::diffie_hellman;
+
+use super::super::host_modes::ECDH_MODE_SOURCE_TEMPKEY;
+use super::super::host_modes::{ECDH_MODE_COPY_TEMP_KEY, ECDH_MODE_SOURCE_EEPROM_SLOT};
+use super::{public_key_from_raw, SoftwareBackend};
+use super::{
+    AtcaDeviceType, AtcaStatus, EcdhOutput, IoProtectionSession, KeyType, OutputProtectionState,
+};
+
+use super::{ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ECDH_PMS_SIZE, ATCA_KEY_SIZE};
+
+// ECDH command mode bit (ATECC608x), the others are shared with the host-side modes
+const ECDH_MODE_COPY_COMPATIBLE: u8 = 0x00;
+
+impl SoftwareBackend {
+    /// Function that performs ECDH key agreement with a private key stored
+    /// in a slot (or in TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT),
+    /// honoring the 'ecdh_output_protection' setting of the chip.
+    /// When encrypted output is required, the IO protection key from the session
+    /// (or the one registered with add_access_key() for slot ATCA_ATECC_SLOTS_COUNT) is used
+    /// to decrypt the premaster secret. When output outside the chip is forbidden,
+    /// or no IO protection key is available, the premaster secret is kept in TempKey.
+    pub(crate) fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        self.ecdh_parameters_check(slot_id, public_key)?;
+
+        let source = match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => ECDH_MODE_SOURCE_TEMPKEY,
+            _ => ECDH_MODE_SOURCE_EEPROM_SLOT,
+        };
+        // Slot configured to store the master secret in the next slot
+        if (slot_id < ATCA_ATECC_SLOTS_COUNT)
+            && self.slots[slot_id as usize]
+                .config
+                .ecc_key_attr
+                .ecdh_secret_out
+        {
+            self.ecdh_without_output(source | ECDH_MODE_COPY_COMPATIBLE, slot_id, public_key)?;
+            return Ok(EcdhOutput::Slot(slot_id + 1));
+        }
+
+        let protection = match self.get_device_type() {
+            AtcaDeviceType::ATECC608A => self.chip_options.ecdh_output_protection,
+            _ => OutputProtectionState::ClearTextAllowed,
+        };
+
+        match protection {
+            OutputProtectionState::ClearTextAllowed => match session {
+                Some(val) => self
+                    .ecdh_with_io_key(slot_id, public_key, val.io_key())
+                    .map(EcdhOutput::PreMasterSecret),
+                None => self
+                    .ecdh_clear(slot_id, public_key)
+                    .map(EcdhOutput::PreMasterSecret),
+            },
+            OutputProtectionState::EncryptedOutputOnly => {
+                let io_key = match session {
+                    Some(val) => Some(Zeroizing::new(val.io_key().to_vec())),
+                    None => {
+                        let mut key = Zeroizing::new(Vec::with_capacity(ATCA_KEY_SIZE));
+                        match self.get_access_key(ATCA_ATECC_SLOTS_COUNT, &mut key) {
+                            AtcaStatus::AtcaSuccess => Some(key),
+                            _ => None,
+                        }
+                    }
+                };
+                match io_key {
+                    Some(key) => self
+                        .ecdh_with_io_key(slot_id, public_key, &key)
+                        .map(EcdhOutput::PreMasterSecret),
+                    None => {
+                        self.ecdh_without_output(
+                            source | ECDH_MODE_COPY_TEMP_KEY,
+                            slot_id,
+                            public_key,
+                        )?;
+                        Ok(EcdhOutput::TempKey)
+                    }
+                }
+            }
+            OutputProtectionState::ForbiddenOutputOutsideChip => {
+                self.ecdh_without_output(source | ECDH_MODE_COPY_TEMP_KEY, slot_id, public_key)?;
+                Ok(EcdhOutput::TempKey)
+            }
+            _ => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // SoftwareBackend::ecdh()
+
+    /// A helper function for the ECDH methods, pre-checking combinations of input parameters
+    pub(crate) fn ecdh_parameters_check(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus> {
+        if slot_id > ATCA_ATECC_SLOTS_COUNT {
+            return Err(AtcaStatus::AtcaInvalidId);
+        }
+        if slot_id < ATCA_ATECC_SLOTS_COUNT {
+            let slot_data = self.slots[slot_id as usize].config;
+            if (slot_data.key_type != KeyType::P256EccKey)
+                || !slot_data.ecc_key_attr.is_private
+                || !slot_data.ecc_key_attr.ecdh_operation
+            {
+                return Err(AtcaStatus::AtcaBadParam);
+            }
+        }
+        if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        Ok(())
+    } // SoftwareBackend::ecdh_parameters_check()
+
+    /// A helper function that executes ECDH command with the premaster secret
+    /// returned in clear text
+    fn ecdh_clear(&self, slot_id: u8, public_key: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.ecdh_premaster_secret(slot_id, public_key)
+    } // SoftwareBackend::ecdh_clear()
+
+    /// A helper function that executes ECDH command with the premaster secret
+    /// kept inside the chip (in TempKey or in a slot, depending on the mode)
+    pub(crate) fn ecdh_without_output(
+        &self,
+        mode: u8,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus> {
+        let pms = self.ecdh_premaster_secret(slot_id, public_key)?;
+
+        let mut state = self.state();
+        match mode & ECDH_MODE_COPY_TEMP_KEY {
+            ECDH_MODE_COPY_TEMP_KEY => {
+                state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+                state.temp_key[..ATCA_ECDH_PMS_SIZE].copy_from_slice(&pms);
+            }
+            _ => {
+                // The premaster secret is written to the slot following the private key
+                let secret_slot = (slot_id + 1) as usize;
+                if secret_slot >= (ATCA_ATECC_SLOTS_COUNT as usize) {
+                    return Err(AtcaStatus::AtcaExecutionError);
+                }
+                state.data_zone[secret_slot][..ATCA_ECDH_PMS_SIZE].copy_from_slice(&pms);
+            }
+        }
+        Ok(())
+    } // SoftwareBackend::ecdh_without_output()
+
+    /// A helper function that calculates the premaster secret (the X coordinate
+    /// of the shared point) from a private key stored in a slot (or in TempKey,
+    /// for slot_id equal to ATCA_ATECC_SLOTS_COUNT) and the peer public key
+    pub(crate) fn ecdh_premaster_secret(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let private_key = self.read_private_key(slot_id)?;
+        let public_key = public_key_from_raw(public_key)?;
+
+        let shared_secret = diffie_hellman(private_key.to_nonzero_scalar(), public_key.as_affine());
+        Ok(shared_secret.raw_secret_bytes().to_vec())
+    } // SoftwareBackend::ecdh_premaster_secret()
+}
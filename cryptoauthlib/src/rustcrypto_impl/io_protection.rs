@@ -0,0 +1,171 @@
+use alloc::vec::Vec;
+
+use super::{constant_time_eq, io_protection_xor, random_bytes, SoftwareBackend};
+use super::{
+    AtcaDeviceType, AtcaStatus, IoProtectionSession, KeyType, SlotId, VerifyMode, WriteConfig,
+};
+
+use super::{
+    ATCA_AES_KEY_SIZE, ATCA_ATECC_PUB_KEY_SIZE, ATCA_BLOCK_SIZE, ATCA_KEY_SIZE, ATCA_NONCE_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
+
+impl SoftwareBackend {
+    /// Function that opens an IO protection session with the ATECC608x chip.
+    /// The key must be the same as the one stored in the slot
+    /// indicated by 'io_key_in_slot' in ChipOptions.
+    pub(crate) fn open_io_protection_session(
+        &self,
+        io_key: &[u8],
+    ) -> Result<IoProtectionSession, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if (self.get_device_type() != AtcaDeviceType::ATECC608A)
+            || !self.chip_options.io_key_enabled
+        {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        if io_key.len() != ATCA_KEY_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        Ok(IoProtectionSession::new(
+            self.chip_options.io_key_in_slot,
+            io_key,
+        ))
+    } // SoftwareBackend::open_io_protection_session()
+
+    /// Function that performs ECDH key agreement with a private key stored
+    /// in a slot (or in TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT).
+    /// The premaster secret is transmitted encrypted with the IO protection key
+    /// and returned to the caller after decryption on the host side.
+    pub(crate) fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        self.ecdh_parameters_check(slot_id, public_key)?;
+
+        self.ecdh_with_io_key(slot_id, public_key, session.io_key())
+    } // SoftwareBackend::ecdh_io_protected()
+
+    /// A helper function that executes ECDH command with the premaster secret
+    /// encrypted by the chip with the given IO protection key and decrypted on the host side
+    pub(crate) fn ecdh_with_io_key(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        io_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let mut pms = self.ecdh_premaster_secret(slot_id, public_key)?;
+        self.io_protected_output(io_key, &mut pms)?;
+        Ok(pms)
+    } // SoftwareBackend::ecdh_with_io_key()
+
+    /// Function that verifies an ECDSA signature and additionally checks
+    /// the validation MAC calculated by the chip with the IO protection key,
+    /// so that the verification result cannot be forged on the bus.
+    /// Only 'External' and 'Internal' verification modes are accepted.
+    pub(crate) fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if (signature.len() != ATCA_SIG_SIZE) || (hash.len() != ATCA_SHA2_256_DIGEST_SIZE) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        };
+
+        let is_verified = match mode {
+            VerifyMode::Internal(slot_number) => {
+                SlotId::new(slot_number)?;
+                self.verify_hash(VerifyMode::Internal(slot_number), hash, signature)?
+            }
+            VerifyMode::External(public_key) => {
+                if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+                    return Err(AtcaStatus::AtcaInvalidSize);
+                }
+                self.verify_hash(VerifyMode::External(public_key), hash, signature)?
+            }
+            _ => return Err(AtcaStatus::AtcaBadParam),
+        };
+
+        // The validation MAC calculated by the chip matches
+        // only when the host knows the IO protection key
+        let io_key = self.slot_key(self.chip_options.io_key_in_slot);
+        Ok(is_verified && constant_time_eq(&io_key[..], session.io_key()))
+    } // SoftwareBackend::verify_hash_io_protected()
+
+    /// Function that loads an AES key into a slot whose write key
+    /// is the IO protection key. The key is transmitted encrypted.
+    pub(crate) fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let slot_data = self.slots[slot_id as usize].config;
+        if !self.chip_options.aes_enabled
+            || (slot_data.key_type != KeyType::Aes)
+            || (slot_data.write_config != WriteConfig::Encrypt)
+            || (slot_data.write_key != session.io_key_slot())
+        {
+            return AtcaStatus::AtcaBadParam;
+        }
+        if key.len() != ATCA_AES_KEY_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        if self.slots[slot_id as usize].is_locked {
+            return AtcaStatus::AtcaExecutionError;
+        }
+        // The chip accepts the encrypted data only with the MAC calculated
+        // with the key stored in the write key slot
+        if !constant_time_eq(&self.slot_key(session.io_key_slot())[..], session.io_key()) {
+            return AtcaStatus::AtcaCheckMacVerifyFailed;
+        }
+
+        let mut data: [u8; ATCA_BLOCK_SIZE] = [0; ATCA_BLOCK_SIZE];
+        data[..ATCA_AES_KEY_SIZE].copy_from_slice(key);
+        self.state().data_zone[slot_id as usize][..ATCA_BLOCK_SIZE].copy_from_slice(&data);
+
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::import_aes_key_io_protected()
+
+    /// A helper function emulating output of data encrypted by the chip
+    /// with the IO protection key stored in the slot 'io_key_in_slot' and decrypted
+    /// on the host side with 'io_key'. Unless both keys are the same, the result is garbage.
+    pub(crate) fn io_protected_output(
+        &self,
+        io_key: &[u8],
+        data: &mut [u8],
+    ) -> Result<(), AtcaStatus> {
+        if (io_key.len() != ATCA_KEY_SIZE) || (data.len() > (2 * ATCA_BLOCK_SIZE)) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        let out_nonce = random_bytes(ATCA_NONCE_SIZE);
+        io_protection_xor(
+            &self.slot_key(self.chip_options.io_key_in_slot)[..],
+            &out_nonce,
+            data,
+        );
+        io_protection_xor(io_key, &out_nonce, data);
+        Ok(())
+    } // SoftwareBackend::io_protected_output()
+}
@@ -0,0 +1,216 @@
+use alloc::vec::Vec;
+use zeroize::Zeroizing;
+
+use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use aes::Aes128;
+
+use super::{hmac_sha256, SoftwareBackend};
+use super::{
+    AtcaDeviceType, AtcaStatus, KdfAlgorithm, KdfSource, KdfTarget, KeyType, OutputProtectionState,
+    SlotId,
+};
+
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_KEY_SIZE,
+    ATCA_SHA2_256_DIGEST_SIZE,
+};
+
+/// Maximum size (in bytes) of a message for PRF and HKDF algorithms
+const KDF_MAX_MSG_SIZE: usize = 128;
+
+impl SoftwareBackend {
+    /// Function that executes the KDF command on the ATECC608x chip.
+    /// For 'KdfTarget::Output' the derived key is returned; if the 'kdf_output_protection'
+    /// setting of the chip requires encrypted output, the key is decrypted on the host side
+    /// with the IO protection key registered by add_access_key() for slot ATCA_ATECC_SLOTS_COUNT.
+    /// For other targets the derived key stays in the chip and an empty vector is returned.
+    pub(crate) fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.kdf_common(algorithm, source, target, message, None)
+    } // SoftwareBackend::kdf()
+
+    /// A helper function executing the KDF command. If 'io_key' is given, output
+    /// of the derived key is always encrypted with it, otherwise the encryption
+    /// follows the 'kdf_output_protection' setting of the chip.
+    pub(crate) fn kdf_common(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+        io_key: Option<&[u8]>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if self.get_device_type() != AtcaDeviceType::ATECC608A {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        match algorithm {
+            KdfAlgorithm::Prf {
+                key_length,
+                target_length,
+            } => {
+                if ![16, 32, 48, 64].contains(&key_length) || ![32, 64].contains(&target_length) {
+                    return Err(AtcaStatus::AtcaInvalidSize);
+                }
+            }
+            KdfAlgorithm::Aes { key_location } => {
+                if key_location > 3 {
+                    return Err(AtcaStatus::AtcaBadParam);
+                }
+                if message.len() != ATCA_AES_DATA_SIZE {
+                    return Err(AtcaStatus::AtcaInvalidSize);
+                }
+            }
+            KdfAlgorithm::Hkdf => (),
+        };
+        if !matches!(algorithm, KdfAlgorithm::Aes { .. }) && (message.len() > KDF_MAX_MSG_SIZE) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        if let KdfSource::Slot(slot_id) = source {
+            SlotId::new(slot_id)?;
+        }
+        if let KdfTarget::Slot(slot_id) = target {
+            SlotId::new(slot_id)?;
+        }
+
+        let protection = self.chip_options.kdf_output_protection;
+        let encrypted_output = match target {
+            KdfTarget::Output => match protection {
+                OutputProtectionState::ClearTextAllowed => io_key.is_some(),
+                OutputProtectionState::EncryptedOutputOnly => true,
+                _ => return Err(AtcaStatus::AtcaBadParam),
+            },
+            _ => false,
+        };
+
+        // the IO protection key must be known before the command is executed
+        let mut io_key = Zeroizing::new(io_key.map(|val| val.to_vec()).unwrap_or_default());
+        if encrypted_output && io_key.is_empty() {
+            let result = self.get_access_key(ATCA_ATECC_SLOTS_COUNT, &mut io_key);
+            if AtcaStatus::AtcaSuccess != result {
+                return Err(result);
+            }
+        }
+
+        let source_key = Zeroizing::new(self.kdf_source_key(source)?);
+        let mut out_data = kdf_derive(algorithm, &source_key, message)?;
+
+        match target {
+            KdfTarget::Output if encrypted_output => {
+                self.io_protected_output(&io_key, &mut out_data)?;
+                Ok(out_data)
+            }
+            KdfTarget::Output => Ok(out_data),
+            _ => {
+                self.kdf_store_key(target, &out_data)?;
+                Ok(Vec::new())
+            }
+        }
+    } // SoftwareBackend::kdf_common()
+
+    /// A helper function that returns the key material the KDF command reads from its source
+    fn kdf_source_key(&self, source: KdfSource) -> Result<Vec<u8>, AtcaStatus> {
+        let state = self.state();
+        match source {
+            KdfSource::TempKey => Ok(state.temp_key.to_vec()),
+            KdfSource::TempKeyUpper => Ok(state.temp_key[ATCA_KEY_SIZE..].to_vec()),
+            KdfSource::AltKeyBuf => Ok(state.alt_key_buf.to_vec()),
+            KdfSource::Slot(slot_id) => {
+                if self.slots[slot_id as usize].config.ecc_key_attr.is_private {
+                    return Err(AtcaStatus::AtcaExecutionError);
+                }
+                Ok(state.data_zone[slot_id as usize].clone())
+            }
+        }
+    } // SoftwareBackend::kdf_source_key()
+
+    /// A helper function that stores a derived key in the KDF command target
+    fn kdf_store_key(&self, target: KdfTarget, key: &[u8]) -> Result<(), AtcaStatus> {
+        let mut state = self.state();
+        match target {
+            KdfTarget::TempKey => {
+                state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+                state.temp_key[..key.len()].copy_from_slice(key);
+            }
+            KdfTarget::TempKeyUpper => {
+                let len = key.len().min(ATCA_KEY_SIZE);
+                state.temp_key[ATCA_KEY_SIZE..].fill(0);
+                state.temp_key[ATCA_KEY_SIZE..(ATCA_KEY_SIZE + len)].copy_from_slice(&key[..len]);
+            }
+            KdfTarget::AltKeyBuf => {
+                let len = key.len().min(ATCA_KEY_SIZE);
+                state.alt_key_buf = [0; ATCA_KEY_SIZE];
+                state.alt_key_buf[..len].copy_from_slice(&key[..len]);
+            }
+            KdfTarget::Slot(slot_id) => {
+                let slot = self.slots[slot_id as usize];
+                if slot.is_locked
+                    || !matches!(slot.config.key_type, KeyType::Aes | KeyType::ShaOrText)
+                {
+                    return Err(AtcaStatus::AtcaExecutionError);
+                }
+                let slot_data = &mut state.data_zone[slot_id as usize];
+                let len = key.len().min(slot_data.len());
+                slot_data[..len].copy_from_slice(&key[..len]);
+            }
+            KdfTarget::Output => (),
+        }
+        Ok(())
+    } // SoftwareBackend::kdf_store_key()
+}
+
+/// Function that derives a key from the source key material:
+/// PRF is the TLS 1.2 pseudorandom function P_SHA256, AES encrypts the message
+/// with the selected 16-byte block of the source, HKDF is HMAC-SHA256 of the message
+fn kdf_derive(
+    algorithm: KdfAlgorithm,
+    source_key: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>, AtcaStatus> {
+    match algorithm {
+        KdfAlgorithm::Prf {
+            key_length,
+            target_length,
+        } => {
+            if key_length > source_key.len() {
+                return Err(AtcaStatus::AtcaExecutionError);
+            }
+            let secret = &source_key[..key_length];
+
+            let mut out_data: Vec<u8> = Vec::with_capacity(target_length);
+            let mut a = hmac_sha256(secret, &[message]);
+            while out_data.len() < target_length {
+                out_data.extend_from_slice(&hmac_sha256(secret, &[&a, message]));
+                a = hmac_sha256(secret, &[&a]);
+            }
+            out_data.truncate(target_length);
+            Ok(out_data)
+        }
+        KdfAlgorithm::Aes { key_location } => {
+            let start_pos = (key_location as usize) * ATCA_AES_KEY_SIZE;
+            if (start_pos + ATCA_AES_KEY_SIZE) > source_key.len() {
+                return Err(AtcaStatus::AtcaExecutionError);
+            }
+            let cipher = Aes128::new(GenericArray::from_slice(
+                &source_key[start_pos..(start_pos + ATCA_AES_KEY_SIZE)],
+            ));
+            let mut block = GenericArray::clone_from_slice(message);
+            cipher.encrypt_block(&mut block);
+            Ok(block.to_vec())
+        }
+        KdfAlgorithm::Hkdf => {
+            if ATCA_SHA2_256_DIGEST_SIZE > source_key.len() {
+                return Err(AtcaStatus::AtcaExecutionError);
+            }
+            Ok(hmac_sha256(&source_key[..ATCA_SHA2_256_DIGEST_SIZE], &[message]).to_vec())
+        }
+    }
+} // kdf_derive()
@@ -0,0 +1,2498 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::sync::{Mutex, MutexGuard};
+
+use cryptoauthlib_sys::{atca_aes_cbc_ctx_t, atca_aes_ctr_ctx_t, atca_aes_gcm_ctx_t};
+
+use hmac::{Hmac, Mac};
+use p256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::{PublicKey, SecretKey};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::host_modes::{self, ModePrimitives};
+use super::hw_impl::atcab_get_config_from_config_zone;
+use super::secure_session::{
+    check_mac_message, gen_dig_message, mac_message, MAC_MODE_CHALLENGE, MAC_MODE_TEMPKEY,
+};
+use super::signature::check_sign_base_mode;
+use super::ATCA_CHECK_MAC_OTHER_DATA_SIZE;
+use super::{
+    check_otp_range, constant_time_eq, convert_signature, private_key_from_der,
+    private_key_from_pem, signature_to_der,
+};
+use super::{
+    AeadAlgorithm, AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaDeviceType, AtcaSha256Ctx, AtcaSlot,
+    AtcaSlotCapacity, AtcaStatus, AteccDeviceTrait, ChipOptions, CipherAlgorithm, CipherOperation,
+    CipherParam, DigestBuffer, EcdhOutput, FeedbackMode, GenDigZone, InfoCmdType,
+    IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget, KeyType, KeyWrapAlgorithm,
+    NonceTarget, OutputProtectionState, SignMode, SignatureFormat, SlotConfig, SlotId,
+    TempKeyState, TlsFinishedLabel, VerifyMode, WriteConfig,
+};
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_GCM_IV_STD_LENGTH, ATCA_AES_KEY_SIZE,
+    ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY, ATCA_ATECC_PRIV_KEY_SIZE,
+    ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ATECC_TEMPKEY_KEYID, ATCA_BLOCK_SIZE,
+    ATCA_COUNTER_MAX_VALUE, ATCA_ECDH_PMS_SIZE, ATCA_KEY_SIZE, ATCA_LIMITED_USE_COUNTER_ID,
+    ATCA_NONCE_NUMIN_SIZE, ATCA_NONCE_SIZE, ATCA_OTP_SIZE, ATCA_RANDOM_BUFFER_SIZE,
+    ATCA_SERIAL_NUM_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIGN_MODE_EXTERNAL,
+    ATCA_SIGN_MODE_SOURCE_MSGDIGBUF, ATCA_SIG_SIZE, ATCA_WORD_SIZE,
+};
+#[cfg(test)]
+use super::{ATCA_ZONE_CONFIG, ATCA_ZONE_DATA};
+
+mod aes_cipher;
+mod aes_gcm;
+mod ecdh;
+mod io_protection;
+mod kdf;
+mod simulator;
+
+pub use simulator::AteccSimulator;
+
+/// Zero bytes preceding a P256 key (and each coordinate of a public key) stored in a slot
+const ECC_KEY_PAD_SIZE: usize = 4;
+/// Value of the LockValue and LockConfig bytes of an unlocked zone
+const ZONE_UNLOCKED: u8 = 0x55;
+/// Nonce command mode mixing a host nonce with a random number, TempKey being updated
+const NONCE_MODE_SEED_UPDATE: u8 = 0x00;
+const NONCE_OPCODE: u8 = 0x16;
+
+/// Configuration zone of a SoftwareBackend created by new(), both zones locked:
+/// - slots 0-1: P256 private keys, created with gen_key() only
+/// - slots 2-7: P256 private keys, also imported by PrivWrite encrypted with the key from slot 9
+/// - slot 8: 416 bytes of data, written and read in clear text
+/// - slot 9: secret 32-byte key, the IO protection key and the access key of slots 2-7, 12 and 13
+/// - slot 10: AES key, written and read in clear text
+/// - slot 11: secret AES key, written in clear text
+/// - slot 12: secret AES key, written encrypted with the key from slot 9
+/// - slot 13: secret AES key, written in clear text and read encrypted with the key from slot 9
+/// - slots 14-15: P256 public keys, written and read in clear text
+pub const SOFTWARE_BACKEND_CONFIG_ZONE: [u8; ATCA_ATECC_CONFIG_BUFFER_SIZE] = [
+    // Serial number, revision, AES enable, I2C settings
+    0x01, 0x23, 0x0A, 0x5C, 0x00, 0x00, 0x60, 0x02, 0x3B, 0x7F, 0x21, 0x96, 0xEE, 0x01, 0x01, 0x00,
+    0xC0, 0x00, 0x00, 0x00, // SlotConfig
+    0x87, 0x20, 0x87, 0x20, 0x87, 0x69, 0x87, 0x69, 0x87, 0x69, 0x87, 0x69, 0x87, 0x69, 0x87, 0x69,
+    0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x80, 0x00, 0x80, 0x49, 0xC9, 0x00, 0x00, 0x00, 0x00, 0x00,
+    // Counter[0], Counter[1]
+    0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00,
+    // UseLock, VolatileKeyPermission, SecureBoot, KdfIvLoc, KdfIvStr, reserved
+    0x00, 0x00, 0x00, 0x00, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    // UserExtra, UserExtraAdd, LockValue, LockConfig, SlotLocked, ChipOptions, X509format
+    0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x06, 0x90, 0x00, 0x00, 0x00, 0x00, // KeyConfig
+    0x33, 0x00, 0x33, 0x00, 0x33, 0x00, 0x33, 0x00, 0x33, 0x00, 0x33, 0x00, 0x33, 0x00, 0x33, 0x00,
+    0x3C, 0x00, 0x3C, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x38, 0x00, 0x30, 0x00, 0x30, 0x00,
+];
+
+/// Volatile state of the emulated chip
+struct ChipState {
+    /// Contents of the slots, each as large as the slot capacity
+    data_zone: Vec<Vec<u8>>,
+    otp_zone: [u8; ATCA_OTP_SIZE],
+    temp_key: [u8; 2 * ATCA_KEY_SIZE],
+    msg_dig_buf: [u8; 2 * ATCA_KEY_SIZE],
+    alt_key_buf: [u8; ATCA_KEY_SIZE],
+    /// SHA engine, holding the digest started by sha_start()
+    sha: Sha256,
+}
+
+/// An ATECC608A chip emulated in memory with RustCrypto primitives.
+/// It implements AteccDeviceTrait with the same parameter checks and the same
+/// slot access rules as the hardware backend, so applications and their tests
+/// can run on machines without a chip.
+pub struct SoftwareBackend {
+    config_zone: [u8; ATCA_ATECC_CONFIG_BUFFER_SIZE],
+    serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
+    config_zone_locked: bool,
+    data_zone_locked: bool,
+    chip_options: ChipOptions,
+    access_keys: Mutex<BTreeMap<u8, Zeroizing<[u8; ATCA_KEY_SIZE]>>>,
+    slots: Vec<AtcaSlot>,
+    state: Mutex<ChipState>,
+    /// Set by release()
+    released: AtomicBool,
+}
+
+impl Default for SoftwareBackend {
+    fn default() -> SoftwareBackend {
+        SoftwareBackend::new()
+    }
+}
+
+impl SoftwareBackend {
+    /// Creates an emulated chip configured as described by SOFTWARE_BACKEND_CONFIG_ZONE,
+    /// with all the slots empty
+    pub fn new() -> SoftwareBackend {
+        SoftwareBackend::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE)
+            .expect("Invalid default configuration zone")
+    } // SoftwareBackend::new()
+
+    /// Creates an emulated chip with the given 128-byte configuration zone,
+    /// with all the slots empty.
+    /// The lock state of both zones is taken from the LockValue and LockConfig bytes.
+    pub fn from_config_zone(config_zone: &[u8]) -> Result<SoftwareBackend, AtcaStatus> {
+        const IDX_LOCK_VALUE: usize = 86;
+        const IDX_LOCK_CONFIG: usize = 87;
+        const IDX_CHIP_OPTIONS: usize = 90;
+        const IDX_AES_ENABLE: usize = 13;
+
+        let config_zone = <[u8; ATCA_ATECC_CONFIG_BUFFER_SIZE]>::try_from(config_zone)
+            .map_err(|_| AtcaStatus::AtcaInvalidSize)?;
+
+        let mut serial_number = [0; ATCA_SERIAL_NUM_SIZE];
+        serial_number[..4].copy_from_slice(&config_zone[..4]);
+        serial_number[4..].copy_from_slice(&config_zone[8..13]);
+
+        let options = &config_zone[IDX_CHIP_OPTIONS..(IDX_CHIP_OPTIONS + 2)];
+        let chip_options = ChipOptions {
+            io_key_enabled: (options[0] & 0b00000010) != 0,
+            kdf_aes_enabled: (options[0] & 0b00000100) != 0,
+            io_key_in_slot: (options[1] >> 4) & 0b00001111,
+            ecdh_output_protection: (options[1] & 0b00000011).into(),
+            kdf_output_protection: ((options[1] >> 2) & 0b00000011).into(),
+            aes_enabled: (config_zone[IDX_AES_ENABLE] & 1) != 0,
+        };
+
+        let mut slots: Vec<AtcaSlot> = Vec::new();
+        atcab_get_config_from_config_zone(&config_zone, &mut slots);
+        let data_zone = (0..ATCA_ATECC_SLOTS_COUNT)
+            .map(|slot_id| vec![0; get_slot_capacity(slot_id).bytes as usize])
+            .collect();
+
+        Ok(SoftwareBackend {
+            config_zone,
+            serial_number,
+            config_zone_locked: config_zone[IDX_LOCK_CONFIG] != ZONE_UNLOCKED,
+            data_zone_locked: config_zone[IDX_LOCK_VALUE] != ZONE_UNLOCKED,
+            chip_options,
+            access_keys: Mutex::new(BTreeMap::new()),
+            slots,
+            state: Mutex::new(ChipState {
+                data_zone,
+                otp_zone: [0; ATCA_OTP_SIZE],
+                temp_key: [0; 2 * ATCA_KEY_SIZE],
+                msg_dig_buf: [0; 2 * ATCA_KEY_SIZE],
+                alt_key_buf: [0; ATCA_KEY_SIZE],
+                sha: Sha256::new(),
+            }),
+            released: AtomicBool::new(false),
+        })
+    } // SoftwareBackend::from_config_zone()
+}
+
+impl AteccDeviceTrait for SoftwareBackend {
+    /// Request ATECC to generate a vector of random bytes
+    /// Trait implementation
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.random(rand_out)
+    } // SoftwareBackend::random()
+
+    /// Request ATECC to compute a message hash (SHA256)
+    /// Trait implementation
+    fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
+        self.sha(message, digest)
+    } // SoftwareBackend::sha()
+
+    /// Starts a SHA256 digest calculated by ATECC in several steps
+    /// Trait implementation
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        self.sha_start()
+    } // SoftwareBackend::sha_start()
+
+    /// Feeds the next part of a message into a SHA256 digest calculation
+    /// Trait implementation
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        self.sha_update(ctx, data)
+    } // SoftwareBackend::sha_update()
+
+    /// Completes a SHA256 digest calculation and returns the digest
+    /// Trait implementation
+    fn sha_finish(&self, ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        self.sha_finish(ctx)
+    } // SoftwareBackend::sha_finish()
+
+    /// Request ATECC to compute HMAC-SHA256 of a message with the key stored in a slot
+    /// Trait implementation
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.sha_hmac(message, slot_id)
+    } // SoftwareBackend::sha_hmac()
+
+    /// Execute a Nonce command in pass-through mode to load one of the
+    /// device's internal buffers with a fixed value.
+    /// For the ATECC608A, available targets are TempKey (32 or 64 bytes), Message
+    /// Digest Buffer (32 or 64 bytes), or the Alternate Key Buffer (32 bytes). For
+    /// all other devices, only TempKey (32 bytes) is available.
+    /// Trait implementation
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        self.nonce(target, data)
+    } // SoftwareBackend::nonce()
+
+    /// Execute a Nonce command to generate a random nonce combining a host
+    /// nonce and a device random number.
+    /// Trait implementation
+    fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.nonce_rand(host_nonce, rand_out)
+    } // SoftwareBackend::nonce_rand()
+
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    /// Trait implementation
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        self.gen_dig(zone, slot_id, other_data)
+    } // SoftwareBackend::gen_dig()
+
+    /// The naive backend does not keep track of TempKey, nothing is reported as loaded.
+    /// AteccSimulator does
+    /// Trait implementation
+    fn tempkey_state(&self) -> TempKeyState {
+        TempKeyState::default()
+    } // SoftwareBackend::tempkey_state()
+
+    /// Execute a MAC command over the key stored in a slot and a challenge or TempKey
+    /// Trait implementation
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        self.mac(slot_id, challenge)
+    } // SoftwareBackend::mac()
+
+    /// Execute a CheckMac command to verify a MAC calculated by the host
+    /// Trait implementation
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.check_mac(slot_id, challenge, response, other_data)
+    } // SoftwareBackend::check_mac()
+
+    /// Request ATECC to read the value of one of the two monotonic counters
+    /// Trait implementation
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.counter_read(counter_id)
+    } // SoftwareBackend::counter_read()
+
+    /// Request ATECC to increment one of the two monotonic counters
+    /// Trait implementation
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.counter_increment(counter_id)
+    } // SoftwareBackend::counter_increment()
+
+    /// Request ATECC to generate a cryptographic key
+    /// Trait implementation
+    fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        self.gen_key(key_type, slot_id)
+    } // SoftwareBackend::gen_key()
+
+    /// Request ATECC to import a cryptographic key
+    /// Trait implementation
+    fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.import_key(key_type, key_data, slot_id)
+    } // SoftwareBackend::import_key()
+
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1)
+    /// Trait implementation
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.import_private_key_der(key_data, slot_id)
+    } // SoftwareBackend::import_private_key_der()
+
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    /// Trait implementation
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus {
+        self.import_private_key_pem(key_data, slot_id)
+    } // SoftwareBackend::import_private_key_pem()
+
+    /// Request ATECC to export a cryptographic key
+    /// Trait implementation
+    fn export_key(&self, key_type: KeyType, key_data: &mut Vec<u8>, slot_id: u8) -> AtcaStatus {
+        self.export_key(key_type, key_data, slot_id)
+    } // SoftwareBackend::export_key()
+
+    /// Request ATECC to write data to a slot, starting at the given byte offset
+    /// Trait implementation
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.write_slot_data(slot_id, offset, data)
+    } // SoftwareBackend::write_slot_data()
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset
+    /// Trait implementation
+    fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.read_slot_data(slot_id, offset, len)
+    } // SoftwareBackend::read_slot_data()
+
+    /// Request ATECC to write the OTP zone, starting at the given byte offset
+    /// Trait implementation
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.write_otp_zone(offset, data)
+    } // SoftwareBackend::write_otp_zone()
+
+    /// Request ATECC to read the OTP zone, starting at the given byte offset
+    /// Trait implementation
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        self.read_otp_zone(offset, len)
+    } // SoftwareBackend::read_otp_zone()
+
+    /// Depending on the socket configuration, this function calculates
+    /// public key based on an existing private key in the socket
+    /// or exports the public key directly
+    /// Trait implementation
+    fn get_public_key(&self, slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus {
+        self.get_public_key(slot_id, public_key)
+    } // SoftwareBackend::get_public_key()
+
+    /// Request ATECC to generate an ECDSA signature
+    /// Trait implementation
+    fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
+        self.sign_hash(mode, slot_id, signature)
+    } // SoftwareBackend::sign_hash()
+
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot
+    /// Trait implementation
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.sign_message(slot_id, message)
+    } // SoftwareBackend::sign_message()
+
+    /// Request ATECC to execute a Sign command in the given mode
+    /// Trait implementation
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.sign_base(mode, slot_id)
+    } // SoftwareBackend::sign_base()
+
+    /// Request ATECC to verify ECDSA signature
+    /// Trait implementation
+    fn verify_hash(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.verify_hash(mode, hash, signature)
+    } // SoftwareBackend::verify_hash()
+
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    /// Trait implementation
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.sign_hash_formatted(mode, slot_id, format)
+    } // SoftwareBackend::sign_hash_formatted()
+
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    /// Trait implementation
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        self.verify_hash_formatted(mode, hash, signature, format)
+    } // SoftwareBackend::verify_hash_formatted()
+
+    /// Request ATECC to perform ECDH key agreement, honoring the output protection settings
+    /// Trait implementation
+    fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        self.ecdh(slot_id, public_key, session)
+    } // SoftwareBackend::ecdh()
+
+    /// Request ATECC608x to derive a key with the KDF command
+    /// Trait implementation
+    fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.kdf(algorithm, source, target, message)
+    } // SoftwareBackend::kdf()
+
+    /// Request ATECC608x to open an IO protection session with the given IO protection key
+    /// Trait implementation
+    fn open_io_protection_session(&self, io_key: &[u8]) -> Result<IoProtectionSession, AtcaStatus> {
+        self.open_io_protection_session(io_key)
+    } // SoftwareBackend::open_io_protection_session()
+
+    /// Request ATECC to perform ECDH key agreement with the premaster secret
+    /// transmitted encrypted with the IO protection key
+    /// Trait implementation
+    fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.ecdh_io_protected(session, slot_id, public_key)
+    } // SoftwareBackend::ecdh_io_protected()
+
+    /// Request ATECC to verify ECDSA signature, with the result
+    /// authenticated by a MAC calculated with the IO protection key
+    /// Trait implementation
+    fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.verify_hash_io_protected(session, mode, hash, signature)
+    } // SoftwareBackend::verify_hash_io_protected()
+
+    /// Request ATECC to import an AES key to a slot protected by the IO protection key
+    /// Trait implementation
+    fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus {
+        self.import_aes_key_io_protected(session, key, slot_id)
+    } // SoftwareBackend::import_aes_key_io_protected()
+
+    /// Data encryption function in AES unauthenticated cipher alhorithms modes
+    /// Trait implementation
+    fn cipher_encrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        self.cipher_encrypt(algorithm, slot_id, data)
+    } // SoftwareBackend::cipher_encrypt()
+
+    /// Data decryption function in AES unauthenticated cipher alhorithms modes
+    /// Trait implementation
+    fn cipher_decrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        self.cipher_decrypt(algorithm, slot_id, data)
+    } // SoftwareBackend::cipher_decrypt()
+
+    /// Data encryption function in AES AEAD (authenticated encryption with associated data) modes
+    /// Trait implementation
+    fn aead_encrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.aead_encrypt(algorithm, slot_id, data)
+    } // SoftwareBackend::aead_encrypt()
+
+    /// Data decryption function in AES AEAD (authenticated encryption with associated data) modes
+    /// Trait implementation
+    fn aead_decrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> Result<bool, AtcaStatus> {
+        self.aead_decrypt(algorithm, slot_id, data)
+    } // SoftwareBackend::aead_decrypt()
+
+    /// Starts a streaming AES GCM encryption or decryption
+    /// Trait implementation
+    fn gcm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        host_modes::gcm_init(self, slot_id, nonce, key)
+    } // SoftwareBackend::gcm_init()
+
+    /// Feeds the next chunk of additional authenticated data into a streaming AES GCM operation
+    /// Trait implementation
+    fn gcm_update_aad(&self, ctx: AtcaAesGcmCtx, data: &[u8]) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        host_modes::gcm_update_aad(self, ctx, data)
+    } // SoftwareBackend::gcm_update_aad()
+
+    /// Encrypts (in place) the next chunk of a streaming AES GCM encryption
+    /// Trait implementation
+    fn gcm_encrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        host_modes::gcm_encrypt_update(self, ctx, data)
+    } // SoftwareBackend::gcm_encrypt_update()
+
+    /// Decrypts (in place) the next chunk of a streaming AES GCM decryption
+    /// Trait implementation
+    fn gcm_decrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        host_modes::gcm_decrypt_update(self, ctx, data)
+    } // SoftwareBackend::gcm_decrypt_update()
+
+    /// Completes a streaming AES GCM encryption and returns the tag
+    /// Trait implementation
+    fn gcm_encrypt_finish(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::gcm_encrypt_finish(self, ctx, tag_length)
+    } // SoftwareBackend::gcm_encrypt_finish()
+
+    /// Completes a streaming AES GCM decryption and verifies the tag
+    /// Trait implementation
+    fn gcm_decrypt_finish(&self, ctx: AtcaAesGcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        host_modes::gcm_decrypt_finish(self, ctx, tag)
+    } // SoftwareBackend::gcm_decrypt_finish()
+
+    /// Starts a streaming AES CCM encryption or decryption
+    /// Trait implementation
+    fn ccm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        host_modes::ccm_init(
+            self,
+            slot_id,
+            nonce,
+            key,
+            aad_length,
+            text_length,
+            tag_length,
+        )
+    } // SoftwareBackend::ccm_init()
+
+    /// Feeds the next chunk of additional authenticated data into a streaming AES CCM operation
+    /// Trait implementation
+    fn ccm_update_aad(&self, ctx: AtcaAesCcmCtx, data: &[u8]) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        host_modes::ccm_update_aad(self, ctx, data)
+    } // SoftwareBackend::ccm_update_aad()
+
+    /// Encrypts (in place) the next chunk of a streaming AES CCM encryption
+    /// Trait implementation
+    fn ccm_encrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        host_modes::ccm_encrypt_update(self, ctx, data)
+    } // SoftwareBackend::ccm_encrypt_update()
+
+    /// Decrypts (in place) the next chunk of a streaming AES CCM decryption
+    /// Trait implementation
+    fn ccm_decrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        host_modes::ccm_decrypt_update(self, ctx, data)
+    } // SoftwareBackend::ccm_decrypt_update()
+
+    /// Completes a streaming AES CCM encryption and returns the tag
+    /// Trait implementation
+    fn ccm_encrypt_finish(&self, ctx: AtcaAesCcmCtx) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::ccm_encrypt_finish(self, ctx)
+    } // SoftwareBackend::ccm_encrypt_finish()
+
+    /// Completes a streaming AES CCM decryption and verifies the tag
+    /// Trait implementation
+    fn ccm_decrypt_finish(&self, ctx: AtcaAesCcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        host_modes::ccm_decrypt_finish(self, ctx, tag)
+    } // SoftwareBackend::ccm_decrypt_finish()
+
+    /// Derives TLS 1.2 session keys
+    /// Trait implementation
+    fn tls_derive_keys(
+        &self,
+        slot_id: u8,
+        server_public_key: &[u8],
+        client_random: &[u8],
+        server_random: &[u8],
+        key_block_length: usize,
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::tls_derive_keys(
+            self,
+            slot_id,
+            server_public_key,
+            client_random,
+            server_random,
+            key_block_length,
+            session,
+        )
+    } // SoftwareBackend::tls_derive_keys()
+
+    /// Calculates verify_data of a TLS 1.2 Finished message
+    /// Trait implementation
+    fn tls_verify_data(
+        &self,
+        label: TlsFinishedLabel,
+        handshake_hash: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::tls_verify_data(self, label, handshake_hash, session)
+    } // SoftwareBackend::tls_verify_data()
+
+    /// Encrypts data for the owner of the given public key
+    /// Trait implementation
+    fn ecies_encrypt(&self, public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::ecies_encrypt(self, public_key, plaintext)
+    } // SoftwareBackend::ecies_encrypt()
+
+    /// Decrypts data produced by ecies_encrypt() with the private key stored in the given slot
+    /// Trait implementation
+    fn ecies_decrypt(&self, slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::ecies_decrypt(self, slot_id, data)
+    } // SoftwareBackend::ecies_decrypt()
+
+    /// Wrap a key with the AES key stored in a slot (RFC 3394 / RFC 5649)
+    /// Trait implementation
+    fn wrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::wrap_key(self, algorithm, slot_id, key)
+    } // SoftwareBackend::wrap_key()
+
+    /// Unwrap a key with the AES key stored in a slot (RFC 3394 / RFC 5649)
+    /// Trait implementation
+    fn unwrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        host_modes::unwrap_key(self, algorithm, slot_id, wrapped_key)
+    } // SoftwareBackend::unwrap_key()
+
+    /// Request ATECC to return own device type
+    /// Trait implementation
+    fn get_device_type(&self) -> AtcaDeviceType {
+        self.get_device_type()
+    } // SoftwareBackend::get_device_type()
+
+    /// Request ATECC to check if its configuration is locked.
+    /// If true, a chip can be used for cryptographic operations
+    /// Trait implementation
+    fn is_configuration_locked(&self) -> bool {
+        self.config_zone_locked
+    } // SoftwareBackend::is_configuration_locked()
+
+    /// Request ATECC to check if its Data Zone is locked.
+    /// If true, a chip can be used for cryptographic operations
+    /// Trait implementation
+    fn is_data_zone_locked(&self) -> bool {
+        self.data_zone_locked
+    } // SoftwareBackend::is_data_zone_locked()
+
+    /// Returns a structure containing configuration data read from ATECC
+    /// during initialization of the SoftwareBackend object.
+    /// Trait implementation
+    fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus {
+        self.get_config(atca_slots)
+    } // SoftwareBackend::get_config()
+
+    /// Request ATECC to read and return own configuration zone.
+    /// Note: this function returns raw data, function get_config(..) implements a more
+    /// structured return.
+    /// Trait implementation
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        self.read_config_zone(config_data)
+    } // SoftwareBackend::read_config_zone()
+
+    /// Request ATECC to write its whole configuration zone
+    /// Trait implementation
+    fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        self.write_config_zone(config_data)
+    } // SoftwareBackend::write_config_zone()
+
+    /// Request ATECC to lock its configuration zone
+    /// Trait implementation
+    fn lock_config_zone(&self) -> AtcaStatus {
+        self.lock_config_zone()
+    } // SoftwareBackend::lock_config_zone()
+
+    /// Request ATECC to lock its data zone
+    /// Trait implementation
+    fn lock_data_zone(&self) -> AtcaStatus {
+        self.lock_data_zone()
+    } // SoftwareBackend::lock_data_zone()
+
+    /// Command accesses some static or dynamic information from the ATECC chip
+    /// Trait implementation
+    fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
+        self.info_cmd(command)
+    } // SoftwareBackend::info_cmd()
+
+    /// A function that adds an access key for securely reading or writing data
+    /// that is located in a specific slot on the ATECCx08 chip.
+    /// Data is not written to the ATECCx08 chip, but to the SoftwareBackend structure.
+    /// Trait implementation
+    fn add_access_key(&self, slot_id: u8, access_key: &[u8]) -> AtcaStatus {
+        self.add_access_key(slot_id, access_key)
+    } // SoftwareBackend::add_access_key()
+
+    /// A function that deletes all access keys for secure read or write operations
+    /// performed by the ATECCx08 chip
+    /// Trait implementation
+    fn flush_access_keys(&self) -> AtcaStatus {
+        self.flush_access_keys()
+    } // SoftwareBackend::flush_access_keys()
+
+    /// Sets the host nonce of the Nonce command that precedes the next
+    /// encrypted read or write of a slot
+    /// Trait implementation
+    fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus {
+        self.set_host_nonce(num_in)
+    } // SoftwareBackend::set_host_nonce()
+
+    /// Get serial number of the ATECC device
+    /// Trait implementation
+    fn get_serial_number(&self) -> [u8; ATCA_SERIAL_NUM_SIZE] {
+        self.serial_number
+    } // SoftwareBackend::get_serial_number()
+
+    /// Checks if the chip supports AES encryption
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn is_aes_enabled(&self) -> bool {
+        self.chip_options.aes_enabled
+    } // SoftwareBackend::is_aes_enabled()
+
+    /// Checks if the chip supports AES for KDF operations
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn is_kdf_aes_enabled(&self) -> bool {
+        self.chip_options.kdf_aes_enabled
+    } // SoftwareBackend::is_kdf_aes_enabled()
+
+    /// Checks whether transmission between chip and host is to be encrypted
+    /// (IO encryption is only possible for ATECC608x chip)
+    /// Trait implementation
+    fn is_io_protection_key_enabled(&self) -> bool {
+        self.chip_options.io_key_enabled
+    } // SoftwareBackend::is_io_protection_key_enabled()
+
+    ///
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn get_ecdh_output_protection_state(&self) -> OutputProtectionState {
+        self.chip_options.ecdh_output_protection
+    } // SoftwareBackend::get_ecdh_output_protection_state()
+
+    ///
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn get_kdf_output_protection_state(&self) -> OutputProtectionState {
+        self.chip_options.kdf_output_protection
+    } // SoftwareBackend::get_kdf_output_protection_state()
+
+    /// Perform an AES-128 encrypt operation with a key in the device
+    /// Trait implementation
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_encrypt_block(key_id, key_block, input)
+    }
+    /// Perform an AES-128 decrypt operation with a key in the device
+    /// Trait implementation
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_decrypt_block(key_id, key_block, input)
+    }
+
+    /// Request ATECC608x to run the SelfTest command
+    /// Trait implementation
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        self.self_test()
+    }
+    /// Wakes the chip up
+    /// Trait implementation
+    fn wake(&self) -> AtcaStatus {
+        self.wake()
+    }
+    /// Puts the chip into the idle mode
+    /// Trait implementation
+    fn idle(&self) -> AtcaStatus {
+        self.idle()
+    }
+    /// Puts the chip into the sleep mode
+    /// Trait implementation
+    fn sleep(&self) -> AtcaStatus {
+        self.sleep()
+    }
+    /// Tells if the chip seems to be gone
+    /// Trait implementation
+    fn is_disconnected(&self) -> bool {
+        self.is_disconnected()
+    }
+    /// Re-initializes the communication with the chip
+    /// Trait implementation
+    fn reconnect(&self) -> AtcaStatus {
+        self.reconnect()
+    }
+    /// Reads again the state cached at initialization
+    /// Trait implementation
+    fn refresh(&self) -> AtcaStatus {
+        self.refresh()
+    }
+    /// Reads again the lock state of the zones
+    /// Trait implementation
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        self.refresh_lock_state()
+    }
+
+    /// ATECC device instance destructor
+    /// Trait implementation
+    fn release(&self) -> AtcaStatus {
+        self.release()
+    } // SoftwareBackend::release()
+
+    //--------------------------------------------------
+    //
+    // Functions available only during testing
+    //
+    //--------------------------------------------------
+
+    /// A generic function that reads data from the chip
+    /// Trait implementation
+    #[cfg(test)]
+    fn read_zone(
+        &self,
+        zone: u8,
+        slot: u16,
+        block: u8,
+        offset: u8,
+        data: &mut Vec<u8>,
+        len: u8,
+    ) -> AtcaStatus {
+        self.read_zone(zone, slot, block, offset, data, len)
+    } // SoftwareBackend::read_zone()
+    /// Compare internal config zone contents vs. config_data.
+    /// Diagnostic function.
+    /// Trait implementation
+    #[cfg(test)]
+    fn cmp_config_zone(&self, config_data: &mut [u8]) -> Result<bool, AtcaStatus> {
+        self.cmp_config_zone(config_data)
+    } // SoftwareBackend::cmp_config_zone()
+    /// A function that takes an encryption key for securely reading or writing data
+    /// that is located in a specific slot on an ATECCx08 chip.
+    /// Data is not taken directly from the ATECCx08 chip, but from the SoftwareBackend structure
+    /// Trait implementation
+    #[cfg(test)]
+    fn get_access_key(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        self.get_access_key(slot_id, key)
+    } // SoftwareBackend::get_access_key()
+    /// Initialize context for AES CTR operation with an existing IV
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.aes_ctr_init(slot_id, counter_size, iv)
+    }
+    /// Increments AES CTR counter value
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.aes_ctr_increment(ctx)
+    }
+    /// Initialize context for AES CBC operation.
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_cbc_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        self.aes_cbc_init(slot_id, iv)
+    }
+}
+
+/// Commands the host-side modes of operation are built on
+impl ModePrimitives for SoftwareBackend {
+    fn get_device_type(&self) -> AtcaDeviceType {
+        self.get_device_type()
+    }
+
+    fn check_that_configuration_is_not_locked(&self, both: bool) -> bool {
+        self.check_that_configuration_is_not_locked(both)
+    }
+
+    fn chip_options(&self) -> Result<ChipOptions, AtcaStatus> {
+        Ok(self.chip_options)
+    }
+
+    fn slot_config(&self, slot_id: u8) -> Result<SlotConfig, AtcaStatus> {
+        self.slots
+            .get(slot_id as usize)
+            .map(|slot| slot.config)
+            .ok_or(AtcaStatus::AtcaInvalidId)
+    }
+
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.random(rand_out)
+    }
+
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        self.nonce(target, data)
+    }
+
+    fn cipher_aes_common(
+        &self,
+        slot_id: u8,
+        data_size: usize,
+        key: Option<Vec<u8>>,
+    ) -> Result<u16, AtcaStatus> {
+        self.cipher_aes_common(slot_id, data_size, key)
+    }
+
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_encrypt_block(key_id, key_block, input)
+    }
+
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_decrypt_block(key_id, key_block, input)
+    }
+
+    fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.aes_ctr_init(slot_id, counter_size, iv)
+    }
+
+    fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.aes_ctr_increment(ctx)
+    }
+
+    fn aes_cbc_encrypt_block(
+        &self,
+        ctx: atca_aes_cbc_ctx_t,
+        plaintext: &[u8],
+        ciphertext: &mut [u8],
+    ) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        self.aes_cbc_encrypt_block(ctx, plaintext, ciphertext)
+    }
+
+    fn aes_gcm_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_init(slot_id, iv)
+    }
+
+    fn aes_gcm_aad_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_aad_update(ctx, data)
+    }
+
+    fn aes_gcm_encrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        encrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_encrypt_update(ctx, data, encrypted)
+    }
+
+    fn aes_gcm_decrypt_update(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        data: &[u8],
+        decrypted: &mut [u8; ATCA_AES_DATA_SIZE],
+    ) -> Result<atca_aes_gcm_ctx_t, AtcaStatus> {
+        self.aes_gcm_decrypt_update(ctx, data, decrypted)
+    }
+
+    fn aes_gcm_encrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.aes_gcm_encrypt_finish(ctx, tag_length)
+    }
+
+    fn aes_gcm_decrypt_finish(
+        &self,
+        ctx: atca_aes_gcm_ctx_t,
+        tag: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.aes_gcm_decrypt_finish(ctx, tag)
+    }
+
+    fn ecdh_parameters_check(&self, slot_id: u8, public_key: &[u8]) -> Result<(), AtcaStatus> {
+        self.ecdh_parameters_check(slot_id, public_key)
+    }
+
+    fn ecdh_without_output(
+        &self,
+        mode: u8,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<(), AtcaStatus> {
+        self.ecdh_without_output(mode, slot_id, public_key)
+    }
+
+    fn kdf_common(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+        io_key: Option<&[u8]>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.kdf_common(algorithm, source, target, message, io_key)
+    }
+
+    fn gen_temp_key(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let private_key = SecretKey::random(&mut rand::thread_rng());
+        self.write_private_key(ATCA_ATECC_SLOTS_COUNT, &private_key.to_bytes());
+        Ok(raw_public_key(&private_key.public_key()))
+    }
+}
+
+/// Implementation of the ATECC608A commands on the emulated chip
+impl SoftwareBackend {
+    /// Request ATECC to generate a vector of random bytes
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(false) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        *rand_out = random_bytes(ATCA_RANDOM_BUFFER_SIZE);
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::random()
+
+    /// Request ATECC to compute a message hash (SHA256)
+    fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(false) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if u16::try_from(message.len()).is_err() {
+            return AtcaStatus::AtcaBadParam;
+        }
+        *digest = Sha256::digest(&message).to_vec();
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::sha()
+
+    /// Starts a SHA256 digest calculated by ATECC in several steps
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(false) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        self.state().sha = Sha256::new();
+        Ok(AtcaSha256Ctx::default())
+    } // SoftwareBackend::sha_start()
+
+    /// Feeds the next part of a message into a SHA256 digest calculation.
+    /// The intermediate state is kept by the SHA engine of the emulated chip
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        let mut ctx = ctx;
+        self.state().sha.update(data);
+        ctx.ctx.total_msg_size = ctx.ctx.total_msg_size.wrapping_add(data.len() as u32);
+        Ok(ctx)
+    } // SoftwareBackend::sha_update()
+
+    /// Completes a SHA256 digest calculation and returns the digest
+    fn sha_finish(&self, _ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        Ok(self.state().sha.finalize_reset().to_vec())
+    } // SoftwareBackend::sha_finish()
+
+    /// Request ATECC to compute HMAC-SHA256 of a message with the 32-byte key stored in a slot,
+    /// the key never leaves the chip
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(false) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+        let slot_data = self.slots[slot_id as usize].config;
+        if slot_data.ecc_key_attr.is_private || slot_data.no_mac {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+
+        Ok(hmac_sha256(&self.slot_key(slot_id)[..], &[message]).to_vec())
+    } // SoftwareBackend::sha_hmac()
+
+    /// Execute a Nonce command in pass-through mode to load one of the
+    /// device's internal buffers with a fixed value.
+    /// Available targets are TempKey (32 or 64 bytes), Message Digest Buffer
+    /// (32 or 64 bytes), or the Alternate Key Buffer (32 bytes).
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        let alt_key_buff: bool = NonceTarget::AltKeyBuf == target;
+        let no_len_32: bool = data.len() != ATCA_NONCE_SIZE;
+        let no_len_64: bool = data.len() != (2 * ATCA_NONCE_SIZE);
+
+        if no_len_32 && (alt_key_buff || no_len_64) {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        let mut state = self.state();
+        match target {
+            NonceTarget::TempKey => {
+                state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+                state.temp_key[..data.len()].copy_from_slice(data);
+            }
+            NonceTarget::MsgDigBuf => {
+                state.msg_dig_buf = [0; 2 * ATCA_KEY_SIZE];
+                state.msg_dig_buf[..data.len()].copy_from_slice(data);
+            }
+            NonceTarget::AltKeyBuf => state.alt_key_buf.copy_from_slice(data),
+        }
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::nonce()
+
+    /// Execute a Nonce command to generate a random nonce combining a host
+    /// nonce and a device random number.
+    /// TempKey is loaded with SHA256(random number || host nonce || 0x16 || 0x00 || 0x00)
+    fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
+        if host_nonce.len() != ATCA_NONCE_NUMIN_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        *rand_out = random_bytes(ATCA_RANDOM_BUFFER_SIZE);
+        let mut sha = Sha256::new();
+        sha.update(&rand_out[..]);
+        sha.update(host_nonce);
+        sha.update([NONCE_OPCODE, NONCE_MODE_SEED_UPDATE, 0x00]);
+
+        let mut state = self.state();
+        state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+        state.temp_key[..ATCA_KEY_SIZE].copy_from_slice(&sha.finalize());
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::nonce_rand()
+
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    /// TempKey is loaded with SHA256 of the data, the command parameters,
+    /// the serial number and the previous TempKey
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(false) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let data = match zone {
+            GenDigZone::Data if other_data.is_empty() => {
+                if self.slots[slot_id as usize].config.ecc_key_attr.is_private {
+                    return AtcaStatus::AtcaExecutionError;
+                }
+                self.slot_key(slot_id)
+            }
+            GenDigZone::SharedNonce if other_data.len() == ATCA_KEY_SIZE => {
+                let mut data = Zeroizing::new([0; ATCA_KEY_SIZE]);
+                data.copy_from_slice(other_data);
+                data
+            }
+            _ => return AtcaStatus::AtcaInvalidSize,
+        };
+
+        let mut state = self.state();
+        let message = Zeroizing::new(gen_dig_message(
+            zone,
+            slot_id,
+            &data[..],
+            &self.serial_number,
+            &state.temp_key,
+        ));
+        state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+        state.temp_key[..ATCA_KEY_SIZE].copy_from_slice(&Sha256::digest(&message[..]));
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::gen_dig()
+
+    /// Execute a MAC command over the key stored in a slot and a challenge or TempKey
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        SlotId::new(slot_id)?;
+        if self.slots[slot_id as usize].config.no_mac {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        let mode = match challenge {
+            None => MAC_MODE_TEMPKEY,
+            Some(_) => MAC_MODE_CHALLENGE,
+        };
+        let challenge = self.mac_challenge(slot_id, challenge)?;
+        let key = self.slot_key(slot_id);
+        let message = Zeroizing::new(mac_message(
+            mode,
+            slot_id,
+            &key[..],
+            &challenge[..],
+            &self.serial_number,
+        ));
+        Ok(Sha256::digest(&message[..]).to_vec())
+    } // SoftwareBackend::mac()
+
+    /// Execute a CheckMac command to verify a MAC calculated by the host
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if response.len() != ATCA_SHA2_256_DIGEST_SIZE
+            || other_data.len() != ATCA_CHECK_MAC_OTHER_DATA_SIZE
+        {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let challenge = self.mac_challenge(slot_id, challenge)?;
+        let key = self.slot_key(slot_id);
+        let message = Zeroizing::new(check_mac_message(
+            &key[..],
+            &challenge[..],
+            other_data,
+            &self.serial_number,
+        ));
+        Ok(constant_time_eq(&Sha256::digest(&message[..]), response))
+    } // SoftwareBackend::check_mac()
+
+    /// The monotonic counters are not emulated, AteccSimulator has them
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        if counter_id > 1 {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        Err(AtcaStatus::AtcaUnimplemented)
+    } // SoftwareBackend::counter_read()
+
+    /// The monotonic counters are not emulated, AteccSimulator has them
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        if counter_id > 1 {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        Err(AtcaStatus::AtcaUnimplemented)
+    } // SoftwareBackend::counter_increment()
+
+    /// Request ATECC to generate a cryptographic key
+    fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(false) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+
+        let key_slot = match self.encryption_key_setup_parameters_check(key_type, slot_id) {
+            Ok(key_slot) => key_slot,
+            Err(err) => return err,
+        };
+
+        match key_type {
+            KeyType::P256EccKey => {
+                let slot_data = self.slots[slot_id as usize].config;
+                if !slot_data.is_secret {
+                    return AtcaStatus::AtcaBadParam;
+                }
+                if !slot_data.ecc_key_attr.is_private {
+                    // GenKey only creates private keys
+                    return AtcaStatus::AtcaExecutionError;
+                }
+                let private_key = SecretKey::random(&mut rand::thread_rng());
+                self.write_private_key(slot_id, &private_key.to_bytes());
+                AtcaStatus::AtcaSuccess
+            }
+            KeyType::Aes => {
+                let mut key: Vec<u8> = random_bytes(ATCA_AES_KEY_SIZE);
+                key.resize(ATCA_BLOCK_SIZE, 0);
+                match key_slot {
+                    SlotId::TempKey => self.nonce(NonceTarget::TempKey, &key),
+                    SlotId::Slot(slot) => match self.slots[slot as usize].config.write_config {
+                        WriteConfig::Always | WriteConfig::Encrypt => {
+                            self.write_slot_data(slot, 0, &key)
+                        }
+                        _ => AtcaStatus::AtcaBadParam,
+                    },
+                }
+            }
+            _ => AtcaStatus::AtcaBadParam,
+        }
+    } // SoftwareBackend::gen_key()
+
+    /// Request ATECC to import a cryptographic key
+    fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        let key_slot = match self.encryption_key_setup_parameters_check(key_type, slot_id) {
+            Ok(key_slot) => key_slot,
+            Err(err) => return err,
+        };
+
+        if ((key_type == KeyType::Aes) && (key_data.len() != ATCA_AES_KEY_SIZE))
+            || ((key_type == KeyType::P256EccKey)
+                && !((key_data.len() == ATCA_ATECC_PRIV_KEY_SIZE)
+                    || (key_data.len() == ATCA_ATECC_PUB_KEY_SIZE)))
+            || ((key_type == KeyType::ShaOrText)
+                && (key_data.is_empty()
+                    || (key_data.len() > get_slot_capacity(slot_id).bytes as usize)))
+        {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        match key_type {
+            KeyType::P256EccKey => match key_data.len() {
+                ATCA_ATECC_PUB_KEY_SIZE => {
+                    if slot_id < ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY {
+                        return AtcaStatus::AtcaInvalidId;
+                    }
+                    let slot_data = self.slots[slot_id as usize].config;
+                    if slot_data.ecc_key_attr.is_private
+                        || !matches!(
+                            slot_data.write_config,
+                            WriteConfig::Always | WriteConfig::PubInvalid
+                        )
+                    {
+                        // The slot does not accept public keys written in clear text
+                        return AtcaStatus::AtcaExecutionError;
+                    }
+                    self.write_public_key(slot_id, key_data);
+                    AtcaStatus::AtcaSuccess
+                }
+                _ => match self.get_write_key_idx(slot_id) {
+                    Some(write_key_idx) => {
+                        if let Err(err) = self.check_access_key(write_key_idx) {
+                            return err;
+                        }
+                        if !self.slots[slot_id as usize].config.ecc_key_attr.is_private {
+                            // PrivWrite only loads private keys
+                            return AtcaStatus::AtcaExecutionError;
+                        }
+                        self.write_private_key(slot_id, key_data);
+                        AtcaStatus::AtcaSuccess
+                    }
+                    None => AtcaStatus::AtcaBadParam,
+                },
+            },
+            KeyType::Aes => {
+                let mut temp_key: Vec<u8> = key_data.to_vec();
+                temp_key.resize(ATCA_BLOCK_SIZE, 0);
+
+                match key_slot {
+                    SlotId::TempKey => self.nonce(NonceTarget::TempKey, &temp_key),
+                    SlotId::Slot(slot) => match self.slots[slot as usize].config.write_config {
+                        WriteConfig::Always | WriteConfig::Encrypt => {
+                            self.write_slot_data(slot, 0, &temp_key)
+                        }
+                        _ => AtcaStatus::AtcaBadParam,
+                    },
+                }
+            }
+            KeyType::ShaOrText => self.write_sha_or_text_key_to_slot(slot_id, key_data),
+            _ => AtcaStatus::AtcaBadParam,
+        }
+    } // SoftwareBackend::import_key()
+
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1).
+    /// The key is written in the same way as by import_key().
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        match private_key_from_der(key_data) {
+            Ok(mut private_key) => {
+                let result = self.import_key(KeyType::P256EccKey, &private_key, slot_id);
+                private_key.iter_mut().for_each(|x| *x = 0x00);
+                result
+            }
+            Err(err) => err,
+        }
+    } // SoftwareBackend::import_private_key_der()
+
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus {
+        match private_key_from_pem(key_data) {
+            Ok(mut private_key) => {
+                let result = self.import_key(KeyType::P256EccKey, &private_key, slot_id);
+                private_key.iter_mut().for_each(|x| *x = 0x00);
+                result
+            }
+            Err(err) => err,
+        }
+    } // SoftwareBackend::import_private_key_pem()
+
+    /// Request ATECC to export a cryptographic key.
+    /// For key type 'ShaOrText', the amount of data returned is as large as
+    /// size of the given buffer 'key_data', but when this size is greater than
+    /// maximum amount of data that can be hold by slot, this function will return an error.
+    /// For other types of keys, the amount of data returned corresponds to size of a given key.
+    fn export_key(&self, key_type: KeyType, key_data: &mut Vec<u8>, slot_id: u8) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        match key_type {
+            KeyType::P256EccKey => self.get_public_key(slot_id, key_data),
+            KeyType::Aes => self.read_aes_key_from_slot(slot_id, key_data),
+            KeyType::ShaOrText => self.read_sha_or_text_key_from_slot(slot_id, key_data),
+            _ => AtcaStatus::AtcaBadParam,
+        }
+    } // SoftwareBackend::export_key()
+
+    /// Request ATECC to write data to a slot, starting at the given byte offset.
+    /// Depending on the slot configuration, data is written in clear text
+    /// or encrypted with the write key previously set by add_access_key().
+    /// Clear text writes must be aligned to 4-byte words, encrypted writes
+    /// (and writes to secret slots) to 32-byte blocks. The last block of a slot
+    /// may be written partially.
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let granularity = match self.get_slot_write_granularity(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        let capacity = get_slot_capacity(slot_id).bytes as usize;
+        if data.is_empty() || (offset > capacity) || (data.len() > (capacity - offset)) {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        let end = offset + data.len();
+        if !offset.is_multiple_of(granularity)
+            || (!data.len().is_multiple_of(granularity) && (end != capacity))
+        {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        let slot = self.slots[slot_id as usize];
+        if slot.is_locked || slot.config.ecc_key_attr.is_private {
+            // Locked slots and private keys cannot be changed by the Write command
+            return AtcaStatus::AtcaExecutionError;
+        }
+        if WriteConfig::Encrypt == slot.config.write_config {
+            if let Err(err) = self.check_access_key(slot.config.write_key) {
+                return err;
+            }
+        }
+
+        self.state().data_zone[slot_id as usize][offset..end].copy_from_slice(data);
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::write_slot_data()
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset.
+    /// Depending on the slot configuration, data is read in clear text
+    /// or encrypted with the read key previously set by add_access_key().
+    /// Slots that are secret and not configured for encrypted reads cannot be read.
+    fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+        let slot_data = self.slots[slot_id as usize].config;
+        if slot_data.is_secret && !slot_data.read_key.encrypt_read {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        let capacity = get_slot_capacity(slot_id).bytes as usize;
+        if (len == 0) || (offset > capacity) || (len > (capacity - offset)) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+
+        if slot_data.is_secret && slot_data.read_key.encrypt_read {
+            match self.get_read_key_idx(slot_id) {
+                Some(read_key_idx) => self.check_access_key(read_key_idx)?,
+                None => return Err(AtcaStatus::AtcaBadParam),
+            }
+        }
+
+        Ok(self.state().data_zone[slot_id as usize][offset..(offset + len)].to_vec())
+    } // SoftwareBackend::read_slot_data()
+
+    /// Request ATECC to write the OTP zone, starting at the given byte offset.
+    /// Writes must be aligned to 4-byte words. The zone can be written only
+    /// after the configuration zone has been locked and before the data zone is locked
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        if !self.config_zone_locked {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if self.data_zone_locked {
+            return AtcaStatus::AtcaDataZoneLocked;
+        }
+        if let Err(err) = check_otp_range(offset, data.len()) {
+            return err;
+        }
+        self.state().otp_zone[offset..(offset + data.len())].copy_from_slice(data);
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::write_otp_zone()
+
+    /// Request ATECC to read the OTP zone, starting at the given byte offset.
+    /// Reads must be aligned to 4-byte words, the zone can be read once the data zone is locked
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        if !self.data_zone_locked {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        check_otp_range(offset, len)?;
+        Ok(self.state().otp_zone[offset..(offset + len)].to_vec())
+    } // SoftwareBackend::read_otp_zone()
+
+    /// Depending on the socket configuration, this function calculates
+    /// public key based on an existing private key in the socket
+    /// or exports the public key directly
+    fn get_public_key(&self, slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let slot_data = self.slots[slot_id as usize].config;
+        if slot_data.key_type != KeyType::P256EccKey {
+            return AtcaStatus::AtcaBadParam;
+        }
+        public_key.resize(ATCA_ATECC_PUB_KEY_SIZE, 0);
+
+        if slot_data.is_secret {
+            if slot_data.pub_info && slot_data.ecc_key_attr.is_private {
+                match self.read_private_key(slot_id) {
+                    Ok(private_key) => {
+                        public_key.copy_from_slice(&raw_public_key(&private_key.public_key()));
+                        AtcaStatus::AtcaSuccess
+                    }
+                    Err(err) => err,
+                }
+            } else if slot_data.read_key.encrypt_read {
+                if slot_id < ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY {
+                    AtcaStatus::AtcaInvalidId
+                } else {
+                    AtcaStatus::AtcaUnimplemented
+                }
+            } else {
+                AtcaStatus::AtcaBadParam
+            }
+        } else if slot_data.write_config == WriteConfig::Always {
+            if slot_id < ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY {
+                AtcaStatus::AtcaInvalidId
+            } else {
+                public_key.copy_from_slice(&self.read_public_key(slot_id));
+                AtcaStatus::AtcaSuccess
+            }
+        } else {
+            AtcaStatus::AtcaBadParam
+        }
+    } // SoftwareBackend::get_public_key()
+
+    /// Request ATECC to generate an ECDSA signature.
+    /// The digest to be signed is loaded into the Message Digest Buffer,
+    /// or the buffer chosen by the caller.
+    fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        signature.resize(ATCA_SIG_SIZE, 0);
+        let (buffer, hash) = match mode {
+            SignMode::External(hash) => (DigestBuffer::MsgDigBuf, hash),
+            SignMode::ExternalVia(buffer, hash) => (buffer, hash),
+            _ => return AtcaStatus::AtcaUnimplemented,
+        };
+        if hash.len() != ATCA_SHA2_256_DIGEST_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        let slot_data = self.slots[slot_id as usize].config;
+        if (slot_data.key_type != KeyType::P256EccKey)
+            || !slot_data.ecc_key_attr.is_private
+            || !slot_data.ecc_key_attr.ext_sign
+        {
+            return AtcaStatus::AtcaExecutionError;
+        }
+        let digest = match self.load_digest(buffer, &hash) {
+            Ok(digest) => digest,
+            Err(err) => return err,
+        };
+        match self.sign_prehash(slot_id, &digest) {
+            Ok(val) => {
+                signature.copy_from_slice(&val);
+                AtcaStatus::AtcaSuccess
+            }
+            Err(err) => err,
+        }
+    } // SoftwareBackend::sign_hash()
+
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot.
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        SlotId::new(slot_id)?;
+
+        let mut ctx = self.sha_start()?;
+        ctx = self.sha_update(ctx, message)?;
+        let digest = self.sha_finish(ctx)?;
+
+        let mut signature: Vec<u8> = Vec::new();
+        match self.sign_hash(SignMode::External(digest), slot_id, &mut signature) {
+            AtcaStatus::AtcaSuccess => Ok(signature),
+            err => Err(err),
+        }
+    } // SoftwareBackend::sign_message()
+
+    /// Request ATECC to execute a Sign command in the given mode.
+    /// Only the external modes are emulated, the message is read from the buffer
+    /// a previous nonce() has loaded.
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+        check_sign_base_mode(self.get_device_type(), mode)?;
+        if (mode & ATCA_SIGN_MODE_EXTERNAL) == 0 {
+            return Err(AtcaStatus::AtcaUnimplemented);
+        }
+        let slot_data = self.slots[slot_id as usize].config;
+        if (slot_data.key_type != KeyType::P256EccKey)
+            || !slot_data.ecc_key_attr.is_private
+            || !slot_data.ecc_key_attr.ext_sign
+        {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        let digest = {
+            let state = self.state();
+            match mode & ATCA_SIGN_MODE_SOURCE_MSGDIGBUF {
+                0 => state.temp_key[..ATCA_SHA2_256_DIGEST_SIZE].to_vec(),
+                _ => state.msg_dig_buf[..ATCA_SHA2_256_DIGEST_SIZE].to_vec(),
+            }
+        };
+        self.sign_prehash(slot_id, &digest)
+    } // SoftwareBackend::sign_base()
+
+    /// Request ATECC to verify ECDSA signature
+    fn verify_hash(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if (signature.len() != ATCA_SIG_SIZE) || (hash.len() != ATCA_SHA2_256_DIGEST_SIZE) {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        };
+
+        match mode {
+            VerifyMode::Internal(slot_number) => {
+                SlotId::new(slot_number)?;
+                let slot_data = self.slots[slot_number as usize].config;
+                if (slot_data.key_type != KeyType::P256EccKey) || slot_data.ecc_key_attr.is_private
+                {
+                    return Err(AtcaStatus::AtcaExecutionError);
+                }
+                verify_digest(&self.read_public_key(slot_number), hash, signature)
+            }
+            VerifyMode::External(public_key) => {
+                if public_key.len() != ATCA_ATECC_PUB_KEY_SIZE {
+                    return Err(AtcaStatus::AtcaInvalidId);
+                }
+                verify_digest(&public_key, hash, signature)
+            }
+            VerifyMode::ExternalVia(buffer, public_key) => {
+                let digest = self.load_digest(buffer, hash)?;
+                self.verify_hash(VerifyMode::External(public_key), &digest, signature)
+            }
+            VerifyMode::InternalVia(buffer, slot_number) => {
+                let digest = self.load_digest(buffer, hash)?;
+                self.verify_hash(VerifyMode::Internal(slot_number), &digest, signature)
+            }
+            _ => Err(AtcaStatus::AtcaUnimplemented),
+        }
+    } // SoftwareBackend::verify_hash()
+
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let mut signature: Vec<u8> = Vec::new();
+        match self.sign_hash(mode, slot_id, &mut signature) {
+            AtcaStatus::AtcaSuccess => match format {
+                SignatureFormat::Raw => Ok(signature),
+                SignatureFormat::Der => signature_to_der(&signature),
+            },
+            err => Err(err),
+        }
+    } // SoftwareBackend::sign_hash_formatted()
+
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        let signature = convert_signature(signature, format, SignatureFormat::Raw)?;
+        self.verify_hash(mode, hash, &signature)
+    } // SoftwareBackend::verify_hash_formatted()
+
+    /// Data encryption function in AES unauthenticated cipher alhorithms modes
+    fn cipher_encrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if !self.chip_options.aes_enabled {
+            // If chip does not support AES hardware encryption, the operation cannot be performed
+            return AtcaStatus::AtcaBadParam;
+        }
+
+        match algorithm {
+            CipherAlgorithm::Ctr(cipher_param) => self.cipher_aes_ctr(cipher_param, slot_id, data),
+            CipherAlgorithm::Cfb(cipher_param) => {
+                self.cipher_aes_cfb(cipher_param, slot_id, data, CipherOperation::Encrypt)
+            }
+            CipherAlgorithm::Ofb(cipher_param) => {
+                self.cipher_aes_ofb(cipher_param, slot_id, data, CipherOperation::Encrypt)
+            }
+            CipherAlgorithm::Ecb(cipher_param) => {
+                self.cipher_aes_ecb(cipher_param, slot_id, data, CipherOperation::Encrypt)
+            }
+            CipherAlgorithm::Cbc(cipher_param) => {
+                self.cipher_aes_cbc(cipher_param, slot_id, data, CipherOperation::Encrypt)
+            }
+            CipherAlgorithm::CbcPkcs7(cipher_param) => {
+                self.cipher_aes_cbc_pkcs7(cipher_param, slot_id, data, CipherOperation::Encrypt)
+            }
+            _ => AtcaStatus::AtcaUnimplemented,
+        }
+    } // SoftwareBackend::cipher_encrypt()
+
+    /// Data decryption function in AES unauthenticated cipher alhorithms modes
+    fn cipher_decrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        if self.check_that_configuration_is_not_locked(true) {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if !self.chip_options.aes_enabled {
+            // If chip does not support AES hardware encryption, the operation cannot be performed
+            return AtcaStatus::AtcaBadParam;
+        }
+
+        match algorithm {
+            CipherAlgorithm::Ctr(cipher_param) => self.cipher_aes_ctr(cipher_param, slot_id, data),
+            CipherAlgorithm::Cfb(cipher_param) => {
+                self.cipher_aes_cfb(cipher_param, slot_id, data, CipherOperation::Decrypt)
+            }
+            CipherAlgorithm::Ofb(cipher_param) => {
+                self.cipher_aes_ofb(cipher_param, slot_id, data, CipherOperation::Decrypt)
+            }
+            CipherAlgorithm::Ecb(cipher_param) => {
+                self.cipher_aes_ecb(cipher_param, slot_id, data, CipherOperation::Decrypt)
+            }
+            CipherAlgorithm::Cbc(cipher_param) => {
+                self.cipher_aes_cbc(cipher_param, slot_id, data, CipherOperation::Decrypt)
+            }
+            CipherAlgorithm::CbcPkcs7(cipher_param) => {
+                self.cipher_aes_cbc_pkcs7(cipher_param, slot_id, data, CipherOperation::Decrypt)
+            }
+            _ => AtcaStatus::AtcaUnimplemented,
+        }
+    } // SoftwareBackend::cipher_decrypt()
+
+    /// Data encryption function in AES AEAD (authenticated encryption with associated data) modes
+    fn aead_encrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut [u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if !self.chip_options.aes_enabled {
+            // If chip does not support AES hardware encryption, the operation cannot be performed
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        match algorithm {
+            AeadAlgorithm::Ccm(aead_param) => {
+                host_modes::encrypt_aes_ccm(self, aead_param, slot_id, data)
+            }
+            AeadAlgorithm::Gcm(aead_param) => {
+                host_modes::encrypt_aes_gcm(self, aead_param, slot_id, data)
+            }
+        }
+    } // SoftwareBackend::aead_encrypt()
+
+    /// Data decryption function in AES AEAD (authenticated encryption with associated data) modes
+    fn aead_decrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut [u8],
+    ) -> Result<bool, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(true) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        if !self.chip_options.aes_enabled {
+            // If chip does not support AES hardware encryption, the operation cannot be performed
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+
+        match algorithm {
+            AeadAlgorithm::Ccm(aead_param) => {
+                host_modes::decrypt_aes_ccm(self, aead_param, slot_id, data)
+            }
+            AeadAlgorithm::Gcm(aead_param) => {
+                host_modes::decrypt_aes_gcm(self, aead_param, slot_id, data)
+            }
+        }
+    } // SoftwareBackend::aead_decrypt()
+
+    /// Request ATECC to return own device type
+    fn get_device_type(&self) -> AtcaDeviceType {
+        AtcaDeviceType::ATECC608A
+    } // SoftwareBackend::get_device_type()
+
+    /// Returns a structure containing configuration data of the emulated chip
+    fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus {
+        atca_slots.clear();
+        atca_slots.extend_from_slice(&self.slots);
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::get_config()
+
+    /// Request ATECC to read and return own configuration zone.
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        *config_data = self.config_zone.to_vec();
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::read_config_zone()
+
+    /// The configuration zone of the emulated chip is fixed when it is created,
+    /// AteccSimulator emulates a chip that can still be provisioned
+    fn write_config_zone(&self, _config_data: &[u8]) -> AtcaStatus {
+        match self.config_zone_locked {
+            true => AtcaStatus::AtcaConfigZoneLocked,
+            false => AtcaStatus::AtcaUnimplemented,
+        }
+    } // SoftwareBackend::write_config_zone()
+
+    /// The lock state of the emulated chip is fixed when it is created
+    fn lock_config_zone(&self) -> AtcaStatus {
+        match self.config_zone_locked {
+            true => AtcaStatus::AtcaConfigZoneLocked,
+            false => AtcaStatus::AtcaUnimplemented,
+        }
+    } // SoftwareBackend::lock_config_zone()
+
+    /// The lock state of the emulated chip is fixed when it is created
+    fn lock_data_zone(&self) -> AtcaStatus {
+        match self.data_zone_locked {
+            true => AtcaStatus::AtcaDataZoneLocked,
+            false => AtcaStatus::AtcaUnimplemented,
+        }
+    } // SoftwareBackend::lock_data_zone()
+
+    /// Command accesses some static or dynamic information from the ATECC chip
+    fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
+        const IDX_REVISION: usize = 4;
+        match command {
+            InfoCmdType::Revision => {
+                Ok(self.config_zone[IDX_REVISION..(IDX_REVISION + ATCA_WORD_SIZE)].to_vec())
+            }
+            InfoCmdType::State => Ok(vec![0; ATCA_WORD_SIZE]),
+            _ => Err(AtcaStatus::AtcaUnimplemented),
+        }
+    } // SoftwareBackend::info_cmd()
+
+    /// A function that adds an access key for securely reading or writing data
+    /// that is located in a specific slot of the chip.
+    /// Data is not written to the chip, but to the SoftwareBackend structure.
+    fn add_access_key(&self, slot_id: u8, access_key: &[u8]) -> AtcaStatus {
+        if let Err(err) = self.access_key_setup_parameters_check(slot_id) {
+            return err;
+        };
+
+        if access_key.len() != ATCA_KEY_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        let mut key_arr = Zeroizing::new([0; ATCA_KEY_SIZE]);
+        key_arr.copy_from_slice(access_key);
+        self.access_keys
+            .lock()
+            .expect("Could not lock 'access_keys' mutex")
+            .insert(slot_id, key_arr);
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::add_access_key()
+
+    /// A function that deletes all access keys for secure read or write operations
+    fn flush_access_keys(&self) -> AtcaStatus {
+        self.access_keys
+            .lock()
+            .expect("Could not lock 'access_keys' mutex")
+            .clear();
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::flush_access_keys()
+
+    /// Encrypted reads and writes are emulated without a bus whose traffic
+    /// could be replayed, the host nonce is only checked
+    fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus {
+        match num_in {
+            Some(val) if val.len() != ATCA_NONCE_NUMIN_SIZE => AtcaStatus::AtcaInvalidSize,
+            _ => AtcaStatus::AtcaSuccess,
+        }
+    } // SoftwareBackend::set_host_nonce()
+
+    /// The emulated chip has no hardware blocks to fail, the test always passes
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        Ok(true)
+    } // SoftwareBackend::self_test()
+
+    /// The emulated chip is always awake
+    fn wake(&self) -> AtcaStatus {
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::wake()
+
+    /// The idle mode keeps the volatile state, there is nothing to do
+    fn idle(&self) -> AtcaStatus {
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::idle()
+
+    /// In the sleep mode the volatile state is lost: TempKey, the message digest buffer,
+    /// the alternate key buffer and the digest started by sha_start()
+    fn sleep(&self) -> AtcaStatus {
+        let mut state = self.state();
+        state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+        state.msg_dig_buf = [0; 2 * ATCA_KEY_SIZE];
+        state.alt_key_buf = [0; ATCA_KEY_SIZE];
+        state.sha = Sha256::new();
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::sleep()
+
+    /// The emulated chip cannot be unplugged
+    fn is_disconnected(&self) -> bool {
+        false
+    } // SoftwareBackend::is_disconnected()
+
+    /// There is no communication to re-initialize
+    fn reconnect(&self) -> AtcaStatus {
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::reconnect()
+
+    /// Nothing is cached, the state of the emulated chip is always current
+    fn refresh(&self) -> AtcaStatus {
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::refresh()
+
+    /// The lock state of the emulated chip is not cached
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::refresh_lock_state()
+
+    /// Releases the emulated chip.
+    /// Only the first call succeeds, the following ones fail with AtcaBadParam.
+    fn release(&self) -> AtcaStatus {
+        if self.released.swap(true, Ordering::SeqCst) {
+            return AtcaStatus::AtcaBadParam;
+        }
+        AtcaStatus::AtcaSuccess
+    } // SoftwareBackend::release()
+
+    //--------------------------------------------------
+    //
+    // Functions available only during testing
+    //
+    //--------------------------------------------------
+
+    /// A generic function that reads data from the configuration or data zone.
+    /// Secret slots cannot be read in clear text.
+    #[cfg(test)]
+    fn read_zone(
+        &self,
+        zone: u8,
+        slot: u16,
+        block: u8,
+        offset: u8,
+        data: &mut Vec<u8>,
+        len: u8,
+    ) -> AtcaStatus {
+        let start = (block as usize * ATCA_BLOCK_SIZE) + (offset as usize * ATCA_WORD_SIZE);
+        let end = start + len as usize;
+        let result = match zone {
+            ATCA_ZONE_CONFIG => self.config_zone.get(start..end).map(|val| val.to_vec()),
+            ATCA_ZONE_DATA if slot < ATCA_ATECC_SLOTS_COUNT as u16 => {
+                if self.slots[slot as usize].config.is_secret {
+                    return AtcaStatus::AtcaExecutionError;
+                }
+                self.state().data_zone[slot as usize]
+                    .get(start..end)
+                    .map(|val| val.to_vec())
+            }
+            _ => None,
+        };
+        match result {
+            Some(val) => {
+                *data = val;
+                AtcaStatus::AtcaSuccess
+            }
+            None => AtcaStatus::AtcaBadParam,
+        }
+    } // SoftwareBackend::read_zone()
+
+    /// Compare internal config zone contents vs. config_data.
+    /// Diagnostic function.
+    #[cfg(test)]
+    fn cmp_config_zone(&self, config_data: &mut [u8]) -> Result<bool, AtcaStatus> {
+        if config_data.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        Ok(config_data[..] == self.config_zone[..])
+    } // SoftwareBackend::cmp_config_zone()
+
+    /// A function that takes an access key for securely reading or writing data
+    /// that is located in a specific slot of the chip.
+    /// Data is taken from the SoftwareBackend structure
+    fn get_access_key(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        if let Err(err) = self.access_key_setup_parameters_check(slot_id) {
+            return err;
+        };
+
+        match self
+            .access_keys
+            .lock()
+            .expect("Could not lock 'access_keys' mutex")
+            .get(&slot_id)
+        {
+            None => AtcaStatus::AtcaInvalidId,
+            Some(access_key) => {
+                *key = access_key.to_vec();
+                AtcaStatus::AtcaSuccess
+            }
+        }
+    } // SoftwareBackend::get_access_key()
+
+    // ---------------------------------------------------------------
+    // Private functions
+    // ---------------------------------------------------------------
+
+    /// A helper function giving access to the volatile state of the chip
+    fn state(&self) -> MutexGuard<'_, ChipState> {
+        self.state.lock().expect("Could not lock 'state' mutex")
+    } // SoftwareBackend::state()
+
+    /// A helper function that returns the first 32 bytes of a slot,
+    /// a key used by the chip for MAC, HMAC and encrypted reads and writes
+    fn slot_key(&self, slot_id: u8) -> Zeroizing<[u8; ATCA_KEY_SIZE]> {
+        let mut key = Zeroizing::new([0; ATCA_KEY_SIZE]);
+        key.copy_from_slice(&self.state().data_zone[slot_id as usize][..ATCA_KEY_SIZE]);
+        key
+    } // SoftwareBackend::slot_key()
+
+    /// A helper function that checks the slot used by MAC or CheckMac
+    /// and returns the challenge (or TempKey) hashed after its key
+    fn mac_challenge(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+    ) -> Result<Zeroizing<[u8; ATCA_KEY_SIZE]>, AtcaStatus> {
+        if self.check_that_configuration_is_not_locked(false) {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        SlotId::new(slot_id)?;
+        if self.slots[slot_id as usize].config.ecc_key_attr.is_private {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        let mut mac_challenge = Zeroizing::new([0; ATCA_KEY_SIZE]);
+        match challenge {
+            None => mac_challenge.copy_from_slice(&self.state().temp_key[..ATCA_KEY_SIZE]),
+            Some(challenge) if challenge.len() == ATCA_KEY_SIZE => {
+                mac_challenge.copy_from_slice(challenge)
+            }
+            Some(_) => return Err(AtcaStatus::AtcaInvalidSize),
+        }
+        Ok(mac_challenge)
+    } // SoftwareBackend::mac_challenge()
+
+    /// A helper function that checks the access key registered by add_access_key()
+    /// against the key stored in the given slot, as the chip does when
+    /// an encrypted read or write is requested
+    fn check_access_key(&self, key_slot: u8) -> Result<(), AtcaStatus> {
+        let mut key = Zeroizing::new(Vec::with_capacity(ATCA_KEY_SIZE));
+        let result = self.get_access_key(key_slot, &mut key);
+        if AtcaStatus::AtcaSuccess != result {
+            return Err(result);
+        }
+        if !constant_time_eq(&key, &self.slot_key(key_slot)[..]) {
+            return Err(AtcaStatus::AtcaCheckMacVerifyFailed);
+        }
+        Ok(())
+    } // SoftwareBackend::check_access_key()
+
+    /// A helper function that stores a P256 private key in a slot
+    /// (or in TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT)
+    fn write_private_key(&self, slot_id: u8, private_key: &[u8]) {
+        let mut state = self.state();
+        match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => {
+                state.temp_key = [0; 2 * ATCA_KEY_SIZE];
+                state.temp_key[..ATCA_ATECC_PRIV_KEY_SIZE].copy_from_slice(private_key);
+            }
+            _ => {
+                let slot = &mut state.data_zone[slot_id as usize];
+                slot[..ECC_KEY_PAD_SIZE].fill(0);
+                slot[ECC_KEY_PAD_SIZE..(ECC_KEY_PAD_SIZE + ATCA_ATECC_PRIV_KEY_SIZE)]
+                    .copy_from_slice(private_key);
+            }
+        }
+    } // SoftwareBackend::write_private_key()
+
+    /// A helper function that loads a P256 private key from a slot
+    /// (or from TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT).
+    /// An empty slot holds an invalid key, which the chip refuses to use.
+    fn read_private_key(&self, slot_id: u8) -> Result<SecretKey, AtcaStatus> {
+        let state = self.state();
+        let key = match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => &state.temp_key[..ATCA_ATECC_PRIV_KEY_SIZE],
+            _ => {
+                &state.data_zone[slot_id as usize]
+                    [ECC_KEY_PAD_SIZE..(ECC_KEY_PAD_SIZE + ATCA_ATECC_PRIV_KEY_SIZE)]
+            }
+        };
+        SecretKey::from_slice(key).map_err(|_| AtcaStatus::AtcaStatusEcc)
+    } // SoftwareBackend::read_private_key()
+
+    /// A helper function that stores a P256 public key in a slot,
+    /// each coordinate preceded by 4 zero bytes
+    fn write_public_key(&self, slot_id: u8, public_key: &[u8]) {
+        const COORD_SIZE: usize = ATCA_ATECC_PUB_KEY_SIZE / 2;
+        let mut state = self.state();
+        let slot = &mut state.data_zone[slot_id as usize];
+        slot[..(2 * (ECC_KEY_PAD_SIZE + COORD_SIZE))].fill(0);
+        slot[ECC_KEY_PAD_SIZE..(ECC_KEY_PAD_SIZE + COORD_SIZE)]
+            .copy_from_slice(&public_key[..COORD_SIZE]);
+        slot[((2 * ECC_KEY_PAD_SIZE) + COORD_SIZE)..(2 * (ECC_KEY_PAD_SIZE + COORD_SIZE))]
+            .copy_from_slice(&public_key[COORD_SIZE..]);
+    } // SoftwareBackend::write_public_key()
+
+    /// A helper function that loads a P256 public key stored in a slot
+    fn read_public_key(&self, slot_id: u8) -> Vec<u8> {
+        const COORD_SIZE: usize = ATCA_ATECC_PUB_KEY_SIZE / 2;
+        let state = self.state();
+        let slot = &state.data_zone[slot_id as usize];
+        let mut public_key: Vec<u8> =
+            slot[ECC_KEY_PAD_SIZE..(ECC_KEY_PAD_SIZE + COORD_SIZE)].to_vec();
+        public_key.extend_from_slice(
+            &slot[((2 * ECC_KEY_PAD_SIZE) + COORD_SIZE)..(2 * (ECC_KEY_PAD_SIZE + COORD_SIZE))],
+        );
+        public_key
+    } // SoftwareBackend::read_public_key()
+
+    /// A helper function that signs a digest with the private key stored in a slot
+    fn sign_prehash(&self, slot_id: u8, digest: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let signing_key = SigningKey::from(self.read_private_key(slot_id)?);
+        let signature: Signature = signing_key
+            .sign_prehash(digest)
+            .map_err(|_| AtcaStatus::AtcaStatusEcc)?;
+        Ok(signature.to_bytes().to_vec())
+    } // SoftwareBackend::sign_prehash()
+
+    /// A helper function that loads a digest into the given buffer, as the Nonce command
+    /// does before Sign or Verify, and returns what the command reads back from it
+    fn load_digest(
+        &self,
+        buffer: DigestBuffer,
+        hash: &[u8],
+    ) -> Result<[u8; ATCA_SHA2_256_DIGEST_SIZE], AtcaStatus> {
+        if hash.len() != ATCA_SHA2_256_DIGEST_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let target = match buffer {
+            DigestBuffer::MsgDigBuf => NonceTarget::MsgDigBuf,
+            DigestBuffer::TempKey => NonceTarget::TempKey,
+        };
+        match self.nonce(target, hash) {
+            AtcaStatus::AtcaSuccess => (),
+            err => return Err(err),
+        }
+        let state = self.state();
+        let loaded = match buffer {
+            DigestBuffer::MsgDigBuf => &state.msg_dig_buf,
+            DigestBuffer::TempKey => &state.temp_key,
+        };
+        let mut digest = [0; ATCA_SHA2_256_DIGEST_SIZE];
+        digest.copy_from_slice(&loaded[..ATCA_SHA2_256_DIGEST_SIZE]);
+        Ok(digest)
+    } // SoftwareBackend::load_digest()
+
+    /// Function that reads a key of the 'Aes' type from the indicated slot
+    fn read_aes_key_from_slot(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        let slot_data = self.slots[slot_id as usize].config;
+        if KeyType::Aes != slot_data.key_type {
+            return AtcaStatus::AtcaBadParam;
+        }
+        if slot_data.is_secret && !slot_data.read_key.encrypt_read {
+            // The chip does not return secret keys in clear text
+            return AtcaStatus::AtcaExecutionError;
+        }
+
+        match self.read_slot_data(slot_id, 0, ATCA_AES_KEY_SIZE) {
+            Ok(val) => {
+                *key = val;
+                AtcaStatus::AtcaSuccess
+            }
+            Err(err) => err,
+        }
+    } // SoftwareBackend::read_aes_key_from_slot()
+
+    /// Function that reads a key of the 'ShaOrText' type from the indicated slot
+    fn read_sha_or_text_key_from_slot(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        let slot_data = self.slots[slot_id as usize].config;
+        if KeyType::ShaOrText != slot_data.key_type {
+            return AtcaStatus::AtcaBadParam;
+        }
+        if key.len() > get_slot_capacity(slot_id).bytes as usize {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+
+        match self.read_slot_data(slot_id, 0, key.len()) {
+            Ok(val) => {
+                *key = val;
+                AtcaStatus::AtcaSuccess
+            }
+            Err(err) => err,
+        }
+    } // SoftwareBackend::read_sha_or_text_key_from_slot()
+
+    /// Function that writes a key of the 'ShaOrText' type to the indicated slot.
+    /// The key is padded with zeros to the write granularity of the slot
+    /// (4 bytes for clear text writes, 32 bytes for secret or encrypted slots),
+    /// but never beyond the slot capacity.
+    fn write_sha_or_text_key_to_slot(&self, slot_id: u8, key: &[u8]) -> AtcaStatus {
+        let slot_data = self.slots[slot_id as usize].config;
+        if KeyType::ShaOrText != slot_data.key_type {
+            return AtcaStatus::AtcaBadParam;
+        }
+        let granularity = match self.get_slot_write_granularity(slot_id) {
+            Ok(val) => val,
+            Err(err) => return err,
+        };
+
+        let capacity = get_slot_capacity(slot_id).bytes as usize;
+        let padded_len = key.len().div_ceil(granularity) * granularity;
+        let mut data: Vec<u8> = key.to_vec();
+        data.resize(std::cmp::min(padded_len, capacity), 0);
+
+        self.write_slot_data(slot_id, 0, &data)
+    } // SoftwareBackend::write_sha_or_text_key_to_slot()
+
+    /// A helper function for the gen_key() and import_key() methods,
+    /// pre-checking combinations of input parameters
+    fn encryption_key_setup_parameters_check(
+        &self,
+        key_type: KeyType,
+        slot_id: u8,
+    ) -> Result<SlotId, AtcaStatus> {
+        let key_slot = SlotId::try_from(slot_id)?;
+        // TempKey can hold an AES key only
+        let slot_key_type = match key_slot {
+            SlotId::Slot(slot) => self.slots[slot as usize].config.key_type,
+            SlotId::TempKey => KeyType::Aes,
+        };
+        if (key_type != slot_key_type)
+            || ((key_type == KeyType::Aes) && !self.chip_options.aes_enabled)
+        {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        Ok(key_slot)
+    } // SoftwareBackend::encryption_key_setup_parameters_check()
+
+    /// A helper function for the add_access_key() and get_access_key()
+    /// methods, pre-checking combinations of input parameters
+    fn access_key_setup_parameters_check(&self, slot_id: u8) -> Result<(), AtcaStatus> {
+        SlotId::try_from(slot_id)?;
+        Ok(())
+    } // SoftwareBackend::access_key_setup_parameters_check()
+
+    /// A helper function that returns socket index containing encryption key
+    /// required for operation of encrypted write to the given socket
+    /// or value 'None' when such an operation cannot be performed for the given socket
+    fn get_write_key_idx(&self, slot_id: u8) -> Option<u8> {
+        if let SlotId::Slot(slot) = SlotId::try_from(slot_id).ok()? {
+            let slot_data = self.slots[slot as usize].config;
+            if slot_data.write_config == WriteConfig::Encrypt {
+                Some(slot_data.write_key)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    } // SoftwareBackend::get_write_key_idx()
+
+    /// A helper function that returns socket index containing encryption key
+    /// required for operation of encrypted reading from the given socket
+    /// or value 'None' when such an operation cannot be performed for the given socket
+    fn get_read_key_idx(&self, slot_id: u8) -> Option<u8> {
+        if let SlotId::Slot(slot) = SlotId::try_from(slot_id).ok()? {
+            let slot_data = self.slots[slot as usize].config;
+            if slot_data.read_key.encrypt_read
+                && slot_data.is_secret
+                && !slot_data.ecc_key_attr.is_private
+            {
+                Some(slot_data.read_key.slot_number)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    } // SoftwareBackend::get_read_key_idx()
+
+    /// A helper function that returns the smallest amount of data (in bytes)
+    /// that can be written to the given slot with a single Write command
+    fn get_slot_write_granularity(&self, slot_id: u8) -> Result<usize, AtcaStatus> {
+        let slot_data = self.slots[slot_id as usize].config;
+        match slot_data.write_config {
+            WriteConfig::Always if !slot_data.is_secret => Ok(ATCA_WORD_SIZE),
+            WriteConfig::Always | WriteConfig::Encrypt => Ok(ATCA_BLOCK_SIZE),
+            _ => Err(AtcaStatus::AtcaBadParam),
+        }
+    } // SoftwareBackend::get_slot_write_granularity()
+
+    /// A helper function that checks locking of configuration and data zones
+    #[inline]
+    fn check_that_configuration_is_not_locked(&self, both: bool) -> bool {
+        (!self.data_zone_locked && both) || !self.config_zone_locked
+    } // SoftwareBackend::check_that_configuration_is_not_locked()
+}
+
+// ---------------------------------------------------------------
+// Free Auxiliary Functions
+// ---------------------------------------------------------------
+
+/// A helper function that returns number of blocks and bytes of data
+/// available for a given socket
+fn get_slot_capacity(slot_id: u8) -> AtcaSlotCapacity {
+    let mut slot_capacity: AtcaSlotCapacity = Default::default();
+    match slot_id {
+        0x00..=0x07 => {
+            slot_capacity.blocks = 2;
+            slot_capacity.last_block_bytes = 4;
+            slot_capacity.bytes = 36;
+        }
+        0x08 => {
+            slot_capacity.blocks = 13;
+            slot_capacity.last_block_bytes = 32;
+            slot_capacity.bytes = 416;
+        }
+        0x09..=0x0F => {
+            slot_capacity.blocks = 3;
+            slot_capacity.last_block_bytes = 8;
+            slot_capacity.bytes = 72;
+        }
+        _ => {}
+    }
+    slot_capacity
+} // get_slot_capacity()
+
+/// Random bytes generated by the host, standing in for the TRNG of the chip
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![0; len];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes
+} // random_bytes()
+
+/// HMAC-SHA256 of the concatenation of the given parts of a message
+fn hmac_sha256(key: &[u8], message: &[&[u8]]) -> [u8; ATCA_SHA2_256_DIGEST_SIZE] {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for part in message {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+} // hmac_sha256()
+
+/// A P256 public key in the format used by the chip: X || Y
+fn raw_public_key(public_key: &PublicKey) -> Vec<u8> {
+    public_key.to_encoded_point(false).as_bytes()[1..].to_vec()
+} // raw_public_key()
+
+/// A P256 public key given in the format used by the chip: X || Y.
+/// A point that is not on the curve is refused as by the chip.
+fn public_key_from_raw(public_key: &[u8]) -> Result<PublicKey, AtcaStatus> {
+    let mut sec1: Vec<u8> = vec![0x04];
+    sec1.extend_from_slice(public_key);
+    PublicKey::from_sec1_bytes(&sec1).map_err(|_| AtcaStatus::AtcaStatusEcc)
+} // public_key_from_raw()
+
+/// ECDSA verification of a signature (R || S) of a digest
+fn verify_digest(public_key: &[u8], digest: &[u8], signature: &[u8]) -> Result<bool, AtcaStatus> {
+    let verifying_key = VerifyingKey::from(public_key_from_raw(public_key)?);
+    match Signature::from_slice(signature) {
+        Ok(signature) => Ok(verifying_key.verify_prehash(digest, &signature).is_ok()),
+        Err(_) => Ok(false),
+    }
+} // verify_digest()
+
+/// Function that encrypts or decrypts data transmitted with the IO protection key.
+/// Each 32-byte block of data is XOR-ed with SHA256(io_key || out_nonce[16 bytes]),
+/// where consecutive blocks use consecutive 16-byte parts of the output nonce.
+fn io_protection_xor(io_key: &[u8], out_nonce: &[u8], data: &mut [u8]) {
+    const OUT_NONCE_BLOCK_SIZE: usize = 16;
+    for (idx, block) in data.chunks_mut(ATCA_BLOCK_SIZE).enumerate() {
+        let nonce_start = idx * OUT_NONCE_BLOCK_SIZE;
+        let mut sha = Sha256::new();
+        sha.update(io_key);
+        sha.update(&out_nonce[nonce_start..(nonce_start + OUT_NONCE_BLOCK_SIZE)]);
+        for (byte, key_byte) in block.iter_mut().zip(sha.finalize().iter()) {
+            *byte ^= key_byte;
+        }
+    }
+} // io_protection_xor()
@@ -0,0 +1,1504 @@
+use alloc::vec::Vec;
+use std::convert::TryFrom;
+use std::mem;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(test)]
+use cryptoauthlib_sys::atca_aes_cbc_ctx_t;
+#[cfg(test)]
+use cryptoauthlib_sys::atca_aes_ctr_ctx_t;
+
+use super::super::sync::{Mutex, MutexGuard};
+use super::super::temp_key::{kdf_temp_key_size, sign_digest_buffer, verify_digest_buffer};
+use super::super::CONFIG_READ_ONLY_BYTES;
+use super::super::{DigestBuffer, TempKeySource};
+use super::{get_slot_capacity, SoftwareBackend, SOFTWARE_BACKEND_CONFIG_ZONE, ZONE_UNLOCKED};
+use super::{
+    AeadAlgorithm, AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaDeviceType, AtcaSha256Ctx, AtcaSlot,
+    AtcaStatus, AteccDeviceTrait, CipherAlgorithm, EcdhOutput, GenDigZone, InfoCmdType,
+    IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget, KeyType, KeyWrapAlgorithm,
+    NonceTarget, OutputProtectionState, SignMode, SignatureFormat, SlotId, TempKeyState,
+    TlsFinishedLabel, VerifyMode,
+};
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_ATECC_CONFIG_BUFFER_SIZE, ATCA_ATECC_SLOTS_COUNT,
+    ATCA_ATECC_TEMPKEY_KEYID, ATCA_COUNTER_MAX_VALUE, ATCA_KEY_SIZE, ATCA_LIMITED_USE_COUNTER_ID,
+    ATCA_SERIAL_NUM_SIZE, ATCA_SIGN_MODE_SOURCE_MSGDIGBUF,
+};
+
+/// Number of monotonic counters of ATECC608A
+const COUNTERS_COUNT: usize = 2;
+
+const IDX_LOCK_VALUE: usize = 86;
+const IDX_LOCK_CONFIG: usize = 87;
+const IDX_SLOT_LOCKED: usize = 88;
+/// Value of the LockValue and LockConfig bytes of a locked zone
+const ZONE_LOCKED: u8 = 0x00;
+
+/// An ATECC608A simulator for testing provisioning and slot policies without a chip.
+/// It runs the commands of SoftwareBackend and additionally models
+/// the life cycle of a chip and the state that the naive backend does not keep:
+/// - a factory new chip has both zones unlocked, the configuration zone
+///   is written with write_config_zone() and locked with lock_config_zone(),
+///   then the slots are filled with provision_slot() or gen_key() and the data zone
+///   is locked with lock_data_zone(); single slots are locked with lock_slot(),
+/// - TempKey is valid only after it has been loaded (Nonce, GenKey, ECDH, KDF, ...)
+///   and is invalidated by the Random command and by release(), commands using
+///   an invalid TempKey fail with AtcaExecutionError,
+/// - each use of a key with the LimitedUse bit set in its SlotConfig increments
+///   the monotonic counter 0, once it reaches ATCA_COUNTER_MAX_VALUE the key
+///   can no longer be used and the commands fail with AtcaExecutionError.
+pub struct AteccSimulator {
+    chip: RwLock<SoftwareBackend>,
+    counters: Mutex<[u32; COUNTERS_COUNT]>,
+    temp_key: Mutex<TempKeyState>,
+}
+
+impl Default for AteccSimulator {
+    fn default() -> AteccSimulator {
+        AteccSimulator::new()
+    }
+}
+
+impl AteccSimulator {
+    /// Creates a factory new simulated chip: the configuration zone of
+    /// SOFTWARE_BACKEND_CONFIG_ZONE with both zones unlocked and all the slots empty
+    pub fn new() -> AteccSimulator {
+        let mut config_zone = SOFTWARE_BACKEND_CONFIG_ZONE;
+        config_zone[IDX_LOCK_VALUE] = ZONE_UNLOCKED;
+        config_zone[IDX_LOCK_CONFIG] = ZONE_UNLOCKED;
+        AteccSimulator::from_config_zone(&config_zone).expect("Invalid default configuration zone")
+    } // AteccSimulator::new()
+
+    /// Creates a simulated chip with the given 128-byte configuration zone,
+    /// with all the slots empty and both counters equal to zero.
+    /// The lock state of both zones is taken from the LockValue and LockConfig bytes.
+    pub fn from_config_zone(config_zone: &[u8]) -> Result<AteccSimulator, AtcaStatus> {
+        Ok(AteccSimulator {
+            chip: RwLock::new(SoftwareBackend::from_config_zone(config_zone)?),
+            counters: Mutex::new([0; COUNTERS_COUNT]),
+            temp_key: Mutex::new(TempKeyState::default()),
+        })
+    } // AteccSimulator::from_config_zone()
+
+    /// Writes the configuration zone of a chip whose configuration zone is not locked yet.
+    /// As with the Write command, the serial number, the revision, the I2C settings,
+    /// UserExtra, UserExtraAdd and the lock bytes keep their values
+    pub fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        if config_data.len() != ATCA_ATECC_CONFIG_BUFFER_SIZE {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        let mut chip = self.chip_mut();
+        if chip.config_zone_locked {
+            return AtcaStatus::AtcaConfigZoneLocked;
+        }
+
+        let mut config_zone = chip.config_zone;
+        let mut start: usize = 0;
+        for (read_only_start, read_only_end) in CONFIG_READ_ONLY_BYTES.iter() {
+            config_zone[start..*read_only_start]
+                .copy_from_slice(&config_data[start..*read_only_start]);
+            start = *read_only_end;
+        }
+        config_zone[start..].copy_from_slice(&config_data[start..]);
+
+        Self::reconfigure(&mut chip, &config_zone)
+    } // AteccSimulator::write_config_zone()
+
+    /// Locks the configuration zone, from now on its contents are fixed
+    /// and the slot access rules it describes are enforced
+    pub fn lock_config_zone(&self) -> AtcaStatus {
+        let mut chip = self.chip_mut();
+        if chip.config_zone_locked {
+            return AtcaStatus::AtcaConfigZoneLocked;
+        }
+        let mut config_zone = chip.config_zone;
+        config_zone[IDX_LOCK_CONFIG] = ZONE_LOCKED;
+        Self::reconfigure(&mut chip, &config_zone)
+    } // AteccSimulator::lock_config_zone()
+
+    /// Locks the data zone, which is possible only after the configuration zone has been locked.
+    /// From now on the slots are written and read as their configuration allows
+    pub fn lock_data_zone(&self) -> AtcaStatus {
+        let mut chip = self.chip_mut();
+        if !chip.config_zone_locked {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if chip.data_zone_locked {
+            return AtcaStatus::AtcaDataZoneLocked;
+        }
+        let mut config_zone = chip.config_zone;
+        config_zone[IDX_LOCK_VALUE] = ZONE_LOCKED;
+        Self::reconfigure(&mut chip, &config_zone)
+    } // AteccSimulator::lock_data_zone()
+
+    /// Locks a single slot, so that its contents can no longer be changed.
+    /// The data zone must be locked and the slot must be marked as lockable in its KeyConfig
+    pub fn lock_slot(&self, slot_id: u8) -> AtcaStatus {
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let mut chip = self.chip_mut();
+        if !chip.data_zone_locked {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        let slot = &chip.slots[slot_id as usize];
+        if !slot.config.lockable || slot.is_locked {
+            return AtcaStatus::AtcaExecutionError;
+        }
+        let mut config_zone = chip.config_zone;
+        config_zone[IDX_SLOT_LOCKED + (slot_id / 8) as usize] &= !(1 << (slot_id % 8));
+        Self::reconfigure(&mut chip, &config_zone)
+    } // AteccSimulator::lock_slot()
+
+    /// Writes data in clear text to a slot before the data zone is locked,
+    /// as done during provisioning. It is possible only after the configuration zone
+    /// has been locked. Private keys cannot be written this way, they are created
+    /// with gen_key() or imported after the data zone is locked
+    pub fn provision_slot(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        if let Err(err) = SlotId::new(slot_id) {
+            return err;
+        }
+        let chip = self.chip();
+        if !chip.config_zone_locked {
+            return AtcaStatus::AtcaNotLocked;
+        }
+        if chip.data_zone_locked {
+            return AtcaStatus::AtcaDataZoneLocked;
+        }
+        if chip.slots[slot_id as usize].config.ecc_key_attr.is_private {
+            return AtcaStatus::AtcaExecutionError;
+        }
+        if (offset + data.len()) > get_slot_capacity(slot_id).bytes as usize {
+            return AtcaStatus::AtcaInvalidSize;
+        }
+        chip.state().data_zone[slot_id as usize][offset..(offset + data.len())]
+            .copy_from_slice(data);
+        AtcaStatus::AtcaSuccess
+    } // AteccSimulator::provision_slot()
+
+    /// Sets the initial value of a monotonic counter, as done by writing
+    /// the configuration zone before it is locked
+    pub fn set_counter(&self, counter_id: u8, value: u32) -> AtcaStatus {
+        if (counter_id as usize >= COUNTERS_COUNT) || (value > ATCA_COUNTER_MAX_VALUE) {
+            return AtcaStatus::AtcaBadParam;
+        }
+        if self.chip().config_zone_locked {
+            return AtcaStatus::AtcaConfigZoneLocked;
+        }
+        self.counters()[counter_id as usize] = value;
+        AtcaStatus::AtcaSuccess
+    } // AteccSimulator::set_counter()
+
+    /// Returns the value of a monotonic counter
+    pub fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        if counter_id as usize >= COUNTERS_COUNT {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        Ok(self.counters()[counter_id as usize])
+    } // AteccSimulator::counter_read()
+
+    /// Increments a monotonic counter and returns its new value.
+    /// A counter that has reached ATCA_COUNTER_MAX_VALUE cannot be incremented
+    pub fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        if counter_id as usize >= COUNTERS_COUNT {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+        if !self.chip().config_zone_locked {
+            return Err(AtcaStatus::AtcaNotLocked);
+        }
+        let mut counters = self.counters();
+        let counter = &mut counters[counter_id as usize];
+        if *counter >= ATCA_COUNTER_MAX_VALUE {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        *counter += 1;
+        Ok(*counter)
+    } // AteccSimulator::counter_increment()
+
+    /// Tells if TempKey holds a valid value that commands can use
+    pub fn is_temp_key_valid(&self) -> bool {
+        self.temp_key().valid
+    } // AteccSimulator::is_temp_key_valid()
+}
+
+impl AteccDeviceTrait for AteccSimulator {
+    /// Request ATECC to generate a vector of random bytes
+    /// Trait implementation
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.random(rand_out)
+    } // AteccSimulator::random()
+
+    /// Request ATECC to compute a message hash (SHA256)
+    /// Trait implementation
+    fn sha(&self, message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
+        self.chip().sha(message, digest)
+    } // AteccSimulator::sha()
+
+    /// Starts a SHA256 digest calculated by ATECC in several steps
+    /// Trait implementation
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        self.chip().sha_start()
+    } // AteccSimulator::sha_start()
+
+    /// Feeds the next part of a message into a SHA256 digest calculation
+    /// Trait implementation
+    fn sha_update(&self, ctx: AtcaSha256Ctx, data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        self.chip().sha_update(ctx, data)
+    } // AteccSimulator::sha_update()
+
+    /// Completes a SHA256 digest calculation and returns the digest
+    /// Trait implementation
+    fn sha_finish(&self, ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().sha_finish(ctx)
+    } // AteccSimulator::sha_finish()
+
+    /// Request ATECC to compute HMAC-SHA256 of a message with the key stored in a slot
+    /// Trait implementation
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.sha_hmac(message, slot_id)
+    } // AteccSimulator::sha_hmac()
+
+    /// Execute a Nonce command in pass-through mode to load one of the
+    /// device's internal buffers with a fixed value.
+    /// For the ATECC608A, available targets are TempKey (32 or 64 bytes), Message
+    /// Digest Buffer (32 or 64 bytes), or the Alternate Key Buffer (32 bytes). For
+    /// all other devices, only TempKey (32 bytes) is available.
+    /// Trait implementation
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        self.nonce(target, data)
+    } // AteccSimulator::nonce()
+
+    /// Execute a Nonce command to generate a random nonce combining a host
+    /// nonce and a device random number.
+    /// Trait implementation
+    fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
+        self.nonce_rand(host_nonce, rand_out)
+    } // AteccSimulator::nonce_rand()
+
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    /// Trait implementation
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        self.gen_dig(zone, slot_id, other_data)
+    } // AteccSimulator::gen_dig()
+
+    /// What the simulator knows about TempKey
+    /// Trait implementation
+    fn tempkey_state(&self) -> TempKeyState {
+        *self.temp_key()
+    } // AteccSimulator::tempkey_state()
+
+    /// Execute a MAC command over the key stored in a slot and a challenge or TempKey
+    /// Trait implementation
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        self.mac(slot_id, challenge)
+    } // AteccSimulator::mac()
+
+    /// Execute a CheckMac command to verify a MAC calculated by the host
+    /// Trait implementation
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.check_mac(slot_id, challenge, response, other_data)
+    } // AteccSimulator::check_mac()
+
+    /// Request ATECC to read the value of one of the two monotonic counters
+    /// Trait implementation
+    fn counter_read(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.counter_read(counter_id)
+    } // AteccSimulator::counter_read()
+
+    /// Request ATECC to increment one of the two monotonic counters
+    /// Trait implementation
+    fn counter_increment(&self, counter_id: u8) -> Result<u32, AtcaStatus> {
+        self.counter_increment(counter_id)
+    } // AteccSimulator::counter_increment()
+
+    /// Request ATECC to generate a cryptographic key
+    /// Trait implementation
+    fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        self.gen_key(key_type, slot_id)
+    } // AteccSimulator::gen_key()
+
+    /// Request ATECC to import a cryptographic key
+    /// Trait implementation
+    fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.import_key(key_type, key_data, slot_id)
+    } // AteccSimulator::import_key()
+
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1)
+    /// Trait implementation
+    fn import_private_key_der(&self, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        self.chip().import_private_key_der(key_data, slot_id)
+    } // AteccSimulator::import_private_key_der()
+
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    /// Trait implementation
+    fn import_private_key_pem(&self, key_data: &str, slot_id: u8) -> AtcaStatus {
+        self.chip().import_private_key_pem(key_data, slot_id)
+    } // AteccSimulator::import_private_key_pem()
+
+    /// Request ATECC to export a cryptographic key
+    /// Trait implementation
+    fn export_key(&self, key_type: KeyType, key_data: &mut Vec<u8>, slot_id: u8) -> AtcaStatus {
+        self.chip().export_key(key_type, key_data, slot_id)
+    } // AteccSimulator::export_key()
+
+    /// Request ATECC to write data to a slot, starting at the given byte offset
+    /// Trait implementation
+    fn write_slot_data(&self, slot_id: u8, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.chip().write_slot_data(slot_id, offset, data)
+    } // AteccSimulator::write_slot_data()
+
+    /// Request ATECC to read data from a slot, starting at the given byte offset
+    /// Trait implementation
+    fn read_slot_data(
+        &self,
+        slot_id: u8,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().read_slot_data(slot_id, offset, len)
+    } // AteccSimulator::read_slot_data()
+
+    /// Request ATECC to write the OTP zone, starting at the given byte offset
+    /// Trait implementation
+    fn write_otp_zone(&self, offset: usize, data: &[u8]) -> AtcaStatus {
+        self.chip().write_otp_zone(offset, data)
+    } // AteccSimulator::write_otp_zone()
+
+    /// Request ATECC to read the OTP zone, starting at the given byte offset
+    /// Trait implementation
+    fn read_otp_zone(&self, offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().read_otp_zone(offset, len)
+    } // AteccSimulator::read_otp_zone()
+
+    /// Depending on the socket configuration, this function calculates
+    /// public key based on an existing private key in the socket
+    /// or exports the public key directly
+    /// Trait implementation
+    fn get_public_key(&self, slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus {
+        self.chip().get_public_key(slot_id, public_key)
+    } // AteccSimulator::get_public_key()
+
+    /// Request ATECC to generate an ECDSA signature
+    /// Trait implementation
+    fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
+        self.sign_hash(mode, slot_id, signature)
+    } // AteccSimulator::sign_hash()
+
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    /// with the private key stored in the given slot
+    /// Trait implementation
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.sign_message(slot_id, message)
+    } // AteccSimulator::sign_message()
+
+    /// Request ATECC to execute a Sign command in the given mode
+    /// Trait implementation
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.sign_base(mode, slot_id)
+    } // AteccSimulator::sign_base()
+
+    /// Request ATECC to verify ECDSA signature
+    /// Trait implementation
+    fn verify_hash(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        let buffer = verify_digest_buffer(&mode);
+        let result = self.chip().verify_hash(mode, hash, signature);
+        self.temp_key_lost_to_digest(result.is_ok(), buffer, "verify_hash");
+        result
+    } // AteccSimulator::verify_hash()
+
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    /// Trait implementation
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.sign_hash_formatted(mode, slot_id, format)
+    } // AteccSimulator::sign_hash_formatted()
+
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    /// Trait implementation
+    fn verify_hash_formatted(
+        &self,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        let buffer = verify_digest_buffer(&mode);
+        let result = self
+            .chip()
+            .verify_hash_formatted(mode, hash, signature, format);
+        self.temp_key_lost_to_digest(result.is_ok(), buffer, "verify_hash_formatted");
+        result
+    } // AteccSimulator::verify_hash_formatted()
+
+    /// Request ATECC to perform ECDH key agreement, honoring the output protection settings
+    /// Trait implementation
+    fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        self.ecdh(slot_id, public_key, session)
+    } // AteccSimulator::ecdh()
+
+    /// Request ATECC608x to derive a key with the KDF command
+    /// Trait implementation
+    fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.kdf(algorithm, source, target, message)
+    } // AteccSimulator::kdf()
+
+    /// Request ATECC608x to open an IO protection session with the given IO protection key
+    /// Trait implementation
+    fn open_io_protection_session(&self, io_key: &[u8]) -> Result<IoProtectionSession, AtcaStatus> {
+        self.chip().open_io_protection_session(io_key)
+    } // AteccSimulator::open_io_protection_session()
+
+    /// Request ATECC to perform ECDH key agreement with the premaster secret
+    /// transmitted encrypted with the IO protection key
+    /// Trait implementation
+    fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.ecdh_io_protected(session, slot_id, public_key)
+    } // AteccSimulator::ecdh_io_protected()
+
+    /// Request ATECC to verify ECDSA signature, with the result
+    /// authenticated by a MAC calculated with the IO protection key
+    /// Trait implementation
+    fn verify_hash_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        mode: VerifyMode,
+        hash: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.chip()
+            .verify_hash_io_protected(session, mode, hash, signature)
+    } // AteccSimulator::verify_hash_io_protected()
+
+    /// Request ATECC to import an AES key to a slot protected by the IO protection key
+    /// Trait implementation
+    fn import_aes_key_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        key: &[u8],
+        slot_id: u8,
+    ) -> AtcaStatus {
+        self.chip()
+            .import_aes_key_io_protected(session, key, slot_id)
+    } // AteccSimulator::import_aes_key_io_protected()
+
+    /// Data encryption function in AES unauthenticated cipher alhorithms modes
+    /// Trait implementation
+    fn cipher_encrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        self.cipher_encrypt(algorithm, slot_id, data)
+    } // AteccSimulator::cipher_encrypt()
+
+    /// Data decryption function in AES unauthenticated cipher alhorithms modes
+    /// Trait implementation
+    fn cipher_decrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        self.cipher_decrypt(algorithm, slot_id, data)
+    } // AteccSimulator::cipher_decrypt()
+
+    /// Data encryption function in AES AEAD (authenticated encryption with associated data) modes
+    /// Trait implementation
+    fn aead_encrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.aead_encrypt(algorithm, slot_id, data)
+    } // AteccSimulator::aead_encrypt()
+
+    /// Data decryption function in AES AEAD (authenticated encryption with associated data) modes
+    /// Trait implementation
+    fn aead_decrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> Result<bool, AtcaStatus> {
+        self.aead_decrypt(algorithm, slot_id, data)
+    } // AteccSimulator::aead_decrypt()
+
+    /// Starts a streaming AES GCM encryption or decryption
+    /// Trait implementation
+    fn gcm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.gcm_init(slot_id, nonce, key)
+    } // AteccSimulator::gcm_init()
+
+    /// Feeds the next chunk of additional authenticated data into a streaming AES GCM operation
+    /// Trait implementation
+    fn gcm_update_aad(&self, ctx: AtcaAesGcmCtx, data: &[u8]) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.chip().gcm_update_aad(ctx, data)
+    } // AteccSimulator::gcm_update_aad()
+
+    /// Encrypts (in place) the next chunk of a streaming AES GCM encryption
+    /// Trait implementation
+    fn gcm_encrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.chip().gcm_encrypt_update(ctx, data)
+    } // AteccSimulator::gcm_encrypt_update()
+
+    /// Decrypts (in place) the next chunk of a streaming AES GCM decryption
+    /// Trait implementation
+    fn gcm_decrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.chip().gcm_decrypt_update(ctx, data)
+    } // AteccSimulator::gcm_decrypt_update()
+
+    /// Completes a streaming AES GCM encryption and returns the tag
+    /// Trait implementation
+    fn gcm_encrypt_finish(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().gcm_encrypt_finish(ctx, tag_length)
+    } // AteccSimulator::gcm_encrypt_finish()
+
+    /// Completes a streaming AES GCM decryption and verifies the tag
+    /// Trait implementation
+    fn gcm_decrypt_finish(&self, ctx: AtcaAesGcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        self.chip().gcm_decrypt_finish(ctx, tag)
+    } // AteccSimulator::gcm_decrypt_finish()
+
+    /// Starts a streaming AES CCM encryption or decryption
+    /// Trait implementation
+    fn ccm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.ccm_init(slot_id, nonce, key, aad_length, text_length, tag_length)
+    } // AteccSimulator::ccm_init()
+
+    /// Feeds the next chunk of additional authenticated data into a streaming AES CCM operation
+    /// Trait implementation
+    fn ccm_update_aad(&self, ctx: AtcaAesCcmCtx, data: &[u8]) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.chip().ccm_update_aad(ctx, data)
+    } // AteccSimulator::ccm_update_aad()
+
+    /// Encrypts (in place) the next chunk of a streaming AES CCM encryption
+    /// Trait implementation
+    fn ccm_encrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.chip().ccm_encrypt_update(ctx, data)
+    } // AteccSimulator::ccm_encrypt_update()
+
+    /// Decrypts (in place) the next chunk of a streaming AES CCM decryption
+    /// Trait implementation
+    fn ccm_decrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.chip().ccm_decrypt_update(ctx, data)
+    } // AteccSimulator::ccm_decrypt_update()
+
+    /// Completes a streaming AES CCM encryption and returns the tag
+    /// Trait implementation
+    fn ccm_encrypt_finish(&self, ctx: AtcaAesCcmCtx) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().ccm_encrypt_finish(ctx)
+    } // AteccSimulator::ccm_encrypt_finish()
+
+    /// Completes a streaming AES CCM decryption and verifies the tag
+    /// Trait implementation
+    fn ccm_decrypt_finish(&self, ctx: AtcaAesCcmCtx, tag: &[u8]) -> Result<bool, AtcaStatus> {
+        self.chip().ccm_decrypt_finish(ctx, tag)
+    } // AteccSimulator::ccm_decrypt_finish()
+
+    /// Derives TLS 1.2 session keys
+    /// Trait implementation
+    fn tls_derive_keys(
+        &self,
+        slot_id: u8,
+        server_public_key: &[u8],
+        client_random: &[u8],
+        server_random: &[u8],
+        key_block_length: usize,
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.tls_derive_keys(
+            slot_id,
+            server_public_key,
+            client_random,
+            server_random,
+            key_block_length,
+            session,
+        )
+    } // AteccSimulator::tls_derive_keys()
+
+    /// Calculates verify_data of a TLS 1.2 Finished message
+    /// Trait implementation
+    fn tls_verify_data(
+        &self,
+        label: TlsFinishedLabel,
+        handshake_hash: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().tls_verify_data(label, handshake_hash, session)
+    } // AteccSimulator::tls_verify_data()
+
+    /// Encrypts data for the owner of the given public key
+    /// Trait implementation
+    fn ecies_encrypt(&self, public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.ecies_encrypt(public_key, plaintext)
+    } // AteccSimulator::ecies_encrypt()
+
+    /// Decrypts data produced by ecies_encrypt() with the private key stored in the given slot
+    /// Trait implementation
+    fn ecies_decrypt(&self, slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.ecies_decrypt(slot_id, data)
+    } // AteccSimulator::ecies_decrypt()
+
+    /// Wrap a key with the AES key stored in a slot (RFC 3394 / RFC 5649)
+    /// Trait implementation
+    fn wrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.wrap_key(algorithm, slot_id, key)
+    } // AteccSimulator::wrap_key()
+
+    /// Unwrap a key with the AES key stored in a slot (RFC 3394 / RFC 5649)
+    /// Trait implementation
+    fn unwrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.unwrap_key(algorithm, slot_id, wrapped_key)
+    } // AteccSimulator::unwrap_key()
+
+    /// Request ATECC to return own device type
+    /// Trait implementation
+    fn get_device_type(&self) -> AtcaDeviceType {
+        self.chip().get_device_type()
+    } // AteccSimulator::get_device_type()
+
+    /// Request ATECC to check if its configuration is locked.
+    /// If true, a chip can be used for cryptographic operations
+    /// Trait implementation
+    fn is_configuration_locked(&self) -> bool {
+        self.chip().config_zone_locked
+    } // AteccSimulator::is_configuration_locked()
+
+    /// Request ATECC to check if its Data Zone is locked.
+    /// If true, a chip can be used for cryptographic operations
+    /// Trait implementation
+    fn is_data_zone_locked(&self) -> bool {
+        self.chip().data_zone_locked
+    } // AteccSimulator::is_data_zone_locked()
+
+    /// Returns a structure containing configuration data read from ATECC
+    /// during initialization of the AteccSimulator object.
+    /// Trait implementation
+    fn get_config(&self, atca_slots: &mut Vec<AtcaSlot>) -> AtcaStatus {
+        self.chip().get_config(atca_slots)
+    } // AteccSimulator::get_config()
+
+    /// Request ATECC to read and return own configuration zone.
+    /// Note: this function returns raw data, function get_config(..) implements a more
+    /// structured return.
+    /// Trait implementation
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        self.chip().read_config_zone(config_data)
+    } // AteccSimulator::read_config_zone()
+
+    /// Request ATECC to write its whole configuration zone
+    /// Trait implementation
+    fn write_config_zone(&self, config_data: &[u8]) -> AtcaStatus {
+        self.write_config_zone(config_data)
+    } // AteccSimulator::write_config_zone()
+
+    /// Request ATECC to lock its configuration zone
+    /// Trait implementation
+    fn lock_config_zone(&self) -> AtcaStatus {
+        self.lock_config_zone()
+    } // AteccSimulator::lock_config_zone()
+
+    /// Request ATECC to lock its data zone
+    /// Trait implementation
+    fn lock_data_zone(&self) -> AtcaStatus {
+        self.lock_data_zone()
+    } // AteccSimulator::lock_data_zone()
+
+    /// Command accesses some static or dynamic information from the ATECC chip
+    /// Trait implementation
+    fn info_cmd(&self, command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
+        self.chip().info_cmd(command)
+    } // AteccSimulator::info_cmd()
+
+    /// A function that adds an access key for securely reading or writing data
+    /// that is located in a specific slot on the ATECCx08 chip.
+    /// Data is not written to the ATECCx08 chip, but to the AteccSimulator structure.
+    /// Trait implementation
+    fn add_access_key(&self, slot_id: u8, access_key: &[u8]) -> AtcaStatus {
+        self.chip().add_access_key(slot_id, access_key)
+    } // AteccSimulator::add_access_key()
+
+    /// A function that deletes all access keys for secure read or write operations
+    /// performed by the ATECCx08 chip
+    /// Trait implementation
+    fn flush_access_keys(&self) -> AtcaStatus {
+        self.chip().flush_access_keys()
+    } // AteccSimulator::flush_access_keys()
+
+    /// Sets the host nonce of the Nonce command that precedes the next
+    /// encrypted read or write of a slot
+    /// Trait implementation
+    fn set_host_nonce(&self, num_in: Option<&[u8]>) -> AtcaStatus {
+        self.chip().set_host_nonce(num_in)
+    } // AteccSimulator::set_host_nonce()
+
+    /// Get serial number of the ATECC device
+    /// Trait implementation
+    fn get_serial_number(&self) -> [u8; ATCA_SERIAL_NUM_SIZE] {
+        self.chip().serial_number
+    } // AteccSimulator::get_serial_number()
+
+    /// Checks if the chip supports AES encryption
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn is_aes_enabled(&self) -> bool {
+        self.chip().chip_options.aes_enabled
+    } // AteccSimulator::is_aes_enabled()
+
+    /// Checks if the chip supports AES for KDF operations
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn is_kdf_aes_enabled(&self) -> bool {
+        self.chip().chip_options.kdf_aes_enabled
+    } // AteccSimulator::is_kdf_aes_enabled()
+
+    /// Checks whether transmission between chip and host is to be encrypted
+    /// (IO encryption is only possible for ATECC608x chip)
+    /// Trait implementation
+    fn is_io_protection_key_enabled(&self) -> bool {
+        self.chip().chip_options.io_key_enabled
+    } // AteccSimulator::is_io_protection_key_enabled()
+
+    ///
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn get_ecdh_output_protection_state(&self) -> OutputProtectionState {
+        self.chip().chip_options.ecdh_output_protection
+    } // AteccSimulator::get_ecdh_output_protection_state()
+
+    ///
+    /// (only relevant for the ATECC608x chip)
+    /// Trait implementation
+    fn get_kdf_output_protection_state(&self) -> OutputProtectionState {
+        self.chip().chip_options.kdf_output_protection
+    } // AteccSimulator::get_kdf_output_protection_state()
+
+    /// Perform an AES-128 encrypt operation with a key in the device
+    /// Trait implementation
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_encrypt_block(key_id, key_block, input)
+    }
+    /// Perform an AES-128 decrypt operation with a key in the device
+    /// Trait implementation
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.aes_decrypt_block(key_id, key_block, input)
+    }
+
+    /// Request ATECC608x to run the SelfTest command
+    /// Trait implementation
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        self.self_test()
+    }
+    /// Wakes the chip up
+    /// Trait implementation
+    fn wake(&self) -> AtcaStatus {
+        self.wake()
+    }
+    /// Puts the chip into the idle mode
+    /// Trait implementation
+    fn idle(&self) -> AtcaStatus {
+        self.idle()
+    }
+    /// Puts the chip into the sleep mode
+    /// Trait implementation
+    fn sleep(&self) -> AtcaStatus {
+        self.sleep()
+    }
+    /// Tells if the chip seems to be gone
+    /// Trait implementation
+    fn is_disconnected(&self) -> bool {
+        self.is_disconnected()
+    }
+    /// Re-initializes the communication with the chip
+    /// Trait implementation
+    fn reconnect(&self) -> AtcaStatus {
+        self.reconnect()
+    }
+    /// Reads again the state cached at initialization
+    /// Trait implementation
+    fn refresh(&self) -> AtcaStatus {
+        self.refresh()
+    }
+    /// Reads again the lock state of the zones
+    /// Trait implementation
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        self.refresh_lock_state()
+    }
+
+    /// ATECC device instance destructor
+    /// Trait implementation
+    fn release(&self) -> AtcaStatus {
+        self.release()
+    } // AteccSimulator::release()
+
+    //--------------------------------------------------
+    //
+    // Functions available only during testing
+    //
+    //--------------------------------------------------
+
+    /// A generic function that reads data from the chip
+    /// Trait implementation
+    #[cfg(test)]
+    fn read_zone(
+        &self,
+        zone: u8,
+        slot: u16,
+        block: u8,
+        offset: u8,
+        data: &mut Vec<u8>,
+        len: u8,
+    ) -> AtcaStatus {
+        self.chip().read_zone(zone, slot, block, offset, data, len)
+    } // AteccSimulator::read_zone()
+    /// Compare internal config zone contents vs. config_data.
+    /// Diagnostic function.
+    /// Trait implementation
+    #[cfg(test)]
+    fn cmp_config_zone(&self, config_data: &mut [u8]) -> Result<bool, AtcaStatus> {
+        self.chip().cmp_config_zone(config_data)
+    } // AteccSimulator::cmp_config_zone()
+    /// A function that takes an encryption key for securely reading or writing data
+    /// that is located in a specific slot on an ATECCx08 chip.
+    /// Data is not taken directly from the ATECCx08 chip, but from the AteccSimulator structure
+    /// Trait implementation
+    #[cfg(test)]
+    fn get_access_key(&self, slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        self.chip().get_access_key(slot_id, key)
+    } // AteccSimulator::get_access_key()
+    /// Initialize context for AES CTR operation with an existing IV
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_ctr_init(
+        &self,
+        slot_id: u8,
+        counter_size: u8,
+        iv: &[u8],
+    ) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.chip().aes_ctr_init(slot_id, counter_size, iv)
+    }
+    /// Increments AES CTR counter value
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_ctr_increment(&self, ctx: atca_aes_ctr_ctx_t) -> Result<atca_aes_ctr_ctx_t, AtcaStatus> {
+        self.chip().aes_ctr_increment(ctx)
+    }
+    /// Initialize context for AES CBC operation.
+    /// Trait implementation
+    #[cfg(test)]
+    fn aes_cbc_init(&self, slot_id: u8, iv: &[u8]) -> Result<atca_aes_cbc_ctx_t, AtcaStatus> {
+        self.chip().aes_cbc_init(slot_id, iv)
+    }
+}
+
+/// Commands whose behaviour depends on the state modelled by the simulator,
+/// all the other ones are executed by SoftwareBackend as they are
+impl AteccSimulator {
+    /// Request ATECC to generate a vector of random bytes, TempKey is invalidated
+    fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
+        let result = self.chip().random(rand_out);
+        if AtcaStatus::AtcaSuccess == result {
+            self.invalidate_temp_key("random");
+        }
+        result
+    } // AteccSimulator::random()
+
+    /// Request ATECC to compute HMAC-SHA256 of a message with the key stored in a slot
+    fn sha_hmac(&self, message: &[u8], slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| chip.sha_hmac(message, slot_id))
+    } // AteccSimulator::sha_hmac()
+
+    /// Execute a Nonce command in pass-through mode, TempKey becomes valid when it is the target
+    fn nonce(&self, target: NonceTarget, data: &[u8]) -> AtcaStatus {
+        let result = self.chip().nonce(target, data);
+        if (AtcaStatus::AtcaSuccess == result) && (NonceTarget::TempKey == target) {
+            self.load_temp_key(TempKeySource::Nonce, data.len());
+        }
+        result
+    } // AteccSimulator::nonce()
+
+    /// Execute a Nonce command generating a random nonce, TempKey becomes valid
+    fn nonce_rand(&self, host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
+        let result = self.chip().nonce_rand(host_nonce, rand_out);
+        if AtcaStatus::AtcaSuccess == result {
+            self.load_temp_key(TempKeySource::RandomNonce, ATCA_KEY_SIZE);
+        }
+        result
+    } // AteccSimulator::nonce_rand()
+
+    /// Execute a GenDig command, TempKey must be valid. Using the key
+    /// stored in a slot counts as a use of a limited-use key
+    fn gen_dig(&self, zone: GenDigZone, slot_id: u8, other_data: &[u8]) -> AtcaStatus {
+        if !self.is_temp_key_valid() {
+            return AtcaStatus::AtcaExecutionError;
+        }
+        match zone {
+            GenDigZone::Data => into_status(self.use_key(slot_id, |chip| {
+                into_result(chip.gen_dig(zone, slot_id, other_data))
+            })),
+            GenDigZone::SharedNonce => self.chip().gen_dig(zone, slot_id, other_data),
+        }
+    } // AteccSimulator::gen_dig()
+
+    /// Execute a MAC command, TempKey must be valid when there is no challenge
+    fn mac(&self, slot_id: u8, challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        if challenge.is_none() && !self.is_temp_key_valid() {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        self.use_key(slot_id, |chip| chip.mac(slot_id, challenge))
+    } // AteccSimulator::mac()
+
+    /// Execute a CheckMac command, TempKey must be valid when there is no challenge
+    fn check_mac(
+        &self,
+        slot_id: u8,
+        challenge: Option<&[u8]>,
+        response: &[u8],
+        other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if challenge.is_none() && !self.is_temp_key_valid() {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        self.use_key(slot_id, |chip| {
+            chip.check_mac(slot_id, challenge, response, other_data)
+        })
+    } // AteccSimulator::check_mac()
+
+    /// Request ATECC to generate a cryptographic key,
+    /// TempKey becomes valid when the key is generated into it
+    fn gen_key(&self, key_type: KeyType, slot_id: u8) -> AtcaStatus {
+        let result = self.chip().gen_key(key_type, slot_id);
+        if (AtcaStatus::AtcaSuccess == result) && (ATCA_ATECC_SLOTS_COUNT == slot_id) {
+            self.load_temp_key(TempKeySource::GenKey, ATCA_KEY_SIZE);
+        }
+        result
+    } // AteccSimulator::gen_key()
+
+    /// Request ATECC to import a cryptographic key,
+    /// TempKey becomes valid when the key is imported into it
+    fn import_key(&self, key_type: KeyType, key_data: &[u8], slot_id: u8) -> AtcaStatus {
+        let result = self.chip().import_key(key_type, key_data, slot_id);
+        if (AtcaStatus::AtcaSuccess == result) && (ATCA_ATECC_SLOTS_COUNT == slot_id) {
+            self.load_temp_key(TempKeySource::Nonce, ATCA_KEY_SIZE);
+        }
+        result
+    } // AteccSimulator::import_key()
+
+    /// Request ATECC to generate an ECDSA signature,
+    /// TempKey is overwritten when the hash is loaded into it
+    fn sign_hash(&self, mode: SignMode, slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
+        let buffer = sign_digest_buffer(&mode);
+        let result = into_status(self.use_key(slot_id, |chip| {
+            into_result(chip.sign_hash(mode, slot_id, signature))
+        }));
+        self.temp_key_lost_to_digest(AtcaStatus::AtcaSuccess == result, buffer, "sign_hash");
+        result
+    } // AteccSimulator::sign_hash()
+
+    /// Request ATECC to hash a message and sign the digest
+    fn sign_message(&self, slot_id: u8, message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| chip.sign_message(slot_id, message))
+    } // AteccSimulator::sign_message()
+
+    /// Request ATECC to execute a Sign command in the given mode,
+    /// a message taken from TempKey must be valid
+    fn sign_base(&self, mode: u8, slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if ((mode & ATCA_SIGN_MODE_SOURCE_MSGDIGBUF) == 0) && !self.is_temp_key_valid() {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        self.use_key(slot_id, |chip| chip.sign_base(mode, slot_id))
+    } // AteccSimulator::sign_base()
+
+    /// Request ATECC to generate an ECDSA signature in the given format
+    fn sign_hash_formatted(
+        &self,
+        mode: SignMode,
+        slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let buffer = sign_digest_buffer(&mode);
+        let result = self.use_key(slot_id, |chip| {
+            chip.sign_hash_formatted(mode, slot_id, format)
+        });
+        self.temp_key_lost_to_digest(result.is_ok(), buffer, "sign_hash_formatted");
+        result
+    } // AteccSimulator::sign_hash_formatted()
+
+    /// Request ATECC to perform ECDH key agreement,
+    /// TempKey becomes valid when the shared secret is stored in it
+    fn ecdh(
+        &self,
+        slot_id: u8,
+        public_key: &[u8],
+        session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        let result = self.use_key(slot_id, |chip| chip.ecdh(slot_id, public_key, session));
+        if let Ok(EcdhOutput::TempKey) = result {
+            self.load_temp_key(TempKeySource::Ecdh, ATCA_KEY_SIZE);
+        }
+        result
+    } // AteccSimulator::ecdh()
+
+    /// Request ATECC to derive a key, a TempKey source must be valid
+    /// and a TempKey target becomes valid
+    fn kdf(
+        &self,
+        algorithm: KdfAlgorithm,
+        source: KdfSource,
+        target: KdfTarget,
+        message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        let command = |chip: &SoftwareBackend| chip.kdf(algorithm, source, target, message);
+        let result = match source {
+            KdfSource::TempKey | KdfSource::TempKeyUpper => {
+                self.use_key(ATCA_ATECC_SLOTS_COUNT, command)
+            }
+            KdfSource::Slot(slot_id) => self.use_key(slot_id, command),
+            KdfSource::AltKeyBuf => command(&self.chip()),
+        };
+        if let (Ok(_), Some(size)) = (&result, kdf_temp_key_size(algorithm, target)) {
+            self.load_temp_key(TempKeySource::Kdf, size);
+        }
+        result
+    } // AteccSimulator::kdf()
+
+    /// Request ATECC to perform ECDH key agreement with the premaster secret
+    /// encrypted with the IO protection key
+    fn ecdh_io_protected(
+        &self,
+        session: &IoProtectionSession,
+        slot_id: u8,
+        public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| {
+            chip.ecdh_io_protected(session, slot_id, public_key)
+        })
+    } // AteccSimulator::ecdh_io_protected()
+
+    /// Data encryption function in AES unauthenticated cipher algorithms modes
+    fn cipher_encrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        into_status(self.use_aes_key(slot_id, |chip| {
+            into_result(chip.cipher_encrypt(algorithm, slot_id, data))
+        }))
+    } // AteccSimulator::cipher_encrypt()
+
+    /// Data decryption function in AES unauthenticated cipher algorithms modes
+    fn cipher_decrypt(
+        &self,
+        algorithm: CipherAlgorithm,
+        slot_id: u8,
+        data: &mut Vec<u8>,
+    ) -> AtcaStatus {
+        into_status(self.use_aes_key(slot_id, |chip| {
+            into_result(chip.cipher_decrypt(algorithm, slot_id, data))
+        }))
+    } // AteccSimulator::cipher_decrypt()
+
+    /// Data encryption function in AES AEAD (authenticated encryption with associated data) modes
+    fn aead_encrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut [u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_aes_key(slot_id, |chip| chip.aead_encrypt(algorithm, slot_id, data))
+    } // AteccSimulator::aead_encrypt()
+
+    /// Data decryption function in AES AEAD (authenticated encryption with associated data) modes
+    fn aead_decrypt(
+        &self,
+        algorithm: AeadAlgorithm,
+        slot_id: u8,
+        data: &mut [u8],
+    ) -> Result<bool, AtcaStatus> {
+        self.use_aes_key(slot_id, |chip| chip.aead_decrypt(algorithm, slot_id, data))
+    } // AteccSimulator::aead_decrypt()
+
+    /// Initializes an AES-GCM operation performed in several steps
+    fn gcm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        self.use_aes_key(slot_id, |chip| chip.gcm_init(slot_id, nonce, key))
+    } // AteccSimulator::gcm_init()
+
+    /// Initializes an AES-CCM operation performed in several steps
+    fn ccm_init(
+        &self,
+        slot_id: u8,
+        nonce: &[u8],
+        key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        self.use_aes_key(slot_id, |chip| {
+            chip.ccm_init(slot_id, nonce, key, aad_length, text_length, tag_length)
+        })
+    } // AteccSimulator::ccm_init()
+
+    /// Derives the TLS 1.2 key block from an ECDH key agreement with the private key in a slot
+    fn tls_derive_keys(
+        &self,
+        slot_id: u8,
+        server_public_key: &[u8],
+        client_random: &[u8],
+        server_random: &[u8],
+        key_block_length: usize,
+        session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| {
+            chip.tls_derive_keys(
+                slot_id,
+                server_public_key,
+                client_random,
+                server_random,
+                key_block_length,
+                session,
+            )
+        })
+    } // AteccSimulator::tls_derive_keys()
+
+    /// Encrypts a message with ECIES, the ephemeral key pair is generated into TempKey,
+    /// which ends up holding the derived key
+    fn ecies_encrypt(&self, public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        let result = self.chip().ecies_encrypt(public_key, plaintext);
+        if result.is_ok() {
+            self.load_temp_key(TempKeySource::Kdf, ATCA_KEY_SIZE);
+        }
+        result
+    } // AteccSimulator::ecies_encrypt()
+
+    /// Decrypts an ECIES message with the private key stored in a slot
+    fn ecies_decrypt(&self, slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| chip.ecies_decrypt(slot_id, data))
+    } // AteccSimulator::ecies_decrypt()
+
+    /// Wraps a key with the AES key stored in a slot
+    fn wrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| chip.wrap_key(algorithm, slot_id, key))
+    } // AteccSimulator::wrap_key()
+
+    /// Unwraps a key with the AES key stored in a slot
+    fn unwrap_key(
+        &self,
+        algorithm: KeyWrapAlgorithm,
+        slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        self.use_key(slot_id, |chip| {
+            chip.unwrap_key(algorithm, slot_id, wrapped_key)
+        })
+    } // AteccSimulator::unwrap_key()
+
+    /// Perform an AES-128 encrypt operation with a key in a slot or in TempKey
+    fn aes_encrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.use_key(key_id_to_slot_id(key_id), |chip| {
+            chip.aes_encrypt_block(key_id, key_block, input)
+        })
+    } // AteccSimulator::aes_encrypt_block()
+
+    /// Perform an AES-128 decrypt operation with a key in a slot or in TempKey
+    fn aes_decrypt_block(
+        &self,
+        key_id: u16,
+        key_block: u8,
+        input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        self.use_key(key_id_to_slot_id(key_id), |chip| {
+            chip.aes_decrypt_block(key_id, key_block, input)
+        })
+    } // AteccSimulator::aes_decrypt_block()
+
+    /// Runs the SelfTest command, the tests use the volatile buffers, so TempKey is lost
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        self.invalidate_temp_key("self_test");
+        self.chip().self_test()
+    } // AteccSimulator::self_test()
+
+    /// Wakes the chip up
+    fn wake(&self) -> AtcaStatus {
+        self.chip().wake()
+    } // AteccSimulator::wake()
+
+    /// Puts the chip into the idle mode, TempKey stays valid
+    fn idle(&self) -> AtcaStatus {
+        self.chip().idle()
+    } // AteccSimulator::idle()
+
+    /// Puts the chip into the sleep mode, TempKey is lost
+    fn sleep(&self) -> AtcaStatus {
+        self.invalidate_temp_key("sleep");
+        self.chip().sleep()
+    } // AteccSimulator::sleep()
+
+    /// The emulated chip cannot be unplugged
+    fn is_disconnected(&self) -> bool {
+        self.chip().is_disconnected()
+    } // AteccSimulator::is_disconnected()
+
+    /// There is no communication to re-initialize
+    fn reconnect(&self) -> AtcaStatus {
+        self.chip().reconnect()
+    } // AteccSimulator::reconnect()
+
+    /// Nothing is cached, the state of the simulated chip is always current
+    fn refresh(&self) -> AtcaStatus {
+        self.chip().refresh()
+    } // AteccSimulator::refresh()
+
+    /// The lock state of the simulated chip is not cached
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        self.chip().refresh_lock_state()
+    } // AteccSimulator::refresh_lock_state()
+
+    /// ATECC device instance destructor, the volatile TempKey is lost
+    fn release(&self) -> AtcaStatus {
+        self.invalidate_temp_key("release");
+        self.chip().release()
+    } // AteccSimulator::release()
+
+    // ---------------------------------------------------------------
+    // Private functions
+    // ---------------------------------------------------------------
+
+    /// A helper function giving shared access to the emulated chip
+    fn chip(&self) -> RwLockReadGuard<'_, SoftwareBackend> {
+        self.chip.read().expect("Could not lock 'chip' lock")
+    } // AteccSimulator::chip()
+
+    /// A helper function giving exclusive access to the emulated chip,
+    /// needed to change its configuration
+    fn chip_mut(&self) -> RwLockWriteGuard<'_, SoftwareBackend> {
+        self.chip.write().expect("Could not lock 'chip' lock")
+    } // AteccSimulator::chip_mut()
+
+    /// A helper function giving access to the monotonic counters
+    fn counters(&self) -> MutexGuard<'_, [u32; COUNTERS_COUNT]> {
+        self.counters
+            .lock()
+            .expect("Could not lock 'counters' mutex")
+    } // AteccSimulator::counters()
+
+    /// A helper function giving access to the state of TempKey
+    fn temp_key(&self) -> MutexGuard<'_, TempKeyState> {
+        self.temp_key
+            .lock()
+            .expect("Could not lock 'temp_key' mutex")
+    } // AteccSimulator::temp_key()
+
+    fn load_temp_key(&self, source: TempKeySource, size: usize) {
+        self.temp_key().load(source, size);
+    } // AteccSimulator::load_temp_key()
+
+    fn invalidate_temp_key(&self, command: &'static str) {
+        self.temp_key().invalidate(command);
+    } // AteccSimulator::invalidate_temp_key()
+
+    /// TempKey is overwritten by Sign and Verify when the caller loads the hash into it
+    fn temp_key_lost_to_digest(
+        &self,
+        succeeded: bool,
+        buffer: Option<DigestBuffer>,
+        command: &'static str,
+    ) {
+        if succeeded && (Some(DigestBuffer::TempKey) == buffer) {
+            self.invalidate_temp_key(command);
+        }
+    } // AteccSimulator::temp_key_lost_to_digest()
+
+    /// A helper function that replaces the emulated chip with one configured
+    /// by the new configuration zone, keeping the contents of the slots,
+    /// the volatile state and the access keys
+    fn reconfigure(
+        chip: &mut SoftwareBackend,
+        config_zone: &[u8; ATCA_ATECC_CONFIG_BUFFER_SIZE],
+    ) -> AtcaStatus {
+        let mut reconfigured = match SoftwareBackend::from_config_zone(config_zone) {
+            Ok(reconfigured) => reconfigured,
+            Err(err) => return err,
+        };
+        mem::swap(&mut reconfigured.state, &mut chip.state);
+        mem::swap(&mut reconfigured.access_keys, &mut chip.access_keys);
+        *chip = reconfigured;
+        AtcaStatus::AtcaSuccess
+    } // AteccSimulator::reconfigure()
+
+    /// A helper function that executes a command using the key stored in a slot
+    /// (or in TempKey, for slot_id equal to ATCA_ATECC_SLOTS_COUNT).
+    /// TempKey must be valid, a limited use key is counted by the monotonic counter 0
+    /// and cannot be used after the counter has reached its limit.
+    /// Other slot numbers are left for the command to reject
+    fn use_key<T>(
+        &self,
+        slot_id: u8,
+        command: impl FnOnce(&SoftwareBackend) -> Result<T, AtcaStatus>,
+    ) -> Result<T, AtcaStatus> {
+        let chip = self.chip();
+        if ATCA_ATECC_SLOTS_COUNT == slot_id {
+            if !self.is_temp_key_valid() {
+                return Err(AtcaStatus::AtcaExecutionError);
+            }
+            return command(&chip);
+        }
+        if (slot_id > ATCA_ATECC_SLOTS_COUNT) || !chip.slots[slot_id as usize].config.limited_use {
+            return command(&chip);
+        }
+
+        let mut counters = self.counters();
+        let counter = &mut counters[ATCA_LIMITED_USE_COUNTER_ID as usize];
+        if *counter >= ATCA_COUNTER_MAX_VALUE {
+            return Err(AtcaStatus::AtcaExecutionError);
+        }
+        let result = command(&chip);
+        if result.is_ok() {
+            *counter += 1;
+        }
+        result
+    } // AteccSimulator::use_key()
+
+    /// A helper function that executes an AES command. For slot_id equal to
+    /// ATCA_ATECC_SLOTS_COUNT the key given with the command is loaded into TempKey,
+    /// which becomes valid, otherwise the key stored in the slot is used
+    fn use_aes_key<T>(
+        &self,
+        slot_id: u8,
+        command: impl FnOnce(&SoftwareBackend) -> Result<T, AtcaStatus>,
+    ) -> Result<T, AtcaStatus> {
+        if ATCA_ATECC_SLOTS_COUNT != slot_id {
+            return self.use_key(slot_id, command);
+        }
+        let result = command(&self.chip());
+        if result.is_ok() {
+            self.load_temp_key(TempKeySource::Nonce, ATCA_KEY_SIZE);
+        }
+        result
+    } // AteccSimulator::use_aes_key()
+}
+
+/// Slot number of the key addressed by the key_id of the AES command
+fn key_id_to_slot_id(key_id: u16) -> u8 {
+    match key_id {
+        ATCA_ATECC_TEMPKEY_KEYID => ATCA_ATECC_SLOTS_COUNT,
+        _ => u8::try_from(key_id).unwrap_or(u8::MAX),
+    }
+}
+
+fn into_result(status: AtcaStatus) -> Result<(), AtcaStatus> {
+    match status {
+        AtcaStatus::AtcaSuccess => Ok(()),
+        _ => Err(status),
+    }
+}
+
+fn into_status(result: Result<(), AtcaStatus>) -> AtcaStatus {
+    match result {
+        Ok(()) => AtcaStatus::AtcaSuccess,
+        Err(err) => err,
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::sync::Arc;
+
+use ::rustls::client::ResolvesClientCert;
+use ::rustls::pki_types::CertificateDer;
+use ::rustls::sign::{CertifiedKey, Signer, SigningKey};
+use ::rustls::{Error, SignatureAlgorithm, SignatureScheme};
+
+use super::signature::signature_to_der;
+use super::tng::TNG_DEVICE_KEY_SLOT;
+use super::{AteccDevice, TngCertChain};
+
+/// The only scheme ATECC can sign with
+const ATECC_SIGNATURE_SCHEME: SignatureScheme = SignatureScheme::ECDSA_NISTP256_SHA256;
+
+/// rustls signing key backed by the P256 private key stored in a slot of ATECC
+#[derive(Clone)]
+pub struct AteccSigningKey {
+    device: Arc<AteccDevice>,
+    slot_id: u8,
+}
+
+impl AteccSigningKey {
+    /// Creates a signing key for the private key stored in the given slot
+    pub fn new(device: Arc<AteccDevice>, slot_id: u8) -> Self {
+        AteccSigningKey { device, slot_id }
+    } // AteccSigningKey::new()
+}
+
+impl fmt::Debug for AteccSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AteccSigningKey")
+            .field("slot_id", &self.slot_id)
+            .finish()
+    }
+}
+
+impl SigningKey for AteccSigningKey {
+    fn choose_scheme(&self, offered: &[SignatureScheme]) -> Option<Box<dyn Signer>> {
+        match offered.contains(&ATECC_SIGNATURE_SCHEME) {
+            true => Some(Box::new(self.clone())),
+            false => None,
+        }
+    } // AteccSigningKey::choose_scheme()
+
+    fn algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::ECDSA
+    }
+}
+
+impl Signer for AteccSigningKey {
+    /// Hashes the message (SHA256) and signs the digest, both on the chip,
+    /// the signature is DER encoded as TLS requires
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        self.device
+            .sign_message(self.slot_id, message)
+            .and_then(|signature| signature_to_der(&signature))
+            .map_err(|err| Error::General(format!("ATECC signing failed: {}", err)))
+    } // AteccSigningKey::sign()
+
+    fn scheme(&self) -> SignatureScheme {
+        ATECC_SIGNATURE_SCHEME
+    }
+}
+
+/// rustls client certificate resolver presenting a fixed certificate chain
+/// with its private key kept in ATECC, for mutual TLS
+#[derive(Debug)]
+pub struct AteccClientCertResolver {
+    certified_key: Arc<CertifiedKey>,
+}
+
+impl AteccClientCertResolver {
+    /// Creates a resolver for the certificate chain (DER, the client certificate first,
+    /// the root may be omitted) whose key is stored in the given slot
+    pub fn new(device: Arc<AteccDevice>, slot_id: u8, cert_chain: Vec<Vec<u8>>) -> Self {
+        let cert_chain = cert_chain.into_iter().map(CertificateDer::from).collect();
+        let key = Arc::new(AteccSigningKey::new(device, slot_id));
+        AteccClientCertResolver {
+            certified_key: Arc::new(CertifiedKey::new(cert_chain, key)),
+        }
+    } // AteccClientCertResolver::new()
+
+    /// Creates a resolver presenting the device and signer certificates of a Trust&GO
+    /// or TrustFLEX part (see tng_get_cert_chain()) with the device key in slot 0
+    pub fn from_tng_chain(device: Arc<AteccDevice>, cert_chain: &TngCertChain) -> Self {
+        Self::new(
+            device,
+            TNG_DEVICE_KEY_SLOT,
+            vec![cert_chain.device.clone(), cert_chain.signer.clone()],
+        )
+    } // AteccClientCertResolver::from_tng_chain()
+}
+
+impl ResolvesClientCert for AteccClientCertResolver {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        match sigschemes.contains(&ATECC_SIGNATURE_SCHEME) {
+            true => Some(self.certified_key.clone()),
+            false => None,
+        }
+    } // AteccClientCertResolver::resolve()
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use rand::{distributions::Standard, Rng};
+use zeroize::Zeroizing;
+
+use super::{constant_time_eq, AtcaStatus, AteccDeviceTrait, GenDigZone, SlotId};
+use super::{
+    ATCA_KEY_SIZE, ATCA_NONCE_NUMIN_SIZE, ATCA_SERIAL_NUM_SIZE, ATCA_SHA2_256_DIGEST_SIZE,
+};
+
+const NONCE_OPCODE: u8 = 0x16;
+const GENDIG_OPCODE: u8 = 0x15;
+const MAC_OPCODE: u8 = 0x08;
+/// Nonce command mode mixing a host nonce with a random number, TempKey being updated
+const NONCE_MODE_SEED_UPDATE: u8 = 0x00;
+/// MAC and CheckMac mode: the key from the slot, followed by a challenge given with the command
+pub(crate) const MAC_MODE_CHALLENGE: u8 = 0x00;
+/// MAC and CheckMac mode: the key from the slot, followed by TempKey
+pub(crate) const MAC_MODE_TEMPKEY: u8 = 0x01;
+/// Size of the data given to CheckMac along with the response
+pub const ATCA_CHECK_MAC_OTHER_DATA_SIZE: usize = 13;
+/// Zero bytes hashed by GenDig in place of unused parameters
+const GENDIG_ZEROS_SIZE: usize = 25;
+
+/// Replay-protected authentication with a secret 32-byte key stored in a slot
+/// and known to the host. Every exchange executes the canonical sequence:
+/// Nonce with a fresh random number, GenDig over the slot and MAC or CheckMac
+/// of the resulting TempKey, so a MAC recorded earlier is never accepted again.
+/// The digests the chip calculates are calculated on the host as well
+pub struct SecureSession<'a, D: AteccDeviceTrait + ?Sized> {
+    device: &'a D,
+    slot_id: u8,
+    key: Zeroizing<[u8; ATCA_KEY_SIZE]>,
+    serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
+}
+
+impl<'a, D: AteccDeviceTrait + ?Sized> SecureSession<'a, D> {
+    /// Creates a session for the key stored in the given slot
+    pub fn new(device: &'a D, slot_id: u8, key: &[u8]) -> Result<Self, AtcaStatus> {
+        SlotId::new(slot_id)?;
+        if key.len() != ATCA_KEY_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let mut session_key = Zeroizing::new([0; ATCA_KEY_SIZE]);
+        session_key.copy_from_slice(key);
+        Ok(SecureSession {
+            device,
+            slot_id,
+            key: session_key,
+            serial_number: device.get_serial_number(),
+        })
+    } // SecureSession::new()
+
+    /// Slot holding the key
+    pub fn slot_id(&self) -> u8 {
+        self.slot_id
+    }
+
+    /// Checks that the chip knows the key: the MAC the chip calculates
+    /// over the fresh TempKey is compared with the one calculated on the host
+    pub fn authenticate_device(&self) -> Result<bool, AtcaStatus> {
+        let temp_key = self.start()?;
+        let mac = self.device.mac(self.slot_id, None)?;
+        let expected = sha256(&mac_message(
+            MAC_MODE_TEMPKEY,
+            self.slot_id,
+            &self.key[..],
+            &temp_key[..],
+            &self.serial_number,
+        ))?;
+        Ok(constant_time_eq(&mac, &expected))
+    } // SecureSession::authenticate_device()
+
+    /// Proves to the chip that the host knows the key: the response calculated
+    /// on the host over the fresh TempKey and 'other_data' is checked by CheckMac.
+    /// Depending on the slot configuration, a successful CheckMac may authorize
+    /// the use of other keys or copy the key to TempKey
+    pub fn authenticate_host(&self, other_data: &[u8]) -> Result<bool, AtcaStatus> {
+        if other_data.len() != ATCA_CHECK_MAC_OTHER_DATA_SIZE {
+            return Err(AtcaStatus::AtcaInvalidSize);
+        }
+        let temp_key = self.start()?;
+        let response = Zeroizing::new(sha256(&check_mac_message(
+            &self.key[..],
+            &temp_key[..],
+            other_data,
+            &self.serial_number,
+        ))?);
+        self.device
+            .check_mac(self.slot_id, None, &response[..], other_data)
+    } // SecureSession::authenticate_host()
+
+    /// Executes Nonce and GenDig, returns the value of TempKey they give
+    fn start(&self) -> Result<Zeroizing<[u8; ATCA_SHA2_256_DIGEST_SIZE]>, AtcaStatus> {
+        let num_in = self.host_nonce()?;
+        let mut rand_out: Vec<u8> = Vec::new();
+        match self.device.nonce_rand(&num_in, &mut rand_out) {
+            AtcaStatus::AtcaSuccess => (),
+            err => return Err(err),
+        }
+        let temp_key = Zeroizing::new(sha256(&nonce_message(&rand_out, &num_in))?);
+
+        match self.device.gen_dig(GenDigZone::Data, self.slot_id, &[]) {
+            AtcaStatus::AtcaSuccess => (),
+            err => return Err(err),
+        }
+        Ok(Zeroizing::new(sha256(&gen_dig_message(
+            GenDigZone::Data,
+            self.slot_id,
+            &self.key[..],
+            &self.serial_number,
+            &temp_key[..],
+        ))?))
+    } // SecureSession::start()
+
+    /// Host part of the random nonce
+    #[cfg(feature = "std")]
+    fn host_nonce(&self) -> Result<Vec<u8>, AtcaStatus> {
+        Ok(rand::thread_rng()
+            .sample_iter(Standard)
+            .take(ATCA_NONCE_NUMIN_SIZE)
+            .collect())
+    }
+
+    /// Host part of the random nonce. Without std there is no random number
+    /// generator on the host side, the TRNG of ATECC is used instead
+    #[cfg(not(feature = "std"))]
+    fn host_nonce(&self) -> Result<Vec<u8>, AtcaStatus> {
+        let mut num_in = Vec::new();
+        match self.device.random(&mut num_in) {
+            AtcaStatus::AtcaSuccess => {
+                num_in.truncate(ATCA_NONCE_NUMIN_SIZE);
+                Ok(num_in)
+            }
+            err => Err(err),
+        }
+    }
+}
+
+/// Message hashed by Nonce in the random mode, the digest is the new TempKey
+pub(crate) fn nonce_message(rand_out: &[u8], num_in: &[u8]) -> Vec<u8> {
+    let mut message: Vec<u8> = rand_out.to_vec();
+    message.extend_from_slice(num_in);
+    message.extend_from_slice(&[NONCE_OPCODE, NONCE_MODE_SEED_UPDATE, 0x00]);
+    message
+} // nonce_message()
+
+/// Message hashed by GenDig, the digest is the new TempKey.
+/// 'data' is the key from the slot or, for the SharedNonce zone, the data given with the command
+pub(crate) fn gen_dig_message(
+    zone: GenDigZone,
+    slot_id: u8,
+    data: &[u8],
+    serial_number: &[u8],
+    temp_key: &[u8],
+) -> Vec<u8> {
+    let mut message: Vec<u8> = data.to_vec();
+    message.extend_from_slice(&[GENDIG_OPCODE, zone as u8, slot_id, 0x00]);
+    message.extend_from_slice(&[serial_number[8], serial_number[0], serial_number[1]]);
+    message.extend_from_slice(&[0; GENDIG_ZEROS_SIZE]);
+    message.extend_from_slice(&temp_key[..ATCA_KEY_SIZE]);
+    message
+} // gen_dig_message()
+
+/// Message hashed by MAC: the key, the challenge (or TempKey), the command parameters
+/// and the parts of the serial number always included. OTP bits are not included
+pub(crate) fn mac_message(
+    mode: u8,
+    slot_id: u8,
+    key: &[u8],
+    challenge: &[u8],
+    serial_number: &[u8],
+) -> Vec<u8> {
+    let mut message: Vec<u8> = key.to_vec();
+    message.extend_from_slice(&challenge[..ATCA_KEY_SIZE]);
+    message.extend_from_slice(&[MAC_OPCODE, mode, slot_id, 0x00]);
+    message.extend_from_slice(&[0; 11]);
+    message.push(serial_number[8]);
+    message.extend_from_slice(&[0; 4]);
+    message.extend_from_slice(&serial_number[0..2]);
+    message.extend_from_slice(&[0; 2]);
+    message
+} // mac_message()
+
+/// Message hashed by CheckMac: the key, the challenge (or TempKey),
+/// 'other_data' and the parts of the serial number always included
+pub(crate) fn check_mac_message(
+    key: &[u8],
+    challenge: &[u8],
+    other_data: &[u8],
+    serial_number: &[u8],
+) -> Vec<u8> {
+    let mut message: Vec<u8> = key.to_vec();
+    message.extend_from_slice(&challenge[..ATCA_KEY_SIZE]);
+    message.extend_from_slice(&other_data[0..4]);
+    message.extend_from_slice(&[0; 8]);
+    message.extend_from_slice(&other_data[4..7]);
+    message.push(serial_number[8]);
+    message.extend_from_slice(&other_data[7..11]);
+    message.extend_from_slice(&serial_number[0..2]);
+    message.extend_from_slice(&other_data[11..13]);
+    message
+} // check_mac_message()
+
+/// SHA256 calculated on the host
+fn sha256(message: &[u8]) -> Result<[u8; ATCA_SHA2_256_DIGEST_SIZE], AtcaStatus> {
+    let mut digest: [u8; ATCA_SHA2_256_DIGEST_SIZE] = [0; ATCA_SHA2_256_DIGEST_SIZE];
+    let result = unsafe {
+        cryptoauthlib_sys::atcac_sw_sha2_256(
+            message.as_ptr(),
+            message.len() as cryptoauthlib_sys::size_t,
+            digest.as_mut_ptr(),
+        )
+    };
+    match result {
+        0 => Ok(digest),
+        _ => Err(AtcaStatus::AtcaFuncFail),
+    }
+} // sha256()
@@ -0,0 +1,59 @@
+use super::ATCA_SHA206A_SYMMETRIC_KEY_SLOT;
+use super::{constant_time_eq, AtcaDeviceType, AtcaStatus, AteccDeviceTrait, Sha206aUseState};
+use alloc::vec::Vec;
+
+/// UseFlag of the derived key, its UpdateCount and UseFlag of the parent key,
+/// in the sixth word of the configuration zone
+const IDX_DK_USE_FLAG: usize = 20;
+const IDX_DK_UPDATE_COUNT: usize = 21;
+const IDX_PK_USE_FLAG: usize = 22;
+
+impl Sha206aUseState {
+    /// The derived key has no uses left and cannot be renewed any more,
+    /// the consumable the chip is built into has been used up
+    pub fn is_consumed(&self) -> bool {
+        (self.derived_key_uses_left == 0) && (self.parent_key_uses_left == 0)
+    }
+} // impl Sha206aUseState
+
+/// Tells how many uses the two keys of an ATSHA206A have left.
+/// Every MAC with the derived key clears one bit of its UseFlag,
+/// every renewal of the derived key one bit of UseFlag of the parent key
+pub fn sha206a_use_state(device: &dyn AteccDeviceTrait) -> Result<Sha206aUseState, AtcaStatus> {
+    if device.get_device_type() != AtcaDeviceType::ATSHA206A {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let mut config_data: Vec<u8> = Vec::new();
+    match device.read_config_zone(&mut config_data) {
+        AtcaStatus::AtcaSuccess => use_state_from_config(&config_data),
+        err => Err(err),
+    }
+} // sha206a_use_state()
+
+/// Authenticates an ATSHA206A: the chip computes the MAC of a 32-byte 'challenge'
+/// with its derived key, which must be 'expected_response' worked out by the host.
+/// Each authentication takes one use of the derived key, so AtcaKeyUsesExhausted
+/// is returned without sending the challenge when none is left
+pub fn sha206a_authenticate(
+    device: &dyn AteccDeviceTrait,
+    challenge: &[u8],
+    expected_response: &[u8],
+) -> Result<bool, AtcaStatus> {
+    if sha206a_use_state(device)?.derived_key_uses_left == 0 {
+        return Err(AtcaStatus::AtcaKeyUsesExhausted);
+    }
+    let response = device.mac(ATCA_SHA206A_SYMMETRIC_KEY_SLOT, Some(challenge))?;
+    Ok(constant_time_eq(&response, expected_response))
+} // sha206a_authenticate()
+
+/// Uses left of the keys, as told by the configuration zone of an ATSHA206A
+pub(crate) fn use_state_from_config(config_data: &[u8]) -> Result<Sha206aUseState, AtcaStatus> {
+    if config_data.len() <= IDX_PK_USE_FLAG {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    Ok(Sha206aUseState {
+        derived_key_uses_left: config_data[IDX_DK_USE_FLAG].count_ones() as u8,
+        parent_key_uses_left: config_data[IDX_PK_USE_FLAG].count_ones() as u8,
+        derived_key_update_count: config_data[IDX_DK_UPDATE_COUNT],
+    })
+} // use_state_from_config()
@@ -0,0 +1,123 @@
+use super::der::{read_element, ASN1_INTEGER, ASN1_SEQUENCE};
+use super::{AtcaDeviceType, AtcaStatus, SignatureFormat};
+use super::{ATCA_SIGN_MODE_EXTERNAL, ATCA_SIGN_MODE_INCLUDE_SN, ATCA_SIGN_MODE_INVALIDATE};
+use super::{ATCA_SIGN_MODE_SOURCE_MSGDIGBUF, ATCA_SIG_DER_MAX_SIZE, ATCA_SIG_SIZE};
+use alloc::vec::Vec;
+
+const SIG_COMPONENT_SIZE: usize = ATCA_SIG_SIZE / 2;
+
+/// Converts a raw (R || S) ECDSA signature into its ASN.1 DER encoding
+pub fn signature_to_der(signature: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    if signature.len() != ATCA_SIG_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let r = der_integer(&signature[..SIG_COMPONENT_SIZE]);
+    let s = der_integer(&signature[SIG_COMPONENT_SIZE..]);
+
+    let mut result: Vec<u8> = Vec::with_capacity(ATCA_SIG_DER_MAX_SIZE);
+    result.push(ASN1_SEQUENCE);
+    result.push((r.len() + s.len()) as u8);
+    result.extend_from_slice(&r);
+    result.extend_from_slice(&s);
+    Ok(result)
+} // signature_to_der()
+
+/// Converts an ASN.1 DER encoded ECDSA signature into the raw (R || S) format
+/// accepted by ATECC
+pub fn signature_from_der(signature: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    if signature.len() > ATCA_SIG_DER_MAX_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let content = match read_element(ASN1_SEQUENCE, signature)? {
+        (content, []) => content,
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+    let (r, rest) = read_element(ASN1_INTEGER, content)?;
+    let (s, rest) = read_element(ASN1_INTEGER, rest)?;
+    if !rest.is_empty() {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+
+    let mut result: Vec<u8> = vec![0x00; ATCA_SIG_SIZE];
+    raw_integer(r, &mut result[..SIG_COMPONENT_SIZE])?;
+    raw_integer(s, &mut result[SIG_COMPONENT_SIZE..])?;
+    Ok(result)
+} // signature_from_der()
+
+/// Converts a signature between the supported formats
+pub fn convert_signature(
+    signature: &[u8],
+    from: SignatureFormat,
+    to: SignatureFormat,
+) -> Result<Vec<u8>, AtcaStatus> {
+    match (from, to) {
+        (SignatureFormat::Raw, SignatureFormat::Der) => signature_to_der(signature),
+        (SignatureFormat::Der, SignatureFormat::Raw) => signature_from_der(signature),
+        (SignatureFormat::Raw, SignatureFormat::Raw) => match signature.len() {
+            ATCA_SIG_SIZE => Ok(signature.to_vec()),
+            _ => Err(AtcaStatus::AtcaInvalidSize),
+        },
+        (SignatureFormat::Der, SignatureFormat::Der) => {
+            signature_from_der(signature).and_then(|raw| signature_to_der(&raw))
+        }
+    }
+} // convert_signature()
+
+/// Checks the mode of a Sign command given to sign_base(): only the bits the chip
+/// defines may be set, the bits of the internal modes cannot be combined with an external
+/// message and only ATECC608x can read the message from the Message Digest Buffer
+pub(crate) fn check_sign_base_mode(
+    device_type: AtcaDeviceType,
+    mode: u8,
+) -> Result<(), AtcaStatus> {
+    const INTERNAL_BITS: u8 = ATCA_SIGN_MODE_INVALIDATE | ATCA_SIGN_MODE_INCLUDE_SN;
+    let known_bits = ATCA_SIGN_MODE_EXTERNAL | ATCA_SIGN_MODE_SOURCE_MSGDIGBUF | INTERNAL_BITS;
+    if (mode & !known_bits) != 0 {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    match (mode & ATCA_SIGN_MODE_EXTERNAL) != 0 {
+        true if (mode & INTERNAL_BITS) != 0 => Err(AtcaStatus::AtcaBadParam),
+        true if ((mode & ATCA_SIGN_MODE_SOURCE_MSGDIGBUF) != 0)
+            && (device_type != AtcaDeviceType::ATECC608A) =>
+        {
+            Err(AtcaStatus::AtcaBadParam)
+        }
+        false if (mode & ATCA_SIGN_MODE_SOURCE_MSGDIGBUF) != 0 => Err(AtcaStatus::AtcaBadParam),
+        _ => Ok(()),
+    }
+} // check_sign_base_mode()
+
+/// Encodes a big-endian unsigned number as a DER INTEGER element,
+/// with leading zeros removed and a zero byte added where the most significant bit is set
+fn der_integer(value: &[u8]) -> Vec<u8> {
+    let first_nonzero = value
+        .iter()
+        .position(|&x| x != 0x00)
+        .unwrap_or(value.len() - 1);
+    let value = &value[first_nonzero..];
+    let padding = (value[0] & 0x80) != 0x00;
+
+    let mut result: Vec<u8> = Vec::with_capacity(value.len() + 3);
+    result.push(ASN1_INTEGER);
+    result.push((value.len() + padding as usize) as u8);
+    if padding {
+        result.push(0x00);
+    }
+    result.extend_from_slice(value);
+    result
+} // der_integer()
+
+/// Stores the value of a positive DER INTEGER as a fixed-size big-endian number
+fn raw_integer(value: &[u8], output: &mut [u8]) -> Result<(), AtcaStatus> {
+    if value.is_empty() || (value[0] & 0x80) != 0x00 {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let first_nonzero = value.iter().position(|&x| x != 0x00).unwrap_or(value.len());
+    let value = &value[first_nonzero..];
+    if value.len() > output.len() {
+        return Err(AtcaStatus::AtcaBadParam);
+    }
+    let offset = output.len() - value.len();
+    output[offset..].copy_from_slice(value);
+    Ok(())
+} // raw_integer()
@@ -0,0 +1,73 @@
+use super::{AtcaSlot, AtcaStatus, AteccDeviceTrait, KeyType};
+use super::{ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY, ATCA_ATECC_SLOTS_COUNT, ATCA_SIG_SIZE};
+use alloc::vec::Vec;
+
+const SIG_COMPONENT_SIZE: usize = ATCA_SIG_SIZE / 2;
+/// Order of the P256 curve, R and S of a signature are smaller
+const P256_ORDER: [u8; SIG_COMPONENT_SIZE] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xBC, 0xE6, 0xFA, 0xAD, 0xA7, 0x17, 0x9E, 0x84, 0xF3, 0xB9, 0xCA, 0xC2, 0xFC, 0x63, 0x25, 0x51,
+];
+
+/// Stores a raw (R || S) ECDSA signature in a data slot, R in its first block and S
+/// in the second, e.g. the signature validating a public key or one belonging
+/// to a certificate kept on the chip.
+/// The slot must hold data (KeyType::ShaOrText) and be large enough: slots below
+/// ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY hold 36 bytes only. R and S must be
+/// in the range a P256 signature can take, the signature is not verified
+pub fn store_signature(device: &dyn AteccDeviceTrait, slot_id: u8, signature: &[u8]) -> AtcaStatus {
+    if let Err(err) = check_signature(signature) {
+        return err;
+    }
+    if let Err(err) = check_signature_slot(device, slot_id) {
+        return err;
+    }
+    device.write_slot_data(slot_id, 0, signature)
+} // store_signature()
+
+/// Reads back a signature stored by store_signature(). A slot not holding
+/// anything a P256 signature can be (e.g. still erased) gives AtcaBadParam
+pub fn load_signature(
+    device: &dyn AteccDeviceTrait,
+    slot_id: u8,
+) -> Result<[u8; ATCA_SIG_SIZE], AtcaStatus> {
+    check_signature_slot(device, slot_id)?;
+    let data = device.read_slot_data(slot_id, 0, ATCA_SIG_SIZE)?;
+    check_signature(&data)?;
+    let mut signature = [0; ATCA_SIG_SIZE];
+    signature.copy_from_slice(&data);
+    Ok(signature)
+} // load_signature()
+
+/// Checks that R and S are both in the range [1, n - 1]
+fn check_signature(signature: &[u8]) -> Result<(), AtcaStatus> {
+    if signature.len() != ATCA_SIG_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    for component in signature.chunks(SIG_COMPONENT_SIZE) {
+        if component.iter().all(|byte| *byte == 0) || (component >= &P256_ORDER[..]) {
+            return Err(AtcaStatus::AtcaBadParam);
+        }
+    }
+    Ok(())
+} // check_signature()
+
+/// Checks that the slot is a data slot able to hold a signature
+fn check_signature_slot(device: &dyn AteccDeviceTrait, slot_id: u8) -> Result<(), AtcaStatus> {
+    if slot_id >= ATCA_ATECC_SLOTS_COUNT {
+        return Err(AtcaStatus::AtcaInvalidId);
+    }
+    if slot_id < ATCA_ATECC_MIN_SLOT_IDX_FOR_PUB_KEY {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    match device.get_config(&mut slots) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    match slots.get(slot_id as usize) {
+        Some(slot) if slot.config.key_type == KeyType::ShaOrText => Ok(()),
+        Some(_) => Err(AtcaStatus::AtcaBadParam),
+        None => Err(AtcaStatus::AtcaInvalidId),
+    }
+} // check_signature_slot()
@@ -0,0 +1,74 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::{AtcaStatus, SlotId};
+use super::{ATCA_ATECC_SLOTS_COUNT, ATCA_ATECC_TEMPKEY_KEYID};
+
+impl SlotId {
+    /// A slot of the data zone, AtcaInvalidId if there is no such slot
+    pub fn new(slot: u8) -> Result<SlotId, AtcaStatus> {
+        match slot < ATCA_ATECC_SLOTS_COUNT {
+            true => Ok(SlotId::Slot(slot)),
+            false => Err(AtcaStatus::AtcaInvalidId),
+        }
+    } // SlotId::new()
+
+    /// The slot a key ID of CryptoAuthLib refers to, ATCA_ATECC_TEMPKEY_KEYID for TempKey
+    pub fn from_key_id(key_id: u16) -> Result<SlotId, AtcaStatus> {
+        match key_id {
+            ATCA_ATECC_TEMPKEY_KEYID => Ok(SlotId::TempKey),
+            _ => SlotId::new(u8::try_from(key_id).map_err(|_| AtcaStatus::AtcaInvalidId)?),
+        }
+    } // SlotId::from_key_id()
+
+    /// Number of the slot, 'None' for TempKey
+    pub fn slot(self) -> Option<u8> {
+        match self {
+            SlotId::Slot(slot) => Some(slot),
+            SlotId::TempKey => None,
+        }
+    } // SlotId::slot()
+
+    /// 'true' for TempKey
+    pub fn is_temp_key(self) -> bool {
+        self == SlotId::TempKey
+    } // SlotId::is_temp_key()
+
+    /// Key ID of CryptoAuthLib, as taken by the commands using a key
+    pub fn key_id(self) -> u16 {
+        match self {
+            SlotId::Slot(slot) => slot as u16,
+            SlotId::TempKey => ATCA_ATECC_TEMPKEY_KEYID,
+        }
+    } // SlotId::key_id()
+}
+
+impl TryFrom<u8> for SlotId {
+    type Error = AtcaStatus;
+
+    /// Checks a slot number taken by the trait functions, ATCA_ATECC_SLOTS_COUNT is TempKey
+    fn try_from(slot_id: u8) -> Result<SlotId, AtcaStatus> {
+        match slot_id {
+            ATCA_ATECC_SLOTS_COUNT => Ok(SlotId::TempKey),
+            _ => SlotId::new(slot_id),
+        }
+    }
+}
+
+impl From<SlotId> for u8 {
+    fn from(slot_id: SlotId) -> u8 {
+        match slot_id {
+            SlotId::Slot(slot) => slot,
+            SlotId::TempKey => ATCA_ATECC_SLOTS_COUNT,
+        }
+    }
+}
+
+impl fmt::Display for SlotId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlotId::Slot(slot) => write!(f, "slot {}", slot),
+            SlotId::TempKey => write!(f, "TempKey"),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::ATCA_BLOCK_SIZE;
+use super::{
+    AtcaSlot, AtcaStatus, AteccDeviceTrait, KeyType, SlotOperation, SlotReport, WriteConfig,
+};
+use alloc::vec::Vec;
+
+/// Describes every slot of the device: its key type, lock status and access policy,
+/// whether it seems to be provisioned and which operations of the wrapper it allows.
+/// It is a higher-level interpretation of get_config(), meant for installers and debugging.
+/// The content of the slots is only read where the configuration permits it
+pub fn slot_report(device: &dyn AteccDeviceTrait) -> Result<Vec<SlotReport>, AtcaStatus> {
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    match device.get_config(&mut slots) {
+        AtcaStatus::AtcaSuccess => (),
+        err => return Err(err),
+    }
+    Ok(slots
+        .iter()
+        .map(|slot| SlotReport {
+            id: slot.id,
+            key_type: slot.config.key_type,
+            is_locked: slot.is_locked,
+            write_config: slot.config.write_config,
+            is_secret: slot.config.is_secret,
+            encrypt_read: slot.config.read_key.encrypt_read,
+            is_provisioned: is_provisioned(device, slot),
+            operations: slot_operations(device, slot),
+        })
+        .collect())
+} // slot_report()
+
+/// Operations of the wrapper the slot configuration allows
+fn slot_operations(device: &dyn AteccDeviceTrait, slot: &AtcaSlot) -> Vec<SlotOperation> {
+    let config = &slot.config;
+    let writable = !slot.is_locked
+        && matches!(
+            config.write_config,
+            WriteConfig::Always | WriteConfig::Encrypt
+        );
+    let mut operations: Vec<SlotOperation> = Vec::new();
+    match config.key_type {
+        KeyType::P256EccKey if config.ecc_key_attr.is_private => {
+            if !slot.is_locked {
+                operations.push(SlotOperation::GenKey);
+            }
+            if writable {
+                operations.push(SlotOperation::ImportKey);
+            }
+            operations.push(SlotOperation::GetPublicKey);
+            if config.ecc_key_attr.ext_sign || config.ecc_key_attr.int_sign {
+                operations.push(SlotOperation::Sign);
+            }
+            if config.ecc_key_attr.ecdh_operation {
+                operations.push(SlotOperation::Ecdh);
+            }
+        }
+        KeyType::P256EccKey => {
+            if writable || (!slot.is_locked && config.write_config == WriteConfig::PubInvalid) {
+                operations.push(SlotOperation::ImportKey);
+            }
+            operations.push(SlotOperation::GetPublicKey);
+            operations.push(SlotOperation::Verify);
+        }
+        KeyType::Aes => {
+            if writable {
+                operations.push(SlotOperation::ImportKey);
+            }
+            if device.is_aes_enabled() {
+                operations.push(SlotOperation::Encrypt);
+                operations.push(SlotOperation::Decrypt);
+            }
+        }
+        KeyType::ShaOrText => {
+            if !config.is_secret || config.read_key.encrypt_read {
+                operations.push(SlotOperation::Read);
+            }
+            if writable {
+                operations.push(SlotOperation::Write);
+            }
+            if config.is_secret {
+                operations.push(SlotOperation::Hmac);
+            }
+        }
+        KeyType::Rfu => (),
+    }
+    operations
+} // slot_operations()
+
+/// Checks if the slot holds something: the public key of a key pair or a public key,
+/// or data readable in clear text. Secret keys cannot be checked
+fn is_provisioned(device: &dyn AteccDeviceTrait, slot: &AtcaSlot) -> Option<bool> {
+    let content = match slot.config.key_type {
+        KeyType::P256EccKey => {
+            let mut public_key: Vec<u8> = Vec::new();
+            match device.get_public_key(slot.id, &mut public_key) {
+                AtcaStatus::AtcaSuccess => public_key,
+                _ => return None,
+            }
+        }
+        KeyType::ShaOrText if !slot.config.is_secret => {
+            device.read_slot_data(slot.id, 0, ATCA_BLOCK_SIZE).ok()?
+        }
+        _ => return None,
+    };
+    Some(content.iter().any(|byte| *byte != 0))
+} // is_provisioned()
@@ -1,3 +1,4 @@
+use alloc::{string::String, vec::Vec};
 #[cfg(test)]
 use cryptoauthlib_sys::atca_aes_cbc_ctx_t;
 #[cfg(test)]
@@ -6,16 +7,28 @@ use cryptoauthlib_sys::atca_aes_ctr_ctx_t;
 use std::mem::MaybeUninit;
 
 use super::{
-    AeadAlgorithm, AtcaDeviceType, AtcaIfaceCfg, AtcaIfaceType, AtcaSlot, AtcaStatus,
-    AteccDeviceTrait, CipherAlgorithm, InfoCmdType, KeyType, NonceTarget, OutputProtectionState,
-    SignMode, VerifyMode,
+    AeadAlgorithm, AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaDeviceType, AtcaIfaceCfg, AtcaIfaceType,
+    AtcaSha256Ctx, AtcaSlot, AtcaStatus, AteccDeviceTrait, CipherAlgorithm, EcdhOutput, GenDigZone,
+    InfoCmdType, IoProtectionSession, KdfAlgorithm, KdfSource, KdfTarget, KeyType,
+    KeyWrapAlgorithm, NonceTarget, OutputProtectionState, SignMode, SignatureFormat, TempKeyState,
+    TlsFinishedLabel, VerifyMode,
 };
 
-use super::{ATCA_AES_DATA_SIZE, ATCA_RANDOM_BUFFER_SIZE, ATCA_SERIAL_NUM_SIZE};
+use super::{convert_signature, private_key_from_der, private_key_from_pem};
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_AES_KEY_SIZE, ATCA_ECDH_PMS_SIZE, ATCA_ECIES_OVERHEAD, ATCA_KEY_SIZE,
+    ATCA_RANDOM_BUFFER_SIZE, ATCA_SERIAL_NUM_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE,
+};
 use rand::{distributions::Standard, Rng};
 
+mod script;
+
+use script::TestOutput;
+pub use script::{AtcaTestFault, AtcaTestResponse, AtcaTestScript};
+
 pub struct AteccDevice {
     dev_type: AtcaDeviceType,
+    script: AtcaTestScript,
 }
 
 // Software ATECC implements following functions:
@@ -25,17 +38,22 @@ pub struct AteccDevice {
 // - always fails
 // - always succeed
 // - fail if they are not implemented but only mocked.
+// A command with a response waiting in the script of the device returns that response instead.
 impl Default for AteccDevice {
     fn default() -> AteccDevice {
         AteccDevice {
             dev_type: AtcaDeviceType::AtcaTestDevNone,
+            script: AtcaTestScript::new(),
         }
     }
 }
 
 impl AteccDeviceTrait for AteccDevice {
     fn random(&self, rand_out: &mut Vec<u8>) -> AtcaStatus {
-        let vector: Vec<u8> = rand::thread_rng()
+        if let Some(status) = self.scripted_status("random", Some(rand_out)) {
+            return status;
+        }
+        let vector: Vec<u8> = test_rng()
             .sample_iter(Standard)
             .take(ATCA_RANDOM_BUFFER_SIZE)
             .collect();
@@ -49,44 +67,245 @@ impl AteccDeviceTrait for AteccDevice {
         }
     }
     /// Request ATECC to compute a message hash (SHA256)
-    fn sha(&self, _message: Vec<u8>, _digest: &mut Vec<u8>) -> AtcaStatus {
+    fn sha(&self, _message: Vec<u8>, digest: &mut Vec<u8>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("sha", Some(digest)) {
+            return status;
+        }
         self.default_dev_status()
     }
+    /// Starts a SHA256 digest of a message that is fed in several parts
+    fn sha_start(&self) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        if let Some(result) = self.scripted("sha_start") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(AtcaSha256Ctx::default()),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Feeds the next part of a message into a SHA256 digest calculation
+    fn sha_update(&self, ctx: AtcaSha256Ctx, _data: &[u8]) -> Result<AtcaSha256Ctx, AtcaStatus> {
+        if let Some(result) = self.scripted("sha_update") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Completes a SHA256 digest calculation and returns the digest
+    fn sha_finish(&self, _ctx: AtcaSha256Ctx) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("sha_finish") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; ATCA_SHA2_256_DIGEST_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to compute HMAC-SHA256 of a message with the key stored in a slot
+    fn sha_hmac(&self, _message: &[u8], _slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("sha_hmac") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; ATCA_SHA2_256_DIGEST_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
     /// Execute a Nonce command in pass-through mode to load one of the
     /// device's internal buffers with a fixed value.
     /// For the ATECC608A, available targets are TempKey (32 or 64 bytes), Message
     /// Digest Buffer (32 or 64 bytes), or the Alternate Key Buffer (32 bytes). For
     /// all other devices, only TempKey (32 bytes) is available.
     fn nonce(&self, _target: NonceTarget, _data: &[u8]) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("nonce", None) {
+            return status;
+        }
         self.default_dev_status()
     }
     /// Execute a Nonce command to generate a random nonce combining a host
     /// nonce and a device random number.
-    fn nonce_rand(&self, _host_nonce: &[u8], _rand_out: &mut Vec<u8>) -> AtcaStatus {
+    fn nonce_rand(&self, _host_nonce: &[u8], rand_out: &mut Vec<u8>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("nonce_rand", Some(rand_out)) {
+            return status;
+        }
         self.default_dev_status()
     }
+    /// Execute a GenDig command to combine TempKey with the key stored in a slot
+    /// (GenDigZone::Data) or with 32 bytes of 'other_data' (GenDigZone::SharedNonce).
+    fn gen_dig(&self, _zone: GenDigZone, _slot_id: u8, _other_data: &[u8]) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("gen_dig", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// There is no TempKey to keep track of
+    fn tempkey_state(&self) -> TempKeyState {
+        TempKeyState::default()
+    }
+    /// Execute a MAC command over the key stored in a slot and a challenge or TempKey
+    fn mac(&self, _slot_id: u8, _challenge: Option<&[u8]>) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("mac") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; ATCA_SHA2_256_DIGEST_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Execute a CheckMac command to verify a MAC calculated by the host
+    fn check_mac(
+        &self,
+        _slot_id: u8,
+        _challenge: Option<&[u8]>,
+        _response: &[u8],
+        _other_data: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("check_mac") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to read the value of one of the two monotonic counters
+    fn counter_read(&self, _counter_id: u8) -> Result<u32, AtcaStatus> {
+        if let Some(result) = self.scripted("counter_read") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(0),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to increment one of the two monotonic counters
+    fn counter_increment(&self, _counter_id: u8) -> Result<u32, AtcaStatus> {
+        if let Some(result) = self.scripted("counter_increment") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(1),
+            _ => Err(self.default_dev_status()),
+        }
+    }
     /// Request ATECC to generate a cryptographic key
     fn gen_key(&self, _key_type: KeyType, _slot_id: u8) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("gen_key", None) {
+            return status;
+        }
         self.default_dev_status()
     }
     /// Request ATECC to import a cryptographic key
     fn import_key(&self, _key_type: KeyType, _key_data: &[u8], _slot_number: u8) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("import_key", None) {
+            return status;
+        }
         self.default_dev_status()
     }
+    /// Request ATECC to import a P256 private key encoded in DER (PKCS#8 or SEC1)
+    fn import_private_key_der(&self, key_data: &[u8], _slot_id: u8) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("import_private_key_der", None) {
+            return status;
+        }
+        match private_key_from_der(key_data) {
+            Ok(_) => self.default_dev_status(),
+            Err(err) => err,
+        }
+    }
+    /// Request ATECC to import a P256 private key from a PEM file (PKCS#8 or SEC1)
+    fn import_private_key_pem(&self, key_data: &str, _slot_id: u8) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("import_private_key_pem", None) {
+            return status;
+        }
+        match private_key_from_pem(key_data) {
+            Ok(_) => self.default_dev_status(),
+            Err(err) => err,
+        }
+    }
     /// Request ATECC to export a cryptographic key
-    fn export_key(&self, _key_type: KeyType, _key_data: &mut Vec<u8>, _slot_id: u8) -> AtcaStatus {
+    fn export_key(&self, _key_type: KeyType, key_data: &mut Vec<u8>, _slot_id: u8) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("export_key", Some(key_data)) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Request ATECC to write data to a slot, starting at the given byte offset
+    fn write_slot_data(&self, _slot_id: u8, _offset: usize, _data: &[u8]) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("write_slot_data", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Request ATECC to read data from a slot, starting at the given byte offset
+    fn read_slot_data(
+        &self,
+        _slot_id: u8,
+        _offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("read_slot_data") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; len]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to write the OTP zone, starting at the given byte offset
+    fn write_otp_zone(&self, _offset: usize, _data: &[u8]) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("write_otp_zone", None) {
+            return status;
+        }
         self.default_dev_status()
     }
+    /// Request ATECC to read the OTP zone, starting at the given byte offset
+    fn read_otp_zone(&self, _offset: usize, len: usize) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("read_otp_zone") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; len]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
     /// Depending on the socket configuration, this function calculates
     /// public key based on an existing private key in the socket
     /// or exports the public key directly
-    fn get_public_key(&self, _slot_id: u8, _public_key: &mut Vec<u8>) -> AtcaStatus {
+    fn get_public_key(&self, _slot_id: u8, public_key: &mut Vec<u8>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("get_public_key", Some(public_key)) {
+            return status;
+        }
         self.default_dev_status()
     }
     /// Request ATECC to generate an ECDSA signature
-    fn sign_hash(&self, _mode: SignMode, _slot_id: u8, _signature: &mut Vec<u8>) -> AtcaStatus {
+    fn sign_hash(&self, _mode: SignMode, _slot_id: u8, signature: &mut Vec<u8>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("sign_hash", Some(signature)) {
+            return status;
+        }
         self.default_dev_status()
     }
+    /// Request ATECC to hash a message of any length (SHA256) and sign the digest
+    fn sign_message(&self, _slot_id: u8, _message: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("sign_message") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; ATCA_SIG_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to execute a Sign command in the given mode
+    fn sign_base(&self, _mode: u8, _slot_id: u8) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("sign_base") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; ATCA_SIG_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
     /// Request ATECC to verify ECDSA signature
     fn verify_hash(
         &self,
@@ -94,18 +313,149 @@ impl AteccDeviceTrait for AteccDevice {
         _hash: &[u8],
         _signature: &[u8],
     ) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("verify_hash") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to generate an ECDSA signature and return it in the requested format
+    fn sign_hash_formatted(
+        &self,
+        _mode: SignMode,
+        _slot_id: u8,
+        format: SignatureFormat,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("sign_hash_formatted") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => {
+                convert_signature(&[0x01; ATCA_SIG_SIZE], SignatureFormat::Raw, format)
+            }
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to verify an ECDSA signature given in the specified format
+    fn verify_hash_formatted(
+        &self,
+        _mode: VerifyMode,
+        _hash: &[u8],
+        signature: &[u8],
+        format: SignatureFormat,
+    ) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("verify_hash_formatted") {
+            return result;
+        }
+        convert_signature(signature, format, SignatureFormat::Raw)?;
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to perform ECDH key agreement, honoring the output protection settings
+    fn ecdh(
+        &self,
+        _slot_id: u8,
+        _public_key: &[u8],
+        _session: Option<&IoProtectionSession>,
+    ) -> Result<EcdhOutput, AtcaStatus> {
+        if let Some(result) = self.scripted("ecdh") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => {
+                Ok(EcdhOutput::PreMasterSecret(vec![0; ATCA_ECDH_PMS_SIZE]))
+            }
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC608x to derive a key with the KDF command
+    fn kdf(
+        &self,
+        _algorithm: KdfAlgorithm,
+        _source: KdfSource,
+        _target: KdfTarget,
+        _message: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("kdf") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(Vec::new()),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC608x to open an IO protection session with the given IO protection key
+    fn open_io_protection_session(&self, io_key: &[u8]) -> Result<IoProtectionSession, AtcaStatus> {
+        if let Some(result) = self.scripted("open_io_protection_session") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess if io_key.len() == ATCA_KEY_SIZE => {
+                Ok(IoProtectionSession::new(0, io_key))
+            }
+            AtcaDeviceType::AtcaTestDevSuccess => Err(AtcaStatus::AtcaInvalidSize),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to perform ECDH key agreement with the premaster secret
+    /// transmitted encrypted with the IO protection key
+    fn ecdh_io_protected(
+        &self,
+        _session: &IoProtectionSession,
+        _slot_id: u8,
+        _public_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("ecdh_io_protected") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; ATCA_ECDH_PMS_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Request ATECC to verify ECDSA signature, with the result
+    /// authenticated by a MAC calculated with the IO protection key
+    fn verify_hash_io_protected(
+        &self,
+        _session: &IoProtectionSession,
+        _mode: VerifyMode,
+        _hash: &[u8],
+        _signature: &[u8],
+    ) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("verify_hash_io_protected") {
+            return result;
+        }
         match self.dev_type {
             AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
             _ => Err(self.default_dev_status()),
         }
     }
+    /// Request ATECC to import an AES key to a slot protected by the IO protection key
+    fn import_aes_key_io_protected(
+        &self,
+        _session: &IoProtectionSession,
+        _key: &[u8],
+        _slot_id: u8,
+    ) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("import_aes_key_io_protected", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
     /// Data encryption function in AES unauthenticated cipher alhorithms modes
     fn cipher_encrypt(
         &self,
         _algorithm: CipherAlgorithm,
         _slot_id: u8,
-        _data: &mut Vec<u8>,
+        data: &mut Vec<u8>,
     ) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("cipher_encrypt", Some(data)) {
+            return status;
+        }
         self.default_dev_status()
     }
     /// Data decryption function in AES unauthenticated cipher alhorithms modes
@@ -113,8 +463,11 @@ impl AteccDeviceTrait for AteccDevice {
         &self,
         _algorithm: CipherAlgorithm,
         _slot_id: u8,
-        _data: &mut Vec<u8>,
+        data: &mut Vec<u8>,
     ) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("cipher_decrypt", Some(data)) {
+            return status;
+        }
         self.default_dev_status()
     }
     /// Data encryption function in AES AEAD (authenticated encryption with associated data) modes
@@ -124,6 +477,9 @@ impl AteccDeviceTrait for AteccDevice {
         _slot_id: u8,
         _data: &mut Vec<u8>,
     ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("aead_encrypt") {
+            return result;
+        }
         match self.dev_type {
             AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; ATCA_AES_DATA_SIZE]),
             _ => Err(self.default_dev_status()),
@@ -136,11 +492,267 @@ impl AteccDeviceTrait for AteccDevice {
         _slot_id: u8,
         _data: &mut Vec<u8>,
     ) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("aead_decrypt") {
+            return result;
+        }
         match self.dev_type {
             AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
             _ => Err(self.default_dev_status()),
         }
     }
+    /// Starts a streaming AES GCM encryption or decryption
+    fn gcm_init(
+        &self,
+        _slot_id: u8,
+        _nonce: &[u8],
+        _key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("gcm_init") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(AtcaAesGcmCtx::default()),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Feeds the next chunk of additional authenticated data into a streaming AES GCM operation
+    fn gcm_update_aad(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        _data: &[u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("gcm_update_aad") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Encrypts (in place) the next chunk of a streaming AES GCM encryption
+    fn gcm_encrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        _data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("gcm_encrypt_update") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Decrypts (in place) the next chunk of a streaming AES GCM decryption
+    fn gcm_decrypt_update(
+        &self,
+        ctx: AtcaAesGcmCtx,
+        _data: &mut [u8],
+    ) -> Result<AtcaAesGcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("gcm_decrypt_update") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Completes a streaming AES GCM encryption and returns the tag
+    fn gcm_encrypt_finish(
+        &self,
+        _ctx: AtcaAesGcmCtx,
+        tag_length: u8,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("gcm_encrypt_finish") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; tag_length as usize]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Completes a streaming AES GCM decryption and verifies the tag
+    fn gcm_decrypt_finish(&self, _ctx: AtcaAesGcmCtx, _tag: &[u8]) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("gcm_decrypt_finish") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Starts a streaming AES CCM encryption or decryption
+    fn ccm_init(
+        &self,
+        _slot_id: u8,
+        _nonce: &[u8],
+        _key: Option<[u8; ATCA_AES_KEY_SIZE]>,
+        _aad_length: usize,
+        text_length: usize,
+        tag_length: u8,
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("ccm_init") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(AtcaAesCcmCtx {
+                m: tag_length.saturating_sub(2) / 2,
+                text_size: text_length,
+                ..Default::default()
+            }),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Feeds the next chunk of additional authenticated data into a streaming AES CCM operation
+    fn ccm_update_aad(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        _data: &[u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("ccm_update_aad") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Encrypts (in place) the next chunk of a streaming AES CCM encryption
+    fn ccm_encrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        _data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("ccm_encrypt_update") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Decrypts (in place) the next chunk of a streaming AES CCM decryption
+    fn ccm_decrypt_update(
+        &self,
+        ctx: AtcaAesCcmCtx,
+        _data: &mut [u8],
+    ) -> Result<AtcaAesCcmCtx, AtcaStatus> {
+        if let Some(result) = self.scripted("ccm_decrypt_update") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(ctx),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Completes a streaming AES CCM encryption and returns the tag
+    fn ccm_encrypt_finish(&self, ctx: AtcaAesCcmCtx) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("ccm_encrypt_finish") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; (ctx.m as usize * 2) + 2]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Completes a streaming AES CCM decryption and verifies the tag
+    fn ccm_decrypt_finish(&self, _ctx: AtcaAesCcmCtx, _tag: &[u8]) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("ccm_decrypt_finish") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Derives TLS 1.2 session keys
+    fn tls_derive_keys(
+        &self,
+        _slot_id: u8,
+        _server_public_key: &[u8],
+        _client_random: &[u8],
+        _server_random: &[u8],
+        key_block_length: usize,
+        _session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("tls_derive_keys") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; key_block_length]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Calculates verify_data of a TLS 1.2 Finished message
+    fn tls_verify_data(
+        &self,
+        _label: TlsFinishedLabel,
+        _handshake_hash: &[u8],
+        _session: Option<&IoProtectionSession>,
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("tls_verify_data") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0x00; 12]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Encrypts data for the owner of the given public key
+    fn ecies_encrypt(&self, _public_key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("ecies_encrypt") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => {
+                Ok(vec![0x00; plaintext.len() + ATCA_ECIES_OVERHEAD])
+            }
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Decrypts data produced by ecies_encrypt()
+    fn ecies_decrypt(&self, _slot_id: u8, data: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("ecies_decrypt") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => match data.len() >= ATCA_ECIES_OVERHEAD {
+                true => Ok(vec![0x00; data.len() - ATCA_ECIES_OVERHEAD]),
+                false => Err(AtcaStatus::AtcaInvalidSize),
+            },
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Wrap (encrypt) a key with the AES key stored in the given slot
+    fn wrap_key(
+        &self,
+        _algorithm: KeyWrapAlgorithm,
+        _slot_id: u8,
+        key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("wrap_key") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; key.len() + 8]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Unwrap (decrypt) a key with the AES key stored in the given slot
+    fn unwrap_key(
+        &self,
+        _algorithm: KeyWrapAlgorithm,
+        _slot_id: u8,
+        wrapped_key: &[u8],
+    ) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("unwrap_key") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(vec![0; wrapped_key.len().saturating_sub(8)]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
     /// Request ATECC to return own device type
     fn get_device_type(&self) -> AtcaDeviceType {
         self.dev_type
@@ -148,12 +760,10 @@ impl AteccDeviceTrait for AteccDevice {
     /// Request ATECC to check if its configuration is locked.
     /// If true, a chip can be used for cryptographic operations
     fn is_configuration_locked(&self) -> bool {
-        match self.dev_type {
-            AtcaDeviceType::AtcaTestDevFailUnimplemented | AtcaDeviceType::AtcaTestDevSuccess => {
-                true
-            }
-            _ => false,
-        }
+        matches!(
+            self.dev_type,
+            AtcaDeviceType::AtcaTestDevFailUnimplemented | AtcaDeviceType::AtcaTestDevSuccess
+        )
     }
     /// Request ATECC to check if its Data Zone is locked.
     /// If true, a chip can be used for cryptographic operations
@@ -170,8 +780,41 @@ impl AteccDeviceTrait for AteccDevice {
             _ => AtcaStatus::AtcaUnimplemented,
         }
     }
+    /// Request ATECC to read and return own configuration zone.
+    /// Note: this function returns raw data, function get_config(..) implements a more
+    /// structured return value.
+    fn read_config_zone(&self, config_data: &mut Vec<u8>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("read_config_zone", Some(config_data)) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Request ATECC to write its whole configuration zone
+    fn write_config_zone(&self, _config_data: &[u8]) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("write_config_zone", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Request ATECC to lock its configuration zone
+    fn lock_config_zone(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("lock_config_zone", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Request ATECC to lock its data zone
+    fn lock_data_zone(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("lock_data_zone", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
     /// Command accesses some static or dynamic information from the ATECC chip
     fn info_cmd(&self, _command: InfoCmdType) -> Result<Vec<u8>, AtcaStatus> {
+        if let Some(result) = self.scripted("info_cmd") {
+            return result;
+        }
         match self.dev_type {
             AtcaDeviceType::AtcaTestDevSuccess => Ok(Vec::new()),
             _ => Err(self.default_dev_status()),
@@ -179,10 +822,23 @@ impl AteccDeviceTrait for AteccDevice {
     }
 
     fn add_access_key(&self, _slot_id: u8, _encryption_key: &[u8]) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("add_access_key", None) {
+            return status;
+        }
         self.default_dev_status()
     }
 
     fn flush_access_keys(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("flush_access_keys", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+
+    fn set_host_nonce(&self, _num_in: Option<&[u8]>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("set_host_nonce", None) {
+            return status;
+        }
         self.default_dev_status()
     }
 
@@ -216,8 +872,98 @@ impl AteccDeviceTrait for AteccDevice {
         OutputProtectionState::ClearTextAllowed
     }
 
+    fn aes_encrypt_block(
+        &self,
+        _key_id: u16,
+        _key_block: u8,
+        _input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        if let Some(result) = self.scripted("aes_encrypt_block") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok([0x00; ATCA_AES_DATA_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    fn aes_decrypt_block(
+        &self,
+        _key_id: u16,
+        _key_block: u8,
+        _input: &[u8],
+    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
+        if let Some(result) = self.scripted("aes_decrypt_block") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok([0x00; ATCA_AES_DATA_SIZE]),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+
+    /// Request ATECC608x to run the SelfTest command
+    fn self_test(&self) -> Result<bool, AtcaStatus> {
+        if let Some(result) = self.scripted("self_test") {
+            return result;
+        }
+        match self.dev_type {
+            AtcaDeviceType::AtcaTestDevSuccess => Ok(true),
+            _ => Err(self.default_dev_status()),
+        }
+    }
+    /// Wakes the chip up
+    fn wake(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("wake", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Puts the chip into the idle mode
+    fn idle(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("idle", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Puts the chip into the sleep mode
+    fn sleep(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("sleep", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+
+    /// The test device is never disconnected
+    fn is_disconnected(&self) -> bool {
+        false
+    }
+    /// Re-initializes the communication with the chip
+    fn reconnect(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("reconnect", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Reads again the state cached at initialization
+    fn refresh(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("refresh", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+    /// Reads again the lock state of the zones
+    fn refresh_lock_state(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("refresh_lock_state", None) {
+            return status;
+        }
+        self.default_dev_status()
+    }
+
     /// ATECC device instance destructor
     fn release(&self) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("release", None) {
+            return status;
+        }
         match self.dev_type {
             AtcaDeviceType::AtcaTestDevFailUnimplemented | AtcaDeviceType::AtcaTestDevSuccess => {
                 AtcaStatus::AtcaSuccess
@@ -243,16 +989,12 @@ impl AteccDeviceTrait for AteccDevice {
         data: &mut Vec<u8>,
         _len: u8,
     ) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("read_zone", Some(data)) {
+            return status;
+        }
         data.clear();
         self.default_dev_status()
     }
-    /// Request ATECC to read and return own configuration zone.
-    /// Note: this function returns raw data, function get_config(..) implements a more
-    /// structured return value.
-    #[cfg(test)]
-    fn read_config_zone(&self, _config_data: &mut Vec<u8>) -> AtcaStatus {
-        self.default_dev_status()
-    }
     /// Compare internal config zone contents vs. config_data.
     /// Diagnostic function.
     #[cfg(test)]
@@ -263,32 +1005,11 @@ impl AteccDeviceTrait for AteccDevice {
         }
     }
     #[cfg(test)]
-    fn get_access_key(&self, _slot_id: u8, _key: &mut Vec<u8>) -> AtcaStatus {
-        self.default_dev_status()
-    }
-    #[cfg(test)]
-    fn aes_encrypt_block(
-        &self,
-        _key_id: u16,
-        _key_block: u8,
-        _input: &[u8],
-    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
-        match self.dev_type {
-            AtcaDeviceType::AtcaTestDevSuccess => Ok([0x00; ATCA_AES_DATA_SIZE]),
-            _ => Err(self.default_dev_status()),
-        }
-    }
-    #[cfg(test)]
-    fn aes_decrypt_block(
-        &self,
-        _key_id: u16,
-        _key_block: u8,
-        _input: &[u8],
-    ) -> Result<[u8; ATCA_AES_DATA_SIZE], AtcaStatus> {
-        match self.dev_type {
-            AtcaDeviceType::AtcaTestDevSuccess => Ok([0x00; ATCA_AES_DATA_SIZE]),
-            _ => Err(self.default_dev_status()),
+    fn get_access_key(&self, _slot_id: u8, key: &mut Vec<u8>) -> AtcaStatus {
+        if let Some(status) = self.scripted_status("get_access_key", Some(key)) {
+            return status;
         }
+        self.default_dev_status()
     }
     #[cfg(test)]
     fn aes_ctr_init(
@@ -333,13 +1054,23 @@ impl AteccDeviceTrait for AteccDevice {
 
 impl AteccDevice {
     pub fn new(r_iface_cfg: AtcaIfaceCfg) -> Result<AteccDevice, String> {
-        let mut device = AteccDevice::default();
+        AteccDevice::with_script(r_iface_cfg, AtcaTestScript::new())
+    }
+    /// Creates a test device whose commands first take their responses from the script
+    pub fn with_script(
+        r_iface_cfg: AtcaIfaceCfg,
+        script: AtcaTestScript,
+    ) -> Result<AteccDevice, String> {
+        let mut device = AteccDevice {
+            script,
+            ..Default::default()
+        };
         match r_iface_cfg.iface_type {
             AtcaIfaceType::AtcaTestIface => (),
             _ => {
                 let err = format!(
                     "Software implementation of an AteccDevice does not support interface {}",
-                    r_iface_cfg.iface_type.to_string()
+                    r_iface_cfg.iface_type
                 );
                 return Err(err);
             }
@@ -353,7 +1084,7 @@ impl AteccDevice {
             _ => {
                 let err = format!(
                     "Software implementation of an AteccDevice does not support interface {}",
-                    r_iface_cfg.devtype.to_string()
+                    r_iface_cfg.devtype
                 );
                 return Err(err);
             }
@@ -366,4 +1097,33 @@ impl AteccDevice {
             _ => AtcaStatus::AtcaUnimplemented,
         }
     }
+    /// Result of a command taken from the script, if a response to it is waiting there
+    fn scripted<T: TestOutput>(&self, command: &str) -> Option<Result<T, AtcaStatus>> {
+        self.script.next_response(command).map(T::from_response)
+    }
+    /// Status of a command taken from the script, if a response to it is waiting there.
+    /// Data of the response is returned in 'output', when the command has one
+    fn scripted_status(&self, command: &str, output: Option<&mut Vec<u8>>) -> Option<AtcaStatus> {
+        let status = match self.scripted::<Vec<u8>>(command)? {
+            Ok(data) => {
+                if let Some(output) = output {
+                    *output = data;
+                }
+                AtcaStatus::AtcaSuccess
+            }
+            Err(err) => err,
+        };
+        Some(status)
+    }
+}
+
+#[cfg(feature = "std")]
+fn test_rng() -> rand::rngs::ThreadRng {
+    rand::thread_rng()
+}
+
+/// Without std there is no entropy source, the test device returns a counter sequence
+#[cfg(not(feature = "std"))]
+fn test_rng() -> rand::rngs::mock::StepRng {
+    rand::rngs::mock::StepRng::new(0, 1)
 }
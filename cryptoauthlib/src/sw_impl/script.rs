@@ -0,0 +1,196 @@
+use alloc::{
+    collections::{BTreeMap, VecDeque},
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::convert::TryFrom;
+
+use super::super::sync::Mutex;
+use super::{AtcaAesCcmCtx, AtcaAesGcmCtx, AtcaSha256Ctx, AtcaStatus, EcdhOutput};
+use super::{IoProtectionSession, ATCA_AES_DATA_SIZE};
+
+/// A fault injected into a command of the test device. Each one makes the command
+/// fail with the status that the hardware backend reports for it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AtcaTestFault {
+    /// The chip does not wake up (AtcaWakeFailed)
+    WakeFailure,
+    /// The response of the chip is received with a wrong CRC (AtcaRxCrcError)
+    CrcError,
+    /// The watchdog puts the chip to sleep before the command completes,
+    /// also when the command is retried (AtcaWatchdogExpired)
+    WatchdogExpired,
+    /// The chip reports an ECC fault, e.g. a key that is not valid (AtcaStatusEcc)
+    EccFault,
+}
+
+impl From<AtcaTestFault> for AtcaStatus {
+    fn from(fault: AtcaTestFault) -> AtcaStatus {
+        match fault {
+            AtcaTestFault::WakeFailure => AtcaStatus::AtcaWakeFailed,
+            AtcaTestFault::CrcError => AtcaStatus::AtcaRxCrcError,
+            AtcaTestFault::WatchdogExpired => AtcaStatus::AtcaWatchdogExpired,
+            AtcaTestFault::EccFault => AtcaStatus::AtcaStatusEcc,
+        }
+    }
+}
+
+/// A canned response of the test device to a single command
+#[derive(Clone, Debug, PartialEq)]
+pub enum AtcaTestResponse {
+    /// The command succeeds and returns the given bytes: random numbers, a digest,
+    /// a signature, slot data... Commands without such an output just succeed
+    Data(Vec<u8>),
+    /// The verification command succeeds with the given result
+    Verified(bool),
+    /// The command fails with the given status
+    Error(AtcaStatus),
+    /// The command fails because of the given fault
+    Fault(AtcaTestFault),
+}
+
+/// Script of the test device: queues of responses to the next calls of each command.
+/// A command is named after its function in AteccDeviceTrait, e.g. "sign_hash".
+/// When the queue of a command is empty, the command behaves as the device type tells.
+/// Clones of a script share the queues, so a test can keep one and add responses
+/// while the device is in use
+#[derive(Clone)]
+pub struct AtcaTestScript {
+    responses: Arc<Mutex<BTreeMap<String, VecDeque<AtcaTestResponse>>>>,
+}
+
+impl Default for AtcaTestScript {
+    fn default() -> AtcaTestScript {
+        AtcaTestScript::new()
+    }
+}
+
+impl AtcaTestScript {
+    /// Creates an empty script
+    pub fn new() -> AtcaTestScript {
+        AtcaTestScript {
+            responses: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    } // AtcaTestScript::new()
+
+    /// Adds a response to the queue of the given command
+    pub fn enqueue_response(&self, command: &str, response: AtcaTestResponse) {
+        self.responses
+            .lock()
+            .expect("Could not lock 'responses' mutex")
+            .entry(command.to_string())
+            .or_default()
+            .push_back(response);
+    } // AtcaTestScript::enqueue_response()
+
+    /// Makes the next call of the given command fail because of the fault
+    pub fn inject_fault(&self, command: &str, fault: AtcaTestFault) {
+        self.enqueue_response(command, AtcaTestResponse::Fault(fault));
+    } // AtcaTestScript::inject_fault()
+
+    /// Number of responses still waiting for the given command
+    pub fn pending(&self, command: &str) -> usize {
+        self.responses
+            .lock()
+            .expect("Could not lock 'responses' mutex")
+            .get(command)
+            .map_or(0, |queue| queue.len())
+    } // AtcaTestScript::pending()
+
+    /// Removes all the responses not used yet
+    pub fn clear(&self) {
+        self.responses
+            .lock()
+            .expect("Could not lock 'responses' mutex")
+            .clear();
+    } // AtcaTestScript::clear()
+
+    /// Takes the next response to the given command, if there is any
+    pub(crate) fn next_response(&self, command: &str) -> Option<AtcaTestResponse> {
+        self.responses
+            .lock()
+            .expect("Could not lock 'responses' mutex")
+            .get_mut(command)?
+            .pop_front()
+    } // AtcaTestScript::next_response()
+}
+
+/// Output of a command that can be given by a canned response.
+/// A response that does not fit the command makes it fail with AtcaBadParam
+pub(crate) trait TestOutput: Sized {
+    fn from_data(_data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Err(AtcaStatus::AtcaBadParam)
+    }
+
+    fn from_verified(_verified: bool) -> Result<Self, AtcaStatus> {
+        Err(AtcaStatus::AtcaBadParam)
+    }
+
+    fn from_response(response: AtcaTestResponse) -> Result<Self, AtcaStatus> {
+        match response {
+            AtcaTestResponse::Data(data) => Self::from_data(data),
+            AtcaTestResponse::Verified(verified) => Self::from_verified(verified),
+            AtcaTestResponse::Error(status) => Err(status),
+            AtcaTestResponse::Fault(fault) => Err(fault.into()),
+        }
+    }
+}
+
+impl TestOutput for Vec<u8> {
+    fn from_data(data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Ok(data)
+    }
+}
+
+/// A counter value is given as 4 bytes, big-endian
+impl TestOutput for u32 {
+    fn from_data(data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        <[u8; 4]>::try_from(data.as_slice())
+            .map(u32::from_be_bytes)
+            .map_err(|_| AtcaStatus::AtcaInvalidSize)
+    }
+}
+
+impl TestOutput for bool {
+    fn from_verified(verified: bool) -> Result<Self, AtcaStatus> {
+        Ok(verified)
+    }
+}
+
+impl TestOutput for [u8; ATCA_AES_DATA_SIZE] {
+    fn from_data(data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        <[u8; ATCA_AES_DATA_SIZE]>::try_from(data.as_slice())
+            .map_err(|_| AtcaStatus::AtcaInvalidSize)
+    }
+}
+
+impl TestOutput for EcdhOutput {
+    fn from_data(data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Ok(EcdhOutput::PreMasterSecret(data))
+    }
+}
+
+impl TestOutput for IoProtectionSession {
+    fn from_data(data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Ok(IoProtectionSession::new(0, &data))
+    }
+}
+
+impl TestOutput for AtcaSha256Ctx {
+    fn from_data(_data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Ok(AtcaSha256Ctx::default())
+    }
+}
+
+impl TestOutput for AtcaAesGcmCtx {
+    fn from_data(_data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Ok(AtcaAesGcmCtx::default())
+    }
+}
+
+impl TestOutput for AtcaAesCcmCtx {
+    fn from_data(_data: Vec<u8>) -> Result<Self, AtcaStatus> {
+        Ok(AtcaAesCcmCtx::default())
+    }
+}
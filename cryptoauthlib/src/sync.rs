@@ -0,0 +1,385 @@
+// Locks of the crate. With std these are the std::sync ones. Bare-metal firmware
+// has neither threads nor std, there the lock is taken and given back
+// in critical sections provided by the critical-section crate.
+
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(feature = "std")]
+pub(crate) use self::fair::{wait_limit, with_wait_limit, FairMutex, FairMutexGuard, WaitLimit};
+#[cfg(feature = "std")]
+pub(crate) use std::sync::Mutex;
+#[cfg(feature = "rustcrypto-backend")]
+pub(crate) use std::sync::MutexGuard;
+
+// There is only one thread of execution on bare metal, nobody to be starved
+#[cfg(not(feature = "std"))]
+pub(crate) use self::bare_metal::Mutex;
+#[cfg(not(feature = "std"))]
+pub(crate) use self::bare_metal::{Mutex as FairMutex, MutexGuard as FairMutexGuard};
+
+/// A value set once: at construction time or by the first thread that needs it
+pub(crate) struct OnceCell<T> {
+    lock: Mutex<()>,
+    is_set: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+}
+
+// The value is written only once, under the lock and before 'is_set' is set,
+// it is only read afterwards
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+unsafe impl<T: Send> Send for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    pub(crate) const fn new() -> OnceCell<T> {
+        OnceCell {
+            lock: Mutex::new(()),
+            is_set: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<&T> {
+        match self.is_set.load(Ordering::Acquire) {
+            true => unsafe { (*self.value.get()).as_ref() },
+            false => None,
+        }
+    }
+
+    pub(crate) fn set(&mut self, value: T) {
+        *self.value.get_mut() = Some(value);
+        *self.is_set.get_mut() = true;
+    }
+
+    /// Returns the value, calling 'init' to get it if it is not set yet.
+    /// When 'init' fails, the cell stays empty and the error is returned
+    pub(crate) fn get_or_try_init<E, F>(&self, init: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+        // A poisoned lock holds the guard all the same
+        let _guard = self.lock.lock();
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+        let value = init()?;
+        unsafe { *self.value.get() = Some(value) };
+        self.is_set.store(true, Ordering::Release);
+        Ok(self.get().expect("OnceCell value has just been set"))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OnceCell").field(&self.get()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+mod fair {
+    use std::cell::RefCell;
+    use std::cmp::min;
+    use std::fmt;
+    use std::mem;
+    use std::ops::{Deref, DerefMut};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Condvar, LockResult, Mutex, MutexGuard, PoisonError};
+    use std::time::{Duration, Instant};
+
+    use super::super::AtcaStatus;
+
+    /// Interval between checks of the cancellation flag of a bounded wait
+    const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+    /// Bound on the waits for a lock: a deadline and a flag that cancels them
+    #[derive(Clone, Debug, Default)]
+    pub(crate) struct WaitLimit {
+        pub(crate) deadline: Option<Instant>,
+        pub(crate) cancelled: Option<Arc<AtomicBool>>,
+    }
+
+    impl WaitLimit {
+        pub(crate) fn is_unbounded(&self) -> bool {
+            self.deadline.is_none() && self.cancelled.is_none()
+        }
+
+        pub(crate) fn is_cancelled(&self) -> bool {
+            match &self.cancelled {
+                Some(cancelled) => cancelled.load(Ordering::SeqCst),
+                None => false,
+            }
+        }
+
+        pub(crate) fn is_expired(&self) -> bool {
+            match self.deadline {
+                Some(deadline) => Instant::now() >= deadline,
+                None => false,
+            }
+        }
+
+        /// Fails with AtcaCancelled or AtcaTimeout once the wait is to be given up
+        pub(crate) fn check(&self) -> Result<(), AtcaStatus> {
+            if self.is_cancelled() {
+                return Err(AtcaStatus::AtcaCancelled);
+            }
+            if self.is_expired() {
+                return Err(AtcaStatus::AtcaTimeout);
+            }
+            Ok(())
+        }
+    }
+
+    thread_local! {
+        /// Limit of the waits of the calling thread, see with_wait_limit()
+        static WAIT_LIMIT: RefCell<WaitLimit> = RefCell::new(WaitLimit::default());
+    }
+
+    /// Runs 'operation' with the limit applying to the waits for the locks taken
+    /// by FairMutex::lock_bounded() on the calling thread, e.g. the atcab_* API lock
+    /// taken deep within a command of AteccHandle
+    pub(crate) fn with_wait_limit<T, F>(limit: WaitLimit, operation: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        // Puts the previous limit back, also when 'operation' panics
+        struct Restore(WaitLimit);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = mem::take(&mut self.0);
+                WAIT_LIMIT.with(|limit| *limit.borrow_mut() = previous);
+            }
+        }
+
+        let _restore = Restore(WAIT_LIMIT.with(|current| current.replace(limit)));
+        operation()
+    }
+
+    /// Limit of the waits of the calling thread, unbounded outside of with_wait_limit()
+    pub(crate) fn wait_limit() -> WaitLimit {
+        WAIT_LIMIT.with(|limit| limit.borrow().clone())
+    }
+
+    /// Tickets of the threads waiting for the lock
+    #[derive(Debug)]
+    struct Queue {
+        next: usize,
+        serving: usize,
+        /// Tickets of the threads that gave up waiting, skipped when their turn comes
+        abandoned: Vec<usize>,
+    }
+
+    impl Queue {
+        fn serve_next(&mut self) {
+            self.serving = self.serving.wrapping_add(1);
+            while let Some(idx) = self.abandoned.iter().position(|t| *t == self.serving) {
+                self.abandoned.swap_remove(idx);
+                self.serving = self.serving.wrapping_add(1);
+            }
+        }
+    }
+
+    /// A mutex handing the lock out in the order the threads asked for it.
+    /// std::sync::Mutex gives no such guarantee, a thread releasing the lock
+    /// in a loop (a long stream of AES blocks) may take it again at once
+    /// and starve the others
+    pub(crate) struct FairMutex<T> {
+        queue: Mutex<Queue>,
+        turn: Condvar,
+        data: Mutex<T>,
+    }
+
+    impl<T> FairMutex<T> {
+        pub(crate) const fn new(data: T) -> FairMutex<T> {
+            FairMutex {
+                queue: Mutex::new(Queue {
+                    next: 0,
+                    serving: 0,
+                    abandoned: Vec::new(),
+                }),
+                turn: Condvar::new(),
+                data: Mutex::new(data),
+            }
+        }
+
+        /// Waits for the turn of the calling thread. Only the thread being served
+        /// locks 'data', so that lock is never contended
+        pub(crate) fn lock(&self) -> LockResult<FairMutexGuard<'_, T>> {
+            let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            let ticket = queue.next;
+            queue.next = queue.next.wrapping_add(1);
+            while queue.serving != ticket {
+                queue = self
+                    .turn
+                    .wait(queue)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            drop(queue);
+            self.lock_data()
+        }
+
+        /// As lock(), but gives up with AtcaTimeout once the deadline of the limit
+        /// has passed or with AtcaCancelled once it is cancelled. The cancellation
+        /// does not wake the thread up, it is checked every few milliseconds
+        pub(crate) fn lock_bounded(
+            &self,
+            limit: &WaitLimit,
+        ) -> Result<LockResult<FairMutexGuard<'_, T>>, AtcaStatus> {
+            if limit.is_unbounded() {
+                return Ok(self.lock());
+            }
+            let mut queue = self.queue.lock().unwrap_or_else(PoisonError::into_inner);
+            let ticket = queue.next;
+            queue.next = queue.next.wrapping_add(1);
+            while queue.serving != ticket {
+                if let Err(err) = limit.check() {
+                    queue.abandoned.push(ticket);
+                    return Err(err);
+                }
+                let timeout = match limit.deadline {
+                    Some(deadline) => min(
+                        deadline.saturating_duration_since(Instant::now()),
+                        CANCELLATION_POLL_INTERVAL,
+                    ),
+                    None => CANCELLATION_POLL_INTERVAL,
+                };
+                queue = self
+                    .turn
+                    .wait_timeout(queue, timeout)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0;
+            }
+            drop(queue);
+            Ok(self.lock_data())
+        }
+
+        /// Locks 'data' for the thread being served
+        fn lock_data(&self) -> LockResult<FairMutexGuard<'_, T>> {
+            match self.data.lock() {
+                Ok(guard) => Ok(FairMutexGuard {
+                    mutex: self,
+                    guard: Some(guard),
+                }),
+                Err(err) => Err(PoisonError::new(FairMutexGuard {
+                    mutex: self,
+                    guard: Some(err.into_inner()),
+                })),
+            }
+        }
+    }
+
+    impl<T> fmt::Debug for FairMutex<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("FairMutex").finish_non_exhaustive()
+        }
+    }
+
+    pub(crate) struct FairMutexGuard<'a, T> {
+        mutex: &'a FairMutex<T>,
+        guard: Option<MutexGuard<'a, T>>,
+    }
+
+    impl<T> Deref for FairMutexGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.guard
+                .as_ref()
+                .expect("FairMutexGuard already released")
+        }
+    }
+
+    impl<T> DerefMut for FairMutexGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            self.guard
+                .as_mut()
+                .expect("FairMutexGuard already released")
+        }
+    }
+
+    impl<T> Drop for FairMutexGuard<'_, T> {
+        /// Unlocks 'data' first, then lets the next thread in the queue go
+        fn drop(&mut self) {
+            self.guard = None;
+            let mut queue = self
+                .mutex
+                .queue
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            queue.serve_next();
+            self.mutex.turn.notify_all();
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod bare_metal {
+    use core::cell::{Cell, UnsafeCell};
+    use core::convert::Infallible;
+    use core::fmt;
+    use core::ops::{Deref, DerefMut};
+
+    /// A lock is never poisoned, there is no unwinding without std
+    pub(crate) type LockResult<Guard> = Result<Guard, Infallible>;
+
+    /// Mutual exclusion between the main code and interrupt handlers or cores.
+    /// Waiting for the lock is a busy loop, the critical sections
+    /// last only as long as it takes to check and set the lock flag
+    pub(crate) struct Mutex<T> {
+        locked: critical_section::Mutex<Cell<bool>>,
+        data: UnsafeCell<T>,
+    }
+
+    unsafe impl<T: Send> Send for Mutex<T> {}
+    unsafe impl<T: Send> Sync for Mutex<T> {}
+
+    impl<T> Mutex<T> {
+        pub(crate) const fn new(data: T) -> Mutex<T> {
+            Mutex {
+                locked: critical_section::Mutex::new(Cell::new(false)),
+                data: UnsafeCell::new(data),
+            }
+        }
+
+        pub(crate) fn lock(&self) -> LockResult<MutexGuard<'_, T>> {
+            while !critical_section::with(|cs| !self.locked.borrow(cs).replace(true)) {
+                core::hint::spin_loop();
+            }
+            Ok(MutexGuard { mutex: self })
+        }
+    }
+
+    impl<T> fmt::Debug for Mutex<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Mutex").finish_non_exhaustive()
+        }
+    }
+
+    pub(crate) struct MutexGuard<'a, T> {
+        mutex: &'a Mutex<T>,
+    }
+
+    impl<T> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            unsafe { &*self.mutex.data.get() }
+        }
+    }
+
+    impl<T> DerefMut for MutexGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            unsafe { &mut *self.mutex.data.get() }
+        }
+    }
+
+    impl<T> Drop for MutexGuard<'_, T> {
+        fn drop(&mut self) {
+            critical_section::with(|cs| self.mutex.locked.borrow(cs).set(false));
+        }
+    }
+}
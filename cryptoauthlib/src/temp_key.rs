@@ -0,0 +1,74 @@
+use super::{AeadAlgorithm, CipherAlgorithm, DigestBuffer, KdfAlgorithm, KdfTarget};
+use super::{SignMode, TempKeySource, TempKeyState, VerifyMode};
+use super::{ATCA_AES_DATA_SIZE, ATCA_KEY_SIZE};
+
+impl TempKeyState {
+    /// Records that a command has loaded TempKey
+    pub(crate) fn load(&mut self, source: TempKeySource, size: usize) {
+        *self = TempKeyState {
+            valid: true,
+            source: Some(source),
+            size,
+            invalidated_by: None,
+        };
+    } // TempKeyState::load()
+
+    /// Records that a command has invalidated or overwritten TempKey.
+    /// The first command to do so is kept, the source tells what has been lost
+    pub(crate) fn invalidate(&mut self, command: &'static str) {
+        if self.valid {
+            self.valid = false;
+            self.invalidated_by = Some(command);
+        }
+    } // TempKeyState::invalidate()
+}
+
+/// Number of bytes a KDF command loads into TempKey, None if its target is not TempKey
+pub(crate) fn kdf_temp_key_size(algorithm: KdfAlgorithm, target: KdfTarget) -> Option<usize> {
+    let size = match algorithm {
+        KdfAlgorithm::Prf { target_length, .. } => target_length,
+        KdfAlgorithm::Aes { .. } => ATCA_AES_DATA_SIZE,
+        KdfAlgorithm::Hkdf => ATCA_KEY_SIZE,
+    };
+    match target {
+        KdfTarget::TempKey => Some(size),
+        KdfTarget::TempKeyUpper => Some(ATCA_KEY_SIZE + size.min(ATCA_KEY_SIZE)),
+        _ => None,
+    }
+} // kdf_temp_key_size()
+
+/// Tells if a cipher operation is given its key, which is loaded into TempKey
+pub(crate) fn cipher_key_given(algorithm: &CipherAlgorithm) -> bool {
+    match algorithm {
+        CipherAlgorithm::Ctr(param)
+        | CipherAlgorithm::Cfb(param)
+        | CipherAlgorithm::Ofb(param)
+        | CipherAlgorithm::Xts(param)
+        | CipherAlgorithm::Ecb(param)
+        | CipherAlgorithm::Cbc(param)
+        | CipherAlgorithm::CbcPkcs7(param) => param.key.is_some(),
+    }
+} // cipher_key_given()
+
+/// Tells if an AEAD operation is given its key, which is loaded into TempKey
+pub(crate) fn aead_key_given(algorithm: &AeadAlgorithm) -> bool {
+    match algorithm {
+        AeadAlgorithm::Ccm(param) | AeadAlgorithm::Gcm(param) => param.key.is_some(),
+    }
+} // aead_key_given()
+
+/// The buffer a signature operation is asked to load its hash into, if any
+pub(crate) fn sign_digest_buffer(mode: &SignMode) -> Option<DigestBuffer> {
+    match mode {
+        SignMode::ExternalVia(buffer, _) => Some(*buffer),
+        _ => None,
+    }
+} // sign_digest_buffer()
+
+/// The buffer a verification is asked to load its hash into, if any
+pub(crate) fn verify_digest_buffer(mode: &VerifyMode) -> Option<DigestBuffer> {
+    match mode {
+        VerifyMode::ExternalVia(buffer, _) | VerifyMode::InternalVia(buffer, _) => Some(*buffer),
+        _ => None,
+    }
+} // verify_digest_buffer()
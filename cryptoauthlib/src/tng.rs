@@ -0,0 +1,381 @@
+use super::atcacert::{cert_build, cert_serial_number, comp_cert_ids};
+use super::der::{
+    peek_tag, read_element, ASN1_BIT_STRING, ASN1_BOOLEAN, ASN1_CONTEXT_0, ASN1_CONTEXT_3,
+    ASN1_GENERALIZED_TIME, ASN1_INTEGER, ASN1_OCTET_STRING, ASN1_OID, ASN1_SEQUENCE, ASN1_SET,
+    ASN1_UTC_TIME,
+};
+use super::signature::signature_from_der;
+use super::{AtcaStatus, AteccDeviceTrait, CertDateFormat, CertDef, CertDeviceLoc, CertLoc};
+use super::{CertStdElements, SignMode, TngCertChain, TngGenuinenessReport, VerifyMode};
+use super::{
+    ATCA_ATECC_PUB_KEY_SIZE, ATCA_COMP_CERT_SIZE, ATCA_RANDOM_BUFFER_SIZE, ATCA_SERIAL_NUM_SIZE,
+};
+use alloc::{string::String, vec::Vec};
+
+// Fixed data zone layout of ATECC608A-TNGTLS and ATECC608A-TFLXTLS parts
+pub(crate) const TNG_DEVICE_KEY_SLOT: u8 = 0;
+const TNG_DEVICE_COMP_CERT_SLOT: u8 = 10;
+const TNG_SIGNER_PUBLIC_KEY_SLOT: u8 = 11;
+const TNG_SIGNER_COMP_CERT_SLOT: u8 = 12;
+/// Public key stored in a slot, each coordinate preceded by 4 bytes of padding
+const TNG_PADDED_PUB_KEY_SIZE: usize = 72;
+
+/// DER encoded OID 2.5.4.3 (commonName)
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+/// DER encoded OID 2.5.29.14 (subjectKeyIdentifier)
+const OID_SUBJECT_KEY_ID: &[u8] = &[0x55, 0x1D, 0x0E];
+/// DER encoded OID 2.5.29.35 (authorityKeyIdentifier)
+const OID_AUTHORITY_KEY_ID: &[u8] = &[0x55, 0x1D, 0x23];
+const AUTHORITY_KEY_ID_KEY_ID: u8 = 0x80;
+/// Prefix of the common name of a device certificate, followed by the hex device serial number
+const DEVICE_CN_PREFIX: &[u8] = b"sn";
+
+/// Reads the certificate chain of a Trust&GO (TNGTLS) or TrustFLEX (TFLXTLS) part
+/// in a single call: the signer and device certificates are reconstructed
+/// from the compressed certificates stored in the fixed slots of these parts.
+/// The root certificate and the templates (any signer and device certificate
+/// issued for the same product, e.g. the ones published by Microchip)
+/// must be supplied by the caller, all DER encoded.
+/// The chain is ready to be used for TLS client authentication with the key in slot 0.
+pub fn tng_get_cert_chain(
+    device: &dyn AteccDeviceTrait,
+    root_cert: &[u8],
+    signer_template: &[u8],
+    device_template: &[u8],
+) -> Result<TngCertChain, AtcaStatus> {
+    let root_public_key = cert_public_key(root_cert)?;
+
+    let padded_key =
+        device.read_slot_data(TNG_SIGNER_PUBLIC_KEY_SLOT, 0, TNG_PADDED_PUB_KEY_SIZE)?;
+    let signer_public_key = [&padded_key[4..36], &padded_key[40..72]].concat();
+    let signer = tng_cert_read(
+        device,
+        signer_template,
+        TNG_SIGNER_COMP_CERT_SLOT,
+        TNG_SIGNER_PUBLIC_KEY_SLOT,
+        &signer_public_key,
+        &root_public_key,
+        false,
+    )?;
+
+    let mut device_public_key: Vec<u8> = Vec::new();
+    let result = device.get_public_key(TNG_DEVICE_KEY_SLOT, &mut device_public_key);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    let mut template = device_template.to_vec();
+    device_cn_set(&mut template, &device.get_serial_number())?;
+    let device_cert = tng_cert_read(
+        device,
+        &template,
+        TNG_DEVICE_COMP_CERT_SLOT,
+        TNG_DEVICE_KEY_SLOT,
+        &device_public_key,
+        &signer_public_key,
+        true,
+    )?;
+
+    Ok(TngCertChain {
+        root: root_cert.to_vec(),
+        signer,
+        device: device_cert,
+    })
+} // tng_get_cert_chain()
+
+/// Checks that the device is a genuine pre-provisioned Trust&GO or TrustFLEX part:
+/// its certificate chain is reconstructed as by tng_get_cert_chain() and verified
+/// up to the given root, then the device proves possession of the private key
+/// by signing a random challenge, which is verified with the device certificate key.
+/// Failed checks are reported, an error is returned only if the device
+/// or the supplied certificates cannot be processed.
+pub fn tng_verify_genuine(
+    device: &dyn AteccDeviceTrait,
+    root_cert: &[u8],
+    signer_template: &[u8],
+    device_template: &[u8],
+) -> Result<TngGenuinenessReport, AtcaStatus> {
+    let cert_chain = tng_get_cert_chain(device, root_cert, signer_template, device_template)?;
+    let root_public_key = cert_public_key(&cert_chain.root)?;
+    let signer_public_key = cert_public_key(&cert_chain.signer)?;
+    let device_public_key = cert_public_key(&cert_chain.device)?;
+
+    let root_cert_verified = cert_verify(device, &cert_chain.root, &root_public_key)?;
+    let signer_cert_verified = cert_verify(device, &cert_chain.signer, &root_public_key)?;
+    let device_cert_verified = cert_verify(device, &cert_chain.device, &signer_public_key)?;
+
+    let mut challenge: Vec<u8> = Vec::new();
+    let result = device.random(&mut challenge);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    challenge.truncate(ATCA_RANDOM_BUFFER_SIZE);
+    let mut signature: Vec<u8> = Vec::new();
+    let result = device.sign_hash(
+        SignMode::External(challenge.clone()),
+        TNG_DEVICE_KEY_SLOT,
+        &mut signature,
+    );
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    let challenge_verified = device.verify_hash(
+        VerifyMode::External(device_public_key),
+        &challenge,
+        &signature,
+    )?;
+
+    Ok(TngGenuinenessReport {
+        cert_chain,
+        root_cert_verified,
+        signer_cert_verified,
+        device_cert_verified,
+        challenge_verified,
+        is_genuine: root_cert_verified
+            && signer_cert_verified
+            && device_cert_verified
+            && challenge_verified,
+    })
+} // tng_verify_genuine()
+
+/// Verifies the signature of a certificate (DER) with the public key of its issuer,
+/// the digest is calculated and the signature verified by ATECC
+pub(crate) fn cert_verify(
+    device: &dyn AteccDeviceTrait,
+    cert: &[u8],
+    ca_public_key: &[u8],
+) -> Result<bool, AtcaStatus> {
+    let (cert, _) = read_element(ASN1_SEQUENCE, cert)?;
+    let (_, rest) = read_element(ASN1_SEQUENCE, cert)?;
+    let tbs_cert = &cert[..(cert.len() - rest.len())];
+    let (_, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let signature = match read_element(ASN1_BIT_STRING, rest)? {
+        ([0x00, signature @ ..], []) => signature_from_der(signature)?,
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+
+    let mut digest: Vec<u8> = Vec::new();
+    let result = device.sha(tbs_cert.to_vec(), &mut digest);
+    if AtcaStatus::AtcaSuccess != result {
+        return Err(result);
+    }
+    device.verify_hash(
+        VerifyMode::External(ca_public_key.to_vec()),
+        &digest,
+        &signature,
+    )
+} // cert_verify()
+
+/// Reconstructs a certificate from the compressed certificate stored in 'comp_cert_slot'
+fn tng_cert_read(
+    device: &dyn AteccDeviceTrait,
+    template: &[u8],
+    comp_cert_slot: u8,
+    public_key_slot: u8,
+    public_key: &[u8],
+    ca_public_key: &[u8],
+    is_device_cert: bool,
+) -> Result<Vec<u8>, AtcaStatus> {
+    let comp_cert = device.read_slot_data(comp_cert_slot, 0, ATCA_COMP_CERT_SIZE)?;
+    let cert_def = cert_def_from_template(
+        template,
+        &comp_cert,
+        comp_cert_slot,
+        public_key_slot,
+        is_device_cert,
+    )?;
+    let serial_number = cert_serial_number(device, &cert_def, &comp_cert, public_key)?;
+    cert_build(
+        &cert_def,
+        &comp_cert,
+        public_key,
+        &serial_number,
+        Some(ca_public_key),
+    )
+} // tng_cert_read()
+
+/// Derives a certificate definition from a template certificate (DER)
+/// and the identifiers stored in the compressed certificate.
+/// The signer ID is taken from the last 4 characters of the issuer common name
+/// of a device certificate and of the subject common name of a signer certificate.
+pub(crate) fn cert_def_from_template(
+    template: &[u8],
+    comp_cert: &[u8],
+    comp_cert_slot: u8,
+    public_key_slot: u8,
+    is_device_cert: bool,
+) -> Result<CertDef, AtcaStatus> {
+    let (template_id, chain_id, sn_source) = comp_cert_ids(comp_cert)?;
+
+    let (cert, _) = read_element(ASN1_SEQUENCE, template)?;
+    let (tbs_cert, rest) = read_element(ASN1_SEQUENCE, cert)?;
+    let (_, signature) = read_element(ASN1_SEQUENCE, rest)?;
+    let (_, rest) = read_element(ASN1_CONTEXT_0, tbs_cert)?;
+    let (serial_number, rest) = read_element(ASN1_INTEGER, rest)?;
+    let (_, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (issuer, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (validity, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (subject, rest) = read_element(ASN1_SEQUENCE, rest)?;
+    let (public_key, mut rest) = spki_public_key(rest)?;
+
+    let (issue_date, issue_date_format, validity) = read_time(validity)?;
+    let (expire_date, expire_date_format, _) = read_time(validity)?;
+
+    let common_name = match is_device_cert {
+        true => name_common_name(issuer)?,
+        false => name_common_name(subject)?,
+    };
+    let signer_id: &[u8] = match common_name {
+        Some(name) if name.len() >= 4 => &name[name.len() - 4..],
+        _ => &[],
+    };
+
+    let mut subj_key_id: &[u8] = &[];
+    let mut auth_key_id: &[u8] = &[];
+    // Skip the optional unique identifiers
+    while !rest.is_empty() && peek_tag(rest) != Some(ASN1_CONTEXT_3) {
+        let tag = peek_tag(rest).ok_or(AtcaStatus::AtcaBadParam)?;
+        rest = read_element(tag, rest)?.1;
+    }
+    if !rest.is_empty() {
+        let (extensions, _) = read_element(ASN1_CONTEXT_3, rest)?;
+        let (mut extensions, _) = read_element(ASN1_SEQUENCE, extensions)?;
+        while !extensions.is_empty() {
+            let (extension, next) = read_element(ASN1_SEQUENCE, extensions)?;
+            extensions = next;
+            let (oid, mut value) = read_element(ASN1_OID, extension)?;
+            if peek_tag(value) == Some(ASN1_BOOLEAN) {
+                value = read_element(ASN1_BOOLEAN, value)?.1;
+            }
+            let (value, _) = read_element(ASN1_OCTET_STRING, value)?;
+            match oid {
+                OID_SUBJECT_KEY_ID => subj_key_id = read_element(ASN1_OCTET_STRING, value)?.0,
+                OID_AUTHORITY_KEY_ID => {
+                    let (value, _) = read_element(ASN1_SEQUENCE, value)?;
+                    if peek_tag(value) == Some(AUTHORITY_KEY_ID_KEY_ID) {
+                        auth_key_id = read_element(AUTHORITY_KEY_ID_KEY_ID, value)?.0;
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let loc = |element: &[u8]| CertLoc {
+        offset: match element.len() {
+            0 => 0,
+            _ => element.as_ptr() as usize - template.as_ptr() as usize,
+        },
+        count: element.len(),
+    };
+    Ok(CertDef {
+        template_id,
+        chain_id,
+        sn_source,
+        issue_date_format,
+        expire_date_format,
+        public_key_slot,
+        comp_cert_dev_loc: CertDeviceLoc {
+            slot_id: comp_cert_slot,
+            offset: 0,
+            count: ATCA_COMP_CERT_SIZE,
+        },
+        cert_sn_dev_loc: None,
+        std_cert_elements: CertStdElements {
+            public_key: loc(public_key),
+            signature: loc(signature),
+            issue_date: loc(issue_date),
+            expire_date: loc(expire_date),
+            signer_id: loc(signer_id),
+            cert_sn: loc(serial_number),
+            auth_key_id: loc(auth_key_id),
+            subj_key_id: loc(subj_key_id),
+        },
+        template: template.to_vec(),
+    })
+} // cert_def_from_template()
+
+/// Returns the raw X || Y public key of a certificate (DER)
+fn cert_public_key(cert: &[u8]) -> Result<Vec<u8>, AtcaStatus> {
+    let (cert, _) = read_element(ASN1_SEQUENCE, cert)?;
+    let (tbs_cert, _) = read_element(ASN1_SEQUENCE, cert)?;
+    let (_, rest) = read_element(ASN1_CONTEXT_0, tbs_cert)?;
+    let (_, rest) = read_element(ASN1_INTEGER, rest)?;
+    let mut rest = rest;
+    // signature algorithm, issuer, validity and subject
+    for _ in 0..4 {
+        rest = read_element(ASN1_SEQUENCE, rest)?.1;
+    }
+    Ok(spki_public_key(rest)?.0.to_vec())
+} // cert_public_key()
+
+/// Splits off a P256 SubjectPublicKeyInfo, returns the raw X || Y public key
+/// and the remaining data
+fn spki_public_key(data: &[u8]) -> Result<(&[u8], &[u8]), AtcaStatus> {
+    let (spki, rest) = read_element(ASN1_SEQUENCE, data)?;
+    let (_, public_key) = read_element(ASN1_SEQUENCE, spki)?;
+    match read_element(ASN1_BIT_STRING, public_key)? {
+        ([0x00, 0x04, public_key @ ..], []) if public_key.len() == ATCA_ATECC_PUB_KEY_SIZE => {
+            Ok((public_key, rest))
+        }
+        _ => Err(AtcaStatus::AtcaBadParam),
+    }
+} // spki_public_key()
+
+/// Splits off a UTCTime or GeneralizedTime element, returns its content,
+/// its format and the remaining data
+fn read_time(data: &[u8]) -> Result<(&[u8], CertDateFormat, &[u8]), AtcaStatus> {
+    let (tag, format) = match peek_tag(data) {
+        Some(ASN1_UTC_TIME) => (ASN1_UTC_TIME, CertDateFormat::Rfc5280Utc),
+        Some(ASN1_GENERALIZED_TIME) => (ASN1_GENERALIZED_TIME, CertDateFormat::Rfc5280Gen),
+        _ => return Err(AtcaStatus::AtcaBadParam),
+    };
+    let (time, rest) = read_element(tag, data)?;
+    Ok((time, format, rest))
+} // read_time()
+
+/// Finds the value of the common name attribute in an X.501 name
+pub(crate) fn name_common_name(name: &[u8]) -> Result<Option<&[u8]>, AtcaStatus> {
+    let mut name = name;
+    while !name.is_empty() {
+        let (attributes, next) = read_element(ASN1_SET, name)?;
+        name = next;
+        let (attribute, _) = read_element(ASN1_SEQUENCE, attributes)?;
+        let (oid, value) = read_element(ASN1_OID, attribute)?;
+        if oid == OID_COMMON_NAME {
+            let tag = peek_tag(value).ok_or(AtcaStatus::AtcaBadParam)?;
+            return Ok(Some(read_element(tag, value)?.0));
+        }
+    }
+    Ok(None)
+} // name_common_name()
+
+/// Puts the device serial number into the subject common name of a device certificate
+/// template ("sn" followed by 18 hex digits), if it has such a name
+fn device_cn_set(template: &mut [u8], serial_number: &[u8]) -> Result<(), AtcaStatus> {
+    let (cert, _) = read_element(ASN1_SEQUENCE, template)?;
+    let (tbs_cert, _) = read_element(ASN1_SEQUENCE, cert)?;
+    let (_, rest) = read_element(ASN1_CONTEXT_0, tbs_cert)?;
+    let (_, mut rest) = read_element(ASN1_INTEGER, rest)?;
+    // signature algorithm, issuer and validity
+    for _ in 0..3 {
+        rest = read_element(ASN1_SEQUENCE, rest)?.1;
+    }
+    let (subject, _) = read_element(ASN1_SEQUENCE, rest)?;
+    let common_name = match name_common_name(subject)? {
+        Some(name)
+            if name.starts_with(DEVICE_CN_PREFIX)
+                && (name.len() == DEVICE_CN_PREFIX.len() + 2 * ATCA_SERIAL_NUM_SIZE) =>
+        {
+            name
+        }
+        _ => return Ok(()),
+    };
+    let offset =
+        common_name.as_ptr() as usize - template.as_ptr() as usize + DEVICE_CN_PREFIX.len();
+    let hex: String = serial_number.iter().map(|x| format!("{:02X}", x)).collect();
+    if hex.len() != 2 * ATCA_SERIAL_NUM_SIZE {
+        return Err(AtcaStatus::AtcaInvalidSize);
+    }
+    template[offset..(offset + hex.len())].copy_from_slice(hex.as_bytes());
+    Ok(())
+} // device_cn_set()
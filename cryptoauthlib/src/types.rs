@@ -2,6 +2,8 @@ use std::mem::MaybeUninit;
 
 use cryptoauthlib_sys::atca_aes_ctr_ctx_t;
 use cryptoauthlib_sys::atca_aes_cmac_ctx_t;
+use cryptoauthlib_sys::atca_aes_gcm_ctx_t;
+use cryptoauthlib_sys::atca_sha256_ctx_t;
 
 /// An ATECC/ATSHA device buffer to load
 #[repr(u8)]
@@ -12,6 +14,39 @@ pub enum NonceTarget {
     AltKeyBuf = 0x80,
 }
 
+/// Command that has loaded TempKey, as tracked by the host
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TempKeySource {
+    /// Nonce command in pass-through mode: nonce(), import_key() into TempKey
+    /// or an AES key given to a cipher operation
+    Nonce,
+    /// Nonce command combining a host nonce with a random number: nonce_rand()
+    RandomNonce,
+    /// GenDig command: gen_dig()
+    GenDig,
+    /// GenKey command generating a private key into TempKey: gen_key(), ecies_encrypt()
+    GenKey,
+    /// ECDH command storing the premaster secret in TempKey
+    Ecdh,
+    /// KDF command deriving a key into TempKey
+    Kdf,
+}
+
+/// What the host knows about TempKey, returned by tempkey_state().
+/// The chip does not tell it, the state is tracked from the commands issued
+/// through the same instance, so commands sent by other means are not accounted for
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TempKeyState {
+    /// TempKey holds the value loaded by the last command that set it
+    pub valid: bool,
+    /// Command that has loaded TempKey last, None if nothing has been loaded
+    pub source: Option<TempKeySource>,
+    /// Number of bytes loaded (32, or 64 when the upper half is used as well)
+    pub size: usize,
+    /// Command that has invalidated or overwritten TempKey since it was loaded
+    pub invalidated_by: Option<&'static str>,
+}
+
 /// Designates the source of the data to hash with TempKey for Generate Digest
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -31,24 +66,53 @@ pub enum InfoCmdType {
     VolKeyPermit = 0x04,
 }
 
+/// The buffer an external hash is loaded into, to be signed or verified
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DigestBuffer {
+    /// Message Digest Buffer of ATECC608x, TempKey is left as it is
+    MsgDigBuf,
+    /// TempKey, whatever it holds is overwritten
+    TempKey,
+}
+
 /// The mode of calling the ECDSA signature function
+#[derive(Clone)]
 pub enum SignMode {
-    /// The input parameter is hash to be signed
+    /// The input parameter is hash to be signed, loaded into the Message
+    /// Digest Buffer of ATECC608x or TempKey of the other chips
     External(Vec<u8>),
+    /// As External, the hash is loaded into the given buffer
+    ExternalVia(DigestBuffer, Vec<u8>),
     Internal(SignEcdsaParam),
 }
 
 /// The mode of calling the ECDSA verification function
+#[derive(Clone)]
 pub enum VerifyMode {
-    /// The input parameter is public key
+    /// The input parameter is public key, the hash is loaded into
+    /// the Message Digest Buffer of ATECC608x or TempKey of the other chips
     External(Vec<u8>),
+    /// As External, the hash is loaded into the given buffer
+    ExternalVia(DigestBuffer, Vec<u8>),
     ExternalMac(VerifyEcdsaParam),
-    /// The input parameter is slot number
+    /// The input parameter is slot number, the hash is loaded as for External
     Internal(u8),
+    /// As Internal, the hash is loaded into the given buffer
+    InternalVia(DigestBuffer, u8),
     InternalMac(VerifyEcdsaParam),
 }
 
+/// Encoding of an ECDSA signature
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SignatureFormat {
+    /// 64 bytes: R || S, each a 32-byte big-endian number, as produced by ATECC
+    Raw,
+    /// ASN.1 DER encoded 'SEQUENCE { r INTEGER, s INTEGER }', as used by X.509 and OpenSSL
+    Der,
+}
+
 /// Detailed parameters of calling the ECDSA signature function
+#[derive(Clone)]
 pub struct SignEcdsaParam {
     /// Set to true if the signature will be used with
     /// the Verify(Invalidate) command. false for all other cases.
@@ -58,7 +122,24 @@ pub struct SignEcdsaParam {
     pub is_full_sn: bool,
 }
 
+/// Flags the chip keeps with TempKey, telling how it has been computed.
+/// They are part of the message signed with SignMode::Internal
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TempKeyFlags {
+    /// Slot used by the command that has computed TempKey (GenDig, GenKey)
+    pub key_id: u8,
+    /// false if TempKey comes from a random nonce, true if from a fixed one
+    pub source_flag: bool,
+    /// TempKey has been computed by GenDig over the contents of a slot
+    pub gen_dig_data: bool,
+    /// TempKey has been computed by GenKey
+    pub gen_key_data: bool,
+    /// The slot TempKey has been computed from has its NoMac bit set
+    pub no_mac_flag: bool,
+}
+
 /// Detailed parameters of calling the ECDSA verification function
+#[derive(Clone, Default)]
 pub struct VerifyEcdsaParam {
     /// Public key for ExternalMac mode 
     pub public_key: Option<Vec<u8>>,
@@ -70,17 +151,6 @@ pub struct VerifyEcdsaParam {
     pub io_key: u8,
 }
 
-impl Default for VerifyEcdsaParam {
-    fn default() -> VerifyEcdsaParam {
-        VerifyEcdsaParam {
-            public_key: None,
-            slot_number: None,
-            num_in: Vec::new(),
-            io_key: 0x00,
-        }
-    }
-}
-
 /// Cipher operation type
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum CipherOperation {
@@ -113,8 +183,17 @@ pub enum CipherAlgorithm {
     CbcPkcs7(CipherParam),
 }
 
+/// AES key wrap algorithm
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KeyWrapAlgorithm {
+    /// AES Key Wrap (RFC 3394), key data length must be a multiple of 8 bytes, at least 16 bytes
+    Kw,
+    /// AES Key Wrap with Padding (RFC 5649), key data of any non-zero length
+    Kwp,
+}
+
 /// Cipher algorithm parameters for compute
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct CipherParam {
     /// IV - Initialization Vector.
     /// For CTR mode it is concatenation of nonce and initial counter value.
@@ -126,16 +205,6 @@ pub struct CipherParam {
     pub key: Option<Vec<u8>>,
 }
 
-impl Default for CipherParam {
-    fn default() -> CipherParam {
-        CipherParam {
-            iv: None,
-            counter_size: None,
-            key: None,
-        }
-    }
-}
-
 /// Type of AEAD algorithm
 #[derive(Clone, Debug, PartialEq)]
 pub enum AeadAlgorithm {
@@ -144,7 +213,7 @@ pub enum AeadAlgorithm {
 }
 
 /// AEAD algorithm parameters for compute
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct AeadParam {
     /// Nonce [number used once aka IV] (default length is 12 bytes)
     pub nonce: Vec<u8>,
@@ -159,18 +228,6 @@ pub struct AeadParam {
     pub additional_data: Option<Vec<u8>>,
 }
 
-impl Default for AeadParam {
-    fn default() -> AeadParam {
-        AeadParam {
-            nonce: Vec::new(),
-            key: None,
-            tag: None,
-            tag_length: None,
-            additional_data: None,
-        }
-    }
-}
-
 /// Data context structure for AEAD encryption in CCM mode
 #[derive(Copy, Clone, Debug)]//, PartialEq)]
 pub struct AtcaAesCcmCtx {
@@ -211,6 +268,45 @@ impl Default for AtcaAesCcmCtx {
     }
 }
 
+/// Context of a streaming AEAD encryption or decryption in GCM mode.
+/// It is created by gcm_init() and passed through the update functions
+/// until the operation is completed by one of the finish functions.
+#[derive(Copy, Clone, Debug)]
+pub struct AtcaAesGcmCtx {
+    pub(crate) ctx: atca_aes_gcm_ctx_t,
+}
+
+impl Default for AtcaAesGcmCtx {
+    fn default() -> AtcaAesGcmCtx {
+        AtcaAesGcmCtx {
+            ctx: {
+                let ctx = MaybeUninit::<atca_aes_gcm_ctx_t>::zeroed();
+                unsafe { ctx.assume_init() }
+            },
+        }
+    }
+}
+
+/// Context of a SHA-256 digest calculated by the chip in several steps.
+/// It is created by sha_start() and passed through sha_update() until the digest
+/// is obtained with sha_finish(). The intermediate hash state is kept inside the chip,
+/// so no other command that uses the SHA engine may be executed in the meantime.
+#[derive(Copy, Clone)]
+pub struct AtcaSha256Ctx {
+    pub(crate) ctx: atca_sha256_ctx_t,
+}
+
+impl Default for AtcaSha256Ctx {
+    fn default() -> AtcaSha256Ctx {
+        AtcaSha256Ctx {
+            ctx: {
+                let ctx = MaybeUninit::<atca_sha256_ctx_t>::zeroed();
+                unsafe { ctx.assume_init() }
+            },
+        }
+    }
+}
+
 /// structure that stores data for options supported by the chip
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ChipOptions {
@@ -222,13 +318,13 @@ pub struct ChipOptions {
     pub io_key_enabled: bool,
     /// slot number where the key for encrypting transmission between chip and host is placed
     pub io_key_in_slot: u8,
-    ///
+    /// if true, the AES command is enabled (only relevant for the ATECC608x chip)
     pub aes_enabled: bool,
-    ///
+    /// if true, the AES mode of the KDF command is enabled (only relevant for the ATECC608x chip)
     pub kdf_aes_enabled: bool,
-    ///
+    /// protection of the ECDH command output
     pub ecdh_output_protection: OutputProtectionState,
-    ///
+    /// protection of the KDF command output
     pub kdf_output_protection: OutputProtectionState,
 }
 
@@ -272,8 +368,577 @@ impl From<u8> for OutputProtectionState {
     }
 }
 
+/// An IO protection session with the ATECC608x chip.
+/// The session owns a copy of the IO protection key, which is used to encrypt
+/// transmission of secrets between host and chip (ECDH premaster secrets,
+/// verification MACs, AES keys written to slots protected by the IO key).
+/// The copy of the key is overwritten with zeros when the session is dropped.
+pub struct IoProtectionSession {
+    io_key_slot: u8,
+    io_key: [u8; ATCA_KEY_SIZE],
+}
+
+impl IoProtectionSession {
+    pub(crate) fn new(io_key_slot: u8, io_key: &[u8]) -> Self {
+        let mut key: [u8; ATCA_KEY_SIZE] = [0; ATCA_KEY_SIZE];
+        key.copy_from_slice(io_key);
+        IoProtectionSession {
+            io_key_slot,
+            io_key: key,
+        }
+    }
+
+    /// Slot number where the IO protection key is stored on the chip
+    pub fn io_key_slot(&self) -> u8 {
+        self.io_key_slot
+    }
+
+    pub(crate) fn io_key(&self) -> &[u8] {
+        &self.io_key
+    }
+}
+
+impl Drop for IoProtectionSession {
+    fn drop(&mut self) {
+        for byte in self.io_key.iter_mut() {
+            unsafe { std::ptr::write_volatile(byte, 0) };
+        }
+    }
+}
+
+/// Result of ECDH key agreement, describing where the premaster secret ended up.
+/// It depends on the 'ecdh_output_protection' setting of the chip
+/// and on the configuration of the slot holding the private key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EcdhOutput {
+    /// Premaster secret returned to the host
+    /// (decrypted on the host side, if it was transmitted encrypted)
+    PreMasterSecret(Vec<u8>),
+    /// Premaster secret stored in the TempKey of the chip
+    TempKey,
+    /// Premaster secret stored in the indicated slot of the chip
+    Slot(u8),
+}
+
+/// KDF algorithm (ATECC608x only)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KdfAlgorithm {
+    /// PRF as defined in TLS 1.2 (P_SHA256).
+    /// 'key_length' is the length of the source key (16, 32, 48 or 64 bytes),
+    /// 'target_length' is the length of the derived key (32 or 64 bytes)
+    Prf {
+        key_length: usize,
+        target_length: usize,
+    },
+    /// AES-ECB encryption of a 16-byte message with a 16-byte key
+    /// taken from the source at 'key_location' (0 to 3, in units of 16 bytes)
+    Aes { key_location: u8 },
+    /// HKDF (HMAC-SHA256) with the message supplied as input
+    Hkdf,
+}
+
+/// Source of the input key for the KDF command
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KdfSource {
+    TempKey,
+    TempKeyUpper,
+    Slot(u8),
+    AltKeyBuf,
+}
+
+/// Destination of the key derived by the KDF command
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KdfTarget {
+    TempKey,
+    TempKeyUpper,
+    Slot(u8),
+    AltKeyBuf,
+    /// Derived key is returned to the host
+    Output,
+}
+
+/// Label of the TLS 1.2 Finished message whose verify_data is calculated
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TlsFinishedLabel {
+    /// "client finished"
+    Client,
+    /// "server finished"
+    Server,
+}
+
+/// Attribute of the distinguished name of a certificate subject
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DnAttribute {
+    /// CN
+    CommonName,
+    /// SERIALNUMBER
+    SerialNumber,
+    /// C, two letter country code
+    Country,
+    /// L
+    Locality,
+    /// ST
+    StateOrProvince,
+    /// O
+    Organization,
+    /// OU
+    OrganizationalUnit,
+}
+
+/// Entry of the Subject Alternative Name extension
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubjectAltName {
+    Dns(String),
+    Email(String),
+    Uri(String),
+    Ip(std::net::IpAddr),
+}
+
+/// Certificate extension requested in a CSR
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrExtension {
+    /// Extension identifier, DER encoded OID without the tag and length
+    pub oid: Vec<u8>,
+    /// Set to true if the extension must be understood by certificate users
+    pub is_critical: bool,
+    /// DER encoded value of the extension
+    pub value: Vec<u8>,
+}
+
+/// Content of a certificate signing request (PKCS#10)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsrParam {
+    /// Distinguished name of the subject, attributes in the given order
+    pub subject: Vec<(DnAttribute, String)>,
+    /// Names put into the Subject Alternative Name extension, if any
+    pub subject_alt_names: Vec<SubjectAltName>,
+    /// Other extensions requested for the certificate
+    pub extensions: Vec<CsrExtension>,
+}
+
+/// Location of an element in a certificate (byte offset and size)
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CertLoc {
+    pub offset: usize,
+    /// Size of the element, 0 if the certificate does not contain it
+    pub count: usize,
+}
+
+/// Location of data stored in a data zone slot of ATECC
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CertDeviceLoc {
+    pub slot_id: u8,
+    pub offset: usize,
+    pub count: usize,
+}
+
+/// Format in which a date is stored in a certificate
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CertDateFormat {
+    /// "YYYY-MM-DDThh:mm:ssZ"
+    Iso8601Sep,
+    /// X.509 UTCTime, "YYMMDDhhmmssZ"
+    Rfc5280Utc,
+    /// Seconds since 1970-01-01, 32-bit big-endian
+    PosixUint32Be,
+    /// X.509 GeneralizedTime, "YYYYMMDDhhmmssZ"
+    Rfc5280Gen,
+}
+
+/// Source of the certificate serial number.
+/// The values are those stored in the compressed certificate.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CertSnSource {
+    /// Serial number stored on the device, see CertDef::cert_sn_dev_loc
+    Stored = 0x0,
+    /// 9-byte device serial number
+    DeviceSn = 0x8,
+    /// 2-byte signer ID from the compressed certificate
+    SignerId = 0x9,
+    /// SHA256 of the public key and the encoded dates, made positive
+    PubKeyHash = 0xA,
+    /// SHA256 of the device serial number and the encoded dates, made positive
+    DeviceSnHash = 0xB,
+    /// As PubKeyHash, but also with the second most significant bit set
+    PubKeyHashPos = 0xC,
+    /// As DeviceSnHash, but also with the second most significant bit set
+    DeviceSnHashPos = 0xD,
+    /// SHA256 of the public key and the encoded dates, unmodified
+    PubKeyHashRaw = 0xE,
+    /// SHA256 of the device serial number and the encoded dates, unmodified
+    DeviceSnHashRaw = 0xF,
+}
+
+/// Locations of the standard elements in a certificate template
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CertStdElements {
+    /// Raw X || Y public key, without the 0x04 prefix
+    pub public_key: CertLoc,
+    /// BIT STRING holding the signature, which must be the last element of the certificate
+    pub signature: CertLoc,
+    pub issue_date: CertLoc,
+    pub expire_date: CertLoc,
+    /// Signer ID as 4 uppercase hex digits
+    pub signer_id: CertLoc,
+    pub cert_sn: CertLoc,
+    /// SHA1 of the CA public key (0x04 || X || Y)
+    pub auth_key_id: CertLoc,
+    /// SHA1 of the public key (0x04 || X || Y)
+    pub subj_key_id: CertLoc,
+}
+
+/// Definition of a compressed certificate: the template of the full certificate,
+/// where its variable parts are and where they are kept on the device
+#[derive(Clone, Debug, PartialEq)]
+pub struct CertDef {
+    pub template_id: u8,
+    pub chain_id: u8,
+    pub sn_source: CertSnSource,
+    pub issue_date_format: CertDateFormat,
+    pub expire_date_format: CertDateFormat,
+    /// Slot holding the private or public key of the certificate
+    pub public_key_slot: u8,
+    /// Where the compressed certificate (72 bytes) is stored
+    pub comp_cert_dev_loc: CertDeviceLoc,
+    /// Where the serial number is stored, for CertSnSource::Stored
+    pub cert_sn_dev_loc: Option<CertDeviceLoc>,
+    pub std_cert_elements: CertStdElements,
+    /// DER encoded certificate with placeholders for the variable parts
+    pub template: Vec<u8>,
+}
+
+/// Certificate chain of a Trust&GO or TrustFLEX part, each certificate DER encoded
+#[derive(Clone, Debug, PartialEq)]
+pub struct TngCertChain {
+    pub root: Vec<u8>,
+    pub signer: Vec<u8>,
+    pub device: Vec<u8>,
+}
+
+/// Outcome of the genuineness check of a Trust&GO or TrustFLEX part
+#[derive(Clone, Debug, PartialEq)]
+pub struct TngGenuinenessReport {
+    /// Certificate chain reconstructed from the data stored on the device
+    pub cert_chain: TngCertChain,
+    /// Root certificate is correctly self-signed
+    pub root_cert_verified: bool,
+    /// Signer certificate is signed by the root
+    pub signer_cert_verified: bool,
+    /// Device certificate is signed by the signer
+    pub device_cert_verified: bool,
+    /// Random challenge signed by the device key verifies with the device certificate key
+    pub challenge_verified: bool,
+    /// All of the above checks passed
+    pub is_genuine: bool,
+}
+
+/// Signature bound to the value of a monotonic counter, made by sign_hash_with_counter()
+#[derive(Clone, Debug, PartialEq)]
+pub struct CounterSignature {
+    /// Monotonic counter incremented for the signature
+    pub counter_id: u8,
+    /// Value of the counter after the increment
+    pub counter_value: u32,
+    /// Signature (R || S) of the message built by counter_signed_message()
+    pub signature: Vec<u8>,
+}
+
+/// How much a limited-use key has been used, told by limited_use_state()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LimitedUseState {
+    /// Slot of the key
+    pub slot: u8,
+    /// Uses counted so far: the value of the monotonic counter ATCA_LIMITED_USE_COUNTER_ID,
+    /// shared by all the limited-use keys and counter_increment(), or the used bits of LastKeyUse
+    /// for slot 15 of ATECC508A and older chips
+    pub used: u32,
+    /// Value of 'used' at which the key cannot be used any more: the count match value,
+    /// ATCA_COUNTER_MAX_VALUE or the 128 bits of LastKeyUse
+    pub limit: u32,
+    /// The limit is the count match value stored in the CountMatchKey slot (ATECC608x only)
+    pub count_match: bool,
+}
+
+/// Uses left of the two keys of an ATSHA206A, told by sha206a_use_state()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sha206aUseState {
+    /// MACs the derived key can still compute, the bits left set in its UseFlag
+    pub derived_key_uses_left: u8,
+    /// How many more times the derived key can be renewed from the parent key
+    pub parent_key_uses_left: u8,
+    /// UpdateCount, how many times the derived key has been renewed
+    pub derived_key_update_count: u8,
+}
+
+/// Statement made by attest_key() that a P256 key is held by a specific device
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyAttestation {
+    /// Slot holding the private key of the attested key
+    pub subject_slot: u8,
+    /// Serial number of the device
+    pub serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
+    /// SHA256 of the configuration zone, as read at the time of the attestation
+    pub config_digest: Vec<u8>,
+    /// Attested public key (X || Y)
+    pub public_key: Vec<u8>,
+    /// Signature (R || S) of the message built by key_attestation_message()
+    pub signature: Vec<u8>,
+}
+
+/// Operation of the wrapper that may be used on a slot, as listed by slot_report()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SlotOperation {
+    /// gen_key()
+    GenKey,
+    /// import_key() and its variants
+    ImportKey,
+    /// get_public_key()
+    GetPublicKey,
+    /// sign_hash() and sign_message()
+    Sign,
+    /// verify_hash() with the public key stored in the slot
+    Verify,
+    /// ecdh()
+    Ecdh,
+    /// AES encryption modes
+    Encrypt,
+    /// AES decryption modes
+    Decrypt,
+    /// read_slot_data()
+    Read,
+    /// write_slot_data()
+    Write,
+    /// sha_hmac()
+    Hmac,
+}
+
+/// Interpretation of the configuration and content of a slot, made by slot_report()
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotReport {
+    /// Number of the slot
+    pub id: u8,
+    /// Type of key the slot is meant for
+    pub key_type: KeyType,
+    /// The slot has been individually locked and cannot be modified any more
+    pub is_locked: bool,
+    /// Write policy of the slot
+    pub write_config: WriteConfig,
+    /// Clear text reads are prohibited
+    pub is_secret: bool,
+    /// Reads are encrypted with the key from the slot given by the read key configuration
+    pub encrypt_read: bool,
+    /// The slot seems to hold a key or data: its public key or the beginning
+    /// of its content is not all zeros. None if it cannot be told, e.g. for secret keys
+    pub is_provisioned: Option<bool>,
+    /// Operations of the wrapper the configuration allows on the slot
+    pub operations: Vec<SlotOperation>,
+}
+
+/// Mode of the OTP zone of ATECC508A and older chips, from the OTPmode byte of the
+/// configuration zone. ATECC608x uses that byte for counter match instead
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OtpMode {
+    /// Writes to the OTP zone are prohibited once the data zone is locked
+    ReadOnly,
+    /// Bits of the OTP zone can only be changed from 1 to 0 once the data zone is locked
+    Consumption,
+    /// Legacy mode, not recommended by the datasheet
+    Legacy,
+    /// A value the datasheet does not define
+    Rfu(u8),
+}
+
+/// The 128-byte configuration zone of ATECC, field by field as the ATECC608x datasheet
+/// lays it out. The fields are kept raw, so that an image converted with TryFrom<&[u8]>
+/// gives the same bytes back with to_bytes(); ConfigReport interprets them.
+/// The older chips use some of the bytes otherwise: OTPmode instead of CountMatch,
+/// LastKeyUse instead of the bytes from UseLock to the last reserved one
+/// and Selector instead of UserExtraAdd
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ConfigZone {
+    /// SN[0:3] and SN[4:8]
+    pub serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
+    /// RevNum
+    pub revision: [u8; ATCA_WORD_SIZE],
+    /// AES_Enable of ATECC608x, reserved on the older chips
+    pub aes_enable: u8,
+    pub i2c_enable: u8,
+    pub reserved_15: u8,
+    /// I2C_Address, in the 8-bit form
+    pub i2c_address: u8,
+    pub reserved_17: u8,
+    /// CountMatch of ATECC608x, OTPmode of the older chips
+    pub count_match: u8,
+    pub chip_mode: u8,
+    /// SlotConfig of every slot, stored little-endian
+    pub slot_config: [u16; ATCA_ATECC_SLOTS_COUNT as usize],
+    /// Counter[0] and Counter[1], the encoded values of the monotonic counters
+    pub counters: [[u8; 8]; 2],
+    /// UseLock of ATECC608x
+    pub use_lock: u8,
+    /// VolatileKeyPermission of ATECC608x
+    pub volatile_key_permission: u8,
+    /// SecureBoot of ATECC608x
+    pub secure_boot: u16,
+    /// KdflvLoc of ATECC608x
+    pub kdf_iv_loc: u8,
+    /// KdflvStr of ATECC608x
+    pub kdf_iv_str: [u8; 2],
+    pub reserved_75: [u8; 9],
+    pub user_extra: u8,
+    /// UserExtraAdd of ATECC608x, Selector of the older chips
+    pub user_extra_add: u8,
+    /// LockValue, 0x55 while the data and OTP zones are not locked
+    pub lock_value: u8,
+    /// LockConfig, 0x55 while the configuration zone is not locked
+    pub lock_config: u8,
+    /// SlotLocked, one bit per slot, cleared when the slot is locked
+    pub slot_locked: u16,
+    pub chip_options: u16,
+    pub x509_format: [u8; ATCA_WORD_SIZE],
+    /// KeyConfig of every slot, stored little-endian
+    pub key_config: [u16; ATCA_ATECC_SLOTS_COUNT as usize],
+}
+
+/// Entry of the X509format array of the configuration zone, selected by the x509id
+/// of a slot: the format of the certificate a public key in the slot is validated with
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct X509Format {
+    /// PublicPosition, bits 0-3: where the public key starts in the certificate message
+    pub public_position: u8,
+    /// TemplateLength, bits 4-7: length of the certificate template, 0 for any format
+    pub template_length: u8,
+}
+
+/// Assembles the configuration zone image of a chip to be provisioned from the typed
+/// configuration of its slots and options, the counterpart of ConfigZone:
+/// 128 bytes for ATECC, 88 bytes for ATSHA204A. The bytes the chip does not let
+/// be written (serial number, revision...) are left zero, write_config_zone() skips them
+#[derive(Clone, Debug)]
+pub struct ConfigZoneBuilder {
+    device_type: AtcaDeviceType,
+    i2c_enable: bool,
+    /// I2C address in the 8-bit form
+    i2c_address: u8,
+    chip_mode: u8,
+    /// ATECC608x only
+    chip_options: ChipOptions,
+    /// Not used by ATECC608x
+    otp_mode: Option<OtpMode>,
+    x509_format: [u8; ATCA_WORD_SIZE],
+    /// Slots whose configuration has been given, in that order, the others are data slots
+    slots: Vec<(u8, SlotConfig)>,
+}
+
+/// Interpretation of the whole configuration zone, made by config_report().
+/// It renders as text with Display and as JSON with to_json(), for support tickets and audits
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigReport {
+    /// Type of the chip the configuration zone was read from
+    pub device_type: AtcaDeviceType,
+    pub serial_number: [u8; ATCA_SERIAL_NUM_SIZE],
+    /// Revision number, e.g. [0x00, 0x00, 0x60, 0x02] for ATECC608A
+    pub revision: [u8; ATCA_WORD_SIZE],
+    pub config_zone_locked: bool,
+    pub data_zone_locked: bool,
+    /// The chip communicates over I2C, otherwise over SWI
+    pub i2c_enabled: bool,
+    /// I2C address of the chip, in the 8-bit form
+    pub i2c_address: u8,
+    /// ChipMode byte: TTL input levels, watchdog duration, clock divider
+    pub chip_mode: u8,
+    /// Options of the chip, the AES and KDF-AES flags and the IO protection key
+    pub chip_options: ChipOptions,
+    /// Mode of the OTP zone, None for ATECC608x
+    pub otp_mode: Option<OtpMode>,
+    /// Slot of the CountMatchKey if counter match is enabled (ATECC608x only)
+    pub count_match_key: Option<u8>,
+    /// Values of the monotonic counters 0 and 1, None where they could not be read
+    pub counters: [Option<u32>; 2],
+    /// X509format bytes, the certificate formats referenced by the x509id of the slots
+    pub x509_format: [u8; ATCA_WORD_SIZE],
+    /// Configuration and lock state of every slot
+    pub slots: Vec<AtcaSlot>,
+}
+
+/// Outcome of write_otp_zone_verified(). The OTP zone cannot be erased,
+/// so the report tells exactly how far a write went and what the chip holds
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtpWriteReport {
+    /// AtcaSuccess, or the error of the write or read that failed
+    pub status: AtcaStatus,
+    /// Mode of the OTP zone, None for ATECC608x
+    pub otp_mode: Option<OtpMode>,
+    /// Number of bytes written from the offset before a write failed,
+    /// 0 if the zone already held the data
+    pub written: usize,
+    /// Result of reading the zone back. None while the data zone is not locked:
+    /// the chip does not allow reads of the OTP zone before
+    pub verified: Option<bool>,
+    /// Offsets in the OTP zone of the bytes read back that differ from the data
+    pub mismatches: Vec<usize>,
+}
+
+/// Outcome of the health check of a chip
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthReport {
+    /// Status of the wake-up, a chip that is missing or stuck does not respond to it
+    pub wake: AtcaStatus,
+    /// Revision read with the Info command, e.g. [0x00, 0x00, 0x60, 0x02] for ATECC608A
+    pub revision: Result<Vec<u8>, AtcaStatus>,
+    /// Result of the SelfTest command, if it was requested: all the tests passed
+    pub self_test: Option<Result<bool, AtcaStatus>>,
+    /// Status of generating a sample random number
+    pub random: AtcaStatus,
+    /// All of the above checks passed
+    pub is_healthy: bool,
+}
+
+/// A chip found on an I2C bus by discover()
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AtcaDiscoveredDevice {
+    /// Address of the chip, in the 8-bit form taken by AtcaIfaceI2c::set_slave_address()
+    pub slave_address: u8,
+    /// Type of the chip, as told by its revision
+    pub device_type: AtcaDeviceType,
+    /// Revision read with the Info command
+    pub revision: [u8; ATCA_WORD_SIZE],
+}
+
+/// Material for registering a device certificate with AWS IoT
+/// without registering its CA first (multi-account registration)
+#[derive(Clone, Debug, PartialEq)]
+pub struct AwsIotRegistration {
+    /// Device certificate, PEM encoded
+    pub certificate_pem: String,
+    /// ID that AWS IoT assigns to the certificate: SHA256 of its DER encoding, lowercase hex
+    pub certificate_id: String,
+}
+
+/// Identity of a device authenticating to Azure DPS and IoT Hub with an X.509 certificate
+#[derive(Clone, Debug, PartialEq)]
+pub struct AzureX509Identity {
+    /// DPS registration ID and IoT Hub device ID: the subject common name of the certificate
+    pub registration_id: String,
+    /// SHA1 thumbprint of the certificate, uppercase hex (self-signed individual enrollments)
+    pub thumbprint_sha1: String,
+    /// SHA256 thumbprint of the certificate, uppercase hex
+    pub thumbprint_sha256: String,
+}
+
+/// Where a command takes its key from: a slot of the data zone or TempKey.
+/// The trait functions take it as a number, ATCA_ATECC_SLOTS_COUNT standing for TempKey;
+/// SlotId::try_from() checks such a number and u8::from() gives it back
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SlotId {
+    /// One of the ATCA_ATECC_SLOTS_COUNT slots, created with SlotId::new() to be in range
+    Slot(u8),
+    /// The volatile key register, for the commands that can use it in place of a slot
+    TempKey,
+}
+
 /// An ATECC slot
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct AtcaSlot {
     /// ATECC slot id (for diagnostic)
     pub id: u8,
@@ -284,36 +949,16 @@ pub struct AtcaSlot {
     pub config: SlotConfig,
 }
 
-impl Default for AtcaSlot {
-    fn default() -> Self {
-        AtcaSlot {
-            id: 0u8,
-            is_locked: false,
-            config: SlotConfig::default(),
-        }
-    }
-}
-
 /// An ATECC slot capacity
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub struct AtcaSlotCapacity {
     pub blocks: u8,
     pub last_block_bytes: u8,
     pub bytes: u16,
 }
 
-impl Default for AtcaSlotCapacity {
-    fn default() -> Self {
-        AtcaSlotCapacity {
-            blocks: 0u8,
-            last_block_bytes: 0u8,
-            bytes: 0u16,
-        }
-    }
-}
-
 /// Detailed ATECC key slot configuration
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct SlotConfig {
     /// Controls the ability to modify the data in this slot.
     pub write_config: WriteConfig,
@@ -362,6 +1007,11 @@ pub struct SlotConfig {
     /// false = There are no usage limitations.
     pub limited_use: bool,
 
+    /// true = Counter match is enabled (ATECC608x only) and this slot is the CountMatchKey,
+    /// it holds the match value the monotonic counter 0 is compared against.
+    /// false = The slot plays no role in counter match.
+    pub count_match: bool,
+
     /// true = The key stored in the slot is intended for verification usage
     /// and cannot be used by the MAC or HMAC commands.
     /// When this key is used to generate or modify TempKey,
@@ -427,6 +1077,7 @@ impl Default for SlotConfig {
             write_key: 0u8,
             is_secret: false,
             limited_use: false,
+            count_match: false,
             no_mac: false,
             persistent_disable: false,
             req_auth: false,
@@ -438,7 +1089,7 @@ impl Default for SlotConfig {
 }
 
 /// Detailed ECC key attributes as stored in slot configuration
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct EccKeyAttr {
     /// true = The key slot contains an ECC private key and
     /// can be accessed only with the Sign, GenKey, and PrivWrite commands.
@@ -471,20 +1122,8 @@ pub struct EccKeyAttr {
     pub ecdh_secret_out: bool,
 }
 
-impl Default for EccKeyAttr {
-    fn default() -> Self {
-        EccKeyAttr {
-            is_private: false,
-            ext_sign: false,
-            int_sign: false,
-            ecdh_operation: false,
-            ecdh_secret_out: false,
-        }
-    }
-}
-
 /// Detailed ATECC key slot read attributes
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct ReadKey {
     /// true = Reads from this slot will be encrypted using the procedure
     /// specified in the Read command using value of 'slot_number'
@@ -503,15 +1142,6 @@ pub struct ReadKey {
     pub slot_number: u8,
 }
 
-impl Default for ReadKey {
-    fn default() -> Self {
-        ReadKey {
-            encrypt_read: false,
-            slot_number: 0u8,
-        }
-    }
-}
-
 /// Detailed ATECC key slot write configuration
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum WriteConfig {
@@ -563,6 +1193,13 @@ pub struct AtcaIfaceCfg {
     iface: Option<AtcaIface>,
     wake_delay: u16,
     rx_retries: i32,
+    /// Power mode the chip is put into after each command
+    power_policy: AtcaPowerPolicy,
+    /// Read the configuration of the chip when first needed rather than at initialization
+    lazy_init: bool,
+    /// Address of the server for "remote" interface
+    #[cfg(feature = "remote")]
+    remote_address: Option<std::net::SocketAddr>,
 } // pub struct AtcaIfaceCfg
 
 /// ATECC interface
@@ -571,13 +1208,16 @@ pub struct AtcaIfaceCfg {
 pub union AtcaIface {
     /// ATECC I2C interface settings
     pub atcai2c: AtcaIfaceI2c,
-    // pub atcaswi: AtcaIfaceSwi,
-    // pub atcauart: AtcaIfaceUart,
-    // pub atcahid: AtcaIfaceHid,
+    /// ATECC SWI interface settings
+    pub atcaswi: AtcaIfaceSwi,
+    /// ATECC UART kit interface settings
+    pub atcauart: AtcaIfaceUart,
+    /// ATECC USB HID kit interface settings
+    pub atcahid: AtcaIfaceHid,
 } // pub union AtcaIface
 
 /// ATECC I2C interface details
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Default)]
 pub struct AtcaIfaceI2c {
     /// ATECC I2C bus address
     slave_address: u8,
@@ -587,6 +1227,66 @@ pub struct AtcaIfaceI2c {
     baud: u32,
 } // pub struct AtcaIfaceI2c
 
+/// ATECC SWI (single-wire) interface details.
+/// The wire is driven through a UART (bit-banged by the CryptoAuthLib HAL),
+/// the wake token and the wake delay are handled as for I2C.
+#[derive(Copy, Clone, Default)]
+pub struct AtcaIfaceSwi {
+    /// Number of the UART/GPIO bus the chip is wired to
+    bus: u8,
+} // pub struct AtcaIfaceSwi
+
+/// USB HID kit interface details (AT88CK590, CryptoTrust and similar development kits).
+/// The kit firmware relays the commands to the chip with the "kit protocol".
+#[derive(Copy, Clone)]
+pub struct AtcaIfaceHid {
+    /// Index of the kit, if more than one with the same VID/PID is connected
+    idx: i32,
+    /// Interface between the kit and the chip
+    dev_interface: AtcaKitType,
+    /// Identity of the chip on the kit interface (I2C address or SWI index)
+    dev_identity: u8,
+    /// USB vendor ID of the kit
+    vid: u32,
+    /// USB product ID of the kit
+    pid: u32,
+    /// Size of a HID report
+    packetsize: u32,
+} // pub struct AtcaIfaceHid
+
+/// UART kit interface details: the "kit protocol" over a serial port,
+/// for boards bridging the chip through a USB-serial MCU
+#[derive(Copy, Clone)]
+pub struct AtcaIfaceUart {
+    /// Number of the serial port (e.g. 0 for /dev/ttyACM0 or COM0)
+    port: i32,
+    /// Baud rate of the serial port
+    baud: u32,
+    /// Number of data bits
+    wordsize: u8,
+    /// Parity of the serial port
+    parity: AtcaUartParity,
+    /// Number of stop bits
+    stopbits: u8,
+} // pub struct AtcaIfaceUart
+
+/// Parity of a serial port
+#[derive(PartialEq, Debug, Copy, Clone, Display)]
+pub enum AtcaUartParity {
+    Even,
+    Odd,
+    None,
+} // pub enum AtcaUartParity
+
+/// Interface between a development kit and the chip
+#[derive(PartialEq, Debug, Copy, Clone, Display)]
+pub enum AtcaKitType {
+    /// Detected by the kit
+    AtcaKitAutoIface,
+    AtcaKitI2cIface,
+    AtcaKitSwiIface,
+} // pub enum AtcaKitType
+
 /// Supported ATECC interfaces
 #[derive(PartialEq, Copy, Clone, Display)]
 pub enum AtcaIfaceType {
@@ -596,10 +1296,31 @@ pub enum AtcaIfaceType {
     AtcaSpiIface,
     AtcaHidIface,
     AtcaCustomIface,
+    /// I2C bus access done by a Rust Linux HAL (i2cdev) in place of the C one,
+    /// configured with the same AtcaIfaceI2c settings. It is an alternative HAL
+    /// only, the commands are still executed by the C CryptoAuthLib
+    AtcaI2cRustIface,
+    /// ATECC608A emulated in memory by SoftwareBackend
+    AtcaSoftwareIface,
+    /// ATECC608A simulated by AteccSimulator
+    AtcaSimulatorIface,
+    /// ATECC of another host, reached over TCP by AteccRemoteDevice
+    AtcaRemoteIface,
     AtcaTestIface,
     AtcaUnknownIface,
 } // pub enum AtcaIfaceType
 
+/// Power mode the chip is put into after each command
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub enum AtcaPowerPolicy {
+    /// The idle mode, as CryptoAuthLib does: TempKey and the other volatile state
+    /// are kept and the chip wakes up quickly for the next command
+    Idle,
+    /// The sleep mode, drawing the least current. The volatile state is lost,
+    /// so commands relying on the state left by a previous one fail
+    Sleep,
+} // pub enum AtcaPowerPolicy
+
 /// ATECC/ATSHA device types supported by CryptoAuth library
 #[derive(PartialEq, Debug, Display, Copy, Clone)]
 pub enum AtcaDeviceType {
@@ -616,7 +1337,7 @@ pub enum AtcaDeviceType {
 } // pub enum AtcaDeviceType
 
 /// Return status for device accessing functions
-#[derive(Debug, Copy, Clone, Display, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AtcaStatus {
     /// Function succeeded.
     AtcaSuccess,
@@ -692,8 +1413,76 @@ pub enum AtcaStatus {
     AtcaUseFlagsConsumed,
     /// Unknown error occured
     AtcaUnknown,
+    /// The command was cancelled while waiting for its turn, it was not sent to the chip
+    AtcaCancelled,
+    /// The watchdog of the chip expired before a command completed, the chip went to sleep
+    /// and lost TempKey. The chip responds again, so the command can be retried
+    AtcaWatchdogExpired,
+    /// The limited-use key has no uses left, the command was not sent to the chip
+    AtcaKeyUsesExhausted,
+    /// The chip cannot execute the operation, e.g. ATSHA206A has no ECC,
+    /// the command was not sent to it
+    AtcaUnsupportedByDevice,
 } // pub enum AtcaStatus
 
+impl std::fmt::Display for AtcaStatus {
+    /// The name of the status followed by the code CryptoAuthLib gives it,
+    /// e.g. "AtcaCheckMacVerifyFailed (0xD1)". The statuses the wrapper adds
+    /// show the code returned for them through the C API
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} (0x{:02X})", self, cryptoauthlib_sys::ATCA_STATUS::from(*self))
+    }
+}
+
+impl std::error::Error for AtcaStatus {}
+
+/// Error in the configuration of the interface and the device
+#[derive(Debug, Clone, PartialEq)]
+pub enum AtcaConfigError {
+    /// The configuration could not be read
+    Io(String),
+    /// The configuration is malformed or has settings that do not exist
+    Parse(String),
+    /// The interface type is not known, or its feature is not enabled.
+    /// The name is empty if it is not known any more, as in AtcaIfaceCfg::build()
+    UnknownIfaceType(String),
+    /// The device type is not known, the name is empty if it is not known any more
+    UnknownDeviceType(String),
+    /// A setting the interface type needs is missing
+    MissingSetting(&'static str),
+    /// A setting has a value that cannot be used
+    InvalidSetting(&'static str, String),
+} // pub enum AtcaConfigError
+
+impl std::fmt::Display for AtcaConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AtcaConfigError::Io(err) => write!(f, "cannot read configuration: {}", err),
+            AtcaConfigError::Parse(err) => write!(f, "malformed configuration: {}", err),
+            AtcaConfigError::UnknownIfaceType(iface_type) if iface_type.is_empty() => {
+                write!(f, "interface type not set or not supported")
+            }
+            AtcaConfigError::UnknownIfaceType(iface_type) => {
+                write!(f, "unsupported interface type '{}'", iface_type)
+            }
+            AtcaConfigError::UnknownDeviceType(device_type) if device_type.is_empty() => {
+                write!(f, "device type not set or not supported")
+            }
+            AtcaConfigError::UnknownDeviceType(device_type) => {
+                write!(f, "unsupported device type '{}'", device_type)
+            }
+            AtcaConfigError::MissingSetting(name) => {
+                write!(f, "'{}' is required by the interface type", name)
+            }
+            AtcaConfigError::InvalidSetting(name, reason) => {
+                write!(f, "invalid '{}': {}", name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AtcaConfigError {}
+
 #[derive(Debug)]
 struct AtcaIfaceCfgPtrWrapper {
     ptr: *mut cryptoauthlib_sys::ATCAIfaceCfg,
@@ -1,18 +1,126 @@
 // Types
 use super::{
-    AtcaSlot, AtcaStatus, InfoCmdType, KeyType, NonceTarget, SignEcdsaParam, SignMode,
-    VerifyEcdsaParam, VerifyMode,
+    AtcaDeviceType, AtcaIface, AtcaIfaceType, AtcaSlot, AtcaStatus, AteccRng, CsrParam,
+    DnAttribute, EcdhOutput, InfoCmdType, KdfAlgorithm, KdfSource, KdfTarget, KeyType, NonceTarget,
+    OutputProtectionState, SignEcdsaParam, SignMode, SignatureFormat, SubjectAltName,
+    TempKeySource, TlsFinishedLabel, VerifyEcdsaParam, VerifyMode, WriteConfig,
 };
 // Constants
 use super::{
-    ATCA_AES_KEY_SIZE, ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_NONCE_NUMIN_SIZE,
-    ATCA_RANDOM_BUFFER_SIZE, ATCA_SIG_SIZE,
+    ATCA_AES_KEY_SIZE, ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_BLOCK_SIZE,
+    ATCA_ECDH_PMS_SIZE, ATCA_ECIES_OVERHEAD, ATCA_KEY_SIZE, ATCA_NONCE_NUMIN_SIZE,
+    ATCA_RANDOM_BUFFER_SIZE, ATCA_SHA2_256_DIGEST_SIZE, ATCA_SIG_SIZE, ATCA_ZONE_DATA,
 };
 
+// Functions
+#[cfg(feature = "rustls")]
+use super::AteccClientCertResolver;
+#[cfg(feature = "rustcrypto")]
+use super::SlotSigner;
+use super::{
+    aws_iot_registration, azure_dps_sas_token, azure_x509_identity, cose_sign1_sign,
+    cose_sign1_verify, csr_to_pem, discover, generate_csr, jwt_claims, jwt_sign, jwt_verify,
+    setup_atecc_device,
+};
+#[cfg(feature = "parsec")]
+use super::{AteccParsecProvider, ParsecKeyProvider};
+#[cfg(feature = "embedded-tls")]
+use super::{TlsKeyExchange, TlsSigner, TLS_ECDSA_SECP256R1_SHA256};
+#[cfg(any(feature = "rustcrypto", feature = "embedded-tls"))]
+use signature::{Signer, Verifier};
+
 use super::hw_backend_common::*;
 use super::hw_impl::atcab_get_config_from_config_zone;
+use super::tng;
+use super::{ATCACERT_TEST_CA_PUBLIC_KEY, ATCACERT_TEST_CERT};
+use rand::RngCore;
+#[cfg(feature = "rustls")]
+use rustls::{client::ResolvesClientCert, SignatureScheme};
 use serial_test::serial;
 
+#[test]
+#[serial]
+fn i2c_discovery() {
+    let iface_cfg = test_iface_cfg();
+    if iface_cfg.iface_type != AtcaIfaceType::AtcaI2cIface {
+        return;
+    }
+    let atcai2c = unsafe { iface_cfg.iface.unwrap().atcai2c };
+
+    let found = discover(atcai2c.bus);
+    let chip = found
+        .iter()
+        .find(|chip| chip.slave_address == atcai2c.slave_address)
+        .expect("The chip was not found on the bus");
+    assert_eq!(chip.device_type, iface_cfg.devtype);
+
+    // The chip is skipped while a device uses it
+    let device = test_setup();
+    let found = discover(atcai2c.bus);
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+    assert!(found
+        .iter()
+        .all(|chip| chip.slave_address != atcai2c.slave_address));
+}
+
+#[test]
+#[serial]
+fn multiple_devices() {
+    let device = test_setup();
+    let iface_cfg = test_iface_cfg();
+
+    // The chip is already used by 'device'
+    let second_device = setup_atecc_device(iface_cfg);
+    // No chip at this address: the failed setup must not affect 'device'
+    let absent_device = match iface_cfg.iface_type {
+        AtcaIfaceType::AtcaI2cIface => {
+            let atcai2c = unsafe { iface_cfg.iface.unwrap().atcai2c };
+            let iface = AtcaIface::default()
+                .set_atcai2c(atcai2c.set_slave_address(atcai2c.slave_address.wrapping_add(0x10)));
+            Some(setup_atecc_device(iface_cfg.set_iface(iface)))
+        }
+        _ => None,
+    };
+    let mut rand_out = Vec::new();
+    let device_random = device.random(&mut rand_out);
+
+    let mut expected = AtcaStatus::AtcaSuccess;
+    if !device.is_configuration_locked() {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration not Locked!\u{001b}[0m");
+        expected = AtcaStatus::AtcaNotLocked;
+    }
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+    assert_eq!(device_random, expected);
+    assert_eq!(
+        second_device.err(),
+        Some(AtcaStatus::AtcaAllocFailure.to_string())
+    );
+    if let Some(absent_device) = absent_device {
+        assert!(absent_device.is_err());
+    }
+
+    // Released, the chip can be used again
+    let device = setup_atecc_device(iface_cfg);
+    assert!(device.is_ok());
+    assert_eq!(device.unwrap().release(), AtcaStatus::AtcaSuccess);
+}
+
+#[test]
+#[serial]
+fn drop_without_release() {
+    let device = test_setup();
+    drop(device);
+
+    // Dropped, the chip can be used again
+    let device = test_setup();
+    let device_release = device.release();
+    let device_release_again = device.release();
+    drop(device);
+
+    assert_eq!(device_release, AtcaStatus::AtcaSuccess);
+    assert_eq!(device_release_again, AtcaStatus::AtcaBadParam);
+}
+
 #[test]
 #[serial]
 fn new() {
@@ -24,11 +132,11 @@ fn new() {
     let mut slots = Vec::new();
     let get_config = device.get_config(&mut slots);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(serial_number[0], 0x01);
     assert_eq!(serial_number[1], 0x23);
-    assert_eq!(get_config.to_string(), "AtcaSuccess");
+    assert_eq!(get_config, AtcaStatus::AtcaSuccess);
     assert_eq!(slots.len(), SLOTS_COUNT);
     assert_eq!(slots[0].id, 0);
     assert_eq!(slots[SLOTS_COUNT - 1].id, (SLOTS_COUNT - 1) as u8);
@@ -57,7 +165,7 @@ fn sha() {
         expected = AtcaStatus::AtcaNotLocked;
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(device_sha, expected);
     if AtcaStatus::AtcaSuccess == expected {
@@ -90,11 +198,11 @@ fn nonce() {
         }
     };
 
-    let nonce_32_ok = device.nonce(NonceTarget::TempKey, &nonce_32);
+    let nonce_32_ok = device.nonce(NonceTarget::TempKey, nonce_32);
     let nonce_64_ok = device.nonce(NonceTarget::MsgDigBuf, &nonce_64);
-    let nonce_bad = device.nonce(NonceTarget::TempKey, &nonce_too_small);
+    let nonce_bad = device.nonce(NonceTarget::TempKey, nonce_too_small);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(nonce_32_ok, AtcaStatus::AtcaSuccess);
     assert_eq!(nonce_64_ok, expected);
@@ -102,6 +210,30 @@ fn nonce() {
     assert_eq!(check_ver_result, AtcaStatus::AtcaSuccess);
 }
 
+#[test]
+#[serial]
+fn tempkey_state() {
+    let device = test_setup();
+
+    let nonce = [0x5A; ATCA_KEY_SIZE];
+    let nonce_result = device.nonce(NonceTarget::TempKey, &nonce);
+    let loaded = device.tempkey_state();
+    let mut rand_out = Vec::new();
+    let random_result = device.random(&mut rand_out);
+    let lost = device.tempkey_state();
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(nonce_result, AtcaStatus::AtcaSuccess);
+    assert!(loaded.valid);
+    assert_eq!(loaded.source, Some(TempKeySource::Nonce));
+    assert_eq!(loaded.size, ATCA_KEY_SIZE);
+    assert_eq!(random_result, AtcaStatus::AtcaSuccess);
+    assert!(!lost.valid);
+    assert_eq!(lost.source, Some(TempKeySource::Nonce));
+    assert_eq!(lost.invalidated_by, Some("random"));
+}
+
 #[test]
 #[serial]
 fn nonce_rand() {
@@ -115,15 +247,33 @@ fn nonce_rand() {
     let mut rand_out = Vec::new();
 
     let nonce_ok = device.nonce_rand(&nonce, &mut rand_out);
-    let nonce_bad = device.nonce_rand(&nonce_too_small, &mut rand_out);
+    let nonce_bad = device.nonce_rand(nonce_too_small, &mut rand_out);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(rand_out.len(), ATCA_RANDOM_BUFFER_SIZE);
     assert_eq!(nonce_ok, AtcaStatus::AtcaSuccess);
     assert_eq!(nonce_bad, AtcaStatus::AtcaInvalidSize);
 }
 
+#[test]
+#[serial]
+fn set_host_nonce() {
+    let device = test_setup();
+
+    let num_in = [0xA5; ATCA_NONCE_NUMIN_SIZE];
+
+    let set_ok = device.set_host_nonce(Some(&num_in));
+    let set_bad = device.set_host_nonce(Some(&num_in[..10]));
+    let discard = device.set_host_nonce(None);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(set_ok, AtcaStatus::AtcaSuccess);
+    assert_eq!(set_bad, AtcaStatus::AtcaInvalidSize);
+    assert_eq!(discard, AtcaStatus::AtcaSuccess);
+}
+
 #[test]
 #[serial]
 fn gen_key() {
@@ -178,7 +328,7 @@ fn gen_key() {
     let device_gen_key_bad_3 = device.gen_key(KeyType::P256EccKey, ATCA_ATECC_SLOTS_COUNT);
     let device_gen_key_bad_4 = device.gen_key(KeyType::ShaOrText, 0x00);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(write_key_set_success, AtcaStatus::AtcaSuccess);
     assert_eq!(device_gen_key_ok_1, expected_device_gen_key_ok_1);
@@ -258,20 +408,20 @@ fn import_key() {
     }
 
     let priv_key_ok = device.import_key(KeyType::P256EccKey, &priv_key, 0x02);
-    let priv_key_bad_1 = device.import_key(KeyType::P256EccKey, &priv_key_bad, 0x00);
+    let priv_key_bad_1 = device.import_key(KeyType::P256EccKey, priv_key_bad, 0x00);
     let priv_key_bad_2 = device.import_key(KeyType::P256EccKey, &priv_key, 0x01);
 
     let pub_key_ok = device.import_key(KeyType::P256EccKey, &pub_key, 0x0B);
-    let pub_key_bad_1 = device.import_key(KeyType::P256EccKey, &pub_key_bad, 0x0B);
+    let pub_key_bad_1 = device.import_key(KeyType::P256EccKey, pub_key_bad, 0x0B);
     // slot number too low
     let pub_key_bad_2 = device.import_key(KeyType::P256EccKey, &pub_key, 0x03);
     // writing to a slot with a key type other than P256
     let pub_key_bad_3 = device.import_key(KeyType::P256EccKey, &pub_key, 0x0C);
 
-    let aes_key_ok = device.import_key(KeyType::Aes, &aes_key, 0x09);
-    let aes_key_bad_1 = device.import_key(KeyType::Aes, &aes_key_bad, 0x09);
+    let aes_key_ok = device.import_key(KeyType::Aes, aes_key, 0x09);
+    let aes_key_bad_1 = device.import_key(KeyType::Aes, aes_key_bad, 0x09);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(write_key_set_success, AtcaStatus::AtcaSuccess);
 
@@ -288,6 +438,463 @@ fn import_key() {
     assert_eq!(aes_key_bad_1, expected_aes_key_bad_1);
 }
 
+#[test]
+#[serial]
+fn import_key_sha_or_text() {
+    let device = test_setup();
+
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    let get_config = device.get_config(&mut slots);
+    // looking for a slot (with at least two full blocks) that can be written
+    // and read back in clear text
+    let text_slot = slots.iter().find(|slot| {
+        (slot.id >= 0x08)
+            && (slot.config.key_type == KeyType::ShaOrText)
+            && (slot.config.write_config == WriteConfig::Always)
+            && !slot.config.is_secret
+    });
+    let (slot_id, capacity) = match text_slot {
+        Some(slot) => (slot.id, if slot.id == 0x08 { 416 } else { 72 }),
+        None => {
+            println!("\u{001b}[1m\u{001b}[33mNo clear-text ShaOrText slot found!\u{001b}[0m ");
+            assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+            return;
+        }
+    };
+
+    // 1.5 blocks of data, the trailing half block is written word by word
+    let text_data: Vec<u8> = (0..(ATCA_BLOCK_SIZE + ATCA_BLOCK_SIZE / 2) as u8).collect();
+    let text_data_bad: Vec<u8> = vec![0x5A; capacity + 1];
+
+    let mut expected_text_ok = AtcaStatus::AtcaSuccess;
+    let mut expected_text_bad_1 = AtcaStatus::AtcaInvalidSize;
+    let mut expected_text_bad_2 = AtcaStatus::AtcaInvalidSize;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+
+        expected_text_ok = AtcaStatus::AtcaNotLocked;
+        expected_text_bad_1 = AtcaStatus::AtcaNotLocked;
+        expected_text_bad_2 = AtcaStatus::AtcaNotLocked;
+    }
+
+    let text_ok = device.import_key(KeyType::ShaOrText, &text_data, slot_id);
+    // data longer than the slot capacity
+    let text_bad_1 = device.import_key(KeyType::ShaOrText, &text_data_bad, slot_id);
+    // empty data
+    let text_bad_2 = device.import_key(KeyType::ShaOrText, &[], slot_id);
+
+    let mut read_back: Vec<u8> = Vec::new();
+    let mut read_status = AtcaStatus::AtcaSuccess;
+    if AtcaStatus::AtcaSuccess == text_ok {
+        for block in 0..2 {
+            let mut data: Vec<u8> = vec![0; ATCA_BLOCK_SIZE];
+            read_status = device.read_zone(
+                ATCA_ZONE_DATA,
+                slot_id as u16,
+                block,
+                0,
+                &mut data,
+                ATCA_BLOCK_SIZE as u8,
+            );
+            if AtcaStatus::AtcaSuccess != read_status {
+                break;
+            }
+            read_back.extend_from_slice(&data);
+        }
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(get_config, AtcaStatus::AtcaSuccess);
+    assert_eq!(text_ok, expected_text_ok);
+    assert_eq!(text_bad_1, expected_text_bad_1);
+    assert_eq!(text_bad_2, expected_text_bad_2);
+    if AtcaStatus::AtcaSuccess == text_ok {
+        assert_eq!(read_status, AtcaStatus::AtcaSuccess);
+        assert_eq!(read_back[..text_data.len()], text_data[..]);
+    }
+}
+
+#[test]
+#[serial]
+fn write_slot_data() {
+    let device = test_setup();
+
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    let get_config = device.get_config(&mut slots);
+    // looking for a slot (with at least two full blocks) that can be written
+    // and read back in clear text
+    let text_slot = slots.iter().find(|slot| {
+        (slot.id >= 0x08)
+            && (slot.config.key_type == KeyType::ShaOrText)
+            && (slot.config.write_config == WriteConfig::Always)
+            && !slot.config.is_secret
+    });
+    let (slot_id, capacity) = match text_slot {
+        Some(slot) => (slot.id, if slot.id == 0x08 { 416 } else { 72 }),
+        None => {
+            println!("\u{001b}[1m\u{001b}[33mNo clear-text ShaOrText slot found!\u{001b}[0m ");
+            assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+            return;
+        }
+    };
+
+    const OFFSET: usize = 28;
+    // crosses the boundary between the first and the second block
+    let data: [u8; 12] = [
+        0xA0, 0xA1, 0xA2, 0xA3, 0xB0, 0xB1, 0xB2, 0xB3, 0xC0, 0xC1, 0xC2, 0xC3,
+    ];
+
+    let mut expected_write_ok = AtcaStatus::AtcaSuccess;
+    let mut expected_write_bad_1 = AtcaStatus::AtcaInvalidSize;
+    let mut expected_write_bad_2 = AtcaStatus::AtcaInvalidSize;
+    let mut expected_write_bad_3 = AtcaStatus::AtcaInvalidId;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+
+        expected_write_ok = AtcaStatus::AtcaNotLocked;
+        expected_write_bad_1 = AtcaStatus::AtcaNotLocked;
+        expected_write_bad_2 = AtcaStatus::AtcaNotLocked;
+        expected_write_bad_3 = AtcaStatus::AtcaNotLocked;
+    }
+
+    let write_ok = device.write_slot_data(slot_id, OFFSET, &data);
+    // offset not aligned to a word
+    let write_bad_1 = device.write_slot_data(slot_id, OFFSET + 1, &data);
+    // data does not fit into the slot
+    let write_bad_2 = device.write_slot_data(slot_id, capacity - 4, &data);
+    // slot number too high
+    let write_bad_3 = device.write_slot_data(ATCA_ATECC_SLOTS_COUNT, 0, &data);
+
+    let mut read_back: Vec<u8> = Vec::new();
+    let mut read_status = AtcaStatus::AtcaSuccess;
+    if AtcaStatus::AtcaSuccess == write_ok {
+        for block in 0..2 {
+            let mut block_data: Vec<u8> = vec![0; ATCA_BLOCK_SIZE];
+            read_status = device.read_zone(
+                ATCA_ZONE_DATA,
+                slot_id as u16,
+                block,
+                0,
+                &mut block_data,
+                ATCA_BLOCK_SIZE as u8,
+            );
+            if AtcaStatus::AtcaSuccess != read_status {
+                break;
+            }
+            read_back.extend_from_slice(&block_data);
+        }
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(get_config, AtcaStatus::AtcaSuccess);
+    assert_eq!(write_ok, expected_write_ok);
+    assert_eq!(write_bad_1, expected_write_bad_1);
+    assert_eq!(write_bad_2, expected_write_bad_2);
+    assert_eq!(write_bad_3, expected_write_bad_3);
+    if AtcaStatus::AtcaSuccess == write_ok {
+        assert_eq!(read_status, AtcaStatus::AtcaSuccess);
+        assert_eq!(read_back[OFFSET..(OFFSET + data.len())], data[..]);
+    }
+}
+
+#[test]
+#[serial]
+fn read_slot_data() {
+    let device = test_setup();
+
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    let get_config = device.get_config(&mut slots);
+    // looking for a slot (with at least two full blocks) that can be written
+    // and read back in clear text
+    let text_slot = slots.iter().find(|slot| {
+        (slot.id >= 0x08)
+            && (slot.config.key_type == KeyType::ShaOrText)
+            && (slot.config.write_config == WriteConfig::Always)
+            && !slot.config.is_secret
+    });
+    let (slot_id, capacity) = match text_slot {
+        Some(slot) => (slot.id, if slot.id == 0x08 { 416 } else { 72 }),
+        None => {
+            println!("\u{001b}[1m\u{001b}[33mNo clear-text ShaOrText slot found!\u{001b}[0m ");
+            assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+            return;
+        }
+    };
+
+    const OFFSET: usize = 24;
+    // crosses the boundary between the first and the second block
+    let data: [u8; 16] = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E,
+        0x1F,
+    ];
+
+    let mut expected_read_ok = AtcaStatus::AtcaSuccess;
+    let mut expected_read_bad_1 = AtcaStatus::AtcaInvalidSize;
+    let mut expected_read_bad_2 = AtcaStatus::AtcaInvalidSize;
+    let mut expected_read_bad_3 = AtcaStatus::AtcaInvalidId;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+
+        expected_read_ok = AtcaStatus::AtcaNotLocked;
+        expected_read_bad_1 = AtcaStatus::AtcaNotLocked;
+        expected_read_bad_2 = AtcaStatus::AtcaNotLocked;
+        expected_read_bad_3 = AtcaStatus::AtcaNotLocked;
+    }
+
+    let write_result = device.write_slot_data(slot_id, OFFSET, &data);
+
+    let mut read_back: Vec<u8> = Vec::new();
+    // reading from an offset not aligned to a word
+    let read_ok = match device.read_slot_data(slot_id, OFFSET + 1, data.len() - 2) {
+        Ok(val) => {
+            read_back = val;
+            AtcaStatus::AtcaSuccess
+        }
+        Err(err) => err,
+    };
+    // nothing to read
+    let read_bad_1 = device.read_slot_data(slot_id, OFFSET, 0).err();
+    // data does not fit into the slot
+    let read_bad_2 = device.read_slot_data(slot_id, capacity - 4, 8).err();
+    // slot number too high
+    let read_bad_3 = device.read_slot_data(ATCA_ATECC_SLOTS_COUNT, 0, 4).err();
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(get_config, AtcaStatus::AtcaSuccess);
+    assert_eq!(write_result, expected_read_ok);
+    assert_eq!(read_ok, expected_read_ok);
+    assert_eq!(read_bad_1, Some(expected_read_bad_1));
+    assert_eq!(read_bad_2, Some(expected_read_bad_2));
+    assert_eq!(read_bad_3, Some(expected_read_bad_3));
+    if AtcaStatus::AtcaSuccess == read_ok {
+        assert_eq!(read_back[..], data[1..(data.len() - 1)]);
+    }
+}
+
+#[test]
+#[serial]
+fn io_protection_session() {
+    const IO_KEY: [u8; ATCA_KEY_SIZE] = [0x37; ATCA_KEY_SIZE];
+
+    let device = test_setup();
+
+    let mut expected_session_ok = AtcaStatus::AtcaSuccess;
+    let mut expected_session_bad = AtcaStatus::AtcaInvalidSize;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+
+        expected_session_ok = AtcaStatus::AtcaNotLocked;
+        expected_session_bad = AtcaStatus::AtcaNotLocked;
+    } else if (device.get_device_type() != AtcaDeviceType::ATECC608A)
+        || !device.is_io_protection_key_enabled()
+    {
+        expected_session_ok = AtcaStatus::AtcaBadParam;
+        expected_session_bad = AtcaStatus::AtcaBadParam;
+    }
+
+    let session_bad = device
+        .open_io_protection_session(&IO_KEY[..(ATCA_KEY_SIZE - 1)])
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+
+    let mut ecdh_bad = AtcaStatus::AtcaSuccess;
+    let mut verify_bad = AtcaStatus::AtcaSuccess;
+    let session_ok = match device.open_io_protection_session(&IO_KEY) {
+        Ok(session) => {
+            // public key too short
+            ecdh_bad = device
+                .ecdh_io_protected(&session, 0x00, &IO_KEY)
+                .err()
+                .unwrap_or(AtcaStatus::AtcaSuccess);
+            // mode with verification MAC parameters is not accepted
+            verify_bad = device
+                .verify_hash_io_protected(
+                    &session,
+                    VerifyMode::ExternalMac(VerifyEcdsaParam {
+                        public_key: None,
+                        slot_number: None,
+                        num_in: Vec::new(),
+                        io_key: session.io_key_slot(),
+                    }),
+                    &IO_KEY,
+                    &[0; ATCA_SIG_SIZE],
+                )
+                .err()
+                .unwrap_or(AtcaStatus::AtcaSuccess);
+            AtcaStatus::AtcaSuccess
+        }
+        Err(err) => err,
+    };
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(session_ok, expected_session_ok);
+    assert_eq!(session_bad, expected_session_bad);
+    if AtcaStatus::AtcaSuccess == session_ok {
+        assert_eq!(ecdh_bad, AtcaStatus::AtcaInvalidSize);
+        assert_eq!(verify_bad, AtcaStatus::AtcaBadParam);
+    }
+}
+
+#[test]
+#[serial]
+fn ecdh() {
+    const PRIV_KEY_SLOT: u8 = 0x00;
+
+    let device = test_setup();
+
+    let pub_key = [
+        0xBA, 0x6A, 0xB5, 0xF1, 0x19, 0xAF, 0x21, 0x73, 0x03, 0x75, 0xD1, 0x8D, 0x6B, 0x5F, 0xF1,
+        0x94, 0x33, 0xE5, 0x3A, 0xEE, 0x5F, 0x6F, 0xBA, 0x22, 0x97, 0x77, 0x13, 0xEA, 0x82, 0xD3,
+        0x74, 0x84, 0x8E, 0x39, 0x78, 0x66, 0xE8, 0x36, 0xB3, 0xFE, 0xD3, 0x22, 0x87, 0x74, 0xA5,
+        0x00, 0xC5, 0x5C, 0x17, 0x73, 0x5A, 0x92, 0x4B, 0xB3, 0x9F, 0xE4, 0x98, 0x52, 0x62, 0xA5,
+        0x36, 0xC5, 0x00, 0x9C,
+    ];
+
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    let get_config = device.get_config(&mut slots);
+    let ecc_key_attr = slots[PRIV_KEY_SLOT as usize].config.ecc_key_attr;
+
+    let mut expected_ecdh_ok = AtcaStatus::AtcaSuccess;
+    let mut expected_ecdh_bad_1 = AtcaStatus::AtcaInvalidSize;
+    let mut expected_ecdh_bad_2 = AtcaStatus::AtcaInvalidId;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+
+        expected_ecdh_ok = AtcaStatus::AtcaNotLocked;
+        expected_ecdh_bad_1 = AtcaStatus::AtcaNotLocked;
+        expected_ecdh_bad_2 = AtcaStatus::AtcaNotLocked;
+    } else if !(ecc_key_attr.is_private && ecc_key_attr.ecdh_operation) {
+        expected_ecdh_ok = AtcaStatus::AtcaBadParam;
+        expected_ecdh_bad_1 = AtcaStatus::AtcaBadParam;
+    }
+
+    let mut ecdh_output: Option<EcdhOutput> = None;
+    let ecdh_ok = match device.ecdh(PRIV_KEY_SLOT, &pub_key, None) {
+        Ok(val) => {
+            ecdh_output = Some(val);
+            AtcaStatus::AtcaSuccess
+        }
+        Err(err) => err,
+    };
+    // public key too short
+    let ecdh_bad_1 = device
+        .ecdh(PRIV_KEY_SLOT, &pub_key[..ATCA_KEY_SIZE], None)
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+    // slot number too high
+    let ecdh_bad_2 = device
+        .ecdh(ATCA_ATECC_SLOTS_COUNT + 1, &pub_key, None)
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+    let protection = device.get_ecdh_output_protection_state();
+    let device_type = device.get_device_type();
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(get_config, AtcaStatus::AtcaSuccess);
+    assert_eq!(ecdh_ok, expected_ecdh_ok);
+    assert_eq!(ecdh_bad_1, expected_ecdh_bad_1);
+    assert_eq!(ecdh_bad_2, expected_ecdh_bad_2);
+    match ecdh_output {
+        Some(EcdhOutput::PreMasterSecret(pms)) => {
+            assert_eq!(pms.len(), ATCA_ECDH_PMS_SIZE);
+            assert!(!ecc_key_attr.ecdh_secret_out);
+        }
+        Some(EcdhOutput::Slot(slot)) => {
+            assert!(ecc_key_attr.ecdh_secret_out);
+            assert_eq!(slot, PRIV_KEY_SLOT + 1);
+        }
+        Some(EcdhOutput::TempKey) => {
+            assert_eq!(device_type, AtcaDeviceType::ATECC608A);
+            assert_ne!(protection, OutputProtectionState::ClearTextAllowed);
+        }
+        None => (),
+    }
+}
+
+#[test]
+#[serial]
+fn kdf() {
+    let device = test_setup();
+
+    let temp_key: [u8; ATCA_KEY_SIZE] = [0x5C; ATCA_KEY_SIZE];
+    let message: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F,
+    ];
+
+    let mut expected_kdf_ok = AtcaStatus::AtcaSuccess;
+    let mut expected_kdf_bad = AtcaStatus::AtcaInvalidSize;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+
+        expected_kdf_ok = AtcaStatus::AtcaNotLocked;
+        expected_kdf_bad = AtcaStatus::AtcaNotLocked;
+    } else if device.get_device_type() != AtcaDeviceType::ATECC608A {
+        expected_kdf_ok = AtcaStatus::AtcaBadParam;
+        expected_kdf_bad = AtcaStatus::AtcaBadParam;
+    } else {
+        match device.get_kdf_output_protection_state() {
+            OutputProtectionState::ClearTextAllowed => (),
+            // IO protection key is not registered
+            OutputProtectionState::EncryptedOutputOnly => {
+                expected_kdf_ok = AtcaStatus::AtcaInvalidId
+            }
+            _ => expected_kdf_ok = AtcaStatus::AtcaBadParam,
+        }
+    }
+
+    let nonce = device.nonce(NonceTarget::TempKey, &temp_key);
+
+    let mut derived_key: Vec<u8> = Vec::new();
+    let kdf_ok = match device.kdf(
+        KdfAlgorithm::Hkdf,
+        KdfSource::TempKey,
+        KdfTarget::Output,
+        &message,
+    ) {
+        Ok(val) => {
+            derived_key = val;
+            AtcaStatus::AtcaSuccess
+        }
+        Err(err) => err,
+    };
+    // unsupported length of the source key
+    let kdf_bad = device
+        .kdf(
+            KdfAlgorithm::Prf {
+                key_length: 20,
+                target_length: 32,
+            },
+            KdfSource::TempKey,
+            KdfTarget::Output,
+            &message,
+        )
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if device.is_configuration_locked() {
+        assert_eq!(nonce, AtcaStatus::AtcaSuccess);
+    }
+    assert_eq!(kdf_ok, expected_kdf_ok);
+    assert_eq!(kdf_bad, expected_kdf_bad);
+    if AtcaStatus::AtcaSuccess == kdf_ok {
+        assert_eq!(derived_key.len(), ATCA_KEY_SIZE);
+    }
+}
+
 #[test]
 #[serial]
 fn get_pubkey() {
@@ -326,7 +933,7 @@ fn get_pubkey() {
     let result_import_key = device.import_key(KeyType::P256EccKey, &public_key_write, 0x0B);
     let get_key_ok_2 = device.get_public_key(0x0B, &mut public_key);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_import_key);
     if chip_is_locked {
@@ -390,7 +997,7 @@ fn export_key_aes() {
     let export_key_ok_1 = device.export_key(KeyType::Aes, &mut aes_key_read, AES_SLOT_IDX_OK);
     // Due to the limited number of available slots, there is no AES slot in the configuration with reading without encryption
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(export_key_bad_1, expected_export_key_bad_1);
     assert_eq!(export_key_bad_2, expected_export_key_bad_2);
@@ -458,7 +1065,7 @@ fn sign_verify_hash() {
         expected_get_pub_key_result = AtcaStatus::AtcaNotLocked;
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     if chip_is_fully_locked {
         assert_eq!(signature.len(), ATCA_SIG_SIZE);
@@ -507,7 +1114,7 @@ fn gen_key_sign_hash() {
     let mut signature = vec![0u8; ATCA_SIG_SIZE];
     let device_sign_hash = device.sign_hash(SignMode::External(digest), 0, &mut signature);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(device_set_write_key, AtcaStatus::AtcaSuccess);
     assert_eq!(device_sha, expected_device_sha);
@@ -515,6 +1122,75 @@ fn gen_key_sign_hash() {
     assert_eq!(device_sign_hash, expected_device_sign_hash);
 }
 
+#[test]
+#[serial]
+fn atecc_rng() {
+    let device = test_setup();
+
+    let mut random_bytes = [0x00; 100];
+    let mut random_numbers: Vec<u64> = Vec::new();
+
+    let mut chip_is_locked: bool = true;
+    if !device.is_configuration_locked() {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+    }
+
+    let mut rng = AteccRng::new(&*device);
+    let fill_result = rng.try_fill_bytes(&mut random_bytes);
+    if fill_result.is_ok() {
+        random_numbers = (0..10).map(|_| rng.next_u64()).collect();
+    }
+    drop(rng);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_locked {
+        assert!(fill_result.is_ok());
+        assert!(random_bytes.iter().any(|x| *x != random_bytes[0]));
+        assert!(random_numbers.iter().any(|x| *x != random_numbers[0]));
+    } else {
+        assert!(fill_result.is_err());
+    }
+}
+
+#[test]
+#[serial]
+fn sign_message() {
+    let device = test_setup();
+
+    let message = [0x5A; 150];
+    let mut digest: Vec<u8> = Vec::new();
+    let mut public_key: Vec<u8> = Vec::new();
+    let mut is_verified: bool = false;
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let sign_result = device.sign_message(0x00, &message);
+    let sha_result = device.sha(message.to_vec(), &mut digest);
+    let get_pub_key_result = device.get_public_key(0x00, &mut public_key);
+    if let Ok(signature) = &sign_result {
+        if let Ok(val) = device.verify_hash(VerifyMode::External(public_key), &digest, signature) {
+            is_verified = val
+        }
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        assert_eq!(sha_result, AtcaStatus::AtcaSuccess);
+        assert_eq!(get_pub_key_result, AtcaStatus::AtcaSuccess);
+        assert_eq!(sign_result.map(|sig| sig.len()), Ok(ATCA_SIG_SIZE));
+        assert!(is_verified);
+    } else {
+        assert_eq!(sign_result, Err(AtcaStatus::AtcaNotLocked));
+    }
+}
+
 #[test]
 #[serial]
 fn cmp_config_zone() {
@@ -522,17 +1198,16 @@ fn cmp_config_zone() {
 
     let mut config_data = Vec::new();
     let device_read_config_zone = device.read_config_zone(&mut config_data);
-    let device_cmp_config_zone: AtcaStatus;
     let mut same_config: bool = false;
-    match device.cmp_config_zone(&mut config_data) {
+    let device_cmp_config_zone = match device.cmp_config_zone(&mut config_data) {
         Ok(val) => {
             same_config = val;
-            device_cmp_config_zone = AtcaStatus::AtcaSuccess
+            AtcaStatus::AtcaSuccess
         }
-        Err(err) => device_cmp_config_zone = err,
+        Err(err) => err,
     };
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(device_read_config_zone, AtcaStatus::AtcaSuccess);
     assert_eq!(device_cmp_config_zone, AtcaStatus::AtcaSuccess);
@@ -546,7 +1221,7 @@ fn is_configuration_locked() {
 
     let is_locked = device.is_configuration_locked();
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert!(is_locked);
 }
@@ -558,7 +1233,7 @@ fn is_data_zone_locked() {
 
     let is_locked = device.is_data_zone_locked();
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert!(is_locked);
 }
@@ -578,7 +1253,7 @@ fn get_config_from_config_zone() {
     let mut slots: Vec<AtcaSlot> = Vec::new();
     atcab_get_config_from_config_zone(&config_data, &mut slots);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(device_atcab_read_config_zone, AtcaStatus::AtcaSuccess);
     assert_eq!(slots.len(), usize::from(ATCA_ATECC_SLOTS_COUNT));
@@ -599,7 +1274,7 @@ fn get_config() {
     let mut slots: Vec<AtcaSlot> = Vec::new();
     let get_config = device.get_config(&mut slots);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(get_config, AtcaStatus::AtcaSuccess);
     assert_eq!(slots.len(), ATCA_ATECC_SLOTS_COUNT as usize);
@@ -624,7 +1299,7 @@ fn info_cmd() {
         Err(err) => result_revision = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_key_valid, AtcaStatus::AtcaUnimplemented);
     assert_eq!(revision.len(), 4);
@@ -678,7 +1353,7 @@ fn add_get_and_flush_access_keys() {
     device.flush_access_keys();
     let device_get_key_bad_3 = device.get_access_key(OK_KEY_IDX_1, &mut temp_arr);
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_get_key_ok_1, AtcaStatus::AtcaSuccess);
     assert_eq!(result_get_key_ok_2, AtcaStatus::AtcaSuccess);
@@ -696,3 +1371,525 @@ fn add_get_and_flush_access_keys() {
     assert_eq!(device_get_key_bad_2, AtcaStatus::AtcaInvalidId);
     assert_eq!(device_get_key_bad_3, AtcaStatus::AtcaInvalidId);
 }
+
+#[test]
+#[serial]
+fn ecies() {
+    const PRIV_KEY_SLOT: u8 = 0x00;
+    const MESSAGE_SIZE: usize = 45;
+
+    let device = test_setup();
+
+    let message: Vec<u8> = (0..MESSAGE_SIZE).map(|x| x as u8).collect();
+    let mut pub_key: Vec<u8> = Vec::new();
+    let get_public_key = device.get_public_key(PRIV_KEY_SLOT, &mut pub_key);
+    if AtcaStatus::AtcaSuccess != get_public_key {
+        println!(
+            "\u{001b}[1m\u{001b}[33mPublic key of slot {} not available, test skipped\u{001b}[0m ",
+            PRIV_KEY_SLOT
+        );
+        assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+        return;
+    }
+
+    let mut expected = AtcaStatus::AtcaSuccess;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        expected = AtcaStatus::AtcaNotLocked;
+    } else if (device.get_device_type() != AtcaDeviceType::ATECC608A) || !device.is_aes_enabled() {
+        expected = AtcaStatus::AtcaBadParam;
+    }
+
+    let encrypted = device.ecies_encrypt(&pub_key, &message);
+    let (decrypted, tampered) = match &encrypted {
+        Ok(data) => {
+            let mut bad_data = data.clone();
+            bad_data[ATCA_ECIES_OVERHEAD] ^= 0x01;
+            (
+                device.ecies_decrypt(PRIV_KEY_SLOT, data),
+                device.ecies_decrypt(PRIV_KEY_SLOT, &bad_data),
+            )
+        }
+        Err(err) => (Err(*err), Err(*err)),
+    };
+    let too_short = device
+        .ecies_decrypt(PRIV_KEY_SLOT, &[0x00; ATCA_ECIES_OVERHEAD - 1])
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if AtcaStatus::AtcaSuccess == expected {
+        assert_eq!(
+            encrypted.map(|val| val.len()),
+            Ok(MESSAGE_SIZE + ATCA_ECIES_OVERHEAD)
+        );
+        assert_eq!(decrypted, Ok(message));
+        assert_eq!(tampered, Err(AtcaStatus::AtcaCheckMacVerifyFailed));
+        assert_eq!(too_short, AtcaStatus::AtcaInvalidSize);
+    } else {
+        assert_eq!(encrypted.err(), Some(expected));
+        assert_eq!(too_short, expected);
+    }
+}
+
+#[test]
+#[serial]
+fn tls_derive_keys() {
+    const PRIV_KEY_SLOT: u8 = 0x00;
+    const KEY_BLOCK_SIZE: usize = 40;
+    const VERIFY_DATA_SIZE: usize = 12;
+
+    let device = test_setup();
+
+    let server_pub_key = [
+        0xBA, 0x6A, 0xB5, 0xF1, 0x19, 0xAF, 0x21, 0x73, 0x03, 0x75, 0xD1, 0x8D, 0x6B, 0x5F, 0xF1,
+        0x94, 0x33, 0xE5, 0x3A, 0xEE, 0x5F, 0x6F, 0xBA, 0x22, 0x97, 0x77, 0x13, 0xEA, 0x82, 0xD3,
+        0x74, 0x84, 0x8E, 0x39, 0x78, 0x66, 0xE8, 0x36, 0xB3, 0xFE, 0xD3, 0x22, 0x87, 0x74, 0xA5,
+        0x00, 0xC5, 0x5C, 0x17, 0x73, 0x5A, 0x92, 0x4B, 0xB3, 0x9F, 0xE4, 0x98, 0x52, 0x62, 0xA5,
+        0x36, 0xC5, 0x00, 0x9C,
+    ];
+    let client_random = [0x11; 32];
+    let server_random = [0x22; 32];
+    let handshake_hash = [0x33; 32];
+
+    let mut slots: Vec<AtcaSlot> = Vec::new();
+    let get_config = device.get_config(&mut slots);
+    let ecc_key_attr = slots[PRIV_KEY_SLOT as usize].config.ecc_key_attr;
+
+    let mut expected = AtcaStatus::AtcaSuccess;
+    let mut expected_bad = AtcaStatus::AtcaInvalidSize;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        expected = AtcaStatus::AtcaNotLocked;
+        expected_bad = AtcaStatus::AtcaNotLocked;
+    } else if (device.get_device_type() != AtcaDeviceType::ATECC608A)
+        || !(ecc_key_attr.is_private && ecc_key_attr.ecdh_operation)
+    {
+        expected = AtcaStatus::AtcaBadParam;
+        expected_bad = AtcaStatus::AtcaBadParam;
+    }
+    // Without an IO protection key the key block can only be released in clear text
+    let clear_output_allowed =
+        device.get_kdf_output_protection_state() == OutputProtectionState::ClearTextAllowed;
+
+    let key_block = device.tls_derive_keys(
+        PRIV_KEY_SLOT,
+        &server_pub_key,
+        &client_random,
+        &server_random,
+        KEY_BLOCK_SIZE,
+        None,
+    );
+    let verify_data = match &key_block {
+        Ok(_) => device.tls_verify_data(TlsFinishedLabel::Client, &handshake_hash, None),
+        Err(err) => Err(*err),
+    };
+    // key block too long
+    let bad_key_block = device
+        .tls_derive_keys(
+            PRIV_KEY_SLOT,
+            &server_pub_key,
+            &client_random,
+            &server_random,
+            2 * ATCA_KEY_SIZE + 1,
+            None,
+        )
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(get_config, AtcaStatus::AtcaSuccess);
+    assert_eq!(bad_key_block, expected_bad);
+    match key_block {
+        Ok(val) => {
+            assert_eq!(expected, AtcaStatus::AtcaSuccess);
+            assert_eq!(val.len(), KEY_BLOCK_SIZE);
+            assert_eq!(verify_data.map(|val| val.len()), Ok(VERIFY_DATA_SIZE));
+        }
+        Err(err) => {
+            if (AtcaStatus::AtcaSuccess != expected) || clear_output_allowed {
+                assert_eq!(err, expected);
+            }
+        }
+    }
+}
+
+#[test]
+#[serial]
+fn generate_csr_signed_on_chip() {
+    let device = test_setup();
+
+    let param = CsrParam {
+        subject: vec![
+            (DnAttribute::Organization, "Example Org".to_owned()),
+            (DnAttribute::CommonName, "ATECC device".to_owned()),
+        ],
+        subject_alt_names: vec![SubjectAltName::Dns("device.example.com".to_owned())],
+        ..Default::default()
+    };
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let csr = generate_csr(&*device, 0x00, &param);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        let csr = csr.expect("CSR generation failed");
+        assert_eq!(csr[0], 0x30);
+        assert!(csr_to_pem(&csr).starts_with("-----BEGIN CERTIFICATE REQUEST-----"));
+    } else {
+        assert_eq!(csr, Err(AtcaStatus::AtcaNotLocked));
+    }
+}
+
+#[test]
+#[serial]
+fn cloud_provisioning_material() {
+    let device = test_setup();
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let aws_registration = aws_iot_registration(&*device, ATCACERT_TEST_CERT);
+    let azure_identity = azure_x509_identity(&*device, ATCACERT_TEST_CERT);
+    let sas_token = azure_dps_sas_token(&*device, 0x09, "0ne00000001", "device-1", 1600000000);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        let aws_registration = aws_registration.expect("AWS IoT registration failed");
+        assert!(aws_registration
+            .certificate_pem
+            .starts_with("-----BEGIN CERTIFICATE-----\nMIIBojCCAUmgAwIBAgIQ"));
+        assert_eq!(
+            aws_registration.certificate_id,
+            "ed8989ed4ab55695587e195f5fb577ee18b1b7aff5e896f38fe008e92bb02604"
+        );
+        let azure_identity = azure_identity.expect("Azure X.509 identity failed");
+        assert_eq!(azure_identity.registration_id, "Example Device");
+        assert_eq!(
+            azure_identity.thumbprint_sha1,
+            "D9176167EC515EB3FE91CFCB5AB0BA3D07B6D534"
+        );
+        assert_eq!(
+            azure_identity.thumbprint_sha256,
+            "ED8989ED4AB55695587E195F5FB577EE18B1B7AFF5E896F38FE008E92BB02604"
+        );
+        let sas_token = sas_token.expect("SAS token computation failed");
+        assert!(sas_token
+            .starts_with("SharedAccessSignature sr=0ne00000001%2Fregistrations%2Fdevice-1&sig="));
+        assert!(sas_token.ends_with("&se=1600000000&skn=registration"));
+    } else {
+        assert_eq!(aws_registration, Err(AtcaStatus::AtcaNotLocked));
+        assert_eq!(sas_token, Err(AtcaStatus::AtcaNotLocked));
+    }
+}
+
+#[test]
+#[serial]
+fn cose_sign1_signed_on_chip() {
+    let device = test_setup();
+
+    let payload = b"device attestation";
+    let external_aad = b"challenge";
+    let mut public_key: Vec<u8> = Vec::new();
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let cose_sign1 = cose_sign1_sign(&*device, 0x00, payload, Some(b"slot0"), external_aad);
+    device.get_public_key(0x00, &mut public_key);
+    let (verified, other_aad) = match &cose_sign1 {
+        Ok(cose_sign1) => (
+            cose_sign1_verify(&*device, cose_sign1, &public_key, external_aad),
+            cose_sign1_verify(&*device, cose_sign1, &public_key, b"other"),
+        ),
+        Err(err) => (Err(*err), Err(*err)),
+    };
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        assert_eq!(verified, Ok(Some(payload.to_vec())));
+        assert_eq!(other_aad, Ok(None));
+    } else {
+        assert_eq!(cose_sign1, Err(AtcaStatus::AtcaNotLocked));
+    }
+}
+
+#[test]
+#[serial]
+fn jwt_signed_on_chip() {
+    let device = test_setup();
+
+    let claims = jwt_claims("my-project", 1600000000, 1600003600);
+    let mut public_key: Vec<u8> = Vec::new();
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let token = jwt_sign(&*device, 0x00, &claims);
+    device.get_public_key(0x00, &mut public_key);
+    let (verified, tampered) = match &token {
+        Ok(token) => (
+            jwt_verify(&*device, token, &public_key),
+            jwt_verify(&*device, &token.replacen(".eyJ", ".eyI", 1), &public_key),
+        ),
+        Err(err) => (Err(*err), Err(*err)),
+    };
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        assert_eq!(verified, Ok(Some(claims)));
+        assert_eq!(tampered.ok().flatten(), None);
+    } else {
+        assert_eq!(token, Err(AtcaStatus::AtcaNotLocked));
+    }
+}
+
+#[test]
+#[serial]
+fn tng_cert_verify() {
+    let device = test_setup();
+
+    let mut tampered_cert = ATCACERT_TEST_CERT.to_vec();
+    tampered_cert[20] ^= 0x01;
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let result = tng::cert_verify(&*device, ATCACERT_TEST_CERT, ATCACERT_TEST_CA_PUBLIC_KEY);
+    let tampered_result = tng::cert_verify(&*device, &tampered_cert, ATCACERT_TEST_CA_PUBLIC_KEY);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        assert_eq!(result, Ok(true));
+        assert_eq!(tampered_result, Ok(false));
+    } else {
+        assert_eq!(result, Err(AtcaStatus::AtcaNotLocked));
+    }
+}
+
+#[cfg(feature = "rustcrypto")]
+#[test]
+#[serial]
+fn slot_signer() {
+    let device = test_setup();
+
+    let message = [0xA5; 100];
+    let other_message = [0x5A; 100];
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let signer = SlotSigner::new(&*device, 0x00);
+    let mut is_signed: bool = false;
+    let mut is_verified: bool = false;
+    let mut is_other_verified: bool = true;
+    if let Ok(signer) = &signer {
+        if let Ok(signature) = signer.try_sign(&message) {
+            is_signed = true;
+            is_verified = signer.verify(&message, &signature).is_ok();
+            is_other_verified = signer.verify(&other_message, &signature).is_ok();
+        }
+    }
+    let public_key_size = signer.map(|signer| signer.public_key().len());
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if chip_is_fully_locked {
+        assert_eq!(public_key_size, Ok(ATCA_ATECC_PUB_KEY_SIZE));
+        assert!(is_signed);
+        assert!(is_verified);
+        assert!(!is_other_verified);
+    } else {
+        assert!(!is_signed);
+    }
+}
+
+#[cfg(feature = "embedded-tls")]
+#[test]
+#[serial]
+fn embedded_tls_signer_and_key_exchange() {
+    const PRIV_KEY_SLOT: u8 = 0x00;
+
+    let device = test_setup();
+
+    let transcript = [0x6B; 80];
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let signer = TlsSigner::new(&*device, PRIV_KEY_SLOT);
+    let signature = signer.try_sign(&transcript);
+    let mut public_key: Vec<u8> = Vec::new();
+    let mut digest: Vec<u8> = Vec::new();
+    let mut is_verified: bool = false;
+    if let Ok(signature) = &signature {
+        if (AtcaStatus::AtcaSuccess == device.get_public_key(PRIV_KEY_SLOT, &mut public_key))
+            && (AtcaStatus::AtcaSuccess == device.sha(transcript.to_vec(), &mut digest))
+        {
+            is_verified = device
+                .verify_hash_formatted(
+                    VerifyMode::External(public_key.clone()),
+                    &digest,
+                    signature.as_ref(),
+                    SignatureFormat::Der,
+                )
+                .unwrap_or(false);
+        }
+    }
+
+    // ECDH of the slot key with its own public key must match the plain ecdh() result
+    let key_exchange = TlsKeyExchange::from_slot(&*device, PRIV_KEY_SLOT);
+    let mut shared_secret: Result<Vec<u8>, AtcaStatus> = Err(AtcaStatus::AtcaNotLocked);
+    let mut expected_ecdh: Result<EcdhOutput, AtcaStatus> = Err(AtcaStatus::AtcaNotLocked);
+    if let Ok(key_exchange) = &key_exchange {
+        shared_secret = key_exchange
+            .public_key()
+            .and_then(|key_share| key_exchange.shared_secret(&key_share));
+        expected_ecdh = device.ecdh(PRIV_KEY_SLOT, &public_key, None);
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(signer.signature_scheme(), TLS_ECDSA_SECP256R1_SHA256);
+    if chip_is_fully_locked {
+        assert!(signature.is_ok());
+        assert!(is_verified);
+        match expected_ecdh {
+            Ok(EcdhOutput::PreMasterSecret(secret)) => assert_eq!(shared_secret, Ok(secret)),
+            Ok(_) => assert_eq!(shared_secret, Err(AtcaStatus::AtcaBadParam)),
+            Err(err) => assert_eq!(shared_secret, Err(err)),
+        }
+    } else {
+        assert!(signature.is_err());
+        assert!(key_exchange.is_err());
+    }
+}
+
+#[cfg(feature = "rustls")]
+#[test]
+#[serial]
+fn rustls_client_cert_resolver() {
+    let device = std::sync::Arc::new(test_setup());
+
+    let message = [0x3C; 64];
+    let mut digest: Vec<u8> = Vec::new();
+    let mut public_key: Vec<u8> = Vec::new();
+    let mut is_verified: bool = false;
+
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let resolver =
+        AteccClientCertResolver::new(device.clone(), 0x00, vec![ATCACERT_TEST_CERT.to_vec()]);
+    let certified_key = resolver.resolve(&[], &[SignatureScheme::ECDSA_NISTP256_SHA256]);
+    let unsupported_key = resolver.resolve(&[], &[SignatureScheme::ED25519]);
+    let signature = certified_key
+        .as_ref()
+        .and_then(|key| {
+            key.key.choose_scheme(&[
+                SignatureScheme::RSA_PSS_SHA256,
+                SignatureScheme::ECDSA_NISTP256_SHA256,
+            ])
+        })
+        .map(|signer| signer.sign(&message));
+    if let Some(Ok(signature)) = &signature {
+        device.sha(message.to_vec(), &mut digest);
+        device.get_public_key(0x00, &mut public_key);
+        if let Ok(val) = device.verify_hash_formatted(
+            VerifyMode::External(public_key),
+            &digest,
+            signature,
+            SignatureFormat::Der,
+        ) {
+            is_verified = val
+        }
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(certified_key.map(|key| key.cert.len()), Some(1));
+    assert!(unsupported_key.is_none());
+    if chip_is_fully_locked {
+        assert!(is_verified);
+    } else {
+        assert!(matches!(signature, Some(Err(_))));
+    }
+}
+
+#[cfg(feature = "parsec")]
+#[test]
+#[serial]
+fn parsec_key_provider() {
+    let device = std::sync::Arc::new(test_setup());
+    let provider = AteccParsecProvider::new(device.clone(), &[0x00]);
+
+    let hash = [0xA5; ATCA_SHA2_256_DIGEST_SIZE];
+    let mut chip_is_fully_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_fully_locked = false;
+    }
+
+    let ping = provider.ping();
+    let generate_key = provider.generate_key("parsec-key");
+    let generate_key_bad_1 = provider.generate_key("parsec-key");
+    let generate_key_bad_2 = provider.generate_key("parsec-key-2");
+    let signature = provider.sign_hash("parsec-key", &hash);
+    let sign_hash_bad = provider.sign_hash("parsec-key-2", &hash);
+    let public_key = provider.export_public_key("parsec-key");
+    let is_verified = match &signature {
+        Ok(signature) => provider.verify_hash("parsec-key", &hash, signature),
+        Err(err) => Err(*err),
+    };
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(ping, Ok((1, 0)));
+    assert_eq!(sign_hash_bad, Err(AtcaStatus::AtcaInvalidId));
+    if chip_is_fully_locked {
+        assert_eq!(generate_key, Ok(()));
+        assert_eq!(generate_key_bad_1, Err(AtcaStatus::AtcaBadParam));
+        assert_eq!(generate_key_bad_2, Err(AtcaStatus::AtcaAllocFailure));
+        assert_eq!(signature.map(|val| val.len()), Ok(ATCA_SIG_SIZE));
+        assert_eq!(
+            public_key.map(|val| val.len()),
+            Ok(ATCA_ATECC_PUB_KEY_SIZE + 1)
+        );
+        assert_eq!(is_verified, Ok(true));
+    } else {
+        assert!(generate_key.is_err());
+    }
+}
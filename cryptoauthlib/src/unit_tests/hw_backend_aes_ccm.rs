@@ -238,7 +238,7 @@ fn aead_ccm_encrypt_proper_data() {
         Err(err) => result_24_internal_key = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -476,7 +476,7 @@ fn aead_ccm_encrypt_bad_data() {
         Err(err) => result_bad_10 = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -718,7 +718,7 @@ fn aead_ccm_decrypt_proper_data() {
         Err(err) => result_24_internal_key = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -1004,7 +1004,7 @@ fn aead_ccm_decrypt_bad_data() {
         Err(err) => result_bad_12 = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -1022,3 +1022,116 @@ fn aead_ccm_decrypt_bad_data() {
         assert!(!tags_match);
     }
 }
+
+#[test]
+#[serial]
+fn aead_ccm_stream_proper_data() {
+    const DATA_24_SIZE: usize = 24;
+    const AAD_20_SIZE: usize = 20;
+    const TAG_8_SIZE: u8 = 8;
+    const AES_KEY_SLOT_IDX: u8 = 0x09;
+
+    let device = test_setup();
+
+    // Test Vectors from https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/AES_CCM.pdf
+    let aes_key = [
+        0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E,
+        0x4F,
+    ];
+    let iv = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B,
+    ];
+    let plain_text = [
+        0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2A, 0x2B, 0x2C, 0x2D, 0x2E,
+        0x2F, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+    ];
+    let aad = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F, 0x10, 0x11, 0x12, 0x13,
+    ];
+    let tag_24 = [0x48, 0x43, 0x92, 0xFB, 0xC1, 0xB0, 0x99, 0x51];
+    let cipher_text_24 = [
+        0xE3, 0xB2, 0x01, 0xA9, 0xF5, 0xB7, 0x1A, 0x7A, 0x9B, 0x1C, 0xEA, 0xEC, 0xCD, 0x97, 0xE7,
+        0x0B, 0x61, 0x76, 0xAA, 0xD9, 0xA4, 0x42, 0x8A, 0xA5,
+    ];
+
+    // Chunk sizes deliberately not aligned to the AES block size
+    let aad_chunks: [usize; 2] = [3, AAD_20_SIZE - 3];
+    let text_chunks: [usize; 3] = [5, 2, DATA_24_SIZE - 7];
+
+    let mut expected = AtcaStatus::AtcaBadParam;
+    let mut chip_is_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+        expected = AtcaStatus::AtcaNotLocked;
+    }
+    if chip_is_locked && device.is_aes_enabled() {
+        expected = AtcaStatus::AtcaSuccess;
+    }
+
+    let result_import_key = device.import_key(KeyType::Aes, &aes_key, AES_KEY_SLOT_IDX);
+
+    let mut encrypt_results = Vec::new();
+    let mut decrypt_results = Vec::new();
+
+    for (slot_id, key) in [
+        (ATCA_ATECC_SLOTS_COUNT, Some(aes_key)),
+        (AES_KEY_SLOT_IDX, None),
+    ]
+    .iter()
+    {
+        let mut data = plain_text.to_vec();
+        let result = (|| {
+            let mut ctx =
+                device.ccm_init(*slot_id, &iv, *key, AAD_20_SIZE, DATA_24_SIZE, TAG_8_SIZE)?;
+            let mut pos: usize = 0;
+            for len in aad_chunks.iter() {
+                ctx = device.ccm_update_aad(ctx, &aad[pos..(pos + len)])?;
+                pos += len;
+            }
+            pos = 0;
+            for len in text_chunks.iter() {
+                ctx = device.ccm_encrypt_update(ctx, &mut data[pos..(pos + len)])?;
+                pos += len;
+            }
+            device.ccm_encrypt_finish(ctx)
+        })();
+        encrypt_results.push((result, data));
+
+        let mut data = cipher_text_24.to_vec();
+        let result = (|| {
+            let mut ctx =
+                device.ccm_init(*slot_id, &iv, *key, AAD_20_SIZE, DATA_24_SIZE, TAG_8_SIZE)?;
+            ctx = device.ccm_update_aad(ctx, &aad)?;
+            let mut pos: usize = 0;
+            for len in text_chunks.iter().rev() {
+                ctx = device.ccm_decrypt_update(ctx, &mut data[pos..(pos + len)])?;
+                pos += len;
+            }
+            device.ccm_decrypt_finish(ctx, &tag_24)
+        })();
+        decrypt_results.push((result, data));
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if expected == AtcaStatus::AtcaSuccess {
+        assert_eq!(result_import_key, AtcaStatus::AtcaSuccess);
+        for (result, data) in encrypt_results.iter() {
+            assert_eq!(result.as_ref(), Ok(&tag_24.to_vec()));
+            assert_eq!(data, &cipher_text_24.to_vec());
+        }
+        for (result, data) in decrypt_results.iter() {
+            assert_eq!(result.as_ref(), Ok(&true));
+            assert_eq!(data, &plain_text.to_vec());
+        }
+    } else {
+        for (result, _) in encrypt_results.iter() {
+            assert_eq!(result.as_ref().err(), Some(&expected));
+        }
+        for (result, _) in decrypt_results.iter() {
+            assert_eq!(result.as_ref().err(), Some(&expected));
+        }
+    }
+}
@@ -1,4 +1,6 @@
 // Types
+#[cfg(feature = "rustcrypto")]
+use super::SlotAes;
 use super::{AtcaStatus, CipherAlgorithm, CipherParam, KeyType};
 // Constants
 use super::{
@@ -6,6 +8,8 @@ use super::{
 };
 
 use super::hw_backend_common::*;
+#[cfg(feature = "rustcrypto")]
+use cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt};
 use serial_test::serial;
 
 #[test]
@@ -15,7 +19,7 @@ fn aes_cipher_block_bad_data() {
 
     let mut chip_is_locked: bool = true;
 
-    let data_bad_len: [u8; (ATCA_AES_DATA_SIZE - 1)] = [0x00; (ATCA_AES_DATA_SIZE - 1)];
+    let data_bad_len: [u8; ATCA_AES_DATA_SIZE - 1] = [0x00; ATCA_AES_DATA_SIZE - 1];
 
     let mut expected_bad_1 = AtcaStatus::AtcaBadParam;
     let mut expected_bad_2 = AtcaStatus::AtcaBadParam;
@@ -54,7 +58,7 @@ fn aes_cipher_block_bad_data() {
         Err(err) => result_bad_3 = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -138,7 +142,7 @@ fn cipher_ecb_encrypt_proper_data() {
         &mut data_internal_key,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -149,6 +153,63 @@ fn cipher_ecb_encrypt_proper_data() {
     assert_eq!(result_internal_key, expected_internal_key);
 }
 
+#[cfg(feature = "rustcrypto")]
+#[test]
+#[serial]
+fn slot_aes_block_cipher() {
+    const AES_KEY_SLOT_IDX: u8 = 0x09;
+
+    let device = test_setup();
+
+    // Test Vector from https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/AES_ECB.pdf
+    let aes_key = [
+        0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F,
+        0x3C,
+    ];
+    let plain_text = [
+        0x6B, 0xC1, 0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17,
+        0x2A, 0xAE, 0x2D, 0x8A, 0x57, 0x1E, 0x03, 0xAC, 0x9C, 0x9E, 0xB7, 0x6F, 0xAC, 0x45, 0xAF,
+        0x8E, 0x51,
+    ];
+    let cipher_text = [
+        0x3A, 0xD7, 0x7B, 0xB4, 0x0D, 0x7A, 0x36, 0x60, 0xA8, 0x9E, 0xCA, 0xF3, 0x24, 0x66, 0xEF,
+        0x97, 0xF5, 0xD3, 0xD5, 0x85, 0x03, 0xB9, 0x69, 0x9D, 0xE7, 0x85, 0x89, 0x5A, 0x96, 0xFD,
+        0xBA, 0xAF,
+    ];
+
+    let mut chip_is_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+    }
+
+    let mut encrypted: Vec<u8> = Vec::new();
+    let mut decrypted: Vec<u8> = Vec::new();
+    let aes_is_usable = chip_is_locked && device.is_aes_enabled();
+    if aes_is_usable {
+        assert_eq!(
+            device.import_key(KeyType::Aes, &aes_key, AES_KEY_SLOT_IDX),
+            AtcaStatus::AtcaSuccess
+        );
+        let cipher = SlotAes::new(&*device, AES_KEY_SLOT_IDX, 0x00);
+        let mut blocks: Vec<_> = plain_text
+            .chunks(ATCA_AES_DATA_SIZE)
+            .map(GenericArray::clone_from_slice)
+            .collect();
+        cipher.encrypt_blocks(&mut blocks);
+        encrypted = blocks.concat();
+        cipher.decrypt_blocks(&mut blocks);
+        decrypted = blocks.concat();
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if aes_is_usable {
+        assert_eq!(encrypted, cipher_text.to_vec());
+        assert_eq!(decrypted, plain_text.to_vec());
+    }
+}
+
 #[test]
 #[serial]
 fn cipher_ecb_encrypt_bad_data() {
@@ -239,7 +300,7 @@ fn cipher_ecb_encrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -326,7 +387,7 @@ fn cipher_ecb_decrypt_proper_data() {
         &mut data_internal_key,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -427,7 +488,7 @@ fn cipher_ecb_decrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -521,7 +582,7 @@ fn cipher_cbc_encrypt_proper_data() {
         &mut data_internal_key,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -639,7 +700,7 @@ fn cipher_cbc_encrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -734,7 +795,7 @@ fn cipher_cbc_decrypt_proper_data() {
         &mut data_internal_key,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -852,7 +913,7 @@ fn cipher_cbc_decrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -980,7 +1041,7 @@ fn cipher_cbc_pkcs7_encrypt_proper_data() {
         &mut data_15,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -1094,7 +1155,7 @@ fn cipher_cbc_pkcs7_encrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -1220,7 +1281,7 @@ fn cipher_cbc_pkcs7_decrypt_proper_data() {
         &mut data_15,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -1369,7 +1430,7 @@ fn cipher_cbc_pkcs7_decrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
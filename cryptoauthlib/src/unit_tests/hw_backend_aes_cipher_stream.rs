@@ -126,7 +126,7 @@ fn cipher_cfb_encrypt_proper_data() {
         &mut data_2,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -239,7 +239,7 @@ fn cipher_cfb_encrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -366,7 +366,7 @@ fn cipher_cfb_decrypt_proper_data() {
         &mut data_2,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -479,7 +479,7 @@ fn cipher_cfb_decrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -606,7 +606,7 @@ fn cipher_ofb_encrypt_proper_data() {
         &mut data_2,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -719,7 +719,7 @@ fn cipher_ofb_encrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -846,7 +846,7 @@ fn cipher_ofb_decrypt_proper_data() {
         &mut data_2,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -959,7 +959,7 @@ fn cipher_ofb_decrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -1010,7 +1010,7 @@ fn aes_ctr_internal_functions() {
         Err(err) => result_2 = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, AtcaStatus::AtcaInvalidSize);
     assert_eq!(result_1, AtcaStatus::AtcaSuccess);
@@ -1139,7 +1139,7 @@ fn cipher_ctr_encrypt_proper_data() {
         &mut data_2,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -1287,7 +1287,7 @@ fn cipher_ctr_encrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -1418,7 +1418,7 @@ fn cipher_ctr_decrypt_proper_data() {
         &mut data_2,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -1566,7 +1566,7 @@ fn cipher_ctr_decrypt_bad_data() {
         &mut data_ok,
     );
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -241,7 +241,7 @@ fn aead_gcm_encrypt_proper_data() {
         Err(err) => result_60_internal_key = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -436,7 +436,7 @@ fn aead_gcm_encrypt_bad_data() {
         Err(err) => result_bad_8 = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -693,7 +693,7 @@ fn aead_gcm_decrypt_proper_data() {
         Err(err) => result_60_internal_key = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_import_key, expected_result_import_key);
     if chip_is_locked && device.is_aes_enabled() {
@@ -937,7 +937,7 @@ fn aead_gcm_decrypt_bad_data() {
         Err(err) => result_bad_10 = err,
     }
 
-    assert_eq!(device.release().to_string(), "AtcaSuccess");
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
 
     assert_eq!(result_bad_1, expected_bad_1);
     assert_eq!(result_bad_2, expected_bad_2);
@@ -953,3 +953,120 @@ fn aead_gcm_decrypt_bad_data() {
         assert!(!tags_match);
     }
 }
+
+#[test]
+#[serial]
+fn aead_gcm_stream_proper_data() {
+    const DATA_60_SIZE: usize = 60;
+    const AAD_20_SIZE: usize = 20;
+    const AES_KEY_SLOT_IDX: u8 = 0x09;
+
+    let device = test_setup();
+
+    // Test Vectors from https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/AES_GCM.pdf
+    let aes_key = [
+        0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83,
+        0x08,
+    ];
+    let iv = [
+        0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88,
+    ];
+    let plain_text = [
+        0xD9, 0x31, 0x32, 0x25, 0xF8, 0x84, 0x06, 0xE5, 0xA5, 0x59, 0x09, 0xC5, 0xAF, 0xF5, 0x26,
+        0x9A, 0x86, 0xA7, 0xA9, 0x53, 0x15, 0x34, 0xF7, 0xDA, 0x2E, 0x4C, 0x30, 0x3D, 0x8A, 0x31,
+        0x8A, 0x72, 0x1C, 0x3C, 0x0C, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2F, 0xCF, 0x0E, 0x24, 0x49,
+        0xA6, 0xB5, 0x25, 0xB1, 0x6A, 0xED, 0xF5, 0xAA, 0x0D, 0xE6, 0x57, 0xBA, 0x63, 0x7B, 0x39,
+    ];
+    let cipher_text = [
+        0x42, 0x83, 0x1E, 0xC2, 0x21, 0x77, 0x74, 0x24, 0x4B, 0x72, 0x21, 0xB7, 0x84, 0xD0, 0xD4,
+        0x9C, 0xE3, 0xAA, 0x21, 0x2F, 0x2C, 0x02, 0xA4, 0xE0, 0x35, 0xC1, 0x7E, 0x23, 0x29, 0xAC,
+        0xA1, 0x2E, 0x21, 0xD5, 0x14, 0xB2, 0x54, 0x66, 0x93, 0x1C, 0x7D, 0x8F, 0x6A, 0x5A, 0xAC,
+        0x84, 0xAA, 0x05, 0x1B, 0xA3, 0x0B, 0x39, 0x6A, 0x0A, 0xAC, 0x97, 0x3D, 0x58, 0xE0, 0x91,
+    ];
+    let aad = [
+        0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF, 0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE,
+        0xEF, 0xAB, 0xAD, 0xDA, 0xD2,
+    ];
+    let tag_60 = [
+        0x5B, 0xC9, 0x4F, 0xBC, 0x32, 0x21, 0xA5, 0xDB, 0x94, 0xFA, 0xE9, 0x5A, 0xE7, 0x12, 0x1A,
+        0x47,
+    ];
+
+    // Chunk sizes deliberately not aligned to the AES block size
+    let aad_chunks: [usize; 2] = [5, AAD_20_SIZE - 5];
+    let text_chunks: [usize; 3] = [7, 20, DATA_60_SIZE - 27];
+
+    let mut expected = AtcaStatus::AtcaBadParam;
+    let mut chip_is_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+        expected = AtcaStatus::AtcaNotLocked;
+    }
+    if chip_is_locked && device.is_aes_enabled() {
+        expected = AtcaStatus::AtcaSuccess;
+    }
+
+    let result_import_key = device.import_key(KeyType::Aes, &aes_key, AES_KEY_SLOT_IDX);
+
+    let mut encrypt_results = Vec::new();
+    let mut decrypt_results = Vec::new();
+
+    for (slot_id, key) in [
+        (ATCA_ATECC_SLOTS_COUNT, Some(aes_key)),
+        (AES_KEY_SLOT_IDX, None),
+    ]
+    .iter()
+    {
+        let mut data = plain_text.to_vec();
+        let result = (|| {
+            let mut ctx = device.gcm_init(*slot_id, &iv, *key)?;
+            let mut pos: usize = 0;
+            for len in aad_chunks.iter() {
+                ctx = device.gcm_update_aad(ctx, &aad[pos..(pos + len)])?;
+                pos += len;
+            }
+            pos = 0;
+            for len in text_chunks.iter() {
+                ctx = device.gcm_encrypt_update(ctx, &mut data[pos..(pos + len)])?;
+                pos += len;
+            }
+            device.gcm_encrypt_finish(ctx, ATCA_AES_DATA_SIZE as u8)
+        })();
+        encrypt_results.push((result, data));
+
+        let mut data = cipher_text.to_vec();
+        let result = (|| {
+            let mut ctx = device.gcm_init(*slot_id, &iv, *key)?;
+            ctx = device.gcm_update_aad(ctx, &aad)?;
+            let mut pos: usize = 0;
+            for len in text_chunks.iter().rev() {
+                ctx = device.gcm_decrypt_update(ctx, &mut data[pos..(pos + len)])?;
+                pos += len;
+            }
+            device.gcm_decrypt_finish(ctx, &tag_60)
+        })();
+        decrypt_results.push((result, data));
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    if expected == AtcaStatus::AtcaSuccess {
+        assert_eq!(result_import_key, AtcaStatus::AtcaSuccess);
+        for (result, data) in encrypt_results.iter() {
+            assert_eq!(result.as_ref(), Ok(&tag_60.to_vec()));
+            assert_eq!(data, &cipher_text.to_vec());
+        }
+        for (result, data) in decrypt_results.iter() {
+            assert_eq!(result.as_ref(), Ok(&true));
+            assert_eq!(data, &plain_text.to_vec());
+        }
+    } else {
+        for (result, _) in encrypt_results.iter() {
+            assert_eq!(result.as_ref().err(), Some(&expected));
+        }
+        for (result, _) in decrypt_results.iter() {
+            assert_eq!(result.as_ref().err(), Some(&expected));
+        }
+    }
+}
@@ -0,0 +1,159 @@
+// Types
+use super::{AtcaStatus, KeyType, KeyWrapAlgorithm};
+// Constants
+use super::ATCA_ATECC_SLOTS_COUNT;
+
+use super::hw_backend_common::*;
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn aes_key_wrap_proper_data() {
+    const AES_KEY_SLOT_IDX: u8 = 0x09;
+
+    let device = test_setup();
+
+    let mut chip_is_locked: bool = true;
+
+    // Test Vector from RFC 3394, section 4.1 (Wrap 128 bits of Key Data with a 128-bit KEK)
+    let kek = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F,
+    ];
+    let key_data = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE,
+        0xFF,
+    ];
+    let wrapped_key_data = [
+        0x1F, 0xA6, 0x8B, 0x0A, 0x81, 0x12, 0xB4, 0x47, 0xAE, 0xF3, 0x4B, 0xD8, 0xFB, 0x5A, 0x7B,
+        0x82, 0x9D, 0x3E, 0x86, 0x23, 0x71, 0xD2, 0xCF, 0xE5,
+    ];
+    // key data with lengths that are not a multiple of 8 bytes, for RFC 5649
+    let key_data_20 = &wrapped_key_data[..20];
+    let key_data_7 = &wrapped_key_data[..7];
+
+    let mut expected = AtcaStatus::AtcaBadParam;
+    let mut expected_result_import_key = AtcaStatus::AtcaBadParam;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+
+        expected = AtcaStatus::AtcaNotLocked;
+        expected_result_import_key = AtcaStatus::AtcaNotLocked;
+    }
+
+    let result_import_key = device.import_key(KeyType::Aes, &kek, AES_KEY_SLOT_IDX);
+
+    if chip_is_locked && device.is_aes_enabled() {
+        expected = AtcaStatus::AtcaSuccess;
+        expected_result_import_key = AtcaStatus::AtcaSuccess;
+    }
+
+    let mut wrapped: Vec<u8> = Vec::new();
+    let result_wrap = match device.wrap_key(KeyWrapAlgorithm::Kw, AES_KEY_SLOT_IDX, &key_data) {
+        Ok(val) => {
+            wrapped = val;
+            AtcaStatus::AtcaSuccess
+        }
+        Err(err) => err,
+    };
+
+    let mut unwrapped: Vec<u8> = Vec::new();
+    let result_unwrap =
+        match device.unwrap_key(KeyWrapAlgorithm::Kw, AES_KEY_SLOT_IDX, &wrapped_key_data) {
+            Ok(val) => {
+                unwrapped = val;
+                AtcaStatus::AtcaSuccess
+            }
+            Err(err) => err,
+        };
+
+    let mut unwrapped_kwp: Vec<Vec<u8>> = Vec::new();
+    let mut result_kwp = AtcaStatus::AtcaSuccess;
+    for data in [key_data_20, key_data_7].iter() {
+        match device
+            .wrap_key(KeyWrapAlgorithm::Kwp, AES_KEY_SLOT_IDX, data)
+            .and_then(|val| device.unwrap_key(KeyWrapAlgorithm::Kwp, AES_KEY_SLOT_IDX, &val))
+        {
+            Ok(val) => unwrapped_kwp.push(val),
+            Err(err) => {
+                result_kwp = err;
+                break;
+            }
+        }
+    }
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(result_import_key, expected_result_import_key);
+    assert_eq!(result_wrap, expected);
+    assert_eq!(result_unwrap, expected);
+    assert_eq!(result_kwp, expected);
+    if AtcaStatus::AtcaSuccess == expected {
+        assert_eq!(wrapped, wrapped_key_data.to_vec());
+        assert_eq!(unwrapped, key_data.to_vec());
+        assert_eq!(unwrapped_kwp[0], key_data_20.to_vec());
+        assert_eq!(unwrapped_kwp[1], key_data_7.to_vec());
+    }
+}
+
+#[test]
+#[serial]
+fn aes_key_wrap_bad_data() {
+    const AES_KEY_SLOT_IDX: u8 = 0x09;
+
+    let device = test_setup();
+
+    let mut chip_is_locked: bool = true;
+
+    let wrapped_key_data_bad = [
+        0x1F, 0xA6, 0x8B, 0x0A, 0x81, 0x12, 0xB4, 0x47, 0xAE, 0xF3, 0x4B, 0xD8, 0xFB, 0x5A, 0x7B,
+        0x82, 0x9D, 0x3E, 0x86, 0x23, 0x71, 0xD2, 0xCF, 0xE6,
+    ];
+    let key_data_bad = [0x00; 12];
+
+    let mut expected_bad_1 = AtcaStatus::AtcaBadParam;
+    let mut expected_bad_2 = AtcaStatus::AtcaBadParam;
+    let mut expected_bad_3 = AtcaStatus::AtcaBadParam;
+
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+
+        expected_bad_1 = AtcaStatus::AtcaNotLocked;
+        expected_bad_2 = AtcaStatus::AtcaNotLocked;
+        expected_bad_3 = AtcaStatus::AtcaNotLocked;
+    }
+
+    if chip_is_locked && device.is_aes_enabled() {
+        expected_bad_1 = AtcaStatus::AtcaInvalidSize;
+        expected_bad_2 = AtcaStatus::AtcaCheckMacVerifyFailed;
+    }
+
+    // key data length not a multiple of 8 bytes
+    let result_bad_1 = device
+        .wrap_key(KeyWrapAlgorithm::Kw, AES_KEY_SLOT_IDX, &key_data_bad)
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+    // integrity check fails
+    let result_bad_2 = device
+        .unwrap_key(
+            KeyWrapAlgorithm::Kw,
+            AES_KEY_SLOT_IDX,
+            &wrapped_key_data_bad,
+        )
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+    // TempKey cannot be used without an external key
+    let result_bad_3 = device
+        .wrap_key(KeyWrapAlgorithm::Kwp, ATCA_ATECC_SLOTS_COUNT, &key_data_bad)
+        .err()
+        .unwrap_or(AtcaStatus::AtcaSuccess);
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+
+    assert_eq!(result_bad_1, expected_bad_1);
+    assert_eq!(result_bad_2, expected_bad_2);
+    assert_eq!(result_bad_3, expected_bad_3);
+}
@@ -0,0 +1,125 @@
+// Types
+use super::{AtcaStatus, DecryptingReader, EncryptingWriter, HashingWriter};
+// Constants
+use super::{ATCA_AES_DATA_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_SHA2_256_DIGEST_SIZE};
+
+use super::hw_backend_common::*;
+use serial_test::serial;
+use std::io::{copy, Read, Write};
+
+#[test]
+#[serial]
+fn hashing_writer() {
+    const MESSAGE_SIZE: usize = 200;
+
+    let device = test_setup();
+
+    let message: Vec<u8> = (0..MESSAGE_SIZE).map(|x| x as u8).collect();
+    let mut expected_digest: Vec<u8> = Vec::new();
+    let expected = device.sha(message.clone(), &mut expected_digest);
+
+    // Small unaligned chunks force the message to be fed in several parts
+    let result = HashingWriter::new(&*device).and_then(|mut writer| {
+        for chunk in message.chunks(7) {
+            writer
+                .write_all(chunk)
+                .map_err(|_| AtcaStatus::AtcaGenFail)?;
+        }
+        writer.finish()
+    });
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+    match result {
+        Ok(digest) => {
+            assert_eq!(expected, AtcaStatus::AtcaSuccess);
+            assert_eq!(digest.len(), ATCA_SHA2_256_DIGEST_SIZE);
+            assert_eq!(digest, expected_digest);
+        }
+        Err(err) => assert_eq!(err, expected),
+    }
+}
+
+#[test]
+#[serial]
+fn encrypting_writer_decrypting_reader() {
+    let device = test_setup();
+
+    // Test Vectors from https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/AES_GCM.pdf
+    let aes_key = [
+        0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83,
+        0x08,
+    ];
+    let iv = [
+        0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88,
+    ];
+    let plain_text = [
+        0xD9, 0x31, 0x32, 0x25, 0xF8, 0x84, 0x06, 0xE5, 0xA5, 0x59, 0x09, 0xC5, 0xAF, 0xF5, 0x26,
+        0x9A, 0x86, 0xA7, 0xA9, 0x53, 0x15, 0x34, 0xF7, 0xDA, 0x2E, 0x4C, 0x30, 0x3D, 0x8A, 0x31,
+        0x8A, 0x72, 0x1C, 0x3C, 0x0C, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2F, 0xCF, 0x0E, 0x24, 0x49,
+        0xA6, 0xB5, 0x25, 0xB1, 0x6A, 0xED, 0xF5, 0xAA, 0x0D, 0xE6, 0x57, 0xBA, 0x63, 0x7B, 0x39,
+    ];
+    let cipher_text = [
+        0x42, 0x83, 0x1E, 0xC2, 0x21, 0x77, 0x74, 0x24, 0x4B, 0x72, 0x21, 0xB7, 0x84, 0xD0, 0xD4,
+        0x9C, 0xE3, 0xAA, 0x21, 0x2F, 0x2C, 0x02, 0xA4, 0xE0, 0x35, 0xC1, 0x7E, 0x23, 0x29, 0xAC,
+        0xA1, 0x2E, 0x21, 0xD5, 0x14, 0xB2, 0x54, 0x66, 0x93, 0x1C, 0x7D, 0x8F, 0x6A, 0x5A, 0xAC,
+        0x84, 0xAA, 0x05, 0x1B, 0xA3, 0x0B, 0x39, 0x6A, 0x0A, 0xAC, 0x97, 0x3D, 0x58, 0xE0, 0x91,
+    ];
+    let aad = [
+        0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE, 0xEF, 0xFE, 0xED, 0xFA, 0xCE, 0xDE, 0xAD, 0xBE,
+        0xEF, 0xAB, 0xAD, 0xDA, 0xD2,
+    ];
+    let tag = [
+        0x5B, 0xC9, 0x4F, 0xBC, 0x32, 0x21, 0xA5, 0xDB, 0x94, 0xFA, 0xE9, 0x5A, 0xE7, 0x12, 0x1A,
+        0x47,
+    ];
+
+    let mut expected = AtcaStatus::AtcaBadParam;
+    let mut chip_is_locked: bool = true;
+    if !(device.is_configuration_locked() && device.is_data_zone_locked()) {
+        println!("\u{001b}[1m\u{001b}[33mConfiguration and/or Data zone not Locked!\u{001b}[0m ");
+        chip_is_locked = false;
+        expected = AtcaStatus::AtcaNotLocked;
+    }
+    if chip_is_locked && device.is_aes_enabled() {
+        expected = AtcaStatus::AtcaSuccess;
+    }
+
+    let encrypt_result = EncryptingWriter::new(
+        &*device,
+        ATCA_ATECC_SLOTS_COUNT,
+        &iv,
+        Some(aes_key),
+        &aad,
+        Vec::new(),
+    )
+    .and_then(|mut writer| {
+        copy(&mut &plain_text[..], &mut writer).map_err(|_| AtcaStatus::AtcaGenFail)?;
+        writer.finish(ATCA_AES_DATA_SIZE as u8)
+    });
+
+    let decrypt_result = DecryptingReader::new(
+        &*device,
+        ATCA_ATECC_SLOTS_COUNT,
+        &iv,
+        Some(aes_key),
+        &aad,
+        &cipher_text[..],
+    )
+    .and_then(|mut reader| {
+        let mut data: Vec<u8> = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .map_err(|_| AtcaStatus::AtcaGenFail)?;
+        let (_, is_verified) = reader.finish(&tag)?;
+        Ok((data, is_verified))
+    });
+
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+    if expected == AtcaStatus::AtcaSuccess {
+        assert_eq!(encrypt_result, Ok((cipher_text.to_vec(), tag.to_vec())));
+        assert_eq!(decrypt_result, Ok((plain_text.to_vec(), true)));
+    } else {
+        assert_eq!(encrypt_result.err(), Some(expected));
+        assert_eq!(decrypt_result.err(), Some(expected));
+    }
+}
@@ -0,0 +1,1929 @@
+// Types
+use super::{
+    AeadAlgorithm, AeadParam, AtcaDeviceType, AtcaStatus, AteccDevice, AteccDeviceTrait,
+    AteccHandle, AteccKeySlots, AteccSimulator, CertDateFormat, CertDef, CertDeviceLoc,
+    CertSnSource, ConfigReport, CounterSignature, DigestBuffer, EcdhOutput, GenDigZone,
+    KdfAlgorithm, KdfSource, KdfTarget, KeyType, NonceTarget, OtpMode, OutputProtectionState,
+    ProvisioningDevice, SecureSession, SignMode, SlotOperation, SoftwareBackend, TempKeySource,
+    TempKeyState, VerifyMode, WriteConfig,
+};
+// Constants
+use super::{
+    ATCA_AES_DATA_SIZE, ATCA_ATECC_PUB_KEY_SIZE, ATCA_ATECC_SLOTS_COUNT, ATCA_ATECC_TEMPKEY_KEYID,
+    ATCA_CHECK_MAC_OTHER_DATA_SIZE, ATCA_COMP_CERT_SIZE, ATCA_COUNTER_MAX_VALUE, ATCA_KEY_SIZE,
+    ATCA_NONCE_NUMIN_SIZE, ATCA_OTP_SIZE, ATCA_RANDOM_BUFFER_SIZE, ATCA_SHA2_256_DIGEST_SIZE,
+    ATCA_SIGN_MODE_EXTERNAL, ATCA_SIGN_MODE_INTERNAL, ATCA_SIGN_MODE_INVALIDATE,
+    ATCA_SIGN_MODE_SOURCE_MSGDIGBUF, ATCA_SIG_SIZE, ATCA_WORD_SIZE, SOFTWARE_BACKEND_CONFIG_ZONE,
+};
+
+// Functions
+use super::{
+    alt_key_buf_aes_encrypt_block, alt_key_buf_kdf, attest_key, cert_read, cert_write,
+    config_report, fingerprint, get_certificate, import_key_xor_shares, limited_use_state,
+    load_alt_key_buf, load_signature, provision_io_protection_key, sign_hash_with_counter,
+    slot_report, store_signature, verify_hash_with_counter, verify_key_attestation,
+    write_otp_zone_verified,
+};
+
+use super::{ATCACERT_TEST_CA_PUBLIC_KEY, ATCACERT_TEST_CERT, ATCACERT_TEST_ELEMENTS};
+
+use serial_test::serial;
+
+const DATA_SLOT_IDX: u8 = 0x08;
+const IO_KEY_SLOT_IDX: u8 = 0x09;
+const AES_KEY_SLOT_IDX: u8 = 0x0A;
+const AES_KEY_ENC_WRITE_SLOT_IDX: u8 = 0x0C;
+const PUB_KEY_SLOT_IDX: u8 = 0x0E;
+
+pub fn test_setup() -> AteccDevice {
+    Box::new(SoftwareBackend::new())
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_config() {
+    let device = test_setup();
+
+    let mut config_data = Vec::new();
+    assert_eq!(
+        device.read_config_zone(&mut config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(config_data, SOFTWARE_BACKEND_CONFIG_ZONE.to_vec());
+    assert!(device.is_configuration_locked());
+    assert!(device.is_data_zone_locked());
+    assert!(device.is_aes_enabled());
+    assert!(device.is_io_protection_key_enabled());
+
+    let mut slots = Vec::new();
+    assert_eq!(device.get_config(&mut slots), AtcaStatus::AtcaSuccess);
+    assert_eq!(slots.len(), ATCA_ATECC_SLOTS_COUNT as usize);
+    assert_eq!(
+        slots[AES_KEY_SLOT_IDX as usize].config.key_type,
+        KeyType::Aes
+    );
+
+    assert!(SoftwareBackend::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE[1..]).is_err());
+    assert_eq!(device.release(), AtcaStatus::AtcaSuccess);
+    assert_eq!(device.release(), AtcaStatus::AtcaBadParam);
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_sha() {
+    let device = test_setup();
+
+    // FIPS 180-2, SHA-256 example "abc"
+    let expected = [
+        0xBA, 0x78, 0x16, 0xBF, 0x8F, 0x01, 0xCF, 0xEA, 0x41, 0x41, 0x40, 0xDE, 0x5D, 0xAE, 0x22,
+        0x23, 0xB0, 0x03, 0x61, 0xA3, 0x96, 0x17, 0x7A, 0x9C, 0xB4, 0x10, 0xFF, 0x61, 0xF2, 0x00,
+        0x15, 0xAD,
+    ];
+
+    let mut digest = Vec::new();
+    assert_eq!(
+        device.sha(b"abc".to_vec(), &mut digest),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(digest, expected.to_vec());
+
+    let ctx = device.sha_start().unwrap();
+    let ctx = device.sha_update(ctx, b"a").unwrap();
+    let ctx = device.sha_update(ctx, b"bc").unwrap();
+    assert_eq!(device.sha_finish(ctx), Ok(expected.to_vec()));
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_fixed_size_results() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let device = test_setup();
+    let message = b"abc";
+
+    let mut expected_digest = Vec::new();
+    assert_eq!(
+        device.sha(message.to_vec(), &mut expected_digest),
+        AtcaStatus::AtcaSuccess
+    );
+    let digest = device.sha256(message).unwrap();
+    assert_eq!(digest.to_vec(), expected_digest);
+
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let public_key = device.public_key(PRIV_KEY_SLOT_IDX).unwrap();
+    let signature = device.sign_digest(PRIV_KEY_SLOT_IDX, &digest).unwrap();
+    assert_eq!(
+        device.verify_hash(
+            VerifyMode::External(public_key.to_vec()),
+            &digest,
+            &signature
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        device.public_key(ATCA_ATECC_SLOTS_COUNT),
+        Err(AtcaStatus::AtcaInvalidId)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_random_fill() {
+    const FILL_SIZE: usize = (2 * ATCA_RANDOM_BUFFER_SIZE) + 5;
+
+    let handle = AteccHandle::new(test_setup());
+
+    let mut dest = [0; FILL_SIZE];
+    assert_eq!(handle.random_fill(&mut dest), AtcaStatus::AtcaSuccess);
+    // every block, including the partial last one, is filled
+    for chunk in dest.chunks(ATCA_RANDOM_BUFFER_SIZE) {
+        assert!(chunk.iter().any(|byte| *byte != 0));
+    }
+    assert_eq!(handle.random_fill(&mut []), AtcaStatus::AtcaSuccess);
+
+    let mut mixed = [0; FILL_SIZE];
+    assert_eq!(
+        handle.random_fill_mixed(&mut mixed),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_ne!(mixed[..], dest[..]);
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_into_variants() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let handle = AteccHandle::new(test_setup());
+    let message = b"abc";
+
+    let mut rand_out = [0; ATCA_RANDOM_BUFFER_SIZE];
+    assert_eq!(handle.random_into(&mut rand_out), AtcaStatus::AtcaSuccess);
+    assert_ne!(rand_out, [0; ATCA_RANDOM_BUFFER_SIZE]);
+
+    let mut digest = [0; ATCA_SHA2_256_DIGEST_SIZE];
+    let mut expected_digest = Vec::new();
+    assert_eq!(
+        handle.sha_into(message, &mut digest),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        handle.sha(message.to_vec(), &mut expected_digest),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(digest.to_vec(), expected_digest);
+
+    assert_eq!(
+        handle.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut public_key = Vec::new();
+    assert_eq!(
+        handle.get_public_key(PRIV_KEY_SLOT_IDX, &mut public_key),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut signature = [0; ATCA_SIG_SIZE];
+    assert_eq!(
+        handle.sign_hash_into(PRIV_KEY_SLOT_IDX, &digest, &mut signature),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        handle.verify_hash(VerifyMode::External(public_key), &digest, &signature),
+        Ok(true)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_sign_verify() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let device = test_setup();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut public_key = Vec::new();
+    assert_eq!(
+        device.get_public_key(PRIV_KEY_SLOT_IDX, &mut public_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(public_key.len(), ATCA_ATECC_PUB_KEY_SIZE);
+
+    let mut signature = Vec::new();
+    assert_eq!(
+        device.sign_hash(
+            SignMode::External(hash.to_vec()),
+            PRIV_KEY_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(signature.len(), ATCA_SIG_SIZE);
+    assert_eq!(
+        device.verify_hash(VerifyMode::External(public_key.clone()), &hash, &signature),
+        Ok(true)
+    );
+
+    // the same key imported into a public key slot
+    assert_eq!(
+        device.import_key(KeyType::P256EccKey, &public_key, PUB_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.verify_hash(VerifyMode::Internal(PUB_KEY_SLOT_IDX), &hash, &signature),
+        Ok(true)
+    );
+
+    signature[0] ^= 0x01;
+    assert_eq!(
+        device.verify_hash(VerifyMode::External(public_key), &hash, &signature),
+        Ok(false)
+    );
+    assert_eq!(
+        device.sign_hash(
+            SignMode::External(hash.to_vec()),
+            DATA_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaExecutionError
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_sign_verify_via_digest_buffer() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let simulator = AteccSimulator::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE).unwrap();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+    assert_eq!(
+        simulator.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut public_key = Vec::new();
+    assert_eq!(
+        simulator.get_public_key(PRIV_KEY_SLOT_IDX, &mut public_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.nonce(NonceTarget::TempKey, &[0x22; ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+
+    // The Message Digest Buffer leaves TempKey as it is
+    let mut signature = Vec::new();
+    assert_eq!(
+        simulator.sign_hash(
+            SignMode::ExternalVia(DigestBuffer::MsgDigBuf, hash.to_vec()),
+            PRIV_KEY_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.verify_hash(
+            VerifyMode::ExternalVia(DigestBuffer::MsgDigBuf, public_key.clone()),
+            &hash,
+            &signature
+        ),
+        Ok(true)
+    );
+    assert!(simulator.tempkey_state().valid);
+
+    assert_eq!(
+        simulator.sign_hash(
+            SignMode::ExternalVia(DigestBuffer::TempKey, hash.to_vec()),
+            PRIV_KEY_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaSuccess
+    );
+    let state = simulator.tempkey_state();
+    assert!(!state.valid);
+    assert_eq!(state.invalidated_by, Some("sign_hash"));
+    assert_eq!(
+        simulator.verify_hash(
+            VerifyMode::ExternalVia(DigestBuffer::TempKey, public_key),
+            &hash,
+            &signature
+        ),
+        Ok(true)
+    );
+    assert_eq!(
+        simulator.verify_hash(
+            VerifyMode::InternalVia(DigestBuffer::TempKey, PRIV_KEY_SLOT_IDX),
+            &hash,
+            &signature
+        ),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_sign_base() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let simulator = AteccSimulator::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE).unwrap();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+    let mut rand_out = Vec::new();
+    assert_eq!(
+        simulator.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut public_key = Vec::new();
+    assert_eq!(
+        simulator.get_public_key(PRIV_KEY_SLOT_IDX, &mut public_key),
+        AtcaStatus::AtcaSuccess
+    );
+
+    assert_eq!(
+        simulator.nonce(NonceTarget::MsgDigBuf, &hash),
+        AtcaStatus::AtcaSuccess
+    );
+    let signature = simulator
+        .sign_base(
+            ATCA_SIGN_MODE_EXTERNAL | ATCA_SIGN_MODE_SOURCE_MSGDIGBUF,
+            PRIV_KEY_SLOT_IDX,
+        )
+        .unwrap();
+    assert_eq!(
+        simulator.verify_hash(VerifyMode::External(public_key.clone()), &hash, &signature),
+        Ok(true)
+    );
+
+    assert_eq!(
+        simulator.nonce(NonceTarget::TempKey, &hash),
+        AtcaStatus::AtcaSuccess
+    );
+    let signature = simulator
+        .sign_base(ATCA_SIGN_MODE_EXTERNAL, PRIV_KEY_SLOT_IDX)
+        .unwrap();
+    assert_eq!(
+        simulator.verify_hash(VerifyMode::External(public_key), &hash, &signature),
+        Ok(true)
+    );
+    assert_eq!(
+        simulator.sign_base(ATCA_SIGN_MODE_INTERNAL, PRIV_KEY_SLOT_IDX),
+        Err(AtcaStatus::AtcaUnimplemented)
+    );
+
+    // Combinations the chip does not accept
+    for mode in [
+        0x02,
+        ATCA_SIGN_MODE_EXTERNAL | ATCA_SIGN_MODE_INVALIDATE,
+        ATCA_SIGN_MODE_INTERNAL | ATCA_SIGN_MODE_SOURCE_MSGDIGBUF,
+    ]
+    .iter()
+    {
+        assert_eq!(
+            simulator.sign_base(*mode, PRIV_KEY_SLOT_IDX),
+            Err(AtcaStatus::AtcaBadParam)
+        );
+    }
+    assert_eq!(
+        simulator.sign_base(ATCA_SIGN_MODE_EXTERNAL, ATCA_ATECC_SLOTS_COUNT),
+        Err(AtcaStatus::AtcaInvalidId)
+    );
+
+    assert_eq!(simulator.random(&mut rand_out), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.sign_base(ATCA_SIGN_MODE_EXTERNAL, PRIV_KEY_SLOT_IDX),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_signature_slot() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let device = test_setup();
+    let message = [0x5A; 100];
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let signature = device.sign_message(PRIV_KEY_SLOT_IDX, &message).unwrap();
+
+    let mut stored = [0; ATCA_SIG_SIZE];
+    stored.copy_from_slice(&signature);
+    assert_eq!(
+        store_signature(&*device, DATA_SLOT_IDX, &signature),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(load_signature(&*device, DATA_SLOT_IDX), Ok(stored));
+
+    assert_eq!(
+        store_signature(&*device, DATA_SLOT_IDX, &signature[..ATCA_SIG_SIZE - 1]),
+        AtcaStatus::AtcaInvalidSize
+    );
+    assert_eq!(
+        store_signature(&*device, DATA_SLOT_IDX, &[0x00; ATCA_SIG_SIZE]),
+        AtcaStatus::AtcaBadParam
+    );
+    assert_eq!(
+        store_signature(&*device, DATA_SLOT_IDX, &[0xFF; ATCA_SIG_SIZE]),
+        AtcaStatus::AtcaBadParam
+    );
+    assert_eq!(
+        store_signature(&*device, PRIV_KEY_SLOT_IDX, &signature),
+        AtcaStatus::AtcaInvalidSize
+    );
+    assert_eq!(
+        store_signature(&*device, PUB_KEY_SLOT_IDX, &signature),
+        AtcaStatus::AtcaBadParam
+    );
+
+    // An erased slot does not hold a signature
+    assert_eq!(
+        device.write_slot_data(DATA_SLOT_IDX, 0, &[0xFF; ATCA_SIG_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        load_signature(&*device, DATA_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_key_slots() {
+    let device = test_setup();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+    let data: Vec<u8> = (0..32).collect();
+
+    let ecc_key = device.ecc_key(0x00).unwrap();
+    assert_eq!(ecc_key.slot_id(), 0x00);
+    assert_eq!(ecc_key.gen_key(), AtcaStatus::AtcaSuccess);
+    let public_key = ecc_key.public_key().unwrap();
+    let signature = ecc_key.sign_hash(&hash).unwrap();
+    assert_eq!(
+        device.verify_hash(VerifyMode::External(public_key), &hash, &signature),
+        Ok(true)
+    );
+
+    let aes_key = device.aes_key(AES_KEY_SLOT_IDX).unwrap();
+    assert_eq!(aes_key.import_key(&[0x44; 16]), AtcaStatus::AtcaSuccess);
+
+    let data_slot = device.data_slot(DATA_SLOT_IDX).unwrap();
+    assert!(data_slot.is_writable());
+    assert_eq!(data_slot.write(0, &data), AtcaStatus::AtcaSuccess);
+    assert_eq!(data_slot.read(0, data.len()), Ok(data));
+
+    // the slot configuration does not match the handle
+    assert!(matches!(
+        device.ecc_key(PUB_KEY_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    ));
+    assert!(matches!(
+        device.aes_key(DATA_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    ));
+    assert!(matches!(
+        device.data_slot(AES_KEY_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    ));
+    assert!(matches!(
+        device.ecc_key(ATCA_ATECC_SLOTS_COUNT),
+        Err(AtcaStatus::AtcaInvalidId)
+    ));
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_secure_session() {
+    let device = test_setup();
+    let key = [0x5C; ATCA_KEY_SIZE];
+    let other_data = [0x01; ATCA_CHECK_MAC_OTHER_DATA_SIZE];
+
+    assert_eq!(
+        device.write_slot_data(DATA_SLOT_IDX, 0, &key),
+        AtcaStatus::AtcaSuccess
+    );
+    let session = SecureSession::new(&*device, DATA_SLOT_IDX, &key).unwrap();
+    assert_eq!(session.authenticate_device(), Ok(true));
+    assert_eq!(session.authenticate_host(&other_data), Ok(true));
+
+    // a MAC is bound to the nonce it was calculated over
+    let mac = device.mac(DATA_SLOT_IDX, None).unwrap();
+    assert_eq!(
+        device.check_mac(DATA_SLOT_IDX, None, &mac, &other_data),
+        Ok(false)
+    );
+
+    let wrong_key = [0xA3; ATCA_KEY_SIZE];
+    let session = SecureSession::new(&*device, DATA_SLOT_IDX, &wrong_key).unwrap();
+    assert_eq!(session.authenticate_device(), Ok(false));
+    assert_eq!(session.authenticate_host(&other_data), Ok(false));
+
+    assert!(matches!(
+        SecureSession::new(&*device, DATA_SLOT_IDX, &key[..16]),
+        Err(AtcaStatus::AtcaInvalidSize)
+    ));
+    assert_eq!(
+        device.gen_dig(GenDigZone::Data, 0x00, &[]),
+        AtcaStatus::AtcaExecutionError
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_slot_report() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let device = test_setup();
+
+    let report = slot_report(&*device).unwrap();
+    assert_eq!(report.len(), ATCA_ATECC_SLOTS_COUNT as usize);
+    let key_slot = &report[PRIV_KEY_SLOT_IDX as usize];
+    assert_eq!(key_slot.id, PRIV_KEY_SLOT_IDX);
+    assert_eq!(key_slot.key_type, KeyType::P256EccKey);
+    assert!(key_slot.is_secret);
+    for operation in [
+        SlotOperation::GenKey,
+        SlotOperation::GetPublicKey,
+        SlotOperation::Sign,
+        SlotOperation::Ecdh,
+    ]
+    .iter()
+    {
+        assert!(key_slot.operations.contains(operation));
+    }
+    assert_ne!(key_slot.is_provisioned, Some(true));
+
+    let aes_slot = &report[AES_KEY_SLOT_IDX as usize];
+    assert_eq!(aes_slot.key_type, KeyType::Aes);
+    assert_eq!(aes_slot.is_provisioned, None);
+    assert!(aes_slot.operations.contains(&SlotOperation::Encrypt));
+    assert!(!aes_slot.operations.contains(&SlotOperation::Sign));
+
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.write_slot_data(DATA_SLOT_IDX, 0, &[0x5C; ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    let report = slot_report(&*device).unwrap();
+    assert_eq!(
+        report[PRIV_KEY_SLOT_IDX as usize].is_provisioned,
+        Some(true)
+    );
+    let data_slot = &report[DATA_SLOT_IDX as usize];
+    assert_eq!(data_slot.key_type, KeyType::ShaOrText);
+    assert_eq!(data_slot.is_provisioned, Some(true));
+    assert!(data_slot.operations.contains(&SlotOperation::Read));
+    assert!(data_slot.operations.contains(&SlotOperation::Write));
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_slot_data() {
+    let device = test_setup();
+    let data: Vec<u8> = (0..64).collect();
+    let io_key = [0x33; ATCA_KEY_SIZE];
+    let aes_key = [0x44; ATCA_KEY_SIZE];
+
+    assert_eq!(
+        device.write_slot_data(DATA_SLOT_IDX, 4, &data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.read_slot_data(DATA_SLOT_IDX, 4, data.len()),
+        Ok(data)
+    );
+    assert_eq!(
+        device.write_slot_data(DATA_SLOT_IDX, 2, &[0x00; 4]),
+        AtcaStatus::AtcaInvalidSize
+    );
+    assert_eq!(
+        device.read_slot_data(IO_KEY_SLOT_IDX, 0, ATCA_KEY_SIZE),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+
+    // a slot written encrypted needs the proper write key
+    assert_eq!(
+        device.write_slot_data(IO_KEY_SLOT_IDX, 0, &io_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.write_slot_data(AES_KEY_ENC_WRITE_SLOT_IDX, 0, &aes_key),
+        AtcaStatus::AtcaInvalidId
+    );
+    assert_eq!(
+        device.add_access_key(IO_KEY_SLOT_IDX, &[0x00; ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.write_slot_data(AES_KEY_ENC_WRITE_SLOT_IDX, 0, &aes_key),
+        AtcaStatus::AtcaCheckMacVerifyFailed
+    );
+    assert_eq!(
+        device.add_access_key(IO_KEY_SLOT_IDX, &io_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.write_slot_data(AES_KEY_ENC_WRITE_SLOT_IDX, 0, &aes_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(device.flush_access_keys(), AtcaStatus::AtcaSuccess);
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_get_certificate() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let device = test_setup();
+    let ca_public_key = Some(ATCACERT_TEST_CA_PUBLIC_KEY);
+    let cert_def = |template_id: u8, public_key_slot: u8| CertDef {
+        template_id,
+        chain_id: 0,
+        sn_source: CertSnSource::DeviceSnHash,
+        issue_date_format: CertDateFormat::Rfc5280Utc,
+        expire_date_format: CertDateFormat::Rfc5280Utc,
+        public_key_slot,
+        comp_cert_dev_loc: CertDeviceLoc {
+            slot_id: DATA_SLOT_IDX,
+            offset: 0,
+            count: ATCA_COMP_CERT_SIZE,
+        },
+        cert_sn_dev_loc: None,
+        std_cert_elements: ATCACERT_TEST_ELEMENTS,
+        template: ATCACERT_TEST_CERT.to_vec(),
+    };
+
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    let stored_def = cert_def(2, PRIV_KEY_SLOT_IDX);
+    assert_eq!(
+        cert_write(&*device, &stored_def, ATCACERT_TEST_CERT),
+        Ok(())
+    );
+    let cert = cert_read(&*device, &stored_def, ca_public_key);
+    assert!(cert.is_ok());
+
+    // the definition is the one of the slot matching the compressed certificate
+    let cert_defs = [
+        cert_def(1, PRIV_KEY_SLOT_IDX),
+        cert_def(2, PUB_KEY_SLOT_IDX),
+        stored_def,
+    ];
+    assert_eq!(
+        get_certificate(&*device, PRIV_KEY_SLOT_IDX, &cert_defs, ca_public_key),
+        cert
+    );
+    assert_eq!(
+        get_certificate(&*device, PRIV_KEY_SLOT_IDX, &cert_defs[..2], ca_public_key),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_host_nonce() {
+    let device = test_setup();
+    let io_key = [0x33; ATCA_KEY_SIZE];
+    let aes_key = [0x44; ATCA_KEY_SIZE];
+
+    assert_eq!(
+        device.set_host_nonce(Some(&[0x5A; ATCA_NONCE_NUMIN_SIZE - 1])),
+        AtcaStatus::AtcaInvalidSize
+    );
+    assert_eq!(
+        device.add_access_key(IO_KEY_SLOT_IDX, &io_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.write_slot_data(IO_KEY_SLOT_IDX, 0, &io_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.set_host_nonce(Some(&[0x5A; ATCA_NONCE_NUMIN_SIZE])),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.write_slot_data(AES_KEY_ENC_WRITE_SLOT_IDX, 0, &aes_key),
+        AtcaStatus::AtcaSuccess
+    );
+    // the next write takes a random host nonce
+    assert_eq!(
+        device.write_slot_data(AES_KEY_ENC_WRITE_SLOT_IDX, 0, &aes_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(device.set_host_nonce(None), AtcaStatus::AtcaSuccess);
+    assert_eq!(device.flush_access_keys(), AtcaStatus::AtcaSuccess);
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_aead() {
+    let device = test_setup();
+
+    // Test Vectors from https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/AES_GCM.pdf
+    let gcm_key = [
+        0xFE, 0xFF, 0xE9, 0x92, 0x86, 0x65, 0x73, 0x1C, 0x6D, 0x6A, 0x8F, 0x94, 0x67, 0x30, 0x83,
+        0x08,
+    ];
+    let gcm_iv = [
+        0xCA, 0xFE, 0xBA, 0xBE, 0xFA, 0xCE, 0xDB, 0xAD, 0xDE, 0xCA, 0xF8, 0x88,
+    ];
+    let gcm_plain_text = [
+        0xD9, 0x31, 0x32, 0x25, 0xF8, 0x84, 0x06, 0xE5, 0xA5, 0x59, 0x09, 0xC5, 0xAF, 0xF5, 0x26,
+        0x9A, 0x86, 0xA7, 0xA9, 0x53, 0x15, 0x34, 0xF7, 0xDA, 0x2E, 0x4C, 0x30, 0x3D, 0x8A, 0x31,
+        0x8A, 0x72, 0x1C, 0x3C, 0x0C, 0x95, 0x95, 0x68, 0x09, 0x53, 0x2F, 0xCF, 0x0E, 0x24, 0x49,
+        0xA6, 0xB5, 0x25, 0xB1, 0x6A, 0xED, 0xF5, 0xAA, 0x0D, 0xE6, 0x57, 0xBA, 0x63, 0x7B, 0x39,
+        0x1A, 0xAF, 0xD2, 0x55,
+    ];
+    let gcm_cipher_text = [
+        0x42, 0x83, 0x1E, 0xC2, 0x21, 0x77, 0x74, 0x24, 0x4B, 0x72, 0x21, 0xB7, 0x84, 0xD0, 0xD4,
+        0x9C, 0xE3, 0xAA, 0x21, 0x2F, 0x2C, 0x02, 0xA4, 0xE0, 0x35, 0xC1, 0x7E, 0x23, 0x29, 0xAC,
+        0xA1, 0x2E, 0x21, 0xD5, 0x14, 0xB2, 0x54, 0x66, 0x93, 0x1C, 0x7D, 0x8F, 0x6A, 0x5A, 0xAC,
+        0x84, 0xAA, 0x05, 0x1B, 0xA3, 0x0B, 0x39, 0x6A, 0x0A, 0xAC, 0x97, 0x3D, 0x58, 0xE0, 0x91,
+        0x47, 0x3F, 0x59, 0x85,
+    ];
+    let gcm_tag = [
+        0x4D, 0x5C, 0x2A, 0xF3, 0x27, 0xCD, 0x64, 0xA6, 0x2C, 0xF3, 0x5A, 0xBD, 0x2B, 0xA6, 0xFA,
+        0xB4,
+    ];
+
+    // Test Vectors from https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/AES_CCM.pdf
+    let ccm_key = [
+        0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E,
+        0x4F,
+    ];
+    let ccm_iv = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B,
+    ];
+    let ccm_aad: Vec<u8> = (0x00..0x14).collect();
+    let ccm_plain_text: Vec<u8> = (0x20..0x38).collect();
+    let ccm_cipher_text = [
+        0xE3, 0xB2, 0x01, 0xA9, 0xF5, 0xB7, 0x1A, 0x7A, 0x9B, 0x1C, 0xEA, 0xEC, 0xCD, 0x97, 0xE7,
+        0x0B, 0x61, 0x76, 0xAA, 0xD9, 0xA4, 0x42, 0x8A, 0xA5,
+    ];
+    let ccm_tag = [0x48, 0x43, 0x92, 0xFB, 0xC1, 0xB0, 0x99, 0x51];
+
+    let mut data = gcm_plain_text.to_vec();
+    let param = AeadParam {
+        key: Some(gcm_key),
+        nonce: gcm_iv.to_vec(),
+        ..Default::default()
+    };
+    assert_eq!(
+        device.aead_encrypt(
+            AeadAlgorithm::Gcm(param.clone()),
+            ATCA_ATECC_SLOTS_COUNT,
+            &mut data
+        ),
+        Ok(gcm_tag.to_vec())
+    );
+    assert_eq!(data, gcm_cipher_text.to_vec());
+    let param = AeadParam {
+        tag: Some(gcm_tag.to_vec()),
+        ..param
+    };
+    assert_eq!(
+        device.aead_decrypt(AeadAlgorithm::Gcm(param), ATCA_ATECC_SLOTS_COUNT, &mut data),
+        Ok(true)
+    );
+    assert_eq!(data, gcm_plain_text.to_vec());
+
+    // the same key stored in a slot
+    let mut ccm_slot_key = ccm_key.to_vec();
+    ccm_slot_key.resize(ATCA_KEY_SIZE, 0x00);
+    assert_eq!(
+        device.write_slot_data(AES_KEY_SLOT_IDX, 0, &ccm_slot_key),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut data = ccm_plain_text.clone();
+    let param = AeadParam {
+        nonce: ccm_iv.to_vec(),
+        tag_length: Some(ccm_tag.len() as u8),
+        additional_data: Some(ccm_aad),
+        ..Default::default()
+    };
+    assert_eq!(
+        device.aead_encrypt(
+            AeadAlgorithm::Ccm(param.clone()),
+            AES_KEY_SLOT_IDX,
+            &mut data
+        ),
+        Ok(ccm_tag.to_vec())
+    );
+    assert_eq!(data, ccm_cipher_text.to_vec());
+    let mut bad_tag = ccm_tag.to_vec();
+    bad_tag[0] ^= 0x01;
+    let param = AeadParam {
+        tag: Some(bad_tag),
+        tag_length: None,
+        ..param
+    };
+    assert_eq!(
+        device.aead_decrypt(AeadAlgorithm::Ccm(param), AES_KEY_SLOT_IDX, &mut data),
+        Ok(false)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_backend_ecdh_ecies() {
+    const PRIV_KEY_1_SLOT_IDX: u8 = 0x00;
+    const PRIV_KEY_2_SLOT_IDX: u8 = 0x01;
+
+    let device = test_setup();
+    let mut public_key_1 = Vec::new();
+    let mut public_key_2 = Vec::new();
+
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_1_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.gen_key(KeyType::P256EccKey, PRIV_KEY_2_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.get_public_key(PRIV_KEY_1_SLOT_IDX, &mut public_key_1),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.get_public_key(PRIV_KEY_2_SLOT_IDX, &mut public_key_2),
+        AtcaStatus::AtcaSuccess
+    );
+
+    let secret_1 = device.ecdh(PRIV_KEY_1_SLOT_IDX, &public_key_2, None);
+    let secret_2 = device.ecdh(PRIV_KEY_2_SLOT_IDX, &public_key_1, None);
+    assert!(matches!(secret_1, Ok(EcdhOutput::PreMasterSecret(_))));
+    assert_eq!(secret_1, secret_2);
+
+    let message = b"message encrypted for the owner of the slot";
+    let encrypted = device.ecies_encrypt(&public_key_1, message).unwrap();
+    assert_eq!(
+        device.ecies_decrypt(PRIV_KEY_1_SLOT_IDX, &encrypted),
+        Ok(message.to_vec())
+    );
+    assert_eq!(
+        device.ecies_decrypt(PRIV_KEY_2_SLOT_IDX, &encrypted),
+        Err(AtcaStatus::AtcaCheckMacVerifyFailed)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_provisioning() {
+    const PRIV_KEY_SLOT_IDX: u8 = 0x00;
+
+    let simulator = AteccSimulator::new();
+    let data: Vec<u8> = (0..32).collect();
+
+    assert!(!simulator.is_configuration_locked());
+    assert!(!simulator.is_data_zone_locked());
+    assert_eq!(
+        simulator.provision_slot(DATA_SLOT_IDX, 0, &data),
+        AtcaStatus::AtcaNotLocked
+    );
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaNotLocked);
+
+    // the serial number cannot be changed by writing the configuration zone
+    let mut config_data = SOFTWARE_BACKEND_CONFIG_ZONE;
+    config_data[0] = 0xFF;
+    assert_eq!(
+        simulator.write_config_zone(&config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.get_serial_number()[0],
+        SOFTWARE_BACKEND_CONFIG_ZONE[0]
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.lock_config_zone(),
+        AtcaStatus::AtcaConfigZoneLocked
+    );
+    assert_eq!(
+        simulator.write_config_zone(&config_data),
+        AtcaStatus::AtcaConfigZoneLocked
+    );
+
+    assert_eq!(
+        simulator.provision_slot(DATA_SLOT_IDX, 0, &data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.provision_slot(PRIV_KEY_SLOT_IDX, 0, &data),
+        AtcaStatus::AtcaExecutionError
+    );
+    assert_eq!(
+        simulator.gen_key(KeyType::P256EccKey, PRIV_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.read_slot_data(DATA_SLOT_IDX, 0, data.len()),
+        Err(AtcaStatus::AtcaNotLocked)
+    );
+    assert_eq!(
+        simulator.lock_slot(DATA_SLOT_IDX),
+        AtcaStatus::AtcaNotLocked
+    );
+
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaDataZoneLocked);
+    assert_eq!(
+        simulator.provision_slot(DATA_SLOT_IDX, 0, &data),
+        AtcaStatus::AtcaDataZoneLocked
+    );
+    assert_eq!(
+        simulator.read_slot_data(DATA_SLOT_IDX, 0, data.len()),
+        Ok(data.clone())
+    );
+
+    assert_eq!(
+        simulator.write_slot_data(DATA_SLOT_IDX, 0, &data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_slot(DATA_SLOT_IDX), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.lock_slot(DATA_SLOT_IDX),
+        AtcaStatus::AtcaExecutionError
+    );
+    assert_eq!(
+        simulator.write_slot_data(DATA_SLOT_IDX, 0, &data),
+        AtcaStatus::AtcaExecutionError
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_temp_key() {
+    let simulator = AteccSimulator::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE).unwrap();
+    let input = [0x11; ATCA_AES_DATA_SIZE];
+
+    assert!(!simulator.is_temp_key_valid());
+    assert_eq!(
+        simulator.aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+
+    assert_eq!(
+        simulator.nonce(NonceTarget::TempKey, &[0x22; ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    assert!(simulator.is_temp_key_valid());
+    let output = simulator
+        .aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input)
+        .unwrap();
+    assert_eq!(
+        simulator.aes_decrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &output),
+        Ok(input)
+    );
+
+    // the Random command invalidates TempKey
+    let mut rand_out = Vec::new();
+    assert_eq!(simulator.random(&mut rand_out), AtcaStatus::AtcaSuccess);
+    assert!(!simulator.is_temp_key_valid());
+    assert_eq!(
+        simulator.aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+
+    // TempKey survives the idle mode, but not the sleep mode
+    assert_eq!(
+        simulator.nonce(NonceTarget::TempKey, &[0x22; ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.idle(), AtcaStatus::AtcaSuccess);
+    assert_eq!(simulator.wake(), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input),
+        Ok(output)
+    );
+    assert_eq!(simulator.sleep(), AtcaStatus::AtcaSuccess);
+    assert!(!simulator.is_temp_key_valid());
+    assert_eq!(
+        simulator.aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_limited_use() {
+    const LIMITED_KEY_SLOT_IDX: u8 = 0x00;
+    const KEY_SLOT_IDX: u8 = 0x01;
+    const SLOT_CONFIG_LIMITED_USE: u8 = 0b00100000;
+
+    let simulator = AteccSimulator::new();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+
+    let mut config_data = SOFTWARE_BACKEND_CONFIG_ZONE;
+    config_data[20 + (2 * LIMITED_KEY_SLOT_IDX as usize)] |= SLOT_CONFIG_LIMITED_USE;
+    assert_eq!(
+        simulator.write_config_zone(&config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.set_counter(0, ATCA_COUNTER_MAX_VALUE + 1),
+        AtcaStatus::AtcaBadParam
+    );
+    assert_eq!(
+        simulator.set_counter(0, ATCA_COUNTER_MAX_VALUE - 1),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.set_counter(0, 0),
+        AtcaStatus::AtcaConfigZoneLocked
+    );
+    for slot_id in [LIMITED_KEY_SLOT_IDX, KEY_SLOT_IDX].iter() {
+        assert_eq!(
+            simulator.gen_key(KeyType::P256EccKey, *slot_id),
+            AtcaStatus::AtcaSuccess
+        );
+    }
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+
+    let mut signature = Vec::new();
+    assert_eq!(
+        simulator.sign_hash(
+            SignMode::External(hash.to_vec()),
+            LIMITED_KEY_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.counter_read(0), Ok(ATCA_COUNTER_MAX_VALUE));
+    assert_eq!(
+        simulator.sign_hash(
+            SignMode::External(hash.to_vec()),
+            LIMITED_KEY_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaExecutionError
+    );
+    assert_eq!(
+        simulator.counter_increment(0),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+
+    // keys without the LimitedUse bit are not counted
+    assert_eq!(
+        simulator.sign_hash(
+            SignMode::External(hash.to_vec()),
+            KEY_SLOT_IDX,
+            &mut signature
+        ),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.counter_increment(1), Ok(1));
+    assert_eq!(simulator.counter_read(0), Ok(ATCA_COUNTER_MAX_VALUE));
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_key_use_check() {
+    const LIMITED_KEY_SLOT_IDX: u8 = 0x00;
+    const KEY_SLOT_IDX: u8 = 0x01;
+    const SLOT_CONFIG_LIMITED_USE: u8 = 0b00100000;
+    const COUNT_MATCH_SLOT_8: u8 = 0x81;
+
+    let simulator = AteccSimulator::new();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+
+    let mut config_data = SOFTWARE_BACKEND_CONFIG_ZONE;
+    config_data[18] = COUNT_MATCH_SLOT_8;
+    config_data[20 + (2 * LIMITED_KEY_SLOT_IDX as usize)] |= SLOT_CONFIG_LIMITED_USE;
+    assert_eq!(
+        simulator.write_config_zone(&config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.set_counter(0, ATCA_COUNTER_MAX_VALUE - 1),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    for slot_id in [LIMITED_KEY_SLOT_IDX, KEY_SLOT_IDX].iter() {
+        assert_eq!(
+            simulator.gen_key(KeyType::P256EccKey, *slot_id),
+            AtcaStatus::AtcaSuccess
+        );
+    }
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+
+    let handle = AteccHandle::new(Box::new(simulator));
+    let checked = handle.with_key_use_check();
+
+    let mut slots = Vec::new();
+    assert_eq!(checked.get_config(&mut slots), AtcaStatus::AtcaSuccess);
+    assert!(slots[LIMITED_KEY_SLOT_IDX as usize].config.limited_use);
+    assert!(!slots[KEY_SLOT_IDX as usize].config.limited_use);
+    assert!(slots[DATA_SLOT_IDX as usize].config.count_match);
+    assert!(!slots[LIMITED_KEY_SLOT_IDX as usize].config.count_match);
+
+    assert!(checked.sign_message(LIMITED_KEY_SLOT_IDX, &hash).is_ok());
+    assert_eq!(checked.counter_read(0), Ok(ATCA_COUNTER_MAX_VALUE));
+    assert_eq!(
+        checked.sign_message(LIMITED_KEY_SLOT_IDX, &hash),
+        Err(AtcaStatus::AtcaKeyUsesExhausted)
+    );
+    assert_eq!(
+        handle.sign_message(LIMITED_KEY_SLOT_IDX, &hash),
+        Err(AtcaStatus::AtcaExecutionError)
+    );
+
+    // keys without the LimitedUse bit are not checked
+    assert!(checked.sign_message(KEY_SLOT_IDX, &hash).is_ok());
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_sign_hash_with_counter() {
+    const KEY_SLOT_IDX: u8 = 0x00;
+    const COUNTER_ID: u8 = 1;
+    const COUNTER_START: u32 = 41;
+
+    let simulator = AteccSimulator::new();
+    let hash = [0x5A; ATCA_SHA2_256_DIGEST_SIZE];
+
+    assert_eq!(
+        simulator.write_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.set_counter(COUNTER_ID, COUNTER_START),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.gen_key(KeyType::P256EccKey, KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+
+    let mut public_key = Vec::new();
+    assert_eq!(
+        simulator.get_public_key(KEY_SLOT_IDX, &mut public_key),
+        AtcaStatus::AtcaSuccess
+    );
+
+    let first = sign_hash_with_counter(&simulator, KEY_SLOT_IDX, COUNTER_ID, &hash).unwrap();
+    let second = sign_hash_with_counter(&simulator, KEY_SLOT_IDX, COUNTER_ID, &hash).unwrap();
+    assert_eq!(first.counter_id, COUNTER_ID);
+    assert_eq!(first.counter_value, COUNTER_START + 1);
+    assert_eq!(second.counter_value, COUNTER_START + 2);
+    assert_eq!(simulator.counter_read(COUNTER_ID), Ok(COUNTER_START + 2));
+
+    for counter_signature in [&first, &second].iter() {
+        assert_eq!(
+            verify_hash_with_counter(&simulator, &public_key, &hash, counter_signature),
+            Ok(true)
+        );
+    }
+
+    // a signature cannot be presented with another counter value
+    let replayed = CounterSignature {
+        counter_value: second.counter_value,
+        ..first.clone()
+    };
+    assert_eq!(
+        verify_hash_with_counter(&simulator, &public_key, &hash, &replayed),
+        Ok(false)
+    );
+    assert_eq!(
+        sign_hash_with_counter(&simulator, KEY_SLOT_IDX, COUNTER_ID, &hash[1..]),
+        Err(AtcaStatus::AtcaInvalidSize)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_attest_key() {
+    const SUBJECT_SLOT_IDX: u8 = 0x00;
+    const ATTESTATION_SLOT_IDX: u8 = 0x01;
+
+    let simulator = AteccSimulator::new();
+
+    assert_eq!(
+        simulator.write_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    for slot_id in [SUBJECT_SLOT_IDX, ATTESTATION_SLOT_IDX].iter() {
+        assert_eq!(
+            simulator.gen_key(KeyType::P256EccKey, *slot_id),
+            AtcaStatus::AtcaSuccess
+        );
+    }
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+
+    let mut subject_public_key = Vec::new();
+    let mut attestation_public_key = Vec::new();
+    assert_eq!(
+        simulator.get_public_key(SUBJECT_SLOT_IDX, &mut subject_public_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.get_public_key(ATTESTATION_SLOT_IDX, &mut attestation_public_key),
+        AtcaStatus::AtcaSuccess
+    );
+
+    let attestation = attest_key(&simulator, SUBJECT_SLOT_IDX, ATTESTATION_SLOT_IDX).unwrap();
+    assert_eq!(attestation.subject_slot, SUBJECT_SLOT_IDX);
+    assert_eq!(attestation.serial_number, simulator.get_serial_number());
+    assert_eq!(attestation.config_digest.len(), ATCA_SHA2_256_DIGEST_SIZE);
+    assert_eq!(attestation.public_key, subject_public_key);
+    assert_eq!(
+        verify_key_attestation(&simulator, &attestation_public_key, &attestation),
+        Ok(true)
+    );
+    assert_eq!(
+        verify_key_attestation(&simulator, &subject_public_key, &attestation),
+        Ok(false)
+    );
+
+    let mut forged = attestation.clone();
+    forged.public_key = attestation_public_key.clone();
+    assert_eq!(
+        verify_key_attestation(&simulator, &attestation_public_key, &forged),
+        Ok(false)
+    );
+
+    assert_eq!(
+        attest_key(&simulator, SUBJECT_SLOT_IDX, SUBJECT_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+    assert_eq!(
+        attest_key(&simulator, DATA_SLOT_IDX, ATTESTATION_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+}
+
+#[cfg(feature = "config")]
+fn hex_string(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(feature = "config")]
+#[test]
+#[serial]
+fn rustcrypto_simulator_provision() {
+    use super::super::config::ConfigFormat;
+    use super::super::provision::{provision, Manifest, ProvisioningStep};
+    use std::path::Path;
+
+    const OTP: [u8; 8] = [0x4D, 0x41, 0x4E, 0x55, 0x46, 0x41, 0x43, 0x54];
+    const DATA: [u8; 4] = [0x48, 0x65, 0x6C, 0x6C];
+
+    let simulator = AteccSimulator::new();
+    let device = ProvisioningDevice::new(&simulator);
+    let manifest = Manifest::from_config_str(
+        &format!(
+            "config_zone = \"{}\"\nlock_config = true\notp = \"{}\"\nlock_data = true\n\
+             [[slot]]\nslot = 0\naction = \"generate\"\n\
+             [[slot]]\nslot = {}\naction = \"write\"\noffset = 0\ndata = \"{}\"\n",
+            hex_string(&SOFTWARE_BACKEND_CONFIG_ZONE),
+            hex_string(&OTP),
+            DATA_SLOT_IDX,
+            hex_string(&DATA)
+        ),
+        ConfigFormat::Toml,
+        Path::new(""),
+    )
+    .unwrap();
+
+    let mut progress_steps = Vec::new();
+    let report = provision(&device, &manifest, |progress| {
+        assert_eq!(progress.status, AtcaStatus::AtcaSuccess);
+        assert_eq!(progress.total, manifest.steps().len());
+        progress_steps.push(progress.step);
+    })
+    .unwrap();
+    assert_eq!(progress_steps, manifest.steps());
+    assert_eq!(report.completed, manifest.steps());
+    assert!(report.skipped.is_empty());
+
+    let mut public_key = Vec::new();
+    assert_eq!(
+        simulator.get_public_key(0x00, &mut public_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(report.public_keys.len(), 1);
+    assert_eq!(report.public_keys[0].0, 0x00);
+    assert_eq!(report.public_keys[0].1.to_vec(), public_key);
+    assert!(simulator.is_configuration_locked());
+    assert!(simulator.is_data_zone_locked());
+    assert_eq!(simulator.read_otp_zone(0, OTP.len()), Ok(OTP.to_vec()));
+    assert_eq!(
+        simulator.read_slot_data(DATA_SLOT_IDX, 0, DATA.len()),
+        Ok(DATA.to_vec())
+    );
+
+    // Running it again skips what the chip has already gone through
+    let manifest = Manifest {
+        slots: Vec::new(),
+        ..manifest
+    };
+    let report = provision(&device, &manifest, |_| ()).unwrap();
+    assert!(report.completed.is_empty());
+    assert_eq!(
+        report.skipped,
+        vec![
+            ProvisioningStep::WriteConfigZone,
+            ProvisioningStep::LockConfigZone,
+            ProvisioningStep::WriteOtpZone,
+            ProvisioningStep::LockDataZone
+        ]
+    );
+}
+
+#[cfg(feature = "config")]
+#[test]
+#[serial]
+fn rustcrypto_simulator_provision_rollback() {
+    use super::super::config::ConfigFormat;
+    use super::super::provision::{provision, Manifest, ProvisioningStep};
+    use std::path::Path;
+
+    let simulator = AteccSimulator::new();
+    let device = ProvisioningDevice::new(&simulator);
+    let mut original_config = Vec::new();
+    assert_eq!(
+        simulator.read_config_zone(&mut original_config),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut config_zone = original_config.clone();
+    config_zone[16] ^= 0x02;
+
+    // Keys can only be generated once the configuration zone is locked
+    let manifest = Manifest::from_config_str(
+        &format!(
+            "config_zone = \"{}\"\n[[slot]]\nslot = 0\naction = \"generate\"\n",
+            hex_string(&config_zone)
+        ),
+        ConfigFormat::Toml,
+        Path::new(""),
+    )
+    .unwrap();
+    let err = provision(&device, &manifest, |_| ()).unwrap_err();
+    assert_eq!(err.step, ProvisioningStep::GenKey(0x00));
+    assert_eq!(err.status, AtcaStatus::AtcaNotLocked);
+    assert_eq!(err.rolled_back, vec![ProvisioningStep::WriteConfigZone]);
+    assert!(err.not_rolled_back.is_empty());
+
+    let mut config_data = Vec::new();
+    assert_eq!(
+        simulator.read_config_zone(&mut config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(config_data, original_config);
+
+    assert!(Manifest::from_config_str(
+        "otp = \"0102\"\nlock_data = true\n[[slot]]\nslot = 0\naction = \"erase\"\n",
+        ConfigFormat::Toml,
+        Path::new(""),
+    )
+    .is_err());
+}
+
+#[test]
+#[serial]
+fn rustcrypto_config_report() {
+    let device = test_setup();
+    let report = config_report(device.as_ref()).unwrap();
+    assert_eq!(report.device_type, AtcaDeviceType::ATECC608A);
+    assert_eq!(report.serial_number, device.get_serial_number());
+    assert_eq!(report.revision, [0x00, 0x00, 0x60, 0x02]);
+    assert!(report.config_zone_locked);
+    assert!(report.data_zone_locked);
+    assert!(report.i2c_enabled);
+    assert_eq!(report.i2c_address, 0xC0);
+    assert!(report.chip_options.aes_enabled);
+    assert!(report.chip_options.io_key_enabled);
+    assert_eq!(report.chip_options.io_key_in_slot, IO_KEY_SLOT_IDX);
+    assert_eq!(
+        report.chip_options.ecdh_output_protection,
+        OutputProtectionState::ClearTextAllowed
+    );
+    assert_eq!(report.otp_mode, None);
+    assert_eq!(report.count_match_key, None);
+    assert_eq!(
+        report.counters,
+        [device.counter_read(0).ok(), device.counter_read(1).ok()]
+    );
+    assert_eq!(report.slots.len(), ATCA_ATECC_SLOTS_COUNT as usize);
+    assert_eq!(report.slots[0].config.key_type, KeyType::P256EccKey);
+    assert!(report.slots[0].config.ecc_key_attr.is_private);
+    assert_eq!(
+        report.slots[DATA_SLOT_IDX as usize].config.write_config,
+        WriteConfig::Always
+    );
+
+    let text = report.to_string();
+    assert!(text.contains("Device type:            ATECC608A\n"));
+    assert!(text.contains("IO protection key:      slot 9\n"));
+    assert_eq!(
+        text.lines()
+            .filter(|line| line.starts_with("Slot "))
+            .count(),
+        16
+    );
+    let json = report.to_json();
+    assert!(json.starts_with(r#"{"device_type":"ATECC608A","#));
+    assert!(json.contains(r#""otp_mode":null,"#));
+    assert!(json.contains(r#"{"id":0,"key_type":"P256EccKey","#));
+    assert!(json.ends_with("]}"));
+
+    let mut config_data = SOFTWARE_BACKEND_CONFIG_ZONE.to_vec();
+    config_data[18] = 0xAA;
+    let report = ConfigReport::from_config_zone(AtcaDeviceType::ATECC508A, &config_data).unwrap();
+    assert_eq!(report.otp_mode, Some(OtpMode::ReadOnly));
+    assert!(!report.chip_options.aes_enabled);
+    assert_eq!(report.counters, [None, None]);
+    assert_eq!(
+        ConfigReport::from_config_zone(AtcaDeviceType::ATECC608A, &config_data[1..]),
+        Err(AtcaStatus::AtcaInvalidSize)
+    );
+}
+
+#[cfg(feature = "config")]
+#[test]
+#[serial]
+fn rustcrypto_simulator_write_verify_lock() {
+    use super::super::provision::{write_verify_lock, ConfigByteChange, ProvisioningStep};
+
+    let simulator = AteccSimulator::new();
+    let device = ProvisioningDevice::new(&simulator);
+    let mut original_config = Vec::new();
+    assert_eq!(
+        simulator.read_config_zone(&mut original_config),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut config_zone = original_config.clone();
+    config_zone[0] ^= 0xFF;
+    config_zone[16] ^= 0x02;
+
+    let report = write_verify_lock(&device, &config_zone, true, true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(
+        report.changes,
+        vec![ConfigByteChange {
+            offset: 16,
+            current: original_config[16],
+            wanted: config_zone[16],
+        }]
+    );
+    assert_eq!(report.ignored.len(), 1);
+    assert_eq!(report.ignored[0].offset, 0);
+    assert_eq!(
+        report.steps,
+        vec![
+            ProvisioningStep::WriteConfigZone,
+            ProvisioningStep::LockConfigZone
+        ]
+    );
+    assert_eq!(report.irreversible, vec![ProvisioningStep::LockConfigZone]);
+    let mut config_data = Vec::new();
+    assert_eq!(
+        simulator.read_config_zone(&mut config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(config_data, original_config);
+    assert!(!simulator.is_configuration_locked());
+
+    let report = write_verify_lock(&device, &config_zone, true, false).unwrap();
+    assert!(!report.dry_run);
+    assert_eq!(report.steps.len(), 2);
+    assert!(simulator.is_configuration_locked());
+    assert_eq!(
+        simulator.read_config_zone(&mut config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(config_data[16], config_zone[16]);
+
+    let report = write_verify_lock(&device, &config_zone, true, false).unwrap();
+    assert!(report.steps.is_empty());
+    config_zone[16] = original_config[16];
+    assert_eq!(
+        write_verify_lock(&device, &config_zone, false, true)
+            .unwrap_err()
+            .status,
+        AtcaStatus::AtcaConfigZoneLocked
+    );
+    assert_eq!(
+        write_verify_lock(&device, &config_zone[1..], false, true)
+            .unwrap_err()
+            .status,
+        AtcaStatus::AtcaInvalidSize
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_write_otp_zone_verified() {
+    const OTP: [u8; 40] = [0x5A; 40];
+    const OTP_OFFSET: usize = 24;
+
+    let simulator = AteccSimulator::new();
+    let device = ProvisioningDevice::new(&simulator);
+    assert_eq!(
+        simulator.write_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+
+    let report = write_otp_zone_verified(&device, OTP_OFFSET, &OTP);
+    assert_eq!(report.status, AtcaStatus::AtcaSuccess);
+    assert_eq!(report.otp_mode, None);
+    assert_eq!(report.written, OTP.len());
+    assert_eq!(report.verified, None);
+
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+    let report = write_otp_zone_verified(&device, OTP_OFFSET, &OTP);
+    assert_eq!(report.status, AtcaStatus::AtcaSuccess);
+    assert_eq!(report.written, 0);
+    assert_eq!(report.verified, Some(true));
+    assert!(report.mismatches.is_empty());
+
+    let mut other = OTP;
+    other[5] = 0x00;
+    let report = write_otp_zone_verified(&device, OTP_OFFSET, &other);
+    assert_eq!(report.status, AtcaStatus::AtcaDataZoneLocked);
+    assert_eq!(report.written, 0);
+    assert_eq!(report.verified, Some(false));
+    assert_eq!(report.mismatches, vec![OTP_OFFSET + 5]);
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_write_otp_zone_verified_out_of_range() {
+    const OTP: [u8; 40] = [0x5A; 40];
+
+    let simulator = AteccSimulator::new();
+    let device = ProvisioningDevice::new(&simulator);
+    assert_eq!(
+        simulator.write_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+
+    for (offset, data) in [
+        (ATCA_OTP_SIZE - OTP.len() + ATCA_WORD_SIZE, &OTP[..]),
+        (ATCA_OTP_SIZE + ATCA_WORD_SIZE, &OTP[..ATCA_WORD_SIZE]),
+        (ATCA_WORD_SIZE + 1, &OTP[..ATCA_WORD_SIZE]),
+        (0, &OTP[..0]),
+    ]
+    .iter()
+    {
+        let report = write_otp_zone_verified(&device, *offset, data);
+        assert_eq!(report.status, AtcaStatus::AtcaInvalidSize);
+        assert_eq!(report.written, 0);
+        assert_eq!(report.verified, None);
+    }
+}
+
+#[test]
+#[serial]
+fn rustcrypto_provision_io_protection_key() {
+    let device = test_setup();
+    let aes_key = [0x2B; ATCA_KEY_SIZE];
+
+    assert_eq!(
+        provision_io_protection_key(device.as_ref(), DATA_SLOT_IDX, None).err(),
+        Some(AtcaStatus::AtcaBadParam)
+    );
+    assert_eq!(
+        provision_io_protection_key(device.as_ref(), IO_KEY_SLOT_IDX, Some(&aes_key[1..])).err(),
+        Some(AtcaStatus::AtcaInvalidSize)
+    );
+
+    let io_key = provision_io_protection_key(device.as_ref(), IO_KEY_SLOT_IDX, None).unwrap();
+    // The slot written encrypted with the IO protection key accepts it
+    assert_eq!(
+        device.write_slot_data(AES_KEY_ENC_WRITE_SLOT_IDX, 0, &aes_key),
+        AtcaStatus::AtcaSuccess
+    );
+    let mut access_key = Vec::new();
+    assert_eq!(
+        device.get_access_key(ATCA_ATECC_SLOTS_COUNT, &mut access_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(access_key, io_key.to_vec());
+    assert_eq!(device.flush_access_keys(), AtcaStatus::AtcaSuccess);
+
+    let io_key = [0x5A; ATCA_KEY_SIZE];
+    assert_eq!(
+        provision_io_protection_key(device.as_ref(), IO_KEY_SLOT_IDX, Some(&io_key)).as_deref(),
+        Ok(&io_key)
+    );
+    assert_eq!(device.flush_access_keys(), AtcaStatus::AtcaSuccess);
+}
+
+#[test]
+#[serial]
+fn rustcrypto_import_key_xor_shares() {
+    let key = [0x44; ATCA_AES_DATA_SIZE];
+    let share_1 = [0xA5; ATCA_AES_DATA_SIZE];
+    let share_2 = [0x3C; ATCA_AES_DATA_SIZE];
+    let mut share_3 = [0x00; ATCA_AES_DATA_SIZE];
+    for (idx, byte) in share_3.iter_mut().enumerate() {
+        *byte = key[idx] ^ share_1[idx] ^ share_2[idx];
+    }
+    let input = [0x6B; ATCA_AES_DATA_SIZE];
+
+    let device = test_setup();
+    assert_eq!(
+        import_key_xor_shares(
+            device.as_ref(),
+            KeyType::Aes,
+            AES_KEY_SLOT_IDX,
+            &[&share_1, &share_2, &share_3]
+        ),
+        AtcaStatus::AtcaSuccess
+    );
+    let reference = test_setup();
+    assert_eq!(
+        reference.import_key(KeyType::Aes, &key, AES_KEY_SLOT_IDX),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        device.aes_encrypt_block(AES_KEY_SLOT_IDX as u16, 0, &input),
+        reference.aes_encrypt_block(AES_KEY_SLOT_IDX as u16, 0, &input)
+    );
+
+    assert_eq!(
+        import_key_xor_shares(device.as_ref(), KeyType::Aes, AES_KEY_SLOT_IDX, &[&key]),
+        AtcaStatus::AtcaBadParam
+    );
+    assert_eq!(
+        import_key_xor_shares(
+            device.as_ref(),
+            KeyType::Aes,
+            AES_KEY_SLOT_IDX,
+            &[&share_1, &share_1]
+        ),
+        AtcaStatus::AtcaBadParam
+    );
+    assert_eq!(
+        import_key_xor_shares(
+            device.as_ref(),
+            KeyType::Aes,
+            AES_KEY_SLOT_IDX,
+            &[&share_1, &share_2[1..]]
+        ),
+        AtcaStatus::AtcaInvalidSize
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_fingerprint() {
+    let simulator = AteccSimulator::new();
+    assert_eq!(
+        simulator.write_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    for slot_id in [0x00, 0x01].iter() {
+        assert_eq!(
+            simulator.gen_key(KeyType::P256EccKey, *slot_id),
+            AtcaStatus::AtcaSuccess
+        );
+    }
+    // Public keys are only readable once the data zone is locked
+    assert_eq!(
+        fingerprint(&simulator, &[0x00, 0x01]).err(),
+        Some(AtcaStatus::AtcaNotLocked)
+    );
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+
+    let config_fingerprint = fingerprint(&simulator, &[]).unwrap();
+    let device_fingerprint = fingerprint(&simulator, &[0x00, 0x01]).unwrap();
+    assert_eq!(
+        fingerprint(&simulator, &[0x00, 0x01]),
+        Ok(device_fingerprint)
+    );
+    assert_ne!(config_fingerprint, device_fingerprint);
+    assert_ne!(
+        fingerprint(&simulator, &[0x01, 0x00]),
+        Ok(device_fingerprint)
+    );
+
+    // Counters do not change it, new keys do
+    assert!(simulator.counter_increment(0).is_ok());
+    assert_eq!(
+        fingerprint(&simulator, &[0x00, 0x01]),
+        Ok(device_fingerprint)
+    );
+    assert_eq!(
+        simulator.gen_key(KeyType::P256EccKey, 0x01),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(fingerprint(&simulator, &[]), Ok(config_fingerprint));
+    assert_ne!(
+        fingerprint(&simulator, &[0x00, 0x01]),
+        Ok(device_fingerprint)
+    );
+
+    assert!(fingerprint(&simulator, &[DATA_SLOT_IDX]).is_err());
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_limited_use_state() {
+    const LIMITED_KEY_SLOT_IDX: u8 = 0x00;
+    const KEY_SLOT_IDX: u8 = 0x01;
+    const SLOT_CONFIG_LIMITED_USE: u8 = 0b00100000;
+    const COUNT_MATCH_SLOT_8: u8 = 0x81;
+    const COUNT_MATCH_VALUE: u32 = 16;
+
+    let simulator = AteccSimulator::new();
+    let mut config_data = SOFTWARE_BACKEND_CONFIG_ZONE;
+    config_data[20 + (2 * LIMITED_KEY_SLOT_IDX as usize)] |= SLOT_CONFIG_LIMITED_USE;
+    assert_eq!(
+        simulator.write_config_zone(&config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+
+    let state = limited_use_state(&simulator, LIMITED_KEY_SLOT_IDX).unwrap();
+    assert_eq!(state.used, 0);
+    assert_eq!(state.limit, ATCA_COUNTER_MAX_VALUE);
+    assert!(!state.count_match);
+    assert!(simulator.counter_increment(0).is_ok());
+    assert_eq!(
+        limited_use_state(&simulator, LIMITED_KEY_SLOT_IDX).map(|state| state.remaining()),
+        Ok(ATCA_COUNTER_MAX_VALUE - 1)
+    );
+    assert_eq!(
+        limited_use_state(&simulator, KEY_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+    assert_eq!(
+        limited_use_state(&simulator, ATCA_ATECC_SLOTS_COUNT),
+        Err(AtcaStatus::AtcaInvalidId)
+    );
+
+    // With counter match, the limit is the value stored twice in the CountMatchKey slot
+    let simulator = AteccSimulator::new();
+    config_data[18] = COUNT_MATCH_SLOT_8;
+    assert_eq!(
+        simulator.write_config_zone(&config_data),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(simulator.lock_config_zone(), AtcaStatus::AtcaSuccess);
+    assert_eq!(simulator.lock_data_zone(), AtcaStatus::AtcaSuccess);
+    let mut match_value = COUNT_MATCH_VALUE.to_le_bytes().to_vec();
+    match_value.extend_from_slice(&COUNT_MATCH_VALUE.to_le_bytes());
+    match_value.resize(ATCA_KEY_SIZE, 0);
+    assert_eq!(
+        simulator.write_slot_data(DATA_SLOT_IDX, 0, &match_value),
+        AtcaStatus::AtcaSuccess
+    );
+    assert!(simulator.counter_increment(0).is_ok());
+
+    let state = limited_use_state(&simulator, LIMITED_KEY_SLOT_IDX).unwrap();
+    assert_eq!(state.slot, LIMITED_KEY_SLOT_IDX);
+    assert_eq!(state.limit, COUNT_MATCH_VALUE);
+    assert!(state.count_match);
+    assert_eq!(state.remaining(), COUNT_MATCH_VALUE - 1);
+
+    // A match value not stored twice is rejected
+    match_value[4] ^= 0x01;
+    assert_eq!(
+        simulator.write_slot_data(DATA_SLOT_IDX, 0, &match_value),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        limited_use_state(&simulator, LIMITED_KEY_SLOT_IDX),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_alt_key_buf() {
+    let device = test_setup();
+    // The two 16-byte halves differ, so key blocks 0 and 1 give different results
+    let key: Vec<u8> = (0..ATCA_KEY_SIZE as u8).collect();
+    let input = [0x11; ATCA_AES_DATA_SIZE];
+
+    assert_eq!(
+        load_alt_key_buf(device.as_ref(), &key[..ATCA_AES_DATA_SIZE]),
+        AtcaStatus::AtcaInvalidSize
+    );
+    assert_eq!(
+        load_alt_key_buf(device.as_ref(), &key),
+        AtcaStatus::AtcaSuccess
+    );
+
+    // The same key in TempKey gives the same results
+    let mut temp_key = key.to_vec();
+    temp_key[ATCA_AES_DATA_SIZE..].copy_from_slice(&[0x5A; ATCA_AES_DATA_SIZE]);
+    assert_eq!(
+        device.nonce(NonceTarget::TempKey, &temp_key),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        alt_key_buf_aes_encrypt_block(device.as_ref(), 0, &input),
+        device.aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input)
+    );
+    assert_ne!(
+        alt_key_buf_aes_encrypt_block(device.as_ref(), 1, &input),
+        device.aes_encrypt_block(ATCA_ATECC_TEMPKEY_KEYID, 0, &input)
+    );
+    let prf = KdfAlgorithm::Prf {
+        key_length: ATCA_AES_DATA_SIZE,
+        target_length: ATCA_KEY_SIZE,
+    };
+    assert_eq!(
+        alt_key_buf_kdf(device.as_ref(), prf, KdfTarget::Output, &input),
+        device.kdf(prf, KdfSource::TempKey, KdfTarget::Output, &input)
+    );
+
+    // The buffer holds 32 bytes
+    assert_eq!(
+        alt_key_buf_aes_encrypt_block(device.as_ref(), 2, &input),
+        Err(AtcaStatus::AtcaBadParam)
+    );
+    assert_eq!(
+        alt_key_buf_aes_encrypt_block(device.as_ref(), 0, &input[1..]),
+        Err(AtcaStatus::AtcaInvalidSize)
+    );
+    let prf = KdfAlgorithm::Prf {
+        key_length: 2 * ATCA_KEY_SIZE,
+        target_length: ATCA_KEY_SIZE,
+    };
+    assert_eq!(
+        alt_key_buf_kdf(device.as_ref(), prf, KdfTarget::Output, &input),
+        Err(AtcaStatus::AtcaInvalidSize)
+    );
+}
+
+#[test]
+#[serial]
+fn rustcrypto_simulator_tempkey_state() {
+    let simulator = AteccSimulator::from_config_zone(&SOFTWARE_BACKEND_CONFIG_ZONE).unwrap();
+    let mut rand_out = Vec::new();
+    assert_eq!(simulator.tempkey_state(), TempKeyState::default());
+
+    assert_eq!(
+        simulator.nonce(NonceTarget::TempKey, &[0x22; 2 * ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    let loaded = TempKeyState {
+        valid: true,
+        source: Some(TempKeySource::Nonce),
+        size: 2 * ATCA_KEY_SIZE,
+        invalidated_by: None,
+    };
+    assert_eq!(simulator.tempkey_state(), loaded);
+
+    // The first command to lose TempKey is reported
+    assert_eq!(simulator.random(&mut rand_out), AtcaStatus::AtcaSuccess);
+    assert_eq!(simulator.sleep(), AtcaStatus::AtcaSuccess);
+    assert_eq!(
+        simulator.tempkey_state(),
+        TempKeyState {
+            valid: false,
+            invalidated_by: Some("random"),
+            ..loaded
+        }
+    );
+
+    assert_eq!(
+        simulator.nonce_rand(&[0x33; 20], &mut rand_out),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(
+        simulator.tempkey_state().source,
+        Some(TempKeySource::RandomNonce)
+    );
+    let prf = KdfAlgorithm::Prf {
+        key_length: ATCA_KEY_SIZE,
+        target_length: 2 * ATCA_KEY_SIZE,
+    };
+    assert!(simulator
+        .kdf(prf, KdfSource::TempKey, KdfTarget::TempKey, &[0x44; 16])
+        .is_ok());
+    let state = simulator.tempkey_state();
+    assert!(state.valid);
+    assert_eq!(state.source, Some(TempKeySource::Kdf));
+    assert_eq!(state.size, 2 * ATCA_KEY_SIZE);
+
+    // The naive backend does not keep track of TempKey
+    let device = test_setup();
+    assert_eq!(
+        device.nonce(NonceTarget::TempKey, &[0x22; ATCA_KEY_SIZE]),
+        AtcaStatus::AtcaSuccess
+    );
+    assert_eq!(device.tempkey_state(), TempKeyState::default());
+}